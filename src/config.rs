@@ -19,9 +19,10 @@ use figment::{
     providers::{Env, Format, Serialized, Toml},
 };
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Settings {
@@ -51,7 +52,7 @@ pub struct Settings {
 
     /// Language-specific settings
     #[serde(default)]
-    pub languages: HashMap<String, LanguageConfig>,
+    pub languages: LanguagesConfig,
 
     /// MCP server settings
     #[serde(default)]
@@ -72,6 +73,38 @@ pub struct Settings {
     /// AI guidance settings for multi-hop queries
     #[serde(default)]
     pub guidance: GuidanceConfig,
+
+    /// Display settings for rendered reports
+    #[serde(default)]
+    pub display: DisplayConfig,
+
+    /// Symbols left out of analysis reports
+    #[serde(default)]
+    pub analysis: AnalysisConfig,
+
+    /// Named index profiles, each stored under `<index_path>/profiles/<name>`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub index_profiles: HashMap<String, IndexProfileConfig>,
+
+    /// Profile selected with `select_index_profile` (not serialized)
+    #[serde(skip)]
+    active_index_profile: Option<ActiveIndexProfile>,
+}
+
+/// A named index with its own set of indexed directories
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct IndexProfileConfig {
+    /// Directories indexed into this profile
+    #[serde(default)]
+    pub indexed_paths: Vec<PathBuf>,
+}
+
+/// The selected profile and the default-profile values it replaced
+#[derive(Debug, Clone)]
+struct ActiveIndexProfile {
+    name: String,
+    index_path: PathBuf,
+    indexed_paths: Vec<PathBuf>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -90,6 +123,42 @@ pub struct IndexingConfig {
     #[serde(default = "default_max_retry_attempts")]
     pub max_retry_attempts: u32,
 
+    /// Files larger than this many bytes are skipped during directory indexing
+    /// Guards against minified bundles and generated files (0 disables the limit)
+    #[serde(default = "default_max_file_size_bytes")]
+    pub max_file_size_bytes: u64,
+
+    /// Files whose parse runs longer than this many milliseconds are skipped
+    /// Keeps a pathological file from stalling indexing (0 disables the limit)
+    #[serde(default = "default_parse_timeout_ms")]
+    pub parse_timeout_ms: u64,
+
+    /// Record every identifier occurrence per file for grep-like lookups
+    /// Stored in occurrences.bin; noticeably larger index, so off by default
+    #[serde(default)]
+    pub index_occurrences: bool,
+
+    /// Index generic type parameters (`<T: Bound>`) as `Parameter` symbols
+    /// parented to their declaration, with `Uses` edges to their bound traits.
+    /// Rust only; adds a symbol per parameter, so off by default
+    #[serde(default)]
+    pub index_type_parameters: bool,
+
+    /// Index generated code (`*.pb.go`, `*_generated.rs`, `@generated` headers)
+    /// Skipped by default to keep search results focused on hand-written code
+    #[serde(default)]
+    pub include_generated: bool,
+
+    /// Warn before `retrieve` commands when indexed files changed on disk
+    /// since they were indexed (also silenced by `--quiet`)
+    #[serde(default = "default_true")]
+    pub stale_check: bool,
+
+    /// Resolve calls, implementations and other relationships between symbols
+    /// Disable for a faster, symbol-only index (no call graph)
+    #[serde(default = "default_true")]
+    pub resolve_relationships: bool,
+
     /// Project root directory (defaults to workspace root)
     /// Used for gitignore resolution and module path calculation
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -103,6 +172,65 @@ pub struct IndexingConfig {
     /// This list is managed by the add-dir and remove-dir commands
     #[serde(default)]
     pub indexed_paths: Vec<PathBuf>,
+
+    /// Macros that define items, mapped to the kind of symbol they define
+    /// (e.g. `define_id = "struct"`). The first identifier argument of each
+    /// invocation is indexed as that symbol. Rust only
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub item_macros: BTreeMap<String, String>,
+}
+
+/// Per-language settings keyed by language name, plus `[languages.overrides]`
+///
+/// Derefs to the per-language map, so `settings.languages.get("rust")` keeps working.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct LanguagesConfig {
+    /// Glob pattern to language name, consulted before extension-based detection
+    /// (e.g. `"*.inc" = "php"`). Patterns use gitignore syntax. When a file matches
+    /// several patterns the longest pattern wins; equal lengths go alphabetically.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub overrides: BTreeMap<String, String>,
+
+    /// Settings for each language
+    #[serde(flatten)]
+    pub configs: HashMap<String, LanguageConfig>,
+}
+
+impl LanguagesConfig {
+    /// Overrides in the order they are tried: longest pattern first, then alphabetical
+    pub fn ordered_overrides(&self) -> Vec<(&str, &str)> {
+        let mut ordered: Vec<_> = self
+            .overrides
+            .iter()
+            .map(|(pattern, language)| (pattern.as_str(), language.as_str()))
+            .collect();
+        // BTreeMap iteration is alphabetical and the sort is stable
+        ordered.sort_by_key(|(pattern, _)| std::cmp::Reverse(pattern.len()));
+        ordered
+    }
+}
+
+impl From<HashMap<String, LanguageConfig>> for LanguagesConfig {
+    fn from(configs: HashMap<String, LanguageConfig>) -> Self {
+        Self {
+            overrides: BTreeMap::new(),
+            configs,
+        }
+    }
+}
+
+impl std::ops::Deref for LanguagesConfig {
+    type Target = HashMap<String, LanguageConfig>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.configs
+    }
+}
+
+impl std::ops::DerefMut for LanguagesConfig {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.configs
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -134,6 +262,21 @@ pub struct McpConfig {
     /// Enable debug logging
     #[serde(default = "default_false")]
     pub debug: bool,
+
+    /// Most symbols an impact analysis collects before stopping and
+    /// reporting its result as truncated
+    #[serde(default = "default_max_impact_nodes")]
+    pub max_impact_nodes: usize,
+
+    /// Server instructions sent to clients at `initialize`, replacing the
+    /// built-in workflow guidance
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+
+    /// Tool descriptions listed in `tools/list`, by tool name, replacing the
+    /// built-in text for those tools
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tool_guidance: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -149,6 +292,45 @@ pub struct SemanticSearchConfig {
     /// Similarity threshold for search results
     #[serde(default = "default_similarity_threshold")]
     pub threshold: f32,
+
+    /// Local copy of `model` to load instead of downloading it (air-gapped machines)
+    /// Either a directory with the ONNX model and tokenizer files, or the `.onnx` file
+    /// itself next to them. Relative paths are resolved from the workspace root
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_path: Option<PathBuf>,
+
+    /// Read stored vectors from a memory map instead of loading them into RAM
+    #[serde(default = "default_true")]
+    pub mmap: bool,
+
+    /// Approximate nearest-neighbor index for large stores
+    #[serde(default)]
+    pub ann: AnnConfig,
+}
+
+/// Approximate (IVF) search over semantic embeddings. Below `min_vectors`
+/// every query compares against all embeddings.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct AnnConfig {
+    /// Build and use the approximate index once the store is large enough
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Embedding count at which approximate search takes over
+    #[serde(default = "default_ann_min_vectors")]
+    pub min_vectors: usize,
+
+    /// Number of clusters (0 = square root of the embedding count)
+    #[serde(default)]
+    pub clusters: usize,
+
+    /// Clusters scanned per query: higher is slower with better recall
+    #[serde(default = "default_ann_probes")]
+    pub probes: usize,
+
+    /// Rebuild once this fraction of embeddings changed since the last build
+    #[serde(default = "default_ann_rebuild_ratio")]
+    pub rebuild_ratio: f32,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -175,6 +357,10 @@ pub struct ServerConfig {
     /// Watch interval for stdio mode (seconds)
     #[serde(default = "default_watch_interval")]
     pub watch_interval: u64,
+
+    /// Log output format: "text" or "json"
+    #[serde(default = "default_log_format")]
+    pub log_format: String,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -192,6 +378,33 @@ pub struct GuidanceConfig {
     pub variables: HashMap<String, String>,
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct DisplayConfig {
+    /// Base URL for file links in Markdown reports
+    /// (e.g. "https://github.com/org/repo/blob/main"). Relative paths are used when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo_url: Option<String>,
+
+    /// How file paths appear in retrieve and MCP output: "relative" to the
+    /// workspace root (default) or "absolute"
+    #[serde(default)]
+    pub path_style: crate::paths::PathStyle,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct AnalysisConfig {
+    /// Symbol names never reported as unused; `*` and `?` wildcards allowed
+    /// (e.g. "test_*", "__*__")
+    #[serde(default)]
+    pub ignore_names: Vec<String>,
+
+    /// Attribute or decorator lines whose symbols are never reported as unused,
+    /// matched against the whole line with `*` and `?` wildcards
+    /// (e.g. "#[used]", "#[export_name*", "@pytest.fixture*")
+    #[serde(default)]
+    pub ignore_attributes: Vec<String>,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct GuidanceTemplate {
     /// Template for no results
@@ -239,6 +452,12 @@ fn default_tantivy_heap_mb() -> usize {
 fn default_max_retry_attempts() -> u32 {
     3 // Exponential backoff: 100ms, 200ms, 400ms
 }
+fn default_max_file_size_bytes() -> u64 {
+    2 * 1024 * 1024 // 2 MiB
+}
+fn default_parse_timeout_ms() -> u64 {
+    10_000 // 10 seconds; typical files parse in a few milliseconds
+}
 fn default_true() -> bool {
     true
 }
@@ -248,12 +467,24 @@ fn default_false() -> bool {
 fn default_max_context_size() -> usize {
     100_000
 }
+fn default_max_impact_nodes() -> usize {
+    5_000
+}
 fn default_embedding_model() -> String {
     "AllMiniLML6V2".to_string()
 }
 fn default_similarity_threshold() -> f32 {
     0.6
 }
+fn default_ann_min_vectors() -> usize {
+    50_000
+}
+fn default_ann_probes() -> usize {
+    8
+}
+fn default_ann_rebuild_ratio() -> f32 {
+    0.2
+}
 fn default_debounce_ms() -> u64 {
     500
 }
@@ -266,6 +497,9 @@ fn default_bind_address() -> String {
 fn default_watch_interval() -> u64 {
     5
 }
+fn default_log_format() -> String {
+    "text".to_string()
+}
 
 impl Default for Settings {
     fn default() -> Self {
@@ -276,12 +510,16 @@ impl Default for Settings {
             debug: false,
             indexing: IndexingConfig::default(),
             indexed_paths_cache: Vec::new(),
-            languages: generate_language_defaults(), // Now uses registry
+            languages: generate_language_defaults().into(), // Now uses registry
             mcp: McpConfig::default(),
             semantic_search: SemanticSearchConfig::default(),
             file_watch: FileWatchConfig::default(),
             server: ServerConfig::default(),
             guidance: GuidanceConfig::default(),
+            display: DisplayConfig::default(),
+            analysis: AnalysisConfig::default(),
+            index_profiles: HashMap::new(),
+            active_index_profile: None,
         }
     }
 }
@@ -292,6 +530,13 @@ impl Default for IndexingConfig {
             parallel_threads: default_parallel_threads(),
             tantivy_heap_mb: default_tantivy_heap_mb(),
             max_retry_attempts: default_max_retry_attempts(),
+            max_file_size_bytes: default_max_file_size_bytes(),
+            parse_timeout_ms: default_parse_timeout_ms(),
+            index_occurrences: false,
+            index_type_parameters: false,
+            include_generated: false,
+            stale_check: true,
+            resolve_relationships: true,
             project_root: None,
             ignore_patterns: vec![
                 "target/**".to_string(),
@@ -300,6 +545,31 @@ impl Default for IndexingConfig {
                 "*.generated.*".to_string(),
             ],
             indexed_paths: Vec::new(),
+            item_macros: BTreeMap::new(),
+        }
+    }
+}
+
+impl IndexingConfig {
+    /// `item_macros` with parsed kinds, keyed by macro name without a trailing `!`
+    ///
+    /// Entries with an unknown kind are left out; loading the settings warns about them.
+    pub fn item_macro_kinds(&self) -> BTreeMap<String, crate::SymbolKind> {
+        self.item_macros
+            .iter()
+            .filter_map(|(name, kind)| {
+                let kind = crate::SymbolKind::parse_filter(kind).ok()?;
+                Some((name.trim_end_matches('!').to_string(), kind))
+            })
+            .collect()
+    }
+
+    /// Report `item_macros` entries with an unknown kind; their macro is skipped
+    fn warn_invalid_item_macros(&self) {
+        for (name, kind) in &self.item_macros {
+            if let Err(e) = crate::SymbolKind::parse_filter(kind) {
+                eprintln!("Warning: {e} (macro '{name}' in [indexing.item_macros])");
+            }
         }
     }
 }
@@ -309,6 +579,9 @@ impl Default for McpConfig {
         Self {
             max_context_size: default_max_context_size(),
             debug: false,
+            max_impact_nodes: default_max_impact_nodes(),
+            instructions: None,
+            tool_guidance: BTreeMap::new(),
         }
     }
 }
@@ -319,6 +592,21 @@ impl Default for SemanticSearchConfig {
             enabled: true, // Enabled by default for better code intelligence
             model: default_embedding_model(),
             threshold: default_similarity_threshold(),
+            model_path: None,
+            mmap: true,
+            ann: AnnConfig::default(),
+        }
+    }
+}
+
+impl Default for AnnConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_vectors: default_ann_min_vectors(),
+            clusters: 0,
+            probes: default_ann_probes(),
+            rebuild_ratio: default_ann_rebuild_ratio(),
         }
     }
 }
@@ -338,6 +626,7 @@ impl Default for ServerConfig {
             mode: default_server_mode(),
             bind: default_bind_address(),
             watch_interval: default_watch_interval(),
+            log_format: default_log_format(),
         }
     }
 }
@@ -521,10 +810,7 @@ impl Settings {
 
     /// Load configuration from all sources
     pub fn load() -> Result<Self, Box<figment::Error>> {
-        // Try to find the workspace root by looking for config directory
-        let local_dir = crate::init::local_dir_name();
-        let config_path = Self::find_workspace_config()
-            .unwrap_or_else(|| PathBuf::from(local_dir).join("settings.toml"));
+        let config_path = Self::config_file_path();
 
         Figment::new()
             // Start with defaults
@@ -549,10 +835,20 @@ impl Settings {
                     settings.workspace_root = Self::workspace_root();
                 }
                 settings.sync_indexed_path_cache();
+                settings.indexing.warn_invalid_item_macros();
                 settings
             })
     }
 
+    /// Path of the config file `load()` reads
+    ///
+    /// The workspace config if one is found, otherwise `settings.toml` in the
+    /// local config directory of the current directory.
+    pub fn config_file_path() -> PathBuf {
+        Self::find_workspace_config()
+            .unwrap_or_else(|| PathBuf::from(crate::init::local_dir_name()).join("settings.toml"))
+    }
+
     /// Find the workspace root by looking for .codanna directory
     /// Searches from current directory up to root
     pub fn find_workspace_config() -> Option<PathBuf> {
@@ -625,11 +921,46 @@ impl Settings {
             .extract()
             .map(|mut settings: Settings| {
                 settings.sync_indexed_path_cache();
+                settings.indexing.warn_invalid_item_macros();
                 settings
             })
             .map_err(Box::new)
     }
 
+    /// Serialize the resolved settings as JSON with a `_source` map
+    ///
+    /// `_source` records, per top-level section, whether it is set in the config
+    /// file at `config_path` (`"file"`) or comes from built-in defaults (`"default"`).
+    pub fn to_json_with_sources(
+        &self,
+        config_path: &Path,
+    ) -> Result<serde_json::Value, serde_json::Error> {
+        let mut value = serde_json::to_value(self)?;
+
+        let file_keys: Vec<String> = std::fs::read_to_string(config_path)
+            .ok()
+            .and_then(|content| toml::from_str::<toml::Table>(&content).ok())
+            .map(|table| table.keys().cloned().collect())
+            .unwrap_or_default();
+
+        if let Some(object) = value.as_object_mut() {
+            let sources: serde_json::Map<String, serde_json::Value> = object
+                .keys()
+                .map(|key| {
+                    let source = if file_keys.contains(key) {
+                        "file"
+                    } else {
+                        "default"
+                    };
+                    (key.clone(), serde_json::Value::from(source))
+                })
+                .collect();
+            object.insert("_source".to_string(), serde_json::Value::Object(sources));
+        }
+
+        Ok(value)
+    }
+
     /// Save current configuration to file
     pub fn save(
         &self,
@@ -638,7 +969,21 @@ impl Settings {
         let parent = path.as_ref().parent().ok_or("Invalid path")?;
         std::fs::create_dir_all(parent)?;
 
-        let toml_string = toml::to_string_pretty(self)?;
+        // With a profile selected, its directories go back into its own section
+        let toml_string = match &self.active_index_profile {
+            Some(active) => {
+                let mut on_disk = self.clone();
+                on_disk
+                    .index_profiles
+                    .entry(active.name.clone())
+                    .or_default()
+                    .indexed_paths = self.indexing.indexed_paths.clone();
+                on_disk.index_path = active.index_path.clone();
+                on_disk.indexing.indexed_paths = active.indexed_paths.clone();
+                toml::to_string_pretty(&on_disk)?
+            }
+            None => toml::to_string_pretty(self)?,
+        };
         std::fs::write(path, toml_string)?;
 
         Ok(())
@@ -761,6 +1106,7 @@ impl Settings {
                 result.push_str(
                     "# Number of parallel threads for indexing (defaults to CPU count)\n",
                 );
+                result.push_str("# Bounds the index writer (at most 8, 15MB of heap per thread)\n");
             } else if line.starts_with("tantivy_heap_mb = ") {
                 result.push_str("\n# Tantivy heap size in megabytes\n");
                 result.push_str("# Reduce to 15-25MB if you have permission issues (antivirus, SELinux, containers)\n");
@@ -770,6 +1116,42 @@ impl Settings {
             } else if line.starts_with("max_retry_attempts = ") {
                 result.push_str("\n# Retry attempts for transient file system errors\n");
                 result.push_str("# Exponential backoff: 100ms, 200ms, 400ms delays\n");
+            } else if line.starts_with("max_file_size_bytes = ") {
+                result.push_str(
+                    "\n# Skip files larger than this many bytes (default: 2 MiB, 0 = no limit)\n",
+                );
+                result.push_str("# Keeps minified bundles and generated files out of the index\n");
+            } else if line.starts_with("parse_timeout_ms = ") {
+                result.push_str(
+                    "\n# Skip files that take longer than this to parse (default: 10000, 0 = no limit)\n",
+                );
+                result.push_str("# Protects the server from a single pathological file\n");
+            } else if line.starts_with("index_occurrences = ") {
+                result.push_str(
+                    "\n# Record every identifier occurrence for find_occurrences (larger index)\n",
+                );
+                result.push_str("# Re-index with --force after enabling\n");
+            } else if line.starts_with("index_type_parameters = ") {
+                result.push_str(
+                    "\n# Index generic type parameters and their trait bounds (Rust, more symbols)\n",
+                );
+                result.push_str("# Re-index with --force after enabling\n");
+            } else if line.starts_with("include_generated = ") {
+                result.push_str(
+                    "\n# Index generated code (*.pb.go, *_generated.rs, @generated headers)\n",
+                );
+                result.push_str("# Skipped by default; --include-generated enables it per run\n");
+            } else if line.starts_with("stale_check = ") {
+                result.push_str(
+                    "\n# Warn before retrieve commands when indexed files changed since indexing\n",
+                );
+            } else if line.starts_with("resolve_relationships = ") {
+                result.push_str(
+                    "\n# Resolve relationships (calls, implementations) between symbols\n",
+                );
+                result.push_str(
+                    "# false builds a faster symbol-only index; --no-relationships per run\n",
+                );
             } else if line.starts_with("ignore_patterns = ") {
                 result.push_str("\n# Additional patterns to ignore during indexing\n");
             } else if line.starts_with("indexed_paths = ") {
@@ -790,6 +1172,8 @@ impl Settings {
                 // Skip MCP debug comment if in languages section
             } else if line.starts_with("debug = ") && line.contains("false") {
                 result.push_str("\n# Enable debug logging for MCP server\n");
+            } else if line.starts_with("max_impact_nodes = ") {
+                result.push_str("\n# Most symbols an impact analysis collects before truncating\n");
             } else if line == "[semantic_search]" {
                 result.push_str("\n[semantic_search]\n");
                 result.push_str("# Semantic search for natural language code queries\n");
@@ -812,8 +1196,38 @@ impl Settings {
                 );
                 result.push_str("# - BGESmallZHV15: Chinese-specialized, 512 dimensions\n");
                 result.push_str("# - See documentation for full list of available models\n");
+                result.push_str(
+                    "# Offline: load the model from a local copy instead of downloading it\n",
+                );
+                result.push_str("# model_path = \"/opt/models/all-MiniLM-L6-v2\"\n");
             } else if line.starts_with("threshold = ") {
                 result.push_str("\n# Similarity threshold for search results (0.0 to 1.0)\n");
+            } else if line.starts_with("mmap = ") {
+                result.push_str(
+                    "\n# Read stored vectors from a memory map instead of loading them into RAM\n",
+                );
+                result.push_str("# Set to false where memory mapping is unreliable (e.g. some network filesystems)\n");
+            } else if line == "[semantic_search.ann]" {
+                result.push_str("\n[semantic_search.ann]\n");
+                result.push_str("# Approximate (IVF) search for large semantic stores\n");
+                result.push_str(
+                    "# Smaller stores always compare the query against every embedding\n",
+                );
+                prev_line_was_section = true;
+                continue;
+            } else if line.starts_with("min_vectors = ") {
+                result.push_str("\n# Embedding count at which approximate search takes over\n");
+            } else if line.starts_with("clusters = ") {
+                result
+                    .push_str("\n# Number of clusters (0 = square root of the embedding count)\n");
+            } else if line.starts_with("probes = ") {
+                result.push_str(
+                    "\n# Clusters scanned per query: raise for recall, lower for speed\n",
+                );
+            } else if line.starts_with("rebuild_ratio = ") {
+                result.push_str(
+                    "\n# Rebuild the index once this fraction of embeddings has changed\n",
+                );
             } else if line == "[file_watch]" {
                 result.push_str("\n[file_watch]\n");
                 result.push_str("# Enable automatic file watching for indexed files\n");
@@ -835,16 +1249,51 @@ impl Settings {
                 );
                 prev_line_was_section = true;
                 continue;
+            } else if line == "[display]" {
+                result.push_str("\n[display]\n");
+                result.push_str("# Base URL for file links in Markdown reports, e.g.\n");
+                result.push_str("# repo_url = \"https://github.com/org/repo/blob/main\"\n");
+                result.push_str("# Default: unset (links use relative paths)\n");
+                prev_line_was_section = true;
+                continue;
+            } else if line.starts_with("path_style = ") {
+                result.push_str(
+                    "\n# How file paths appear in retrieve and MCP output (override with\n",
+                );
+                result.push_str("# --absolute-paths / --relative-paths):\n");
+                result.push_str("# \"relative\" (default): relative to the workspace root\n");
+                result.push_str("# \"absolute\": resolved against the workspace root\n");
+            } else if line == "[analysis]" {
+                result.push_str("\n[analysis]\n");
+                result.push_str("# Symbols never reported as public functions without callers\n");
+                prev_line_was_section = true;
+                continue;
+            } else if line.starts_with("ignore_names = ") {
+                result.push_str("\n# Symbol names to skip; * and ? wildcards, e.g. [\"test_*\"]\n");
+            } else if line.starts_with("ignore_attributes = ") {
+                result.push_str(
+                    "\n# Attribute or decorator lines to skip, matched against the whole line\n",
+                );
+                result.push_str(
+                    "# with * and ? wildcards, e.g. [\"#[used]\", \"@pytest.fixture*\"]\n",
+                );
             } else if line.starts_with("mode = ") {
                 // mode field - comment already added above
             } else if line.starts_with("bind = ") {
                 result.push_str("\n# HTTP server bind address (only used when mode = \"http\" or --http flag)\n");
             } else if line.starts_with("watch_interval = ") {
                 result.push_str("\n# Watch interval for stdio mode in seconds (how often to check for file changes)\n");
+            } else if line.starts_with("log_format = ") {
+                result.push_str("\n# Log output format: \"text\" (default) or \"json\" (one JSON object per line)\n");
             } else if line.starts_with("[languages.") {
                 if !in_languages_section {
                     result.push_str("\n# Language-specific settings\n");
                     result.push_str("# Currently supported: Rust, Python, PHP, TypeScript, Go, C, C++, CSharp, Gdscript\n");
+                    result.push_str(
+                        "# Force a language for files matching a glob (longest pattern wins):\n",
+                    );
+                    result.push_str("# [languages.overrides]\n");
+                    result.push_str("# \"*.inc\" = \"php\"\n");
                     in_languages_section = true;
                 }
                 result.push('\n');
@@ -1020,8 +1469,74 @@ __pycache__/
     pub fn get_indexed_paths(&self) -> Vec<PathBuf> {
         self.indexing.indexed_paths.clone()
     }
+
+    /// Target a named index profile instead of the default index
+    ///
+    /// The profile's index lives in `<index_path>/profiles/<name>` and its
+    /// `indexed_paths` replace the top-level list, so indexing, sync and queries
+    /// only see that profile. `"default"` keeps the single-index layout. Saving
+    /// writes path changes back to the profile's own section.
+    pub fn select_index_profile(&mut self, name: &str) -> Result<(), String> {
+        if name == DEFAULT_INDEX_PROFILE {
+            return Ok(());
+        }
+        if self.active_index_profile.is_some() {
+            return Err("An index profile is already selected".to_string());
+        }
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(format!(
+                "Invalid index profile name '{name}': use letters, digits, '-' and '_'"
+            ));
+        }
+
+        let profile = self.index_profiles.get(name).ok_or_else(|| {
+            let mut known: Vec<_> = self.index_profiles.keys().map(String::as_str).collect();
+            known.sort_unstable();
+            let known = if known.is_empty() {
+                "none configured".to_string()
+            } else {
+                known.join(", ")
+            };
+            format!(
+                "Unknown index profile '{name}' (available: {known}). Define it under [index_profiles.{name}] in settings.toml"
+            )
+        })?;
+        let profile_paths = profile.indexed_paths.clone();
+
+        let index_path = self.index_path.join("profiles").join(name);
+        self.active_index_profile = Some(ActiveIndexProfile {
+            name: name.to_string(),
+            index_path: std::mem::replace(&mut self.index_path, index_path),
+            indexed_paths: std::mem::replace(&mut self.indexing.indexed_paths, profile_paths),
+        });
+        self.sync_indexed_path_cache();
+        Ok(())
+    }
+
+    /// Name of the selected index profile, if any
+    pub fn index_profile(&self) -> Option<&str> {
+        self.active_index_profile
+            .as_ref()
+            .map(|active| active.name.as_str())
+    }
+
+    /// Configured local embedding model, with relative paths resolved from the workspace root
+    pub fn semantic_model_path(&self) -> Option<PathBuf> {
+        let path = self.semantic_search.model_path.as_ref()?;
+        match &self.workspace_root {
+            Some(root) if path.is_relative() => Some(root.join(path)),
+            _ => Some(path.clone()),
+        }
+    }
 }
 
+/// Name of the profile that keeps the single-index layout
+pub const DEFAULT_INDEX_PROFILE: &str = "default";
+
 /// Global check for whether debug logging is enabled.
 /// Uses settings from .codanna/settings.toml and caches the result.
 pub fn is_global_debug_enabled() -> bool {
@@ -1029,6 +1544,19 @@ pub fn is_global_debug_enabled() -> bool {
     *DEBUG_FLAG.get_or_init(|| Settings::load().map(|s| s.debug).unwrap_or(false))
 }
 
+static QUIET_FLAG: AtomicBool = AtomicBool::new(false);
+
+/// Suppress informational stderr output for the rest of the process.
+/// Set from the global `--quiet` CLI flag; errors and warnings still print.
+pub fn set_global_quiet(quiet: bool) {
+    QUIET_FLAG.store(quiet, Ordering::Relaxed);
+}
+
+/// Global check for whether informational stderr output is suppressed.
+pub fn is_global_quiet() -> bool {
+    QUIET_FLAG.load(Ordering::Relaxed)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1078,6 +1606,113 @@ enabled = false
         assert!(!settings.languages["rust"].enabled);
     }
 
+    #[test]
+    fn test_language_overrides() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("settings.toml");
+
+        let toml_content = r#"
+[languages.rust]
+enabled = true
+
+[languages.overrides]
+"*.inc" = "php"
+"legacy/*.inc" = "c"
+"*.sql.txt" = "sql"
+"#;
+
+        fs::write(&config_path, toml_content).unwrap();
+
+        let settings = Settings::load_from(&config_path).unwrap();
+        assert!(settings.languages["rust"].enabled);
+        assert!(!settings.languages.contains_key("overrides"));
+        assert_eq!(
+            settings.languages.ordered_overrides(),
+            vec![
+                ("legacy/*.inc", "c"),
+                ("*.sql.txt", "sql"),
+                ("*.inc", "php")
+            ]
+        );
+
+        // Overrides survive a save/load round trip
+        settings.save(&config_path).unwrap();
+        let reloaded = Settings::load_from(&config_path).unwrap();
+        assert_eq!(reloaded.languages.overrides, settings.languages.overrides);
+        assert!(reloaded.languages.contains_key("rust"));
+    }
+
+    #[test]
+    fn test_ann_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("settings.toml");
+        fs::write(
+            &config_path,
+            "[semantic_search.ann]\nmin_vectors = 1000\nprobes = 16\n",
+        )
+        .unwrap();
+
+        let ann = Settings::load_from(&config_path)
+            .unwrap()
+            .semantic_search
+            .ann;
+        assert!(ann.enabled);
+        assert_eq!(ann.min_vectors, 1000);
+        assert_eq!(ann.probes, 16);
+        assert_eq!(ann.clusters, 0);
+        assert_eq!(ann.rebuild_ratio, 0.2);
+    }
+
+    #[test]
+    fn test_mcp_guidance_overrides() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("settings.toml");
+        fs::write(
+            &config_path,
+            "[mcp]\ninstructions = \"Read the code first\"\n\n[mcp.tool_guidance]\nget_calls = \"Calls made by a function\"\n",
+        )
+        .unwrap();
+
+        let mcp = Settings::load_from(&config_path).unwrap().mcp;
+        assert_eq!(mcp.instructions.as_deref(), Some("Read the code first"));
+        assert_eq!(
+            mcp.tool_guidance.get("get_calls").map(String::as_str),
+            Some("Calls made by a function")
+        );
+        assert!(McpConfig::default().tool_guidance.is_empty());
+    }
+
+    #[test]
+    fn test_semantic_model_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("settings.toml");
+
+        fs::write(
+            &config_path,
+            "[semantic_search]\nmodel_path = \"models/minilm\"\n",
+        )
+        .unwrap();
+        let mut settings = Settings::load_from(&config_path).unwrap();
+        assert_eq!(
+            settings.semantic_search.model_path,
+            Some(PathBuf::from("models/minilm"))
+        );
+
+        settings.workspace_root = Some(temp_dir.path().to_path_buf());
+        assert_eq!(
+            settings.semantic_model_path(),
+            Some(temp_dir.path().join("models/minilm"))
+        );
+
+        settings.semantic_search.model_path = Some(temp_dir.path().join("abs"));
+        assert_eq!(
+            settings.semantic_model_path(),
+            Some(temp_dir.path().join("abs"))
+        );
+
+        assert_eq!(Settings::default().semantic_model_path(), None);
+    }
+
     #[test]
     fn test_save_settings() {
         let temp_dir = TempDir::new().unwrap();
@@ -1094,6 +1729,62 @@ enabled = false
         assert!(loaded.mcp.debug);
     }
 
+    #[test]
+    fn test_index_profile_selection_and_save() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("settings.toml");
+        fs::write(
+            &config_path,
+            r#"
+[indexing]
+indexed_paths = ["/repo/src"]
+
+[index_profiles.tests]
+indexed_paths = ["/repo/tests"]
+"#,
+        )
+        .unwrap();
+
+        let mut settings = Settings::load_from(&config_path).unwrap();
+        let default_index_path = settings.index_path.clone();
+        settings
+            .select_index_profile(DEFAULT_INDEX_PROFILE)
+            .unwrap();
+        assert_eq!(settings.index_profile(), None);
+
+        assert!(settings.select_index_profile("missing").is_err());
+        assert!(settings.select_index_profile("../escape").is_err());
+
+        settings.select_index_profile("tests").unwrap();
+        assert_eq!(settings.index_profile(), Some("tests"));
+        assert_eq!(
+            settings.index_path,
+            default_index_path.join("profiles").join("tests")
+        );
+        assert_eq!(
+            settings.indexing.indexed_paths,
+            vec![PathBuf::from("/repo/tests")]
+        );
+
+        // Path changes are saved into the profile, leaving the default untouched
+        settings
+            .indexing
+            .indexed_paths
+            .push(PathBuf::from("/repo/benches"));
+        settings.save(&config_path).unwrap();
+
+        let reloaded = Settings::load_from(&config_path).unwrap();
+        assert_eq!(reloaded.index_path, default_index_path);
+        assert_eq!(
+            reloaded.indexing.indexed_paths,
+            vec![PathBuf::from("/repo/src")]
+        );
+        assert_eq!(
+            reloaded.index_profiles["tests"].indexed_paths,
+            vec![PathBuf::from("/repo/tests"), PathBuf::from("/repo/benches")]
+        );
+    }
+
     #[test]
     fn test_partial_config() {
         let temp_dir = TempDir::new().unwrap();
@@ -1455,4 +2146,19 @@ indexed_paths = ["{path1_str}", "{path2_str}"]
             .unwrap();
         assert_eq!(canonical_loaded, canonical_test);
     }
+
+    #[test]
+    fn test_to_json_with_sources() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("settings.toml");
+        fs::write(&config_path, "[file_watch]\nenabled = false\n").unwrap();
+
+        let settings = Settings::load_from(&config_path).unwrap();
+        let json = settings.to_json_with_sources(&config_path).unwrap();
+
+        assert_eq!(json["file_watch"]["enabled"], false);
+        assert_eq!(json["_source"]["file_watch"], "file");
+        assert_eq!(json["_source"]["indexing"], "default");
+        assert!(json["indexing"]["parallel_threads"].is_number());
+    }
 }
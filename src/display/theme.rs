@@ -2,11 +2,54 @@
 
 use console::Style;
 use owo_colors::OwoColorize;
+use std::fmt;
+use std::str::FromStr;
 use std::sync::LazyLock;
+use std::sync::atomic::{AtomicU8, Ordering};
 
 /// Global theme instance for consistent styling across the application.
 pub static THEME: LazyLock<Theme> = LazyLock::new(Theme::default);
 
+/// Color choice in effect, stored as a `ColorChoice` discriminant.
+static COLOR_CHOICE: AtomicU8 = AtomicU8::new(ColorChoice::Auto as u8);
+
+/// When to use colored output, as selected by the global `--color` flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorChoice {
+    /// Color when stdout is a terminal and `NO_COLOR` is unset
+    #[default]
+    Auto = 0,
+    /// Always color, even when piped
+    Always = 1,
+    /// Never color
+    Never = 2,
+}
+
+impl FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(Self::Auto),
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            other => Err(format!(
+                "Unknown color choice '{other}'. Expected 'auto', 'always' or 'never'"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ColorChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Auto => write!(f, "auto"),
+            Self::Always => write!(f, "always"),
+            Self::Never => write!(f, "never"),
+        }
+    }
+}
+
 /// Color theme for terminal output.
 #[derive(Debug, Clone)]
 pub struct Theme {
@@ -77,10 +120,44 @@ impl Theme {
         }
     }
 
+    /// Force or restore automatic color detection for the whole process.
+    ///
+    /// Also applies the choice to `console`, which styles progress bars and
+    /// tables independently of this theme.
+    pub fn set_color_choice(choice: ColorChoice) {
+        COLOR_CHOICE.store(choice as u8, Ordering::Relaxed);
+        match choice {
+            ColorChoice::Auto => {}
+            ColorChoice::Always | ColorChoice::Never => {
+                let enabled = choice == ColorChoice::Always;
+                console::set_colors_enabled(enabled);
+                console::set_colors_enabled_stderr(enabled);
+            }
+        }
+    }
+
+    /// The color choice currently in effect.
+    pub fn color_choice() -> ColorChoice {
+        match COLOR_CHOICE.load(Ordering::Relaxed) {
+            1 => ColorChoice::Always,
+            2 => ColorChoice::Never,
+            _ => ColorChoice::Auto,
+        }
+    }
+
     /// Check if color output should be disabled.
+    ///
+    /// An explicit `--color always|never` wins; otherwise colors are disabled
+    /// when `NO_COLOR` is set or stdout is not a terminal.
     pub fn should_disable_colors() -> bool {
         use is_terminal::IsTerminal;
-        std::env::var("NO_COLOR").is_ok() || !std::io::stdout().is_terminal()
+        match Self::color_choice() {
+            ColorChoice::Always => false,
+            ColorChoice::Never => true,
+            ColorChoice::Auto => {
+                std::env::var("NO_COLOR").is_ok() || !std::io::stdout().is_terminal()
+            }
+        }
     }
 
     /// Apply theme styling conditionally based on terminal support.
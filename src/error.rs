@@ -36,6 +36,12 @@ pub enum IndexError {
     )]
     UnsupportedFileType { path: PathBuf, extension: String },
 
+    #[error("Skipped '{path}': binary/non-utf8 content ({reason})")]
+    BinaryContent { path: PathBuf, reason: String },
+
+    #[error("Skipped '{path}': parse timeout after {timeout_ms} ms")]
+    ParseTimeout { path: PathBuf, timeout_ms: u64 },
+
     /// Storage errors
     #[error("Failed to persist index to '{path}': {source}")]
     PersistenceError {
@@ -86,6 +92,14 @@ pub enum IndexError {
     #[error("Index appears to be corrupted: {reason}")]
     IndexCorrupted { reason: String },
 
+    /// Index written by a version with a different on-disk format
+    #[error("Index format version {found} is not supported (expected {expected})")]
+    IncompatibleIndex { found: u32, expected: u32 },
+
+    /// Query errors (invalid regex, malformed filters)
+    #[error("Invalid query '{query}': {reason}")]
+    InvalidQuery { query: String, reason: String },
+
     /// General errors for cases where we need to preserve existing behavior
     #[error("{0}")]
     General(String),
@@ -102,6 +116,8 @@ impl IndexError {
             Self::FileWrite { .. } => "FILE_WRITE_ERROR",
             Self::ParseError { .. } => "PARSE_ERROR",
             Self::UnsupportedFileType { .. } => "UNSUPPORTED_FILE_TYPE",
+            Self::BinaryContent { .. } => "BINARY_CONTENT",
+            Self::ParseTimeout { .. } => "PARSE_TIMEOUT",
             Self::PersistenceError { .. } => "PERSISTENCE_ERROR",
             Self::LoadError { .. } => "LOAD_ERROR",
             Self::SymbolNotFound { .. } => "SYMBOL_NOT_FOUND",
@@ -113,6 +129,8 @@ impl IndexError {
             Self::TransactionFailed { .. } => "TRANSACTION_FAILED",
             Self::MutexPoisoned => "MUTEX_POISONED",
             Self::IndexCorrupted { .. } => "INDEX_CORRUPTED",
+            Self::IncompatibleIndex { .. } => "INCOMPATIBLE_INDEX",
+            Self::InvalidQuery { .. } => "INVALID_QUERY",
             Self::General(_) => "GENERAL_ERROR",
         }
         .to_string()
@@ -137,6 +155,9 @@ impl IndexError {
                 "Run 'codanna index --force' to rebuild from scratch",
                 "Check for disk errors or filesystem corruption",
             ],
+            Self::IncompatibleIndex { .. } => {
+                vec!["Run 'codanna index --force' to rebuild the index in the current format"]
+            }
             Self::LoadError { .. } | Self::PersistenceError { .. } => vec![
                 "The index will be loaded from Tantivy on next start",
                 "Run 'codanna index --force' if you continue to have issues",
@@ -149,6 +170,14 @@ impl IndexError {
                 "Currently only Rust files (.rs) are supported",
                 "Support for other languages is coming soon",
             ],
+            Self::BinaryContent { .. } => vec![
+                "Only UTF-8 text files are indexed",
+                "Add the file to ignore_patterns if it is generated or vendored",
+            ],
+            Self::ParseTimeout { .. } => vec![
+                "Raise indexing.parse_timeout_ms if the file is legitimate but large",
+                "Add the file to ignore_patterns if it is generated or vendored",
+            ],
             _ => vec![],
         }
     }
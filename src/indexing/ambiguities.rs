@@ -0,0 +1,92 @@
+//! Relationship sites where resolution picked among several candidates
+//!
+//! Resolution records [`Provenance::Ambiguous`](crate::Provenance) when an
+//! unqualified name matched more than one indexed symbol of the same kind.
+//! Listing those sites with their candidates shows where imports or
+//! qualified paths would make the analysis exact.
+
+use crate::symbol::context::SymbolContext;
+use crate::{RelationKind, Symbol};
+use serde::Serialize;
+use std::fmt;
+
+/// One relationship whose target was picked from several same-named symbols
+#[derive(Debug, Clone, Serialize)]
+pub struct AmbiguousResolution {
+    pub kind: RelationKind,
+    /// The symbol the relationship starts from, such as the calling function
+    pub source: Symbol,
+    /// Site as `path:line`: the call site when recorded, else the source symbol
+    pub location: String,
+    /// The candidate resolution chose
+    pub target: Symbol,
+    /// Every indexed symbol sharing the target's name and kind, chosen one first
+    pub candidates: Vec<Symbol>,
+}
+
+impl fmt::Display for AmbiguousResolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {:?} {} ({} candidates)",
+            self.location,
+            self.source.name,
+            self.kind,
+            self.target.name,
+            self.candidates.len()
+        )?;
+        for candidate in &self.candidates {
+            let (marker, note) = if candidate.id == self.target.id {
+                ('*', " (chosen)")
+            } else {
+                ('-', "")
+            };
+            write!(
+                f,
+                "\n  {marker} {:?} {} at {} [symbol_id:{}]{note}",
+                candidate.kind,
+                candidate.name,
+                SymbolContext::symbol_location(candidate),
+                candidate.id.value()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Range;
+    use crate::{FileId, SymbolId, SymbolKind};
+
+    #[test]
+    fn test_display_marks_chosen_candidate() {
+        let symbol = |id: u32, name: &str, path: &str, line: u32| {
+            let mut symbol = Symbol::new(
+                SymbolId::new(id).unwrap(),
+                name,
+                SymbolKind::Function,
+                FileId::new(1).unwrap(),
+                Range::new(line, 0, line, 10),
+            );
+            symbol.file_path = path.into();
+            symbol
+        };
+        let chosen = symbol(2, "helper", "src/a.rs", 4);
+        let site = AmbiguousResolution {
+            kind: RelationKind::Calls,
+            source: symbol(1, "run", "src/main.rs", 0),
+            location: "src/main.rs:3".to_string(),
+            target: chosen.clone(),
+            candidates: vec![chosen, symbol(3, "helper", "src/b.rs", 9)],
+        };
+
+        assert_eq!(
+            site.to_string(),
+            "src/main.rs:3 run Calls helper (2 candidates)\n  \
+             * Function helper at src/a.rs:5 [symbol_id:2] (chosen)\n  \
+             - Function helper at src/b.rs:10 [symbol_id:3]"
+        );
+    }
+}
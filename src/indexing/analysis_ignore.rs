@@ -0,0 +1,100 @@
+//! Symbols excluded from unused-symbol reports
+//!
+//! Built from `[analysis]` in settings. Conventionally kept symbols (test
+//! helpers, FFI exports, symbols reached through reflection or derive
+//! macros) otherwise show up as public functions without callers.
+
+use crate::config::AnalysisConfig;
+
+/// Name and attribute patterns from `analysis.ignore_names` and
+/// `analysis.ignore_attributes`
+#[derive(Debug, Clone, Default)]
+pub struct AnalysisIgnore {
+    names: Vec<String>,
+    attributes: Vec<String>,
+}
+
+impl AnalysisIgnore {
+    pub fn from_config(config: &AnalysisConfig) -> Self {
+        Self {
+            names: config.ignore_names.clone(),
+            attributes: config
+                .ignore_attributes
+                .iter()
+                .map(|pattern| collapse_whitespace(pattern))
+                .collect(),
+        }
+    }
+
+    /// Whether a symbol with this name and these attributes (see
+    /// `Symbol::attributes`) is left out of reports
+    pub fn ignores(&self, name: &str, attributes: &[&str]) -> bool {
+        self.names
+            .iter()
+            .any(|pattern| wildcard_match(pattern, name))
+            || attributes.iter().any(|attribute| {
+                let attribute = collapse_whitespace(attribute);
+                self.attributes
+                    .iter()
+                    .any(|pattern| wildcard_match(pattern, &attribute))
+            })
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Match `text` against a pattern where `*` is any run of characters and
+/// `?` is a single character
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Position after the last `*` and the text position it was tried at
+    let mut backtrack = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p + 1, t));
+                p += 1;
+            }
+            Some(&ch) if ch == '?' || ch == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((after_star, tried)) => {
+                    backtrack = Some((after_star, tried + 1));
+                    p = after_star;
+                    t = tried + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&ch| ch == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ignores_names_and_attributes() {
+        let ignore = AnalysisIgnore::from_config(&AnalysisConfig {
+            ignore_names: vec!["test_*".to_string(), "__?__".to_string()],
+            ignore_attributes: vec!["#[used]".to_string(), "@pytest.fixture*".to_string()],
+        });
+
+        assert!(ignore.ignores("test_helper", &[]));
+        assert!(ignore.ignores("__x__", &[]));
+        assert!(!ignore.ignores("__init__", &[]));
+        assert!(!ignore.ignores("contest_helper", &[]));
+        assert!(ignore.ignores("BUFFER", &["#[used]"]));
+        assert!(ignore.ignores("db", &["@pytest.fixture(scope=\"module\")"]));
+        assert!(!ignore.ignores("run", &["#[inline]"]));
+        assert!(!AnalysisIgnore::default().ignores("test_helper", &["#[used]"]));
+    }
+}
@@ -0,0 +1,214 @@
+//! Reading source files straight out of `.tar`, `.tar.gz`/`.tgz` and `.zip` archives
+//!
+//! Entries are streamed one at a time, so a vendored crate or a release
+//! artifact can be indexed without extracting it. Only regular files are
+//! visited; directories, links and other special entries are passed over.
+
+use crate::{IndexError, IndexResult};
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+/// Archive formats `codanna index` reads directly
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    Tar,
+    TarGz,
+    Zip,
+}
+
+impl ArchiveFormat {
+    /// The format a file name indicates, if it names an archive
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+        if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else if name.ends_with(".tar") {
+            Some(Self::Tar)
+        } else if name.ends_with(".zip") {
+            Some(Self::Zip)
+        } else {
+            None
+        }
+    }
+}
+
+/// Separates an archive's path from an entry name in stored file paths
+pub const ENTRY_SEPARATOR: &str = "!/";
+
+/// The path an archive entry is stored under, `<archive>!/<entry>`
+pub fn entry_path(archive: &str, entry: &str) -> String {
+    format!("{archive}{ENTRY_SEPARATOR}{entry}")
+}
+
+/// Split a stored path into its archive path and entry name
+///
+/// Returns `None` for paths that do not name an archive entry.
+pub fn split_entry_path(path: &str) -> Option<(&Path, &str)> {
+    let (archive, entry) = path.split_once(ENTRY_SEPARATOR)?;
+    ArchiveFormat::from_path(Path::new(archive))?;
+    Some((Path::new(archive), entry))
+}
+
+/// Read the content of one entry of an archive
+///
+/// Fails with a `NotFound` read error when the archive has no such entry.
+pub fn read_entry(path: &Path, entry: &str) -> IndexResult<Vec<u8>> {
+    let format = ArchiveFormat::from_path(path).ok_or_else(|| {
+        read_error(
+            path,
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, "not an archive"),
+        )
+    })?;
+    let mut content = None;
+    for_each_file(path, format, |name, _, reader| {
+        if content.is_none() && name == entry {
+            let mut bytes = Vec::new();
+            reader
+                .read_to_end(&mut bytes)
+                .map_err(|e| read_error(path, e))?;
+            content = Some(bytes);
+        }
+        Ok(())
+    })?;
+    content.ok_or_else(|| IndexError::FileRead {
+        path: entry_path(&path.to_string_lossy(), entry).into(),
+        source: std::io::Error::new(std::io::ErrorKind::NotFound, "no such archive entry"),
+    })
+}
+
+/// Call `visit` with the name, size and content of each regular file in an archive
+///
+/// Names are the entry paths inside the archive with any leading `./`
+/// removed. Stops at the first error `visit` returns.
+pub fn for_each_file<F>(path: &Path, format: ArchiveFormat, mut visit: F) -> IndexResult<()>
+where
+    F: FnMut(&str, u64, &mut dyn Read) -> IndexResult<()>,
+{
+    let file = File::open(path).map_err(|e| read_error(path, e))?;
+    let reader = BufReader::new(file);
+    match format {
+        ArchiveFormat::Tar => for_each_tar_file(path, reader, &mut visit),
+        ArchiveFormat::TarGz => {
+            for_each_tar_file(path, flate2::read::GzDecoder::new(reader), &mut visit)
+        }
+        ArchiveFormat::Zip => for_each_zip_file(path, reader, &mut visit),
+    }
+}
+
+fn for_each_tar_file<R, F>(path: &Path, reader: R, visit: &mut F) -> IndexResult<()>
+where
+    R: Read,
+    F: FnMut(&str, u64, &mut dyn Read) -> IndexResult<()>,
+{
+    let mut archive = tar::Archive::new(reader);
+    for entry in archive.entries().map_err(|e| read_error(path, e))? {
+        let mut entry = entry.map_err(|e| read_error(path, e))?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let name = entry
+            .path()
+            .map_err(|e| read_error(path, e))?
+            .to_string_lossy()
+            .into_owned();
+        let size = entry.size();
+        visit(entry_name(&name), size, &mut entry)?;
+    }
+    Ok(())
+}
+
+fn for_each_zip_file<F>(path: &Path, reader: BufReader<File>, visit: &mut F) -> IndexResult<()>
+where
+    F: FnMut(&str, u64, &mut dyn Read) -> IndexResult<()>,
+{
+    let mut archive =
+        zip::ZipArchive::new(reader).map_err(|e| read_error(path, std::io::Error::other(e)))?;
+    for index in 0..archive.len() {
+        let mut entry = archive
+            .by_index(index)
+            .map_err(|e| read_error(path, std::io::Error::other(e)))?;
+        if !entry.is_file() {
+            continue;
+        }
+        let name = entry.name().to_string();
+        let size = entry.size();
+        visit(entry_name(&name), size, &mut entry)?;
+    }
+    Ok(())
+}
+
+fn entry_name(name: &str) -> &str {
+    name.trim_start_matches("./")
+}
+
+fn read_error(path: &Path, source: std::io::Error) -> IndexError {
+    IndexError::FileRead {
+        path: path.to_path_buf(),
+        source,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_archive_format_from_path() {
+        assert_eq!(
+            ArchiveFormat::from_path(Path::new("serde-1.0.0.tar.gz")),
+            Some(ArchiveFormat::TarGz)
+        );
+        assert_eq!(
+            ArchiveFormat::from_path(Path::new("vendor/crate.TGZ")),
+            Some(ArchiveFormat::TarGz)
+        );
+        assert_eq!(
+            ArchiveFormat::from_path(Path::new("snapshot.tar")),
+            Some(ArchiveFormat::Tar)
+        );
+        assert_eq!(
+            ArchiveFormat::from_path(Path::new("release.zip")),
+            Some(ArchiveFormat::Zip)
+        );
+        assert_eq!(ArchiveFormat::from_path(Path::new("main.rs")), None);
+        assert_eq!(ArchiveFormat::from_path(Path::new("data.gz")), None);
+    }
+
+    #[test]
+    fn test_entry_path_round_trip() {
+        let stored = entry_path("vendor/pkg-1.0.0.tar.gz", "pkg/src/lib.rs");
+        assert_eq!(stored, "vendor/pkg-1.0.0.tar.gz!/pkg/src/lib.rs");
+        assert_eq!(
+            split_entry_path(&stored),
+            Some((Path::new("vendor/pkg-1.0.0.tar.gz"), "pkg/src/lib.rs"))
+        );
+        assert_eq!(split_entry_path("src/lib.rs"), None);
+        assert_eq!(split_entry_path("docs/wow!/notes.md"), None);
+    }
+
+    #[test]
+    fn test_read_entry() {
+        use std::io::Write;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let zipped = temp_dir.path().join("src.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&zipped).unwrap());
+        writer
+            .start_file("src/lib.rs", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(b"pub fn zipped() {}\n").unwrap();
+        writer.finish().unwrap();
+
+        assert_eq!(
+            read_entry(&zipped, "src/lib.rs").unwrap(),
+            b"pub fn zipped() {}\n"
+        );
+        match read_entry(&zipped, "src/gone.rs") {
+            Err(IndexError::FileRead { source, .. }) => {
+                assert_eq!(source.kind(), std::io::ErrorKind::NotFound)
+            }
+            other => panic!("expected a missing entry, got {other:?}"),
+        }
+    }
+}
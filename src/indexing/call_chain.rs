@@ -0,0 +1,194 @@
+//! Receiver types of chained method calls
+//!
+//! Parsers record the receiver of `builder.name("x").size(3).build()` as the
+//! text before the last call: `builder.name("x").size(3)`. Resolution walks
+//! that text back to its base (a variable, `self`/`this` or `Type::new()`)
+//! and follows the declared return type of each call to type the next link.
+//! Both helpers here work on source text only; symbol lookups stay in the
+//! indexer.
+
+/// Last link of a chained receiver
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ChainLink<'a> {
+    /// Receiver of the call: an expression, or a type path for static calls
+    pub receiver: &'a str,
+    /// Name of the method called
+    pub method: &'a str,
+    /// `Type::method()` rather than `value.method()`
+    pub is_static: bool,
+}
+
+/// Split `a.b(x).c()` into the receiver `a.b(x)` and the method `c`
+///
+/// Also accepts `Type::new()`, Rust's `?` after a call and TypeScript's
+/// `?.`. Returns `None` when the text does not end in a method call.
+pub(crate) fn split_chained_call(receiver: &str) -> Option<ChainLink<'_>> {
+    let text = receiver.trim_end().trim_end_matches('?').trim_end();
+    let without_args = text.strip_suffix(')')?;
+
+    let mut depth = 0usize;
+    let mut open = None;
+    for (i, c) in without_args.char_indices().rev() {
+        match c {
+            ')' => depth += 1,
+            '(' if depth == 0 => {
+                open = Some(i);
+                break;
+            }
+            '(' => depth -= 1,
+            _ => {}
+        }
+    }
+    let callee = without_args[..open?].trim_end();
+    // Turbofish arguments do not change the method: `collect::<Vec<_>>()`
+    let callee = callee.split_once("::<").map_or(callee, |(name, _)| name);
+
+    let (receiver, method, is_static) = match callee.rfind('.') {
+        Some(dot) => (
+            callee[..dot].trim_end().trim_end_matches('?').trim_end(),
+            callee[dot + 1..].trim(),
+            false,
+        ),
+        None => {
+            let (path, method) = callee.rsplit_once("::")?;
+            (path.trim(), method.trim(), true)
+        }
+    };
+    let is_identifier = |s: &str| {
+        !s.is_empty()
+            && s.chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+    };
+    if receiver.is_empty() || !is_identifier(method) {
+        return None;
+    }
+    Some(ChainLink {
+        receiver,
+        method,
+        is_static,
+    })
+}
+
+/// Type name a function signature declares it returns
+///
+/// Reads Rust's `-> T` and TypeScript's `): T`. References, lifetimes and
+/// generic arguments are dropped, and `Result`, `Option`, `Box`, `Rc`, `Arc`
+/// and `Promise` are looked through to their first argument. `Self` and
+/// `this` are returned as written for the caller to substitute.
+pub(crate) fn declared_return_type(signature: &str) -> Option<&str> {
+    let params = signature.find('(')?;
+    let mut depth = 0usize;
+    let mut close = None;
+    for (i, c) in signature[params..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    close = Some(params + i);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    let rest = signature[close? + 1..].trim_start();
+    let rest = rest.strip_prefix("->").or_else(|| rest.strip_prefix(':'))?;
+    let end = rest
+        .find(['{', ';', '='])
+        .into_iter()
+        .chain(rest.find(" where"))
+        .min()
+        .unwrap_or(rest.len());
+    type_name(rest[..end].trim())
+}
+
+/// Base name of a type expression, looking through common wrappers
+fn type_name(ty: &str) -> Option<&str> {
+    let mut ty = ty.trim_start_matches('&').trim_start();
+    if let Some(lifetime) = ty.strip_prefix('\'') {
+        ty = lifetime.split_once(' ')?.1.trim_start();
+    }
+    ty = ty.strip_prefix("mut ").unwrap_or(ty).trim_start();
+
+    let (base, args) = match ty.split_once('<') {
+        Some((base, args)) => (base.trim(), Some(args)),
+        None => (ty.trim(), None),
+    };
+    let base = base.rsplit("::").next().unwrap_or(base);
+    match (base, args) {
+        ("Result" | "Option" | "Box" | "Rc" | "Arc" | "Promise", Some(args)) => {
+            let first = args.split([',', '>']).next()?;
+            type_name(first)
+        }
+        _ if !base.is_empty() && base.chars().all(|c| c.is_alphanumeric() || c == '_') => {
+            Some(base)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_chained_call() {
+        assert_eq!(
+            split_chained_call(r#"builder.name("x").size(3)"#),
+            Some(ChainLink {
+                receiver: r#"builder.name("x")"#,
+                method: "size",
+                is_static: false,
+            })
+        );
+        assert_eq!(
+            split_chained_call("Builder::new()"),
+            Some(ChainLink {
+                receiver: "Builder",
+                method: "new",
+                is_static: true,
+            })
+        );
+        let multiline = split_chained_call("client\n    .get(url)?\n    .header(k, f(v))").unwrap();
+        assert_eq!(multiline.receiver, "client\n    .get(url)");
+        assert_eq!(multiline.method, "header");
+        assert_eq!(
+            split_chained_call("query?.where(x)").map(|l| (l.receiver, l.method)),
+            Some(("query", "where"))
+        );
+        assert_eq!(split_chained_call("builder"), None);
+        assert_eq!(split_chained_call("self.config"), None);
+        assert_eq!(split_chained_call("helper()"), None);
+    }
+
+    #[test]
+    fn test_declared_return_type() {
+        assert_eq!(
+            declared_return_type("pub fn name(mut self, name: &str) -> Self"),
+            Some("Self")
+        );
+        assert_eq!(
+            declared_return_type("fn size(&mut self, n: usize) -> &mut Builder"),
+            Some("Builder")
+        );
+        assert_eq!(
+            declared_return_type("pub fn build(self) -> Result<crate::Config, Error>"),
+            Some("Config")
+        );
+        assert_eq!(
+            declared_return_type("fn iter<'a>(&'a self) -> Box<Iter<'a>> where Self: Sized"),
+            Some("Iter")
+        );
+        assert_eq!(
+            declared_return_type("name(value: string): this {"),
+            Some("this")
+        );
+        assert_eq!(
+            declared_return_type("async fetch(): Promise<Response>"),
+            Some("Response")
+        );
+        assert_eq!(declared_return_type("fn run(&self)"), None);
+        assert_eq!(declared_return_type("fn pair() -> (u32, u32)"), None);
+    }
+}
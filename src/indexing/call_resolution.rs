@@ -0,0 +1,173 @@
+//! Step-by-step account of how a call expression resolves
+//!
+//! `codanna debug resolve` replays relationship resolution for the calls on
+//! one line and reports what the resolver looked at: the imports bringing
+//! the name into scope, each lookup it tried, the indexed symbols sharing the
+//! called name and the target it settled on. Candidates are listed even when
+//! nothing was chosen, since that is when they matter most. The replay is
+//! checked against the `Calls` edges stored for the call site, so a trace
+//! that no longer matches what indexing recorded says so.
+
+use crate::Symbol;
+use crate::parsing::resolution::ImportOrigin;
+use crate::relationship::Provenance;
+use crate::symbol::context::SymbolContext;
+use serde::Serialize;
+use std::fmt;
+
+/// How the resolver handles one call expression
+#[derive(Debug, Clone, Serialize)]
+pub struct CallResolutionTrace {
+    /// The called name, without its receiver
+    pub name: String,
+    /// Receiver expression (`self`, a variable or a type), if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receiver: Option<String>,
+    /// `Type::method` rather than `value.method`
+    pub is_static: bool,
+    /// 1-based line of the call
+    pub line: u32,
+    /// 0-based column of the call
+    pub column: u16,
+    /// Function or method containing the call, when indexed
+    pub caller: Option<Symbol>,
+    /// Imports that bring the receiver or the called name into scope
+    pub imports: Vec<ConsideredImport>,
+    /// Lookups the resolver tries, in order
+    pub steps: Vec<ResolutionStep>,
+    /// Indexed symbols sharing the called name
+    pub candidates: Vec<ResolutionCandidate>,
+    /// The symbol the call resolves to; `None` when the edge is not recorded
+    pub chosen: Option<Symbol>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<Provenance>,
+    /// Why the target was chosen, or why none was
+    pub reason: String,
+    /// Targets of the `Calls` edges stored for this call site
+    pub stored: Vec<Symbol>,
+    /// How the replay differs from the stored edges, when it does
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disagreement: Option<String>,
+}
+
+/// An import binding consulted while resolving a call
+#[derive(Debug, Clone, Serialize)]
+pub struct ConsideredImport {
+    /// Name the import makes visible (alias or last path segment)
+    pub name: String,
+    /// Import path as written
+    pub path: String,
+    /// `internal`, `external` or `unknown`
+    pub origin: &'static str,
+    /// Indexed symbol the import points to, if resolved
+    pub resolved: Option<Symbol>,
+}
+
+impl ConsideredImport {
+    pub(crate) fn origin_name(origin: ImportOrigin) -> &'static str {
+        match origin {
+            ImportOrigin::Internal => "internal",
+            ImportOrigin::External => "external",
+            ImportOrigin::Unknown => "unknown",
+        }
+    }
+}
+
+/// One lookup in the file's resolution context
+#[derive(Debug, Clone, Serialize)]
+pub struct ResolutionStep {
+    /// Name looked up, or the receiver when the step is about the receiver
+    pub lookup: String,
+    /// Symbol the lookup found
+    pub result: Option<Symbol>,
+    /// Confidence of an edge created from this step
+    pub provenance: Provenance,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// An indexed symbol sharing the called name
+#[derive(Debug, Clone, Serialize)]
+pub struct ResolutionCandidate {
+    pub symbol: Symbol,
+    /// Scope level the symbol is visible at in the calling file
+    /// (`local`, `module`, `package`, `global`); `None` when not in scope
+    pub scope: Option<String>,
+}
+
+fn describe(symbol: &Symbol) -> String {
+    format!(
+        "{:?} {} at {} [symbol_id:{}]",
+        symbol.kind,
+        symbol.name,
+        SymbolContext::symbol_location(symbol),
+        symbol.id.value()
+    )
+}
+
+impl fmt::Display for CallResolutionTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Call to '{}'", self.name)?;
+        if let Some(receiver) = &self.receiver {
+            let kind = if self.is_static { "type" } else { "receiver" };
+            write!(f, " on {kind} '{receiver}'")?;
+        }
+        write!(f, " at line {}, column {}", self.line, self.column)?;
+        match &self.caller {
+            Some(caller) => writeln!(f, " in {:?} {}", caller.kind, caller.name)?,
+            None => writeln!(f, " (no indexed caller)")?,
+        }
+
+        if !self.imports.is_empty() {
+            writeln!(f, "  Imports considered:")?;
+            for import in &self.imports {
+                write!(
+                    f,
+                    "    {} <- {} ({}",
+                    import.name, import.path, import.origin
+                )?;
+                match &import.resolved {
+                    Some(symbol) => writeln!(f, ", {})", describe(symbol))?,
+                    None => writeln!(f, ")")?,
+                }
+            }
+        }
+
+        writeln!(f, "  Lookups:")?;
+        for step in &self.steps {
+            write!(f, "    {} -> ", step.lookup)?;
+            match &step.result {
+                Some(symbol) => write!(f, "{} ({})", describe(symbol), step.provenance)?,
+                None => write!(f, "not found")?,
+            }
+            match &step.note {
+                Some(note) => writeln!(f, "; {note}")?,
+                None => writeln!(f)?,
+            }
+        }
+
+        writeln!(f, "  Candidates ({}):", self.candidates.len())?;
+        for candidate in &self.candidates {
+            write!(f, "    {}", describe(&candidate.symbol))?;
+            match &candidate.scope {
+                Some(scope) => writeln!(f, " (in scope: {scope})")?,
+                None => writeln!(f)?,
+            }
+        }
+
+        match (&self.chosen, self.provenance) {
+            (Some(symbol), Some(provenance)) => {
+                writeln!(f, "  Chosen: {} ({provenance})", describe(symbol))?
+            }
+            _ => writeln!(f, "  Chosen: none")?,
+        }
+        write!(f, "  Reason: {}", self.reason)?;
+        for symbol in &self.stored {
+            write!(f, "\n  Stored edge: {}", describe(symbol))?;
+        }
+        if let Some(disagreement) = &self.disagreement {
+            write!(f, "\n  Warning: {disagreement}")?;
+        }
+        Ok(())
+    }
+}
@@ -31,6 +31,8 @@ pub struct ConfigFileWatcher {
     broadcaster: Option<Arc<NotificationBroadcaster>>,
     /// Last known indexed paths
     last_indexed_paths: HashSet<PathBuf>,
+    /// Index profile whose indexed_paths are tracked (None = default)
+    index_profile: Option<String>,
     /// MCP debug flag
     mcp_debug: bool,
     /// Channel receiver for file events
@@ -44,6 +46,7 @@ impl ConfigFileWatcher {
     pub fn new(
         settings_path: PathBuf,
         indexer: Arc<RwLock<SimpleIndexer>>,
+        index_profile: Option<String>,
         mcp_debug: bool,
     ) -> IndexResult<Self> {
         // Create channel for events
@@ -59,18 +62,14 @@ impl ConfigFileWatcher {
         })?;
 
         // Load initial indexed_paths
-        let initial_config =
-            Settings::load_from(&settings_path).map_err(|e| IndexError::ConfigError {
-                reason: format!("Failed to load config: {e}"),
-            })?;
-        let last_indexed_paths: HashSet<_> =
-            initial_config.indexing.indexed_paths.into_iter().collect();
+        let last_indexed_paths = load_indexed_paths(&settings_path, index_profile.as_deref())?;
 
         Ok(Self {
             settings_path,
             indexer,
             broadcaster: None,
             last_indexed_paths,
+            index_profile,
             mcp_debug,
             event_rx: rx,
             _watcher: watcher,
@@ -104,14 +103,14 @@ impl ConfigFileWatcher {
                 source: std::io::Error::other(e.to_string()),
             })?;
 
-        eprintln!(
+        crate::log_info!(
             "Config watcher: Monitoring {}",
             self.settings_path.display()
         );
 
         // Check for any pending changes on startup (config modified while server was down)
         if let Err(e) = self.check_initial_sync().await {
-            eprintln!("Warning: Initial config sync failed: {e}");
+            crate::log_warn!("Warning: Initial config sync failed: {e}");
         }
 
         // Event loop
@@ -124,7 +123,7 @@ impl ConfigFileWatcher {
                             match event.kind {
                                 EventKind::Modify(_) | EventKind::Create(_) => {
                                     if let Err(e) = self.handle_config_change().await {
-                                        eprintln!("Config watcher error: {e}");
+                                        crate::log_error!("Config watcher error: {e}");
                                     }
                                 }
                                 _ => {}
@@ -132,7 +131,7 @@ impl ConfigFileWatcher {
                         }
                     }
                     Err(e) => {
-                        eprintln!("Config watch error: {e}");
+                        crate::log_error!("Config watch error: {e}");
                     }
                 }
             }
@@ -142,7 +141,7 @@ impl ConfigFileWatcher {
     /// Check if config has changes that need syncing on startup
     async fn check_initial_sync(&mut self) -> IndexResult<()> {
         if self.mcp_debug {
-            eprintln!("DEBUG: Checking for pending config changes on startup");
+            crate::log_debug!("Checking for pending config changes on startup");
         }
 
         // Get current indexed paths from the indexer
@@ -160,32 +159,36 @@ impl ConfigFileWatcher {
 
         if added.is_empty() {
             if self.mcp_debug {
-                eprintln!("DEBUG: No pending config changes detected");
+                crate::log_debug!("No pending config changes detected");
             }
             return Ok(());
         }
 
-        eprintln!(
+        crate::log_info!(
             "Initial sync: Found {} new directories to index",
             added.len()
         );
         for path in &added {
-            eprintln!("  + {}", path.display());
+            crate::log_info!("  + {}", path.display());
         }
 
         // Index new directories
         let mut indexer = self.indexer.write().await;
         for path in &added {
-            eprintln!("Indexing new directory: {}", path.display());
+            crate::log_info!("Indexing new directory: {}", path.display());
             match indexer.index_directory(path, false, false) {
                 Ok(stats) => {
-                    eprintln!(
+                    crate::log_info!(
+                        path = path.display().to_string(),
+                        files = stats.files_indexed as u64,
+                        symbols = stats.symbols_found as u64;
                         "  ✓ Indexed {} files, {} symbols",
-                        stats.files_indexed, stats.symbols_found
+                        stats.files_indexed,
+                        stats.symbols_found
                     );
                 }
                 Err(e) => {
-                    eprintln!("  ✗ Failed to index {}: {e}", path.display());
+                    crate::log_error!(path = path.display().to_string(); "  ✗ Failed to index {}: {e}", path.display());
                 }
             }
         }
@@ -194,10 +197,10 @@ impl ConfigFileWatcher {
         // Send notification to update file watcher and MCP clients
         if let Some(ref broadcaster) = self.broadcaster {
             if self.mcp_debug {
-                eprintln!("DEBUG: Sending IndexReloaded notification");
+                crate::log_debug!("Sending IndexReloaded notification");
             }
             broadcaster.send(FileChangeEvent::IndexReloaded);
-            eprintln!("  ✓ Notified watchers of index changes");
+            crate::log_info!("  ✓ Notified watchers of index changes");
         }
 
         Ok(())
@@ -206,28 +209,24 @@ impl ConfigFileWatcher {
     /// Handle configuration file change
     async fn handle_config_change(&mut self) -> IndexResult<()> {
         if self.mcp_debug {
-            eprintln!("DEBUG: Config file changed, checking indexed_paths");
+            crate::log_debug!("Config file changed, checking indexed_paths");
         }
 
         // Small delay to ensure file write is complete
         tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
 
         // Reload config
-        let new_config =
-            Settings::load_from(&self.settings_path).map_err(|e| IndexError::ConfigError {
-                reason: format!("Failed to reload config: {e}"),
-            })?;
-        let new_paths: HashSet<PathBuf> = new_config.indexing.indexed_paths.into_iter().collect();
+        let new_paths = load_indexed_paths(&self.settings_path, self.index_profile.as_deref())?;
 
         // Check if indexed_paths changed
         if new_paths == self.last_indexed_paths {
             if self.mcp_debug {
-                eprintln!("DEBUG: indexed_paths unchanged, ignoring");
+                crate::log_debug!("indexed_paths unchanged, ignoring");
             }
             return Ok(());
         }
 
-        eprintln!("Config change detected: indexed_paths modified");
+        crate::log_info!("Config change detected: indexed_paths modified");
 
         // Find added and removed paths
         let added: Vec<_> = new_paths
@@ -241,35 +240,39 @@ impl ConfigFileWatcher {
             .collect();
 
         if !added.is_empty() {
-            eprintln!("New directories to index: {}", added.len());
+            crate::log_info!("New directories to index: {}", added.len());
             for path in &added {
-                eprintln!("  + {}", path.display());
+                crate::log_info!("  + {}", path.display());
             }
 
             // Index new directories
             let mut indexer = self.indexer.write().await;
             for path in &added {
-                eprintln!("Indexing new directory: {}", path.display());
+                crate::log_info!("Indexing new directory: {}", path.display());
                 match indexer.index_directory(path, false, false) {
                     Ok(stats) => {
-                        eprintln!(
+                        crate::log_info!(
+                            path = path.display().to_string(),
+                            files = stats.files_indexed as u64,
+                            symbols = stats.symbols_found as u64;
                             "  ✓ Indexed {} files, {} symbols",
-                            stats.files_indexed, stats.symbols_found
+                            stats.files_indexed,
+                            stats.symbols_found
                         );
                     }
                     Err(e) => {
-                        eprintln!("  ✗ Failed to index {}: {e}", path.display());
+                        crate::log_error!(path = path.display().to_string(); "  ✗ Failed to index {}: {e}", path.display());
                     }
                 }
             }
         }
 
         if !removed.is_empty() {
-            eprintln!("Directories removed from config: {}", removed.len());
+            crate::log_info!("Directories removed from config: {}", removed.len());
             for path in &removed {
-                eprintln!("  - {}", path.display());
+                crate::log_info!("  - {}", path.display());
             }
-            eprintln!(
+            crate::log_info!(
                 "Run 'codanna clean' or 'codanna index' to remove symbols from these directories"
             );
         }
@@ -280,12 +283,28 @@ impl ConfigFileWatcher {
         // Send notification to update file watcher and MCP clients
         if let Some(ref broadcaster) = self.broadcaster {
             if self.mcp_debug {
-                eprintln!("DEBUG: Sending IndexReloaded notification");
+                crate::log_debug!("Sending IndexReloaded notification");
             }
             broadcaster.send(FileChangeEvent::IndexReloaded);
-            eprintln!("  ✓ Notified watchers of index changes");
+            crate::log_info!("  ✓ Notified watchers of index changes");
         }
 
         Ok(())
     }
 }
+
+/// Load the indexed paths from `settings_path` as seen by `index_profile`.
+fn load_indexed_paths(
+    settings_path: &std::path::Path,
+    index_profile: Option<&str>,
+) -> IndexResult<HashSet<PathBuf>> {
+    let mut config = Settings::load_from(settings_path).map_err(|e| IndexError::ConfigError {
+        reason: format!("Failed to load config: {e}"),
+    })?;
+    if let Some(name) = index_profile {
+        config
+            .select_index_profile(name)
+            .map_err(|reason| IndexError::ConfigError { reason })?;
+    }
+    Ok(config.indexing.indexed_paths.into_iter().collect())
+}
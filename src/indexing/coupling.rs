@@ -0,0 +1,178 @@
+//! File-level coupling analysis over the relationship graph
+//!
+//! Aggregates symbol relationship edges by the files that define each
+//! endpoint, so hotspots (high fan-in) and god-objects (high fan-out) can be
+//! spotted without walking individual symbols.
+
+use crate::{FileId, SymbolId};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Number of related files reported per file
+const MAX_RELATED_FILES: usize = 5;
+
+/// Coupling summary for one file
+#[derive(Debug, Clone, Serialize)]
+pub struct FileCoupling {
+    pub file_path: String,
+    /// Edges from other files into symbols of this file (fan-in)
+    pub inbound: usize,
+    /// Edges from symbols of this file into other files (fan-out)
+    pub outbound: usize,
+    /// Files this one is most coupled to, by total edge count
+    pub related: Vec<RelatedFile>,
+}
+
+/// Edge counts between a file and one of its related files
+#[derive(Debug, Clone, Serialize)]
+pub struct RelatedFile {
+    pub file_path: String,
+    /// Edges from the related file into this file
+    pub inbound: usize,
+    /// Edges from this file into the related file
+    pub outbound: usize,
+}
+
+impl FileCoupling {
+    pub fn total(&self) -> usize {
+        self.inbound + self.outbound
+    }
+}
+
+impl fmt::Display for FileCoupling {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (in: {}, out: {})",
+            self.file_path, self.inbound, self.outbound
+        )?;
+        for related in &self.related {
+            write!(
+                f,
+                "\n  {} (in: {}, out: {})",
+                related.file_path, related.inbound, related.outbound
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Aggregate relationship edges by file.
+///
+/// Edges within a single file are ignored, as are edges whose endpoints have
+/// no known file. Results are sorted by total edge count, highest first.
+pub fn aggregate_file_coupling(
+    edges: &[(SymbolId, SymbolId)],
+    symbol_files: &HashMap<SymbolId, FileId>,
+    file_paths: &HashMap<FileId, String>,
+) -> Vec<FileCoupling> {
+    // (inbound, outbound) per file, and per (file, related file)
+    let mut totals: HashMap<FileId, (usize, usize)> = HashMap::new();
+    let mut pairs: HashMap<(FileId, FileId), (usize, usize)> = HashMap::new();
+
+    for (from, to) in edges {
+        let (Some(&from_file), Some(&to_file)) = (symbol_files.get(from), symbol_files.get(to))
+        else {
+            continue;
+        };
+        if from_file == to_file {
+            continue;
+        }
+
+        totals.entry(from_file).or_default().1 += 1;
+        totals.entry(to_file).or_default().0 += 1;
+        pairs.entry((from_file, to_file)).or_default().1 += 1;
+        pairs.entry((to_file, from_file)).or_default().0 += 1;
+    }
+
+    let path_of = |file_id: FileId| {
+        file_paths
+            .get(&file_id)
+            .cloned()
+            .unwrap_or_else(|| format!("<file {}>", file_id.value()))
+    };
+
+    let mut related_by_file: HashMap<FileId, Vec<RelatedFile>> = HashMap::new();
+    for ((file_id, other), (inbound, outbound)) in pairs {
+        related_by_file
+            .entry(file_id)
+            .or_default()
+            .push(RelatedFile {
+                file_path: path_of(other),
+                inbound,
+                outbound,
+            });
+    }
+
+    let mut coupling: Vec<FileCoupling> = totals
+        .into_iter()
+        .map(|(file_id, (inbound, outbound))| {
+            let mut related = related_by_file.remove(&file_id).unwrap_or_default();
+            related.sort_by(|a, b| {
+                (b.inbound + b.outbound)
+                    .cmp(&(a.inbound + a.outbound))
+                    .then_with(|| a.file_path.cmp(&b.file_path))
+            });
+            related.truncate(MAX_RELATED_FILES);
+
+            FileCoupling {
+                file_path: path_of(file_id),
+                inbound,
+                outbound,
+                related,
+            }
+        })
+        .collect();
+
+    coupling.sort_by(|a, b| {
+        b.total()
+            .cmp(&a.total())
+            .then_with(|| a.file_path.cmp(&b.file_path))
+    });
+    coupling
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aggregate_file_coupling() {
+        let file = |id| FileId::new(id).unwrap();
+        let sym = |id| SymbolId::new(id).unwrap();
+
+        // Symbols 1-2 in a.rs, 3 in b.rs, 4 in c.rs
+        let symbol_files = HashMap::from([
+            (sym(1), file(1)),
+            (sym(2), file(1)),
+            (sym(3), file(2)),
+            (sym(4), file(3)),
+        ]);
+        let file_paths = HashMap::from([
+            (file(1), "a.rs".to_string()),
+            (file(2), "b.rs".to_string()),
+            (file(3), "c.rs".to_string()),
+        ]);
+        let edges = vec![
+            (sym(3), sym(1)), // b -> a
+            (sym(4), sym(1)), // c -> a
+            (sym(4), sym(2)), // c -> a
+            (sym(1), sym(2)), // intra-file, ignored
+            (sym(1), sym(9)), // unknown endpoint, ignored
+        ];
+
+        let coupling = aggregate_file_coupling(&edges, &symbol_files, &file_paths);
+        assert_eq!(coupling.len(), 3);
+
+        let a = &coupling[0];
+        assert_eq!(a.file_path, "a.rs");
+        assert_eq!((a.inbound, a.outbound), (3, 0));
+        assert_eq!(a.related[0].file_path, "c.rs");
+        assert_eq!(a.related[0].inbound, 2);
+        assert_eq!(a.related[1].file_path, "b.rs");
+
+        let c = coupling.iter().find(|f| f.file_path == "c.rs").unwrap();
+        assert_eq!((c.inbound, c.outbound), (0, 2));
+    }
+}
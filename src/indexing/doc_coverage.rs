@@ -0,0 +1,201 @@
+//! Documentation coverage of public symbols
+//!
+//! A symbol counts as public by the visibility its language behavior
+//! assigned while indexing (`pub` in Rust, exported names in Go, no leading
+//! underscore in Python, ...). Parameters, fields, enum variants and
+//! variables are left out: they are usually documented through their parent.
+
+use crate::symbol::Symbol;
+use crate::symbol::context::SymbolContext;
+use crate::{SymbolId, SymbolKind, Visibility};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A public symbol without a doc comment
+#[derive(Debug, Clone, Serialize)]
+pub struct UndocumentedSymbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// Location as `path:line`
+    pub location: String,
+    pub symbol_id: SymbolId,
+}
+
+/// Coverage of one module, or of one file for symbols without a module path
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleDocCoverage {
+    pub module: String,
+    pub documented: usize,
+    pub total: usize,
+    /// Sorted by file, then line
+    pub undocumented: Vec<UndocumentedSymbol>,
+}
+
+impl ModuleDocCoverage {
+    /// Documented fraction, 1.0 when the module has no public symbols
+    pub fn ratio(&self) -> f64 {
+        ratio(self.documented, self.total)
+    }
+}
+
+impl fmt::Display for ModuleDocCoverage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:5.1}% {} ({}/{})",
+            self.ratio() * 100.0,
+            self.module,
+            self.documented,
+            self.total
+        )?;
+        for symbol in &self.undocumented {
+            write!(
+                f,
+                "\n  {:?} {} at {} [symbol_id:{}]",
+                symbol.kind,
+                symbol.name,
+                symbol.location,
+                symbol.symbol_id.value()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Coverage across the index, with the per-module breakdown
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DocCoverage {
+    pub documented: usize,
+    pub total: usize,
+    /// Lowest coverage first, then by module name
+    pub modules: Vec<ModuleDocCoverage>,
+}
+
+impl DocCoverage {
+    /// Tally the public symbols among `symbols`
+    pub fn from_symbols(symbols: impl IntoIterator<Item = Symbol>) -> Self {
+        let mut public: Vec<Symbol> = symbols.into_iter().filter(counts_toward_coverage).collect();
+        public.sort_by(|a, b| {
+            a.file_path
+                .cmp(&b.file_path)
+                .then(a.range.start_line.cmp(&b.range.start_line))
+        });
+
+        let mut by_module: BTreeMap<String, ModuleDocCoverage> = BTreeMap::new();
+        for symbol in public {
+            let module = match symbol.module_path.as_deref() {
+                Some(module) if !module.is_empty() => module.to_string(),
+                _ => crate::paths::display_path(&symbol.file_path).into_owned(),
+            };
+            let entry = by_module
+                .entry(module.clone())
+                .or_insert_with(|| ModuleDocCoverage {
+                    module,
+                    documented: 0,
+                    total: 0,
+                    undocumented: Vec::new(),
+                });
+            entry.total += 1;
+            if symbol
+                .doc_comment
+                .as_deref()
+                .is_some_and(|doc| !doc.trim().is_empty())
+            {
+                entry.documented += 1;
+            } else {
+                entry.undocumented.push(UndocumentedSymbol {
+                    name: symbol.name.to_string(),
+                    kind: symbol.kind,
+                    location: SymbolContext::symbol_location(&symbol),
+                    symbol_id: symbol.id,
+                });
+            }
+        }
+
+        let mut modules: Vec<ModuleDocCoverage> = by_module.into_values().collect();
+        // Stable, so equal ratios keep module name order
+        modules.sort_by(|a, b| a.ratio().total_cmp(&b.ratio()));
+
+        Self {
+            documented: modules.iter().map(|module| module.documented).sum(),
+            total: modules.iter().map(|module| module.total).sum(),
+            modules,
+        }
+    }
+
+    /// Documented fraction, 1.0 when the index has no public symbols
+    pub fn ratio(&self) -> f64 {
+        ratio(self.documented, self.total)
+    }
+}
+
+fn counts_toward_coverage(symbol: &Symbol) -> bool {
+    symbol.visibility == Visibility::Public
+        && !matches!(
+            symbol.kind,
+            SymbolKind::Parameter
+                | SymbolKind::Field
+                | SymbolKind::EnumVariant
+                | SymbolKind::Variable
+        )
+}
+
+fn ratio(documented: usize, total: usize) -> f64 {
+    if total == 0 {
+        1.0
+    } else {
+        documented as f64 / total as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FileId, Range};
+
+    fn symbol(id: u32, name: &str, kind: SymbolKind, module: &str, doc: Option<&str>) -> Symbol {
+        let mut symbol = Symbol::new(
+            SymbolId::new(id).unwrap(),
+            name,
+            kind,
+            FileId::new(1).unwrap(),
+            Range::new(id, 0, id + 1, 0),
+        )
+        .with_visibility(Visibility::Public)
+        .with_module_path(module);
+        symbol.doc_comment = doc.map(Into::into);
+        symbol
+    }
+
+    #[test]
+    fn test_coverage_groups_public_symbols_by_module() {
+        let private = symbol(5, "helper", SymbolKind::Function, "crate::a", None)
+            .with_visibility(Visibility::Private);
+        let coverage = DocCoverage::from_symbols([
+            symbol(1, "open", SymbolKind::Function, "crate::a", Some("Opens")),
+            symbol(2, "close", SymbolKind::Function, "crate::a", None),
+            symbol(3, "Config", SymbolKind::Struct, "crate::b", Some("Config")),
+            symbol(4, "path", SymbolKind::Field, "crate::b", None),
+            symbol(6, "blank", SymbolKind::Method, "crate::b", Some("  ")),
+            private,
+        ]);
+
+        assert_eq!((coverage.documented, coverage.total), (2, 4));
+        assert_eq!(coverage.ratio(), 0.5);
+        let modules: Vec<(&str, usize, usize)> = coverage
+            .modules
+            .iter()
+            .map(|module| (module.module.as_str(), module.documented, module.total))
+            .collect();
+        assert_eq!(modules, vec![("crate::a", 1, 2), ("crate::b", 1, 2)]);
+        let undocumented: Vec<&str> = coverage
+            .modules
+            .iter()
+            .flat_map(|module| &module.undocumented)
+            .map(|symbol| symbol.name.as_str())
+            .collect();
+        assert_eq!(undocumented, vec!["close", "blank"]);
+        assert_eq!(DocCoverage::from_symbols([]).ratio(), 1.0);
+    }
+}
@@ -0,0 +1,100 @@
+//! Entry point detection: where execution likely starts
+//!
+//! Language behaviors classify symbols from their names, signatures and
+//! attributes (see `LanguageBehavior::entry_point_category`) and may
+//! report file-level script blocks. The indexer adds public functions that
+//! nothing in the index calls.
+
+use crate::SymbolId;
+use serde::Serialize;
+use std::fmt;
+
+/// Why a symbol is considered an entry point, in reporting order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryPointCategory {
+    /// Program entry such as `fn main`, `#[tokio::main]` or a `__main__` block
+    Main,
+    /// Functions exported for foreign callers, such as Rust `#[no_mangle]`
+    FfiExport,
+    /// Request handlers recognized by route attributes or naming conventions
+    HttpHandler,
+    /// Test harness entry such as Go's `TestMain`
+    TestMain,
+    /// Public functions with no indexed callers
+    Uncalled,
+}
+
+impl fmt::Display for EntryPointCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Self::Main => "Main",
+            Self::FfiExport => "FFI exports",
+            Self::HttpHandler => "HTTP handlers",
+            Self::TestMain => "Test mains",
+            Self::Uncalled => "Public functions without callers",
+        };
+        write!(f, "{label}")
+    }
+}
+
+/// One likely entry point
+#[derive(Debug, Clone, Serialize)]
+pub struct EntryPoint {
+    pub category: EntryPointCategory,
+    pub name: String,
+    /// Location as `path:line`
+    pub file_path: String,
+    /// Absent for file-level blocks that are not indexed symbols
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol_id: Option<SymbolId>,
+}
+
+impl fmt::Display for EntryPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}", self.name, self.file_path)?;
+        if let Some(id) = self.symbol_id {
+            write!(f, " [symbol_id:{}]", id.value())?;
+        }
+        Ok(())
+    }
+}
+
+/// The path an attribute line names, without arguments or delimiters:
+/// `tokio::main` for `#[tokio::main]`, `app.route` for `@app.route("/")`,
+/// `HttpGet` for `[HttpGet("items")]`, `nodiscard` for `[[nodiscard]]`.
+pub fn attribute_path(line: &str) -> &str {
+    let inner = line
+        .trim()
+        .trim_start_matches("#!")
+        .trim_start_matches(['#', '@', '['])
+        .trim_start();
+    let inner = inner.strip_prefix("unsafe(").unwrap_or(inner);
+    let end = inner
+        .find(|ch: char| !(ch.is_alphanumeric() || matches!(ch, '_' | '.' | ':' | '\\')))
+        .unwrap_or(inner.len());
+    &inner[..end]
+}
+
+/// HTTP verbs used by route attributes and handler naming conventions
+pub const HTTP_VERBS: &[&str] = &[
+    "get", "post", "put", "delete", "patch", "head", "options", "route",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attribute_path() {
+        assert_eq!(attribute_path("#[tokio::main]"), "tokio::main");
+        assert_eq!(attribute_path("#[unsafe(no_mangle)]"), "no_mangle");
+        assert_eq!(attribute_path("#[get(\"/users\")]"), "get");
+        assert_eq!(attribute_path("@app.route(\"/\")"), "app.route");
+        assert_eq!(attribute_path("[HttpGet]"), "HttpGet");
+        assert_eq!(attribute_path("[HttpGet(\"items\")]"), "HttpGet");
+        assert_eq!(attribute_path("#![allow(dead_code)]"), "allow");
+        assert_eq!(attribute_path("[[nodiscard]]"), "nodiscard");
+        assert_eq!(attribute_path("#[\\App\\Route('/')]"), "\\App\\Route");
+    }
+}
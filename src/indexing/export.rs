@@ -0,0 +1,268 @@
+//! Streaming export of the whole index
+//!
+//! Writes every symbol and relationship as JSON or JSON Lines, one record at a
+//! time, so large indexes can be dumped without building the document in memory.
+//! Records come in id order, so exporting an unchanged index gives the same output.
+//! The SQLite format is handled by [`super::sqlite_export`].
+
+use crate::error::IndexError;
+use crate::storage::StorageError;
+use crate::{RelationKind, Symbol, SymbolId};
+use serde::Serialize;
+use std::fmt;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+/// Output format for `codanna export`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// A single document: `{"symbols": [...], "relationships": [...]}`
+    Json,
+    /// One tagged record per line, symbols first, then relationships
+    Jsonl,
+    /// A SQLite database with `files`, `symbols` and `relationships` tables
+    Sqlite,
+}
+
+impl ExportFormat {
+    /// True for formats written as a stream of text records
+    pub fn is_streaming(&self) -> bool {
+        matches!(self, Self::Json | Self::Jsonl)
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Self::Json),
+            "jsonl" => Ok(Self::Jsonl),
+            "sqlite" => Ok(Self::Sqlite),
+            other => Err(format!(
+                "Unknown export format '{other}'. Expected 'json', 'jsonl' or 'sqlite'"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json => write!(f, "json"),
+            Self::Jsonl => write!(f, "jsonl"),
+            Self::Sqlite => write!(f, "sqlite"),
+        }
+    }
+}
+
+/// A relationship edge as written to the export
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedRelationship {
+    pub from: SymbolId,
+    pub to: SymbolId,
+    pub kind: RelationKind,
+}
+
+/// Number of records written by an export
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExportStats {
+    pub symbols: usize,
+    pub relationships: usize,
+}
+
+/// Tagged record for JSON Lines output
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum Record<'a> {
+    Symbol(&'a Symbol),
+    Relationship(&'a ExportedRelationship),
+}
+
+/// Incremental writer for an index export
+///
+/// Only streaming formats are supported. Symbols must all be written before the
+/// first relationship; `finish` closes the document and flushes the writer.
+pub struct IndexExporter<W: Write> {
+    writer: W,
+    format: ExportFormat,
+    stats: ExportStats,
+    in_relationships: bool,
+}
+
+impl<W: Write> IndexExporter<W> {
+    pub fn new(writer: W, format: ExportFormat) -> io::Result<Self> {
+        if !format.is_streaming() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("'{format}' export cannot be written to a stream"),
+            ));
+        }
+
+        let mut exporter = Self {
+            writer,
+            format,
+            stats: ExportStats::default(),
+            in_relationships: false,
+        };
+        if format == ExportFormat::Json {
+            exporter.writer.write_all(b"{\"symbols\":[")?;
+        }
+        Ok(exporter)
+    }
+
+    pub fn write_symbol(&mut self, symbol: &Symbol) -> io::Result<()> {
+        debug_assert!(!self.in_relationships, "symbols must precede relationships");
+        match self.format {
+            ExportFormat::Json => {
+                if self.stats.symbols > 0 {
+                    self.writer.write_all(b",")?;
+                }
+                serde_json::to_writer(&mut self.writer, symbol)?;
+            }
+            ExportFormat::Jsonl => {
+                serde_json::to_writer(&mut self.writer, &Record::Symbol(symbol))?;
+                self.writer.write_all(b"\n")?;
+            }
+            ExportFormat::Sqlite => unreachable!("rejected in IndexExporter::new"),
+        }
+        self.stats.symbols += 1;
+        Ok(())
+    }
+
+    pub fn write_relationship(&mut self, relationship: &ExportedRelationship) -> io::Result<()> {
+        self.start_relationships()?;
+        match self.format {
+            ExportFormat::Json => {
+                if self.stats.relationships > 0 {
+                    self.writer.write_all(b",")?;
+                }
+                serde_json::to_writer(&mut self.writer, relationship)?;
+            }
+            ExportFormat::Jsonl => {
+                serde_json::to_writer(&mut self.writer, &Record::Relationship(relationship))?;
+                self.writer.write_all(b"\n")?;
+            }
+            ExportFormat::Sqlite => unreachable!("rejected in IndexExporter::new"),
+        }
+        self.stats.relationships += 1;
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> io::Result<ExportStats> {
+        self.start_relationships()?;
+        if self.format == ExportFormat::Json {
+            self.writer.write_all(b"]}\n")?;
+        }
+        self.writer.flush()?;
+        Ok(self.stats)
+    }
+
+    fn start_relationships(&mut self) -> io::Result<()> {
+        if !self.in_relationships {
+            self.in_relationships = true;
+            if self.format == ExportFormat::Json {
+                self.writer.write_all(b"],\"relationships\":[")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Map a storage failure during export to an indexer error
+///
+/// Write failures are reported as such rather than as Tantivy errors.
+pub(crate) fn export_error(e: StorageError) -> IndexError {
+    match e {
+        StorageError::Io(e) => IndexError::General(format!("Failed to write export: {e}")),
+        StorageError::General(message) => IndexError::General(message),
+        e => IndexError::TantivyError {
+            operation: "export".to_string(),
+            cause: e.to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FileId, Range, SymbolKind};
+
+    fn export(format: ExportFormat) -> (String, ExportStats) {
+        let symbol = Symbol::new(
+            SymbolId::new(1).unwrap(),
+            "main",
+            SymbolKind::Function,
+            FileId::new(1).unwrap(),
+            Range::new(1, 0, 3, 1),
+        );
+        let edge = ExportedRelationship {
+            from: SymbolId::new(1).unwrap(),
+            to: SymbolId::new(2).unwrap(),
+            kind: RelationKind::Calls,
+        };
+
+        let mut out = Vec::new();
+        let mut exporter = IndexExporter::new(&mut out, format).unwrap();
+        exporter.write_symbol(&symbol).unwrap();
+        exporter.write_relationship(&edge).unwrap();
+        let stats = exporter.finish().unwrap();
+        (String::from_utf8(out).unwrap(), stats)
+    }
+
+    #[test]
+    fn test_json_export_is_single_document() {
+        let (out, stats) = export(ExportFormat::Json);
+        assert_eq!(
+            stats,
+            ExportStats {
+                symbols: 1,
+                relationships: 1
+            }
+        );
+
+        let value: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(value["symbols"][0]["name"], "main");
+        assert_eq!(value["symbols"][0]["kind"], "Function");
+        assert_eq!(value["relationships"][0]["from"], 1);
+        assert_eq!(value["relationships"][0]["to"], 2);
+        assert_eq!(value["relationships"][0]["kind"], "Calls");
+    }
+
+    #[test]
+    fn test_jsonl_export_tags_each_line() {
+        let (out, _) = export(ExportFormat::Jsonl);
+        let lines: Vec<serde_json::Value> = out
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["type"], "symbol");
+        assert_eq!(lines[0]["name"], "main");
+        assert_eq!(lines[1]["type"], "relationship");
+        assert_eq!(lines[1]["kind"], "Calls");
+    }
+
+    #[test]
+    fn test_sqlite_format_is_not_streamed() {
+        let err = IndexExporter::new(Vec::new(), ExportFormat::Sqlite)
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_empty_json_export() {
+        let mut out = Vec::new();
+        let stats = IndexExporter::new(&mut out, ExportFormat::Json)
+            .unwrap()
+            .finish()
+            .unwrap();
+        assert_eq!(stats, ExportStats::default());
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "{\"symbols\":[],\"relationships\":[]}\n"
+        );
+    }
+}
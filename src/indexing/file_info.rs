@@ -3,7 +3,7 @@
 //! This module provides hash-based tracking of indexed files to enable
 //! efficient incremental updates.
 
-use crate::FileId;
+use crate::{FileId, Range};
 use chrono::Utc;
 use sha2::{Digest, Sha256};
 use std::path::PathBuf;
@@ -45,6 +45,38 @@ pub fn calculate_hash(content: &str) -> String {
     format!("{:x}", hasher.finalize())
 }
 
+/// Calculate SHA256 hash of the source text covered by `range`
+///
+/// Used to anchor symbols by content so they can be matched across re-indexes
+/// even when their line numbers shift. Returns `None` if the range falls
+/// outside `content` or splits a UTF-8 character.
+pub fn calculate_range_hash(content: &str, range: &Range) -> Option<String> {
+    LineStarts::new(content).range_hash(content, range)
+}
+
+/// Byte offset of every line start in a file
+///
+/// Built once per file so hashing each symbol's range is a lookup instead
+/// of a scan from the top, which matters for files with many symbols.
+#[derive(Debug, Clone)]
+pub struct LineStarts(Vec<usize>);
+
+impl LineStarts {
+    pub fn new(content: &str) -> Self {
+        let starts = std::iter::once(0)
+            .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self(starts)
+    }
+
+    /// Same as `calculate_range_hash`, for the content these offsets were built from
+    pub fn range_hash(&self, content: &str, range: &Range) -> Option<String> {
+        let start = self.0.get(range.start_line as usize)? + range.start_column as usize;
+        let end = self.0.get(range.end_line as usize)? + range.end_column as usize;
+        content.get(start..end).map(calculate_hash)
+    }
+}
+
 /// Get current UTC timestamp in seconds since UNIX_EPOCH
 pub fn get_utc_timestamp() -> u64 {
     // Use chrono for accurate cross-platform timestamp
@@ -55,6 +87,32 @@ pub fn get_utc_timestamp() -> u64 {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_range_hash_survives_line_shift() {
+        let before = "fn a() {}\nfn b() {\n    1\n}\n";
+        let after = "// new comment\n\nfn a() {}\nfn b() {\n    1\n}\n";
+
+        let old_hash = calculate_range_hash(before, &Range::new(1, 0, 3, 1));
+        let new_hash = calculate_range_hash(after, &Range::new(3, 0, 5, 1));
+        assert!(old_hash.is_some());
+        assert_eq!(old_hash, new_hash);
+        assert_eq!(old_hash, Some(calculate_hash("fn b() {\n    1\n}")));
+
+        // Out-of-bounds ranges produce no hash
+        assert_eq!(
+            calculate_range_hash(before, &Range::new(10, 0, 11, 0)),
+            None
+        );
+
+        // Shared offsets agree with the one-off hash for every range
+        let starts = LineStarts::new(after);
+        assert_eq!(starts.range_hash(after, &Range::new(3, 0, 5, 1)), new_hash);
+        assert_eq!(
+            starts.range_hash(after, &Range::new(0, 0, 0, 14)),
+            Some(calculate_hash("// new comment"))
+        );
+    }
+
     #[test]
     fn test_hash_calculation() {
         let content1 = "Hello, World!";
@@ -3,8 +3,10 @@
 //! This module implements the "watch what you indexed" philosophy:
 //! - Only watches files that are already in the index
 //! - Ignores all other files, even in watched directories
-//! - No auto-indexing of new files (by design)
+//! - No auto-indexing of new files (by design); a renamed indexed file is
+//!   treated as a delete of the old path and an add of the new one
 
+use notify::event::ModifyKind;
 use notify::{Event, EventKind, RecursiveMode, Watcher};
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
@@ -127,16 +129,16 @@ impl FileSystemWatcher {
         let paths = indexer.get_all_indexed_paths();
 
         if paths.is_empty() {
-            eprintln!("No indexed files found in the index");
+            crate::log_info!("No indexed files found in the index");
         } else {
-            eprintln!("Found {} indexed files to watch", paths.len());
+            crate::log_info!("Found {} indexed files to watch", paths.len());
             // Show detailed file list only when mcp_debug is true
             if self.mcp_debug {
                 for (i, path) in paths.iter().take(3).enumerate() {
-                    eprintln!("  [{}] {}", i + 1, path.display());
+                    crate::log_info!("  [{}] {}", i + 1, path.display());
                 }
                 if paths.len() > 3 {
-                    eprintln!("  ... and {} more", paths.len() - 3);
+                    crate::log_info!("  ... and {} more", paths.len() - 3);
                 }
             }
         }
@@ -156,11 +158,11 @@ impl FileSystemWatcher {
         let indexed_paths = self.get_indexed_paths().await;
 
         if indexed_paths.is_empty() {
-            eprintln!("Warning: No indexed files found. File watcher has nothing to watch.");
-            eprintln!("Run 'codanna index <path>' first to index some files.");
+            crate::log_warn!("Warning: No indexed files found. File watcher has nothing to watch.");
+            crate::log_info!("Run 'codanna index <path>' first to index some files.");
             // Still continue - maybe files will be indexed later
         } else {
-            eprintln!(
+            crate::log_info!(
                 "File watcher: Monitoring {} indexed files for changes",
                 indexed_paths.len()
             );
@@ -180,7 +182,7 @@ impl FileSystemWatcher {
         let watch_dirs = Self::compute_watch_dirs(&indexed_paths);
 
         if !watch_dirs.is_empty() {
-            eprintln!(
+            crate::log_info!(
                 "Watching {} directories containing indexed files",
                 watch_dirs.len()
             );
@@ -201,11 +203,11 @@ impl FileSystemWatcher {
             {
                 Ok(_) => {
                     if self.mcp_debug {
-                        eprintln!("  Watching: {}", watch_path.display());
+                        crate::log_info!("  Watching: {}", watch_path.display());
                     }
                 }
                 Err(e) => {
-                    eprintln!("  Warning: Failed to watch {}: {}", watch_path.display(), e);
+                    crate::log_warn!("  Warning: Failed to watch {}: {}", watch_path.display(), e);
                     // Continue with other directories
                 }
             }
@@ -231,11 +233,11 @@ impl FileSystemWatcher {
         // 6. Subscribe to broadcast notifications if broadcaster is available
         let mut broadcast_receiver = self.broadcaster.as_ref().map(|b| b.subscribe());
         if broadcast_receiver.is_some() {
-            eprintln!("File watcher subscribed to index reload notifications");
+            crate::log_info!("File watcher subscribed to index reload notifications");
         }
 
         // 7. Event handling loop with debouncing
-        eprintln!("File watcher started. Press Ctrl+C to stop.");
+        crate::log_info!("File watcher started. Press Ctrl+C to stop.");
 
         loop {
             // Use timeout to periodically process pending changes
@@ -249,59 +251,45 @@ impl FileSystemWatcher {
                         Ok(event) => {
                             // Handle different event types for indexed files
                             for path in &event.paths {
-                                if indexed_set.contains(path) {
-                                    match event.kind {
-                                        EventKind::Modify(_) => {
-                                            // Record this change with current timestamp
-                                            pending_changes.insert(path.clone(), Instant::now());
-                                        }
-                                        EventKind::Remove(_) => {
-                                            // File was deleted - remove it from index immediately
-                                            let path_display = path.display();
-                                            eprintln!("Detected deletion of indexed file: {path_display}");
-                                            eprintln!("  Removing from index...");
-
-                                            // Convert absolute path to relative path for the index
-                                            let relative_path = if path.is_absolute() {
-                                                if let Ok(cwd) = std::env::current_dir() {
-                                                    match path.strip_prefix(&cwd) {
-                                                        Ok(rel) => rel.to_path_buf(),
-                                                        Err(_) => path.clone(),
-                                                    }
-                                                } else {
-                                                    path.clone()
-                                                }
-                                            } else {
-                                                path.clone()
-                                            };
-
-                                            let relative_display = relative_path.display();
-                                            eprintln!("  Using relative path: {relative_display}");
+                                // Renames are a delete of the old path plus an add of
+                                // the new one; backends differ in how they report the
+                                // two sides, so existence decides which side this is
+                                let renamed =
+                                    matches!(event.kind, EventKind::Modify(ModifyKind::Name(_)));
+
+                                if !indexed_set.contains(path) {
+                                    if renamed && path.is_file() && self.supports_file(path).await {
+                                        crate::log_info!(file = path.display().to_string(); "Detected indexed file renamed to: {}", path.display());
+                                        indexed_set.insert(path.clone());
+                                        pending_changes.insert(path.clone(), Instant::now());
+                                    }
+                                    continue;
+                                }
 
-                                            let mut indexer = self.indexer.write().await;
-                                            if let Err(e) = indexer.remove_file(&relative_path) {
-                                                eprintln!("  ✗ Failed to remove from index: {e}");
-                                            } else {
-                                                eprintln!("  ✓ Removed from index successfully");
-
-                                                // Send notification to MCP clients
-                                                if let Some(ref broadcaster) = self.broadcaster {
-                                                    if self.mcp_debug {
-                                                        eprintln!("DEBUG: Sending FileDeleted notification for: {}", path.display());
-                                                    }
-                                                    broadcaster.send(FileChangeEvent::FileDeleted {
-                                                        path: path.clone(),
-                                                    });
-                                                }
-                                            }
-                                        }
-                                        _ => {} // Ignore other event types
+                                match event.kind {
+                                    EventKind::Modify(ModifyKind::Name(_)) if !path.exists() => {
+                                        crate::log_info!(file = path.display().to_string(); "Detected rename of indexed file: {}", path.display());
+                                        indexed_set.remove(path);
+                                        pending_changes.remove(path);
+                                        self.remove_deleted_file(path).await;
                                     }
+                                    EventKind::Modify(_) => {
+                                        // Record this change with current timestamp
+                                        pending_changes.insert(path.clone(), Instant::now());
+                                    }
+                                    EventKind::Remove(_) => {
+                                        // File was deleted - remove it from index immediately
+                                        crate::log_info!(file = path.display().to_string(); "Detected deletion of indexed file: {}", path.display());
+                                        indexed_set.remove(path);
+                                        pending_changes.remove(path);
+                                        self.remove_deleted_file(path).await;
+                                    }
+                                    _ => {} // Ignore other event types
                                 }
                             }
                         }
                         Err(e) => {
-                            eprintln!("File watch error: {e}");
+                            crate::log_error!("File watch error: {e}");
                         }
                     }
                 }
@@ -323,48 +311,66 @@ impl FileSystemWatcher {
 
                     // Process debounced files
                     for path in files_to_process {
-                        eprintln!("Detected change in indexed file: {}", path.display());
-                        eprintln!("  Re-indexing...");
+                        let file = path.display().to_string();
+                        crate::log_info!(file = file.as_str(); "Detected change in indexed file: {file}");
+                        crate::log_info!("  Re-indexing...");
 
-                        eprintln!("  Using absolute path for file reading: {}", path.display());
+                        crate::log_info!("  Using absolute path for file reading: {}", path.display());
 
                         let mut indexer = self.indexer.write().await;
-                        match indexer.index_file(&path) {
+                        let started = Instant::now();
+                        let outcome = indexer.index_file(&path);
+                        let duration_ms = started.elapsed().as_millis() as u64;
+                        match outcome {
                             Ok(result) => {
                                 use crate::IndexingResult;
                                 match result {
                                     IndexingResult::Indexed(_) => {
-                                        eprintln!("  ✓ Re-indexed successfully (file updated)");
+                                        crate::log_info!(
+                                            file = file.as_str(),
+                                            duration_ms = duration_ms;
+                                            "  ✓ Re-indexed successfully (file updated)"
+                                        );
 
                                         // CRITICAL: Save semantic search data after re-indexing
                                         if indexer.has_semantic_search() {
                                             let semantic_path = self.index_path.join("semantic");
                                             if let Err(e) = indexer.save_semantic_search(&semantic_path) {
-                                                eprintln!("  ✗ Failed to save semantic search after re-indexing: {e}");
+                                                crate::log_error!("  ✗ Failed to save semantic search after re-indexing: {e}");
                                             } else {
-                                                eprintln!("  ✓ Semantic search saved successfully");
+                                                crate::log_info!("  ✓ Semantic search saved successfully");
                                             }
                                         }
 
                                         // Send notification if broadcaster is available
                                         if let Some(ref broadcaster) = self.broadcaster {
                                             if self.mcp_debug {
-                                                eprintln!("DEBUG: FileSystemWatcher sending notification for: {}", path.display());
+                                                crate::log_debug!("FileSystemWatcher sending notification for: {}", path.display());
                                             }
                                             broadcaster.send(FileChangeEvent::FileReindexed {
                                                 path: path.clone(),
                                             });
                                         } else if self.mcp_debug {
-                                            eprintln!("DEBUG: No broadcaster available to send notification");
+                                            crate::log_debug!("No broadcaster available to send notification");
                                         }
                                     }
                                     IndexingResult::Cached(_) => {
-                                        eprintln!("  ✓ File unchanged (hash match, skipped)");
+                                        crate::log_info!(
+                                            file = file.as_str(),
+                                            duration_ms = duration_ms;
+                                            "  ✓ File unchanged (hash match, skipped)"
+                                        );
                                     }
                                 }
                             }
+                            Err(
+                                e @ (crate::IndexError::BinaryContent { .. }
+                                | crate::IndexError::ParseTimeout { .. }),
+                            ) => {
+                                crate::log_info!(file = file.as_str(); "  - {e}");
+                            }
                             Err(e) => {
-                                eprintln!("  ✗ Re-index failed: {e}");
+                                crate::log_error!(file = file.as_str(); "  ✗ Re-index failed: {e}");
                             }
                         }
                     }
@@ -379,8 +385,8 @@ impl FileSystemWatcher {
                 } => {
                     match event {
                         FileChangeEvent::IndexReloaded => {
-                            eprintln!("File watcher received IndexReloaded notification");
-                            eprintln!("  Refreshing watched file list...");
+                            crate::log_info!("File watcher received IndexReloaded notification");
+                            crate::log_info!("  Refreshing watched file list...");
 
                             // Get the updated list of indexed files
                             let new_indexed_paths = self.get_indexed_paths().await;
@@ -403,11 +409,11 @@ impl FileSystemWatcher {
 
                             if !added.is_empty() {
                                 let added_count = added.len();
-                                eprintln!("  Added {added_count} new files to watch");
+                                crate::log_info!("  Added {added_count} new files to watch");
                                 if self.mcp_debug {
                                     for path in &added {
                                         let path_display = path.display();
-                                        eprintln!("    + {path_display}");
+                                        crate::log_info!("    + {path_display}");
                                     }
                                 }
 
@@ -416,18 +422,18 @@ impl FileSystemWatcher {
                                 for watch_path in new_dirs {
                                     if let Err(e) = self._watcher.watch(&watch_path, RecursiveMode::NonRecursive) {
                                         let watch_display = watch_path.display();
-                                        eprintln!("  Warning: Failed to watch {watch_display}: {e}");
+                                        crate::log_warn!("  Warning: Failed to watch {watch_display}: {e}");
                                     }
                                 }
                             }
 
                             if !removed.is_empty() {
                                 let removed_count = removed.len();
-                                eprintln!("  Removed {removed_count} files from watch");
+                                crate::log_info!("  Removed {removed_count} files from watch");
                                 if self.mcp_debug {
                                     for path in &removed {
                                         let path_display = path.display();
-                                        eprintln!("    - {path_display}");
+                                        crate::log_info!("    - {path_display}");
                                     }
                                 }
                             }
@@ -435,7 +441,7 @@ impl FileSystemWatcher {
                             // Update the indexed set
                             indexed_set = new_indexed_set;
                             let total_files = indexed_set.len();
-                            eprintln!("  ✓ Now watching {total_files} files");
+                            crate::log_info!("  ✓ Now watching {total_files} files");
                         }
                         _ => {
                             // Ignore other event types
@@ -453,6 +459,56 @@ impl FileSystemWatcher {
         Ok(())
     }
 
+    /// Whether the indexer can parse `path` (used for rename targets)
+    async fn supports_file(&self, path: &Path) -> bool {
+        self.indexer.read().await.supports_file(path)
+    }
+
+    /// Purge a deleted or renamed-away file from the index and notify clients
+    async fn remove_deleted_file(&self, path: &Path) {
+        crate::log_info!("  Removing from index...");
+
+        // Convert absolute path to relative path for the index
+        let relative_path = if path.is_absolute() {
+            if let Ok(cwd) = std::env::current_dir() {
+                match path.strip_prefix(&cwd) {
+                    Ok(rel) => rel.to_path_buf(),
+                    Err(_) => path.to_path_buf(),
+                }
+            } else {
+                path.to_path_buf()
+            }
+        } else {
+            path.to_path_buf()
+        };
+
+        let relative_display = relative_path.display();
+        crate::log_info!("  Using relative path: {relative_display}");
+
+        let file = relative_path.display().to_string();
+        let mut indexer = self.indexer.write().await;
+        let started = Instant::now();
+        if let Err(e) = indexer.remove_file(&relative_path) {
+            crate::log_error!(file = file.as_str(); "  ✗ Failed to remove from index: {e}");
+            return;
+        }
+        crate::log_info!(
+            file = file.as_str(),
+            duration_ms = started.elapsed().as_millis() as u64;
+            "  ✓ Removed from index successfully"
+        );
+
+        // Send notification to MCP clients
+        if let Some(ref broadcaster) = self.broadcaster {
+            if self.mcp_debug {
+                crate::log_debug!("Sending FileDeleted notification for: {}", path.display());
+            }
+            broadcaster.send(FileChangeEvent::FileDeleted {
+                path: path.to_path_buf(),
+            });
+        }
+    }
+
     /// Compute minimal set of directories to watch
     ///
     /// Given a list of file paths, returns the unique parent directories
@@ -0,0 +1,147 @@
+//! Detection of machine-generated source files
+//!
+//! Protobuf stubs, ORM models and similar generator output are usually large,
+//! repetitive and never edited by hand, so they crowd search results without
+//! adding much. A file counts as generated when its name matches a well-known
+//! generator suffix, or when one of its first lines carries a generator marker.
+//! Directory indexing skips these files unless `indexing.include_generated`
+//! is set.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// File name suffixes produced by common code generators
+const GENERATED_SUFFIXES: &[&str] = &[
+    ".pb.go",
+    ".pb.gw.go",
+    ".pb.cc",
+    ".pb.h",
+    "_pb2.py",
+    "_pb2_grpc.py",
+    "_pb2.pyi",
+    "_generated.rs",
+    "_generated.go",
+    ".g.dart",
+    ".freezed.dart",
+    ".g.cs",
+    ".designer.cs",
+];
+
+/// Only markers near the top of a file count; later mentions are just text
+const HEADER_LINES: usize = 10;
+const HEADER_BYTES: u64 = 2048;
+
+/// Why a file counts as generated
+pub fn generated_reason(path: &Path) -> Option<String> {
+    if let Some(reason) = suffix_reason(path) {
+        return Some(reason);
+    }
+
+    let mut header = Vec::new();
+    File::open(path)
+        .and_then(|file| file.take(HEADER_BYTES).read_to_end(&mut header))
+        .ok()?;
+    marker_reason(&String::from_utf8_lossy(&header))
+}
+
+/// Why content already in memory, such as an archive entry, counts as generated
+pub fn generated_content_reason(path: &Path, content: &str) -> Option<String> {
+    suffix_reason(path).or_else(|| marker_reason(content))
+}
+
+fn suffix_reason(path: &Path) -> Option<String> {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .and_then(generated_suffix)
+        .map(|suffix| format!("file name matches *{suffix}"))
+}
+
+fn marker_reason(header: &str) -> Option<String> {
+    has_generated_marker(header).then(|| "generated-code header".to_string())
+}
+
+/// The generator suffix `file_name` ends with, if any
+fn generated_suffix(file_name: &str) -> Option<&'static str> {
+    let lower = file_name.to_ascii_lowercase();
+    GENERATED_SUFFIXES
+        .iter()
+        .copied()
+        .find(|suffix| lower.ends_with(suffix))
+}
+
+/// Whether the first lines of `header` carry a generator marker
+///
+/// Recognises `@generated` (Meta tooling, Rust build scripts), Go's
+/// `Code generated ... DO NOT EDIT.` and .NET `<auto-generated>` comments.
+fn has_generated_marker(header: &str) -> bool {
+    header.lines().take(HEADER_LINES).any(|line| {
+        line.contains(concat!("@", "generated"))
+            || line.contains(concat!("<auto", "-generated"))
+            || (line.contains("Code generated") && line.contains("DO NOT EDIT"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_generated_suffix() {
+        assert_eq!(generated_suffix("api.pb.go"), Some(".pb.go"));
+        assert_eq!(
+            generated_suffix("schema_generated.rs"),
+            Some("_generated.rs")
+        );
+        assert_eq!(generated_suffix("user.g.dart"), Some(".g.dart"));
+        assert_eq!(generated_suffix("Form1.Designer.cs"), Some(".designer.cs"));
+        assert_eq!(generated_suffix("service_pb2.py"), Some("_pb2.py"));
+
+        assert_eq!(generated_suffix("main.go"), None);
+        assert_eq!(generated_suffix("generated.rs"), None);
+        assert_eq!(generated_suffix("pb.go"), None);
+    }
+
+    #[test]
+    fn test_generated_marker() {
+        let at_generated = format!("// {}{} by build.rs\nfn main() {{}}\n", "@", "generated");
+        assert!(has_generated_marker(&at_generated));
+        assert!(has_generated_marker(
+            "// Code generated by protoc-gen-go. DO NOT EDIT.\npackage api\n"
+        ));
+        assert!(has_generated_marker(
+            "// <auto-generated>\n//   This code was generated by a tool.\n"
+        ));
+
+        assert!(!has_generated_marker(
+            "// Code generated files live elsewhere\n"
+        ));
+        assert!(!has_generated_marker("fn main() {}\n"));
+
+        let late = format!("{}// {}{}\n", "\n".repeat(HEADER_LINES), "@", "generated");
+        assert!(!has_generated_marker(&late));
+    }
+
+    #[test]
+    fn test_generated_reason() {
+        let temp = TempDir::new().unwrap();
+        let by_name = temp.path().join("api.pb.go");
+        let by_header = temp.path().join("models.py");
+        let handwritten = temp.path().join("lib.rs");
+        std::fs::write(&by_name, "package api\n").unwrap();
+        std::fs::write(&by_header, format!("# {}{}\n", "@", "generated")).unwrap();
+        std::fs::write(&handwritten, "pub fn parse() {}\n").unwrap();
+
+        assert_eq!(
+            generated_reason(&by_name).as_deref(),
+            Some("file name matches *.pb.go")
+        );
+        assert_eq!(
+            generated_reason(&by_header).as_deref(),
+            Some("generated-code header")
+        );
+        assert_eq!(generated_reason(&handwritten), None);
+        assert_eq!(generated_reason(&temp.path().join("missing.rs")), None);
+    }
+}
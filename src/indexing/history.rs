@@ -0,0 +1,261 @@
+//! Git history for a symbol's line range
+//!
+//! Blames the lines a symbol spans to report the last commit that touched
+//! them and how many distinct commits the current lines come from, a quick
+//! signal of how volatile the code is. The working copy is blamed, so line
+//! numbers match the index even with local edits; edited lines count as
+//! uncommitted.
+
+use chrono::DateTime;
+use git2::{Oid, Repository};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Reasons history cannot be reported
+#[derive(Error, Debug)]
+pub enum HistoryError {
+    #[error("History unavailable: {path} is not inside a git repository")]
+    NotARepository { path: PathBuf },
+
+    #[error("History unavailable: {path} is not tracked by git")]
+    Untracked { path: PathBuf },
+
+    #[error("Failed to read git history for {path}: {reason}")]
+    Git { path: PathBuf, reason: String },
+}
+
+/// Last change to a line range
+#[derive(Debug, Clone, Serialize)]
+pub struct LineHistory {
+    pub file_path: String,
+    /// First line of the range (1-based)
+    pub start_line: u32,
+    /// Last line of the range (1-based, inclusive)
+    pub end_line: u32,
+    /// Most recent commit touching the range; `None` when every line is uncommitted
+    pub last_commit: Option<CommitInfo>,
+    /// Distinct commits the committed lines come from
+    pub commit_count: usize,
+    /// Lines edited in the working copy but not committed yet
+    pub uncommitted_lines: usize,
+}
+
+/// A commit as reported by blame
+#[derive(Debug, Clone, Serialize)]
+pub struct CommitInfo {
+    pub id: String,
+    pub author: String,
+    pub email: String,
+    /// Author date as RFC 3339 (UTC)
+    pub date: String,
+    /// Author date as seconds since the Unix epoch
+    pub timestamp: i64,
+    pub summary: String,
+}
+
+impl fmt::Display for LineHistory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}-{}",
+            self.file_path, self.start_line, self.end_line
+        )?;
+        match &self.last_commit {
+            Some(commit) => {
+                let short_id = &commit.id[..commit.id.len().min(10)];
+                let age = crate::mcp::format_relative_time(commit.timestamp.max(0) as u64);
+                write!(
+                    f,
+                    "\n  Last changed: {short_id} by {} <{}> on {} ({age})",
+                    commit.author, commit.email, commit.date
+                )?;
+                write!(f, "\n  Summary: {}", commit.summary)?;
+            }
+            None => write!(f, "\n  Last changed: never committed")?,
+        }
+        write!(f, "\n  Commits touching these lines: {}", self.commit_count)?;
+        if self.uncommitted_lines > 0 {
+            write!(f, "\n  Uncommitted lines: {}", self.uncommitted_lines)?;
+        }
+        Ok(())
+    }
+}
+
+/// Blame lines `start_line..=end_line` (1-based) of `path` in its repository
+///
+/// `display_path` is the path reported back, usually the one stored in the index.
+pub fn line_history(
+    path: &Path,
+    display_path: &str,
+    start_line: u32,
+    end_line: u32,
+) -> Result<LineHistory, HistoryError> {
+    let git_error = |e: git2::Error| HistoryError::Git {
+        path: path.to_path_buf(),
+        reason: e.message().to_string(),
+    };
+
+    let absolute = path.canonicalize().map_err(|e| HistoryError::Git {
+        path: path.to_path_buf(),
+        reason: e.to_string(),
+    })?;
+    let repo = absolute
+        .parent()
+        .and_then(|dir| Repository::discover(dir).ok())
+        .ok_or_else(|| HistoryError::NotARepository {
+            path: path.to_path_buf(),
+        })?;
+    let workdir = repo
+        .workdir()
+        .and_then(|dir| dir.canonicalize().ok())
+        .ok_or_else(|| HistoryError::NotARepository {
+            path: path.to_path_buf(),
+        })?;
+    let relative = absolute
+        .strip_prefix(&workdir)
+        .map_err(|_| HistoryError::NotARepository {
+            path: path.to_path_buf(),
+        })?;
+
+    let blame = repo.blame_file(relative, None).map_err(|e| {
+        if e.code() == git2::ErrorCode::NotFound {
+            HistoryError::Untracked {
+                path: path.to_path_buf(),
+            }
+        } else {
+            git_error(e)
+        }
+    })?;
+    let content = std::fs::read(&absolute).map_err(|e| HistoryError::Git {
+        path: path.to_path_buf(),
+        reason: e.to_string(),
+    })?;
+    let blame = blame.blame_buffer(&content).map_err(git_error)?;
+
+    let start_line = start_line.max(1);
+    let end_line = end_line.max(start_line);
+    let mut commits: HashSet<Oid> = HashSet::new();
+    let mut uncommitted_lines = 0;
+    let mut latest: Option<(i64, Oid)> = None;
+
+    for hunk in blame.iter() {
+        let hunk_start = hunk.final_start_line() as u32;
+        let hunk_end = hunk_start + (hunk.lines_in_hunk() as u32).saturating_sub(1);
+        if hunk_end < start_line || hunk_start > end_line {
+            continue;
+        }
+        let overlap = hunk_end.min(end_line) - hunk_start.max(start_line) + 1;
+
+        let id = hunk.final_commit_id();
+        if id.is_zero() {
+            uncommitted_lines += overlap as usize;
+            continue;
+        }
+        commits.insert(id);
+
+        let time = hunk.final_signature().when().seconds();
+        if latest.is_none_or(|(latest_time, _)| time > latest_time) {
+            latest = Some((time, id));
+        }
+    }
+
+    let last_commit = match latest {
+        Some((_, id)) => {
+            let commit = repo.find_commit(id).map_err(git_error)?;
+            let author = commit.author();
+            let timestamp = author.when().seconds();
+            Some(CommitInfo {
+                id: id.to_string(),
+                author: author.name().unwrap_or("unknown").to_string(),
+                email: author.email().unwrap_or("").to_string(),
+                date: DateTime::from_timestamp(timestamp, 0)
+                    .map(|date| date.to_rfc3339())
+                    .unwrap_or_default(),
+                timestamp,
+                summary: commit.summary().unwrap_or("").to_string(),
+            })
+        }
+        None => None,
+    };
+
+    Ok(LineHistory {
+        file_path: display_path.to_string(),
+        start_line,
+        end_line,
+        last_commit,
+        commit_count: commits.len(),
+        uncommitted_lines,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Signature;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn commit_all(repo: &Repository, message: &str, time: i64) {
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature =
+            Signature::new("Ada", "ada@example.com", &git2::Time::new(time, 0)).unwrap();
+        let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_line_history_reports_latest_commit_in_range() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        let file = temp_dir.path().join("lib.rs");
+
+        fs::write(&file, "fn a() {}\nfn b() {}\nfn c() {}\n").unwrap();
+        commit_all(&repo, "Add functions", 1_700_000_000);
+        fs::write(&file, "fn a() {}\nfn b() { todo!() }\nfn c() {}\n").unwrap();
+        commit_all(&repo, "Change b", 1_700_100_000);
+        fs::write(&file, "fn a() {}\nfn b() { todo!() }\nfn c() { 1 }\n").unwrap();
+
+        let history = line_history(&file, "lib.rs", 1, 2).unwrap();
+        let commit = history.last_commit.unwrap();
+        assert_eq!(commit.summary, "Change b");
+        assert_eq!(commit.author, "Ada");
+        assert_eq!(history.commit_count, 2);
+        assert_eq!(history.uncommitted_lines, 0);
+
+        let edited = line_history(&file, "lib.rs", 3, 3).unwrap();
+        assert!(edited.last_commit.is_none());
+        assert_eq!(edited.uncommitted_lines, 1);
+    }
+
+    #[test]
+    fn test_line_history_outside_git() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("lib.rs");
+        fs::write(&file, "fn a() {}\n").unwrap();
+
+        // A TempDir could sit inside a repository; only assert when it does not
+        if Repository::discover(temp_dir.path()).is_err() {
+            assert!(matches!(
+                line_history(&file, "lib.rs", 1, 1),
+                Err(HistoryError::NotARepository { .. })
+            ));
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! Lookup of the definition behind an imported name
+//!
+//! Uses the same resolution context as relationship resolution, so the answer
+//! matches how the indexer links calls through imports.
+
+use crate::Symbol;
+use serde::Serialize;
+use std::fmt;
+
+/// Outcome of resolving a name imported by a file
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ImportResolution {
+    /// The import points to a single indexed symbol
+    Resolved { import_path: String, symbol: Symbol },
+    /// The import path did not resolve; the only indexed symbol with the
+    /// imported name is taken as its definition
+    Heuristic { import_path: String, symbol: Symbol },
+    /// Several indexed symbols could be the definition
+    Ambiguous {
+        import_path: String,
+        candidates: Vec<Symbol>,
+    },
+    /// The definition is outside the index (external dependency) or unknown
+    Unresolved { import_path: String, external: bool },
+}
+
+impl ImportResolution {
+    /// Outcome of looking up an unresolved import by its imported name
+    ///
+    /// A single match is only a guess, since the import path itself did not
+    /// lead to it, so it is reported as `Heuristic` rather than `Resolved`.
+    pub(crate) fn from_name_matches(import_path: String, mut candidates: Vec<Symbol>) -> Self {
+        match candidates.len() {
+            0 => Self::Unresolved {
+                import_path,
+                external: false,
+            },
+            1 => Self::Heuristic {
+                import_path,
+                symbol: candidates.remove(0),
+            },
+            _ => Self::Ambiguous {
+                import_path,
+                candidates,
+            },
+        }
+    }
+
+    /// Import path as written in the importing file
+    pub fn import_path(&self) -> &str {
+        match self {
+            Self::Resolved { import_path, .. }
+            | Self::Heuristic { import_path, .. }
+            | Self::Ambiguous { import_path, .. }
+            | Self::Unresolved { import_path, .. } => import_path,
+        }
+    }
+}
+
+impl fmt::Display for ImportResolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Resolved { .. } => write!(f, "resolved"),
+            Self::Heuristic { .. } => write!(f, "heuristic (matched by name only)"),
+            Self::Ambiguous { candidates, .. } => {
+                write!(f, "ambiguous ({} candidates)", candidates.len())
+            }
+            Self::Unresolved { external: true, .. } => write!(f, "unresolved (external)"),
+            Self::Unresolved {
+                external: false, ..
+            } => write!(f, "unresolved"),
+        }
+    }
+}
+
+/// Last segment of an import path, i.e. the name it defines
+pub(crate) fn imported_name(import_path: &str) -> &str {
+    import_path
+        .rsplit(|c| matches!(c, ':' | '.' | '/' | '\\'))
+        .find(|segment| !segment.is_empty())
+        .unwrap_or(import_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FileId, Range, SymbolId, SymbolKind};
+
+    fn symbol(id: u32, name: &str) -> Symbol {
+        Symbol::new(
+            SymbolId::new(id).unwrap(),
+            name,
+            SymbolKind::Function,
+            FileId::new(1).unwrap(),
+            Range::new(0, 0, 0, 10),
+        )
+    }
+
+    #[test]
+    fn test_name_matches_are_never_resolved() {
+        let path = || "crate::util::helper".to_string();
+        assert!(matches!(
+            ImportResolution::from_name_matches(path(), Vec::new()),
+            ImportResolution::Unresolved {
+                external: false,
+                ..
+            }
+        ));
+
+        let single = ImportResolution::from_name_matches(path(), vec![symbol(1, "helper")]);
+        assert!(matches!(single, ImportResolution::Heuristic { .. }));
+        assert_eq!(single.to_string(), "heuristic (matched by name only)");
+
+        let several = ImportResolution::from_name_matches(
+            path(),
+            vec![symbol(1, "helper"), symbol(2, "helper")],
+        );
+        assert!(matches!(several, ImportResolution::Ambiguous { .. }));
+    }
+
+    #[test]
+    fn test_imported_name() {
+        assert_eq!(imported_name("crate::config::Settings"), "Settings");
+        assert_eq!(imported_name("foo.bar.Baz"), "Baz");
+        assert_eq!(imported_name("./utils/helpers"), "helpers");
+        assert_eq!(imported_name("App\\Models\\User"), "User");
+        assert_eq!(imported_name("Bar"), "Bar");
+    }
+}
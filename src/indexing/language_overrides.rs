@@ -0,0 +1,126 @@
+//! Per-file language overrides from `[languages.overrides]`
+//!
+//! Some files carry misleading extensions: a `.txt` that is really SQL, or a
+//! `.inc` PHP include. Overrides map a glob pattern to a language name and are
+//! consulted before extension-based detection. Patterns use gitignore syntax,
+//! the same matcher as `.codannaignore`, and are tried longest first so a
+//! specific `legacy/*.inc` beats a general `*.inc`; equal lengths are tried in
+//! alphabetical order. The first match wins.
+
+use crate::Settings;
+use crate::parsing::{LanguageId, get_registry};
+use ignore::overrides::{Override, OverrideBuilder};
+use std::path::{Path, PathBuf};
+
+/// Compiled override patterns, in match order
+#[derive(Debug, Default)]
+pub struct LanguageOverrides {
+    rules: Vec<(Override, LanguageId)>,
+}
+
+impl LanguageOverrides {
+    /// Compile the overrides configured in `settings`
+    ///
+    /// Invalid patterns and unknown language names are reported and skipped
+    /// so one bad entry does not stop indexing.
+    pub fn from_settings(settings: &Settings) -> Self {
+        let ordered = settings.languages.ordered_overrides();
+        if ordered.is_empty() {
+            return Self::default();
+        }
+
+        let root = settings
+            .workspace_root
+            .clone()
+            .unwrap_or_else(|| PathBuf::from("."));
+        let Ok(registry) = get_registry().lock() else {
+            return Self::default();
+        };
+
+        let mut rules = Vec::new();
+        for (pattern, language) in ordered {
+            let Some(language_id) = registry.find_language_id(&language.to_ascii_lowercase())
+            else {
+                eprintln!(
+                    "Warning: Unknown language '{language}' for override '{pattern}' in [languages.overrides]"
+                );
+                continue;
+            };
+
+            let mut builder = OverrideBuilder::new(&root);
+            match builder.add(pattern).and_then(|builder| builder.build()) {
+                Ok(matcher) => rules.push((matcher, language_id)),
+                Err(e) => {
+                    eprintln!("Warning: Invalid override pattern '{pattern}': {e}");
+                }
+            }
+        }
+
+        Self { rules }
+    }
+
+    /// The language forced for `path`, if any override pattern matches it
+    pub fn language_for(&self, path: &Path) -> Option<LanguageId> {
+        self.rules
+            .iter()
+            .find(|(matcher, _)| matcher.matched(path, false).is_whitelist())
+            .map(|(_, language_id)| *language_id)
+    }
+
+    /// Whether no overrides are configured
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings_with(overrides: &[(&str, &str)]) -> Settings {
+        let mut settings = Settings::default();
+        for (pattern, language) in overrides {
+            settings
+                .languages
+                .overrides
+                .insert(pattern.to_string(), language.to_string());
+        }
+        settings
+    }
+
+    #[test]
+    fn test_override_matches_before_extension() {
+        let overrides = LanguageOverrides::from_settings(&settings_with(&[("*.inc", "php")]));
+
+        assert_eq!(
+            overrides.language_for(Path::new("lib/header.inc")),
+            Some(LanguageId::new("php"))
+        );
+        assert_eq!(overrides.language_for(Path::new("src/main.rs")), None);
+    }
+
+    #[test]
+    fn test_longest_pattern_wins() {
+        let overrides = LanguageOverrides::from_settings(&settings_with(&[
+            ("*.inc", "php"),
+            ("legacy/**/*.inc", "c"),
+        ]));
+
+        assert_eq!(
+            overrides.language_for(Path::new("legacy/io/port.inc")),
+            Some(LanguageId::new("c"))
+        );
+        assert_eq!(
+            overrides.language_for(Path::new("web/page.inc")),
+            Some(LanguageId::new("php"))
+        );
+    }
+
+    #[test]
+    fn test_unknown_language_is_skipped() {
+        let overrides = LanguageOverrides::from_settings(&settings_with(&[("*.txt", "cobol")]));
+
+        assert!(overrides.is_empty());
+        assert_eq!(overrides.language_for(Path::new("notes.txt")), None);
+    }
+}
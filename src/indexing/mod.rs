@@ -1,18 +1,50 @@
+pub mod ambiguities;
+pub mod analysis_ignore;
+pub mod archive;
+pub mod call_chain;
+pub mod call_resolution;
 pub mod config_watcher;
+pub mod coupling;
+pub mod doc_coverage;
+pub mod entrypoints;
+pub mod export;
 pub mod file_info;
 pub mod fs_watcher;
+pub mod generated;
+pub mod history;
+pub mod import_lookup;
+pub mod language_overrides;
 pub mod progress;
+pub mod signature_changes;
 pub mod simple;
+pub mod sqlite_export;
 pub mod transaction;
 pub mod walker;
 
 #[cfg(test)]
 pub mod import_resolution_proof;
 
+pub use ambiguities::AmbiguousResolution;
+pub use analysis_ignore::AnalysisIgnore;
+pub use call_resolution::CallResolutionTrace;
 pub use config_watcher::ConfigFileWatcher;
-pub use file_info::{FileInfo, calculate_hash, get_utc_timestamp};
+pub use coupling::{FileCoupling, RelatedFile};
+pub use doc_coverage::{DocCoverage, ModuleDocCoverage, UndocumentedSymbol};
+pub use entrypoints::{EntryPoint, EntryPointCategory};
+pub use export::{ExportFormat, ExportStats, IndexExporter};
+pub use file_info::{
+    FileInfo, LineStarts, calculate_hash, calculate_range_hash, get_utc_timestamp,
+};
 pub use fs_watcher::{FileSystemWatcher, WatchError};
-pub use progress::IndexStats;
-pub use simple::SimpleIndexer;
+pub use history::{CommitInfo, HistoryError, LineHistory};
+pub use import_lookup::ImportResolution;
+pub use language_overrides::LanguageOverrides;
+pub use progress::{IndexStats, SkipReason, SymbolUpdates};
+pub use signature_changes::{ChangeSeverity, SignatureChange, SignatureDelta};
+pub use simple::{
+    ImpactRadius, IndexVerification, MethodImplementation, OccurrenceLocation, SignatureUse,
+    SimpleIndexer, SourceSnippet, SymbolAt, SymbolSource, TypeMethod,
+};
+pub use sqlite_export::SqliteExporter;
 pub use transaction::{FileTransaction, IndexTransaction};
 pub use walker::FileWalker;
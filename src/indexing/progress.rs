@@ -1,8 +1,88 @@
 //! Progress reporting for indexing operations
 
+use std::fmt;
 use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
+/// Why a file was left out of the index
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipReason {
+    /// Larger than `indexing.max_file_size_bytes`
+    TooLarge,
+    /// Binary data or invalid UTF-8
+    Binary,
+    /// Produced by a code generator (see `indexing.include_generated`)
+    Generated,
+    /// Parsing ran past `indexing.parse_timeout_ms`
+    ParseTimeout,
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooLarge => write!(f, "too large"),
+            Self::Binary => write!(f, "binary/non-utf8"),
+            Self::Generated => write!(f, "generated"),
+            Self::ParseTimeout => write!(f, "parse timeout"),
+        }
+    }
+}
+
+/// How symbols of re-indexed files compare with their previous version
+///
+/// The code and the doc comment of each symbol are hashed separately, so each
+/// kind of change only rebuilds the data derived from it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SymbolUpdates {
+    /// Code and doc comment unchanged; embeddings are kept
+    pub unchanged: usize,
+    /// Only the doc comment changed; its embedding is refreshed
+    pub doc_only: usize,
+    /// Code changed for a symbol of the same name and kind; everything is rebuilt
+    pub code_changed: usize,
+    /// New symbols without a previous version
+    pub added: usize,
+    /// Previous symbols that no longer exist
+    pub removed: usize,
+    /// Changed files whose relationships were kept because no code moved or changed
+    pub relationships_kept: usize,
+}
+
+impl SymbolUpdates {
+    /// Whether any previously indexed file was re-indexed
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Display the counts in a human-readable format
+    pub fn display(&self) {
+        println!("\nSymbol Updates:");
+        println!("  Unchanged: {}", self.unchanged);
+        println!("  Doc comment only (re-embedded): {}", self.doc_only);
+        println!(
+            "  Code changed (re-embedded, relationships re-resolved): {}",
+            self.code_changed
+        );
+        println!("  Added: {}", self.added);
+        println!("  Removed: {}", self.removed);
+        println!(
+            "  Files with relationships kept (doc-only edits): {}",
+            self.relationships_kept
+        );
+    }
+}
+
+impl std::ops::AddAssign for SymbolUpdates {
+    fn add_assign(&mut self, other: Self) {
+        self.unchanged += other.unchanged;
+        self.doc_only += other.doc_only;
+        self.code_changed += other.code_changed;
+        self.added += other.added;
+        self.removed += other.removed;
+        self.relationships_kept += other.relationships_kept;
+    }
+}
+
 /// Statistics collected during indexing
 #[derive(Debug, Default)]
 pub struct IndexStats {
@@ -12,15 +92,33 @@ pub struct IndexStats {
     /// Number of files that failed to index
     pub files_failed: usize,
 
+    /// Number of files skipped for exceeding the size limit
+    pub files_too_large: usize,
+
+    /// Number of files skipped for binary or non-UTF-8 content
+    pub files_binary: usize,
+
+    /// Number of files skipped as generated code
+    pub files_generated: usize,
+
+    /// Number of files skipped for parsing past the timeout
+    pub files_parse_timeout: usize,
+
     /// Total number of symbols found
     pub symbols_found: usize,
 
     /// Time elapsed during indexing
     pub elapsed: Duration,
 
+    /// How symbols of previously indexed files changed
+    pub symbol_updates: SymbolUpdates,
+
     /// Errors encountered (limited to first N errors)
     pub errors: Vec<(PathBuf, String)>,
 
+    /// Skipped files with the reason and details (limited to first N files)
+    pub skipped: Vec<(PathBuf, SkipReason, String)>,
+
     /// Start time of indexing
     start_time: Option<Instant>,
 }
@@ -50,11 +148,44 @@ impl IndexStats {
         self.files_failed += 1;
     }
 
+    /// Record a skipped file (details kept for the first 100 files)
+    pub fn add_skipped(&mut self, path: PathBuf, reason: SkipReason, detail: String) {
+        if self.skipped.len() < 100 {
+            self.skipped.push((path, reason, detail));
+        }
+        match reason {
+            SkipReason::TooLarge => self.files_too_large += 1,
+            SkipReason::Binary => self.files_binary += 1,
+            SkipReason::Generated => self.files_generated += 1,
+            SkipReason::ParseTimeout => self.files_parse_timeout += 1,
+        }
+    }
+
+    /// Total number of skipped files
+    pub fn files_skipped(&self) -> usize {
+        self.files_too_large + self.files_binary + self.files_generated + self.files_parse_timeout
+    }
+
     /// Display the statistics in a human-readable format
     pub fn display(&self) {
         println!("\nIndexing Complete:");
         println!("  Files indexed: {}", self.files_indexed);
         println!("  Files failed: {}", self.files_failed);
+        if self.files_too_large > 0 {
+            println!("  Files skipped (too large): {}", self.files_too_large);
+        }
+        if self.files_binary > 0 {
+            println!("  Files skipped (binary/non-utf8): {}", self.files_binary);
+        }
+        if self.files_generated > 0 {
+            println!("  Files skipped (generated): {}", self.files_generated);
+        }
+        if self.files_parse_timeout > 0 {
+            println!(
+                "  Files skipped (parse timeout): {}",
+                self.files_parse_timeout
+            );
+        }
         println!("  Symbols found: {}", self.symbols_found);
         println!("  Time elapsed: {:.2}s", self.elapsed.as_secs_f64());
 
@@ -75,6 +206,16 @@ impl IndexStats {
                 println!("  ... and {} more errors", self.errors.len() - 5);
             }
         }
+
+        if !self.skipped.is_empty() {
+            println!("\nSkipped (showing first {}):", self.skipped.len().min(5));
+            for (path, reason, detail) in &self.skipped[..5.min(self.skipped.len())] {
+                println!("  {}: {reason} ({detail})", path.display());
+            }
+            if self.files_skipped() > 5 {
+                println!("  ... and {} more skipped files", self.files_skipped() - 5);
+            }
+        }
     }
 }
 
@@ -107,4 +248,33 @@ mod tests {
         assert_eq!(stats.errors.len(), 100);
         assert_eq!(stats.files_failed, 150);
     }
+
+    #[test]
+    fn test_skipped_files_are_counted_separately() {
+        let mut stats = IndexStats::new();
+        for i in 0..120 {
+            stats.add_skipped(
+                PathBuf::from(format!("bundle{i}.js")),
+                SkipReason::TooLarge,
+                "3 MiB".into(),
+            );
+        }
+        stats.add_skipped(
+            PathBuf::from("logo.rs"),
+            SkipReason::Binary,
+            "contains NUL bytes".into(),
+        );
+        stats.add_skipped(
+            PathBuf::from("api.pb.go"),
+            SkipReason::Generated,
+            "file name matches *.pb.go".into(),
+        );
+
+        assert_eq!(stats.skipped.len(), 100);
+        assert_eq!(stats.files_too_large, 120);
+        assert_eq!(stats.files_binary, 1);
+        assert_eq!(stats.files_generated, 1);
+        assert_eq!(stats.files_skipped(), 122);
+        assert_eq!(stats.files_failed, 0);
+    }
 }
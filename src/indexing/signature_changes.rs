@@ -0,0 +1,760 @@
+//! Public API signature changes between a baseline index and the current one
+//!
+//! Public functions and methods are matched by qualified name and their
+//! signature strings compared parameter by parameter. Classification is a
+//! heuristic over signature text: parameters are matched by position in
+//! languages whose callers cannot name arguments (Rust, Go, C, C++,
+//! TypeScript) and by name elsewhere, and an added parameter only counts as
+//! non-breaking when it has a default value, is marked optional (`x?: T`) or
+//! is variadic. Functions that were removed or stopped being public, and
+//! changes the heuristic does not recognize, are breaking.
+
+use crate::{SymbolId, parsing::LanguageId, symbol::Symbol};
+use serde::Serialize;
+use std::fmt;
+
+/// Whether callers of the baseline API may stop compiling or working
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeSeverity {
+    Breaking,
+    NonBreaking,
+}
+
+impl fmt::Display for ChangeSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Breaking => write!(f, "breaking"),
+            Self::NonBreaking => write!(f, "non-breaking"),
+        }
+    }
+}
+
+/// One difference between a baseline signature and the current one
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum SignatureDelta {
+    ParameterAdded {
+        name: String,
+        optional: bool,
+    },
+    ParameterRemoved {
+        name: String,
+    },
+    /// A parameter lost its default value or optional marker
+    ParameterMadeRequired {
+        name: String,
+    },
+    ParameterTypeChanged {
+        name: String,
+        from: String,
+        to: String,
+    },
+    /// Parameters kept in both versions, in baseline and current order
+    ParametersReordered {
+        from: Vec<String>,
+        to: Vec<String>,
+    },
+    /// A parameter kept its position under another name, which callers
+    /// passing arguments by position do not notice
+    ParameterRenamed {
+        from: String,
+        to: String,
+    },
+    ReturnTypeChanged {
+        from: Option<String>,
+        to: Option<String>,
+    },
+    /// The function is gone from the current index
+    Removed,
+    /// The function still exists but is no longer public
+    MadePrivate,
+    /// The signature text changed in a way not recognized above, such as
+    /// generic bounds or modifiers
+    Other,
+}
+
+impl SignatureDelta {
+    pub fn severity(&self) -> ChangeSeverity {
+        match self {
+            Self::ParameterAdded { optional: true, .. } | Self::ParameterRenamed { .. } => {
+                ChangeSeverity::NonBreaking
+            }
+            _ => ChangeSeverity::Breaking,
+        }
+    }
+}
+
+impl fmt::Display for SignatureDelta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ParameterAdded {
+                name,
+                optional: true,
+            } => write!(f, "optional parameter `{name}` added"),
+            Self::ParameterAdded { name, .. } => write!(f, "parameter `{name}` added"),
+            Self::ParameterRemoved { name } => write!(f, "parameter `{name}` removed"),
+            Self::ParameterMadeRequired { name } => {
+                write!(f, "parameter `{name}` no longer optional")
+            }
+            Self::ParameterTypeChanged { name, from, to } => {
+                write!(f, "parameter `{name}` type changed: {from} -> {to}")
+            }
+            Self::ParametersReordered { from, to } => write!(
+                f,
+                "parameters reordered: ({}) -> ({})",
+                from.join(", "),
+                to.join(", ")
+            ),
+            Self::ParameterRenamed { from, to } => {
+                write!(f, "parameter `{from}` renamed to `{to}`")
+            }
+            Self::ReturnTypeChanged { from, to } => write!(
+                f,
+                "return type changed: {} -> {}",
+                from.as_deref().unwrap_or("(none)"),
+                to.as_deref().unwrap_or("(none)")
+            ),
+            Self::Removed => write!(f, "removed"),
+            Self::MadePrivate => write!(f, "no longer public"),
+            Self::Other => write!(f, "signature changed"),
+        }
+    }
+}
+
+/// A public function or method of the baseline whose signature differs, or
+/// that is no longer part of the public API
+#[derive(Debug, Clone, Serialize)]
+pub struct SignatureChange {
+    pub qualified_name: String,
+    /// Breaking when any delta is breaking
+    pub severity: ChangeSeverity,
+    pub baseline_signature: String,
+    /// `None` when the function was removed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_signature: Option<String>,
+    /// Current location as `path:line`, the baseline one when removed
+    pub location: String,
+    /// `None` when the function was removed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol_id: Option<SymbolId>,
+    pub deltas: Vec<SignatureDelta>,
+}
+
+impl SignatureChange {
+    /// Compare a baseline symbol with its current version, `None` when the
+    /// signatures match apart from layout
+    pub fn between(
+        qualified_name: String,
+        location: String,
+        baseline: &Symbol,
+        current: &Symbol,
+    ) -> Option<Self> {
+        let baseline_signature = baseline.signature.as_deref()?;
+        let current_signature = current.signature.as_deref()?;
+        let deltas = compare_signatures(
+            baseline_signature,
+            current_signature,
+            &current.name,
+            ParameterStyle::of(current.language_id),
+        );
+        let severity = deltas
+            .iter()
+            .map(SignatureDelta::severity)
+            .min()
+            .unwrap_or(ChangeSeverity::NonBreaking);
+        (!deltas.is_empty()).then(|| Self {
+            qualified_name,
+            severity,
+            baseline_signature: baseline_signature.to_string(),
+            current_signature: Some(current_signature.to_string()),
+            location,
+            symbol_id: Some(current.id),
+            deltas,
+        })
+    }
+
+    /// A baseline public function missing from the current public API:
+    /// `hidden` is its current non-public version with its location, `None`
+    /// when it was removed
+    pub fn withdrawn(
+        qualified_name: String,
+        baseline_location: String,
+        baseline: &Symbol,
+        hidden: Option<(&Symbol, String)>,
+    ) -> Self {
+        let baseline_signature = baseline
+            .signature
+            .as_deref()
+            .unwrap_or_default()
+            .to_string();
+        match hidden {
+            Some((current, location)) => Self {
+                qualified_name,
+                severity: ChangeSeverity::Breaking,
+                baseline_signature,
+                current_signature: current.signature.as_deref().map(str::to_string),
+                location,
+                symbol_id: Some(current.id),
+                deltas: vec![SignatureDelta::MadePrivate],
+            },
+            None => Self {
+                qualified_name,
+                severity: ChangeSeverity::Breaking,
+                baseline_signature,
+                current_signature: None,
+                location: baseline_location,
+                symbol_id: None,
+                deltas: vec![SignatureDelta::Removed],
+            },
+        }
+    }
+}
+
+impl fmt::Display for SignatureChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{}] {} at {}",
+            self.severity, self.qualified_name, self.location
+        )?;
+        if let Some(symbol_id) = self.symbol_id {
+            write!(f, " [symbol_id:{}]", symbol_id.value())?;
+        }
+        write!(
+            f,
+            "\n  - {}\n  + {}",
+            collapse_whitespace(&self.baseline_signature),
+            self.current_signature
+                .as_deref()
+                .map_or_else(|| "(removed)".to_string(), collapse_whitespace)
+        )?;
+        for delta in &self.deltas {
+            write!(f, "\n  * {delta} ({})", delta.severity())?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether two symbols have the same signature apart from layout
+pub fn signatures_match(a: &Symbol, b: &Symbol) -> bool {
+    a.signature.as_deref().map(layout_insensitive) == b.signature.as_deref().map(layout_insensitive)
+}
+
+/// How a language writes and passes parameters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParameterStyle {
+    /// `name Type` parameters (Go) rather than `Type name` ones (C#, Java,
+    /// PHP) when a parameter has no `name: Type` separator
+    pub name_first: bool,
+    /// Arguments are passed by position only, so parameters are matched by
+    /// position and renaming one is not a change for callers
+    pub positional: bool,
+}
+
+impl ParameterStyle {
+    const POSITIONAL_LANGUAGES: &'static [&'static str] = &[
+        "rust",
+        "go",
+        "c",
+        "cpp",
+        "typescript",
+        "javascript",
+        "gdscript",
+    ];
+
+    pub fn of(language_id: Option<LanguageId>) -> Self {
+        let language = language_id.map(|language| language.as_str());
+        Self {
+            name_first: language == Some("go"),
+            positional: language
+                .is_some_and(|language| Self::POSITIONAL_LANGUAGES.contains(&language)),
+        }
+    }
+}
+
+/// A parameter as written in a signature
+#[derive(Debug, PartialEq, Eq)]
+struct Parameter {
+    name: String,
+    /// Type annotation without the default value, empty when untyped
+    ty: String,
+    optional: bool,
+}
+
+/// Differences between two signatures of the function `name`
+pub fn compare_signatures(
+    baseline: &str,
+    current: &str,
+    name: &str,
+    style: ParameterStyle,
+) -> Vec<SignatureDelta> {
+    if layout_insensitive(baseline) == layout_insensitive(current) {
+        return Vec::new();
+    }
+    let (Some((old_params, old_return)), Some((new_params, new_return))) = (
+        parse_signature(baseline, name, style.name_first),
+        parse_signature(current, name, style.name_first),
+    ) else {
+        return vec![SignatureDelta::Other];
+    };
+
+    let mut deltas = if style.positional {
+        compare_by_position(&old_params, &new_params)
+    } else {
+        compare_by_name(&old_params, &new_params)
+    };
+
+    let kept = |params: &[Parameter], other: &[Parameter]| -> Vec<String> {
+        params
+            .iter()
+            .filter(|param| other.iter().any(|o| o.name == param.name))
+            .map(|param| param.name.clone())
+            .collect()
+    };
+    let (from, to) = (
+        kept(&old_params, &new_params),
+        kept(&new_params, &old_params),
+    );
+    if from != to {
+        deltas.push(SignatureDelta::ParametersReordered { from, to });
+    }
+
+    if old_return != new_return {
+        deltas.push(SignatureDelta::ReturnTypeChanged {
+            from: old_return,
+            to: new_return,
+        });
+    }
+
+    if deltas.is_empty() {
+        deltas.push(SignatureDelta::Other);
+    }
+    deltas
+}
+
+/// Parameter deltas pairing parameters by name
+fn compare_by_name(old_params: &[Parameter], new_params: &[Parameter]) -> Vec<SignatureDelta> {
+    let mut deltas = Vec::new();
+    for old in old_params {
+        match new_params.iter().find(|new| new.name == old.name) {
+            None => deltas.push(SignatureDelta::ParameterRemoved {
+                name: old.name.clone(),
+            }),
+            Some(new) if new.ty != old.ty => deltas.push(SignatureDelta::ParameterTypeChanged {
+                name: old.name.clone(),
+                from: old.ty.clone(),
+                to: new.ty.clone(),
+            }),
+            Some(new) if old.optional && !new.optional => {
+                deltas.push(SignatureDelta::ParameterMadeRequired {
+                    name: old.name.clone(),
+                })
+            }
+            Some(_) => {}
+        }
+    }
+    for new in new_params {
+        if !old_params.iter().any(|old| old.name == new.name) {
+            deltas.push(SignatureDelta::ParameterAdded {
+                name: new.name.clone(),
+                optional: new.optional,
+            });
+        }
+    }
+    deltas
+}
+
+/// Parameter deltas pairing parameters by position, named after the baseline
+///
+/// A parameter moved to another position also shows up as
+/// [`SignatureDelta::ParametersReordered`]; one whose new name is not in the
+/// baseline is a rename.
+fn compare_by_position(old_params: &[Parameter], new_params: &[Parameter]) -> Vec<SignatureDelta> {
+    let mut deltas = Vec::new();
+    for (old, new) in old_params.iter().zip(new_params) {
+        if new.ty != old.ty {
+            deltas.push(SignatureDelta::ParameterTypeChanged {
+                name: old.name.clone(),
+                from: old.ty.clone(),
+                to: new.ty.clone(),
+            });
+        } else if old.optional && !new.optional {
+            deltas.push(SignatureDelta::ParameterMadeRequired {
+                name: old.name.clone(),
+            });
+        }
+        if new.name != old.name && !old_params.iter().any(|param| param.name == new.name) {
+            deltas.push(SignatureDelta::ParameterRenamed {
+                from: old.name.clone(),
+                to: new.name.clone(),
+            });
+        }
+    }
+    for old in old_params.iter().skip(new_params.len()) {
+        deltas.push(SignatureDelta::ParameterRemoved {
+            name: old.name.clone(),
+        });
+    }
+    for new in new_params.iter().skip(old_params.len()) {
+        deltas.push(SignatureDelta::ParameterAdded {
+            name: new.name.clone(),
+            optional: new.optional,
+        });
+    }
+    deltas
+}
+
+/// Keywords stripped from the text before a function name when looking for
+/// a leading return type (`public static int Foo(...)`)
+const MODIFIERS: &[&str] = &[
+    "abstract",
+    "async",
+    "const",
+    "def",
+    "default",
+    "export",
+    "extern",
+    "final",
+    "fn",
+    "fun",
+    "func",
+    "function",
+    "inline",
+    "internal",
+    "new",
+    "open",
+    "override",
+    "partial",
+    "private",
+    "protected",
+    "pub",
+    "public",
+    "readonly",
+    "sealed",
+    "static",
+    "suspend",
+    "unsafe",
+    "virtual",
+];
+
+/// Parameters and return type of a signature, `None` without a parameter list
+fn parse_signature(
+    signature: &str,
+    name: &str,
+    name_first: bool,
+) -> Option<(Vec<Parameter>, Option<String>)> {
+    let (name_at, after_name) = match find_name(signature, name) {
+        Some(at) => (at, at + name.len()),
+        None => (0, 0),
+    };
+    let open = after_name + find_top_level(&signature[after_name..], '(')?;
+    let close = open + matching_close(&signature[open..])?;
+
+    let params = split_top_level(&signature[open + 1..close], ',')
+        .into_iter()
+        .map(str::trim)
+        .filter(|param| !param.is_empty())
+        .map(|param| parse_parameter(param, name_first))
+        .collect();
+
+    let rest = format!(" {}", collapse_whitespace(&signature[close + 1..]));
+    let rest = rest.split(" where ").next().unwrap_or_default();
+    let rest = rest.split('{').next().unwrap_or_default();
+    let rest = rest
+        .trim()
+        .trim_end_matches([':', ';'])
+        .trim()
+        .trim_start_matches("->")
+        .trim_start_matches(':')
+        .trim();
+    let returns = if rest.is_empty() {
+        // C-like languages put the return type before the name
+        let prefix = &signature[..name_at];
+        let words: Vec<&str> = prefix
+            .split_whitespace()
+            .filter(|word| !MODIFIERS.contains(word) && !word.starts_with(['#', '@', '"']))
+            .collect();
+        (!prefix.contains('(') && !words.is_empty()).then(|| words.join(" "))
+    } else {
+        Some(collapse_whitespace(rest))
+    };
+
+    Some((params, returns))
+}
+
+fn parse_parameter(param: &str, name_first: bool) -> Parameter {
+    let (declaration, has_default) = match find_top_level(param, '=') {
+        Some(at) => (param[..at].trim(), true),
+        None => (param, false),
+    };
+    let variadic = declaration.contains("...") || declaration.starts_with('*');
+
+    let (name, ty) = match find_type_separator(declaration) {
+        Some(at) => (
+            declaration[..at].trim(),
+            declaration[at + 1..].trim().to_string(),
+        ),
+        None => {
+            let words: Vec<&str> = declaration.split_whitespace().collect();
+            match words.as_slice() {
+                [] => ("", String::new()),
+                [only] => (*only, String::new()),
+                [first, rest @ ..] if name_first => (*first, rest.join(" ")),
+                [init @ .., last] => (*last, init.join(" ")),
+            }
+        }
+    };
+
+    let marked_optional = name.ends_with('?');
+    let name = name
+        .trim_start_matches("mut ")
+        .trim_start_matches(['&', '*', '$'])
+        .trim_start_matches("...")
+        .trim_end_matches('?')
+        .trim();
+    Parameter {
+        name: name.to_string(),
+        ty: collapse_whitespace(&ty),
+        optional: has_default || marked_optional || variadic,
+    }
+}
+
+/// Position of `name` as a whole word followed by its parameter or generic list
+fn find_name(signature: &str, name: &str) -> Option<usize> {
+    let is_ident = |ch: char| ch.is_alphanumeric() || ch == '_';
+    signature.match_indices(name).map(|(at, _)| at).find(|&at| {
+        let before = signature[..at].chars().next_back();
+        let after = signature[at + name.len()..].trim_start().chars().next();
+        !before.is_some_and(is_ident) && matches!(after, Some('(' | '<' | '['))
+    })
+}
+
+/// Position of a `name: Type` colon at nesting depth zero, skipping `::` paths
+fn find_type_separator(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let mut depth = 0i32;
+    for (i, &byte) in bytes.iter().enumerate() {
+        match byte {
+            b'(' | b'[' | b'{' | b'<' => depth += 1,
+            b')' | b']' | b'}' => depth -= 1,
+            b'>' if i == 0 || bytes[i - 1] != b'-' => depth -= 1,
+            b':' if depth == 0
+                && bytes.get(i + 1) != Some(&b':')
+                && (i == 0 || bytes[i - 1] != b':') =>
+            {
+                return Some(i);
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Position of `needle` outside brackets and generic arguments
+fn find_top_level(text: &str, needle: char) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let mut depth = 0i32;
+    for (i, ch) in text.char_indices() {
+        if depth == 0 && ch == needle {
+            return Some(i);
+        }
+        match ch {
+            '(' | '[' | '{' | '<' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            // `->` and `=>` are arrows, not closing generics
+            '>' if i == 0 || !matches!(bytes[i - 1], b'-' | b'=') => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Offset of the `)` closing the `(` that `text` starts with
+fn matching_close(text: &str) -> Option<usize> {
+    let mut depth = 0i32;
+    for (i, ch) in text.char_indices() {
+        match ch {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Split on `separator` outside brackets and generic arguments
+fn split_top_level(text: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut rest = text;
+    while let Some(at) = find_top_level(rest, separator) {
+        parts.push(&rest[..at]);
+        rest = &rest[at + separator.len_utf8()..];
+    }
+    parts.push(rest);
+    parts
+}
+
+/// Signature text without whitespace or trailing commas, so reformatting
+/// a signature across lines does not count as a change
+fn layout_insensitive(text: &str) -> String {
+    let compact: String = text.chars().filter(|ch| !ch.is_whitespace()).collect();
+    compact.replace(",)", ")").replace(",>", ">")
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rust_signature_changes() {
+        let rust = ParameterStyle::of(Some(LanguageId::new("rust")));
+        let deltas = compare_signatures(
+            "pub fn load(path: &Path, strict: bool) -> Result<Index>",
+            "pub fn load(strict: bool, path: &Path, cache: Option<u32>) -> Result<Index, Error>",
+            "load",
+            rust,
+        );
+        assert_eq!(
+            deltas,
+            vec![
+                SignatureDelta::ParameterTypeChanged {
+                    name: "path".into(),
+                    from: "&Path".into(),
+                    to: "bool".into(),
+                },
+                SignatureDelta::ParameterTypeChanged {
+                    name: "strict".into(),
+                    from: "bool".into(),
+                    to: "&Path".into(),
+                },
+                SignatureDelta::ParameterAdded {
+                    name: "cache".into(),
+                    optional: false,
+                },
+                SignatureDelta::ParametersReordered {
+                    from: vec!["path".into(), "strict".into()],
+                    to: vec!["strict".into(), "path".into()],
+                },
+                SignatureDelta::ReturnTypeChanged {
+                    from: Some("Result<Index>".into()),
+                    to: Some("Result<Index, Error>".into()),
+                },
+            ]
+        );
+        assert!(
+            compare_signatures(
+                "pub fn run<F: Fn(u32) -> u32>(&self, f: F)",
+                "pub fn run<F: Fn(u32) -> u32>(\n    &self,\n    f: F,\n)",
+                "run",
+                rust
+            )
+            .is_empty()
+        );
+
+        // Callers pass arguments by position, so a rename alone is harmless
+        let renamed = compare_signatures(
+            "pub fn open(path: &Path) -> File",
+            "pub fn open(file: &Path) -> File",
+            "open",
+            rust,
+        );
+        assert_eq!(
+            renamed,
+            vec![SignatureDelta::ParameterRenamed {
+                from: "path".into(),
+                to: "file".into(),
+            }]
+        );
+        assert_eq!(renamed[0].severity(), ChangeSeverity::NonBreaking);
+
+        // Unrecognized changes, like a tighter bound, count as breaking
+        let other = compare_signatures(
+            "pub fn spawn<F: Send>(f: F)",
+            "pub fn spawn<F: Send + 'static>(f: F)",
+            "spawn",
+            rust,
+        );
+        assert_eq!(other, vec![SignatureDelta::Other]);
+        assert_eq!(other[0].severity(), ChangeSeverity::Breaking);
+    }
+
+    #[test]
+    fn test_optional_parameters_are_non_breaking() {
+        let added = compare_signatures(
+            "def fetch(url: str) -> bytes:",
+            "def fetch(url: str, timeout: float = 10.0) -> bytes:",
+            "fetch",
+            ParameterStyle::default(),
+        );
+        assert_eq!(
+            added,
+            vec![SignatureDelta::ParameterAdded {
+                name: "timeout".into(),
+                optional: true,
+            }]
+        );
+        assert_eq!(added[0].severity(), ChangeSeverity::NonBreaking);
+
+        let removed = compare_signatures(
+            "function send(to: string, body?: string): void",
+            "function send(to: string): void",
+            "send",
+            ParameterStyle::default(),
+        );
+        assert_eq!(
+            removed,
+            vec![SignatureDelta::ParameterRemoved {
+                name: "body".into()
+            }]
+        );
+        assert_eq!(removed[0].severity(), ChangeSeverity::Breaking);
+    }
+
+    #[test]
+    fn test_type_before_name_languages() {
+        let csharp = compare_signatures(
+            "public int Count(string text)",
+            "public long Count(string text, int start = 0)",
+            "Count",
+            ParameterStyle::default(),
+        );
+        assert_eq!(
+            csharp,
+            vec![
+                SignatureDelta::ParameterAdded {
+                    name: "start".into(),
+                    optional: true,
+                },
+                SignatureDelta::ReturnTypeChanged {
+                    from: Some("int".into()),
+                    to: Some("long".into()),
+                },
+            ]
+        );
+
+        let go = compare_signatures(
+            "func (s *Server) Serve(addr string) error",
+            "func (s *Server) Serve(addr int) error",
+            "Serve",
+            ParameterStyle::of(Some(LanguageId::new("go"))),
+        );
+        assert_eq!(
+            go,
+            vec![SignatureDelta::ParameterTypeChanged {
+                name: "addr".into(),
+                from: "string".into(),
+                to: "int".into(),
+            }]
+        );
+    }
+}
@@ -1,14 +1,20 @@
 //! Tantivy-only implementation of SimpleIndexer
 //! This version uses Tantivy as the single source of truth for all data
 
+use crate::indexing::archive::{self, ArchiveFormat};
+use crate::indexing::call_chain::{declared_return_type, split_chained_call};
 use crate::indexing::{
-    FileWalker, IndexStats, IndexTransaction, calculate_hash, get_utc_timestamp,
+    FileWalker, HistoryError, IndexStats, IndexTransaction, LanguageOverrides, LineHistory,
+    LineStarts, SkipReason, calculate_hash, generated::generated_reason, get_utc_timestamp,
+    history::line_history,
 };
-use crate::io::status_line::StatusLine;
+use crate::io::status_line::{Options as StatusLineOptions, StatusLine};
 use crate::io::{ProgressBar, ProgressBarOptions, ProgressBarStyle};
 use crate::parsing::resolution::ResolutionScope;
-use crate::parsing::{LanguageId, MethodCall, ParserFactory, get_registry};
-use crate::relationship::RelationshipMetadata;
+use crate::parsing::{
+    LanguageId, MethodCall, ParseDeadline, ParserFactory, PositionedUse, get_registry,
+};
+use crate::relationship::{CallMetadata, Provenance, RelationshipMetadata};
 use crate::semantic::SimpleSemanticSearch;
 use crate::storage::{DocumentIndex, SearchResult};
 use crate::types::SymbolCounter;
@@ -19,6 +25,7 @@ use crate::{
 };
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 
 /// Debug print macro that respects the debug setting
@@ -62,6 +69,345 @@ struct UnresolvedRelationship {
     metadata: Option<RelationshipMetadata>,
 }
 
+/// Source text of a symbol as currently on disk
+#[derive(Debug, Clone)]
+pub struct SymbolSource {
+    /// Path the source was read from
+    pub path: PathBuf,
+    /// First line returned (0-based, includes leading context)
+    pub start_line: u32,
+    /// Last line returned (0-based, inclusive)
+    pub end_line: u32,
+    /// The source text
+    pub text: String,
+    /// The file changed since it was indexed, so line ranges may be off
+    pub stale: bool,
+    /// The indexed range extended past the end of the file and was clamped
+    pub clamped: bool,
+}
+
+/// Source lines around a symbol's start line, as currently on disk
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SourceSnippet {
+    /// First line shown (1-based)
+    pub start_line: u32,
+    /// The symbol's start line (1-based)
+    pub symbol_line: u32,
+    /// Lines from `start_line` on
+    pub lines: Vec<String>,
+    /// The file changed since it was indexed, so the lines may be off
+    pub stale: bool,
+}
+
+impl SourceSnippet {
+    /// Format like `rg -n --no-heading` with context: `path:line:text` for
+    /// the symbol's line and `path-line-text` around it
+    pub fn format_rg(&self, path: &str) -> String {
+        let mut output = String::new();
+        for (line_number, text) in (self.start_line..).zip(&self.lines) {
+            let separator = if line_number == self.symbol_line {
+                ':'
+            } else {
+                '-'
+            };
+            output.push_str(&format!(
+                "{path}{separator}{line_number}{separator}{text}\n"
+            ));
+        }
+        output
+    }
+}
+
+/// Identifier occurrence resolved to its file path
+#[derive(Debug, Clone, serde::Serialize, schemars::JsonSchema)]
+pub struct OccurrenceLocation {
+    #[serde(serialize_with = "crate::paths::serialize_display_path")]
+    #[schemars(with = "String")]
+    pub file_path: String,
+    /// 1-based line number
+    pub line: u32,
+    /// 1-based byte column
+    pub column: u32,
+}
+
+/// Innermost symbol at a source position and the symbols enclosing it
+#[derive(Debug, Clone, serde::Serialize, schemars::JsonSchema)]
+pub struct SymbolAt {
+    pub symbol: Symbol,
+    /// Enclosing symbols, innermost first (e.g. impl type, then module)
+    pub enclosing: Vec<Symbol>,
+}
+
+/// A type's own implementation of a trait or interface method
+#[derive(Debug, Clone, serde::Serialize, schemars::JsonSchema)]
+pub struct MethodImplementation {
+    /// The implementing type
+    pub implementor: Symbol,
+    pub method: Symbol,
+}
+
+/// A function or method whose signature mentions a type
+/// (`SimpleIndexer::get_signature_uses`)
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SignatureUse {
+    pub symbol: Symbol,
+    /// Where the type appears: `parameter` or `return`
+    pub position: crate::relationship::TypeUsePosition,
+}
+
+/// How indexed files compare to their content on disk
+/// (`SimpleIndexer::verify_against_disk`)
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct IndexVerification {
+    /// Files whose content still hashes to the indexed hash
+    pub ok: usize,
+    /// Files whose content changed since indexing, as stored in the index
+    pub changed: Vec<PathBuf>,
+    /// Indexed files no longer on disk, as stored in the index
+    pub missing: Vec<PathBuf>,
+}
+
+impl IndexVerification {
+    /// True when no indexed file changed or disappeared
+    pub fn is_in_sync(&self) -> bool {
+        self.changed.is_empty() && self.missing.is_empty()
+    }
+}
+
+/// A method a type defines (`SimpleIndexer::get_type_methods`)
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct TypeMethod {
+    pub method: Symbol,
+    /// The trait or interface this method implements; `None` for inherent methods
+    pub implements: Option<Symbol>,
+}
+
+/// Dependents found by `SimpleIndexer::get_impact_radius`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImpactRadius {
+    /// Nearest dependents first
+    pub symbols: Vec<SymbolId>,
+    /// The walk stopped at `mcp.max_impact_nodes` before reaching every dependent
+    pub truncated: bool,
+}
+
+/// Previously indexed symbols of a file keyed by (name, kind, content hash)
+type SymbolAnchors = std::collections::HashMap<(String, crate::SymbolKind, String), Vec<SymbolId>>;
+
+/// How a symbol differs from its previous version, deciding which embeddings are rebuilt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SymbolChange {
+    Unchanged,
+    DocOnly,
+    /// Changed code or a symbol without a previous version
+    Code,
+}
+
+/// A file's symbols and relationships, extracted before the index is touched
+struct ParsedFile<'a> {
+    language_id: LanguageId,
+    behavior: Box<dyn crate::parsing::LanguageBehavior>,
+    /// Carry a placeholder file ID until the file is registered
+    symbols: Vec<Symbol>,
+    symbol_counter: SymbolCounter,
+    /// Carry the same placeholder file ID as the symbols
+    imports: Vec<crate::parsing::Import>,
+    /// `None` unless `indexing.resolve_relationships` is set
+    relations: Option<FileRelations<'a>>,
+}
+
+type NamePairs<'a> = Vec<(&'a str, &'a str, crate::Range)>;
+
+/// What a file's parser reports about how its symbols relate, before resolution
+struct FileRelations<'a> {
+    method_calls: Vec<MethodCall>,
+    calls: NamePairs<'a>,
+    implementations: NamePairs<'a>,
+    derives: NamePairs<'a>,
+    extends: NamePairs<'a>,
+    /// Empty unless the language supports inherent methods
+    inherent_methods: Vec<(String, String, crate::Range)>,
+    trait_impl_methods: Vec<(String, String, String, crate::Range)>,
+    /// Empty unless `indexing.index_type_parameters` is set
+    type_parameter_bounds: Vec<(String, String, crate::Range)>,
+    uses: Vec<PositionedUse<'a>>,
+    defines: NamePairs<'a>,
+    variable_types: NamePairs<'a>,
+}
+
+impl<'a> FileRelations<'a> {
+    fn extract(
+        parser: &mut dyn crate::parsing::LanguageParser,
+        behavior: &dyn crate::parsing::LanguageBehavior,
+        content: &'a str,
+        index_type_parameters: bool,
+    ) -> Self {
+        Self {
+            method_calls: parser.find_method_calls(content),
+            calls: parser.find_calls(content),
+            implementations: parser.find_implementations(content),
+            derives: parser.find_derives(content),
+            extends: parser.find_extends(content),
+            inherent_methods: if behavior.supports_inherent_methods() {
+                parser.find_inherent_methods(content)
+            } else {
+                Vec::new()
+            },
+            trait_impl_methods: parser.find_trait_impl_methods(content),
+            type_parameter_bounds: if index_type_parameters {
+                parser.find_type_parameter_bounds(content)
+            } else {
+                Vec::new()
+            },
+            uses: parser.find_uses_with_position(content),
+            defines: parser.find_defines(content),
+            variable_types: parser.find_variable_types(content),
+        }
+    }
+}
+
+/// The indexed version of a file that is being re-indexed
+#[derive(Default)]
+struct PreviousVersion {
+    /// Symbol IDs not yet re-anchored to a symbol of the new version
+    anchors: SymbolAnchors,
+    /// Range and doc comment hash of every previous symbol
+    details: std::collections::HashMap<SymbolId, (crate::Range, Option<String>)>,
+    imports: Vec<crate::parsing::Import>,
+    /// Set once any symbol or import was added, changed, moved or removed
+    code_changed: bool,
+    updates: crate::indexing::SymbolUpdates,
+}
+
+impl PreviousVersion {
+    /// Compare a symbol re-anchored by its unchanged code with its previous version
+    fn compare_reanchored(&mut self, symbol: &Symbol) -> SymbolChange {
+        let Some((range, doc_hash)) = self.details.get(&symbol.id) else {
+            self.code_changed = true;
+            return SymbolChange::Code;
+        };
+        if *range != symbol.range {
+            self.code_changed = true;
+        }
+        if *doc_hash == doc_comment_hash(symbol) {
+            self.updates.unchanged += 1;
+            SymbolChange::Unchanged
+        } else {
+            self.updates.doc_only += 1;
+            SymbolChange::DocOnly
+        }
+    }
+
+    /// Count symbols that were not re-anchored and return the previous IDs left over
+    ///
+    /// A new symbol whose name and kind match a leftover counts as changed code,
+    /// any other as added; leftovers without a match were removed.
+    fn finish<'a>(
+        &mut self,
+        unmatched: impl Iterator<Item = (&'a str, crate::SymbolKind)>,
+    ) -> Vec<SymbolId> {
+        let mut leftovers: std::collections::HashMap<(&str, crate::SymbolKind), usize> =
+            std::collections::HashMap::new();
+        for ((name, kind, _), ids) in &self.anchors {
+            *leftovers.entry((name.as_str(), *kind)).or_default() += ids.len();
+        }
+        for (name, kind) in unmatched {
+            self.code_changed = true;
+            match leftovers.get_mut(&(name, kind)).filter(|count| **count > 0) {
+                Some(count) => {
+                    *count -= 1;
+                    self.updates.code_changed += 1;
+                }
+                None => self.updates.added += 1,
+            }
+        }
+        let removed: usize = leftovers.values().sum();
+        self.updates.removed += removed;
+        if removed > 0 {
+            self.code_changed = true;
+        }
+        self.anchors.drain().flat_map(|(_, ids)| ids).collect()
+    }
+
+    /// Note a change in the file's imports, which feed relationship resolution
+    fn compare_imports(&mut self, imports: &[crate::parsing::Import]) {
+        let key = |import: &crate::parsing::Import| {
+            (
+                import.path.clone(),
+                import.alias.clone(),
+                import.is_glob,
+                import.is_type_only,
+            )
+        };
+        let mut previous: Vec<_> = self.imports.iter().map(key).collect();
+        let mut current: Vec<_> = imports.iter().map(key).collect();
+        previous.sort();
+        current.sort();
+        if previous != current {
+            self.code_changed = true;
+        }
+    }
+}
+
+/// Decode source bytes and hash them
+///
+/// Binary content (NUL bytes near the start) and content that is not valid
+/// UTF-8 is rejected with `IndexError::BinaryContent` rather than being
+/// parsed into garbage symbols.
+fn decode_source(path: &Path, bytes: Vec<u8>) -> IndexResult<(String, String)> {
+    // Binary sniffing window, same heuristic as git and ripgrep
+    const SNIFF_LEN: usize = 8 * 1024;
+
+    if bytes[..bytes.len().min(SNIFF_LEN)].contains(&0) {
+        return Err(IndexError::BinaryContent {
+            path: path.to_path_buf(),
+            reason: "contains NUL bytes".to_string(),
+        });
+    }
+    let content = String::from_utf8(bytes).map_err(|e| IndexError::BinaryContent {
+        path: path.to_path_buf(),
+        reason: format!("invalid UTF-8 at byte {}", e.utf8_error().valid_up_to()),
+    })?;
+
+    let hash = calculate_hash(&content);
+    Ok((content, hash))
+}
+
+/// Hash of a symbol's doc comment, compared separately from its code
+fn doc_comment_hash(symbol: &Symbol) -> Option<String> {
+    symbol.doc_comment.as_deref().map(calculate_hash)
+}
+
+/// Symbols of the file being indexed by name, sharing one copy of each name
+/// with [`SymbolLines`] and [`CallerSpans`]
+type SymbolMap = std::collections::HashMap<Rc<str>, SymbolId>;
+
+/// Symbols of the file being indexed keyed by (name, start line)
+type SymbolLines = std::collections::HashMap<(Rc<str>, u32), SymbolId>;
+
+/// Functions and methods of the file being indexed with their ranges
+type CallerSpans = Vec<(crate::Range, SymbolId, Rc<str>)>;
+
+/// The function or method a call at `at` is made from, when the parser's
+/// caller name is not itself a symbol name in the file
+///
+/// Python records methods as `Class.method` but reports calls from
+/// `method`; the innermost function or method with that name (or ending in
+/// `.method`) that encloses the call site is the caller.
+fn enclosing_caller(spans: &CallerSpans, caller: &str, at: &crate::Range) -> Option<SymbolId> {
+    spans
+        .iter()
+        .filter(|(range, _, name)| {
+            let named = &**name == caller
+                || name
+                    .strip_suffix(caller)
+                    .is_some_and(|owner| owner.ends_with('.'));
+            named && range.contains(at.start_line, at.start_column)
+        })
+        .max_by_key(|(range, _, _)| (range.start_line, range.start_column))
+        .map(|(_, id, _)| *id)
+}
+
 /// The main indexer struct that handles parsing and indexing of source code
 pub struct SimpleIndexer {
     parser_factory: ParserFactory,
@@ -96,6 +442,14 @@ pub struct SimpleIndexer {
     file_behaviors: std::collections::HashMap<FileId, Box<dyn crate::parsing::LanguageBehavior>>,
     /// Indexed directory paths (canonicalized) to track which directories are currently indexed
     indexed_paths: std::collections::HashSet<std::path::PathBuf>,
+    /// Identifier occurrences, present when `indexing.index_occurrences` is enabled
+    occurrences: Option<crate::storage::OccurrenceIndex>,
+    /// Glob-based language overrides from `[languages.overrides]`
+    language_overrides: LanguageOverrides,
+    /// False once any file was indexed with `indexing.resolve_relationships` off
+    relationships_indexed: bool,
+    /// How symbols of re-indexed files changed since this indexer was created
+    symbol_updates: crate::indexing::SymbolUpdates,
 }
 
 impl Default for SimpleIndexer {
@@ -134,6 +488,7 @@ impl SimpleIndexer {
 
         let mut indexer = Self {
             parser_factory: ParserFactory::new(settings.clone()),
+            language_overrides: LanguageOverrides::from_settings(&settings),
             settings,
             document_index,
             symbol_cache,
@@ -148,36 +503,59 @@ impl SimpleIndexer {
             file_languages: std::collections::HashMap::new(),
             file_behaviors: std::collections::HashMap::new(),
             indexed_paths: std::collections::HashSet::new(),
+            occurrences: None,
+            relationships_indexed: true,
+            symbol_updates: Default::default(),
         };
 
         // Try to load symbol cache for fast lookups
         if let Err(e) = indexer.load_symbol_cache() {
             debug_print!(indexer, "Could not load symbol cache: {e}");
         }
+        indexer.load_occurrences();
 
         indexer
     }
 
     /// Create indexer with lazy initialization for faster CLI startup
     pub fn with_settings_lazy(settings: Arc<Settings>) -> Self {
-        // Construct the full index path
+        let tantivy_path = Self::tantivy_path(&settings);
+        let document_index =
+            DocumentIndex::new(tantivy_path, &settings).expect("Failed to create Tantivy index");
+        Self::with_document_index(settings, document_index)
+    }
+
+    /// Open an existing index for queries only
+    ///
+    /// Like `with_settings_lazy`, but Tantivy is opened without write support:
+    /// no index writer or writer lock is ever created, so several query processes
+    /// can share one index. Any attempt to index or remove files fails.
+    pub fn with_settings_read_only(settings: Arc<Settings>) -> IndexResult<Self> {
+        let tantivy_path = Self::tantivy_path(&settings);
+        let document_index =
+            DocumentIndex::open_read_only(&tantivy_path, &settings).map_err(|e| {
+                IndexError::LoadError {
+                    path: tantivy_path,
+                    source: Box::new(e),
+                }
+            })?;
+        Ok(Self::with_document_index(settings, document_index))
+    }
+
+    /// Tantivy data always goes under index_path/tantivy
+    fn tantivy_path(settings: &Settings) -> PathBuf {
         let index_base = if let Some(ref workspace_root) = settings.workspace_root {
             workspace_root.join(&settings.index_path)
         } else {
             settings.index_path.clone()
         };
+        index_base.join("tantivy")
+    }
 
-        // Tantivy data always goes under index_path/tantivy
-        let tantivy_path = index_base.join("tantivy");
-
-        let document_index =
-            DocumentIndex::new(tantivy_path, &settings).expect("Failed to create Tantivy index");
-
-        #[allow(unused_variables)]
-        let debug = settings.debug;
-
+    fn with_document_index(settings: Arc<Settings>, document_index: DocumentIndex) -> Self {
         let mut indexer = Self {
             parser_factory: ParserFactory::new(settings.clone()),
+            language_overrides: LanguageOverrides::from_settings(&settings),
             settings,
             document_index,
             symbol_cache: None,
@@ -192,6 +570,9 @@ impl SimpleIndexer {
             file_languages: std::collections::HashMap::new(),
             file_behaviors: std::collections::HashMap::new(),
             indexed_paths: std::collections::HashSet::new(),
+            occurrences: None,
+            relationships_indexed: true,
+            symbol_updates: Default::default(),
         };
 
         // Resolution system now handled through LanguageBehavior:
@@ -208,10 +589,53 @@ impl SimpleIndexer {
         if let Err(e) = indexer.load_symbol_cache() {
             debug_print!(indexer, "Could not load symbol cache: {e}");
         }
+        indexer.load_occurrences();
 
         indexer
     }
 
+    /// Whether every indexed file had its relationships resolved
+    ///
+    /// False when any part of the index was built with
+    /// `indexing.resolve_relationships = false`; call graph queries are then incomplete.
+    pub fn relationships_indexed(&self) -> bool {
+        self.relationships_indexed
+    }
+
+    /// How symbols of re-indexed files changed since this indexer was created
+    ///
+    /// Only files that were already indexed are counted.
+    pub fn symbol_updates(&self) -> crate::indexing::SymbolUpdates {
+        self.symbol_updates
+    }
+
+    /// Fail with a clear message when relationships were not indexed
+    ///
+    /// Call graph queries use this instead of returning empty results.
+    pub fn ensure_relationships_indexed(&self) -> IndexResult<()> {
+        if self.relationships_indexed {
+            return Ok(());
+        }
+        Err(IndexError::General(
+            "Relationships were not indexed (indexing.resolve_relationships = false or --no-relationships). Set indexing.resolve_relationships = true and re-index with --force".to_string(),
+        ))
+    }
+
+    /// Restore the relationship state recorded in the index metadata
+    pub fn set_relationships_indexed(&mut self, indexed: bool) {
+        self.relationships_indexed = indexed;
+    }
+
+    /// Whether `path` maps to a language this indexer can parse
+    pub fn supports_file(&self, path: &Path) -> bool {
+        self.detect_language(path).is_ok()
+    }
+
+    /// Whether the index was opened with `with_settings_read_only`
+    pub fn is_read_only(&self) -> bool {
+        self.document_index.is_read_only()
+    }
+
     /// Create from loaded data (compatibility method)
     /// With Tantivy-only architecture, this just creates a new instance
     #[deprecated(note = "Use new() or with_settings() instead")]
@@ -251,12 +675,23 @@ impl SimpleIndexer {
 
     /// Enable semantic search for documentation.
     ///
-    /// Uses the model specified in settings (semantic_search.model).
+    /// Uses the model specified in settings (semantic_search.model), read from
+    /// semantic_search.model_path when that is set.
     pub fn enable_semantic_search(&mut self) -> IndexResult<()> {
         let model_name = &self.settings.semantic_search.model;
 
-        match SimpleSemanticSearch::from_model_name(model_name) {
-            Ok(search) => {
+        let search = match self.settings.semantic_model_path() {
+            Some(model_path) => SimpleSemanticSearch::from_model_path(
+                model_name,
+                &model_path,
+                self.embedding_threads(),
+            ),
+            None => SimpleSemanticSearch::from_model_name(model_name, self.embedding_threads()),
+        };
+        match search {
+            Ok(mut search) => {
+                search.set_mmap(self.settings.semantic_search.mmap);
+                search.set_ann_config(self.settings.semantic_search.ann.clone());
                 self.semantic_search = Some(Arc::new(Mutex::new(search)));
                 Ok(())
             }
@@ -266,6 +701,18 @@ impl SimpleIndexer {
         }
     }
 
+    /// Number of threads the Tantivy writer uses
+    pub fn writer_threads(&self) -> usize {
+        self.document_index.writer_threads()
+    }
+
+    /// Threads semantic search embeds on, from `indexing.parallel_threads`
+    ///
+    /// `None` (for a setting of 0) lets the embedding session use every core.
+    pub fn embedding_threads(&self) -> Option<usize> {
+        Some(self.settings.indexing.parallel_threads).filter(|&threads| threads > 0)
+    }
+
     /// Check if semantic search is enabled
     #[must_use]
     pub fn has_semantic_search(&self) -> bool {
@@ -299,8 +746,21 @@ impl SimpleIndexer {
     ) -> Result<(), crate::semantic::SemanticSearchError> {
         debug_print!(self, "save_semantic_search called with path: {:?}", path);
         if let Some(semantic) = &self.semantic_search {
+            let mut semantic = semantic.lock().unwrap();
+
+            // Build the approximate index once embedding is done, before it is persisted
+            match semantic.refresh_ann_index() {
+                Ok(true) => debug_print!(
+                    self,
+                    "Built approximate index over {} embeddings",
+                    semantic.embedding_count()
+                ),
+                Ok(false) => {}
+                Err(e) => eprintln!("Warning: {e}"),
+            }
+
             debug_print!(self, "Semantic search exists, calling save()");
-            let result = semantic.lock().unwrap().save(path);
+            let result = semantic.save(path);
             match &result {
                 Ok(_) => debug_print!(self, "Semantic save() succeeded"),
                 Err(e) => eprintln!("Semantic save() failed: {e}"),
@@ -340,9 +800,34 @@ impl SimpleIndexer {
         }
 
         // Try to load semantic search
-        match SimpleSemanticSearch::load(path) {
-            Ok(semantic) => {
+        let model_path = self.settings.semantic_model_path();
+        match SimpleSemanticSearch::load_with_options(
+            path,
+            model_path.as_deref(),
+            self.settings.semantic_search.mmap,
+            self.embedding_threads(),
+        ) {
+            Ok(mut semantic) => {
+                semantic.set_ann_config(self.settings.semantic_search.ann.clone());
                 let count = semantic.embedding_count();
+
+                // Keep searching with the model the vectors came from, but say so
+                let configured = &self.settings.semantic_search.model;
+                if let Some(stored) = semantic.metadata().map(|m| &m.model_name) {
+                    if stored != configured {
+                        eprintln!(
+                            "Warning: {}",
+                            crate::semantic::SemanticSearchError::ModelMismatch {
+                                stored: stored.clone(),
+                                configured: configured.clone(),
+                                suggestion: format!(
+                                    "Searches keep using '{stored}' until you rebuild the semantic index with: codanna index --force"
+                                ),
+                            }
+                        );
+                    }
+                }
+
                 self.semantic_search = Some(Arc::new(Mutex::new(semantic)));
                 if info {
                     eprintln!("Loaded semantic search with {count} embeddings");
@@ -395,6 +880,10 @@ impl SimpleIndexer {
             eprintln!("Warning: Failed to build symbol cache: {e}");
         }
 
+        if let Err(e) = self.save_occurrences() {
+            eprintln!("Warning: Failed to save occurrence index: {e}");
+        }
+
         Ok(())
     }
 
@@ -510,24 +999,46 @@ impl SimpleIndexer {
         // This ensures file reading always works
         let (content, content_hash) = self.read_file_with_hash(path)?;
 
+        self.index_content_internal(normalized_path, path_str, &content, content_hash, force)
+    }
+
+    /// Index source content stored under `path_str`, wherever it was read from
+    ///
+    /// `path` picks the language and module path. Content whose hash matches
+    /// the indexed hash is left alone unless `force` is set.
+    fn index_content_internal(
+        &mut self,
+        path: &Path,
+        path_str: &str,
+        content: &str,
+        content_hash: String,
+        force: bool,
+    ) -> IndexResult<crate::IndexingResult> {
+        // Symbols from the previous version of this file, for re-anchoring by content
+        let mut previous = None;
+
         // Check if file already exists by querying Tantivy
-        if let Ok(Some((file_id, existing_hash))) = self.document_index.get_file_info(path_str) {
-            if !force && existing_hash == content_hash {
+        let existing = self.document_index.get_file_info(path_str).ok().flatten();
+        if let Some((file_id, existing_hash)) = &existing {
+            if !force && *existing_hash == content_hash {
                 // File hasn't changed, skip re-indexing
-                return Ok(crate::IndexingResult::Cached(file_id));
+                self.refresh_indexed_at(*file_id, path_str, existing_hash)?;
+                return Ok(crate::IndexingResult::Cached(*file_id));
             }
+        }
 
-            // File has changed or force re-indexing
-            // First, collect symbols that will be removed (for semantic search cleanup)
-            let symbols_to_remove = if self.has_semantic_search() {
-                self.document_index
-                    .find_symbols_by_file(file_id)
-                    .ok()
-                    .map(|symbols| symbols.into_iter().map(|s| s.id).collect::<Vec<_>>())
-            } else {
-                None
-            };
+        // Parse before changing the index, so a file that fails or times out
+        // keeps its previously indexed version
+        let parsed = self.parse_file_content(path, content)?;
 
+        if let Some((file_id, _)) = existing {
+            previous = Some(self.collect_previous_version(file_id)?);
+
+            if let Some(occurrences) = &mut self.occurrences {
+                occurrences.remove_file(file_id);
+            }
+
+            // File has changed or force re-indexing
             // Use remove_file_documents to remove ALL documents for this file path
             self.document_index
                 .remove_file_documents(path_str)
@@ -535,35 +1046,90 @@ impl SimpleIndexer {
                     operation: "remove_file_documents".to_string(),
                     cause: e.to_string(),
                 })?;
-
-            // Remove embeddings for the old symbols if semantic search is enabled
-            if let Some(symbol_ids) = symbols_to_remove {
-                if let Some(semantic) = &self.semantic_search {
-                    semantic.lock().unwrap().remove_embeddings(&symbol_ids);
-
-                    // CRITICAL: Save embeddings to disk after removal to prevent cache desync
-                    let semantic_path = self.settings.index_path.join("semantic");
-                    if let Err(e) = semantic.lock().unwrap().save(&semantic_path) {
-                        eprintln!(
-                            "Warning: Failed to save semantic search after embedding removal: {e}"
-                        );
-                    }
-                }
-            }
         }
 
         // Register or update file
         let file_id = self.register_file(path_str, content_hash)?;
 
+        if let Some(occurrences) = &mut self.occurrences {
+            occurrences.add_file(file_id, content);
+        }
+
         // Index the file content
-        // Pass normalized_path for consistent processing
-        self.reindex_file_content(normalized_path, path_str, file_id, &content)?;
+        self.reindex_file_content(path, path_str, file_id, content, parsed, previous)?;
 
         Ok(crate::IndexingResult::Indexed(file_id))
     }
 
+    /// Collect a file's indexed symbols and imports before it is re-indexed.
+    ///
+    /// Symbols are keyed by content hash so unchanged ones get re-anchored to
+    /// their previous `SymbolId`, which keeps incoming edges from other files valid.
+    fn collect_previous_version(&self, file_id: FileId) -> IndexResult<PreviousVersion> {
+        let hashed_symbols = self
+            .document_index
+            .find_symbol_hashes_by_file(file_id)
+            .map_err(|e| IndexError::TantivyError {
+                operation: "find_symbol_hashes_by_file".to_string(),
+                cause: e.to_string(),
+            })?;
+
+        let mut previous = PreviousVersion {
+            imports: self
+                .document_index
+                .get_imports_for_file(file_id)
+                .unwrap_or_default(),
+            ..Default::default()
+        };
+        for (symbol, hash) in hashed_symbols {
+            previous
+                .details
+                .insert(symbol.id, (symbol.range, doc_comment_hash(&symbol)));
+            previous
+                .anchors
+                .entry((symbol.name.to_string(), symbol.kind, hash))
+                .or_default()
+                .push(symbol.id);
+        }
+
+        // Highest IDs first so pop() hands out IDs in original source order
+        for ids in previous.anchors.values_mut() {
+            ids.sort_unstable_by(|a, b| b.cmp(a));
+        }
+
+        Ok(previous)
+    }
+
+    /// Delete the outgoing relationships of a file's previous symbols
+    ///
+    /// Re-extraction recreates them; incoming edges from other files are kept.
+    fn delete_previous_relationships(&self, previous: &PreviousVersion) -> IndexResult<()> {
+        for id in previous.details.keys() {
+            self.document_index
+                .delete_outgoing_relationships(*id)
+                .map_err(|e| IndexError::TantivyError {
+                    operation: "delete_outgoing_relationships".to_string(),
+                    cause: e.to_string(),
+                })?;
+        }
+        Ok(())
+    }
+
     /// Remove a file and all its symbols from the index
     pub fn remove_file(&mut self, path: impl AsRef<Path>) -> IndexResult<()> {
+        if self.remove_file_in_pass(path)? > 0 {
+            self.save_semantic_after_removal();
+        }
+        Ok(())
+    }
+
+    /// Remove a file as one step of a removal pass
+    ///
+    /// Same as [`Self::remove_file`], except that the semantic store is not
+    /// saved: the caller saves it once with
+    /// [`Self::save_semantic_after_removal`] when the pass removed any
+    /// symbols. Returns the number of symbols removed.
+    fn remove_file_in_pass(&mut self, path: impl AsRef<Path>) -> IndexResult<usize> {
         let path = path.as_ref();
         let path_display = path.display();
         if self.settings.debug {
@@ -590,63 +1156,66 @@ impl SimpleIndexer {
             eprintln!("  get_file_info result: {file_info:?}");
         }
 
-        let symbols_to_remove = if let Some(info) = file_info {
-            let file_id = info.0;
-
-            // Get all symbols for this file before removing
-            let symbols = self
-                .document_index
-                .find_symbols_by_file(file_id)
-                .map_err(|e| IndexError::TantivyError {
-                    operation: "find_symbols_by_file".to_string(),
-                    cause: e.to_string(),
-                })?;
+        match file_info {
+            Some((file_id, _)) => {
+                let symbol_count = self.remove_file_by_id_in_pass(file_id)?;
+                if self.settings.debug {
+                    eprintln!("  Removed {symbol_count} symbols from {path_str}");
+                }
+                Ok(symbol_count)
+            }
+            None => {
+                // File not in index, nothing to remove
+                if self.settings.debug {
+                    eprintln!("  File not found in index: {path_str}");
+                }
+                Ok(0)
+            }
+        }
+    }
 
-            // Start batch for write operations
-            self.document_index
-                .start_batch()
-                .map_err(|e| IndexError::TantivyError {
-                    operation: "start_batch".to_string(),
-                    cause: e.to_string(),
-                })?;
+    /// Remove an indexed file by ID
+    ///
+    /// Deletes the file's symbols, every relationship from or to them, its
+    /// imports and Tantivy documents in one batch, then drops its occurrences
+    /// and semantic vectors. If any deletion fails the batch is rolled back and
+    /// the index is left unchanged. Returns the number of symbols removed.
+    pub fn remove_file_by_id(&mut self, file_id: FileId) -> IndexResult<usize> {
+        let removed = self.remove_file_by_id_in_pass(file_id)?;
+        if removed > 0 {
+            self.save_semantic_after_removal();
+        }
+        Ok(removed)
+    }
 
-            // CRITICAL: Delete imports for this file to maintain consistency
-            // This prevents stale import metadata from being used after file deletion
-            self.document_index
-                .delete_imports_for_file(file_id)
-                .map_err(|e| IndexError::TantivyError {
-                    operation: "delete_imports_for_file".to_string(),
-                    cause: e.to_string(),
-                })?;
+    /// [`Self::remove_file_by_id`] without saving the semantic store
+    fn remove_file_by_id_in_pass(&mut self, file_id: FileId) -> IndexResult<usize> {
+        let path_str = self
+            .get_file_path(file_id)
+            .ok_or(IndexError::FileNotFound { id: file_id })?;
 
-            symbols
-        } else {
-            // File not in index, nothing to remove
-            if self.settings.debug {
-                eprintln!("  File not found in index: {path_str}");
-            }
-            return Ok(());
-        };
+        // Get all symbols for this file before removing
+        let symbols = self
+            .document_index
+            .find_symbols_by_file(file_id)
+            .map_err(|e| IndexError::TantivyError {
+                operation: "find_symbols_by_file".to_string(),
+                cause: e.to_string(),
+            })?;
 
-        // Remove ALL documents for this file from Tantivy
+        // Start batch for write operations
         self.document_index
-            .remove_file_documents(path_str)
+            .start_batch()
             .map_err(|e| IndexError::TantivyError {
-                operation: "remove_file_documents".to_string(),
+                operation: "start_batch".to_string(),
                 cause: e.to_string(),
             })?;
 
-        // Remove embeddings for the symbols if semantic search is enabled
-        if !symbols_to_remove.is_empty() {
-            if let Some(semantic) = &self.semantic_search {
-                let symbol_ids: Vec<SymbolId> = symbols_to_remove.iter().map(|s| s.id).collect();
-                semantic.lock().unwrap().remove_embeddings(&symbol_ids);
+        if let Err(e) = self.delete_file_documents(file_id, &path_str, &symbols) {
+            if let Err(rollback_err) = self.document_index.rollback_batch() {
+                eprintln!("Warning: Failed to roll back partial file removal: {rollback_err}");
             }
-        }
-
-        if self.settings.debug {
-            let symbol_count = symbols_to_remove.len();
-            eprintln!("  Removed {symbol_count} symbols from {path_str}");
+            return Err(e);
         }
 
         // Commit the changes to persist them
@@ -660,27 +1229,105 @@ impl SimpleIndexer {
             eprintln!("  Changes committed to index");
         }
 
+        // The documents are gone; drop in-memory state kept for the file
+        if let Some(occurrences) = &mut self.occurrences {
+            occurrences.remove_file(file_id);
+        }
+        self.file_languages.remove(&file_id);
+        self.file_behaviors.remove(&file_id);
+        self.trait_symbols_by_file.remove(&file_id);
+        self.method_calls_by_file.remove(&file_id);
+        self.variable_types
+            .retain(|(variable_file, _), _| *variable_file != file_id);
+
+        // Remove embeddings for the symbols if semantic search is enabled;
+        // the store is saved once the removal pass is done
+        if !symbols.is_empty() {
+            if let Some(semantic) = &self.semantic_search {
+                let symbol_ids: Vec<SymbolId> = symbols.iter().map(|s| s.id).collect();
+                semantic.lock().unwrap().remove_embeddings(&symbol_ids);
+            }
+        }
+
         // Rebuild symbol cache after file removal to remove stale entries
         if let Err(e) = self.build_symbol_cache() {
             eprintln!("Warning: Failed to rebuild symbol cache after file removal: {e}");
         }
+        if let Err(e) = self.save_occurrences() {
+            eprintln!("Warning: Failed to save occurrence index after file removal: {e}");
+        }
 
-        Ok(())
+        Ok(symbols.len())
     }
 
-    /// Read file content and calculate its hash
-    /// Uses lossy UTF-8 conversion to handle files with invalid encoding
-    fn read_file_with_hash(&self, path: &Path) -> IndexResult<(String, String)> {
-        // Read as bytes first, then convert with lossy UTF-8
-        // This handles BOM, Windows-1252, and other non-UTF-8 encodings
-        let bytes = fs::read(path).map_err(|e| IndexError::FileRead {
+    /// Save the semantic store so the vectors on disk match the index after
+    /// files were removed
+    fn save_semantic_after_removal(&self) {
+        if let Some(semantic) = &self.semantic_search {
+            let semantic_path = self.settings.index_path.join("semantic");
+            if let Err(e) = semantic.lock().unwrap().save(&semantic_path) {
+                eprintln!("Warning: Failed to save semantic search after file removal: {e}");
+            }
+        }
+    }
+
+    /// Queue deletion of everything stored for a file in the current batch
+    fn delete_file_documents(
+        &self,
+        file_id: FileId,
+        path_str: &str,
+        symbols: &[Symbol],
+    ) -> IndexResult<()> {
+        // Edges from other files into this one would otherwise point at missing symbols
+        for symbol in symbols {
+            self.document_index
+                .delete_relationships_for_symbol(symbol.id)
+                .map_err(|e| IndexError::TantivyError {
+                    operation: "delete_relationships_for_symbol".to_string(),
+                    cause: e.to_string(),
+                })?;
+        }
+
+        // CRITICAL: Delete imports for this file to maintain consistency
+        // This prevents stale import metadata from being used after file deletion
+        self.document_index
+            .delete_imports_for_file(file_id)
+            .map_err(|e| IndexError::TantivyError {
+                operation: "delete_imports_for_file".to_string(),
+                cause: e.to_string(),
+            })?;
+
+        // Remove ALL documents for this file from Tantivy
+        self.document_index
+            .remove_file_documents(path_str)
+            .map_err(|e| IndexError::TantivyError {
+                operation: "remove_file_documents".to_string(),
+                cause: e.to_string(),
+            })
+    }
+
+    /// Size of the file when it exceeds `indexing.max_file_size_bytes`
+    ///
+    /// Only file metadata is read, so oversized files are never loaded into memory.
+    fn oversized_file_len(&self, path: &Path) -> Option<u64> {
+        let limit = self.settings.indexing.max_file_size_bytes;
+        if limit == 0 {
+            return None;
+        }
+        fs::metadata(path)
+            .ok()
+            .map(|metadata| metadata.len())
+            .filter(|&len| len > limit)
+    }
+
+    /// Read file content and calculate its hash, rejecting binary files
+    /// as [`decode_source`] does
+    fn read_file_with_hash(&self, path: &Path) -> IndexResult<(String, String)> {
+        let bytes = fs::read(path).map_err(|e| IndexError::FileRead {
             path: path.to_path_buf(),
             source: e,
         })?;
-        let content = String::from_utf8_lossy(&bytes).into_owned();
-
-        let hash = calculate_hash(&content);
-        Ok((content, hash))
+        decode_source(path, bytes)
     }
 
     /// Register a new file in the index
@@ -722,6 +1369,53 @@ impl SimpleIndexer {
         Ok(file_id)
     }
 
+    /// Detect a file's language and run its parser within `indexing.parse_timeout_ms`
+    ///
+    /// One deadline covers the symbols, imports and relationships: it cancels
+    /// tree-sitter in every pass and stops the symbol traversal (see
+    /// [`ParseDeadline`]), and whatever was extracted by then is discarded. A
+    /// timeout of 0 parses without a deadline.
+    fn parse_file_content<'a>(&self, path: &Path, content: &'a str) -> IndexResult<ParsedFile<'a>> {
+        let language_id = self.detect_language(path)?;
+        let crate::parsing::ParserWithBehavior {
+            mut parser,
+            behavior,
+        } = self.create_parser_with_behavior(language_id)?;
+        let mut symbol_counter = self.get_next_symbol_counter()?;
+        // Replaced by the real ID once the file is registered
+        let placeholder = FileId::new(1).expect("1 is a valid file ID");
+
+        let timeout_ms = self.settings.indexing.parse_timeout_ms;
+        let deadline = (timeout_ms > 0)
+            .then(|| ParseDeadline::start(std::time::Duration::from_millis(timeout_ms)));
+        let mut symbols = parser.parse(content, placeholder, &mut symbol_counter);
+        behavior.attach_attributes(content, &mut symbols);
+        let imports = parser.find_imports(content, placeholder);
+        let relations = self.settings.indexing.resolve_relationships.then(|| {
+            FileRelations::extract(
+                parser.as_mut(),
+                behavior.as_ref(),
+                content,
+                self.settings.indexing.index_type_parameters,
+            )
+        });
+        if deadline.as_ref().is_some_and(ParseDeadline::expired) {
+            return Err(IndexError::ParseTimeout {
+                path: path.to_path_buf(),
+                timeout_ms,
+            });
+        }
+
+        Ok(ParsedFile {
+            language_id,
+            behavior,
+            symbols,
+            symbol_counter,
+            imports,
+            relations,
+        })
+    }
+
     /// Index or re-index file content
     fn reindex_file_content(
         &mut self,
@@ -729,6 +1423,8 @@ impl SimpleIndexer {
         path_str: &str,
         file_id: FileId,
         content: &str,
+        parsed: ParsedFile,
+        mut previous: Option<PreviousVersion>,
     ) -> IndexResult<FileId> {
         debug_print!(
             self,
@@ -736,10 +1432,20 @@ impl SimpleIndexer {
             path,
             path.is_absolute()
         );
-        let language_id = self.detect_language(path)?;
-        let parser_with_behavior = self.create_parser_with_behavior(language_id)?;
-        let mut parser = parser_with_behavior.parser;
-        let behavior = parser_with_behavior.behavior;
+        let ParsedFile {
+            language_id,
+            behavior,
+            mut symbols,
+            mut symbol_counter,
+            mut imports,
+            relations,
+        } = parsed;
+        for symbol in &mut symbols {
+            symbol.file_id = file_id;
+        }
+        for import in &mut imports {
+            import.file_id = file_id;
+        }
         let module_path = self.calculate_module_path(path, &*behavior);
 
         // Store language ID for this file to enable language-specific resolution
@@ -761,9 +1467,9 @@ impl SimpleIndexer {
             debug_print!(self, "No module path for file {:?}", path);
         }
 
-        let mut symbol_counter = self.get_next_symbol_counter()?;
-        let symbol_map = self.extract_and_store_symbols(
-            &mut parser,
+        let (symbol_map, symbol_lines, caller_spans) = self.extract_and_store_symbols(
+            symbols,
+            imports,
             content,
             file_id,
             path_str,
@@ -771,14 +1477,37 @@ impl SimpleIndexer {
             behavior.as_ref(),
             &mut symbol_counter,
             language_id,
+            previous.as_mut(),
         )?;
-        self.extract_and_store_relationships(
-            &mut parser,
-            content,
-            file_id,
-            behavior.as_ref(),
-            &symbol_map,
-        )?;
+
+        // Relationships stay valid when only doc comments changed: same symbols
+        // at the same positions with the same imports
+        let relationships_current = match &previous {
+            Some(previous) => {
+                self.symbol_updates += previous.updates;
+                !previous.code_changed && self.settings.indexing.resolve_relationships
+            }
+            None => false,
+        };
+        if let (false, Some(previous)) = (relationships_current, &previous) {
+            self.delete_previous_relationships(previous)?;
+        }
+
+        if relationships_current {
+            debug_print!(self, "Keeping relationships of {:?} (doc-only edit)", path);
+            self.symbol_updates.relationships_kept += 1;
+        } else if let Some(relations) = relations {
+            self.extract_and_store_relationships(
+                relations,
+                file_id,
+                behavior.as_ref(),
+                &symbol_map,
+                &symbol_lines,
+                &caller_spans,
+            )?;
+        } else {
+            self.relationships_indexed = false;
+        }
         self.update_symbol_counter(&symbol_counter)?;
 
         // Store behavior for persistent state (imports, etc.) - AFTER it's been configured
@@ -787,8 +1516,15 @@ impl SimpleIndexer {
         Ok(file_id)
     }
 
-    /// Detect the programming language from file extension using the registry
+    /// Detect the programming language of a file
+    ///
+    /// `[languages.overrides]` patterns are consulted first, then the extension
+    /// is looked up in the registry.
     fn detect_language(&self, path: &Path) -> IndexResult<LanguageId> {
+        if let Some(language_id) = self.language_overrides.language_for(path) {
+            return Ok(language_id);
+        }
+
         let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("");
 
         // Use the registry for language detection
@@ -893,7 +1629,8 @@ impl SimpleIndexer {
     /// Extract symbols from content and store them in Tantivy
     fn extract_and_store_symbols(
         &mut self,
-        parser: &mut Box<dyn crate::parsing::LanguageParser>,
+        mut symbols: Vec<Symbol>,
+        imports: Vec<crate::parsing::Import>,
         content: &str,
         file_id: FileId,
         path_str: &str,
@@ -901,11 +1638,14 @@ impl SimpleIndexer {
         behavior: &dyn crate::parsing::LanguageBehavior,
         symbol_counter: &mut SymbolCounter,
         language_id: LanguageId,
-    ) -> IndexResult<std::collections::HashMap<String, SymbolId>> {
-        let symbols = parser.parse(content, file_id, symbol_counter);
+        mut previous: Option<&mut PreviousVersion>,
+    ) -> IndexResult<(SymbolMap, SymbolLines, CallerSpans)> {
+        crate::parsing::assign_parents(&mut symbols);
 
-        // Extract and register imports
-        let imports = parser.find_imports(content, file_id);
+        // Register imports
+        if let Some(previous) = previous.as_deref_mut() {
+            previous.compare_imports(&imports);
+        }
         if !imports.is_empty() {
             debug_print!(
                 self,
@@ -943,21 +1683,73 @@ impl SimpleIndexer {
             std::collections::HashMap::new();
 
         // Build symbol map for relationship resolution
-        let mut symbol_map = std::collections::HashMap::new();
+        let mut symbol_map = SymbolMap::new();
+        let mut symbol_lines = SymbolLines::new();
+        let mut caller_spans = CallerSpans::new();
+
+        // Re-anchor unchanged symbols to their previous ID so edges survive line shifts
+        let mut hashes = Vec::with_capacity(symbols.len());
+        let mut changes = Vec::with_capacity(symbols.len());
+        let mut reanchored = std::collections::HashMap::new();
+        let line_starts = LineStarts::new(content);
+        for symbol in &mut symbols {
+            let symbol_hash = line_starts.range_hash(content, &symbol.range);
+            let mut change = SymbolChange::Code;
+            if let (Some(previous), Some(hash)) = (previous.as_deref_mut(), &symbol_hash) {
+                let key = (symbol.name.to_string(), symbol.kind, hash.clone());
+                if let Some(previous_id) = previous.anchors.get_mut(&key).and_then(|ids| ids.pop())
+                {
+                    debug_print!(
+                        self,
+                        "Re-anchored '{}' to previous id {:?}",
+                        symbol.name,
+                        previous_id
+                    );
+                    reanchored.insert(symbol.id, previous_id);
+                    symbol.id = previous_id;
+                    change = previous.compare_reanchored(symbol);
+                }
+            }
+            hashes.push(symbol_hash);
+            changes.push(change);
+        }
+
+        // Previous symbols that were not re-anchored are gone along with their embeddings
+        if let Some(previous) = previous {
+            let unmatched = symbols
+                .iter()
+                .zip(&changes)
+                .filter(|(_, change)| **change == SymbolChange::Code)
+                .map(|(symbol, _)| (symbol.name.as_str(), symbol.kind));
+            let removed = previous.finish(unmatched);
+            self.remove_embeddings(&removed);
+        }
 
-        for mut symbol in symbols {
-            // Track trait symbols
-            trait_symbols.insert(symbol.name.to_string(), symbol.kind);
+        for ((mut symbol, symbol_hash), change) in symbols.into_iter().zip(hashes).zip(changes) {
+            // Track trait symbols; other kinds are never looked up
+            if symbol.kind == SymbolKind::Trait {
+                trait_symbols.insert(symbol.name.to_string(), symbol.kind);
+            }
 
             // Set the language_id on the symbol
             symbol.language_id = Some(language_id);
 
-            // Capture name and ID before configuring
-            let name = symbol.name.to_string();
+            // Parents may have been re-anchored as well
+            if let Some(parent) = symbol.parent.and_then(|id| reanchored.get(&id)) {
+                symbol.parent = Some(*parent);
+            }
+
+            // Capture name and ID before configuring; one copy of the name
+            // serves every lookup table
+            let name: Rc<str> = Rc::from(&*symbol.name);
             let id = symbol.id;
+            symbol_lines.insert((Rc::clone(&name), symbol.range.start_line), id);
+            if matches!(symbol.kind, SymbolKind::Function | SymbolKind::Method) {
+                caller_spans.push((symbol.range, id, Rc::clone(&name)));
+            }
 
             self.configure_symbol(&mut symbol, module_path, behavior);
-            self.store_symbol(symbol, path_str)?;
+            self.store_symbol(symbol, path_str, symbol_hash.as_deref(), change)?;
 
             // Map name to ID for relationship resolution
             symbol_map.insert(name, id);
@@ -966,7 +1758,7 @@ impl SimpleIndexer {
         // Store trait symbols for this file
         self.trait_symbols_by_file.insert(file_id, trait_symbols);
 
-        Ok(symbol_map)
+        Ok((symbol_map, symbol_lines, caller_spans))
     }
 
     /// Configure a symbol with module path and visibility
@@ -990,10 +1782,46 @@ impl SimpleIndexer {
         );
     }
 
+    /// Drop semantic embeddings of symbols and save so the vectors on disk match
+    fn remove_embeddings(&self, symbol_ids: &[SymbolId]) {
+        if symbol_ids.is_empty() {
+            return;
+        }
+        if let Some(semantic) = &self.semantic_search {
+            let mut semantic = semantic.lock().unwrap();
+            semantic.remove_embeddings(symbol_ids);
+
+            // CRITICAL: Save embeddings to disk after removal to prevent cache desync
+            let semantic_path = self.settings.index_path.join("semantic");
+            if let Err(e) = semantic.save(&semantic_path) {
+                eprintln!("Warning: Failed to save semantic search after embedding removal: {e}");
+            }
+        }
+    }
+
     /// Store a single symbol in Tantivy
-    fn store_symbol(&mut self, symbol: crate::Symbol, path_str: &str) -> IndexResult<()> {
+    ///
+    /// Embeddings are only rebuilt for the parts of the symbol that changed:
+    /// the doc embedding when its doc comment changed, the symbol text
+    /// embedding when its code changed.
+    fn store_symbol(
+        &mut self,
+        symbol: crate::Symbol,
+        path_str: &str,
+        content_hash: Option<&str>,
+        change: SymbolChange,
+    ) -> IndexResult<()> {
+        // A doc comment that was removed leaves nothing to embed
+        if change == SymbolChange::DocOnly && symbol.doc_comment.is_none() {
+            self.remove_embeddings(&[symbol.id]);
+        }
+
         // Index doc comment for semantic search if enabled
-        if let (Some(semantic), Some(doc)) = (&self.semantic_search, &symbol.doc_comment) {
+        let semantic = self
+            .semantic_search
+            .as_ref()
+            .filter(|_| change != SymbolChange::Unchanged);
+        if let (Some(semantic), Some(doc)) = (semantic, &symbol.doc_comment) {
             // Get the language for this symbol's file
             let language = self
                 .file_languages
@@ -1028,14 +1856,17 @@ impl SimpleIndexer {
 
         // Store the symbol in Tantivy
         self.document_index
-            .index_symbol(&symbol, path_str)
+            .index_symbol_with_hash(&symbol, path_str, content_hash)
             .map_err(|e| IndexError::TantivyError {
                 operation: "store_symbol".to_string(),
                 cause: e.to_string(),
             })?;
 
         // If vector support is enabled, prepare for embedding
-        if self.vector_engine.is_some() && self.embedding_generator.is_some() {
+        if self.vector_engine.is_some()
+            && self.embedding_generator.is_some()
+            && change == SymbolChange::Code
+        {
             let symbol_text =
                 create_symbol_text(&symbol.name, symbol.kind, symbol.signature.as_deref());
             self.pending_embeddings.push((symbol.id, symbol_text));
@@ -1044,20 +1875,34 @@ impl SimpleIndexer {
         Ok(())
     }
 
-    /// Extract relationships from content and store them
+    /// Store the relationships extracted from a file
     fn extract_and_store_relationships(
         &mut self,
-        parser: &mut Box<dyn crate::parsing::LanguageParser>,
-        content: &str,
+        relations: FileRelations<'_>,
         file_id: FileId,
         behavior: &dyn crate::parsing::LanguageBehavior,
-        symbol_map: &std::collections::HashMap<String, SymbolId>,
+        symbol_map: &SymbolMap,
+        symbol_lines: &SymbolLines,
+        caller_spans: &CallerSpans,
     ) -> IndexResult<()> {
         use std::collections::HashSet;
-        // Track relationships added in this file to avoid duplicates
-        let mut added: HashSet<(String, String, RelationKind)> = HashSet::new();
+        // Track relationships added in this file to avoid duplicates; callers
+        // sharing a name but not a symbol are kept apart
+        let mut added: HashSet<(String, Option<SymbolId>, String, RelationKind)> = HashSet::new();
         // 1. Function/method calls
-        let method_calls: Vec<MethodCall> = parser.find_method_calls(content);
+        let FileRelations {
+            method_calls,
+            calls: function_calls,
+            implementations,
+            derives,
+            extends,
+            inherent_methods,
+            trait_impl_methods,
+            type_parameter_bounds,
+            uses,
+            defines,
+            variable_types: var_types,
+        } = relations;
         debug_print!(
             self,
             "Found {} method calls in file {:?}",
@@ -1126,19 +1971,25 @@ impl SimpleIndexer {
             let metadata = method_call.receiver.as_ref().map(|receiver| {
                 RelationshipMetadata::new()
                     .at_position(method_call.range.start_line, method_call.range.start_column)
-                    .with_context(format!(
-                        "receiver:{},static:{}",
-                        receiver, method_call.is_static
+                    .with_call(CallMetadata::new(
+                        Some(receiver.clone()),
+                        method_call.is_static,
                     ))
             });
 
             let kind = behavior.map_relationship("calls");
+            let from_id = symbol_map
+                .get(method_call.caller.as_str())
+                .copied()
+                .or_else(|| {
+                    enclosing_caller(caller_spans, &method_call.caller, &method_call.range)
+                });
             if added.insert((
                 method_call.caller.clone(),
+                from_id,
                 method_call.method_name.clone(),
                 kind,
             )) {
-                let from_id = symbol_map.get(&method_call.caller).copied();
                 self.add_relationships_by_name(
                     from_id,
                     &method_call.caller,
@@ -1159,7 +2010,6 @@ impl SimpleIndexer {
         }
 
         // Process plain function calls
-        let function_calls = parser.find_calls(content);
         debug_print!(
             self,
             "Found {} function calls in file {:?}",
@@ -1183,8 +2033,16 @@ impl SimpleIndexer {
             );
 
             let kind = behavior.map_relationship("calls");
-            if added.insert((caller.to_string(), called_function.to_string(), kind)) {
-                let from_id = symbol_map.get(caller).copied();
+            let from_id = symbol_map
+                .get(caller)
+                .copied()
+                .or_else(|| enclosing_caller(caller_spans, caller, &range));
+            if added.insert((
+                caller.to_string(),
+                from_id,
+                called_function.to_string(),
+                kind,
+            )) {
                 self.add_relationships_by_name(
                     from_id,
                     caller,
@@ -1205,7 +2063,6 @@ impl SimpleIndexer {
         }
 
         // 2. Trait implementations
-        let implementations = parser.find_implementations(content);
         for (type_name, trait_name, _range) in implementations {
             debug_print!(
                 self,
@@ -1229,9 +2086,34 @@ impl SimpleIndexer {
             )?;
         }
 
-        // 2.3. Inheritance relationships (extends)
-        let extends = parser.find_extends(content);
-        for (derived_type, base_type, _range) in extends {
+        // 2.2. Derived trait implementations (`#[derive(Clone)]`), marked so
+        // they can be told apart from impl blocks
+        for (type_name, trait_name, range) in derives {
+            debug_print!(
+                self,
+                "Registering derived implementation: {} implements {}",
+                type_name,
+                trait_name
+            );
+            behavior.add_trait_impl(type_name.to_string(), trait_name.to_string(), file_id);
+            let from_id = symbol_map.get(type_name).copied();
+            self.add_relationships_by_name(
+                from_id,
+                type_name,
+                trait_name,
+                file_id,
+                behavior.map_relationship("implements"),
+                Some(
+                    RelationshipMetadata::new()
+                        .at_position(range.start_line, range.start_column)
+                        .with_context(RelationshipMetadata::DERIVED),
+                ),
+            )?;
+        }
+
+        // 2.3. Inheritance relationships (extends). The position of each base
+        // keeps multiple inheritance in declaration order
+        for (derived_type, base_type, range) in extends {
             debug_print!(
                 self,
                 "Registering inheritance: {} extends {}",
@@ -1245,58 +2127,96 @@ impl SimpleIndexer {
                 base_type,
                 file_id,
                 behavior.map_relationship("extends"),
-                None,
+                Some(RelationshipMetadata::new().at_position(range.start_line, range.start_column)),
             )?;
         }
 
         // 2.5. Inherent methods (for complex method resolution)
-        // TODO: Stage 4 will fix the trait signature to return Vec<(String, String, Range)>
-        // For now, we'll use the trait method directly and handle the borrowing issue
-        if behavior.supports_inherent_methods() {
-            let inherent_methods = parser.find_inherent_methods(content);
-            if !inherent_methods.is_empty() {
-                // Group methods by type
-                let mut methods_by_type: std::collections::HashMap<String, Vec<String>> =
-                    std::collections::HashMap::new();
-                for (type_name, method_name, _range) in inherent_methods {
-                    debug_print!(
-                        self,
-                        "Found inherent method: {}::{}",
-                        type_name,
-                        method_name
-                    );
-                    methods_by_type
-                        .entry(type_name.to_string())
-                        .or_default()
-                        .push(method_name.to_string());
-                }
+        if !inherent_methods.is_empty() {
+            // Group methods by type
+            let mut methods_by_type: std::collections::HashMap<String, Vec<String>> =
+                std::collections::HashMap::new();
+            for (type_name, method_name, _range) in inherent_methods {
+                debug_print!(
+                    self,
+                    "Found inherent method: {}::{}",
+                    type_name,
+                    method_name
+                );
+                methods_by_type
+                    .entry(type_name.to_string())
+                    .or_default()
+                    .push(method_name.to_string());
+            }
 
-                // RESOLUTION SYSTEM: Inherent methods now tracked by LanguageBehavior
-                // For Rust: RustBehavior.add_inherent_methods() updates RustTraitResolver
-                // This tracks methods defined directly on types (not from traits)
-                // Replaces the old TraitResolver.add_inherent_methods() functionality
-                for (type_name, methods) in methods_by_type {
-                    behavior.add_inherent_methods(type_name, methods);
-                }
+            // RESOLUTION SYSTEM: Inherent methods now tracked by LanguageBehavior
+            // For Rust: RustBehavior.add_inherent_methods() updates RustTraitResolver
+            // This tracks methods defined directly on types (not from traits)
+            // Replaces the old TraitResolver.add_inherent_methods() functionality
+            for (type_name, methods) in methods_by_type {
+                behavior.add_inherent_methods(type_name, methods);
+            }
+        }
+
+        // 2.6. Trait impl methods: each implements its impl block's trait
+        for (type_name, trait_name, method_name, range) in trait_impl_methods {
+            debug_print!(
+                self,
+                "Found trait impl method: {}::{} for {}",
+                trait_name,
+                method_name,
+                type_name
+            );
+            if let Some(&method_id) =
+                symbol_lines.get(&(Rc::from(method_name.as_str()), range.start_line))
+            {
+                self.add_relationships_by_name(
+                    Some(method_id),
+                    &method_name,
+                    &trait_name,
+                    file_id,
+                    behavior.map_relationship("implements"),
+                    None,
+                )?;
+            }
+        }
+
+        // 2.7. Trait bounds of generic type parameters
+        for (param_name, bound, range) in type_parameter_bounds {
+            if let Some(&param_id) =
+                symbol_lines.get(&(Rc::from(param_name.as_str()), range.start_line))
+            {
+                self.add_relationships_by_name(
+                    Some(param_id),
+                    &param_name,
+                    &bound,
+                    file_id,
+                    behavior.map_relationship("uses"),
+                    None,
+                )?;
             }
         }
 
-        // 3. Type usage (in fields, parameters, returns)
-        let uses = parser.find_uses(content);
-        for (context_name, used_type, _range) in uses {
+        // 3. Type usage (in fields, parameters, returns), tagged with the
+        // position when the parser knows it
+        for (context_name, used_type, range, position) in uses {
             let from_id = symbol_map.get(context_name).copied();
+            let metadata = position.map(|position| {
+                RelationshipMetadata::new()
+                    .at_position(range.start_line, range.start_column)
+                    .with_context(position.as_str())
+            });
             self.add_relationships_by_name(
                 from_id,
                 context_name,
                 used_type,
                 file_id,
                 behavior.map_relationship("uses"),
-                None,
+                metadata,
             )?;
         }
 
         // 4. Method definitions (trait defines methods)
-        let defines = parser.find_defines(content);
         debug_print!(
             self,
             "Found {} defines for file {:?}",
@@ -1343,7 +2263,6 @@ impl SimpleIndexer {
         }
 
         // Variable type tracking for method resolution
-        let var_types = parser.find_variable_types(content);
         for (var_name, type_name, _range) in var_types {
             self.variable_types
                 .insert((file_id, var_name.to_string()), type_name.to_string());
@@ -1487,8 +2406,10 @@ impl SimpleIndexer {
                 caller_can_call(&from_kind) && callee_can_be_called(&to_kind)
             }
             Implements | ImplementedBy => {
-                // Types can implement interfaces/traits
-                let implementor = |k: &crate::SymbolKind| matches!(k, Struct | Enum | Class);
+                // Types can implement interfaces/traits, and so can the methods
+                // of a trait impl block (`impl Trait for Type`)
+                let implementor =
+                    |k: &crate::SymbolKind| matches!(k, Struct | Enum | Class | Method);
                 let interface = |k: &crate::SymbolKind| matches!(k, Trait | Interface);
 
                 match rel_kind {
@@ -1503,7 +2424,15 @@ impl SimpleIndexer {
                 let can_use = |k: &crate::SymbolKind| {
                     matches!(
                         k,
-                        Function | Method | Struct | Class | Trait | Interface | Module | Enum
+                        Function
+                            | Method
+                            | Struct
+                            | Class
+                            | Trait
+                            | Interface
+                            | Module
+                            | Enum
+                            | Parameter
                     )
                 };
                 let can_be_used = |k: &crate::SymbolKind| {
@@ -1519,6 +2448,7 @@ impl SimpleIndexer {
                             | Variable
                             | Function
                             | Method
+                            | EnumVariant
                     )
                 };
 
@@ -1534,7 +2464,10 @@ impl SimpleIndexer {
                     matches!(k, Trait | Interface | Module | Struct | Enum | Class)
                 };
                 let member = |k: &crate::SymbolKind| {
-                    matches!(k, Method | Function | Constant | Field | Variable)
+                    matches!(
+                        k,
+                        Method | Function | Constant | Field | Variable | EnumVariant
+                    )
                 };
 
                 match rel_kind {
@@ -1594,7 +2527,9 @@ impl SimpleIndexer {
         };
 
         if let Some(reverse_kind) = reverse_kind {
-            let mut reverse_rel = Relationship::new(reverse_kind).with_weight(rel.weight);
+            let mut reverse_rel = Relationship::new(reverse_kind)
+                .with_weight(rel.weight)
+                .with_provenance(rel.provenance);
 
             // Copy metadata if present
             if let Some(ref metadata) = rel.metadata {
@@ -1721,28 +2656,28 @@ impl SimpleIndexer {
 
     /// Returns called functions with receiver metadata for enhanced method call analysis.
     ///
-    /// Provides full relationship metadata including call site line/column and receiver info.
+    /// Provides full relationship metadata including call site line/column, with
+    /// receiver info decoded into [`RelationshipMetadata::call`], and how each
+    /// call target was resolved.
     pub fn get_called_functions_with_metadata(
         &self,
         symbol_id: SymbolId,
-    ) -> Vec<(Symbol, Option<RelationshipMetadata>)> {
+    ) -> Vec<(Symbol, Option<RelationshipMetadata>, Provenance)> {
         self.document_index
             .get_relationships_from(symbol_id, RelationKind::Calls)
             .ok()
             .unwrap_or_default()
             .into_iter()
             .filter_map(|(_, to_id, rel)| {
-                self.get_symbol(to_id).map(|symbol| (symbol, rel.metadata))
+                self.get_symbol(to_id)
+                    .map(|symbol| (symbol, rel.metadata, rel.provenance))
             })
             .collect()
     }
 
     pub fn get_calling_functions(&self, symbol_id: SymbolId) -> Vec<Symbol> {
         // Query relationships where to_symbol_id = symbol_id and kind = Calls
-        self.document_index
-            .get_relationships_to(symbol_id, RelationKind::Calls)
-            .ok()
-            .unwrap_or_default()
+        self.caller_relationships(symbol_id)
             .into_iter()
             .filter_map(|(from_id, _, _)| self.get_symbol(from_id))
             .collect()
@@ -1750,23 +2685,42 @@ impl SimpleIndexer {
 
     /// Returns calling functions with full relationship metadata.
     ///
-    /// Provides call site line/column and receiver information from stored relationship metadata.
+    /// Provides call site line/column and receiver information
+    /// ([`RelationshipMetadata::call`]) from stored relationship metadata, and
+    /// how each call was resolved.
     pub fn get_calling_functions_with_metadata(
         &self,
         symbol_id: SymbolId,
-    ) -> Vec<(Symbol, Option<RelationshipMetadata>)> {
-        self.document_index
-            .get_relationships_to(symbol_id, RelationKind::Calls)
-            .ok()
-            .unwrap_or_default()
+    ) -> Vec<(Symbol, Option<RelationshipMetadata>, Provenance)> {
+        self.caller_relationships(symbol_id)
             .into_iter()
             .filter_map(|(from_id, _, rel)| {
                 self.get_symbol(from_id)
-                    .map(|symbol| (symbol, rel.metadata))
+                    .map(|symbol| (symbol, rel.metadata, rel.provenance))
             })
             .collect()
     }
 
+    /// Incoming relationships that count as "callers" of a symbol.
+    ///
+    /// Enum variants are never called; their construction and match sites are
+    /// stored as `Uses`, so those are reported as callers instead.
+    fn caller_relationships(&self, symbol_id: SymbolId) -> Vec<(SymbolId, SymbolId, Relationship)> {
+        let is_variant = self
+            .get_symbol(symbol_id)
+            .is_some_and(|symbol| symbol.kind == SymbolKind::EnumVariant);
+        let kind = if is_variant {
+            RelationKind::Uses
+        } else {
+            RelationKind::Calls
+        };
+
+        self.document_index
+            .get_relationships_to(symbol_id, kind)
+            .ok()
+            .unwrap_or_default()
+    }
+
     /// Get comprehensive context for a symbol including all relationships.
     ///
     /// Aggregates symbol data with configurable relationship information.
@@ -1780,7 +2734,10 @@ impl SimpleIndexer {
         let symbol = self.get_symbol(symbol_id)?;
         let file_path = SymbolContext::symbol_location(&symbol);
 
-        let mut relationships = SymbolRelationships::default();
+        let mut relationships = SymbolRelationships {
+            parent: symbol.parent.and_then(|id| self.get_symbol(id)),
+            ..Default::default()
+        };
 
         // Load requested relationships using existing methods
         if include.contains(crate::symbol::context::ContextIncludes::IMPLEMENTATIONS) {
@@ -1804,6 +2761,9 @@ impl SimpleIndexer {
                     }
                 }
             }
+            relationships.derived = self.derived_implementations(symbol_id);
+            relationships.extends = self.get_base_classes(symbol_id);
+            relationships.extended_by = self.get_subclasses(symbol_id);
         }
 
         if include.contains(crate::symbol::context::ContextIncludes::DEFINITIONS) {
@@ -1836,57 +2796,367 @@ impl SimpleIndexer {
 
     pub fn get_implementations(&self, trait_id: SymbolId) -> Vec<Symbol> {
         // Query relationships where to_symbol_id = trait_id and kind = Implements
+        // Trait impl methods also implement the trait; only types are reported
         self.document_index
             .get_relationships_to(trait_id, RelationKind::Implements)
             .ok()
             .unwrap_or_default()
             .into_iter()
             .filter_map(|(from_id, _, _)| self.get_symbol(from_id))
+            .filter(|symbol| symbol.kind != SymbolKind::Method)
             .collect()
     }
 
-    pub fn get_all_symbols(&self) -> Vec<Symbol> {
-        self.document_index
-            .get_all_symbols(10000)
-            .unwrap_or_else(|e| {
-                tracing::warn!("Failed to retrieve all symbols: {}", e);
-                Vec::new()
+    /// Functions and methods whose parameter or return types mention `type_id`
+    ///
+    /// Only `Uses` edges the parser tagged with a position count, so fields
+    /// and locals are left out. A symbol using the type both ways is listed
+    /// once per position. Sorted by file, then line. Fails for types of
+    /// languages whose parser records no positions (see
+    /// [`LanguageBehavior::records_type_use_positions`](crate::parsing::LanguageBehavior::records_type_use_positions)),
+    /// rather than reporting no users.
+    pub fn get_signature_uses(&self, type_id: SymbolId) -> IndexResult<Vec<SignatureUse>> {
+        if let Some(language_id) = self
+            .get_symbol(type_id)
+            .and_then(|symbol| symbol.language_id)
+        {
+            let supported = get_registry()
+                .lock()
+                .unwrap()
+                .get(language_id)
+                .map(|definition| definition.create_behavior().records_type_use_positions());
+            if supported == Some(false) {
+                return Err(IndexError::General(format!(
+                    "Signature uses are not supported for {language_id}: its parser does not record where types are used"
+                )));
+            }
+        }
+
+        let mut uses: Vec<SignatureUse> = self
+            .document_index
+            .get_relationships_to(type_id, RelationKind::Uses)
+            .ok()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(from_id, _, relationship)| {
+                let position = relationship.metadata?.type_use_position()?;
+                position
+                    .is_signature()
+                    .then(|| self.get_symbol(from_id))
+                    .flatten()
+                    .map(|symbol| SignatureUse { symbol, position })
             })
+            .collect();
+        uses.sort_by(|a, b| {
+            (
+                &a.symbol.file_path,
+                a.symbol.range.start_line,
+                a.position.as_str(),
+            )
+                .cmp(&(
+                    &b.symbol.file_path,
+                    b.symbol.range.start_line,
+                    b.position.as_str(),
+                ))
+        });
+        uses.dedup_by(|a, b| a.symbol.id == b.symbol.id && a.position == b.position);
+        Ok(uses)
     }
 
-    /// Get all dependencies of a symbol (what it depends on)
-    pub fn get_dependencies(
-        &self,
-        symbol_id: SymbolId,
-    ) -> std::collections::HashMap<RelationKind, Vec<Symbol>> {
-        use std::collections::HashMap;
-        let mut deps = HashMap::new();
-
-        // Get all outgoing relationships
-        for kind in &[
-            RelationKind::Calls,
-            RelationKind::Uses,
-            RelationKind::Implements,
-            RelationKind::Defines,
-        ] {
-            let symbols = self
-                .document_index
-                .get_relationships_from(symbol_id, *kind)
-                .ok()
-                .unwrap_or_default()
-                .into_iter()
-                .filter_map(|(_, to_id, _)| self.get_symbol(to_id))
-                .collect::<Vec<_>>();
+    /// Classes a class directly extends, in declaration order
+    ///
+    /// For Python multiple inheritance this is the order the method
+    /// resolution order walks bases in.
+    pub fn get_base_classes(&self, class_id: SymbolId) -> Vec<Symbol> {
+        let mut bases = self
+            .document_index
+            .get_relationships_from(class_id, RelationKind::Extends)
+            .ok()
+            .unwrap_or_default();
+        bases.sort_by_key(|(_, _, relationship)| {
+            relationship
+                .metadata
+                .as_ref()
+                .map(|metadata| (metadata.line, metadata.column))
+        });
+        bases
+            .into_iter()
+            .filter_map(|(_, to_id, _)| self.get_symbol(to_id))
+            .collect()
+    }
 
-            if !symbols.is_empty() {
-                deps.insert(*kind, symbols);
-            }
-        }
+    /// Classes that directly extend a class
+    pub fn get_subclasses(&self, class_id: SymbolId) -> Vec<Symbol> {
+        self.document_index
+            .get_relationships_to(class_id, RelationKind::Extends)
+            .ok()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(from_id, _, _)| self.get_symbol(from_id))
+            .collect()
+    }
 
-        deps
+    /// The other side of `Implements` edges that come from derive attributes:
+    /// the traits a type derives, or the types deriving a trait
+    pub fn derived_implementations(&self, symbol_id: SymbolId) -> Vec<SymbolId> {
+        let derived = |relationship: &Relationship| {
+            relationship
+                .metadata
+                .as_ref()
+                .is_some_and(RelationshipMetadata::is_derived)
+        };
+        let implementors = self
+            .document_index
+            .get_relationships_to(symbol_id, RelationKind::Implements)
+            .ok()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(_, _, relationship)| derived(relationship))
+            .map(|(from_id, _, _)| from_id);
+        let traits = self
+            .document_index
+            .get_relationships_from(symbol_id, RelationKind::Implements)
+            .ok()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(_, _, relationship)| derived(relationship))
+            .map(|(_, to_id, _)| to_id);
+        implementors.chain(traits).collect()
     }
 
-    /// Get all dependents of a symbol (what depends on it)
+    /// Types deriving `trait_name` by name, for traits that are not indexed
+    /// (`#[derive(Serialize)]`) and so have no `Implements` edges
+    pub fn find_derived_implementors(
+        &self,
+        trait_name: &str,
+        language_filter: Option<&str>,
+    ) -> Vec<Symbol> {
+        self.document_index
+            .find_symbols_deriving(trait_name, language_filter)
+            .unwrap_or_default()
+    }
+
+    /// Traits or interfaces a symbol implements, per `Implements` edges
+    fn implemented_traits(&self, symbol_id: SymbolId) -> Vec<SymbolId> {
+        self.document_index
+            .get_relationships_from(symbol_id, RelationKind::Implements)
+            .ok()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(_, to_id, _)| to_id)
+            .collect()
+    }
+
+    /// Methods a type defines, each with the trait it implements
+    ///
+    /// Inherent methods (no trait) come first, then trait impl methods grouped
+    /// by trait. Methods are found through their parent or a `Defines` edge;
+    /// a method whose parent is another type is not included.
+    pub fn get_type_methods(&self, type_id: SymbolId) -> Vec<TypeMethod> {
+        let mut methods: Vec<Symbol> = self
+            .get_symbol(type_id)
+            .map(|owner| self.get_symbols_by_file(owner.file_id))
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|s| s.parent == Some(type_id))
+            .collect();
+        for (_, to_id, _) in self
+            .document_index
+            .get_relationships_from(type_id, RelationKind::Defines)
+            .ok()
+            .unwrap_or_default()
+        {
+            if let Some(symbol) = self.get_symbol(to_id) {
+                if symbol.parent.is_none_or(|parent| parent == type_id) {
+                    methods.push(symbol);
+                }
+            }
+        }
+        methods.retain(|s| matches!(s.kind, SymbolKind::Method | SymbolKind::Function));
+        methods.sort_by_key(|s| s.id.value());
+        methods.dedup_by_key(|s| s.id);
+
+        let mut type_methods: Vec<TypeMethod> = methods
+            .into_iter()
+            .map(|method| TypeMethod {
+                implements: self
+                    .implemented_traits(method.id)
+                    .into_iter()
+                    .find_map(|id| self.get_symbol(id)),
+                method,
+            })
+            .collect();
+        type_methods.sort_by_cached_key(|m| {
+            (
+                m.implements.as_ref().map(|t| t.name.to_string()),
+                m.method.file_path.to_string(),
+                m.method.range.start_line,
+            )
+        });
+        type_methods
+    }
+
+    /// Concrete implementations of one trait or interface method.
+    ///
+    /// For each type implementing `trait_id`, returns its own method named
+    /// `method_name`. Types that rely on a
+    /// default body have no entry. Fails when the trait declares no such method.
+    pub fn get_method_implementations(
+        &self,
+        trait_id: SymbolId,
+        method_name: &str,
+    ) -> IndexResult<Vec<MethodImplementation>> {
+        let trait_symbol = self
+            .get_symbol(trait_id)
+            .ok_or_else(|| IndexError::General(format!("Symbol {} not found", trait_id.value())))?;
+
+        // A method belongs to a type through its parent or a `Defines` edge
+        let defined_by = |owner: SymbolId| -> Vec<SymbolId> {
+            self.document_index
+                .get_relationships_from(owner, RelationKind::Defines)
+                .ok()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(_, to_id, _)| to_id)
+                .collect()
+        };
+        let candidates: Vec<Symbol> = self
+            .find_symbols_by_name(method_name, None)
+            .into_iter()
+            .filter(|s| matches!(s.kind, SymbolKind::Method | SymbolKind::Function))
+            .collect();
+
+        let trait_members = defined_by(trait_id);
+        let declared = candidates
+            .iter()
+            .any(|m| m.parent == Some(trait_id) || trait_members.contains(&m.id));
+        if !declared {
+            let mut methods: Vec<String> = trait_members
+                .iter()
+                .filter_map(|id| self.get_symbol(*id))
+                .filter(|s| matches!(s.kind, SymbolKind::Method | SymbolKind::Function))
+                .map(|s| s.name.to_string())
+                .collect();
+            methods.sort();
+            methods.dedup();
+            let hint = if methods.is_empty() {
+                String::new()
+            } else {
+                format!("; it declares: {}", methods.join(", "))
+            };
+            return Err(IndexError::General(format!(
+                "'{}' declares no method '{method_name}'{hint}",
+                trait_symbol.name
+            )));
+        }
+
+        let mut implementations = Vec::new();
+        for implementor in self.get_implementations(trait_id) {
+            let members = defined_by(implementor.id);
+            let owned: Vec<&Symbol> = candidates
+                .iter()
+                .filter(|m| m.parent == Some(implementor.id) || members.contains(&m.id))
+                .collect();
+            // When the trait impl method is known, skip same-named inherent methods
+            let from_impl: Vec<&Symbol> = owned
+                .iter()
+                .copied()
+                .filter(|m| self.implemented_traits(m.id).contains(&trait_id))
+                .collect();
+            let owned = if from_impl.is_empty() {
+                owned
+            } else {
+                from_impl
+            };
+            for method in owned {
+                implementations.push(MethodImplementation {
+                    implementor: implementor.clone(),
+                    method: method.clone(),
+                });
+            }
+        }
+        implementations.sort_by(|a, b| {
+            (&a.method.file_path, a.method.range.start_line)
+                .cmp(&(&b.method.file_path, b.method.range.start_line))
+        });
+        implementations.dedup_by_key(|i| i.method.id);
+        Ok(implementations)
+    }
+
+    /// Implementations of a method named by path, like `Parser::parse`
+    ///
+    /// Accepts `Trait::method` or `Trait.method` and resolves the trait by
+    /// name among traits, interfaces and classes.
+    pub fn find_method_implementations(
+        &self,
+        method_path: &str,
+        language: Option<&str>,
+    ) -> IndexResult<Vec<MethodImplementation>> {
+        let (trait_name, method_name) = method_path
+            .rsplit_once("::")
+            .or_else(|| method_path.rsplit_once('.'))
+            .filter(|(t, m)| !t.is_empty() && !m.is_empty())
+            .ok_or_else(|| {
+                IndexError::General(format!("Expected 'Trait::method', got '{method_path}'"))
+            })?;
+
+        let trait_symbol = self
+            .find_symbols_by_name(trait_name, language)
+            .into_iter()
+            .find(|s| {
+                matches!(
+                    s.kind,
+                    SymbolKind::Trait | SymbolKind::Interface | SymbolKind::Class
+                )
+            })
+            .ok_or_else(|| IndexError::SymbolNotFound {
+                name: trait_name.to_string(),
+            })?;
+        self.get_method_implementations(trait_symbol.id, method_name)
+    }
+
+    pub fn get_all_symbols(&self) -> Vec<Symbol> {
+        self.document_index
+            .get_all_symbols(10000)
+            .unwrap_or_else(|e| {
+                tracing::warn!("Failed to retrieve all symbols: {}", e);
+                Vec::new()
+            })
+    }
+
+    /// Get all dependencies of a symbol (what it depends on)
+    pub fn get_dependencies(
+        &self,
+        symbol_id: SymbolId,
+    ) -> std::collections::HashMap<RelationKind, Vec<Symbol>> {
+        use std::collections::HashMap;
+        let mut deps = HashMap::new();
+
+        // Get all outgoing relationships
+        for kind in &[
+            RelationKind::Calls,
+            RelationKind::Uses,
+            RelationKind::Implements,
+            RelationKind::Defines,
+        ] {
+            let symbols = self
+                .document_index
+                .get_relationships_from(symbol_id, *kind)
+                .ok()
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|(_, to_id, _)| self.get_symbol(to_id))
+                .collect::<Vec<_>>();
+
+            if !symbols.is_empty() {
+                deps.insert(*kind, symbols);
+            }
+        }
+
+        deps
+    }
+
+    /// Get all dependents of a symbol (what depends on it)
     pub fn get_dependents(
         &self,
         symbol_id: SymbolId,
@@ -1919,2683 +3189,7948 @@ impl SimpleIndexer {
 
     /// Get impact radius - all symbols that would be affected by changing a symbol
     /// This is a simplified version that finds direct dependents only
+    ///
+    /// With `resolved_only`, ambiguous and heuristic edges are not followed.
+    /// Each symbol is visited once, so cycles end the walk, and at most
+    /// `mcp.max_impact_nodes` symbols are collected; past that the result is
+    /// marked truncated.
     pub fn get_impact_radius(
         &self,
         symbol_id: SymbolId,
         max_depth: Option<usize>,
-    ) -> Vec<SymbolId> {
-        use std::collections::{HashSet, VecDeque};
-
+        resolved_only: bool,
+    ) -> ImpactRadius {
         let depth = max_depth.unwrap_or(2); // Default depth of 2
-        let mut visited = HashSet::new();
-        let mut result = Vec::new();
-        let mut queue = VecDeque::new();
-
-        // Start with the given symbol at depth 0
-        queue.push_back((symbol_id, 0));
-        visited.insert(symbol_id);
-
-        while let Some((current_id, current_depth)) = queue.pop_front() {
-            // Don't include the starting symbol in results
-            if current_id != symbol_id {
-                result.push(current_id);
-            }
-
-            // Stop if we've reached max depth
-            if current_depth >= depth {
-                continue;
-            }
-
-            // Find all symbols that depend on the current symbol
-            for kind in &[
-                RelationKind::Calls,
-                RelationKind::Uses,
-                RelationKind::Implements,
-            ] {
-                if let Ok(relationships) =
-                    self.document_index.get_relationships_to(current_id, *kind)
-                {
-                    for (from_id, _, _) in relationships {
-                        if visited.insert(from_id) {
-                            queue.push_back((from_id, current_depth + 1));
-                        }
+        let max_nodes = self.settings.mcp.max_impact_nodes;
+        let mut result = ImpactRadius::default();
+
+        self.walk_relationships(
+            symbol_id,
+            depth,
+            |current_id| {
+                // Find all symbols that depend on the current symbol
+                let mut dependents = Vec::new();
+                for kind in &[
+                    RelationKind::Calls,
+                    RelationKind::Uses,
+                    RelationKind::Implements,
+                ] {
+                    if let Ok(relationships) =
+                        self.document_index.get_relationships_to(current_id, *kind)
+                    {
+                        dependents.extend(
+                            relationships
+                                .into_iter()
+                                .filter(|(_, _, rel)| Self::edge_kept(rel, resolved_only))
+                                .map(|(from_id, _, _)| from_id),
+                        );
                     }
                 }
-            }
-        }
+                dependents
+            },
+            |_, dependent_id, _| {
+                if result.symbols.len() < max_nodes {
+                    result.symbols.push(dependent_id);
+                    true
+                } else {
+                    result.truncated = true;
+                    false
+                }
+            },
+        );
 
         result
     }
 
-    pub fn symbol_count(&self) -> usize {
-        self.document_index.count_symbols().unwrap_or(0)
-    }
+    /// Callers or callees of `symbol_id` expanded to `max_depth` levels.
+    ///
+    /// Uses the same traversal as [`SimpleIndexer::get_impact_radius`]. At most
+    /// `max_children` symbols are kept under each node; the rest are counted in
+    /// the node's `truncated` so large fan-in stays readable.
+    pub fn get_call_tree(
+        &self,
+        symbol_id: SymbolId,
+        direction: crate::symbol::context::CallDirection,
+        max_depth: usize,
+        max_children: usize,
+    ) -> crate::symbol::context::CallTree {
+        use crate::symbol::context::CallTree;
+        use std::collections::HashMap;
 
-    /// Get import resolver for testing
-    pub fn get_symbols_by_file(&self, file_id: FileId) -> Vec<Symbol> {
-        self.document_index
-            .find_symbols_by_file(file_id)
-            .unwrap_or_default()
-    }
+        let mut children: HashMap<SymbolId, Vec<SymbolId>> = HashMap::new();
+        let mut truncated: HashMap<SymbolId, usize> = HashMap::new();
+
+        self.walk_relationships(
+            symbol_id,
+            max_depth,
+            |current_id| self.call_neighbors(current_id, direction, false),
+            |parent_id, related_id, _| {
+                let kept = children.entry(parent_id).or_default();
+                if kept.len() < max_children {
+                    kept.push(related_id);
+                    true
+                } else {
+                    *truncated.entry(parent_id).or_default() += 1;
+                    false
+                }
+            },
+        );
 
-    pub fn file_count(&self) -> u32 {
-        self.document_index.count_files().unwrap_or(0) as u32
+        CallTree {
+            depth: max_depth,
+            symbols: self.call_tree_nodes(symbol_id, &children, &truncated),
+            truncated: truncated.get(&symbol_id).copied().unwrap_or(0),
+        }
     }
 
-    pub fn relationship_count(&self) -> usize {
-        self.document_index.count_relationships().unwrap_or(0)
-    }
+    /// Every function reaching `symbol_id` through calls within `max_depth` steps.
+    ///
+    /// Each caller is listed once with its distance from the target, the
+    /// shortest call chain found. Only call edges are followed, unlike
+    /// [`SimpleIndexer::get_impact_radius`], and with `resolved_only` only
+    /// resolved ones. Sorted by distance, then name.
+    pub fn get_transitive_callers(
+        &self,
+        symbol_id: SymbolId,
+        max_depth: usize,
+        resolved_only: bool,
+    ) -> Vec<(Symbol, usize)> {
+        let mut reached = Vec::new();
+
+        self.walk_relationships(
+            symbol_id,
+            max_depth,
+            |current_id| {
+                self.call_neighbors(
+                    current_id,
+                    crate::symbol::context::CallDirection::Callers,
+                    resolved_only,
+                )
+            },
+            |_, caller_id, distance| {
+                reached.push((caller_id, distance));
+                true
+            },
+        );
 
-    pub fn get_file_path(&self, file_id: FileId) -> Option<String> {
-        self.document_index.get_file_path(file_id).ok().flatten()
+        let mut callers: Vec<(Symbol, usize)> = reached
+            .into_iter()
+            .filter_map(|(id, distance)| self.get_symbol(id).map(|symbol| (symbol, distance)))
+            .collect();
+        callers.sort_by(|(a, da), (b, db)| da.cmp(db).then_with(|| a.name.cmp(&b.name)));
+        callers
     }
 
-    /// Get all indexed file paths - used by file watcher
-    pub fn get_all_indexed_paths(&self) -> Vec<PathBuf> {
-        self.document_index
-            .get_all_indexed_paths()
-            .unwrap_or_else(|e| {
-                eprintln!("Warning: Failed to get indexed paths: {e}");
-                Vec::new()
-            })
-    }
+    /// Callers or callees of `symbol_id` as a depth-first hierarchy.
+    ///
+    /// Unlike [`SimpleIndexer::get_call_tree`], a symbol reached again (in a
+    /// cycle or from another branch) is listed where it recurs, marked
+    /// `repeated` and not expanded a second time. At most `max_nodes` symbols
+    /// are listed in total; the rest are counted as truncated. Siblings are
+    /// sorted by name. With `resolved_only`, only resolved edges are followed.
+    pub fn get_call_hierarchy(
+        &self,
+        symbol_id: SymbolId,
+        direction: crate::symbol::context::CallDirection,
+        max_depth: usize,
+        max_nodes: usize,
+        resolved_only: bool,
+    ) -> crate::symbol::context::CallTree {
+        let mut expanded = std::collections::HashSet::from([symbol_id]);
+        let mut budget = max_nodes;
+        let (symbols, truncated) = self.call_hierarchy_nodes(
+            symbol_id,
+            direction,
+            resolved_only,
+            max_depth,
+            &mut expanded,
+            &mut budget,
+        );
 
-    /// Track a directory as indexed (stores canonicalized path)
-    pub fn add_indexed_path(&mut self, dir_path: &Path) -> IndexResult<()> {
-        let canonical = dir_path.canonicalize().map_err(|e| IndexError::FileRead {
-            path: dir_path.to_path_buf(),
-            source: e,
-        })?;
+        crate::symbol::context::CallTree {
+            depth: max_depth,
+            symbols,
+            truncated,
+        }
+    }
 
-        // Skip if this path is already covered by an existing indexed directory
-        if self
-            .indexed_paths
-            .iter()
-            .any(|existing| canonical.starts_with(existing))
-        {
-            return Ok(());
+    fn call_hierarchy_nodes(
+        &self,
+        parent_id: SymbolId,
+        direction: crate::symbol::context::CallDirection,
+        resolved_only: bool,
+        levels_left: usize,
+        expanded: &mut std::collections::HashSet<SymbolId>,
+        budget: &mut usize,
+    ) -> (Vec<crate::symbol::context::CallTreeNode>, usize) {
+        use crate::symbol::context::CallTreeNode;
+
+        if levels_left == 0 {
+            return (Vec::new(), 0);
         }
 
-        // Remove any tracked paths that are descendants of the new canonical path
-        self.indexed_paths
-            .retain(|existing| !existing.starts_with(canonical.as_path()));
+        let mut ids = self.call_neighbors(parent_id, direction, resolved_only);
+        let mut seen = std::collections::HashSet::new();
+        ids.retain(|id| seen.insert(*id));
+        let mut related: Vec<Symbol> = ids
+            .into_iter()
+            .filter_map(|id| self.get_symbol(id))
+            .collect();
+        related.sort_by(|a, b| a.name.cmp(&b.name));
 
-        self.indexed_paths.insert(canonical);
-        Ok(())
-    }
+        let mut nodes = Vec::new();
+        let mut truncated = 0;
+        for symbol in related {
+            if *budget == 0 {
+                truncated += 1;
+                continue;
+            }
+            *budget -= 1;
 
-    /// Get all currently tracked indexed directories
-    pub fn get_indexed_paths(&self) -> &std::collections::HashSet<PathBuf> {
-        &self.indexed_paths
+            let id = symbol.id;
+            if expanded.contains(&id) {
+                nodes.push(CallTreeNode {
+                    symbol,
+                    symbols: Vec::new(),
+                    truncated: 0,
+                    repeated: true,
+                });
+                continue;
+            }
+            // Only symbols whose children get listed count as expanded, so a
+            // leaf cut off by the depth limit can still be expanded elsewhere
+            if levels_left > 1 {
+                expanded.insert(id);
+            }
+            let (symbols, truncated_below) = self.call_hierarchy_nodes(
+                id,
+                direction,
+                resolved_only,
+                levels_left - 1,
+                expanded,
+                budget,
+            );
+            nodes.push(CallTreeNode {
+                symbol,
+                symbols,
+                truncated: truncated_below,
+                repeated: false,
+            });
+        }
+        (nodes, truncated)
     }
 
-    /// Sync indexed paths with config (settings.toml is source of truth)
-    ///
-    /// Compares stored indexed_paths with current config.
-    /// - Indexes new directories in config
-    /// - Removes symbols from directories not in config
+    /// Ancestors and descendants of a type named `name`
     ///
-    /// Returns (added_count, removed_count, files_indexed, symbols_found)
-    pub fn sync_with_config(
-        &mut self,
-        stored_paths: Option<Vec<PathBuf>>,
-        config_paths: &[PathBuf],
-        progress: bool,
-    ) -> IndexResult<(usize, usize, usize, usize)> {
-        // Convert to sets for comparison (canonicalized)
-        let stored_set: std::collections::HashSet<PathBuf> = stored_paths
-            .unwrap_or_default()
+    /// Resolves the name with [`SimpleIndexer::find_type_by_name`], then walks
+    /// as [`SimpleIndexer::get_type_hierarchy`].
+    pub fn find_type_hierarchy(
+        &self,
+        name: &str,
+        language: Option<&str>,
+        direction: crate::symbol::context::HierarchyDirection,
+        max_depth: usize,
+    ) -> IndexResult<crate::symbol::context::TypeHierarchy> {
+        let symbol =
+            self.find_type_by_name(name, language)
+                .ok_or_else(|| IndexError::SymbolNotFound {
+                    name: name.to_string(),
+                })?;
+        Ok(self.get_type_hierarchy(symbol, direction, max_depth))
+    }
+
+    /// The first struct, enum, class, trait, interface or type alias named `name`
+    pub fn find_type_by_name(&self, name: &str, language: Option<&str>) -> Option<Symbol> {
+        self.find_symbols_by_name(name, language)
             .into_iter()
-            .filter_map(|p| p.canonicalize().ok())
-            .collect();
+            .find(|s| {
+                matches!(
+                    s.kind,
+                    SymbolKind::Struct
+                        | SymbolKind::Enum
+                        | SymbolKind::Class
+                        | SymbolKind::Trait
+                        | SymbolKind::Interface
+                        | SymbolKind::TypeAlias
+                )
+            })
+    }
 
-        let config_set: std::collections::HashSet<PathBuf> = config_paths
-            .iter()
-            .filter_map(|p| p.canonicalize().ok())
-            .collect();
+    /// What `symbol` extends or implements and what extends or implements it,
+    /// each expanded to `max_depth` levels.
+    ///
+    /// As in [`SimpleIndexer::get_call_hierarchy`], a type reached again (a
+    /// diamond or a cycle) is listed where it recurs, marked `repeated` and
+    /// not expanded a second time. Trait impl methods are not followed.
+    pub fn get_type_hierarchy(
+        &self,
+        symbol: Symbol,
+        direction: crate::symbol::context::HierarchyDirection,
+        max_depth: usize,
+    ) -> crate::symbol::context::TypeHierarchy {
+        use std::collections::HashSet;
 
-        // Find new paths in config that aren't in stored state
-        let new_paths: Vec<PathBuf> = config_set.difference(&stored_set).cloned().collect();
+        let ancestors = direction.includes_ancestors().then(|| {
+            self.type_hierarchy_nodes(symbol.id, true, max_depth, &mut HashSet::from([symbol.id]))
+        });
+        let descendants = direction.includes_descendants().then(|| {
+            self.type_hierarchy_nodes(symbol.id, false, max_depth, &mut HashSet::from([symbol.id]))
+        });
 
-        // Find removed paths (in stored but not in config)
-        let removed_paths: Vec<PathBuf> = stored_set.difference(&config_set).cloned().collect();
-
-        if new_paths.is_empty() && removed_paths.is_empty() {
-            return Ok((0, 0, 0, 0));
+        crate::symbol::context::TypeHierarchy {
+            location: crate::symbol::context::SymbolContext::symbol_location(&symbol),
+            symbol,
+            depth: max_depth,
+            ancestors,
+            descendants,
         }
+    }
 
-        eprintln!("Sync: Found {} new directories to index", new_paths.len());
-        for path in &new_paths {
-            eprintln!("  + {}", path.display());
+    fn type_hierarchy_nodes(
+        &self,
+        parent_id: SymbolId,
+        ancestors: bool,
+        levels_left: usize,
+        expanded: &mut std::collections::HashSet<SymbolId>,
+    ) -> Vec<crate::symbol::context::TypeHierarchyNode> {
+        use crate::symbol::context::{SymbolContext, TypeHierarchyNode};
+
+        if levels_left == 0 {
+            return Vec::new();
         }
 
-        // Index new directories
-        let mut total_files = 0;
-        let mut total_symbols = 0;
-
-        for path in &new_paths {
-            eprintln!("Indexing new directory: {}", path.display());
-            match self.index_directory(path, progress, false) {
-                Ok(stats) => {
-                    eprintln!(
-                        "  ✓ Indexed {} files, {} symbols",
-                        stats.files_indexed, stats.symbols_found
-                    );
-                    total_files += stats.files_indexed;
-                    total_symbols += stats.symbols_found;
+        let mut related = self.type_neighbors(parent_id, ancestors);
+        let mut seen = std::collections::HashSet::new();
+        related.retain(|(symbol, _)| seen.insert(symbol.id));
 
-                    // Track this directory as indexed
-                    if self.settings.debug {
-                        eprintln!("DEBUG: Tracking indexed path: {}", path.display());
-                    }
-                    match self.add_indexed_path(path) {
-                        Ok(_) => {
-                            if self.settings.debug {
-                                eprintln!(
-                                    "DEBUG: Successfully tracked path (total: {})",
-                                    self.indexed_paths.len()
-                                );
-                            }
-                        }
-                        Err(e) => {
-                            eprintln!("  ✗ Failed to track indexed path: {e}");
-                        }
-                    }
-                }
-                Err(e) => {
-                    eprintln!("  ✗ Failed to index {}: {e}", path.display());
-                }
+        let mut nodes = Vec::new();
+        for (symbol, relation) in related {
+            let id = symbol.id;
+            let repeated = expanded.contains(&id);
+            // Only types whose children get listed count as expanded, so a
+            // leaf cut off by the depth limit can still be expanded elsewhere
+            if !repeated && levels_left > 1 {
+                expanded.insert(id);
             }
+            let types = if repeated {
+                Vec::new()
+            } else {
+                self.type_hierarchy_nodes(id, ancestors, levels_left - 1, expanded)
+            };
+            nodes.push(TypeHierarchyNode {
+                location: SymbolContext::symbol_location(&symbol),
+                symbol,
+                relation,
+                types,
+                repeated,
+            });
         }
+        nodes
+    }
 
-        // Clean up removed directories
-        let mut removed_file_count = 0;
-        if !removed_paths.is_empty() {
-            eprintln!("Sync: Found {} directories to remove", removed_paths.len());
-            for path in &removed_paths {
-                eprintln!("  - {}", path.display());
-            }
-
-            // Collect all files to remove first
-            let all_files = self.get_all_indexed_paths();
-            let mut files_to_remove = Vec::new();
-
-            for file_path in all_files {
-                if let Ok(file_canonical) = file_path.canonicalize() {
-                    for removed_path in &removed_paths {
-                        if file_canonical.starts_with(removed_path) {
-                            files_to_remove.push(file_path.clone());
-                            break;
-                        }
-                    }
-                }
-            }
-
-            // Remove each file (each manages its own batch/commit)
-            if !files_to_remove.is_empty() {
-                let progress_view = if files_to_remove.len() > 1 {
-                    let options = ProgressBarOptions::default()
-                        .with_style(ProgressBarStyle::VerticalSolid)
-                        .with_width(28);
-                    let bar = Arc::new(ProgressBar::with_options(
-                        files_to_remove.len() as u64,
-                        "files",
-                        "removed",
-                        "failed",
-                        options,
-                    ));
-                    let status = StatusLine::new(Arc::clone(&bar));
-                    Some((bar, status))
-                } else {
-                    None
-                };
-
-                for file_path in &files_to_remove {
-                    let mut success = false;
-                    if let Err(e) = self.remove_file(file_path) {
-                        eprintln!("  ✗ Failed to remove {}: {e}", file_path.display());
-                    } else {
-                        removed_file_count += 1;
-                        success = true;
-                    }
-
-                    if let Some((bar, _)) = &progress_view {
-                        bar.inc();
-                        if success {
-                            bar.add_extra1(1);
-                        } else {
-                            bar.add_extra2(1);
-                        }
-                    }
-                }
+    /// Types one inheritance step from `symbol_id`: base classes (in
+    /// declaration order) then implemented traits for ancestors, subclasses
+    /// then implementors for descendants
+    fn type_neighbors(&self, symbol_id: SymbolId, ancestors: bool) -> Vec<(Symbol, RelationKind)> {
+        let extends = if ancestors {
+            self.get_base_classes(symbol_id)
+        } else {
+            self.get_subclasses(symbol_id)
+        };
+        let implements = if ancestors {
+            self.document_index
+                .get_relationships_from(symbol_id, RelationKind::Implements)
+                .ok()
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|(_, to_id, _)| self.get_symbol(to_id))
+                .collect()
+        } else {
+            self.get_implementations(symbol_id)
+        };
 
-                if let Some((bar, status)) = progress_view {
-                    drop(status);
-                    eprintln!("{bar}");
-                }
+        extends
+            .into_iter()
+            .map(|symbol| (symbol, RelationKind::Extends))
+            .chain(
+                implements
+                    .into_iter()
+                    .map(|symbol| (symbol, RelationKind::Implements)),
+            )
+            .filter(|(symbol, _)| symbol.kind != SymbolKind::Method)
+            .collect()
+    }
 
-                if removed_file_count > 0 {
-                    eprintln!("  ✓ Removed {removed_file_count} files from index");
-                }
-            }
+    /// Symbols one call away from `symbol_id` in `direction`
+    fn call_neighbors(
+        &self,
+        symbol_id: SymbolId,
+        direction: crate::symbol::context::CallDirection,
+        resolved_only: bool,
+    ) -> Vec<SymbolId> {
+        use crate::symbol::context::CallDirection;
 
-            // Remove paths from tracked set after successful removal
-            for path in &removed_paths {
-                self.indexed_paths.remove(path);
-            }
+        match direction {
+            CallDirection::Callers => self
+                .caller_relationships(symbol_id)
+                .into_iter()
+                .filter(|(_, _, rel)| Self::edge_kept(rel, resolved_only))
+                .map(|(from_id, _, _)| from_id)
+                .collect(),
+            CallDirection::Callees => self
+                .document_index
+                .get_relationships_from(symbol_id, RelationKind::Calls)
+                .ok()
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|(_, _, rel)| Self::edge_kept(rel, resolved_only))
+                .map(|(_, to_id, _)| to_id)
+                .collect(),
         }
+    }
 
-        Ok((
-            new_paths.len(),
-            removed_paths.len(),
-            total_files,
-            total_symbols,
-        ))
+    /// Whether a traversal limited to resolved edges follows `rel`
+    fn edge_kept(rel: &Relationship, resolved_only: bool) -> bool {
+        !resolved_only || rel.provenance == Provenance::Resolved
     }
 
-    /// Search documentation using natural language query
-    /// Returns symbols with their similarity scores, sorted by relevance
-    pub fn semantic_search_docs(
+    fn call_tree_nodes(
         &self,
-        query: &str,
-        limit: usize,
-    ) -> IndexResult<Vec<(Symbol, f32)>> {
-        self.semantic_search_docs_with_language(query, limit, None)
+        parent_id: SymbolId,
+        children: &std::collections::HashMap<SymbolId, Vec<SymbolId>>,
+        truncated: &std::collections::HashMap<SymbolId, usize>,
+    ) -> Vec<crate::symbol::context::CallTreeNode> {
+        use crate::symbol::context::CallTreeNode;
+
+        children
+            .get(&parent_id)
+            .into_iter()
+            .flatten()
+            .filter_map(|&id| {
+                self.get_symbol(id).map(|symbol| CallTreeNode {
+                    symbol,
+                    symbols: self.call_tree_nodes(id, children, truncated),
+                    truncated: truncated.get(&id).copied().unwrap_or(0),
+                    repeated: false,
+                })
+            })
+            .collect()
     }
 
-    /// Search documentation using natural language query with optional language filter
-    /// Returns symbols with their similarity scores, sorted by relevance
-    pub fn semantic_search_docs_with_language(
+    /// Breadth-first walk of the relationship graph from `symbol_id`.
+    ///
+    /// `neighbors` lists the symbols one step away from a node. For each one
+    /// not yet reached within `max_depth` levels, `visit(parent, symbol,
+    /// depth)` decides whether to keep it; kept symbols are expanded in turn
+    /// and never visited again.
+    fn walk_relationships(
         &self,
-        query: &str,
-        limit: usize,
-        language_filter: Option<&str>,
-    ) -> IndexResult<Vec<(Symbol, f32)>> {
-        let semantic = self.semantic_search.as_ref().ok_or_else(|| {
-            IndexError::General(
-                "Semantic search is not enabled. Call enable_semantic_search() first.".to_string(),
-            )
-        })?;
+        symbol_id: SymbolId,
+        max_depth: usize,
+        mut neighbors: impl FnMut(SymbolId) -> Vec<SymbolId>,
+        mut visit: impl FnMut(SymbolId, SymbolId, usize) -> bool,
+    ) {
+        use std::collections::{HashSet, VecDeque};
 
-        // Use the new language-aware search that filters BEFORE computing similarity
-        let results = semantic
-            .lock()
-            .unwrap()
-            .search_with_language(query, limit, language_filter)
-            .map_err(|e| IndexError::General(format!("Semantic search failed: {e}")))?;
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::new();
 
-        // Convert SymbolIds to Symbols
-        let mut symbol_results = Vec::with_capacity(results.len());
-        for (symbol_id, score) in results {
-            if let Some(symbol) = self.get_symbol(symbol_id) {
-                symbol_results.push((symbol, score));
+        // Start with the given symbol at depth 0
+        queue.push_back((symbol_id, 0));
+        visited.insert(symbol_id);
+
+        while let Some((current_id, current_depth)) = queue.pop_front() {
+            // Stop if we've reached max depth
+            if current_depth >= max_depth {
+                continue;
+            }
+
+            let mut seen = HashSet::new();
+            for next_id in neighbors(current_id) {
+                if visited.contains(&next_id) || !seen.insert(next_id) {
+                    continue;
+                }
+                if visit(current_id, next_id, current_depth + 1) {
+                    visited.insert(next_id);
+                    queue.push_back((next_id, current_depth + 1));
+                }
             }
         }
+    }
 
-        Ok(symbol_results)
+    pub fn symbol_count(&self) -> usize {
+        self.document_index.count_symbols().unwrap_or(0)
     }
 
-    /// Search documentation with similarity threshold
-    pub fn semantic_search_docs_with_threshold(
-        &self,
-        query: &str,
-        limit: usize,
-        threshold: f32,
-    ) -> IndexResult<Vec<(Symbol, f32)>> {
-        self.semantic_search_docs_with_threshold_and_language(query, limit, threshold, None)
+    /// Get import resolver for testing
+    pub fn get_symbols_by_file(&self, file_id: FileId) -> Vec<Symbol> {
+        self.document_index
+            .find_symbols_by_file(file_id)
+            .unwrap_or_default()
     }
 
-    /// Search documentation with similarity threshold and optional language filter
-    pub fn semantic_search_docs_with_threshold_and_language(
-        &self,
-        query: &str,
-        limit: usize,
-        threshold: f32,
-        language_filter: Option<&str>,
-    ) -> IndexResult<Vec<(Symbol, f32)>> {
-        let semantic = self.semantic_search.as_ref().ok_or_else(|| {
-            IndexError::General(
-                "Semantic search is not enabled. Call enable_semantic_search() first.".to_string(),
-            )
-        })?;
+    pub fn file_count(&self) -> u32 {
+        self.document_index.count_files().unwrap_or(0) as u32
+    }
 
-        // First get language-filtered results, then apply threshold
-        let results = semantic
-            .lock()
-            .unwrap()
-            .search_with_language(query, limit * 2, language_filter)
-            .map_err(|e| IndexError::General(format!("Semantic search failed: {e}")))?;
+    pub fn relationship_count(&self) -> usize {
+        self.document_index.count_relationships().unwrap_or(0)
+    }
 
-        // Convert SymbolIds to Symbols and apply threshold filter
-        let mut symbol_results = Vec::with_capacity(limit);
-        for (symbol_id, score) in results {
-            // Apply threshold filter
-            if score < threshold {
-                continue;
+    /// Likely entry points across the index, grouped by category in order.
+    ///
+    /// Each symbol's language behavior classifies it from its name, signature
+    /// and attribute lines, read from the file on disk, and may report a
+    /// file-level script block. When relationships were indexed, public
+    /// functions without callers are added as `Uncalled`, except those
+    /// matching `analysis.ignore_names` or `analysis.ignore_attributes`.
+    pub fn entry_points(&self) -> Vec<crate::indexing::EntryPoint> {
+        use crate::indexing::entrypoints::{EntryPoint, EntryPointCategory};
+        use crate::symbol::context::SymbolContext;
+        use std::collections::{BTreeMap, HashMap};
+
+        let mut by_file: BTreeMap<Box<str>, Vec<Symbol>> = BTreeMap::new();
+        for symbol in self.get_all_symbols() {
+            by_file
+                .entry(symbol.file_path.clone())
+                .or_default()
+                .push(symbol);
+        }
+
+        let check_uncalled = self.ensure_relationships_indexed().is_ok();
+        let ignore = crate::indexing::AnalysisIgnore::from_config(&self.settings.analysis);
+        let mut behaviors: HashMap<LanguageId, Option<Box<dyn crate::parsing::LanguageBehavior>>> =
+            HashMap::new();
+        let mut entry_points = Vec::new();
+
+        for (file_path, mut symbols) in by_file {
+            symbols.sort_by_key(|symbol| symbol.range.start_line);
+
+            let behavior = symbols[0].language_id.and_then(|language_id| {
+                behaviors
+                    .entry(language_id)
+                    .or_insert_with(|| {
+                        let registry = get_registry().lock().unwrap();
+                        registry
+                            .get(language_id)
+                            .map(|definition| definition.create_behavior())
+                    })
+                    .as_deref()
+            });
+            // Read for file-level script blocks; unreadable files have none
+            let source = match behavior {
+                Some(_) => self
+                    .read_indexed_file(&file_path)
+                    .map(|(content, _)| content)
+                    .unwrap_or_default(),
+                None => String::new(),
+            };
+
+            if let Some(line) = behavior.and_then(|behavior| behavior.script_entry_line(&source)) {
+                entry_points.push(EntryPoint {
+                    category: EntryPointCategory::Main,
+                    name: "__main__".to_string(),
+                    file_path: format!("{}:{}", crate::paths::display_path(&file_path), line + 1),
+                    symbol_id: None,
+                });
             }
 
-            if let Some(symbol) = self.get_symbol(symbol_id) {
-                symbol_results.push((symbol, score));
-                if symbol_results.len() >= limit {
-                    break;
+            for symbol in &symbols {
+                let attributes: Vec<&str> = symbol.attributes.iter().map(AsRef::as_ref).collect();
+                let category = behavior
+                    .and_then(|behavior| behavior.entry_point_category(symbol, &attributes))
+                    .or_else(|| {
+                        (check_uncalled
+                            && symbol.kind == SymbolKind::Function
+                            && symbol.visibility == crate::Visibility::Public
+                            && !ignore.ignores(&symbol.name, &attributes)
+                            && self.get_calling_functions(symbol.id).is_empty())
+                        .then_some(EntryPointCategory::Uncalled)
+                    });
+                if let Some(category) = category {
+                    entry_points.push(EntryPoint {
+                        category,
+                        name: symbol.name.to_string(),
+                        file_path: SymbolContext::symbol_location(symbol),
+                        symbol_id: Some(symbol.id),
+                    });
                 }
             }
         }
 
-        Ok(symbol_results)
+        // Stable: keeps file and line order within each category
+        entry_points.sort_by_key(|entry_point| entry_point.category);
+        entry_points
     }
 
-    /// Clear the Tantivy index
-    pub fn clear_tantivy_index(&mut self) -> IndexResult<()> {
-        // RESOLUTION SYSTEM: Behaviors maintain their own state
-        // No centralized resolver to clear anymore
-        self.trait_symbols_by_file.clear();
-        self.variable_types.clear();
-
-        // Clear semantic search if enabled
-        if let Some(ref semantic) = self.semantic_search {
-            semantic.lock().unwrap().clear();
-        }
+    /// Relationships resolved by picking one of several same-named symbols.
+    ///
+    /// Each site lists every indexed symbol sharing the chosen target's name
+    /// and kind. Only forward edges are reported (`Calls`, not `CalledBy`).
+    /// Sorted by file, then line.
+    pub fn ambiguous_resolutions(&self) -> IndexResult<Vec<crate::indexing::AmbiguousResolution>> {
+        use crate::indexing::AmbiguousResolution;
+        use crate::symbol::context::SymbolContext;
+        use std::collections::HashMap;
 
+        let mut edges = Vec::new();
         self.document_index
-            .clear()
+            .for_each_relationship(|from, to, relationship| {
+                let forward = matches!(
+                    relationship.kind,
+                    RelationKind::Calls
+                        | RelationKind::Extends
+                        | RelationKind::Implements
+                        | RelationKind::Uses
+                        | RelationKind::Defines
+                        | RelationKind::References
+                );
+                if forward && relationship.provenance == Provenance::Ambiguous {
+                    edges.push((from, to, relationship));
+                }
+                Ok(())
+            })
             .map_err(|e| IndexError::TantivyError {
-                operation: "clear_index".to_string(),
+                operation: "for_each_relationship".to_string(),
                 cause: e.to_string(),
-            })
-    }
+            })?;
 
-    /// Search using full-text search
-    #[must_use = "Search results should be used"]
-    pub fn search(
-        &self,
-        query: &str,
-        limit: usize,
-        kind_filter: Option<crate::types::SymbolKind>,
-        module_filter: Option<&str>,
-        language_filter: Option<&str>,
-    ) -> IndexResult<Vec<SearchResult>> {
-        self.document_index
-            .search(query, limit, kind_filter, module_filter, language_filter)
-            .map_err(|e| IndexError::General(format!("Search failed: {e}")))
+        let mut same_name: HashMap<(String, SymbolKind), Vec<Symbol>> = HashMap::new();
+        let mut sites = Vec::new();
+        for (from, to, relationship) in edges {
+            let (Some(source), Some(target)) = (self.get_symbol(from), self.get_symbol(to)) else {
+                continue;
+            };
+
+            let mut candidates = same_name
+                .entry((target.name.to_string(), target.kind))
+                .or_insert_with(|| {
+                    let mut symbols: Vec<Symbol> = self
+                        .find_symbols_by_name(&target.name, None)
+                        .into_iter()
+                        .filter(|symbol| symbol.kind == target.kind)
+                        .collect();
+                    symbols.sort_by(|a, b| {
+                        a.file_path
+                            .cmp(&b.file_path)
+                            .then(a.range.start_line.cmp(&b.range.start_line))
+                    });
+                    symbols
+                })
+                .clone();
+            // Stable, so the others keep their file order
+            candidates.sort_by_key(|symbol| symbol.id != target.id);
+
+            let line = relationship
+                .metadata
+                .as_ref()
+                .and_then(|metadata| metadata.line);
+            let location = match line {
+                Some(line) => format!(
+                    "{}:{}",
+                    crate::paths::display_path(&source.file_path),
+                    line + 1
+                ),
+                None => SymbolContext::symbol_location(&source),
+            };
+            let sort_key = (
+                source.file_path.clone(),
+                line.unwrap_or(source.range.start_line),
+            );
+            sites.push((
+                sort_key,
+                AmbiguousResolution {
+                    kind: relationship.kind,
+                    source,
+                    location,
+                    target,
+                    candidates,
+                },
+            ));
+        }
+
+        sites.sort_by(|(a, _), (b, _)| a.cmp(b));
+        Ok(sites.into_iter().map(|(_, site)| site).collect())
     }
 
-    /// Get total number of indexed documents
-    pub fn document_count(&self) -> IndexResult<u64> {
+    /// Doc comment coverage of public symbols, overall and per module.
+    pub fn doc_coverage(&self) -> IndexResult<crate::indexing::DocCoverage> {
+        let mut symbols = Vec::new();
         self.document_index
-            .document_count()
-            .map_err(|e| IndexError::General(format!("Failed to get document count: {e}")))
+            .for_each_symbol(|symbol| {
+                if symbol.visibility == crate::Visibility::Public {
+                    symbols.push(symbol);
+                }
+                Ok(())
+            })
+            .map_err(|e| IndexError::TantivyError {
+                operation: "for_each_symbol".to_string(),
+                cause: e.to_string(),
+            })?;
+        Ok(crate::indexing::DocCoverage::from_symbols(symbols))
     }
 
-    #[must_use = "The indexing result should be checked for errors"]
-    pub fn index_directory(
-        &mut self,
-        dir: impl AsRef<Path>,
-        progress: bool,
-        dry_run: bool,
-    ) -> IndexResult<IndexStats> {
-        self.index_directory_with_options(dir, progress, dry_run, false, None)
-    }
+    /// Public function and method signatures that differ from `baseline`.
+    ///
+    /// Symbols are matched by language and qualified name. Overloads sharing
+    /// a name are paired only once identical signatures are set aside and a
+    /// single candidate remains on each side. A baseline public function with
+    /// no public match is reported as removed, or as no longer public when a
+    /// non-public one has its name. Breaking changes sort first, then by
+    /// qualified name.
+    pub fn signature_changes(
+        &self,
+        baseline: &SimpleIndexer,
+    ) -> IndexResult<Vec<crate::indexing::SignatureChange>> {
+        use crate::indexing::SignatureChange;
+        use crate::indexing::signature_changes::signatures_match;
+        use crate::symbol::context::SymbolContext;
+
+        let is_public = |symbol: &Symbol| symbol.visibility == crate::Visibility::Public;
+        let after = self.function_signatures()?;
+        let mut changes = Vec::new();
+        for (key, mut previous) in baseline.function_signatures()? {
+            previous.retain(is_public);
+            let Some(old) = previous.first() else {
+                continue;
+            };
+            let all_current = after.get(&key).map(Vec::as_slice).unwrap_or_default();
+            let mut current: Vec<Symbol> = all_current
+                .iter()
+                .filter(|s| is_public(s))
+                .cloned()
+                .collect();
+            if current.is_empty() {
+                let hidden = all_current
+                    .first()
+                    .map(|symbol| (symbol, SymbolContext::symbol_location(symbol)));
+                changes.push(SignatureChange::withdrawn(
+                    key.1.clone(),
+                    SymbolContext::symbol_location(old),
+                    old,
+                    hidden,
+                ));
+                continue;
+            }
+            if current.len() > 1 || previous.len() > 1 {
+                current.retain(|symbol| {
+                    let same = previous
+                        .iter()
+                        .position(|old| signatures_match(old, symbol));
+                    same.map(|at| previous.swap_remove(at)).is_none()
+                });
+            }
+            if let ([old], [new]) = (previous.as_slice(), current.as_slice()) {
+                changes.extend(SignatureChange::between(
+                    key.1.clone(),
+                    SymbolContext::symbol_location(new),
+                    old,
+                    new,
+                ));
+            }
+        }
 
-    #[must_use = "The indexing result should be checked for errors"]
-    pub fn index_directory_with_force(
-        &mut self,
-        dir: impl AsRef<Path>,
-        progress: bool,
-        dry_run: bool,
-        force: bool,
-    ) -> IndexResult<IndexStats> {
-        self.index_directory_with_options(dir, progress, dry_run, force, None)
+        changes.sort_by(|a, b| {
+            a.severity
+                .cmp(&b.severity)
+                .then_with(|| a.qualified_name.cmp(&b.qualified_name))
+        });
+        Ok(changes)
     }
 
-    #[must_use = "The indexing result should be checked for errors"]
-    pub fn index_directory_with_options(
-        &mut self,
-        dir: impl AsRef<Path>,
-        progress: bool,
-        dry_run: bool,
-        force: bool,
-        max_files: Option<usize>,
-    ) -> IndexResult<IndexStats> {
-        let walker = FileWalker::new(self.settings.clone());
-        let files: Vec<_> = walker.walk(dir.as_ref()).collect();
+    /// Functions and methods with a signature, keyed by language and
+    /// qualified name (`module::Parent::name` in the language's separator)
+    fn function_signatures(
+        &self,
+    ) -> IndexResult<std::collections::HashMap<(Option<LanguageId>, String), Vec<Symbol>>> {
+        use std::collections::HashMap;
 
-        // Apply max_files limit if specified
-        let files = if let Some(max) = max_files {
-            files.into_iter().take(max).collect()
-        } else {
-            files
-        };
+        let mut names = HashMap::new();
+        let mut api = Vec::new();
+        self.document_index
+            .for_each_symbol(|symbol| {
+                names.insert(symbol.id, symbol.name.to_string());
+                if matches!(symbol.kind, SymbolKind::Function | SymbolKind::Method)
+                    && symbol.signature.is_some()
+                {
+                    api.push(symbol);
+                }
+                Ok(())
+            })
+            .map_err(|e| IndexError::TantivyError {
+                operation: "for_each_symbol".to_string(),
+                cause: e.to_string(),
+            })?;
 
-        let total_files = files.len();
+        let mut separators = HashMap::new();
+        let mut by_name: HashMap<_, Vec<Symbol>> = HashMap::new();
+        for symbol in api {
+            let separator = *separators.entry(symbol.language_id).or_insert_with(|| {
+                symbol
+                    .language_id
+                    .and_then(|language_id| {
+                        let registry = get_registry().lock().unwrap();
+                        registry
+                            .get(language_id)
+                            .map(|definition| definition.create_behavior().module_separator())
+                    })
+                    .unwrap_or("::")
+            });
+            let module = symbol.module_path.as_deref().unwrap_or_default();
+            let parent = symbol
+                .parent
+                .and_then(|parent| names.get(&parent))
+                .filter(|parent| !module.ends_with(parent.as_str()));
+            let qualified_name = [Some(module), parent.map(String::as_str)]
+                .into_iter()
+                .flatten()
+                .filter(|part| !part.is_empty())
+                .chain([symbol.name.as_str()])
+                .collect::<Vec<_>>()
+                .join(separator);
+            by_name
+                .entry((symbol.language_id, qualified_name))
+                .or_default()
+                .push(symbol);
+        }
+        Ok(by_name)
+    }
 
-        // Handle dry-run mode
-        if dry_run {
-            println!("Would index {total_files} files:");
-            for (i, file_path) in files.iter().enumerate() {
-                if i < 5 {
-                    println!("  {}", file_path.display());
-                } else if i == 5 && total_files > 5 {
-                    println!("  ... and {} more files", total_files - 5);
-                    break;
+    /// Aggregate relationship edges by file to report fan-in and fan-out.
+    ///
+    /// Files are sorted by total cross-file edges, highest first.
+    pub fn file_coupling(&self) -> IndexResult<Vec<crate::indexing::FileCoupling>> {
+        let edges = self
+            .document_index
+            .get_all_relationship_endpoints()
+            .map_err(|e| IndexError::TantivyError {
+                operation: "get_all_relationship_endpoints".to_string(),
+                cause: e.to_string(),
+            })?;
+        let symbol_files =
+            self.document_index
+                .get_symbol_file_ids()
+                .map_err(|e| IndexError::TantivyError {
+                    operation: "get_symbol_file_ids".to_string(),
+                    cause: e.to_string(),
+                })?;
+
+        let mut file_paths = std::collections::HashMap::new();
+        for file_id in symbol_files.values() {
+            if !file_paths.contains_key(file_id) {
+                if let Some(path) = self.get_file_path(*file_id) {
+                    file_paths.insert(*file_id, path);
                 }
             }
-
-            let mut stats = IndexStats::new();
-            stats.files_indexed = total_files;
-            return Ok(stats);
         }
 
-        let mut stats = IndexStats::new();
+        Ok(crate::indexing::coupling::aggregate_file_coupling(
+            &edges,
+            &symbol_files,
+            &file_paths,
+        ))
+    }
 
-        // Process files one at a time with batched commits
-        let progress_view = if progress && total_files > 0 {
-            let options = ProgressBarOptions::default()
-                .with_style(ProgressBarStyle::VerticalSolid)
-                .with_width(28);
-            let bar = Arc::new(ProgressBar::with_options(
-                total_files as u64,
-                "files",
-                "indexed",
-                "failed",
-                options,
-            ));
-            let status = StatusLine::new(Arc::clone(&bar));
-            Some((bar, status))
-        } else {
-            None
-        };
+    /// Resolve a name imported by `file_path` to the symbol that defines it.
+    ///
+    /// `name` is the name visible in the file: an alias, the last segment of
+    /// the import path, or the full path. When the language resolver cannot
+    /// pin the import down, indexed symbols with the imported name are used
+    /// as candidates, and a single match is reported as heuristic.
+    pub fn resolve_import(
+        &self,
+        file_path: &str,
+        name: &str,
+    ) -> IndexResult<crate::indexing::ImportResolution> {
+        use crate::indexing::ImportResolution;
+        use crate::indexing::import_lookup::imported_name;
+        use crate::parsing::resolution::ImportOrigin;
+
+        let (file_id, file_path) = self.indexed_file(file_path)?;
+        let context = self.file_resolution_context(file_id, Path::new(file_path))?;
+
+        let binding = context.import_binding(name).ok_or_else(|| {
+            IndexError::General(format!("'{name}' is not imported in '{file_path}'"))
+        })?;
+        let import_path = binding.import.path;
 
-        // Start batch once before the loop
-        self.start_tantivy_batch()?;
-        const COMMIT_BATCH_SIZE: usize = 100; // Commit every 100 files to reduce I/O
-        let mut files_in_batch = 0;
+        if let Some(symbol) = binding.resolved_symbol.and_then(|id| self.get_symbol(id)) {
+            return Ok(ImportResolution::Resolved {
+                import_path,
+                symbol,
+            });
+        }
+        if binding.origin == ImportOrigin::External {
+            return Ok(ImportResolution::Unresolved {
+                import_path,
+                external: true,
+            });
+        }
 
-        for file_path in files {
-            let mut file_success = false;
+        let candidates: Vec<Symbol> = self
+            .find_symbols_by_name(imported_name(&import_path), None)
+            .into_iter()
+            .filter(|symbol| symbol.file_id != file_id)
+            .collect();
+        Ok(ImportResolution::from_name_matches(import_path, candidates))
+    }
 
-            {
-                match self.index_file_internal(&file_path, force) {
-                    Ok(result) => {
-                        files_in_batch += 1;
+    /// Resolution context of an indexed file, as relationship resolution
+    /// builds it
+    fn file_resolution_context(
+        &self,
+        file_id: FileId,
+        path: &Path,
+    ) -> IndexResult<Box<dyn ResolutionScope>> {
+        if self.file_behaviors.contains_key(&file_id) {
+            return self.build_resolution_context(file_id);
+        }
 
-                        // Commit batch periodically to avoid excessive memory usage
-                        if files_in_batch >= COMMIT_BATCH_SIZE {
-                            self.commit_tantivy_batch()?;
-                            self.start_tantivy_batch()?;
-                            files_in_batch = 0;
-                        }
+        // Index loaded from disk: rebuild the file's behavior state
+        let behavior = self
+            .create_parser_with_behavior(self.detect_language(path)?)?
+            .behavior;
+        if let Some(module_path) = self.calculate_module_path(path, behavior.as_ref()) {
+            behavior.register_file(path.to_path_buf(), file_id, module_path);
+        }
+        match self.symbol_cache() {
+            Some(cache) => {
+                behavior.build_resolution_context_with_cache(file_id, cache, &self.document_index)
+            }
+            None => behavior.build_resolution_context(file_id, &self.document_index),
+        }
+    }
 
-                        let file_id = result.file_id();
+    /// Explain how the calls on one line of an indexed file resolve.
+    ///
+    /// `line` is 1-based. The file is re-parsed as it is on disk and each call
+    /// found on that line is replayed against the file's resolution context,
+    /// recording the imports and lookups involved, the indexed symbols sharing
+    /// the called name and the target resolution settles on. Receiver types
+    /// come from the re-parse, so the replay does not depend on indexing state;
+    /// each trace lists the `Calls` edges stored for its call site and notes
+    /// when they differ from the replayed target.
+    pub fn explain_call_resolution(
+        &self,
+        file_path: &str,
+        line: u32,
+    ) -> IndexResult<Vec<crate::indexing::CallResolutionTrace>> {
+        let (file_id, stored_path) = self.indexed_file(file_path)?;
+        let path = Path::new(stored_path);
+        let (content, _) = self.read_indexed_file(stored_path)?;
+        let mut parser = self
+            .create_parser_with_behavior(self.detect_language(path)?)?
+            .parser;
+        let context = self.file_resolution_context(file_id, path)?;
+
+        let row = line.saturating_sub(1);
+        let mut calls: Vec<MethodCall> = parser
+            .find_method_calls(&content)
+            .into_iter()
+            .filter(|call| call.range.start_line == row)
+            .collect();
+        for (caller, target, range) in parser.find_calls(&content) {
+            let seen = calls.iter().any(|call| {
+                call.range.start_column == range.start_column
+                    || call.method_name == crate::indexing::import_lookup::imported_name(target)
+            });
+            if range.start_line == row && !seen {
+                calls.push(MethodCall::new(caller, target, range));
+            }
+        }
+        calls.sort_by_key(|call| call.range.start_column);
 
-                        // Count all processed files (cached + newly indexed)
-                        stats.files_indexed += 1;
-                        file_success = true;
+        let variable_types: std::collections::HashMap<&str, &str> = parser
+            .find_variable_types(&content)
+            .into_iter()
+            .map(|(variable, type_name, _)| (variable, type_name))
+            .collect();
+        let in_scope: std::collections::HashMap<SymbolId, crate::parsing::ScopeLevel> = context
+            .symbols_in_scope()
+            .into_iter()
+            .map(|(_, id, level)| (id, level))
+            .collect();
+        let file_symbols = self.get_symbols_by_file(file_id);
 
-                        // Count symbols from all files
-                        let new_symbols = self
-                            .document_index
-                            .find_symbols_by_file(file_id)
-                            .map(|symbols| symbols.len())
-                            .unwrap_or(0);
-                        stats.symbols_found += new_symbols;
-                    }
-                    Err(e) => {
-                        eprintln!("Failed to index {}: {}", file_path.display(), e);
-                        stats.files_failed += 1;
-                        // Rollback is automatic
-                    }
-                }
+        Ok(calls
+            .iter()
+            .map(|call| {
+                let caller = file_symbols
+                    .iter()
+                    .filter(|s| matches!(s.kind, SymbolKind::Function | SymbolKind::Method))
+                    .filter(|s| {
+                        s.range
+                            .contains(call.range.start_line, call.range.start_column)
+                    })
+                    .min_by_key(|s| s.range.end_line - s.range.start_line)
+                    .cloned();
+                self.trace_call(
+                    call,
+                    caller,
+                    file_id,
+                    context.as_ref(),
+                    &variable_types,
+                    &in_scope,
+                )
+            })
+            .collect())
+    }
+
+    /// Replay the resolution of one call, following `resolve_method_call`
+    /// and the checks `resolve_cross_file_relationships` applies after it
+    fn trace_call(
+        &self,
+        call: &MethodCall,
+        caller: Option<Symbol>,
+        file_id: FileId,
+        context: &dyn ResolutionScope,
+        variable_types: &std::collections::HashMap<&str, &str>,
+        in_scope: &std::collections::HashMap<SymbolId, crate::parsing::ScopeLevel>,
+    ) -> crate::indexing::CallResolutionTrace {
+        use crate::indexing::call_resolution::{
+            CallResolutionTrace, ConsideredImport, ResolutionCandidate, ResolutionStep,
+        };
+        use crate::indexing::import_lookup::imported_name;
+
+        let name = call.method_name.as_str();
+        let step = |lookup: String, result: Option<SymbolId>, provenance, note: Option<&str>| {
+            ResolutionStep {
+                lookup,
+                result: result.and_then(|id| self.get_symbol(id)),
+                provenance,
+                note: note.map(str::to_string),
             }
+        };
 
-            if let Some((bar, _)) = progress_view.as_ref() {
-                bar.inc();
-                if file_success {
-                    bar.add_extra1(1);
+        // Why resolution stops before any lookup, when it does
+        let mut blocked = None;
+        let mut steps = Vec::new();
+        match (&call.receiver, &caller) {
+            (_, None) => steps.push(step(
+                name.to_string(),
+                context.resolve_relationship(&call.caller, name, RelationKind::Calls, file_id),
+                Provenance::Resolved,
+                Some("no indexed caller; resolved as a plain relationship"),
+            )),
+            (None, Some(_)) => steps.push(step(
+                name.to_string(),
+                context.resolve(name),
+                Provenance::Resolved,
+                None,
+            )),
+            (Some(receiver), Some(_)) if call.is_static => {
+                if context.is_external_import(receiver) {
+                    blocked = Some(format!(
+                        "type '{receiver}' is an external import, so the call is not resolved"
+                    ));
                 } else {
-                    bar.add_extra2(1);
+                    let qualified = format!("{receiver}::{name}");
+                    steps.push(step(
+                        qualified.clone(),
+                        context.resolve(&qualified),
+                        Provenance::Resolved,
+                        None,
+                    ));
+                    steps.push(step(
+                        name.to_string(),
+                        context.resolve(name),
+                        Provenance::Heuristic,
+                        Some("fallback on the bare method name"),
+                    ));
+                }
+            }
+            (Some(receiver), Some(caller)) if receiver.starts_with("super(") => steps.push(step(
+                receiver.clone(),
+                self.resolve_super_call(caller, name),
+                Provenance::Resolved,
+                Some("bases of the calling class, depth-first"),
+            )),
+            (Some(receiver), Some(caller)) => {
+                match self.receiver_type(receiver, Some(caller), context, &|name| {
+                    variable_types.get(name).map(|t| t.to_string())
+                }) {
+                    Some(type_name) => {
+                        let method =
+                            self.find_method_of_type(&type_name, name, Some(caller), context);
+                        steps.push(step(
+                            format!("{type_name}.{name}"),
+                            method.as_ref().map(|(method, _)| method.id),
+                            method.map_or(Provenance::Resolved, |(_, provenance)| provenance),
+                            Some(format!("method of receiver type {type_name}").as_str()),
+                        ));
+                        steps.push(step(
+                            name.to_string(),
+                            context.resolve(name),
+                            Provenance::Heuristic,
+                            Some("fallback on the method name alone"),
+                        ));
+                    }
+                    None => {
+                        blocked = Some(format!(
+                            "the type of receiver '{receiver}' is unknown, so the method is not looked up"
+                        ))
+                    }
                 }
             }
         }
 
-        if let Some((bar, status)) = progress_view {
-            drop(status);
-            eprintln!("{bar}");
-        }
-
-        // Commit any remaining files in the batch
-        if files_in_batch > 0 {
-            self.commit_tantivy_batch()?;
-        }
-
-        // Resolve cross-file relationships after all files are indexed
-        if !dry_run {
-            self.resolve_cross_file_relationships()?;
+        let mut imports: Vec<ConsideredImport> = Vec::new();
+        let receiver_type = call.receiver.as_deref().filter(|_| call.is_static);
+        for visible in receiver_type.into_iter().chain([name]) {
+            let visible = visible.split([':', '.']).next().unwrap_or(visible);
+            if imports.iter().any(|import| import.name == visible) {
+                continue;
+            }
+            if let Some(binding) = context.import_binding(visible) {
+                imports.push(ConsideredImport {
+                    name: binding.exposed_name,
+                    path: binding.import.path,
+                    origin: ConsideredImport::origin_name(binding.origin),
+                    resolved: binding.resolved_symbol.and_then(|id| self.get_symbol(id)),
+                });
+            }
         }
 
-        // Stop timing and update final stats before returning
-        stats.stop_timing();
-        stats.symbols_found = self.symbol_count();
+        let candidates: Vec<ResolutionCandidate> = self
+            .find_symbols_by_name(imported_name(name), None)
+            .into_iter()
+            .map(|symbol| ResolutionCandidate {
+                scope: in_scope
+                    .get(&symbol.id)
+                    .map(|level| format!("{level:?}").to_lowercase()),
+                symbol,
+            })
+            .collect();
 
-        Ok(stats)
-    }
+        let found = steps
+            .iter()
+            .find_map(|step| step.result.clone().map(|symbol| (symbol, step)));
+        let (chosen, provenance, reason) = match found {
+            None => {
+                let reason = blocked.unwrap_or_else(|| match candidates.len() {
+                    0 => format!("no indexed symbol is named '{}'", imported_name(name)),
+                    n => format!(
+                        "{n} indexed symbol(s) share the name, but no lookup found one in scope"
+                    ),
+                });
+                (None, None, reason)
+            }
+            Some((symbol, step)) => {
+                let same_kind = candidates
+                    .iter()
+                    .filter(|candidate| candidate.symbol.kind == symbol.kind)
+                    .count();
+                match &caller {
+                    Some(caller)
+                        if !Self::is_compatible_relationship(
+                            caller.kind,
+                            symbol.kind,
+                            RelationKind::Calls,
+                        ) =>
+                    {
+                        let reason = format!(
+                            "'{}' finds {:?} {}, which a {:?} cannot call, so the edge is dropped",
+                            step.lookup, symbol.kind, symbol.name, caller.kind
+                        );
+                        (None, None, reason)
+                    }
+                    _ if step.provenance == Provenance::Resolved
+                        && symbol.file_id != file_id
+                        && !name.contains(['.', ':'])
+                        && same_kind > 1 =>
+                    {
+                        let reason = format!(
+                            "'{}' is in scope, but {same_kind} indexed {:?}s share the name",
+                            step.lookup, symbol.kind
+                        );
+                        (Some(symbol), Some(Provenance::Ambiguous), reason)
+                    }
+                    _ if step.provenance == Provenance::Heuristic => {
+                        let reason = format!(
+                            "matched on the bare name '{}' after the qualified or receiver lookup failed",
+                            step.lookup
+                        );
+                        (Some(symbol), Some(Provenance::Heuristic), reason)
+                    }
+                    _ => {
+                        let reason = format!("'{}' is in scope in this file", step.lookup);
+                        (Some(symbol), Some(step.provenance), reason)
+                    }
+                }
+            }
+        };
 
-    // RESOLUTION SYSTEM: State reconstruction removed
-    // Resolution state is now maintained by language behaviors
-    // The new behavior system builds state incrementally during indexing
-    // No reconstruction needed!
+        let stored = caller
+            .as_ref()
+            .map(|caller| self.stored_call_targets(caller.id, call))
+            .unwrap_or_default();
+        let disagreement = self
+            .relationships_indexed
+            .then(|| Self::call_disagreement(chosen.as_ref(), &stored))
+            .flatten();
+
+        CallResolutionTrace {
+            name: name.to_string(),
+            receiver: call.receiver.clone(),
+            is_static: call.is_static,
+            line: call.range.start_line + 1,
+            column: call.range.start_column,
+            caller,
+            imports,
+            steps,
+            candidates,
+            chosen,
+            provenance,
+            reason,
+            stored,
+            disagreement,
+        }
+    }
 
-    /// Stores MethodCall objects for enhanced resolution during symbol resolution phase.
-    ///
-    /// Enables precise method resolution by preserving receiver and static call information.
-    fn store_method_call_for_resolution(
-        &mut self,
-        method_call: &crate::parsing::MethodCall,
-        file_id: FileId,
-    ) {
-        debug_print!(
-            self,
-            "Storing method call for enhanced resolution: {} calls {} (static: {}, receiver: {:?})",
-            method_call.caller,
-            method_call.method_name,
-            method_call.is_static,
-            method_call.receiver
-        );
+    /// Targets of the `Calls` edges `caller` has stored at `call`'s position,
+    /// or on its line to a symbol of the called name when the parsers place
+    /// the call at different columns
+    fn stored_call_targets(&self, caller: SymbolId, call: &MethodCall) -> Vec<Symbol> {
+        let name = crate::indexing::import_lookup::imported_name(&call.method_name);
+        let on_line: Vec<(Symbol, Option<u16>)> = self
+            .get_called_functions_with_metadata(caller)
+            .into_iter()
+            .filter_map(|(symbol, metadata, _)| {
+                let metadata = metadata?;
+                (metadata.line == Some(call.range.start_line)).then_some((symbol, metadata.column))
+            })
+            .collect();
+        let at_column = on_line
+            .iter()
+            .any(|(_, column)| *column == Some(call.range.start_column));
+        let mut targets: Vec<Symbol> = on_line
+            .into_iter()
+            .filter(|(symbol, column)| {
+                if at_column {
+                    *column == Some(call.range.start_column)
+                } else {
+                    symbol.name.as_ref() == name
+                }
+            })
+            .map(|(symbol, _)| symbol)
+            .collect();
+        targets.sort_by_key(|symbol| symbol.id.value());
+        targets.dedup_by_key(|symbol| symbol.id);
+        targets
+    }
 
-        // Index by (caller_name, method_name) for O(1) lookup
-        let key = (method_call.caller.clone(), method_call.method_name.clone());
-        self.method_calls_by_file
-            .entry(file_id)
-            .or_default()
-            .insert(key, method_call.clone());
+    /// How a replayed target differs from the stored edges, if it does
+    fn call_disagreement(chosen: Option<&Symbol>, stored: &[Symbol]) -> Option<String> {
+        let stored_names = || {
+            stored
+                .iter()
+                .map(|symbol| format!("{} [symbol_id:{}]", symbol.name, symbol.id.value()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        match chosen {
+            Some(symbol) if stored.is_empty() => Some(format!(
+                "the replay resolves to {} but no Calls edge is stored for this call; \
+                 the file may have changed since it was indexed",
+                symbol.name
+            )),
+            Some(symbol) if !stored.iter().any(|target| target.id == symbol.id) => Some(format!(
+                "the replay resolves to {} but the stored edge points to {}",
+                symbol.name,
+                stored_names()
+            )),
+            None if !stored.is_empty() => Some(format!(
+                "the replay resolves nothing but the stored edge points to {}",
+                stored_names()
+            )),
+            _ => None,
+        }
     }
 
-    /// Resolves method calls using enhanced MethodCall data with fallback to legacy resolution.
+    /// Find the innermost symbol covering a position in `file_path`.
     ///
-    /// Matches caller/method names to stored MethodCall objects for precise resolution.
-    /// Falls back to string-based resolution when no MethodCall data is available.
-    fn resolve_method_call_enhanced(
+    /// `line` and `column` are 0-based, like [`crate::Range`]. The symbol is
+    /// the tightest range containing the position; `enclosing` follows its
+    /// [`Symbol::parent`] links, e.g. the type owning a method, then the
+    /// module. Members are followed by their type even when that type's range
+    /// does not enclose them (Rust `impl` blocks).
+    pub fn symbol_at(
         &self,
-        call_target: &str,
-        caller_name: &str,
-        file_id: FileId,
-        context: &dyn ResolutionScope,
-    ) -> Option<SymbolId> {
-        // Try to find corresponding MethodCall object for enhanced resolution
-        if let Some(method_calls) = self.method_calls_by_file.get(&file_id) {
-            // Normalize caller for language-specific matching (e.g., Python "<module>")
-            let caller_normalized = if let Ok(behavior) = self.get_behavior_for_file(file_id) {
-                behavior.normalize_caller_name(caller_name, file_id)
-            } else {
-                caller_name.to_string()
-            };
+        file_path: &str,
+        line: u32,
+        column: u16,
+    ) -> IndexResult<Option<SymbolAt>> {
+        let (file_id, _) = self.indexed_file(file_path)?;
+
+        let mut covering: Vec<Symbol> = self
+            .get_symbols_by_file(file_id)
+            .into_iter()
+            .filter(|symbol| symbol.range.contains(line, column))
+            .collect();
+        // Nested ranges span fewer lines, or start later and end earlier on the same lines
+        covering.sort_by_key(|symbol| {
+            let range = &symbol.range;
+            (
+                range.end_line - range.start_line,
+                std::cmp::Reverse(range.start_column),
+                range.end_column,
+            )
+        });
 
-            // O(1) hashmap lookup instead of O(m) linear search
-            let key = (caller_normalized, call_target.to_string());
-            if let Some(method_call) = method_calls.get(&key) {
-                debug_print!(
-                    self,
-                    "Found MethodCall object for {}->{}! Using enhanced resolution",
-                    caller_name,
-                    call_target
-                );
-                return self.resolve_method_call(method_call, file_id, context);
+        // Follow recorded parents; indexes built before parents were
+        // recorded fall back to the covering ranges and `Defines` edges
+        let mut chain: Vec<Symbol> = Vec::with_capacity(covering.len());
+        if let Some(innermost) = covering.first().filter(|s| s.parent.is_some()) {
+            let mut next = Some(innermost.clone());
+            while let Some(symbol) = next.take() {
+                if chain.iter().any(|s| s.id == symbol.id) {
+                    break;
+                }
+                next = symbol.parent.and_then(|id| self.get_symbol(id));
+                chain.push(symbol);
             }
+            return Ok(Self::split_symbol_at(chain));
         }
 
-        // No MethodCall object found - treat as regular function call
-        debug_print!(
-            self,
-            "No MethodCall object found for {}->{}. Resolving as regular function",
-            caller_name,
-            call_target
-        );
-        context.resolve(call_target)
+        for symbol in covering {
+            if chain.iter().any(|s| s.id == symbol.id) {
+                continue;
+            }
+            let definer = self
+                .document_index
+                .get_relationships_to(symbol.id, RelationKind::Defines)
+                .ok()
+                .unwrap_or_default()
+                .into_iter()
+                .find_map(|(from_id, _, _)| self.get_symbol(from_id));
+            chain.push(symbol);
+            if let Some(definer) = definer {
+                if !chain.iter().any(|s| s.id == definer.id) {
+                    chain.push(definer);
+                }
+            }
+        }
+
+        Ok(Self::split_symbol_at(chain))
     }
 
-    /// Resolve a method call using MethodCall struct with rich receiver information
-    fn resolve_method_call(
-        &self,
-        method_call: &crate::parsing::MethodCall,
-        file_id: FileId,
-        context: &dyn ResolutionScope,
-    ) -> Option<SymbolId> {
-        // If no receiver, treat as regular function call
-        let receiver = match &method_call.receiver {
-            Some(recv) => recv,
-            None => {
-                debug_print!(
-                    self,
-                    "No receiver found, resolving '{}' as regular function",
-                    method_call.method_name
-                );
-                let result = context.resolve(&method_call.method_name);
-                debug_print!(self, "Regular function resolution result: {:?}", result);
-                return result;
-            }
+    fn split_symbol_at(chain: Vec<Symbol>) -> Option<SymbolAt> {
+        let mut chain = chain.into_iter();
+        chain.next().map(|symbol| SymbolAt {
+            symbol,
+            enclosing: chain.collect(),
+        })
+    }
+
+    /// Look up an indexed file, accepting absolute or workspace-relative paths.
+    ///
+    /// Returns the file id and the path as stored in the index.
+    fn indexed_file<'a>(&self, file_path: &'a str) -> IndexResult<(FileId, &'a str)> {
+        // Indexed paths are stored relative to the workspace root
+        let file_path = match &self.settings.workspace_root {
+            Some(root) => Path::new(file_path)
+                .strip_prefix(root)
+                .ok()
+                .and_then(|p| p.to_str())
+                .unwrap_or(file_path),
+            None => file_path,
         };
 
-        debug_print!(
-            self,
-            "Resolving method call: receiver={}, method={}, is_static={}",
-            receiver,
-            method_call.method_name,
-            method_call.is_static
-        );
+        let (file_id, _) = self
+            .document_index
+            .get_file_info(file_path)
+            .map_err(|e| IndexError::TantivyError {
+                operation: "get_file_info".to_string(),
+                cause: e.to_string(),
+            })?
+            .ok_or_else(|| IndexError::General(format!("File '{file_path}' is not indexed")))?;
+        Ok((file_id, file_path))
+    }
 
-        // Handle static methods differently - they don't need receiver type lookup
-        if method_call.is_static {
-            debug_print!(
-                self,
-                "Static method call: {}::{}",
-                receiver,
-                method_call.method_name
-            );
+    /// Stream every symbol and relationship in the index to `writer`.
+    ///
+    /// Records are written as they are read from storage, so memory use does
+    /// not grow with the size of the index.
+    pub fn export<W: std::io::Write>(
+        &self,
+        writer: W,
+        format: crate::indexing::ExportFormat,
+    ) -> IndexResult<crate::indexing::ExportStats> {
+        use crate::indexing::export::{ExportedRelationship, IndexExporter, export_error};
+        use crate::storage::StorageError;
+
+        let mut exporter = IndexExporter::new(writer, format)
+            .map_err(StorageError::Io)
+            .map_err(export_error)?;
+        self.document_index
+            .for_each_symbol(|symbol| Ok(exporter.write_symbol(&symbol)?))
+            .map_err(export_error)?;
+        self.document_index
+            .for_each_relationship(|from, to, relationship| {
+                Ok(exporter.write_relationship(&ExportedRelationship {
+                    from,
+                    to,
+                    kind: relationship.kind,
+                })?)
+            })
+            .map_err(export_error)?;
+        exporter
+            .finish()
+            .map_err(StorageError::Io)
+            .map_err(export_error)
+    }
 
-            // CRITICAL: Check if receiver is from an external import FIRST
-            // This must happen BEFORE we try to resolve, otherwise we might
-            // incorrectly resolve external symbols (e.g., indicatif::ProgressBar)
-            // to local symbols with the same name
-            if context.is_external_import(receiver) {
-                debug_print!(
-                    self,
-                    "Receiver '{}' is from external import, not resolving static method '{}'",
-                    receiver,
-                    method_call.method_name
-                );
-                return None; // External library - don't resolve
-            }
+    /// Write the whole index into a new SQLite database at `path`.
+    ///
+    /// The database is built in a temporary file next to `path` and renamed
+    /// over it once complete, so a failed export leaves any previous file in
+    /// place. Files, symbols and relationships are inserted in a single
+    /// transaction.
+    pub fn export_sqlite(&self, path: &Path) -> IndexResult<crate::indexing::ExportStats> {
+        use crate::indexing::SqliteExporter;
+        use crate::indexing::export::{ExportedRelationship, export_error};
+        use crate::storage::StorageError;
+
+        let sqlite_error = |e: rusqlite::Error| {
+            StorageError::General(format!("Failed to write SQLite export: {e}"))
+        };
 
-            // For static calls, receiver is the type name, try Type::method format
-            let static_method = format!("{}::{}", receiver, method_call.method_name);
+        let write_error = |source| IndexError::FileWrite {
+            path: path.to_path_buf(),
+            source,
+        };
+        let dir = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        let temp = tempfile::Builder::new()
+            .prefix(".codanna-export-")
+            .suffix(".db")
+            .tempfile_in(dir)
+            .map_err(write_error)?;
 
-            // Try qualified resolution
-            if let Some(id) = context.resolve(&static_method) {
-                debug_print!(
-                    self,
-                    "Resolved static method {} to symbol_id: {:?}",
-                    static_method,
-                    id
-                );
-                return Some(id);
-            }
+        let mut exporter = SqliteExporter::create(temp.path())
+            .map_err(sqlite_error)
+            .map_err(export_error)?;
 
-            // Fall back to method name only (receiver was already checked as non-external above)
-            let result = context.resolve(&method_call.method_name);
-            debug_print!(
-                self,
-                "Static method fallback resolution for {}: {:?}",
-                method_call.method_name,
-                result
-            );
-            return result;
+        let mut files = self
+            .document_index
+            .query_file_info()
+            .map_err(export_error)?;
+        files.sort_by_key(|(file_id, ..)| file_id.value());
+        for (file_id, file_path, _hash, _timestamp) in files {
+            let language = crate::parsing::Language::from_path(Path::new(&file_path))
+                .map(|language| language.config_key().to_string());
+            exporter
+                .write_file(file_id, &file_path, language.as_deref())
+                .map_err(sqlite_error)
+                .map_err(export_error)?;
         }
 
-        // For instance methods, look up receiver's type
-        let type_name = self.variable_types.get(&(file_id, receiver.to_string()))?;
+        self.document_index
+            .for_each_symbol(|symbol| exporter.write_symbol(&symbol).map_err(sqlite_error))
+            .map_err(export_error)?;
+        self.document_index
+            .for_each_relationship(|from, to, relationship| {
+                exporter
+                    .write_relationship(
+                        &ExportedRelationship {
+                            from,
+                            to,
+                            kind: relationship.kind,
+                        },
+                        relationship.metadata.as_ref(),
+                    )
+                    .map_err(sqlite_error)
+            })
+            .map_err(export_error)?;
+        let stats = exporter
+            .finish()
+            .map_err(sqlite_error)
+            .map_err(export_error)?;
 
-        debug_print!(self, "Found type for {}: {}", receiver, type_name);
+        temp.persist(path).map_err(|e| write_error(e.error))?;
+        Ok(stats)
+    }
 
-        // Check if method comes from a trait
-        // Without legacy resolution, just try direct resolution
-        context.resolve(&method_call.method_name)
+    pub fn get_file_path(&self, file_id: FileId) -> Option<String> {
+        self.document_index.get_file_path(file_id).ok().flatten()
     }
 
-    /// Build resolution context for a file with all available symbols
-    fn build_resolution_context(&self, file_id: FileId) -> IndexResult<Box<dyn ResolutionScope>> {
-        // Use behavior's build_resolution_context which handles imports with our new matching logic
-        let behavior = self.get_behavior_for_file(file_id)?;
+    /// Read a symbol's source text from disk.
+    ///
+    /// Includes up to `context_lines` lines before the symbol (attributes,
+    /// decorators, doc comments). The range is clamped to the file's current
+    /// length, and `stale` is set when the file changed since it was indexed.
+    pub fn get_symbol_source(
+        &self,
+        symbol: &Symbol,
+        context_lines: u32,
+    ) -> IndexResult<SymbolSource> {
+        let (path, content, stale) = self.read_symbol_file(symbol)?;
+
+        let lines: Vec<&str> = content.lines().collect();
+        let last_line = lines.len().saturating_sub(1) as u32;
+        let end_line = symbol.range.end_line.min(last_line);
+        let symbol_start = symbol.range.start_line.min(end_line);
+        let start_line = symbol_start.saturating_sub(context_lines);
+        let clamped = symbol.range.end_line > last_line;
+
+        let text = lines
+            .get(start_line as usize..=end_line as usize)
+            .map(|l| l.join("\n"))
+            .unwrap_or_default();
+
+        Ok(SymbolSource {
+            path,
+            start_line,
+            end_line,
+            text,
+            stale,
+            clamped,
+        })
+    }
 
-        // NEW: Check if we can use cache-based resolution
-        if let Some(cache) = self.symbol_cache() {
-            // Build context with cache (fast path)
-            behavior.build_resolution_context_with_cache(file_id, cache, &self.document_index)
-        } else {
-            // Fall back to existing path (compatibility)
-            behavior.build_resolution_context(file_id, &self.document_index)
+    /// Read `context_lines` lines of source on each side of a symbol's start line.
+    ///
+    /// Fails when the file can no longer be read or is now shorter than the
+    /// symbol's start line.
+    pub fn get_source_snippet(
+        &self,
+        symbol: &Symbol,
+        context_lines: u32,
+    ) -> IndexResult<SourceSnippet> {
+        let (path, content, stale) = self.read_symbol_file(symbol)?;
+
+        let lines: Vec<&str> = content.lines().collect();
+        let symbol_line = symbol.range.start_line;
+        if symbol_line as usize >= lines.len() {
+            return Err(IndexError::General(format!(
+                "{} has {} lines, symbol starts on line {}",
+                path.display(),
+                lines.len(),
+                symbol_line + 1
+            )));
         }
+
+        let start_line = symbol_line.saturating_sub(context_lines);
+        let end_line = symbol_line
+            .saturating_add(context_lines)
+            .min(lines.len() as u32 - 1);
+
+        Ok(SourceSnippet {
+            start_line: start_line + 1,
+            symbol_line: symbol_line + 1,
+            lines: lines[start_line as usize..=end_line as usize]
+                .iter()
+                .map(|line| line.to_string())
+                .collect(),
+            stale,
+        })
     }
 
-    /// Resolve cross-file relationships using imports
-    fn resolve_cross_file_relationships(&mut self) -> IndexResult<()> {
-        // Process all unresolved relationships
-        let unresolved = std::mem::take(&mut self.unresolved_relationships);
+    /// Git blame summary for the lines a symbol spans.
+    ///
+    /// Fails with `HistoryError::NotARepository` when the workspace is not
+    /// under git.
+    pub fn symbol_history(&self, symbol: &Symbol) -> Result<LineHistory, HistoryError> {
+        let path = self.resolve_indexed_path(&symbol.file_path);
+        line_history(
+            &path,
+            &crate::paths::display_path(&symbol.file_path),
+            symbol.range.start_line + 1,
+            symbol.range.end_line + 1,
+        )
+    }
 
-        eprintln!(
-            "Resolving cross-file relationships: {} unresolved entries",
-            unresolved.len()
-        );
-        debug_print!(
-            self,
-            "resolve_cross_file_relationships: {} unresolved relationships",
-            unresolved.len()
-        );
+    /// Read the file a symbol lives in; `stale` is set when it changed since indexing
+    fn read_symbol_file(&self, symbol: &Symbol) -> IndexResult<(PathBuf, String, bool)> {
+        let path = match archive::split_entry_path(&symbol.file_path) {
+            Some(_) => PathBuf::from(&*symbol.file_path),
+            None => self.resolve_indexed_path(&symbol.file_path),
+        };
+        let (content, content_hash) = self.read_indexed_file(&symbol.file_path)?;
 
-        if unresolved.is_empty() {
-            eprintln!("DEBUG: No unresolved relationships to process");
-            return Ok(());
-        }
+        let stale = match self.document_index.get_file_info(&symbol.file_path) {
+            Ok(Some((_, indexed_hash))) => indexed_hash != content_hash,
+            _ => false,
+        };
 
-        // Start a batch for relationship updates
-        self.start_tantivy_batch()?;
+        Ok((path, content, stale))
+    }
 
-        let mut resolved_count = 0;
-        let mut skipped_count = 0;
-        let total_unresolved = unresolved.len();
+    /// Read and hash a file as stored in the index
+    ///
+    /// Archive entries are read out of their archive.
+    fn read_indexed_file(&self, stored: &str) -> IndexResult<(String, String)> {
+        match archive::split_entry_path(stored) {
+            Some((_, entry)) => {
+                let bytes = archive::read_entry(&self.resolve_indexed_path(stored), entry)?;
+                decode_source(Path::new(stored), bytes)
+            }
+            None => self.read_file_with_hash(&self.resolve_indexed_path(stored)),
+        }
+    }
 
-        let progress = if total_unresolved > 0 {
-            let options = ProgressBarOptions::default()
-                .with_style(ProgressBarStyle::VerticalSolid)
-                .with_width(28);
-            let bar = Arc::new(ProgressBar::with_options(
-                total_unresolved as u64,
-                "relationships",
-                "resolved",
-                "skipped",
-                options,
-            ));
-            let status = StatusLine::new(Arc::clone(&bar));
-            Some((bar, status))
-        } else {
-            None
-        };
+    /// Move an unchanged file's indexing timestamp past its mtime
+    ///
+    /// A touched but unchanged file is not re-indexed; without this it would
+    /// count as stale in [`SimpleIndexer::stale_files`] forever.
+    fn refresh_indexed_at(&self, file_id: FileId, path_str: &str, hash: &str) -> IndexResult<()> {
+        let modified = std::fs::metadata(self.resolve_indexed_path(path_str))
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|modified| modified.as_secs());
+        let indexed_at = self
+            .document_index
+            .get_file_timestamp(path_str)
+            .map_err(|e| IndexError::TantivyError {
+                operation: "get_file_timestamp".to_string(),
+                cause: e.to_string(),
+            })?;
+        match (modified, indexed_at) {
+            (Some(modified), Some(indexed_at)) if modified > indexed_at => self
+                .document_index
+                .replace_file_info(file_id, path_str, hash, get_utc_timestamp().max(modified))
+                .map_err(|e| IndexError::TantivyError {
+                    operation: "replace_file_info".to_string(),
+                    cause: e.to_string(),
+                }),
+            _ => Ok(()),
+        }
+    }
 
-        // Group relationships by file for efficient context building
-        let mut relationships_by_file: std::collections::HashMap<
-            FileId,
-            Vec<UnresolvedRelationship>,
-        > = std::collections::HashMap::new();
-        for rel in unresolved {
-            relationships_by_file
-                .entry(rel.file_id)
-                .or_default()
-                .push(rel);
+    /// Resolve a path as stored in the index against the workspace root
+    ///
+    /// Archive entries resolve to their archive, the file on disk holding them.
+    fn resolve_indexed_path(&self, stored: &str) -> PathBuf {
+        let stored_path = archive::split_entry_path(stored)
+            .map_or(Path::new(stored), |(archive_path, _)| archive_path);
+        match &self.settings.workspace_root {
+            Some(root) if stored_path.is_relative() => root.join(stored_path),
+            _ => stored_path.to_path_buf(),
         }
+    }
 
-        // Symbol lookup cache to avoid millions of duplicate Tantivy queries
-        // Maps symbol_name -> Vec<Symbol>
-        let mut symbol_lookup_cache: std::collections::HashMap<String, Vec<Symbol>> =
-            std::collections::HashMap::new();
+    /// Indexed files modified on disk after they were last indexed.
+    ///
+    /// Compares each file's mtime with the timestamp recorded when it was
+    /// indexed. Files that no longer exist are not reported. Paths are
+    /// returned as stored in the index, sorted.
+    pub fn stale_files(&self) -> Vec<PathBuf> {
+        let files = self.document_index.query_file_info().unwrap_or_else(|e| {
+            eprintln!("Warning: Failed to read file info: {e}");
+            Vec::new()
+        });
 
-        // Process each file's relationships with its resolution context
-        for (file_id, file_relationships) in relationships_by_file {
-            // Build resolution context for this file
-            let context = self.build_resolution_context(file_id)?;
+        let mut stale: Vec<PathBuf> = files
+            .into_iter()
+            .filter(|(_, path, _, indexed_at)| {
+                std::fs::metadata(self.resolve_indexed_path(path))
+                    .and_then(|metadata| metadata.modified())
+                    .ok()
+                    .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+                    .is_some_and(|modified| modified.as_secs() > *indexed_at)
+            })
+            .map(|(_, path, _, _)| PathBuf::from(path))
+            .collect();
+        stale.sort();
+        stale
+    }
 
-            for rel in file_relationships {
-                if let Some((bar, _)) = &progress {
-                    bar.inc();
+    /// Re-hash every indexed file and compare it with the hash recorded at
+    /// indexing time.
+    ///
+    /// Unlike [`SimpleIndexer::stale_files`] this reads each file, so touched
+    /// but unchanged files count as in sync and edits that kept the mtime do
+    /// not. Files that can no longer be read as text count as changed.
+    pub fn verify_against_disk(&self) -> IndexVerification {
+        let files = self.document_index.query_file_info().unwrap_or_else(|e| {
+            eprintln!("Warning: Failed to read file info: {e}");
+            Vec::new()
+        });
+
+        let mut verification = IndexVerification::default();
+        for (_, path, indexed_hash, _) in files {
+            match self.read_indexed_file(&path) {
+                Ok((_, hash)) if hash == indexed_hash => verification.ok += 1,
+                Ok(_) => verification.changed.push(PathBuf::from(path)),
+                Err(IndexError::FileRead { source, .. })
+                    if source.kind() == std::io::ErrorKind::NotFound =>
+                {
+                    verification.missing.push(PathBuf::from(path))
                 }
+                Err(_) => verification.changed.push(PathBuf::from(path)),
+            }
+        }
+        verification.changed.sort();
+        verification.missing.sort();
+        verification
+    }
 
-                debug_print!(
-                    self,
-                    "Processing relationship: {} -> {} (kind: {:?}, file: {:?})",
-                    rel.from_name,
-                    rel.to_name,
-                    rel.kind,
-                    rel.file_id
-                );
+    /// Paths of indexed files ending with `file`, as stored in the index
+    ///
+    /// Whole path components are compared, so `parser.rs` matches
+    /// `src/parser.rs` but not `src/myparser.rs`.
+    pub fn files_matching(&self, file: &str) -> std::collections::HashSet<String> {
+        let file = Path::new(file);
+        self.document_index
+            .query_file_info()
+            .unwrap_or_else(|e| {
+                eprintln!("Warning: Failed to read file info: {e}");
+                Vec::new()
+            })
+            .into_iter()
+            .map(|(_, path, _, _)| path)
+            .filter(|path| Path::new(path).ends_with(file))
+            .collect()
+    }
 
-                // Find 'from' symbols - use from_id when available to skip lookup
-                let from_symbols: Vec<_> = if let Some(from_id) = rel.from_id {
-                    // We already have the from_id, just fetch the symbol
-                    debug_print!(
-                        self,
-                        "Using cached from_id: {:?} for '{}'",
-                        from_id,
-                        rel.from_name
-                    );
-                    match self.document_index.find_symbol_by_id(from_id) {
-                        Ok(Some(symbol)) => vec![symbol],
-                        Ok(None) => {
-                            debug_print!(
-                                self,
-                                "WARNING: from_id {:?} not found for '{}'",
-                                from_id,
-                                rel.from_name
-                            );
-                            Vec::new()
-                        }
-                        Err(e) => {
-                            debug_print!(
-                                self,
-                                "ERROR: Failed to fetch symbol {:?}: {}",
-                                from_id,
-                                e
-                            );
-                            Vec::new()
-                        }
-                    }
-                } else {
-                    // Fallback to name-based lookup
-                    debug_print!(
-                        self,
-                        "No from_id available, falling back to name lookup for '{}'",
-                        rel.from_name
-                    );
-                    let behavior_for_file = self.get_behavior_for_file(file_id)?;
-                    let from_query_name =
-                        behavior_for_file.normalize_caller_name(&rel.from_name, file_id);
+    /// Paths of files indexed at or after `since` (UTC seconds since the
+    /// epoch), as stored in the index
+    pub fn files_indexed_since(&self, since: u64) -> std::collections::HashSet<String> {
+        self.document_index
+            .query_file_info()
+            .unwrap_or_else(|e| {
+                eprintln!("Warning: Failed to read file info: {e}");
+                Vec::new()
+            })
+            .into_iter()
+            .filter(|(_, _, _, indexed_at)| *indexed_at >= since)
+            .map(|(_, path, _, _)| path)
+            .collect()
+    }
 
-                    // Check cache first to avoid duplicate Tantivy queries
-                    let all_from_symbols =
-                        if let Some(cached) = symbol_lookup_cache.get(&from_query_name) {
-                            cached.clone()
-                        } else {
-                            // Cache miss - query Tantivy and cache the result
-                            let symbols = self
-                                .document_index
-                                .find_symbols_by_name(&from_query_name, None)
-                                .map_err(|e| IndexError::TantivyError {
-                                    operation: "find_symbols_by_name".to_string(),
-                                    cause: e.to_string(),
-                                })?;
-                            symbol_lookup_cache.insert(from_query_name.clone(), symbols.clone());
-                            symbols
-                        };
+    /// Get all indexed file paths - used by file watcher
+    pub fn get_all_indexed_paths(&self) -> Vec<PathBuf> {
+        self.document_index
+            .get_all_indexed_paths()
+            .unwrap_or_else(|e| {
+                eprintln!("Warning: Failed to get indexed paths: {e}");
+                Vec::new()
+            })
+    }
 
-                    debug_print!(
-                        self,
-                        "Looking for '{}' symbols, found {} total",
-                        from_query_name,
-                        all_from_symbols.len()
-                    );
+    /// Track a directory as indexed (stores canonicalized path)
+    pub fn add_indexed_path(&mut self, dir_path: &Path) -> IndexResult<()> {
+        let canonical = dir_path.canonicalize().map_err(|e| IndexError::FileRead {
+            path: dir_path.to_path_buf(),
+            source: e,
+        })?;
 
-                    // Filter to only symbols from the current file
-                    let from_symbols: Vec<_> = all_from_symbols
-                        .into_iter()
-                        .filter(|s| s.file_id == file_id)
-                        .collect();
+        // Skip if this path is already covered by an existing indexed directory
+        if self
+            .indexed_paths
+            .iter()
+            .any(|existing| canonical.starts_with(existing))
+        {
+            return Ok(());
+        }
 
-                    debug_print!(
-                        self,
-                        "Found {} from_symbols in current file",
-                        from_symbols.len()
-                    );
+        // Remove any tracked paths that are descendants of the new canonical path
+        self.indexed_paths
+            .retain(|existing| !existing.starts_with(canonical.as_path()));
 
-                    if from_symbols.is_empty() && rel.kind == RelationKind::Calls {
-                        debug_print!(
-                            self,
-                            "WARNING: No '{}' symbol found in file {:?} for Calls relationship to '{}'",
-                            from_query_name,
-                            file_id,
-                            rel.to_name
-                        );
-                    }
+        self.indexed_paths.insert(canonical);
+        Ok(())
+    }
 
-                    from_symbols
-                };
+    /// Get all currently tracked indexed directories
+    pub fn get_indexed_paths(&self) -> &std::collections::HashSet<PathBuf> {
+        &self.indexed_paths
+    }
 
-                // Use the clean resolution API that delegates to language-specific logic
-                let to_symbol_id = if rel.kind == RelationKind::Calls && from_symbols.len() == 1 {
-                    // Special handling for method calls with enhanced resolution
-                    debug_print!(self, "Resolving as method call: '{}'", rel.to_name);
-                    let res = self.resolve_method_call_enhanced(
-                        &rel.to_name,
-                        &rel.from_name,
-                        file_id,
-                        context.as_ref(),
-                    );
-                    debug_print!(
-                        self,
-                        "resolve_method_call_enhanced returned: {:?} for {}",
-                        res,
-                        rel.to_name
-                    );
-                    if res.is_none() {
-                        debug_print!(
-                            self,
-                            "Resolution failed, trying external mapping for {}",
-                            rel.to_name
-                        );
-                        // Try external mapping as a fallback
-                        if let Some(behavior) = self.file_behaviors.get(&file_id) {
-                            // Build a better external key using MethodCall receiver if available
-                            let to_key = if let Some(method_calls) =
-                                self.method_calls_by_file.get(&file_id)
-                            {
-                                // O(1) hashmap lookup by caller and method name
-                                let caller_name =
-                                    from_symbols.first().map(|s| s.name.as_ref()).unwrap_or("");
-                                let key = (caller_name.to_string(), rel.to_name.to_string());
-                                if let Some(mc) = method_calls.get(&key) {
-                                    if let Some(recv) = &mc.receiver {
-                                        format!("{recv}.{}", mc.method_name)
-                                    } else {
-                                        rel.to_name.to_string()
-                                    }
-                                } else {
-                                    rel.to_name.to_string()
-                                }
-                            } else {
-                                rel.to_name.to_string()
-                            };
+    /// Whether `sync_with_config` would add or remove any directory
+    ///
+    /// Lets query commands decide up front if a read-only load is enough.
+    pub fn needs_sync(stored_paths: Option<Vec<PathBuf>>, config_paths: &[PathBuf]) -> bool {
+        let (new_paths, removed_paths) = Self::indexed_path_changes(stored_paths, config_paths);
+        !new_paths.is_empty() || !removed_paths.is_empty()
+    }
 
-                            debug_print!(
-                                self,
-                                "Trying to resolve external call target: '{}' for file {:?}",
-                                to_key,
-                                file_id
-                            );
-                            if let Some((module_path, symbol_name)) =
-                                behavior.resolve_external_call_target(&to_key, file_id)
-                            {
-                                // Skip external symbol creation for resolved external calls
-                                debug_print!(
-                                    self,
-                                    "Skipping external symbol for resolved call: {} -> {}::{}",
-                                    to_key,
-                                    module_path,
-                                    symbol_name
-                                );
-                                None
-                            } else {
-                                None
-                            }
-                        } else {
-                            None
-                        }
-                    } else {
-                        res
-                    }
-                } else {
-                    // Delegate all relationship resolution to the language-specific context
-                    // This includes Defines, Implements, Extends, and other relationships
-                    debug_print!(
-                        self,
-                        "Resolving relationship: {} -> {} (kind: {:?})",
-                        rel.from_name,
-                        rel.to_name,
-                        rel.kind
-                    );
-                    let result = context.resolve_relationship(
-                        &rel.from_name,
-                        &rel.to_name,
-                        rel.kind,
-                        file_id,
+    /// Directories to add and remove to bring stored paths in line with config
+    fn indexed_path_changes(
+        stored_paths: Option<Vec<PathBuf>>,
+        config_paths: &[PathBuf],
+    ) -> (Vec<PathBuf>, Vec<PathBuf>) {
+        // Convert to sets for comparison (canonicalized)
+        let stored_set: std::collections::HashSet<PathBuf> = stored_paths
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|p| p.canonicalize().ok())
+            .collect();
+
+        let config_set: std::collections::HashSet<PathBuf> = config_paths
+            .iter()
+            .filter_map(|p| p.canonicalize().ok())
+            .collect();
+
+        // Find new paths in config that aren't in stored state
+        let new_paths: Vec<PathBuf> = config_set.difference(&stored_set).cloned().collect();
+
+        // Find removed paths (in stored but not in config)
+        let removed_paths: Vec<PathBuf> = stored_set.difference(&config_set).cloned().collect();
+
+        (new_paths, removed_paths)
+    }
+
+    /// Sync indexed paths with config (settings.toml is source of truth)
+    ///
+    /// Compares stored indexed_paths with current config.
+    /// - Indexes new directories in config
+    /// - Removes symbols from directories not in config
+    ///
+    /// Returns (added_count, removed_count, files_indexed, symbols_found)
+    pub fn sync_with_config(
+        &mut self,
+        stored_paths: Option<Vec<PathBuf>>,
+        config_paths: &[PathBuf],
+        progress: bool,
+    ) -> IndexResult<(usize, usize, usize, usize)> {
+        let (new_paths, removed_paths) = Self::indexed_path_changes(stored_paths, config_paths);
+
+        if new_paths.is_empty() && removed_paths.is_empty() {
+            return Ok((0, 0, 0, 0));
+        }
+
+        crate::log_info!("Sync: Found {} new directories to index", new_paths.len());
+        for path in &new_paths {
+            crate::log_info!("  + {}", path.display());
+        }
+
+        // Index new directories
+        let mut total_files = 0;
+        let mut total_symbols = 0;
+
+        for path in &new_paths {
+            crate::log_info!("Indexing new directory: {}", path.display());
+            let result = if path.is_file() && ArchiveFormat::from_path(path).is_some() {
+                self.index_archive(path, false, false)
+            } else {
+                self.index_directory(path, progress, false)
+            };
+            match result {
+                Ok(stats) => {
+                    crate::log_info!(
+                        path = path.display().to_string(),
+                        files = stats.files_indexed as u64,
+                        symbols = stats.symbols_found as u64;
+                        "  ✓ Indexed {} files, {} symbols",
+                        stats.files_indexed,
+                        stats.symbols_found
                     );
-                    debug_print!(self, "Resolution result: {:?}", result);
-                    // If unresolved call, try language behavior external mapping
-                    if result.is_none() && rel.kind == RelationKind::Calls {
-                        if let Some(behavior) = self.file_behaviors.get(&file_id) {
-                            if let Some((module_path, symbol_name)) =
-                                behavior.resolve_external_call_target(&rel.to_name, file_id)
-                            {
-                                // Skip external symbol creation for mapped external calls
-                                debug_print!(
-                                    self,
-                                    "Skipping external symbol for mapped call: {} -> {}::{}",
-                                    rel.to_name,
-                                    module_path,
-                                    symbol_name
+                    total_files += stats.files_indexed;
+                    total_symbols += stats.symbols_found;
+
+                    // Track this directory as indexed
+                    if self.settings.debug {
+                        crate::log_debug!("Tracking indexed path: {}", path.display());
+                    }
+                    match self.add_indexed_path(path) {
+                        Ok(_) => {
+                            if self.settings.debug {
+                                crate::log_debug!(
+                                    "Successfully tracked path (total: {})",
+                                    self.indexed_paths.len()
                                 );
-                                None
-                            } else {
-                                None
                             }
-                        } else {
-                            None
                         }
-                    } else {
-                        result
+                        Err(e) => {
+                            crate::log_error!("  ✗ Failed to track indexed path: {e}");
+                        }
                     }
-                };
+                }
+                Err(e) => {
+                    crate::log_error!(path = path.display().to_string(); "  ✗ Failed to index {}: {e}", path.display());
+                }
+            }
+        }
 
-                let to_symbol_id = match to_symbol_id {
-                    Some(id) => {
-                        debug_print!(
-                            self,
-                            "Resolved target symbol '{}' to ID: {:?}",
-                            rel.to_name,
-                            id
-                        );
-                        id
-                    }
-                    None => {
-                        debug_print!(
-                            self,
-                            "[SKIP-RESOLUTION] Failed to resolve '{}' from '{}' in file {:?} (kind: {:?})",
-                            rel.to_name,
-                            rel.from_name,
-                            rel.file_id,
-                            rel.kind
-                        );
-                        // Symbol not in scope - skip this relationship
-                        skipped_count += 1;
-                        if let Some((bar, _)) = &progress {
-                            bar.add_extra2(1);
+        // Clean up removed directories
+        let mut removed_file_count = 0;
+        if !removed_paths.is_empty() {
+            crate::log_info!("Sync: Found {} directories to remove", removed_paths.len());
+            for path in &removed_paths {
+                crate::log_info!("  - {}", path.display());
+            }
+
+            // Collect all files to remove first
+            let all_files = self.get_all_indexed_paths();
+            let mut files_to_remove = Vec::new();
+
+            for file_path in all_files {
+                // Archive entries are purged with their archive
+                let on_disk = match file_path.to_str() {
+                    Some(stored) => self.resolve_indexed_path(stored),
+                    None => file_path.clone(),
+                };
+                if let Ok(file_canonical) = on_disk.canonicalize() {
+                    for removed_path in &removed_paths {
+                        if file_canonical.starts_with(removed_path) {
+                            files_to_remove.push(file_path.clone());
+                            break;
                         }
-                        continue;
                     }
+                }
+            }
+
+            // Remove each file (each manages its own batch/commit)
+            if !files_to_remove.is_empty() {
+                let progress_view = if files_to_remove.len() > 1 && !crate::io::logging::is_json() {
+                    let options = ProgressBarOptions::default()
+                        .with_style(ProgressBarStyle::VerticalSolid)
+                        .with_width(28);
+                    let bar = Arc::new(ProgressBar::with_options(
+                        files_to_remove.len() as u64,
+                        "files",
+                        "removed",
+                        "failed",
+                        options,
+                    ));
+                    let status = StatusLine::new(Arc::clone(&bar));
+                    Some((bar, status))
+                } else {
+                    None
                 };
 
-                // Get the full symbol data
-                debug_print!(self, "Looking up symbol by ID: {:?}", to_symbol_id);
-                let to_symbol = match self
-                    .document_index
-                    .find_symbol_by_id(to_symbol_id)
-                    .map_err(|e| IndexError::TantivyError {
-                        operation: "find_symbol_by_id".to_string(),
-                        cause: e.to_string(),
-                    })? {
-                    Some(symbol) => {
-                        debug_print!(self, "Found target symbol: {}", symbol.name);
-                        symbol
-                    }
-                    None => {
-                        debug_print!(
-                            self,
-                            "[SKIP-NOT-FOUND] Symbol ID {:?} not found in index for '{}'",
-                            to_symbol_id,
-                            rel.to_name
-                        );
-                        skipped_count += 1;
-                        if let Some((bar, _)) = &progress {
-                            bar.add_extra2(1);
+                let mut removed_symbols = 0;
+                for file_path in &files_to_remove {
+                    let mut success = false;
+                    match self.remove_file_in_pass(file_path) {
+                        Ok(count) => {
+                            removed_file_count += 1;
+                            removed_symbols += count;
+                            success = true;
+                        }
+                        Err(e) => {
+                            crate::log_error!("  ✗ Failed to remove {}: {e}", file_path.display());
                         }
-                        continue;
                     }
-                };
-
-                // Process with our filtering logic
-                debug_print!(self, "Processing {} from symbols", from_symbols.len());
-                for from_symbol in &from_symbols {
-                    debug_print!(
-                        self,
-                        "Checking relationship from {} to {}",
-                        from_symbol.name,
-                        to_symbol.name
-                    );
 
-                    // Check symbol kind compatibility
-                    if !Self::is_compatible_relationship(from_symbol.kind, to_symbol.kind, rel.kind)
-                    {
-                        debug_print!(
-                            self,
-                            "[SKIP-INCOMPATIBLE] {} ({:?}) -> {} ({:?}) for {:?}",
-                            from_symbol.name,
-                            from_symbol.kind,
-                            to_symbol.name,
-                            to_symbol.kind,
-                            rel.kind
-                        );
-                        skipped_count += 1;
-                        if let Some((bar, _)) = &progress {
+                    if let Some((bar, _)) = &progress_view {
+                        bar.inc();
+                        if success {
+                            bar.add_extra1(1);
+                        } else {
                             bar.add_extra2(1);
                         }
-                        continue;
                     }
+                }
 
-                    // Check visibility (skip for Defines - a type can always see its own methods)
-                    if rel.kind != RelationKind::Defines {
-                        debug_print!(
-                            self,
-                            "Checking visibility: {} (vis: {:?}, module: {:?}) from {} (module: {:?})",
-                            to_symbol.name,
-                            to_symbol.visibility,
-                            to_symbol.module_path,
-                            from_symbol.name,
-                            from_symbol.module_path
-                        );
-                        if !Self::is_symbol_visible_from(&to_symbol, from_symbol) {
-                            debug_print!(
-                                self,
-                                "[SKIP-VISIBILITY] {} not visible from {} (to_vis: {:?}, to_module: {:?}, from_module: {:?})",
-                                to_symbol.name,
-                                from_symbol.name,
-                                to_symbol.visibility,
-                                to_symbol.module_path,
-                                from_symbol.module_path
-                            );
-                            skipped_count += 1;
-                            if let Some((bar, _)) = &progress {
-                                bar.add_extra2(1);
-                            }
-                            continue;
-                        }
-                    }
+                if let Some((bar, status)) = progress_view {
+                    drop(status);
+                    eprintln!("{bar}");
+                }
 
-                    // Add the relationship with preserved metadata
-                    debug_print!(
-                        self,
-                        "[SUCCESS] Adding relationship: {} ({:?}) -> {} ({:?}) kind: {:?}",
-                        from_symbol.name,
-                        from_symbol.id,
-                        to_symbol.name,
-                        to_symbol.id,
-                        rel.kind
-                    );
-                    let mut relationship = Relationship::new(rel.kind);
-                    if let Some(ref metadata) = rel.metadata {
-                        relationship = relationship.with_metadata(metadata.clone());
-                    }
-                    self.add_relationship_internal(from_symbol.id, to_symbol.id, relationship)?;
-                    resolved_count += 1;
-                    if let Some((bar, _)) = &progress {
-                        bar.add_extra1(1);
-                    }
+                if removed_symbols > 0 {
+                    self.save_semantic_after_removal();
+                }
+                if removed_file_count > 0 {
+                    crate::log_info!("  ✓ Removed {removed_file_count} files from index");
                 }
             }
-        }
-
-        // Commit the batch with all the relationships
-        self.commit_tantivy_batch()?;
 
-        if let Some((bar, status)) = progress {
-            drop(status);
-            eprintln!("{bar}");
+            // Remove paths from tracked set after successful removal
+            for path in &removed_paths {
+                self.indexed_paths.remove(path);
+            }
         }
 
-        debug_print!(
-            self,
-            "Relationship resolution complete - resolved: {}, skipped: {}, total: {}",
-            resolved_count,
-            skipped_count,
-            total_unresolved
-        );
-
-        Ok(())
+        Ok((
+            new_paths.len(),
+            removed_paths.len(),
+            total_files,
+            total_symbols,
+        ))
     }
 
-    // Note: external symbol creation moved to language behavior implementations
-
-    /// Process pending embeddings after a successful Tantivy commit
-    fn process_pending_embeddings(
-        &mut self,
-        vector_engine: &Arc<Mutex<VectorSearchEngine>>,
-        embedding_generator: &Arc<dyn EmbeddingGenerator>,
-    ) -> IndexResult<()> {
-        if self.pending_embeddings.is_empty() {
-            return Ok(());
-        }
-
-        // Extract texts for embedding generation
-        let texts: Vec<&str> = self
-            .pending_embeddings
-            .iter()
-            .map(|(_, text)| text.as_str())
-            .collect();
+    /// Search documentation using natural language query
+    /// Returns symbols with their similarity scores, sorted by relevance
+    pub fn semantic_search_docs(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> IndexResult<Vec<(Symbol, f32)>> {
+        self.semantic_search_docs_with_language(query, limit, None)
+    }
 
-        // Generate embeddings
-        let embeddings = embedding_generator
-            .generate_embeddings(&texts)
-            .map_err(|e| IndexError::General(format!("Vector embedding generation failed: {e}")))?;
+    /// Search documentation using natural language query with optional language filter
+    /// Returns symbols with their similarity scores, sorted by relevance
+    pub fn semantic_search_docs_with_language(
+        &self,
+        query: &str,
+        limit: usize,
+        language_filter: Option<&str>,
+    ) -> IndexResult<Vec<(Symbol, f32)>> {
+        let semantic = self.semantic_search.as_ref().ok_or_else(|| {
+            IndexError::General(
+                "Semantic search is not enabled. Call enable_semantic_search() first.".to_string(),
+            )
+        })?;
 
-        // Validate embedding count matches input
-        if embeddings.len() != texts.len() {
-            return Err(IndexError::General(format!(
-                "Embedding count mismatch: expected {}, got {}",
-                texts.len(),
-                embeddings.len()
-            )));
-        }
+        // Use the new language-aware search that filters BEFORE computing similarity
+        let results = semantic
+            .lock()
+            .unwrap()
+            .search_with_language(query, limit, language_filter)
+            .map_err(|e| IndexError::General(format!("Semantic search failed: {e}")))?;
 
-        // Create vector IDs and embeddings pairs
-        let mut vectors = Vec::with_capacity(self.pending_embeddings.len());
-        for (i, (symbol_id, _)) in self.pending_embeddings.iter().enumerate() {
-            // Convert SymbolId to VectorId (both wrap u32)
-            if let Some(vector_id) = crate::vector::VectorId::new(symbol_id.value()) {
-                vectors.push((vector_id, embeddings[i].clone()));
+        // Convert SymbolIds to Symbols
+        let mut symbol_results = Vec::with_capacity(results.len());
+        for (symbol_id, score) in results {
+            if let Some(symbol) = self.get_symbol(symbol_id) {
+                symbol_results.push((symbol, score));
             }
         }
 
-        // Index vectors
-        vector_engine
-            .lock()
-            .map_err(|_| IndexError::General("Vector engine mutex poisoned".to_string()))?
-            .index_vectors(&vectors)
-            .map_err(|e| IndexError::General(format!("Vector indexing failed: {e}")))?;
-
-        // Clear pending embeddings
-        self.pending_embeddings.clear();
-
-        Ok(())
+        Ok(symbol_results)
     }
 
-    /// Build or rebuild the symbol cache from current index
-    pub fn build_symbol_cache(&mut self) -> IndexResult<()> {
-        let cache_path = self.get_cache_path();
-
-        // Clear any existing cache first to release memory-mapped views (Windows fix)
-        self.clear_symbol_cache(false)?;
+    /// Whether a semantic query over `language_filter` runs exact or approximate
+    pub fn semantic_search_mode(
+        &self,
+        language_filter: Option<&str>,
+    ) -> Option<crate::semantic::SearchMode> {
+        self.semantic_search
+            .as_ref()
+            .map(|semantic| semantic.lock().unwrap().search_mode(language_filter))
+    }
 
-        // Get all symbols from the index (use the existing public method)
-        let all_symbols = self.get_all_symbols();
-        debug_print!(
-            self,
-            "Building symbol cache with {} symbols at {}",
-            all_symbols.len(),
-            cache_path.display()
-        );
+    /// Search documentation with similarity threshold
+    pub fn semantic_search_docs_with_threshold(
+        &self,
+        query: &str,
+        limit: usize,
+        threshold: f32,
+    ) -> IndexResult<Vec<(Symbol, f32)>> {
+        self.semantic_search_docs_with_threshold_and_language(query, limit, threshold, None)
+    }
 
-        // Build the cache file
-        crate::storage::symbol_cache::SymbolHashCache::build_from_symbols(
-            &cache_path,
-            all_symbols.iter(),
-        )
-        .map_err(|e| {
-            // Check for Windows file locking error
-            let error_msg = if cfg!(windows) && e.to_string().contains("os error 1224") {
-                format!(
-                    "Failed to build symbol cache: {e}\n\n\
-                    Windows file locking detected. The cache file is currently in use.\n\
-                    To fix this issue:\n\
-                    1. Stop all running codanna processes\n\
-                    2. Delete the cache file: {}\n\
-                    3. Re-run the index command with --force flag\n\
-                    \n\
-                    Example: codanna index src --force",
-                    cache_path.display()
-                )
-            } else {
-                format!("Failed to build symbol cache: {e}")
-            };
-            IndexError::General(error_msg)
+    /// Search documentation with similarity threshold and optional language filter
+    pub fn semantic_search_docs_with_threshold_and_language(
+        &self,
+        query: &str,
+        limit: usize,
+        threshold: f32,
+        language_filter: Option<&str>,
+    ) -> IndexResult<Vec<(Symbol, f32)>> {
+        let semantic = self.semantic_search.as_ref().ok_or_else(|| {
+            IndexError::General(
+                "Semantic search is not enabled. Call enable_semantic_search() first.".to_string(),
+            )
         })?;
 
-        // Load the cache for immediate use
-        self.load_symbol_cache()?;
-
-        debug_print!(
-            self,
-            "Built symbol cache with {} symbols",
-            all_symbols.len()
-        );
-        Ok(())
-    }
+        // First get language-filtered results, then apply threshold
+        let results = semantic
+            .lock()
+            .unwrap()
+            .search_with_language(query, limit * 2, language_filter)
+            .map_err(|e| IndexError::General(format!("Semantic search failed: {e}")))?;
 
-    /// Clear symbol cache (drop memory-mapped view and optionally delete file)
-    pub fn clear_symbol_cache(&mut self, delete_file: bool) -> IndexResult<()> {
-        // Drop the existing cache to release any memory-mapped views
-        self.symbol_cache = None;
+        // Convert SymbolIds to Symbols and apply threshold filter
+        let mut symbol_results = Vec::with_capacity(limit);
+        for (symbol_id, score) in results {
+            // Apply threshold filter
+            if score < threshold {
+                continue;
+            }
 
-        if delete_file {
-            let cache_path = self.get_cache_path();
-            if cache_path.exists() {
-                std::fs::remove_file(&cache_path).map_err(|e| {
-                    IndexError::General(format!("Failed to delete symbol cache file: {e}"))
-                })?;
-                debug_print!(self, "Deleted symbol cache file: {}", cache_path.display());
+            if let Some(symbol) = self.get_symbol(symbol_id) {
+                symbol_results.push((symbol, score));
+                if symbol_results.len() >= limit {
+                    break;
+                }
             }
         }
 
-        Ok(())
+        Ok(symbol_results)
     }
 
-    /// Load symbol cache if it exists
-    pub fn load_symbol_cache(&mut self) -> IndexResult<()> {
-        let cache_path = self.get_cache_path();
+    /// Clear the Tantivy index
+    pub fn clear_tantivy_index(&mut self) -> IndexResult<()> {
+        // RESOLUTION SYSTEM: Behaviors maintain their own state
+        // No centralized resolver to clear anymore
+        self.trait_symbols_by_file.clear();
+        self.variable_types.clear();
 
-        if cache_path.exists() {
-            match crate::storage::symbol_cache::SymbolHashCache::open(&cache_path) {
-                Ok(cache) => {
-                    self.symbol_cache = Some(Arc::new(
-                        crate::storage::symbol_cache::ConcurrentSymbolCache::new(cache),
-                    ));
-                    debug_print!(self, "Loaded symbol cache from {}", cache_path.display());
-                    Ok(())
+        // Clear semantic search if enabled
+        if let Some(ref semantic) = self.semantic_search {
+            semantic.lock().unwrap().clear();
+        }
+
+        if self.occurrences.is_some() {
+            self.occurrences = Some(crate::storage::OccurrenceIndex::new());
+        }
+
+        self.document_index
+            .clear()
+            .map_err(|e| IndexError::TantivyError {
+                operation: "clear_index".to_string(),
+                cause: e.to_string(),
+            })
+    }
+
+    /// Search using full-text search
+    #[must_use = "Search results should be used"]
+    pub fn search(
+        &self,
+        query: &str,
+        limit: usize,
+        kind_filter: Option<crate::types::SymbolKind>,
+        module_filter: Option<&str>,
+        language_filter: Option<&str>,
+        filters: &crate::storage::SearchFilters,
+    ) -> IndexResult<Vec<SearchResult>> {
+        self.document_index
+            .search_filtered(
+                query,
+                limit,
+                kind_filter,
+                module_filter,
+                language_filter,
+                filters,
+            )
+            .map_err(|e| IndexError::General(format!("Search failed: {e}")))
+    }
+
+    /// Search symbols by matching their names against a regular expression.
+    ///
+    /// Unlike [`search`](Self::search), no fuzzy matching or tokenization is
+    /// applied: `^handle_.*_request$` matches exactly those names. The compiled
+    /// pattern size is capped and matching is bounded by a time budget.
+    pub fn search_regex(
+        &self,
+        pattern: &str,
+        limit: usize,
+        kind_filter: Option<crate::types::SymbolKind>,
+        module_filter: Option<&str>,
+        language_filter: Option<&str>,
+        filters: &crate::storage::SearchFilters,
+    ) -> IndexResult<Vec<SearchResult>> {
+        const REGEX_SIZE_LIMIT: usize = 1 << 20;
+        const REGEX_TIME_BUDGET: std::time::Duration = std::time::Duration::from_secs(5);
+
+        let regex = regex::RegexBuilder::new(pattern)
+            .size_limit(REGEX_SIZE_LIMIT)
+            .dfa_size_limit(REGEX_SIZE_LIMIT)
+            .build()
+            .map_err(|e| IndexError::InvalidQuery {
+                query: pattern.to_string(),
+                reason: e.to_string(),
+            })?;
+
+        let deadline = std::time::Instant::now() + REGEX_TIME_BUDGET;
+        self.document_index
+            .search_by_name_regex(
+                &regex,
+                limit,
+                kind_filter,
+                module_filter,
+                language_filter,
+                filters,
+                Some(deadline),
+            )
+            .map_err(|e| IndexError::General(format!("Regex search failed: {e}")))
+    }
+
+    /// Search symbols whose name contains `needle`, ignoring case.
+    ///
+    /// Names are scanned directly, so `proc` finds `process_file` regardless
+    /// of how Tantivy tokenizes identifiers. Filters apply as in
+    /// [`search`](Self::search); every match scores 1.0.
+    pub fn search_substring(
+        &self,
+        needle: &str,
+        limit: usize,
+        kind_filter: Option<crate::types::SymbolKind>,
+        module_filter: Option<&str>,
+        language_filter: Option<&str>,
+        filters: &crate::storage::SearchFilters,
+    ) -> IndexResult<Vec<SearchResult>> {
+        if needle.is_empty() {
+            return Err(IndexError::InvalidQuery {
+                query: needle.to_string(),
+                reason: "substring must not be empty".to_string(),
+            });
+        }
+
+        let regex = regex::RegexBuilder::new(&regex::escape(needle))
+            .case_insensitive(true)
+            .build()
+            .map_err(|e| IndexError::InvalidQuery {
+                query: needle.to_string(),
+                reason: e.to_string(),
+            })?;
+
+        // A literal pattern matches in linear time, so no deadline is needed
+        self.document_index
+            .search_by_name_regex(
+                &regex,
+                limit,
+                kind_filter,
+                module_filter,
+                language_filter,
+                filters,
+                None,
+            )
+            .map_err(|e| IndexError::General(format!("Substring search failed: {e}")))
+    }
+
+    /// Search symbols whose whole name equals `name`, with no fuzzy matching.
+    ///
+    /// Case is ignored unless `case_sensitive` is set. Filters apply as in
+    /// [`search`](Self::search); every match scores 1.0.
+    #[allow(clippy::too_many_arguments)]
+    pub fn search_exact(
+        &self,
+        name: &str,
+        case_sensitive: bool,
+        limit: usize,
+        kind_filter: Option<crate::types::SymbolKind>,
+        module_filter: Option<&str>,
+        language_filter: Option<&str>,
+        filters: &crate::storage::SearchFilters,
+    ) -> IndexResult<Vec<SearchResult>> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(IndexError::InvalidQuery {
+                query: name.to_string(),
+                reason: "exact name must not be empty".to_string(),
+            });
+        }
+
+        self.document_index
+            .search_by_exact_name(
+                name,
+                case_sensitive,
+                limit,
+                kind_filter,
+                module_filter,
+                language_filter,
+                filters,
+            )
+            .map_err(|e| IndexError::General(format!("Exact search failed: {e}")))
+    }
+
+    /// Semantic search results shaped like full-text results, for use when
+    /// a full-text search found nothing.
+    ///
+    /// Results are tagged [`SearchSource::Semantic`](crate::storage::SearchSource)
+    /// and scored by similarity (0-1). Kind, module, visibility and attribute
+    /// filters are applied to the semantic hits, so more candidates are
+    /// fetched when they are set.
+    pub fn search_semantic_fallback(
+        &self,
+        query: &str,
+        limit: usize,
+        kind_filter: Option<crate::types::SymbolKind>,
+        module_filter: Option<&str>,
+        language_filter: Option<&str>,
+        filters: &crate::storage::SearchFilters,
+    ) -> IndexResult<Vec<SearchResult>> {
+        let filtered = kind_filter.is_some()
+            || module_filter.is_some()
+            || filters.visibility.is_some()
+            || filters.attribute.is_some();
+        let fetch = if filtered {
+            limit.saturating_mul(4)
+        } else {
+            limit
+        };
+
+        let results = self
+            .semantic_search_docs_with_language(query, fetch, language_filter)?
+            .into_iter()
+            .filter(|(symbol, _)| kind_filter.is_none_or(|kind| symbol.kind == kind))
+            .filter(|(symbol, _)| {
+                module_filter.is_none_or(|module| symbol.module_path.as_deref() == Some(module))
+            })
+            .filter(|(symbol, _)| {
+                filters
+                    .visibility
+                    .is_none_or(|visibility| symbol.visibility == visibility)
+            })
+            .filter(|(symbol, _)| {
+                filters.attribute.is_none_or(|attribute| {
+                    symbol
+                        .attributes
+                        .iter()
+                        .any(|line| crate::symbol::attribute_matches(line, attribute))
+                })
+            })
+            .take(limit)
+            .map(|(symbol, score)| {
+                SearchResult::from_symbol(&symbol, score, crate::storage::SearchSource::Semantic)
+            })
+            .collect();
+        Ok(results)
+    }
+
+    /// Get total number of indexed documents
+    pub fn document_count(&self) -> IndexResult<u64> {
+        self.document_index
+            .document_count()
+            .map_err(|e| IndexError::General(format!("Failed to get document count: {e}")))
+    }
+
+    #[must_use = "The indexing result should be checked for errors"]
+    pub fn index_directory(
+        &mut self,
+        dir: impl AsRef<Path>,
+        progress: bool,
+        dry_run: bool,
+    ) -> IndexResult<IndexStats> {
+        self.index_directory_with_options(dir, progress, dry_run, false, None)
+    }
+
+    #[must_use = "The indexing result should be checked for errors"]
+    pub fn index_directory_with_force(
+        &mut self,
+        dir: impl AsRef<Path>,
+        progress: bool,
+        dry_run: bool,
+        force: bool,
+    ) -> IndexResult<IndexStats> {
+        self.index_directory_with_options(dir, progress, dry_run, force, None)
+    }
+
+    #[must_use = "The indexing result should be checked for errors"]
+    pub fn index_directory_with_options(
+        &mut self,
+        dir: impl AsRef<Path>,
+        progress: bool,
+        dry_run: bool,
+        force: bool,
+        max_files: Option<usize>,
+    ) -> IndexResult<IndexStats> {
+        let walker = FileWalker::new(self.settings.clone());
+        let files: Vec<_> = walker.walk(dir.as_ref()).collect();
+
+        // Apply max_files limit if specified
+        let files = if let Some(max) = max_files {
+            files.into_iter().take(max).collect()
+        } else {
+            files
+        };
+
+        // Skip oversized files based on metadata alone, before reading them
+        let mut stats = IndexStats::new();
+        let mut too_large = Vec::new();
+        let files: Vec<_> = files
+            .into_iter()
+            .filter(|file_path| match self.oversized_file_len(file_path) {
+                Some(len) => {
+                    too_large.push((file_path.clone(), len));
+                    false
                 }
-                Err(e) => {
-                    eprintln!("Warning: Failed to load symbol cache: {e}");
-                    self.symbol_cache = None;
-                    Ok(()) // Non-fatal, continue without cache
+                None => true,
+            })
+            .collect();
+        let size_limit = self.settings.indexing.max_file_size_bytes;
+        for (file_path, len) in &too_large {
+            stats.add_skipped(
+                file_path.clone(),
+                SkipReason::TooLarge,
+                format!("{len} bytes exceeds limit of {size_limit} bytes"),
+            );
+        }
+
+        // Skip generated code unless it was asked for
+        let mut generated = Vec::new();
+        let files: Vec<_> = if self.settings.indexing.include_generated {
+            files
+        } else {
+            files
+                .into_iter()
+                .filter(|file_path| match generated_reason(file_path) {
+                    Some(reason) => {
+                        generated.push((file_path.clone(), reason));
+                        false
+                    }
+                    None => true,
+                })
+                .collect()
+        };
+        for (file_path, reason) in &generated {
+            stats.add_skipped(file_path.clone(), SkipReason::Generated, reason.clone());
+        }
+
+        let total_files = files.len();
+
+        // Handle dry-run mode
+        if dry_run {
+            println!("Would index {total_files} files:");
+            for (i, file_path) in files.iter().enumerate() {
+                if i < 5 {
+                    println!("  {}", file_path.display());
+                } else if i == 5 && total_files > 5 {
+                    println!("  ... and {} more files", total_files - 5);
+                    break;
                 }
             }
-        } else {
-            debug_print!(self, "No symbol cache found at {}", cache_path.display());
-            Ok(())
+
+            if !too_large.is_empty() {
+                println!("Would skip {} files (too large):", too_large.len());
+                for (file_path, len) in too_large.iter().take(5) {
+                    println!("  {} ({len} bytes)", file_path.display());
+                }
+                if too_large.len() > 5 {
+                    println!("  ... and {} more files", too_large.len() - 5);
+                }
+            }
+
+            if !generated.is_empty() {
+                println!("Would skip {} files (generated):", generated.len());
+                for (file_path, reason) in generated.iter().take(5) {
+                    println!("  {} ({reason})", file_path.display());
+                }
+                if generated.len() > 5 {
+                    println!("  ... and {} more files", generated.len() - 5);
+                }
+            }
+
+            stats.files_indexed = total_files;
+            return Ok(stats);
         }
+
+        // Process files one at a time with batched commits
+        let progress_view = if progress && total_files > 0 {
+            // Only interactive runs get an ETA; it is noise in piped logs
+            let options = ProgressBarOptions::default()
+                .with_style(ProgressBarStyle::VerticalSolid)
+                .with_width(28)
+                .show_eta(is_terminal::is_terminal(std::io::stdout()));
+            let bar = Arc::new(
+                ProgressBar::with_options(
+                    total_files as u64,
+                    "files",
+                    "indexed",
+                    "failed",
+                    options,
+                )
+                .with_units("symbols"),
+            );
+            // Redraw in place only on an interactive, colored, non-quiet terminal
+            let ansi = is_terminal::is_terminal(std::io::stderr())
+                && !crate::display::Theme::should_disable_colors()
+                && !crate::config::is_global_quiet();
+            let status =
+                StatusLine::with_options(Arc::clone(&bar), StatusLineOptions::for_terminal(ansi));
+            Some((bar, status))
+        } else {
+            None
+        };
+
+        // Count symbol updates of this run only
+        let updates_before = std::mem::take(&mut self.symbol_updates);
+
+        // Start batch once before the loop
+        self.start_tantivy_batch()?;
+        const COMMIT_BATCH_SIZE: usize = 100; // Commit every 100 files to reduce I/O
+        let mut files_in_batch = 0;
+
+        for file_path in files {
+            let mut file_success = false;
+            let mut file_skipped = false;
+            let mut file_symbols = 0;
+
+            if let Some((bar, _)) = progress_view.as_ref() {
+                bar.set_message(file_path.display().to_string());
+            }
+
+            {
+                match self.index_file_internal(&file_path, force) {
+                    Ok(result) => {
+                        files_in_batch += 1;
+
+                        // Commit batch periodically to avoid excessive memory usage
+                        if files_in_batch >= COMMIT_BATCH_SIZE {
+                            self.commit_tantivy_batch()?;
+                            self.start_tantivy_batch()?;
+                            files_in_batch = 0;
+                        }
+
+                        let file_id = result.file_id();
+
+                        // Count all processed files (cached + newly indexed)
+                        stats.files_indexed += 1;
+                        file_success = true;
+
+                        // Count symbols from all files
+                        let new_symbols = self
+                            .document_index
+                            .find_symbols_by_file(file_id)
+                            .map(|symbols| symbols.len())
+                            .unwrap_or(0);
+                        stats.symbols_found += new_symbols;
+                        file_symbols = new_symbols;
+                    }
+                    Err(IndexError::BinaryContent { reason, .. }) => {
+                        stats.add_skipped(file_path.clone(), SkipReason::Binary, reason);
+                        file_skipped = true;
+                    }
+                    Err(IndexError::ParseTimeout { timeout_ms, .. }) => {
+                        stats.add_skipped(
+                            file_path.clone(),
+                            SkipReason::ParseTimeout,
+                            format!("no result after {timeout_ms} ms"),
+                        );
+                        file_skipped = true;
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to index {}: {}", file_path.display(), e);
+                        stats.files_failed += 1;
+                        // Rollback is automatic
+                    }
+                }
+            }
+
+            if let Some((bar, _)) = progress_view.as_ref() {
+                bar.inc();
+                bar.add_units(file_symbols as u64);
+                if file_success {
+                    bar.add_extra1(1);
+                } else if !file_skipped {
+                    bar.add_extra2(1);
+                }
+            }
+        }
+
+        if let Some((bar, status)) = progress_view {
+            drop(status);
+            bar.set_message("");
+            eprintln!("{bar}");
+        }
+
+        // Commit any remaining files in the batch
+        if files_in_batch > 0 {
+            self.commit_tantivy_batch()?;
+        }
+
+        // Resolve cross-file relationships after all files are indexed
+        if !dry_run {
+            self.resolve_cross_file_relationships()?;
+        }
+
+        // Stop timing and update final stats before returning
+        stats.stop_timing();
+        stats.symbols_found = self.symbol_count();
+        stats.symbol_updates = self.symbol_updates;
+        self.symbol_updates += updates_before;
+
+        Ok(stats)
+    }
+
+    /// Index the source files inside a `.tar`, `.tar.gz`/`.tgz` or `.zip` archive
+    ///
+    /// Entries are read into memory one at a time and never written to disk.
+    /// Each is stored as `<archive>!/<entry>`, so it cannot collide with a
+    /// workspace file and can be purged with the archive; the entry name
+    /// alone picks its language. Entries in unsupported languages are passed
+    /// over, and oversized, binary and generated entries are skipped as in
+    /// directory indexing.
+    #[must_use = "The indexing result should be checked for errors"]
+    pub fn index_archive(
+        &mut self,
+        archive: impl AsRef<Path>,
+        dry_run: bool,
+        force: bool,
+    ) -> IndexResult<IndexStats> {
+        use crate::indexing::generated::generated_content_reason;
+        use std::io::Read;
+
+        let archive = archive.as_ref();
+        let format =
+            ArchiveFormat::from_path(archive).ok_or_else(|| IndexError::UnsupportedFileType {
+                path: archive.to_path_buf(),
+                extension: archive
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .unwrap_or("")
+                    .to_string(),
+            })?;
+
+        // Normalize the archive path relative to workspace_root, as for files
+        let archive_path = match &self.settings.workspace_root {
+            Some(workspace_root) if archive.is_absolute() => {
+                archive.strip_prefix(workspace_root).unwrap_or(archive)
+            }
+            _ => archive,
+        };
+        let archive_str = archive_path
+            .to_str()
+            .ok_or_else(|| IndexError::FileRead {
+                path: archive.to_path_buf(),
+                source: std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "Invalid UTF-8 in path",
+                ),
+            })?
+            .to_string();
+
+        let mut stats = IndexStats::new();
+        let size_limit = self.settings.indexing.max_file_size_bytes;
+        let include_generated = self.settings.indexing.include_generated;
+
+        if dry_run {
+            let mut names = Vec::new();
+            archive::for_each_file(archive, format, |name, _, _| {
+                if self.detect_language(Path::new(name)).is_ok() {
+                    names.push(archive::entry_path(&archive_str, name));
+                }
+                Ok(())
+            })?;
+            println!(
+                "Would index {} files from {}:",
+                names.len(),
+                archive.display()
+            );
+            for name in names.iter().take(5) {
+                println!("  {name}");
+            }
+            if names.len() > 5 {
+                println!("  ... and {} more files", names.len() - 5);
+            }
+            stats.files_indexed = names.len();
+            return Ok(stats);
+        }
+
+        let updates_before = std::mem::take(&mut self.symbol_updates);
+
+        self.start_tantivy_batch()?;
+        const COMMIT_BATCH_SIZE: usize = 100;
+        let mut files_in_batch = 0;
+        let mut seen = std::collections::HashSet::new();
+
+        archive::for_each_file(archive, format, |name, size, reader| {
+            let entry = Path::new(name);
+            if self.detect_language(entry).is_err() {
+                return Ok(());
+            }
+            let stored = archive::entry_path(&archive_str, name);
+            seen.insert(stored.clone());
+            let path = Path::new(&stored);
+            if size_limit > 0 && size > size_limit {
+                stats.add_skipped(
+                    path.to_path_buf(),
+                    SkipReason::TooLarge,
+                    format!("{size} bytes exceeds limit of {size_limit} bytes"),
+                );
+                return Ok(());
+            }
+
+            // The declared size comes from the archive and is not trusted:
+            // read at most one byte past the limit, and only preallocate
+            // when a limit bounds the allocation
+            let (capacity, read_limit) = if size_limit > 0 {
+                (size.min(size_limit), size_limit + 1)
+            } else {
+                (0, u64::MAX)
+            };
+            let mut bytes = Vec::with_capacity(capacity as usize);
+            reader
+                .take(read_limit)
+                .read_to_end(&mut bytes)
+                .map_err(|e| IndexError::FileRead {
+                    path: path.to_path_buf(),
+                    source: e,
+                })?;
+            if size_limit > 0 && bytes.len() as u64 > size_limit {
+                stats.add_skipped(
+                    path.to_path_buf(),
+                    SkipReason::TooLarge,
+                    format!("content exceeds limit of {size_limit} bytes"),
+                );
+                return Ok(());
+            }
+            let (content, content_hash) = match decode_source(path, bytes) {
+                Ok(decoded) => decoded,
+                Err(IndexError::BinaryContent { reason, .. }) => {
+                    stats.add_skipped(path.to_path_buf(), SkipReason::Binary, reason);
+                    return Ok(());
+                }
+                Err(e) => return Err(e),
+            };
+            if !include_generated {
+                if let Some(reason) = generated_content_reason(entry, &content) {
+                    stats.add_skipped(path.to_path_buf(), SkipReason::Generated, reason);
+                    return Ok(());
+                }
+            }
+
+            match self.index_content_internal(entry, &stored, &content, content_hash, force) {
+                Ok(_) => {
+                    stats.files_indexed += 1;
+                    files_in_batch += 1;
+                    if files_in_batch >= COMMIT_BATCH_SIZE {
+                        self.commit_tantivy_batch()?;
+                        self.start_tantivy_batch()?;
+                        files_in_batch = 0;
+                    }
+                }
+                Err(IndexError::ParseTimeout { timeout_ms, .. }) => {
+                    stats.add_skipped(
+                        path.to_path_buf(),
+                        SkipReason::ParseTimeout,
+                        format!("no result after {timeout_ms} ms"),
+                    );
+                }
+                Err(e) => {
+                    eprintln!("Failed to index {stored}: {e}");
+                    stats.files_failed += 1;
+                }
+            }
+            Ok(())
+        })?;
+
+        if files_in_batch > 0 {
+            self.commit_tantivy_batch()?;
+        }
+
+        // Entries indexed from an earlier version of the archive that it no longer has
+        let prefix = archive::entry_path(&archive_str, "");
+        let gone: Vec<PathBuf> = self
+            .get_all_indexed_paths()
+            .into_iter()
+            .filter(|path| {
+                path.to_str()
+                    .is_some_and(|path| path.starts_with(&prefix) && !seen.contains(path))
+            })
+            .collect();
+        let mut removed_symbols = 0;
+        let mut removal = Ok(());
+        for path in &gone {
+            match self.remove_file_in_pass(path) {
+                Ok(count) => removed_symbols += count,
+                Err(e) => {
+                    removal = Err(e);
+                    break;
+                }
+            }
+        }
+        // Save what was removed even when a later removal failed
+        if removed_symbols > 0 {
+            self.save_semantic_after_removal();
+        }
+        removal?;
+        if !gone.is_empty() {
+            crate::log_info!(
+                "Removed {} entries no longer in {}",
+                gone.len(),
+                archive.display()
+            );
+        }
+
+        self.resolve_cross_file_relationships()?;
+
+        stats.stop_timing();
+        stats.symbols_found = self.symbol_count();
+        stats.symbol_updates = self.symbol_updates;
+        self.symbol_updates += updates_before;
+
+        Ok(stats)
+    }
+
+    // RESOLUTION SYSTEM: State reconstruction removed
+    // Resolution state is now maintained by language behaviors
+    // The new behavior system builds state incrementally during indexing
+    // No reconstruction needed!
+
+    /// Stores MethodCall objects for enhanced resolution during symbol resolution phase.
+    ///
+    /// Enables precise method resolution by preserving receiver and static call information.
+    fn store_method_call_for_resolution(
+        &mut self,
+        method_call: &crate::parsing::MethodCall,
+        file_id: FileId,
+    ) {
+        debug_print!(
+            self,
+            "Storing method call for enhanced resolution: {} calls {} (static: {}, receiver: {:?})",
+            method_call.caller,
+            method_call.method_name,
+            method_call.is_static,
+            method_call.receiver
+        );
+
+        // Index by (caller_name, method_name) for O(1) lookup
+        let key = (method_call.caller.clone(), method_call.method_name.clone());
+        self.method_calls_by_file
+            .entry(file_id)
+            .or_default()
+            .insert(key, method_call.clone());
+    }
+
+    /// Resolves method calls using enhanced MethodCall data with fallback to legacy resolution.
+    ///
+    /// Matches caller/method names to stored MethodCall objects for precise resolution.
+    /// Falls back to string-based resolution when no MethodCall data is available.
+    /// Returns the provenance alongside the target: bare-name fallbacks are heuristic.
+    fn resolve_method_call_enhanced(
+        &self,
+        call_target: &str,
+        caller: &Symbol,
+        caller_name: &str,
+        file_id: FileId,
+        context: &dyn ResolutionScope,
+    ) -> Option<(SymbolId, Provenance)> {
+        // Try to find corresponding MethodCall object for enhanced resolution
+        if let Some(method_calls) = self.method_calls_by_file.get(&file_id) {
+            // Normalize caller for language-specific matching (e.g., Python "<module>")
+            let caller_normalized = if let Ok(behavior) = self.get_behavior_for_file(file_id) {
+                behavior.normalize_caller_name(caller_name, file_id)
+            } else {
+                caller_name.to_string()
+            };
+
+            // O(1) hashmap lookup instead of O(m) linear search
+            let key = (caller_normalized, call_target.to_string());
+            if let Some(method_call) = method_calls.get(&key) {
+                debug_print!(
+                    self,
+                    "Found MethodCall object for {}->{}! Using enhanced resolution",
+                    caller_name,
+                    call_target
+                );
+                return self.resolve_method_call(method_call, caller, file_id, context);
+            }
+        }
+
+        // No MethodCall object found - treat as regular function call
+        debug_print!(
+            self,
+            "No MethodCall object found for {}->{}. Resolving as regular function",
+            caller_name,
+            call_target
+        );
+        context
+            .resolve(call_target)
+            .map(|id| (id, Provenance::Resolved))
+    }
+
+    /// Resolve a method call using MethodCall struct with rich receiver information
+    fn resolve_method_call(
+        &self,
+        method_call: &crate::parsing::MethodCall,
+        caller: &Symbol,
+        file_id: FileId,
+        context: &dyn ResolutionScope,
+    ) -> Option<(SymbolId, Provenance)> {
+        // If no receiver, treat as regular function call
+        let receiver = match &method_call.receiver {
+            Some(recv) => recv,
+            None => {
+                debug_print!(
+                    self,
+                    "No receiver found, resolving '{}' as regular function",
+                    method_call.method_name
+                );
+                let result = context.resolve(&method_call.method_name);
+                debug_print!(self, "Regular function resolution result: {:?}", result);
+                return result.map(|id| (id, Provenance::Resolved));
+            }
+        };
+
+        debug_print!(
+            self,
+            "Resolving method call: receiver={}, method={}, is_static={}",
+            receiver,
+            method_call.method_name,
+            method_call.is_static
+        );
+
+        // Handle static methods differently - they don't need receiver type lookup
+        if method_call.is_static {
+            debug_print!(
+                self,
+                "Static method call: {}::{}",
+                receiver,
+                method_call.method_name
+            );
+
+            // CRITICAL: Check if receiver is from an external import FIRST
+            // This must happen BEFORE we try to resolve, otherwise we might
+            // incorrectly resolve external symbols (e.g., indicatif::ProgressBar)
+            // to local symbols with the same name
+            if context.is_external_import(receiver) {
+                debug_print!(
+                    self,
+                    "Receiver '{}' is from external import, not resolving static method '{}'",
+                    receiver,
+                    method_call.method_name
+                );
+                return None; // External library - don't resolve
+            }
+
+            // For static calls, receiver is the type name, try Type::method format
+            let static_method = format!("{}::{}", receiver, method_call.method_name);
+
+            // Try qualified resolution
+            if let Some(id) = context.resolve(&static_method) {
+                debug_print!(
+                    self,
+                    "Resolved static method {} to symbol_id: {:?}",
+                    static_method,
+                    id
+                );
+                return Some((id, Provenance::Resolved));
+            }
+
+            // Fall back to method name only (receiver was already checked as non-external above)
+            let result = context.resolve(&method_call.method_name);
+            debug_print!(
+                self,
+                "Static method fallback resolution for {}: {:?}",
+                method_call.method_name,
+                result
+            );
+            return result.map(|id| (id, Provenance::Heuristic));
+        }
+
+        // Python `super().method()` and `super(Cls, self).method()`
+        if receiver.starts_with("super(") {
+            return self
+                .resolve_super_call(caller, &method_call.method_name)
+                .map(|id| (id, Provenance::Resolved));
+        }
+
+        // For instance methods, look up receiver's type
+        let type_name = self.receiver_type(receiver, Some(caller), context, &|name| {
+            self.variable_types
+                .get(&(file_id, name.to_string()))
+                .cloned()
+        })?;
+
+        debug_print!(self, "Found type for {}: {}", receiver, type_name);
+
+        // A method defined on the receiver type is an exact match, unless
+        // several types of that name could be meant
+        if let Some((method, provenance)) =
+            self.find_method_of_type(&type_name, &method_call.method_name, Some(caller), context)
+        {
+            return Some((method.id, provenance));
+        }
+
+        // Trait methods and methods of types declared elsewhere: the receiver
+        // type is not consulted, so the match is by method name only.
+        context
+            .resolve(&method_call.method_name)
+            .map(|id| (id, Provenance::Heuristic))
+    }
+
+    /// Type of a method call's receiver
+    ///
+    /// `self` and `this` are the calling method's own type and variables use
+    /// the type `variable_type` recorded for them. A chained receiver such as
+    /// `builder.name("x").size(3)` is typed link by link, from the declared
+    /// return type of each call on the type before it.
+    fn receiver_type(
+        &self,
+        receiver: &str,
+        caller: Option<&Symbol>,
+        context: &dyn ResolutionScope,
+        variable_type: &dyn Fn(&str) -> Option<String>,
+    ) -> Option<String> {
+        let receiver = receiver.trim();
+        if matches!(receiver, "self" | "this") {
+            return caller
+                .and_then(|caller| caller.parent)
+                .and_then(|parent| self.get_symbol(parent))
+                .map(|owner| owner.name.to_string());
+        }
+        if let Some(type_name) = variable_type(receiver) {
+            return Some(type_name);
+        }
+
+        let link = split_chained_call(receiver)?;
+        let owner = if link.is_static {
+            let type_path = link.receiver;
+            type_path
+                .rsplit("::")
+                .next()
+                .unwrap_or(type_path)
+                .to_string()
+        } else {
+            self.receiver_type(link.receiver, caller, context, variable_type)?
+        };
+        let (method, _) = self.find_method_of_type(&owner, link.method, caller, context)?;
+        match declared_return_type(method.signature.as_deref()?)? {
+            "Self" | "this" => Some(owner),
+            returned => Some(returned.to_string()),
+        }
+    }
+
+    /// A method named `method_name` whose parent is a type named `type_name`
+    fn find_method_of_type(
+        &self,
+        type_name: &str,
+        method_name: &str,
+        caller: Option<&Symbol>,
+        context: &dyn ResolutionScope,
+    ) -> Option<(Symbol, Provenance)> {
+        self.find_member_of_type(
+            type_name,
+            method_name,
+            &[SymbolKind::Method, SymbolKind::Function],
+            caller,
+            context,
+        )
+    }
+
+    /// A member of one of `kinds` named `member_name` whose parent is a type
+    /// named `type_name`
+    ///
+    /// When types in several places share the name, one in the caller's scope
+    /// short of crate-wide visibility (locals, imports, module) is taken, then
+    /// one declared in the caller's file, then in the caller's module. Without
+    /// such a preference the first match is returned as `Ambiguous`.
+    fn find_member_of_type(
+        &self,
+        type_name: &str,
+        member_name: &str,
+        kinds: &[SymbolKind],
+        caller: Option<&Symbol>,
+        context: &dyn ResolutionScope,
+    ) -> Option<(Symbol, Provenance)> {
+        let mut candidates: Vec<(Symbol, Symbol)> = self
+            .find_symbols_by_name(member_name, None)
+            .into_iter()
+            .filter(|s| kinds.contains(&s.kind))
+            .filter_map(|member| {
+                let owner = self.get_symbol(member.parent?)?;
+                (owner.name.as_ref() == type_name).then_some((member, owner))
+            })
+            .collect();
+        let first_owner = candidates.first()?.1.id;
+        if candidates.iter().all(|(_, owner)| owner.id == first_owner) {
+            return Some((candidates.swap_remove(0).0, Provenance::Resolved));
+        }
+
+        let in_scope: std::collections::HashSet<SymbolId> = context
+            .symbols_in_scope()
+            .into_iter()
+            .filter(|(_, _, level)| *level != crate::parsing::ScopeLevel::Global)
+            .map(|(_, id, _)| id)
+            .collect();
+        let preferred = candidates
+            .iter()
+            .position(|(_, owner)| in_scope.contains(&owner.id))
+            .or_else(|| {
+                let caller = caller?;
+                candidates
+                    .iter()
+                    .position(|(_, owner)| owner.file_id == caller.file_id)
+                    .or_else(|| {
+                        let module = caller.module_path.as_deref()?;
+                        candidates
+                            .iter()
+                            .position(|(_, owner)| owner.module_path.as_deref() == Some(module))
+                    })
+            });
+        match preferred {
+            Some(index) => Some((candidates.swap_remove(index).0, Provenance::Resolved)),
+            None => Some((candidates.swap_remove(0).0, Provenance::Ambiguous)),
+        }
+    }
+
+    /// Resolve `super().method()` called from a method of some class
+    ///
+    /// Bases are searched depth-first, left to right, each class once, for a
+    /// method with that name, following the stored Extends edges (see
+    /// [`Self::get_base_classes`]). This matches Python's method resolution
+    /// order for hierarchies without diamonds. Bases that are not indexed
+    /// (stdlib, third-party) end the search down that branch.
+    fn resolve_super_call(&self, caller: &Symbol, method_name: &str) -> Option<SymbolId> {
+        let class = self.get_symbol(caller.parent?)?;
+        // Python names methods `Class.method`
+        let defined_in = |class: &Symbol| -> Option<SymbolId> {
+            [
+                format!("{}.{method_name}", class.name),
+                method_name.to_string(),
+            ]
+            .iter()
+            .flat_map(|name| {
+                self.document_index
+                    .find_symbols_by_name(name, None)
+                    .unwrap_or_default()
+            })
+            .find(|symbol| symbol.kind == SymbolKind::Method && symbol.parent == Some(class.id))
+            .map(|symbol| symbol.id)
+        };
+
+        let mut visited = std::collections::HashSet::from([class.id]);
+        // Indexed bases of a class not seen yet, rightmost first so the stack
+        // pops the leftmost
+        let mut unvisited_bases = |class: &Symbol| -> Vec<Symbol> {
+            let mut bases: Vec<Symbol> = self
+                .get_base_classes(class.id)
+                .into_iter()
+                .filter(|base| base.kind == SymbolKind::Class && visited.insert(base.id))
+                .collect();
+            bases.reverse();
+            bases
+        };
+
+        let mut pending = unvisited_bases(&class);
+        while let Some(base) = pending.pop() {
+            if let Some(method) = defined_in(&base) {
+                return Some(method);
+            }
+            pending.extend(unvisited_bases(&base));
+        }
+        None
+    }
+
+    /// Build resolution context for a file with all available symbols
+    fn build_resolution_context(&self, file_id: FileId) -> IndexResult<Box<dyn ResolutionScope>> {
+        // Use behavior's build_resolution_context which handles imports with our new matching logic
+        let behavior = self.get_behavior_for_file(file_id)?;
+
+        // NEW: Check if we can use cache-based resolution
+        if let Some(cache) = self.symbol_cache() {
+            // Build context with cache (fast path)
+            behavior.build_resolution_context_with_cache(file_id, cache, &self.document_index)
+        } else {
+            // Fall back to existing path (compatibility)
+            behavior.build_resolution_context(file_id, &self.document_index)
+        }
+    }
+
+    /// Resolve cross-file relationships using imports
+    fn resolve_cross_file_relationships(&mut self) -> IndexResult<()> {
+        if !self.settings.indexing.resolve_relationships {
+            self.unresolved_relationships.clear();
+            return Ok(());
+        }
+
+        // Process all unresolved relationships
+        let unresolved = std::mem::take(&mut self.unresolved_relationships);
+
+        eprintln!(
+            "Resolving cross-file relationships: {} unresolved entries",
+            unresolved.len()
+        );
+        debug_print!(
+            self,
+            "resolve_cross_file_relationships: {} unresolved relationships",
+            unresolved.len()
+        );
+
+        if unresolved.is_empty() {
+            eprintln!("DEBUG: No unresolved relationships to process");
+            return Ok(());
+        }
+
+        // Start a batch for relationship updates
+        self.start_tantivy_batch()?;
+
+        let mut resolved_count = 0;
+        let mut skipped_count = 0;
+        let total_unresolved = unresolved.len();
+
+        let progress = if total_unresolved > 0 {
+            let options = ProgressBarOptions::default()
+                .with_style(ProgressBarStyle::VerticalSolid)
+                .with_width(28);
+            let bar = Arc::new(ProgressBar::with_options(
+                total_unresolved as u64,
+                "relationships",
+                "resolved",
+                "skipped",
+                options,
+            ));
+            let status = StatusLine::new(Arc::clone(&bar));
+            Some((bar, status))
+        } else {
+            None
+        };
+
+        // Group relationships by file for efficient context building, with
+        // inheritance first: `super()` calls resolve through the stored
+        // Extends edges, so those are committed before any call is resolved
+        let mut relationships_by_file: std::collections::HashMap<
+            (bool, FileId),
+            Vec<UnresolvedRelationship>,
+        > = std::collections::HashMap::new();
+        for rel in unresolved {
+            relationships_by_file
+                .entry((rel.kind != RelationKind::Extends, rel.file_id))
+                .or_default()
+                .push(rel);
+        }
+        let mut relationships_by_file: Vec<_> = relationships_by_file.into_iter().collect();
+        relationships_by_file.sort_by_key(|((after_inheritance, _), _)| *after_inheritance);
+        let mut inheritance_committed = false;
+
+        // Symbol lookup cache to avoid millions of duplicate Tantivy queries
+        // Maps symbol_name -> Vec<Symbol>
+        let mut symbol_lookup_cache: std::collections::HashMap<String, Vec<Symbol>> =
+            std::collections::HashMap::new();
+
+        // Process each file's relationships with its resolution context
+        for ((after_inheritance, file_id), file_relationships) in relationships_by_file {
+            if after_inheritance && !inheritance_committed {
+                inheritance_committed = true;
+                self.document_index
+                    .commit_batch()
+                    .map_err(|e| IndexError::TantivyError {
+                        operation: "commit_batch".to_string(),
+                        cause: e.to_string(),
+                    })?;
+                self.start_tantivy_batch()?;
+            }
+
+            // Build resolution context for this file
+            let context = self.build_resolution_context(file_id)?;
+
+            for rel in file_relationships {
+                if let Some((bar, _)) = &progress {
+                    bar.inc();
+                }
+
+                debug_print!(
+                    self,
+                    "Processing relationship: {} -> {} (kind: {:?}, file: {:?})",
+                    rel.from_name,
+                    rel.to_name,
+                    rel.kind,
+                    rel.file_id
+                );
+
+                // Find 'from' symbols - use from_id when available to skip lookup
+                let from_symbols: Vec<_> = if let Some(from_id) = rel.from_id {
+                    // We already have the from_id, just fetch the symbol
+                    debug_print!(
+                        self,
+                        "Using cached from_id: {:?} for '{}'",
+                        from_id,
+                        rel.from_name
+                    );
+                    match self.document_index.find_symbol_by_id(from_id) {
+                        Ok(Some(symbol)) => vec![symbol],
+                        Ok(None) => {
+                            debug_print!(
+                                self,
+                                "WARNING: from_id {:?} not found for '{}'",
+                                from_id,
+                                rel.from_name
+                            );
+                            Vec::new()
+                        }
+                        Err(e) => {
+                            debug_print!(
+                                self,
+                                "ERROR: Failed to fetch symbol {:?}: {}",
+                                from_id,
+                                e
+                            );
+                            Vec::new()
+                        }
+                    }
+                } else {
+                    // Fallback to name-based lookup
+                    debug_print!(
+                        self,
+                        "No from_id available, falling back to name lookup for '{}'",
+                        rel.from_name
+                    );
+                    let behavior_for_file = self.get_behavior_for_file(file_id)?;
+                    let from_query_name =
+                        behavior_for_file.normalize_caller_name(&rel.from_name, file_id);
+
+                    // Check cache first to avoid duplicate Tantivy queries
+                    let all_from_symbols =
+                        if let Some(cached) = symbol_lookup_cache.get(&from_query_name) {
+                            cached.clone()
+                        } else {
+                            // Cache miss - query Tantivy and cache the result
+                            let symbols = self
+                                .document_index
+                                .find_symbols_by_name(&from_query_name, None)
+                                .map_err(|e| IndexError::TantivyError {
+                                    operation: "find_symbols_by_name".to_string(),
+                                    cause: e.to_string(),
+                                })?;
+                            symbol_lookup_cache.insert(from_query_name.clone(), symbols.clone());
+                            symbols
+                        };
+
+                    debug_print!(
+                        self,
+                        "Looking for '{}' symbols, found {} total",
+                        from_query_name,
+                        all_from_symbols.len()
+                    );
+
+                    // Filter to only symbols from the current file
+                    let from_symbols: Vec<_> = all_from_symbols
+                        .into_iter()
+                        .filter(|s| s.file_id == file_id)
+                        .collect();
+
+                    debug_print!(
+                        self,
+                        "Found {} from_symbols in current file",
+                        from_symbols.len()
+                    );
+
+                    if from_symbols.is_empty() && rel.kind == RelationKind::Calls {
+                        debug_print!(
+                            self,
+                            "WARNING: No '{}' symbol found in file {:?} for Calls relationship to '{}'",
+                            from_query_name,
+                            file_id,
+                            rel.to_name
+                        );
+                    }
+
+                    from_symbols
+                };
+
+                // Use the clean resolution API that delegates to language-specific logic
+                let to_symbol_id = if rel.kind == RelationKind::Calls && from_symbols.len() == 1 {
+                    // Special handling for method calls with enhanced resolution
+                    debug_print!(self, "Resolving as method call: '{}'", rel.to_name);
+                    let res = self.resolve_method_call_enhanced(
+                        &rel.to_name,
+                        &from_symbols[0],
+                        &rel.from_name,
+                        file_id,
+                        context.as_ref(),
+                    );
+                    debug_print!(
+                        self,
+                        "resolve_method_call_enhanced returned: {:?} for {}",
+                        res,
+                        rel.to_name
+                    );
+                    if res.is_none() {
+                        debug_print!(
+                            self,
+                            "Resolution failed, trying external mapping for {}",
+                            rel.to_name
+                        );
+                        // Try external mapping as a fallback
+                        if let Some(behavior) = self.file_behaviors.get(&file_id) {
+                            // Build a better external key using MethodCall receiver if available
+                            let to_key = if let Some(method_calls) =
+                                self.method_calls_by_file.get(&file_id)
+                            {
+                                // O(1) hashmap lookup by caller and method name
+                                let caller_name =
+                                    from_symbols.first().map(|s| s.name.as_ref()).unwrap_or("");
+                                let key = (caller_name.to_string(), rel.to_name.to_string());
+                                if let Some(mc) = method_calls.get(&key) {
+                                    if let Some(recv) = &mc.receiver {
+                                        format!("{recv}.{}", mc.method_name)
+                                    } else {
+                                        rel.to_name.to_string()
+                                    }
+                                } else {
+                                    rel.to_name.to_string()
+                                }
+                            } else {
+                                rel.to_name.to_string()
+                            };
+
+                            debug_print!(
+                                self,
+                                "Trying to resolve external call target: '{}' for file {:?}",
+                                to_key,
+                                file_id
+                            );
+                            if let Some((module_path, symbol_name)) =
+                                behavior.resolve_external_call_target(&to_key, file_id)
+                            {
+                                // Skip external symbol creation for resolved external calls
+                                debug_print!(
+                                    self,
+                                    "Skipping external symbol for resolved call: {} -> {}::{}",
+                                    to_key,
+                                    module_path,
+                                    symbol_name
+                                );
+                                None
+                            } else {
+                                None
+                            }
+                        } else {
+                            None
+                        }
+                    } else {
+                        res
+                    }
+                } else {
+                    // Delegate all relationship resolution to the language-specific context
+                    // This includes Defines, Implements, Extends, and other relationships
+                    debug_print!(
+                        self,
+                        "Resolving relationship: {} -> {} (kind: {:?})",
+                        rel.from_name,
+                        rel.to_name,
+                        rel.kind
+                    );
+                    // `Enum::Variant` names a variant of that enum. The bare
+                    // name fallback of the context would take a same-named
+                    // variant of any enum in scope, so such a pick is dropped.
+                    let variant_path = rel.kind == RelationKind::Uses && rel.to_name.contains("::");
+                    let variant = variant_path
+                        .then(|| {
+                            let (enum_name, variant_name) = rel.to_name.rsplit_once("::")?;
+                            self.find_member_of_type(
+                                enum_name,
+                                variant_name,
+                                &[SymbolKind::EnumVariant],
+                                from_symbols.first(),
+                                context.as_ref(),
+                            )
+                        })
+                        .flatten();
+                    let result = match variant {
+                        Some((variant, provenance)) => Some((variant.id, provenance)),
+                        None => context
+                            .resolve_relationship(&rel.from_name, &rel.to_name, rel.kind, file_id)
+                            .filter(|id| {
+                                !variant_path
+                                    || self
+                                        .get_symbol(*id)
+                                        .is_none_or(|s| s.kind != SymbolKind::EnumVariant)
+                            })
+                            .map(|id| (id, Provenance::Resolved)),
+                    };
+                    debug_print!(self, "Resolution result: {:?}", result);
+                    // If unresolved call, try language behavior external mapping
+                    if result.is_none() && rel.kind == RelationKind::Calls {
+                        if let Some(behavior) = self.file_behaviors.get(&file_id) {
+                            if let Some((module_path, symbol_name)) =
+                                behavior.resolve_external_call_target(&rel.to_name, file_id)
+                            {
+                                // Skip external symbol creation for mapped external calls
+                                debug_print!(
+                                    self,
+                                    "Skipping external symbol for mapped call: {} -> {}::{}",
+                                    rel.to_name,
+                                    module_path,
+                                    symbol_name
+                                );
+                                None
+                            } else {
+                                None
+                            }
+                        } else {
+                            None
+                        }
+                    } else {
+                        result
+                    }
+                };
+
+                let (to_symbol_id, provenance) = match to_symbol_id {
+                    Some((id, provenance)) => {
+                        debug_print!(
+                            self,
+                            "Resolved target symbol '{}' to ID: {:?} ({})",
+                            rel.to_name,
+                            id,
+                            provenance
+                        );
+                        (id, provenance)
+                    }
+                    None => {
+                        debug_print!(
+                            self,
+                            "[SKIP-RESOLUTION] Failed to resolve '{}' from '{}' in file {:?} (kind: {:?})",
+                            rel.to_name,
+                            rel.from_name,
+                            rel.file_id,
+                            rel.kind
+                        );
+                        // Symbol not in scope - skip this relationship
+                        skipped_count += 1;
+                        if let Some((bar, _)) = &progress {
+                            bar.add_extra2(1);
+                        }
+                        continue;
+                    }
+                };
+
+                // Get the full symbol data
+                debug_print!(self, "Looking up symbol by ID: {:?}", to_symbol_id);
+                let to_symbol = match self
+                    .document_index
+                    .find_symbol_by_id(to_symbol_id)
+                    .map_err(|e| IndexError::TantivyError {
+                        operation: "find_symbol_by_id".to_string(),
+                        cause: e.to_string(),
+                    })? {
+                    Some(symbol) => {
+                        debug_print!(self, "Found target symbol: {}", symbol.name);
+                        symbol
+                    }
+                    None => {
+                        debug_print!(
+                            self,
+                            "[SKIP-NOT-FOUND] Symbol ID {:?} not found in index for '{}'",
+                            to_symbol_id,
+                            rel.to_name
+                        );
+                        skipped_count += 1;
+                        if let Some((bar, _)) = &progress {
+                            bar.add_extra2(1);
+                        }
+                        continue;
+                    }
+                };
+
+                // An unqualified name resolved outside the caller's file is a
+                // pick among candidates when others share its name and kind
+                let provenance = if provenance == Provenance::Resolved
+                    && to_symbol.file_id != file_id
+                    && !rel.to_name.contains(['.', ':'])
+                {
+                    let to_name = to_symbol.name.to_string();
+                    let candidates = if let Some(cached) = symbol_lookup_cache.get(&to_name) {
+                        cached.clone()
+                    } else {
+                        let symbols = self
+                            .document_index
+                            .find_symbols_by_name(&to_name, None)
+                            .map_err(|e| IndexError::TantivyError {
+                                operation: "find_symbols_by_name".to_string(),
+                                cause: e.to_string(),
+                            })?;
+                        symbol_lookup_cache.insert(to_name, symbols.clone());
+                        symbols
+                    };
+                    let same_kind = candidates
+                        .iter()
+                        .filter(|s| s.kind == to_symbol.kind)
+                        .count();
+                    if same_kind > 1 {
+                        Provenance::Ambiguous
+                    } else {
+                        Provenance::Resolved
+                    }
+                } else {
+                    provenance
+                };
+                // A caller name matching several symbols fans the edge out
+                let provenance = if from_symbols.len() > 1 {
+                    provenance.max(Provenance::Ambiguous)
+                } else {
+                    provenance
+                };
+
+                // Process with our filtering logic
+                debug_print!(self, "Processing {} from symbols", from_symbols.len());
+                for from_symbol in &from_symbols {
+                    debug_print!(
+                        self,
+                        "Checking relationship from {} to {}",
+                        from_symbol.name,
+                        to_symbol.name
+                    );
+
+                    // Check symbol kind compatibility
+                    if !Self::is_compatible_relationship(from_symbol.kind, to_symbol.kind, rel.kind)
+                    {
+                        debug_print!(
+                            self,
+                            "[SKIP-INCOMPATIBLE] {} ({:?}) -> {} ({:?}) for {:?}",
+                            from_symbol.name,
+                            from_symbol.kind,
+                            to_symbol.name,
+                            to_symbol.kind,
+                            rel.kind
+                        );
+                        skipped_count += 1;
+                        if let Some((bar, _)) = &progress {
+                            bar.add_extra2(1);
+                        }
+                        continue;
+                    }
+
+                    // Check visibility (skip for Defines - a type can always see its own methods)
+                    if rel.kind != RelationKind::Defines {
+                        debug_print!(
+                            self,
+                            "Checking visibility: {} (vis: {:?}, module: {:?}) from {} (module: {:?})",
+                            to_symbol.name,
+                            to_symbol.visibility,
+                            to_symbol.module_path,
+                            from_symbol.name,
+                            from_symbol.module_path
+                        );
+                        if !Self::is_symbol_visible_from(&to_symbol, from_symbol) {
+                            debug_print!(
+                                self,
+                                "[SKIP-VISIBILITY] {} not visible from {} (to_vis: {:?}, to_module: {:?}, from_module: {:?})",
+                                to_symbol.name,
+                                from_symbol.name,
+                                to_symbol.visibility,
+                                to_symbol.module_path,
+                                from_symbol.module_path
+                            );
+                            skipped_count += 1;
+                            if let Some((bar, _)) = &progress {
+                                bar.add_extra2(1);
+                            }
+                            continue;
+                        }
+                    }
+
+                    // Add the relationship with preserved metadata
+                    debug_print!(
+                        self,
+                        "[SUCCESS] Adding relationship: {} ({:?}) -> {} ({:?}) kind: {:?}",
+                        from_symbol.name,
+                        from_symbol.id,
+                        to_symbol.name,
+                        to_symbol.id,
+                        rel.kind
+                    );
+                    let mut relationship = Relationship::new(rel.kind).with_provenance(provenance);
+                    if let Some(ref metadata) = rel.metadata {
+                        relationship = relationship.with_metadata(metadata.clone());
+                    }
+                    self.add_relationship_internal(from_symbol.id, to_symbol.id, relationship)?;
+                    resolved_count += 1;
+                    if let Some((bar, _)) = &progress {
+                        bar.add_extra1(1);
+                    }
+                }
+            }
+        }
+
+        // Commit the batch with all the relationships
+        self.commit_tantivy_batch()?;
+
+        if let Some((bar, status)) = progress {
+            drop(status);
+            eprintln!("{bar}");
+        }
+
+        debug_print!(
+            self,
+            "Relationship resolution complete - resolved: {}, skipped: {}, total: {}",
+            resolved_count,
+            skipped_count,
+            total_unresolved
+        );
+
+        Ok(())
+    }
+
+    // Note: external symbol creation moved to language behavior implementations
+
+    /// Process pending embeddings after a successful Tantivy commit
+    fn process_pending_embeddings(
+        &mut self,
+        vector_engine: &Arc<Mutex<VectorSearchEngine>>,
+        embedding_generator: &Arc<dyn EmbeddingGenerator>,
+    ) -> IndexResult<()> {
+        if self.pending_embeddings.is_empty() {
+            return Ok(());
+        }
+
+        // Extract texts for embedding generation
+        let texts: Vec<&str> = self
+            .pending_embeddings
+            .iter()
+            .map(|(_, text)| text.as_str())
+            .collect();
+
+        // Generate embeddings
+        let embeddings = embedding_generator
+            .generate_embeddings(&texts)
+            .map_err(|e| IndexError::General(format!("Vector embedding generation failed: {e}")))?;
+
+        // Validate embedding count matches input
+        if embeddings.len() != texts.len() {
+            return Err(IndexError::General(format!(
+                "Embedding count mismatch: expected {}, got {}",
+                texts.len(),
+                embeddings.len()
+            )));
+        }
+
+        // Create vector IDs and embeddings pairs
+        let mut vectors = Vec::with_capacity(self.pending_embeddings.len());
+        for (i, (symbol_id, _)) in self.pending_embeddings.iter().enumerate() {
+            // Convert SymbolId to VectorId (both wrap u32)
+            if let Some(vector_id) = crate::vector::VectorId::new(symbol_id.value()) {
+                vectors.push((vector_id, embeddings[i].clone()));
+            }
+        }
+
+        // Index vectors
+        vector_engine
+            .lock()
+            .map_err(|_| IndexError::General("Vector engine mutex poisoned".to_string()))?
+            .index_vectors(&vectors)
+            .map_err(|e| IndexError::General(format!("Vector indexing failed: {e}")))?;
+
+        // Clear pending embeddings
+        self.pending_embeddings.clear();
+
+        Ok(())
+    }
+
+    /// Build or rebuild the symbol cache from current index
+    pub fn build_symbol_cache(&mut self) -> IndexResult<()> {
+        let cache_path = self.get_cache_path();
+
+        // Clear any existing cache first to release memory-mapped views (Windows fix)
+        self.clear_symbol_cache(false)?;
+
+        // Get all symbols from the index (use the existing public method)
+        let all_symbols = self.get_all_symbols();
+        debug_print!(
+            self,
+            "Building symbol cache with {} symbols at {}",
+            all_symbols.len(),
+            cache_path.display()
+        );
+
+        // Build the cache file
+        crate::storage::symbol_cache::SymbolHashCache::build_from_symbols(
+            &cache_path,
+            all_symbols.iter(),
+        )
+        .map_err(|e| {
+            // Check for Windows file locking error
+            let error_msg = if cfg!(windows) && e.to_string().contains("os error 1224") {
+                format!(
+                    "Failed to build symbol cache: {e}\n\n\
+                    Windows file locking detected. The cache file is currently in use.\n\
+                    To fix this issue:\n\
+                    1. Stop all running codanna processes\n\
+                    2. Delete the cache file: {}\n\
+                    3. Re-run the index command with --force flag\n\
+                    \n\
+                    Example: codanna index src --force",
+                    cache_path.display()
+                )
+            } else {
+                format!("Failed to build symbol cache: {e}")
+            };
+            IndexError::General(error_msg)
+        })?;
+
+        // Load the cache for immediate use
+        self.load_symbol_cache()?;
+
+        debug_print!(
+            self,
+            "Built symbol cache with {} symbols",
+            all_symbols.len()
+        );
+        Ok(())
+    }
+
+    /// Clear symbol cache (drop memory-mapped view and optionally delete file)
+    pub fn clear_symbol_cache(&mut self, delete_file: bool) -> IndexResult<()> {
+        // Drop the existing cache to release any memory-mapped views
+        self.symbol_cache = None;
+
+        if delete_file {
+            let cache_path = self.get_cache_path();
+            if cache_path.exists() {
+                std::fs::remove_file(&cache_path).map_err(|e| {
+                    IndexError::General(format!("Failed to delete symbol cache file: {e}"))
+                })?;
+                debug_print!(self, "Deleted symbol cache file: {}", cache_path.display());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Load symbol cache if it exists
+    pub fn load_symbol_cache(&mut self) -> IndexResult<()> {
+        let cache_path = self.get_cache_path();
+
+        if cache_path.exists() {
+            match crate::storage::symbol_cache::SymbolHashCache::open(&cache_path) {
+                Ok(cache) => {
+                    self.symbol_cache = Some(Arc::new(
+                        crate::storage::symbol_cache::ConcurrentSymbolCache::new(cache),
+                    ));
+                    debug_print!(self, "Loaded symbol cache from {}", cache_path.display());
+                    Ok(())
+                }
+                Err(e) => {
+                    eprintln!("Warning: Failed to load symbol cache: {e}");
+                    self.symbol_cache = None;
+                    Ok(()) // Non-fatal, continue without cache
+                }
+            }
+        } else {
+            debug_print!(self, "No symbol cache found at {}", cache_path.display());
+            Ok(())
+        }
+    }
+
+    /// Get the path for the symbol cache file
+    fn get_cache_path(&self) -> PathBuf {
+        let index_base = if let Some(ref workspace_root) = self.settings.workspace_root {
+            workspace_root.join(&self.settings.index_path)
+        } else {
+            self.settings.index_path.clone()
+        };
+
+        index_base.join("symbol_cache.bin")
+    }
+
+    /// Load the occurrence index when `indexing.index_occurrences` is enabled
+    fn load_occurrences(&mut self) {
+        if !self.settings.indexing.index_occurrences {
+            return;
+        }
+
+        let path = self.get_occurrences_path();
+        let occurrences = if path.exists() {
+            crate::storage::OccurrenceIndex::load(&path).unwrap_or_else(|e| {
+                eprintln!("Warning: Failed to load occurrence index: {e}");
+                crate::storage::OccurrenceIndex::new()
+            })
+        } else {
+            crate::storage::OccurrenceIndex::new()
+        };
+        debug_print!(
+            self,
+            "Loaded {} identifier occurrences from {}",
+            occurrences.len(),
+            path.display()
+        );
+        self.occurrences = Some(occurrences);
+    }
+
+    /// Write the occurrence index to disk if it changed
+    fn save_occurrences(&mut self) -> IndexResult<()> {
+        let path = self.get_occurrences_path();
+        if let Some(occurrences) = &mut self.occurrences {
+            if occurrences.is_dirty() {
+                occurrences.save(&path).map_err(|e| {
+                    IndexError::General(format!("Failed to save occurrence index: {e}"))
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Drop all recorded occurrences and delete the occurrence index file
+    pub fn clear_occurrences(&mut self) -> IndexResult<()> {
+        if self.occurrences.is_some() {
+            self.occurrences = Some(crate::storage::OccurrenceIndex::new());
+        }
+
+        let path = self.get_occurrences_path();
+        if path.exists() {
+            std::fs::remove_file(&path).map_err(|e| {
+                IndexError::General(format!("Failed to delete occurrence index file: {e}"))
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Whether identifier occurrences are being recorded
+    pub fn has_occurrences(&self) -> bool {
+        self.occurrences.is_some()
+    }
+
+    /// Find every occurrence of the identifier `name` in indexed files.
+    ///
+    /// Unlike symbol lookups this is purely textual: it also finds uses the
+    /// relationship graph misses, such as names in strings or reflective calls.
+    pub fn find_occurrences(
+        &self,
+        name: &str,
+        limit: usize,
+    ) -> IndexResult<Vec<OccurrenceLocation>> {
+        let occurrences = self.occurrences.as_ref().ok_or_else(|| {
+            IndexError::General(
+                "Occurrence index is disabled. Set indexing.index_occurrences = true and re-index with --force".to_string(),
+            )
+        })?;
+
+        let mut paths: std::collections::HashMap<FileId, Option<String>> =
+            std::collections::HashMap::new();
+        let mut locations = Vec::new();
+        for occ in occurrences.find(name) {
+            if locations.len() >= limit {
+                break;
+            }
+            let path = paths
+                .entry(occ.file_id)
+                .or_insert_with(|| self.get_file_path(occ.file_id));
+            if let Some(file_path) = path {
+                locations.push(OccurrenceLocation {
+                    file_path: file_path.clone(),
+                    line: occ.line + 1,
+                    column: occ.column as u32 + 1,
+                });
+            }
+        }
+        Ok(locations)
+    }
+
+    /// Get the path for the occurrence index file
+    fn get_occurrences_path(&self) -> PathBuf {
+        let index_base = if let Some(ref workspace_root) = self.settings.workspace_root {
+            workspace_root.join(&self.settings.index_path)
+        } else {
+            self.settings.index_path.clone()
+        };
+
+        index_base.join("occurrences.bin")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    use crate::storage::SearchFilters;
+    use crate::types::SymbolCounter;
+    use crate::{FileId, RelationKind, Symbol, SymbolKind, Visibility};
+
+    #[test]
+    fn test_index_directory_skips_oversized_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let src = temp_dir.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("small.rs"), "fn small() {}\n").unwrap();
+        fs::write(src.join("bundle.rs"), "fn big() {}\n".repeat(100)).unwrap();
+
+        let mut settings = Settings {
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        };
+        settings.indexing.max_file_size_bytes = 256;
+        let mut indexer = SimpleIndexer::with_settings(Arc::new(settings));
+
+        let dry_run = indexer.index_directory(&src, false, true).unwrap();
+        assert_eq!(dry_run.files_indexed, 1);
+        assert_eq!(dry_run.files_too_large, 1);
+
+        let stats = indexer.index_directory(&src, false, false).unwrap();
+        assert_eq!(stats.files_indexed, 1);
+        assert_eq!(stats.files_too_large, 1);
+        assert_eq!(stats.files_failed, 0);
+        assert!(stats.skipped[0].0.ends_with("bundle.rs"));
+        assert!(indexer.find_symbols_by_name("big", None).is_empty());
+    }
+
+    #[test]
+    fn test_index_directory_skips_files_past_parse_timeout() {
+        let temp_dir = TempDir::new().unwrap();
+        let src = temp_dir.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("small.rs"), "fn small() {}\n").unwrap();
+        let huge: String = (0..100_000)
+            .map(|i| format!("fn f{i}() -> u32 {{ {i} }}\n"))
+            .collect();
+        fs::write(src.join("huge.rs"), huge).unwrap();
+
+        let mut settings = Settings {
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        };
+        settings.indexing.max_file_size_bytes = 0;
+        settings.indexing.parse_timeout_ms = 20;
+        let mut indexer = SimpleIndexer::with_settings(Arc::new(settings));
+
+        let stats = indexer.index_directory(&src, false, false).unwrap();
+        assert_eq!(stats.files_indexed, 1);
+        assert_eq!(stats.files_parse_timeout, 1);
+        assert_eq!(stats.files_failed, 0);
+        assert_eq!(stats.skipped[0].1, SkipReason::ParseTimeout);
+        assert!(stats.skipped[0].0.ends_with("huge.rs"));
+        assert_eq!(indexer.find_symbols_by_name("small", None).len(), 1);
+        assert!(indexer.find_symbols_by_name("f0", None).is_empty());
+        // The skipped file is not registered, so the next run tries it again
+        assert!(
+            indexer
+                .get_all_indexed_paths()
+                .iter()
+                .all(|p| !p.ends_with("huge.rs"))
+        );
+    }
+
+    #[test]
+    fn test_index_directory_skips_generated_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let src = temp_dir.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("lib.rs"), "fn handwritten() {}\n").unwrap();
+        fs::write(src.join("schema_generated.rs"), "fn from_name() {}\n").unwrap();
+        fs::write(
+            src.join("bindings.rs"),
+            format!(
+                "// {}{} by build.rs\nfn from_header() {{}}\n",
+                "@", "generated"
+            ),
+        )
+        .unwrap();
+
+        let settings = Settings {
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        };
+        let mut indexer = SimpleIndexer::with_settings(Arc::new(settings));
+
+        let stats = indexer.index_directory(&src, false, false).unwrap();
+        assert_eq!(stats.files_indexed, 1);
+        assert_eq!(stats.files_generated, 2);
+        assert!(
+            stats
+                .skipped
+                .iter()
+                .all(|(_, reason, _)| *reason == SkipReason::Generated)
+        );
+        assert!(indexer.find_symbols_by_name("from_name", None).is_empty());
+        assert!(indexer.find_symbols_by_name("from_header", None).is_empty());
+
+        let mut settings = Settings {
+            index_path: temp_dir.path().join("index_all"),
+            ..Settings::default()
+        };
+        settings.indexing.include_generated = true;
+        let mut indexer = SimpleIndexer::with_settings(Arc::new(settings));
+
+        let stats = indexer.index_directory(&src, false, false).unwrap();
+        assert_eq!(stats.files_indexed, 3);
+        assert_eq!(stats.files_generated, 0);
+        assert!(!indexer.find_symbols_by_name("from_name", None).is_empty());
+    }
+
+    #[test]
+    fn test_index_directory_skips_binary_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let src = temp_dir.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("lib.rs"), "fn text() {}\n").unwrap();
+        fs::write(src.join("blob.rs"), b"fn blob() {}\0\x01\x02").unwrap();
+        fs::write(src.join("latin1.rs"), b"// caf\xe9\nfn latin() {}\n").unwrap();
+
+        let settings = Settings {
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        };
+        let mut indexer = SimpleIndexer::with_settings(Arc::new(settings));
+
+        let stats = indexer.index_directory(&src, false, false).unwrap();
+        assert_eq!(stats.files_indexed, 1);
+        assert_eq!(stats.files_binary, 2);
+        assert_eq!(stats.files_failed, 0);
+        assert!(indexer.find_symbols_by_name("blob", None).is_empty());
+        assert!(indexer.find_symbols_by_name("latin", None).is_empty());
+
+        let err = indexer.index_file(src.join("blob.rs")).unwrap_err();
+        assert!(matches!(err, IndexError::BinaryContent { .. }));
+    }
+
+    #[test]
+    fn test_indexer_skips_child_path_when_parent_tracked() {
+        let temp_dir = TempDir::new().unwrap();
+        let parent = temp_dir.path().join("parent");
+        let child = parent.join("child");
+        fs::create_dir_all(&child).unwrap();
+
+        let settings = Arc::new(Settings {
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        });
+        let mut indexer = SimpleIndexer::with_settings(settings);
+
+        indexer.add_indexed_path(&parent).unwrap();
+        let tracked_after_parent = indexer.get_indexed_paths().len();
+
+        // Adding child should be a no-op because parent already covers it
+        indexer.add_indexed_path(&child).unwrap();
+        assert_eq!(
+            indexer.get_indexed_paths().len(),
+            tracked_after_parent,
+            "child directory should not be tracked when parent already exists"
+        );
+    }
+
+    #[test]
+    fn test_indexer_replaces_children_when_parent_added() {
+        let temp_dir = TempDir::new().unwrap();
+        let parent = temp_dir.path().join("parent");
+        let child = parent.join("child");
+        fs::create_dir_all(&child).unwrap();
+
+        let settings = Arc::new(Settings {
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        });
+        let mut indexer = SimpleIndexer::with_settings(settings);
+
+        indexer.add_indexed_path(&child).unwrap();
+        assert_eq!(indexer.get_indexed_paths().len(), 1);
+
+        indexer.add_indexed_path(&parent).unwrap();
+        assert_eq!(indexer.get_indexed_paths().len(), 1);
+
+        let stored_paths: Vec<_> = indexer.get_indexed_paths().iter().cloned().collect();
+        assert_eq!(stored_paths[0], parent.canonicalize().unwrap());
+    }
+
+    #[test]
+    fn test_trait_implementations_resolution() {
+        // Test the relationship resolution bug directly by creating symbols manually
+        use tempfile::TempDir;
+
+        // Create a temporary directory for the test index
+        let temp_dir = TempDir::new().unwrap();
+        let index_path = temp_dir.path().join("test_index");
+
+        let settings = Settings {
+            debug: true,
+            index_path,
+            ..Default::default()
+        };
+        let mut indexer = SimpleIndexer::with_settings(Arc::new(settings));
+
+        let file_id = FileId(1);
+        let file_path = "test.rs";
+
+        // Set up language behavior for Rust
+        let language_id = LanguageId::new("rust");
+        indexer.file_languages.insert(file_id, language_id);
+
+        // Also need to store the behavior for resolution to work
+        let behavior = indexer
+            .parser_factory
+            .create_behavior_from_registry(language_id);
+        indexer.file_behaviors.insert(file_id, behavior);
+
+        // Start transaction to get proper symbol IDs
+        indexer.start_tantivy_batch().unwrap();
+
+        // Get proper symbol IDs using the counter
+        let mut counter = indexer.get_next_symbol_counter().unwrap();
+        let trait_id = counter.next_id();
+        let struct_id = counter.next_id();
+
+        // Create symbols with proper IDs
+        let trait_symbol = Symbol {
+            id: trait_id,
+            name: "MyTrait".into(),
+            kind: SymbolKind::Trait,
+            range: crate::Range::new(0, 0, 0, 0),
+            file_id,
+            file_path: "<unknown>".into(),
+            visibility: Visibility::Public,
+            doc_comment: None,
+            signature: None,
+            module_path: Some("test".into()),
+            scope_context: None,
+            parent: None,
+            attributes: Vec::new(),
+            language_id: None,
+        };
+
+        let struct_symbol = Symbol {
+            id: struct_id,
+            name: "MyStruct".into(),
+            kind: SymbolKind::Struct,
+            range: crate::Range::new(1, 0, 1, 0),
+            file_id,
+            file_path: "<unknown>".into(),
+            visibility: Visibility::Public,
+            doc_comment: None,
+            signature: None,
+            module_path: Some("test".into()),
+            scope_context: None,
+            parent: None,
+            attributes: Vec::new(),
+            language_id: None,
+        };
+
+        // Store symbols
+        indexer
+            .store_symbol(trait_symbol.clone(), file_path, None, SymbolChange::Code)
+            .unwrap();
+        indexer
+            .store_symbol(struct_symbol.clone(), file_path, None, SymbolChange::Code)
+            .unwrap();
+
+        // Register the implementation relationship
+        indexer
+            .add_relationships_by_name(
+                None,
+                "MyStruct",
+                "MyTrait",
+                file_id,
+                RelationKind::Implements,
+                None,
+            )
+            .unwrap();
+
+        // Commit
+        indexer.commit_tantivy_batch().unwrap();
+
+        // Debug: Check unresolved relationships
+        eprintln!(
+            "Unresolved relationships before resolution: {:?}",
+            indexer.unresolved_relationships
+        );
+        assert_eq!(indexer.unresolved_relationships.len(), 1);
+        let unresolved = &indexer.unresolved_relationships[0];
+        assert_eq!(unresolved.from_name.as_ref(), "MyStruct");
+        assert_eq!(unresolved.to_name.as_ref(), "MyTrait");
+
+        // Resolve relationships - THIS IS WHERE THE BUG HAPPENS
+        // First, let's see what symbols are in the index
+        if indexer.settings.debug {
+            let all_symbols = indexer.document_index.get_all_symbols(100).unwrap();
+            for sym in &all_symbols {
+                debug_print!(
+                    indexer,
+                    "Symbol in index - ID {:?}: name='{}', kind={:?}",
+                    sym.id,
+                    sym.name,
+                    sym.kind
+                );
+            }
+        }
+
+        indexer.resolve_cross_file_relationships().unwrap();
+
+        // Find the trait
+        let found_trait = indexer
+            .find_symbols_by_name("MyTrait", None)
+            .into_iter()
+            .find(|s| s.kind == SymbolKind::Trait)
+            .expect("Should find MyTrait");
+
+        eprintln!("Created symbols: trait_id={trait_id:?}, struct_id={struct_id:?}");
+        eprintln!("Found trait with ID: {:?}", found_trait.id);
+
+        // THIS SHOULD WORK: get_implementations should return MyStruct
+        let implementations = indexer.get_implementations(found_trait.id);
+
+        eprintln!("Found {} implementations", implementations.len());
+        for impl_sym in &implementations {
+            eprintln!("  Implementation: {} ({:?})", impl_sym.name, impl_sym.kind);
+        }
+
+        // This assertion will fail, exposing the bug
+        assert_eq!(
+            implementations.len(),
+            1,
+            "Should find MyStruct implements MyTrait"
+        );
+        if !implementations.is_empty() {
+            assert_eq!(implementations[0].name.as_ref(), "MyStruct");
+        }
+    }
+
+    #[test]
+    fn test_symbol_module_paths() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        // Create temporary directory
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path();
+
+        // Create src directory
+        let src_dir = project_root.join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+
+        // Create a simple Rust file
+        let test_file = src_dir.join("test.rs");
+        fs::write(
+            &test_file,
+            r#"
+pub fn hello() {}
+pub struct World;
+"#,
+        )
+        .unwrap();
+
+        // Create indexer with proper settings
+        let settings = Arc::new(Settings {
+            workspace_root: Some(project_root.to_path_buf()),
+            index_path: project_root.join(".test_index"),
+            ..Settings::default()
+        });
+
+        let mut indexer = SimpleIndexer::with_settings(settings);
+
+        // Index the file
+        indexer.index_file(&test_file).unwrap();
+
+        // Find symbols and check their module paths
+        let hello_symbols = indexer
+            .document_index
+            .find_symbols_by_name("hello", None)
+            .unwrap();
+        assert_eq!(hello_symbols.len(), 1);
+        assert_eq!(
+            hello_symbols[0].module_path.as_ref().map(|s| s.as_ref()),
+            Some("crate::test::hello")
+        );
+
+        let world_symbols = indexer
+            .document_index
+            .find_symbols_by_name("World", None)
+            .unwrap();
+        assert_eq!(world_symbols.len(), 1);
+        assert_eq!(
+            world_symbols[0].module_path.as_ref().map(|s| s.as_ref()),
+            Some("crate::test::World")
+        );
+    }
+
+    #[test]
+    fn test_symbols_in_same_module() {
+        let mut symbol_counter = SymbolCounter::new();
+
+        let sym1 = Symbol::new(
+            symbol_counter.next_id(),
+            "test1",
+            SymbolKind::Function,
+            FileId::new(1).unwrap(),
+            crate::Range::new(0, 0, 0, 0),
+        )
+        .with_module_path("crate::module_a");
+
+        let sym2 = Symbol::new(
+            symbol_counter.next_id(),
+            "test2",
+            SymbolKind::Function,
+            FileId::new(1).unwrap(),
+            crate::Range::new(0, 0, 0, 0),
+        )
+        .with_module_path("crate::module_a");
+
+        let sym3 = Symbol::new(
+            symbol_counter.next_id(),
+            "test3",
+            SymbolKind::Function,
+            FileId::new(2).unwrap(),
+            crate::Range::new(0, 0, 0, 0),
+        )
+        .with_module_path("crate::module_b");
+
+        let sym4 = Symbol::new(
+            symbol_counter.next_id(),
+            "test4",
+            SymbolKind::Function,
+            FileId::new(2).unwrap(),
+            crate::Range::new(0, 0, 0, 0),
+        ); // No module path
+
+        assert!(SimpleIndexer::symbols_in_same_module(&sym1, &sym2));
+        assert!(!SimpleIndexer::symbols_in_same_module(&sym1, &sym3));
+        assert!(!SimpleIndexer::symbols_in_same_module(&sym1, &sym4));
+        assert!(!SimpleIndexer::symbols_in_same_module(&sym4, &sym4)); // Both have no module
+    }
+
+    #[test]
+    fn test_is_symbol_visible_from() {
+        let mut symbol_counter = SymbolCounter::new();
+
+        let pub_sym = Symbol::new(
+            symbol_counter.next_id(),
+            "public_fn",
+            SymbolKind::Function,
+            FileId::new(1).unwrap(),
+            crate::Range::new(0, 0, 0, 0),
+        )
+        .with_module_path("crate::module_a")
+        .with_visibility(Visibility::Public);
+
+        let priv_sym = Symbol::new(
+            symbol_counter.next_id(),
+            "private_fn",
+            SymbolKind::Function,
+            FileId::new(1).unwrap(),
+            crate::Range::new(0, 0, 0, 0),
+        )
+        .with_module_path("crate::module_a")
+        .with_visibility(Visibility::Private);
+
+        let other_module_sym = Symbol::new(
+            symbol_counter.next_id(),
+            "other_fn",
+            SymbolKind::Function,
+            FileId::new(2).unwrap(),
+            crate::Range::new(0, 0, 0, 0),
+        )
+        .with_module_path("crate::module_b");
+
+        // Same module - both visible
+        assert!(SimpleIndexer::is_symbol_visible_from(&pub_sym, &priv_sym));
+        assert!(SimpleIndexer::is_symbol_visible_from(&priv_sym, &pub_sym));
+
+        // Different modules - only public visible
+        assert!(SimpleIndexer::is_symbol_visible_from(
+            &pub_sym,
+            &other_module_sym
+        ));
+        assert!(!SimpleIndexer::is_symbol_visible_from(
+            &priv_sym,
+            &other_module_sym
+        ));
+    }
+
+    #[test]
+    fn test_resolve_import() {
+        use crate::indexing::ImportResolution;
+        use std::fs;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+
+        let config_path = src_dir.join("config.rs");
+        fs::write(
+            &config_path,
+            "pub fn create_config() -> Config {\n    Config {}\n}\n\npub struct Config {}\n",
+        )
+        .unwrap();
+        let main_path = src_dir.join("main.rs");
+        fs::write(
+            &main_path,
+            "use crate::config::create_config;\nuse std::collections::HashMap;\n\nfn main() {\n    let c = create_config();\n}\n",
+        )
+        .unwrap();
+
+        let settings = Arc::new(Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        });
+        let mut indexer = SimpleIndexer::with_settings(settings);
+        indexer.index_file_no_resolve(&config_path).unwrap();
+        indexer.index_file_no_resolve(&main_path).unwrap();
+        indexer.resolve_cross_file_relationships().unwrap();
+
+        let main_str = main_path.to_str().unwrap();
+        match indexer.resolve_import(main_str, "create_config").unwrap() {
+            ImportResolution::Resolved {
+                import_path,
+                symbol,
+            } => {
+                assert_eq!(import_path, "crate::config::create_config");
+                assert_eq!(symbol.name.as_ref(), "create_config");
+                assert_eq!(
+                    indexer.get_file_path(symbol.file_id).as_deref(),
+                    Some("src/config.rs")
+                );
+            }
+            other => panic!("expected resolved import, got {other:?}"),
+        }
+
+        // Workspace-relative paths work as well
+        assert!(matches!(
+            indexer
+                .resolve_import("src/main.rs", "create_config")
+                .unwrap(),
+            ImportResolution::Resolved { .. }
+        ));
+
+        // Nothing in the index defines std types
+        assert!(matches!(
+            indexer.resolve_import(main_str, "HashMap").unwrap(),
+            ImportResolution::Unresolved { .. }
+        ));
+
+        // Names that are not imported and unknown files are errors
+        assert!(indexer.resolve_import(main_str, "Config").is_err());
+        assert!(indexer.resolve_import("src/missing.rs", "Config").is_err());
+    }
+
+    #[test]
+    fn test_symbol_at() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("parser.py");
+        fs::write(
+            &path,
+            "class Parser:\n    def parse(self, text):\n        return text\n\n# end\n",
+        )
+        .unwrap();
+
+        let settings = Arc::new(Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        });
+        let mut indexer = SimpleIndexer::with_settings(settings);
+        indexer.index_file(&path).unwrap();
+
+        // Inside the method body: the method, enclosed by its class
+        let found = indexer.symbol_at("parser.py", 2, 10).unwrap().unwrap();
+        assert_eq!(found.symbol.name.as_ref(), "parse");
+        assert!(
+            found.enclosing.iter().any(|s| s.name.as_ref() == "Parser"),
+            "enclosing: {:?}",
+            found.enclosing
+        );
+
+        // Absolute paths resolve to the same file
+        let absolute = indexer
+            .symbol_at(path.to_str().unwrap(), 2, 10)
+            .unwrap()
+            .unwrap();
+        assert_eq!(absolute.symbol.id, found.symbol.id);
+
+        // On the class header only the class covers the position
+        let found = indexer.symbol_at("parser.py", 0, 6).unwrap().unwrap();
+        assert_eq!(found.symbol.name.as_ref(), "Parser");
+
+        assert!(indexer.symbol_at("parser.py", 4, 0).unwrap().is_none());
+        assert!(indexer.symbol_at("missing.py", 0, 0).is_err());
+    }
+
+    #[test]
+    fn test_symbol_parents() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let lib_path = temp_dir.path().join("lib.rs");
+        fs::write(
+            &lib_path,
+            "pub mod shapes {\n\
+             \x20   pub struct Circle;\n\
+             \x20   impl Circle {\n\
+             \x20       pub fn area(&self) -> f64 {\n\
+             \x20           fn helper() -> f64 { 1.0 }\n\
+             \x20           helper()\n\
+             \x20       }\n\
+             \x20   }\n\
+             }\n\
+             pub fn top() {}\n",
+        )
+        .unwrap();
+
+        let settings = Arc::new(Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        });
+        let mut indexer = SimpleIndexer::with_settings(settings);
+        indexer.index_file(&lib_path).unwrap();
+
+        let find = |name: &str| indexer.find_symbols_by_name(name, None).remove(0);
+        let parent_name = |name: &str| {
+            find(name)
+                .parent
+                .and_then(|id| indexer.get_symbol(id))
+                .map(|parent| parent.name.to_string())
+        };
+
+        assert_eq!(parent_name("Circle").as_deref(), Some("shapes"));
+        // Impl blocks are not symbols; methods belong to the implemented type
+        assert_eq!(parent_name("area").as_deref(), Some("Circle"));
+        assert_eq!(parent_name("helper").as_deref(), Some("area"));
+        assert_eq!(parent_name("top"), None);
+        assert_eq!(find("shapes").parent, None);
+
+        // symbol_at walks the same chain
+        let found = indexer.symbol_at("lib.rs", 5, 12).unwrap().unwrap();
+        let chain: Vec<String> = std::iter::once(&found.symbol)
+            .chain(&found.enclosing)
+            .map(|s| s.name.to_string())
+            .collect();
+        assert_eq!(chain, ["area", "Circle", "shapes"]);
+
+        let context = indexer
+            .get_symbol_context(
+                find("area").id,
+                crate::symbol::context::ContextIncludes::empty(),
+            )
+            .unwrap();
+        assert_eq!(
+            context.relationships.parent.map(|p| p.name.to_string()),
+            Some("Circle".to_string())
+        );
+    }
+
+    #[test]
+    fn test_get_method_implementations() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let lib_path = temp_dir.path().join("lib.rs");
+        fs::write(
+            &lib_path,
+            "pub trait Shape {\n\
+             \x20   fn area(&self) -> f64;\n\
+             \x20   fn name(&self) -> String { String::new() }\n\
+             }\n\
+             pub struct Circle;\n\
+             impl Shape for Circle {\n\
+             \x20   fn area(&self) -> f64 { 3.0 }\n\
+             }\n\
+             pub struct Square;\n\
+             impl Shape for Square {\n\
+             \x20   fn area(&self) -> f64 { 1.0 }\n\
+             \x20   fn name(&self) -> String { String::from(\"square\") }\n\
+             }\n\
+             pub struct Unrelated;\n\
+             impl Unrelated {\n\
+             \x20   fn area(&self) -> f64 { 0.0 }\n\
+             }\n",
+        )
+        .unwrap();
+
+        let settings = Arc::new(Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        });
+        let mut indexer = SimpleIndexer::with_settings(settings);
+        indexer.index_file(&lib_path).unwrap();
+
+        let shape = indexer
+            .find_symbols_by_name("Shape", None)
+            .into_iter()
+            .find(|s| s.kind == SymbolKind::Trait)
+            .unwrap();
+        let names = |method: &str| -> Vec<(String, u32)> {
+            indexer
+                .get_method_implementations(shape.id, method)
+                .unwrap()
+                .into_iter()
+                .map(|i| (i.implementor.name.to_string(), i.method.range.start_line))
+                .collect()
+        };
+
+        // Unrelated::area is not an implementation of Shape::area
+        assert_eq!(
+            names("area"),
+            vec![("Circle".to_string(), 6), ("Square".to_string(), 10)]
+        );
+        // Circle keeps the default body
+        assert_eq!(names("name"), vec![("Square".to_string(), 11)]);
+
+        assert_eq!(
+            indexer
+                .find_method_implementations("Shape::name", None)
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(indexer.find_method_implementations("area", None).is_err());
+
+        let err = indexer
+            .get_method_implementations(shape.id, "perimeter")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("declares no method 'perimeter'"), "{err}");
+    }
+
+    #[test]
+    fn test_get_call_tree() {
+        use crate::symbol::context::CallDirection;
+        use std::fs;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let lib_path = temp_dir.path().join("lib.rs");
+        fs::write(
+            &lib_path,
+            "pub fn leaf() {}\n\
+             pub fn middle() { leaf(); }\n\
+             pub fn top_a() { middle(); }\n\
+             pub fn top_b() { middle(); }\n\
+             pub fn entry() { top_a(); }\n",
+        )
+        .unwrap();
+
+        let settings = Arc::new(Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        });
+        let mut indexer = SimpleIndexer::with_settings(settings);
+        indexer.index_file_no_resolve(&lib_path).unwrap();
+        indexer.resolve_cross_file_relationships().unwrap();
+
+        let id = |name: &str| indexer.find_symbols_by_name(name, None)[0].id;
+        let names = |nodes: &[crate::symbol::context::CallTreeNode]| {
+            let mut names: Vec<String> = nodes.iter().map(|n| n.symbol.name.to_string()).collect();
+            names.sort();
+            names
+        };
+
+        let callers = indexer.get_call_tree(id("leaf"), CallDirection::Callers, 3, 10);
+        assert_eq!(names(&callers.symbols), vec!["middle"]);
+        let middle = &callers.symbols[0];
+        assert_eq!(names(&middle.symbols), vec!["top_a", "top_b"]);
+        let top_a = middle
+            .symbols
+            .iter()
+            .find(|n| n.symbol.name.as_ref() == "top_a")
+            .unwrap();
+        assert_eq!(names(&top_a.symbols), vec!["entry"]);
+        assert!(!callers.is_truncated());
+
+        // Depth limits the levels walked
+        let shallow = indexer.get_call_tree(id("leaf"), CallDirection::Callers, 2, 10);
+        assert!(
+            shallow.symbols[0]
+                .symbols
+                .iter()
+                .all(|n| n.symbols.is_empty())
+        );
+
+        // Fan-out beyond the cap is counted, not listed
+        let capped = indexer.get_call_tree(id("leaf"), CallDirection::Callers, 3, 1);
+        assert_eq!(capped.symbols[0].symbols.len(), 1);
+        assert_eq!(capped.symbols[0].truncated, 1);
+        assert!(capped.is_truncated());
+
+        let callees = indexer.get_call_tree(id("entry"), CallDirection::Callees, 3, 10);
+        assert_eq!(names(&callees.symbols), vec!["top_a"]);
+        assert_eq!(names(&callees.symbols[0].symbols), vec!["middle"]);
+        assert_eq!(names(&callees.symbols[0].symbols[0].symbols), vec!["leaf"]);
+    }
+
+    #[test]
+    fn test_get_call_hierarchy_marks_repeats() {
+        use crate::symbol::context::{CallDirection, CallTreeNode};
+        use std::fs;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let lib_path = temp_dir.path().join("lib.rs");
+        fs::write(
+            &lib_path,
+            "pub fn shared() { entry(); }\n\
+             pub fn alpha() { shared(); }\n\
+             pub fn beta() { shared(); }\n\
+             pub fn entry() { alpha(); beta(); }\n",
+        )
+        .unwrap();
+
+        let settings = Arc::new(Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        });
+        let mut indexer = SimpleIndexer::with_settings(settings);
+        indexer.index_file_no_resolve(&lib_path).unwrap();
+        indexer.resolve_cross_file_relationships().unwrap();
+
+        let entry = indexer.find_symbols_by_name("entry", None)[0].id;
+        let node = |nodes: &[CallTreeNode], name: &str| {
+            nodes
+                .iter()
+                .find(|n| n.symbol.name.as_ref() == name)
+                .cloned()
+                .unwrap()
+        };
+
+        let tree = indexer.get_call_hierarchy(entry, CallDirection::Callees, 5, 100, false);
+        let names: Vec<&str> = tree
+            .symbols
+            .iter()
+            .map(|n| n.symbol.name.as_ref())
+            .collect();
+        assert_eq!(names, vec!["alpha", "beta"]);
+
+        // alpha expands shared, which cycles back to entry
+        let shared = node(&node(&tree.symbols, "alpha").symbols, "shared");
+        assert!(!shared.repeated);
+        assert!(node(&shared.symbols, "entry").repeated);
+
+        // beta reaches shared again and does not expand it
+        let shared_again = node(&node(&tree.symbols, "beta").symbols, "shared");
+        assert!(shared_again.repeated);
+        assert!(shared_again.symbols.is_empty());
+
+        // The node budget cuts the listing short
+        let capped = indexer.get_call_hierarchy(entry, CallDirection::Callees, 5, 1, false);
+        assert_eq!(capped.symbols.len(), 1);
+        assert_eq!(capped.truncated, 1);
+        assert!(capped.is_truncated());
+    }
+
+    #[test]
+    fn test_entry_points() {
+        use crate::indexing::EntryPointCategory;
+        use std::fs;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let main_path = temp_dir.path().join("main.rs");
+        fs::write(
+            &main_path,
+            "#[tokio::main]\n\
+             async fn start() { helper(); }\n\
+             fn helper() {}\n\
+             pub fn unused_api() {}\n\
+             pub fn used_api() {}\n\
+             #[unsafe(no_mangle)]\n\
+             pub extern \"C\" fn plugin_init() { used_api(); }\n",
+        )
+        .unwrap();
+
+        let settings = Arc::new(Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        });
+        let mut indexer = SimpleIndexer::with_settings(settings);
+        indexer.index_file_no_resolve(&main_path).unwrap();
+        indexer.resolve_cross_file_relationships().unwrap();
+
+        let found: Vec<(EntryPointCategory, String)> = indexer
+            .entry_points()
+            .into_iter()
+            .map(|entry_point| (entry_point.category, entry_point.name))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (EntryPointCategory::Main, "start".to_string()),
+                (EntryPointCategory::FfiExport, "plugin_init".to_string()),
+                (EntryPointCategory::Uncalled, "unused_api".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_multi_line_attributes() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let rust_path = temp_dir.path().join("point.rs");
+        fs::write(
+            &rust_path,
+            "/// A point\n\
+             #[derive(\n    Debug,\n    Clone,\n)]\n\
+             // wire format\n\
+             #[serde(rename_all = \"camelCase\")]\n\
+             pub struct Point { x: i32 }\n\
+             pub struct Plain;\n",
+        )
+        .unwrap();
+        let python_path = temp_dir.path().join("views.py");
+        fs::write(
+            &python_path,
+            "@app.route(\n    \"/users\",\n    methods=[\"GET\"],\n)\n\
+             def users():\n    pass\n",
+        )
+        .unwrap();
+
+        let settings = Arc::new(Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        });
+        let mut indexer = SimpleIndexer::with_settings(settings);
+        indexer.index_file_no_resolve(&rust_path).unwrap();
+        indexer.index_file_no_resolve(&python_path).unwrap();
+
+        let attributes = |name: &str| {
+            indexer.find_symbols_by_name(name, None)[0]
+                .attributes
+                .clone()
+        };
+        assert_eq!(
+            attributes("Point"),
+            vec![
+                Box::from("#[derive( Debug, Clone, )]"),
+                Box::from("#[serde(rename_all = \"camelCase\")]"),
+            ]
+        );
+        assert!(attributes("Plain").is_empty());
+        assert_eq!(
+            attributes("users"),
+            vec![Box::from("@app.route( \"/users\", methods=[\"GET\"], )")]
+        );
+    }
+
+    #[test]
+    fn test_get_transitive_callers() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let lib_path = temp_dir.path().join("lib.rs");
+        fs::write(
+            &lib_path,
+            "pub fn leaf() {}\n\
+             pub fn middle() { leaf(); }\n\
+             pub fn top_b() { middle(); leaf(); }\n\
+             pub fn top_a() { middle(); }\n\
+             pub fn entry() { top_a(); entry(); }\n",
+        )
+        .unwrap();
+
+        let settings = Arc::new(Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        });
+        let mut indexer = SimpleIndexer::with_settings(settings);
+        indexer.index_file_no_resolve(&lib_path).unwrap();
+        indexer.resolve_cross_file_relationships().unwrap();
+
+        let leaf = indexer.find_symbols_by_name("leaf", None)[0].id;
+        let reached = |depth| {
+            indexer
+                .get_transitive_callers(leaf, depth, false)
+                .into_iter()
+                .map(|(symbol, distance)| (symbol.name.to_string(), distance))
+                .collect::<Vec<_>>()
+        };
+
+        // top_b calls leaf directly and through middle; the shorter chain wins
+        assert_eq!(
+            reached(5),
+            vec![
+                ("middle".to_string(), 1),
+                ("top_b".to_string(), 1),
+                ("top_a".to_string(), 2),
+                ("entry".to_string(), 3),
+            ]
+        );
+        assert_eq!(reached(1).len(), 2);
+    }
+
+    #[test]
+    fn test_impact_radius_stops_at_cycles_and_node_cap() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let lib_path = temp_dir.path().join("lib.rs");
+        fs::write(
+            &lib_path,
+            "pub fn leaf() { ping(); }\n\
+             pub fn ping() { leaf(); }\n\
+             pub fn one() { leaf(); }\n\
+             pub fn two() { one(); }\n",
+        )
+        .unwrap();
+
+        let impact = |max_impact_nodes| {
+            let settings = Arc::new(Settings {
+                workspace_root: Some(temp_dir.path().to_path_buf()),
+                index_path: temp_dir.path().join(format!("index-{max_impact_nodes}")),
+                mcp: crate::config::McpConfig {
+                    max_impact_nodes,
+                    ..Default::default()
+                },
+                ..Settings::default()
+            });
+            let mut indexer = SimpleIndexer::with_settings(settings);
+            indexer.index_file_no_resolve(&lib_path).unwrap();
+            indexer.resolve_cross_file_relationships().unwrap();
+            let leaf = indexer.find_symbols_by_name("leaf", None)[0].id;
+            indexer.get_impact_radius(leaf, Some(10), false)
+        };
+
+        // leaf and ping call each other; the walk still ends
+        let full = impact(100);
+        assert_eq!(full.symbols.len(), 3);
+        assert!(!full.truncated);
+
+        let capped = impact(2);
+        assert_eq!(capped.symbols.len(), 2);
+        assert!(capped.symbols.iter().all(|id| full.symbols.contains(id)));
+        assert!(capped.truncated);
+    }
+
+    #[test]
+    fn test_resolved_only_skips_uncertain_edges() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let lib_path = temp_dir.path().join("lib.rs");
+        fs::write(
+            &lib_path,
+            "pub fn leaf() {}\n\
+             pub fn middle() { leaf(); }\n\
+             pub fn guessed() {}\n",
+        )
+        .unwrap();
+
+        let settings = Arc::new(Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        });
+        let mut indexer = SimpleIndexer::with_settings(settings);
+        indexer.index_file_no_resolve(&lib_path).unwrap();
+        indexer.resolve_cross_file_relationships().unwrap();
+
+        let id = |name: &str| indexer.find_symbols_by_name(name, None)[0].id;
+        let (leaf, guessed) = (id("leaf"), id("guessed"));
+        indexer.start_tantivy_batch().unwrap();
+        indexer
+            .add_relationship_internal(
+                guessed,
+                leaf,
+                Relationship::new(RelationKind::Calls).with_provenance(Provenance::Heuristic),
+            )
+            .unwrap();
+        indexer.commit_tantivy_batch().unwrap();
+
+        let mut callers: Vec<(String, Provenance)> = indexer
+            .get_calling_functions_with_metadata(leaf)
+            .into_iter()
+            .map(|(symbol, _, provenance)| (symbol.name.to_string(), provenance))
+            .collect();
+        callers.sort();
+        assert_eq!(
+            callers,
+            vec![
+                ("guessed".to_string(), Provenance::Heuristic),
+                ("middle".to_string(), Provenance::Resolved),
+            ]
+        );
+
+        // Listing callees reports the same edge
+        let callees = indexer.get_called_functions_with_metadata(guessed);
+        assert_eq!(callees.len(), 1);
+        assert_eq!(callees[0].2, Provenance::Heuristic);
+
+        let names = |callers: Vec<(Symbol, usize)>| {
+            callers
+                .into_iter()
+                .map(|(symbol, _)| symbol.name.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            names(indexer.get_transitive_callers(leaf, 2, false)),
+            vec!["guessed", "middle"]
+        );
+        assert_eq!(
+            names(indexer.get_transitive_callers(leaf, 2, true)),
+            vec!["middle"]
+        );
+        assert_eq!(
+            indexer
+                .get_impact_radius(leaf, Some(2), false)
+                .symbols
+                .len(),
+            2
+        );
+        assert_eq!(
+            indexer.get_impact_radius(leaf, Some(2), true).symbols,
+            vec![id("middle")]
+        );
+    }
+
+    #[test]
+    fn test_ambiguous_resolutions() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let files = [
+            ("a.rs", "pub fn helper() {}\n"),
+            ("b.rs", "pub fn helper() {}\n"),
+            ("main.rs", "fn run() {\n    other();\n}\nfn other() {}\n"),
+        ];
+
+        let settings = Arc::new(Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        });
+        let mut indexer = SimpleIndexer::with_settings(settings);
+        for (name, source) in files {
+            let path = temp_dir.path().join(name);
+            fs::write(&path, source).unwrap();
+            indexer.index_file_no_resolve(&path).unwrap();
+        }
+        indexer.resolve_cross_file_relationships().unwrap();
+        assert!(indexer.ambiguous_resolutions().unwrap().is_empty());
+
+        let run = indexer.find_symbols_by_name("run", None)[0].id;
+        let mut helpers = indexer.find_symbols_by_name("helper", None);
+        helpers.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+        let chosen = helpers[1].id;
+        indexer.start_tantivy_batch().unwrap();
+        indexer
+            .add_relationship_internal(
+                run,
+                chosen,
+                Relationship::new(RelationKind::Calls)
+                    .with_metadata(RelationshipMetadata::new().at_position(1, 4))
+                    .with_provenance(Provenance::Ambiguous),
+            )
+            .unwrap();
+        indexer.commit_tantivy_batch().unwrap();
+
+        // The reverse CalledBy edge is not reported again
+        let sites = indexer.ambiguous_resolutions().unwrap();
+        assert_eq!(sites.len(), 1);
+        let site = &sites[0];
+        assert_eq!(site.kind, RelationKind::Calls);
+        assert_eq!(site.source.id, run);
+        assert!(site.location.ends_with("main.rs:2"), "{}", site.location);
+        assert_eq!(site.target.id, chosen);
+        let candidates: Vec<SymbolId> = site.candidates.iter().map(|c| c.id).collect();
+        assert_eq!(candidates, vec![chosen, helpers[0].id]);
+    }
+
+    #[test]
+    fn test_signature_changes_report_withdrawn_functions() {
+        use crate::indexing::{ChangeSeverity, SignatureDelta};
+        use std::fs;
+
+        let index = |source: &str| {
+            let temp_dir = TempDir::new().unwrap();
+            let settings = Arc::new(Settings {
+                workspace_root: Some(temp_dir.path().to_path_buf()),
+                index_path: temp_dir.path().join("index"),
+                ..Settings::default()
+            });
+            let mut indexer = SimpleIndexer::with_settings(settings);
+            let path = temp_dir.path().join("api.rs");
+            fs::write(&path, source).unwrap();
+            indexer.index_file_no_resolve(&path).unwrap();
+            (temp_dir, indexer)
+        };
+        let (_baseline_dir, baseline) = index(
+            "pub fn open(path: &str) {}\npub fn close(fd: u32) {}\npub fn flush(fd: u32) {}\n",
+        );
+        let (_current_dir, current) = index("pub fn open(file: &str) {}\nfn close(fd: u32) {}\n");
+
+        let changes = current.signature_changes(&baseline).unwrap();
+        let summary: Vec<(&str, &[SignatureDelta], ChangeSeverity)> = changes
+            .iter()
+            .map(|change| {
+                let name = change.qualified_name.rsplit("::").next().unwrap();
+                (name, change.deltas.as_slice(), change.severity)
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "close",
+                    &[SignatureDelta::MadePrivate][..],
+                    ChangeSeverity::Breaking
+                ),
+                (
+                    "flush",
+                    &[SignatureDelta::Removed][..],
+                    ChangeSeverity::Breaking
+                ),
+                (
+                    "open",
+                    &[SignatureDelta::ParameterRenamed {
+                        from: "path".into(),
+                        to: "file".into(),
+                    }][..],
+                    ChangeSeverity::NonBreaking
+                ),
+            ]
+        );
+        let flush = &changes[1];
+        assert!(flush.symbol_id.is_none() && flush.current_signature.is_none());
+        assert!(changes[0].symbol_id.is_some());
+    }
+
+    #[test]
+    fn test_trait_impl_methods_keep_type_and_trait() {
+        use std::fs;
+
+        let temp_dir = TempDir::new().unwrap();
+        let source = r#"pub trait Shape {
+    fn area(&self) -> f64;
+}
+
+pub struct Square {
+    side: f64,
+}
+
+impl Square {
+    pub fn new(side: f64) -> Self {
+        Square { side }
+    }
+
+    pub fn area(&self) -> f64 {
+        self.side * self.side
+    }
+}
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.side * self.side
+    }
+}
+"#;
+        let settings = Arc::new(Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        });
+        let mut indexer = SimpleIndexer::with_settings(settings);
+        let path = temp_dir.path().join("shapes.rs");
+        fs::write(&path, source).unwrap();
+        indexer.index_file_no_resolve(&path).unwrap();
+        indexer.resolve_cross_file_relationships().unwrap();
+
+        let find = |name: &str, kind: SymbolKind| {
+            indexer
+                .find_symbols_by_name(name, None)
+                .into_iter()
+                .find(|s| s.kind == kind)
+                .unwrap()
+        };
+        let shape = find("Shape", SymbolKind::Trait);
+        let square = find("Square", SymbolKind::Struct);
+
+        // Inherent methods first, then the Shape impl
+        let methods: Vec<(String, u32, Option<String>)> = indexer
+            .get_type_methods(square.id)
+            .into_iter()
+            .map(|m| {
+                (
+                    m.method.name.to_string(),
+                    m.method.range.start_line,
+                    m.implements.map(|t| t.name.to_string()),
+                )
+            })
+            .collect();
+        assert_eq!(
+            methods,
+            vec![
+                ("new".to_string(), 9, None),
+                ("area".to_string(), 13, None),
+                ("area".to_string(), 19, Some("Shape".to_string())),
+            ]
+        );
+
+        // Trait impl methods are not reported as implementing types
+        let implementors: Vec<SymbolId> = indexer
+            .get_implementations(shape.id)
+            .into_iter()
+            .map(|s| s.id)
+            .collect();
+        assert_eq!(implementors, vec![square.id]);
+
+        // The same-named inherent method is not the trait's implementation
+        let implementations = indexer
+            .get_method_implementations(shape.id, "area")
+            .unwrap();
+        assert_eq!(implementations.len(), 1);
+        assert_eq!(implementations[0].implementor.id, square.id);
+        assert_eq!(implementations[0].method.range.start_line, 19);
+    }
+
+    #[test]
+    fn test_type_parameter_bounds() {
+        use std::fs;
+
+        let temp_dir = TempDir::new().unwrap();
+        let source = r#"pub trait Render {}
+
+pub trait Named {}
+
+pub fn show<T: Render>(item: &T) {}
+
+pub fn label<N>(item: &N)
+where
+    N: Named + Render,
+{
+}
+"#;
+        let mut settings = Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        };
+        settings.indexing.index_type_parameters = true;
+        let mut indexer = SimpleIndexer::with_settings(Arc::new(settings));
+        let path = temp_dir.path().join("generics.rs");
+        fs::write(&path, source).unwrap();
+        indexer.index_file_no_resolve(&path).unwrap();
+        indexer.resolve_cross_file_relationships().unwrap();
+
+        // Generic functions bounded by a trait, through their type parameters
+        let bounded_by = |trait_name: &str| {
+            let trait_id = indexer.find_symbols_by_name(trait_name, None)[0].id;
+            let mut owners: Vec<String> = indexer
+                .get_dependents(trait_id)
+                .remove(&RelationKind::Uses)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|s| s.kind == SymbolKind::Parameter)
+                .filter_map(|param| indexer.get_symbol(param.parent?))
+                .map(|owner| owner.name.to_string())
+                .collect();
+            owners.sort();
+            owners
+        };
+        assert_eq!(bounded_by("Render"), vec!["label", "show"]);
+        assert_eq!(bounded_by("Named"), vec!["label"]);
+    }
+
+    #[test]
+    fn test_derived_implementations() {
+        use std::fs;
+
+        let temp_dir = TempDir::new().unwrap();
+        let source = r#"pub trait Shape {}
+
+pub trait Named {}
+
+#[derive(Debug, Named, serde::Serialize)]
+pub struct Square {
+    side: f64,
+}
+
+impl Shape for Square {}
+"#;
+        let settings = Arc::new(Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        });
+        let mut indexer = SimpleIndexer::with_settings(settings);
+        let path = temp_dir.path().join("shapes.rs");
+        fs::write(&path, source).unwrap();
+        indexer.index_file_no_resolve(&path).unwrap();
+        indexer.resolve_cross_file_relationships().unwrap();
+
+        let find = |name: &str, kind: SymbolKind| {
+            indexer
+                .find_symbols_by_name(name, None)
+                .into_iter()
+                .find(|s| s.kind == kind)
+                .unwrap()
+        };
+        let shape = find("Shape", SymbolKind::Trait);
+        let named = find("Named", SymbolKind::Trait);
+        let square = find("Square", SymbolKind::Struct);
+
+        // Both traits list Square; only the derive edge is marked
+        let implementors = |trait_id| -> Vec<SymbolId> {
+            indexer
+                .get_implementations(trait_id)
+                .into_iter()
+                .map(|s| s.id)
+                .collect()
+        };
+        assert_eq!(implementors(shape.id), vec![square.id]);
+        assert_eq!(implementors(named.id), vec![square.id]);
+        assert_eq!(indexer.derived_implementations(named.id), vec![square.id]);
+        assert!(indexer.derived_implementations(shape.id).is_empty());
+        // Debug is not indexed, so only the resolvable derive is recorded
+        assert_eq!(indexer.derived_implementations(square.id), vec![named.id]);
+
+        // Unindexed traits are still found by name, with or without the path
+        let deriving = |trait_name: &str| -> Vec<SymbolId> {
+            indexer
+                .find_derived_implementors(trait_name, None)
+                .into_iter()
+                .map(|s| s.id)
+                .collect()
+        };
+        assert_eq!(deriving("Debug"), vec![square.id]);
+        assert_eq!(deriving("Serialize"), vec![square.id]);
+        assert_eq!(deriving("serde::Serialize"), vec![square.id]);
+        assert!(deriving("Shape").is_empty());
+    }
+
+    #[test]
+    fn test_python_class_hierarchy() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("shapes.py");
+        std::fs::copy(
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/python/shapes.py"),
+            &path,
+        )
+        .unwrap();
+        let settings = Arc::new(Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        });
+        let mut indexer = SimpleIndexer::with_settings(settings.clone());
+        indexer.index_file(&path).unwrap();
+
+        let class = |name: &str| {
+            indexer
+                .find_symbols_by_name(name, None)
+                .into_iter()
+                .find(|s| s.kind == SymbolKind::Class)
+                .unwrap()
+        };
+        let method = |class: &Symbol, name: &str| {
+            indexer
+                .find_symbols_by_name(&format!("{}.{name}", class.name), None)
+                .into_iter()
+                .find(|s| s.parent == Some(class.id))
+                .unwrap()
+        };
+        let names = |symbols: Vec<Symbol>| -> Vec<String> {
+            symbols.iter().map(|s| s.name.to_string()).collect()
+        };
+        let (shape, named, square, cube) = (
+            class("Shape"),
+            class("Named"),
+            class("Square"),
+            class("Cube"),
+        );
+
+        // Querying a base returns its subclasses; bases keep declaration order
+        assert_eq!(names(indexer.get_subclasses(shape.id)), vec!["Square"]);
+        assert_eq!(names(indexer.get_subclasses(named.id)), vec!["Square"]);
+        assert_eq!(names(indexer.get_subclasses(square.id)), vec!["Cube"]);
+        assert_eq!(
+            names(indexer.get_base_classes(square.id)),
+            vec!["Shape", "Named"]
+        );
+
+        // super() follows the bases in order: Shape defines describe before Named
+        let called = |caller: Symbol| -> Vec<SymbolId> {
+            indexer
+                .get_called_functions(caller.id)
+                .iter()
+                .map(|s| s.id)
+                .collect()
+        };
+        assert!(called(method(&square, "describe")).contains(&method(&shape, "describe").id));
+        assert!(!called(method(&square, "describe")).contains(&method(&named, "describe").id));
+        assert!(called(method(&cube, "area")).contains(&method(&square, "area").id));
+
+        // A later session follows the stored Extends edges
+        let shape_describe = method(&shape, "describe").id;
+        drop(indexer);
+        let indexer = SimpleIndexer::with_settings(settings);
+        let traces = indexer.explain_call_resolution("shapes.py", 25).unwrap();
+        let trace = traces.iter().find(|t| t.name == "describe").unwrap();
+        assert_eq!(trace.receiver.as_deref(), Some("super()"));
+        assert!(
+            trace
+                .steps
+                .iter()
+                .any(|step| step.result.as_ref().map(|s| s.id) == Some(shape_describe))
+        );
+    }
+
+    #[test]
+    fn test_explain_call_resolution_lists_steps_and_candidates() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("lib.rs");
+        fs::write(
+            &file,
+            r#"pub struct Config;
+
+impl Config {
+    pub fn new() -> Self {
+        Config
+    }
+}
+
+pub fn helper() {}
+
+pub fn run() {
+    helper();
+    let _config = Config::new();
+    missing();
+}
+"#,
+        )
+        .unwrap();
+        let settings = Arc::new(Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        });
+        let mut indexer = SimpleIndexer::with_settings(settings);
+        indexer.index_file(&file).unwrap();
+
+        let traces = indexer.explain_call_resolution("lib.rs", 12).unwrap();
+        assert_eq!(traces.len(), 1);
+        let helper = &traces[0];
+        assert_eq!(helper.name, "helper");
+        assert_eq!(helper.line, 12);
+        assert_eq!(helper.caller.as_ref().unwrap().name.as_ref(), "run");
+        assert_eq!(helper.steps[0].lookup, "helper");
+        assert_eq!(helper.candidates.len(), 1);
+        assert_eq!(helper.chosen.as_ref().unwrap().name.as_ref(), "helper");
+        assert_eq!(helper.provenance, Some(Provenance::Resolved));
+        assert_eq!(helper.stored.len(), 1);
+        assert_eq!(helper.stored[0].name.as_ref(), "helper");
+        assert!(helper.disagreement.is_none());
+
+        let traces = indexer.explain_call_resolution("lib.rs", 14).unwrap();
+        let missing = &traces[0];
+        assert_eq!(missing.name, "missing");
+        assert!(missing.chosen.is_none());
+        assert!(missing.candidates.is_empty());
+        assert!(missing.stored.is_empty());
+        assert!(missing.disagreement.is_none());
+        assert!(missing.reason.contains("no indexed symbol"));
+        assert!(missing.to_string().contains("Chosen: none"));
+
+        // Edited since indexing: the replay no longer matches the stored edge
+        let source = fs::read_to_string(&file).unwrap();
+        fs::write(&file, source.replace("    helper();", "    Config::new();")).unwrap();
+        let traces = indexer.explain_call_resolution("lib.rs", 12).unwrap();
+        let edited = &traces[0];
+        assert_eq!(edited.chosen.as_ref().unwrap().name.as_ref(), "new");
+        assert!(edited.disagreement.is_some());
+        assert!(edited.to_string().contains("Warning:"));
+
+        assert!(
+            indexer
+                .explain_call_resolution("lib.rs", 1)
+                .unwrap()
+                .is_empty()
+        );
+        assert!(indexer.explain_call_resolution("other.rs", 1).is_err());
+    }
+
+    #[test]
+    fn test_enum_variant_uses_resolve_within_their_enum() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("shapes.rs");
+        fs::write(
+            &file,
+            r#"pub enum Shape {
+    Square,
+    Circle,
+}
+
+pub enum Tile {
+    Square,
+    Blank,
+}
+
+pub struct Grid;
+
+impl Grid {
+    pub const MAX: usize = 8;
+}
+
+pub fn draw() -> Shape {
+    Shape::Square
+}
+
+pub fn pave() -> usize {
+    let _tile = Tile::Square;
+    Grid::MAX
+}
+"#,
+        )
+        .unwrap();
+        let settings = Arc::new(Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        });
+        let mut indexer = SimpleIndexer::with_settings(settings);
+        indexer.index_file(&file).unwrap();
+
+        let variant = |enum_name: &str| {
+            indexer
+                .find_symbols_by_name("Square", None)
+                .into_iter()
+                .find(|s| {
+                    s.kind == SymbolKind::EnumVariant
+                        && s.parent
+                            .and_then(|parent| indexer.get_symbol(parent))
+                            .is_some_and(|parent| parent.name.as_ref() == enum_name)
+                })
+                .unwrap_or_else(|| panic!("{enum_name}::Square should be indexed"))
+        };
+        let users = |variant: Symbol| -> Vec<String> {
+            indexer
+                .get_calling_functions(variant.id)
+                .iter()
+                .map(|s| s.name.to_string())
+                .collect()
+        };
+
+        // Each path reaches the variant of its own enum only
+        assert_eq!(users(variant("Shape")), vec!["draw"]);
+        assert_eq!(users(variant("Tile")), vec!["pave"]);
+
+        // `Grid::MAX` is an associated constant, not a variant use
+        let pave = indexer.find_symbols_by_name("pave", None).remove(0);
+        let used: Vec<String> = indexer
+            .get_dependencies(pave.id)
+            .remove(&RelationKind::Uses)
+            .unwrap_or_default()
+            .iter()
+            .map(|s| s.name.to_string())
+            .collect();
+        assert!(!used.contains(&"MAX".to_string()), "{used:?}");
+    }
+
+    /// Owners of the methods the function `caller` calls, by method name
+    fn called_method_owners(indexer: &SimpleIndexer, caller: &str) -> Vec<(String, String)> {
+        let caller = indexer.find_symbols_by_name(caller, None).remove(0);
+        indexer
+            .get_called_functions(caller.id)
+            .into_iter()
+            .filter_map(|method| {
+                let owner = indexer.get_symbol(method.parent?)?;
+                Some((method.name.to_string(), owner.name.to_string()))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_method_chain_calls_resolve_through_return_types() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("chain.rs");
+        fs::copy(
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/method_chains.rs"),
+            &file,
+        )
+        .unwrap();
+        let settings = Arc::new(Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        });
+        let mut indexer = SimpleIndexer::with_settings(settings);
+        indexer.index_file(&file).unwrap();
+
+        // `builder.name("app").size(3).build()`: each link is typed by the
+        // return type of the one before it, and `Config::build` of the same
+        // name is not called
+        let owners = called_method_owners(&indexer, "configure");
+        for method in ["name", "size", "build"] {
+            let calls: Vec<_> = owners.iter().filter(|(name, _)| name == method).collect();
+            assert_eq!(
+                calls.len(),
+                1,
+                "configure should call {method} once: {owners:?}"
+            );
+            assert_eq!(calls[0].1, "Builder");
+        }
+
+        // `client.get("/").send().text()`: every link returns a different type
+        let owners = called_method_owners(&indexer, "fetch");
+        for (method, owner) in [("get", "Client"), ("send", "Request"), ("text", "Response")] {
+            let calls: Vec<_> = owners.iter().filter(|(name, _)| name == method).collect();
+            assert_eq!(
+                calls.len(),
+                1,
+                "fetch should call {method} once: {owners:?}"
+            );
+            assert_eq!(calls[0].1, owner);
+        }
+    }
+
+    #[test]
+    fn test_typescript_method_chain_calls_resolve_through_return_types() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("chain.ts");
+        fs::copy(
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests/fixtures/typescript/method_chains.ts"),
+            &file,
+        )
+        .unwrap();
+        let settings = Arc::new(Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        });
+        let mut indexer = SimpleIndexer::with_settings(settings);
+        indexer.index_file(&file).unwrap();
+
+        // `client.get("/").header("accept").send().text()`: `this` keeps the
+        // receiver type, the other links change it
+        let owners = called_method_owners(&indexer, "fetchText");
+        for (method, owner) in [
+            ("get", "Client"),
+            ("header", "Request"),
+            ("send", "Request"),
+            ("text", "Response"),
+        ] {
+            let calls: Vec<_> = owners.iter().filter(|(name, _)| name == method).collect();
+            assert_eq!(
+                calls.len(),
+                1,
+                "fetchText should call {method} once: {owners:?}"
+            );
+            assert_eq!(calls[0].1, owner);
+        }
+    }
+
+    #[test]
+    fn test_method_of_type_prefers_type_in_scope() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = r#"pub struct Store;
+
+impl Store {
+    pub fn new() -> Self {
+        Store
+    }
+
+    pub fn save(&self) {}
+
+    pub fn flush(&self) {
+        self.save();
+    }
+}
+"#;
+        fs::write(temp_dir.path().join("a.rs"), store).unwrap();
+        fs::write(temp_dir.path().join("b.rs"), store).unwrap();
+        fs::write(
+            temp_dir.path().join("c.rs"),
+            "pub fn run() {\n    let store = Store::new();\n    store.save();\n}\n",
+        )
+        .unwrap();
+        let settings = Arc::new(Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        });
+        let mut indexer = SimpleIndexer::with_settings(settings);
+        let mut file_ids = Vec::new();
+        for name in ["a.rs", "b.rs", "c.rs"] {
+            file_ids.push(
+                indexer
+                    .index_file(temp_dir.path().join(name))
+                    .unwrap()
+                    .file_id(),
+            );
+        }
+
+        // Both files declare a `Store`; the caller's own file wins
+        for (index, name) in ["a.rs", "b.rs"].into_iter().enumerate() {
+            let traces = indexer.explain_call_resolution(name, 11).unwrap();
+            let step = &traces[0].steps[0];
+            assert_eq!(step.lookup, "Store.save");
+            assert_eq!(step.result.as_ref().unwrap().file_id, file_ids[index]);
+            assert_eq!(step.provenance, Provenance::Resolved);
+        }
+
+        // Neither is in scope from c.rs, so the pick is ambiguous
+        let traces = indexer.explain_call_resolution("c.rs", 3).unwrap();
+        let step = &traces[0].steps[0];
+        assert_eq!(step.lookup, "Store.save");
+        assert!(step.result.is_some());
+        assert_eq!(step.provenance, Provenance::Ambiguous);
+    }
+
+    #[test]
+    fn test_item_macros_define_symbols_of_configured_kind() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = r#"
+macro_rules! define_id {
+    ($name:ident) => {
+        pub struct $name(u64);
+    };
+}
+
+define_id! { UserId }
+crate::define_id!(OrderId);
+other_macro! { Ignored }
+"#;
+        let mut settings = Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        };
+        settings
+            .indexing
+            .item_macros
+            .insert("define_id!".to_string(), "struct".to_string());
+        let mut indexer = SimpleIndexer::with_settings(Arc::new(settings));
+        let path = temp_dir.path().join("ids.rs");
+        fs::write(&path, source).unwrap();
+        indexer.index_file(&path).unwrap();
+
+        for name in ["UserId", "OrderId"] {
+            let symbols = indexer.find_symbols_by_name(name, None);
+            assert_eq!(symbols.len(), 1, "{name} should be indexed once");
+            assert_eq!(symbols[0].kind, SymbolKind::Struct);
+        }
+        assert!(indexer.find_symbols_by_name("Ignored", None).is_empty());
+    }
+
+    #[test]
+    fn test_signature_uses_skip_fields_and_locals() {
+        use crate::relationship::TypeUsePosition;
+
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("config.rs");
+        std::fs::write(
+            &file,
+            r#"pub struct Config {
+    pub name: String,
+}
+
+pub struct App {
+    config: Config,
+}
+
+pub fn load(path: &str) -> Config {
+    Config { name: path.to_string() }
+}
+
+pub fn apply(config: &Config) {}
+
+pub fn run() {
+    let config: Config = load("app.toml");
+    apply(&config);
+}
+"#,
+        )
+        .unwrap();
+        let settings = Arc::new(Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        });
+        let mut indexer = SimpleIndexer::with_settings(settings);
+        indexer.index_file(&file).unwrap();
+        let python_file = temp_dir.path().join("options.py");
+        std::fs::write(&python_file, "class Options:\n    pass\n").unwrap();
+        indexer.index_file(&python_file).unwrap();
+
+        let config = indexer.find_type_by_name("Config", Some("rust")).unwrap();
+        let uses: Vec<(String, TypeUsePosition)> = indexer
+            .get_signature_uses(config.id)
+            .unwrap()
+            .into_iter()
+            .map(|signature_use| {
+                (
+                    signature_use.symbol.name.to_string(),
+                    signature_use.position,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            uses,
+            vec![
+                ("load".to_string(), TypeUsePosition::Return),
+                ("apply".to_string(), TypeUsePosition::Parameter),
+            ]
+        );
+
+        // Python records no positions, which is reported rather than an empty list
+        let options = indexer
+            .find_type_by_name("Options", Some("python"))
+            .unwrap();
+        let error = indexer.get_signature_uses(options.id).unwrap_err();
+        assert!(error.to_string().contains("not supported for python"));
+    }
+
+    #[test]
+    fn test_type_hierarchy_walks_both_directions_and_stops_at_cycles() {
+        use crate::symbol::context::{HierarchyDirection, TypeHierarchyNode};
+
+        let temp_dir = TempDir::new().unwrap();
+        let shapes = temp_dir.path().join("shapes.py");
+        std::fs::copy(
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/python/shapes.py"),
+            &shapes,
+        )
+        .unwrap();
+        let cycle = temp_dir.path().join("cycle.py");
+        std::fs::write(
+            &cycle,
+            "class Ping(Pong):\n    pass\n\n\nclass Pong(Ping):\n    pass\n",
+        )
+        .unwrap();
+        let settings = Arc::new(Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        });
+        let mut indexer = SimpleIndexer::with_settings(settings);
+        indexer.index_file(&shapes).unwrap();
+        indexer.index_file(&cycle).unwrap();
+
+        fn render(nodes: &[TypeHierarchyNode]) -> Vec<String> {
+            nodes
+                .iter()
+                .map(|node| {
+                    let children = render(&node.types);
+                    let repeated = if node.repeated { "*" } else { "" };
+                    if children.is_empty() {
+                        format!("{}{repeated}", node.symbol.name)
+                    } else {
+                        format!("{}[{}]", node.symbol.name, children.join(","))
+                    }
+                })
+                .collect()
+        }
+
+        let cube = indexer
+            .find_type_hierarchy("Cube", None, HierarchyDirection::Ancestors, 5)
+            .unwrap();
+        assert_eq!(
+            render(&cube.ancestors.unwrap()),
+            vec!["Square[Shape,Named]"]
+        );
+        assert!(cube.descendants.is_none());
+        assert!(cube.location.contains("shapes.py:28"));
+
+        let shape = indexer
+            .find_type_hierarchy("Shape", None, HierarchyDirection::Both, 5)
+            .unwrap();
+        assert_eq!(render(&shape.ancestors.unwrap()), Vec::<String>::new());
+        let descendants = shape.descendants.unwrap();
+        assert_eq!(render(&descendants), vec!["Square[Cube]"]);
+        assert_eq!(descendants[0].relation, RelationKind::Extends);
+
+        let shallow = indexer
+            .find_type_hierarchy("Shape", None, HierarchyDirection::Descendants, 1)
+            .unwrap();
+        assert_eq!(render(&shallow.descendants.unwrap()), vec!["Square"]);
+
+        // The cycle ends where it comes back to the root
+        let ping = indexer
+            .find_type_hierarchy("Ping", None, HierarchyDirection::Ancestors, 10)
+            .unwrap();
+        assert_eq!(render(&ping.ancestors.unwrap()), vec!["Pong[Ping*]"]);
+
+        assert!(
+            indexer
+                .find_type_hierarchy("Missing", None, HierarchyDirection::Both, 3)
+                .is_err()
+        );
+    }
+
+    /// Peak resident set size of this process in KiB
+    #[cfg(target_os = "linux")]
+    fn peak_rss_kib() -> u64 {
+        std::fs::read_to_string("/proc/self/status")
+            .unwrap()
+            .lines()
+            .find_map(|line| line.strip_prefix("VmHWM:"))
+            .and_then(|value| value.trim().trim_end_matches("kB").trim().parse().ok())
+            .unwrap()
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_index_very_large_single_file_within_memory_bound() {
+        use std::fmt::Write as _;
+
+        // Set in the child process to the file it should index
+        const PROBE_FILE_ENV: &str = "CODANNA_LARGE_FILE_PROBE";
+        const TARGET_BYTES: usize = 50 * 1024 * 1024;
+        // Peak growth allowed per byte of source: about 10 for one pass's
+        // syntax tree, 6 for the symbols and 3 for the relationship
+        // candidates held until they are stored, plus headroom. Another
+        // copy of the source or of each symbol's text would exceed it
+        const MAX_GROWTH_PER_BYTE: u64 = 24;
+
+        let Some(path) = std::env::var_os(PROBE_FILE_ENV).map(PathBuf::from) else {
+            // VmHWM is per process: measure in a fresh one, so neither the
+            // generated source nor tests on other threads count toward it
+            let temp_dir = TempDir::new().unwrap();
+            let mut source = String::with_capacity(TARGET_BYTES + 256);
+            let mut functions = 0;
+            while source.len() < TARGET_BYTES {
+                writeln!(
+                    source,
+                    "/// Adds {functions} to the input\npub fn add_{functions}(x: u64) -> u64 {{\n    let y = x.wrapping_add({functions});\n    y\n}}\n"
+                )
+                .unwrap();
+                functions += 1;
+            }
+            let path = temp_dir.path().join("huge.rs");
+            std::fs::write(&path, &source).unwrap();
+            drop(source);
+
+            let output = std::process::Command::new(std::env::current_exe().unwrap())
+                .args([
+                    "--exact",
+                    "indexing::simple::tests::test_index_very_large_single_file_within_memory_bound",
+                    "--nocapture",
+                ])
+                .env(PROBE_FILE_ENV, &path)
+                .output()
+                .unwrap();
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            assert!(
+                output.status.success() && stdout.contains("1 passed"),
+                "memory probe failed:\n{stdout}\n{}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+            return;
+        };
+
+        let workspace = path.parent().unwrap();
+        let mut settings = Settings {
+            workspace_root: Some(workspace.to_path_buf()),
+            index_path: workspace.join("index"),
+            ..Settings::default()
+        };
+        // A slow machine must not turn the measurement into a parse timeout
+        settings.indexing.parse_timeout_ms = 0;
+        let mut indexer = SimpleIndexer::with_settings(Arc::new(settings));
+
+        let before = peak_rss_kib();
+        let result = indexer.index_file_no_resolve(&path).unwrap();
+        let growth = peak_rss_kib().saturating_sub(before);
+
+        assert!(matches!(result, crate::IndexingResult::Indexed(_)));
+        // The whole file was parsed: its last function was indexed
+        let source = std::fs::read_to_string(&path).unwrap();
+        let last = source.rsplit("pub fn ").next().unwrap();
+        let last = &last[..last.find('(').unwrap()];
+        assert_eq!(indexer.find_symbols_by_name(last, None).len(), 1);
+        let source_kib = source.len() as u64 / 1024;
+        assert!(
+            growth < MAX_GROWTH_PER_BYTE * source_kib,
+            "indexing a {source_kib} KiB file grew peak RSS by {growth} KiB"
+        );
+    }
+
+    #[test]
+    fn test_embedding_threads_follow_parallel_threads() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut settings = Settings {
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        };
+        settings.indexing.parallel_threads = 3;
+        let indexer = SimpleIndexer::with_settings(Arc::new(settings.clone()));
+        assert_eq!(indexer.embedding_threads(), Some(3));
+
+        settings.indexing.parallel_threads = 0;
+        settings.index_path = temp_dir.path().join("index-all-cores");
+        let indexer = SimpleIndexer::with_settings(Arc::new(settings));
+        assert_eq!(indexer.embedding_threads(), None);
+    }
+
+    #[test]
+    fn test_remove_file_by_id_purges_symbols() {
+        use std::fs;
+
+        let temp_dir = TempDir::new().unwrap();
+        let kept_path = temp_dir.path().join("kept.rs");
+        let gone_path = temp_dir.path().join("gone.rs");
+        fs::write(&kept_path, "pub fn kept_helper() {}\n").unwrap();
+        fs::write(
+            &gone_path,
+            "pub fn gone_helper() {}\npub fn gone_caller() { gone_helper(); }\n",
+        )
+        .unwrap();
+
+        let settings = Arc::new(Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        });
+        let mut indexer = SimpleIndexer::with_settings(settings);
+        indexer.index_file(&kept_path).unwrap();
+        let gone_id = match indexer.index_file(&gone_path).unwrap() {
+            crate::IndexingResult::Indexed(file_id) => file_id,
+            crate::IndexingResult::Cached(_) => panic!("new file should be indexed"),
+        };
+        assert!(
+            !indexer
+                .search(
+                    "gone_helper",
+                    10,
+                    None,
+                    None,
+                    None,
+                    &SearchFilters::default()
+                )
+                .unwrap()
+                .is_empty()
+        );
+        assert!(indexer.relationship_count() > 0);
+
+        fs::remove_file(&gone_path).unwrap();
+        assert_eq!(indexer.remove_file_by_id(gone_id).unwrap(), 2);
+
+        assert!(
+            indexer
+                .search(
+                    "gone_helper",
+                    10,
+                    None,
+                    None,
+                    None,
+                    &SearchFilters::default()
+                )
+                .unwrap()
+                .is_empty()
+        );
+        assert!(indexer.find_symbols_by_name("gone_caller", None).is_empty());
+        assert_eq!(indexer.relationship_count(), 0);
+        assert_eq!(indexer.find_symbols_by_name("kept_helper", None).len(), 1);
+        assert!(matches!(
+            indexer.remove_file_by_id(gone_id),
+            Err(IndexError::FileNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_index_without_relationships() {
+        use std::fs;
+
+        let temp_dir = TempDir::new().unwrap();
+        let lib_path = temp_dir.path().join("lib.rs");
+        fs::write(&lib_path, "pub fn leaf() {}\npub fn top() { leaf(); }\n").unwrap();
+
+        let mut settings = Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        };
+        settings.indexing.resolve_relationships = false;
+        let mut indexer = SimpleIndexer::with_settings(Arc::new(settings));
+        assert!(indexer.relationships_indexed());
+
+        indexer.index_file(&lib_path).unwrap();
+
+        let top = indexer.find_symbols_by_name("top", None)[0].id;
+        assert!(indexer.get_called_functions(top).is_empty());
+        assert!(!indexer.relationships_indexed());
+        assert!(indexer.ensure_relationships_indexed().is_err());
+    }
+
+    #[test]
+    fn test_search_semantic_fallback_requires_semantic_search() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings = Arc::new(Settings {
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        });
+        let indexer = SimpleIndexer::with_settings(settings);
+
+        assert!(!indexer.has_semantic_search());
+        assert!(
+            indexer
+                .search_semantic_fallback(
+                    "parse config",
+                    5,
+                    None,
+                    None,
+                    None,
+                    &SearchFilters::default()
+                )
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_find_occurrences() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+        let lib_path = src_dir.join("lib.rs");
+        fs::write(
+            &lib_path,
+            "pub fn handler() {}\n\npub fn dispatch() {\n    call(\"handler\");\n}\n",
+        )
+        .unwrap();
+
+        let mut settings = Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        };
+
+        // Disabled by default
+        let indexer = SimpleIndexer::with_settings(Arc::new(settings.clone()));
+        assert!(!indexer.has_occurrences());
+        assert!(indexer.find_occurrences("handler", 10).is_err());
+        drop(indexer);
+
+        settings.indexing.index_occurrences = true;
+        let settings = Arc::new(settings);
+        let mut indexer = SimpleIndexer::with_settings(settings.clone());
+        indexer.index_file_no_resolve(&lib_path).unwrap();
+
+        let found = indexer.find_occurrences("handler", 10).unwrap();
+        let positions: Vec<_> = found.iter().map(|o| (o.line, o.column)).collect();
+        assert_eq!(positions, vec![(1, 8), (4, 11)]);
+        assert_eq!(found[0].file_path, "src/lib.rs");
+        assert_eq!(indexer.find_occurrences("handler", 1).unwrap().len(), 1);
+        drop(indexer);
+
+        // Persisted alongside the index
+        let mut indexer = SimpleIndexer::with_settings(settings);
+        assert_eq!(indexer.find_occurrences("handler", 10).unwrap().len(), 2);
+
+        indexer.remove_file("src/lib.rs").unwrap();
+        assert!(indexer.find_occurrences("handler", 10).unwrap().is_empty());
+    }
+
+    // Test import-based resolution - should now work with our fixes
+    #[test]
+    fn test_import_based_relationship_resolution() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        // Create temporary directory
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path();
+
+        // Create test files
+        let src_dir = project_root.join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+
+        // config.rs with a struct
+        let config_path = src_dir.join("config.rs");
+        fs::write(
+            &config_path,
+            r#"
+pub struct Config {
+    value: String,
+}
+
+impl Config {
+    pub fn new() -> Self {
+        Config { value: String::new() }
+    }
+}
+"#,
+        )
+        .unwrap();
+
+        // another.rs with a different struct that also has new()
+        let another_path = src_dir.join("another.rs");
+        fs::write(
+            &another_path,
+            r#"
+pub struct Another {
+    data: i32,
+}
+
+impl Another {
+    pub fn new() -> Self {
+        Another { data: 0 }
+    }
+}
+"#,
+        )
+        .unwrap();
+
+        // main.rs that imports only Config - using a direct function instead
+        let main_path = src_dir.join("main.rs");
+        fs::write(
+            &main_path,
+            r#"
+use crate::config::create_config;
+
+fn main() {
+    let c = create_config();  // Should link to config::create_config
+}
+"#,
+        )
+        .unwrap();
+
+        // Update config.rs to have a function
+        fs::write(
+            &config_path,
+            r#"
+pub fn create_config() -> Config {
+    Config { value: String::new() }
+}
+
+pub struct Config {
+    value: String,
+}
+"#,
+        )
+        .unwrap();
+
+        // Update another.rs to also have a create function
+        fs::write(
+            &another_path,
+            r#"
+pub fn create_config() -> Another {
+    Another { data: 0 }
+}
+
+pub struct Another {
+    data: i32,
+}
+"#,
+        )
+        .unwrap();
+
+        // Create indexer with debug enabled
+        let settings = Arc::new(Settings {
+            workspace_root: Some(project_root.to_path_buf()),
+            index_path: PathBuf::from(".test_import_resolution"),
+            debug: true,
+            ..Settings::default()
+        });
+
+        let mut indexer = SimpleIndexer::with_settings(settings);
+
+        // Index files
+        // Use index_file_no_resolve to batch indexing without resolving relationships
+        indexer.index_file_no_resolve(&config_path).unwrap();
+        indexer.index_file_no_resolve(&another_path).unwrap();
+        indexer.index_file_no_resolve(&main_path).unwrap();
+
+        // Debug: Check unresolved relationships before resolution
+        eprintln!(
+            "Unresolved relationships before resolution: {:?}",
+            indexer.unresolved_relationships
+        );
+
+        // Now resolve all relationships after all files are indexed
+        indexer.resolve_cross_file_relationships().unwrap();
+
+        // Verify correct resolution
+        let main_symbols = indexer
+            .document_index
+            .find_symbols_by_name("main", None)
+            .unwrap();
+        assert_eq!(main_symbols.len(), 1);
+
+        // Get relationships from main
+        let main_id = main_symbols[0].id;
+        let relationships = indexer
+            .document_index
+            .get_relationships_from(main_id, RelationKind::Calls)
+            .unwrap();
+
+        eprintln!("Relationships from main: {relationships:?}");
+
+        // The new function call tracking from PR #17 creates duplicate relationships:
+        // 1. One from syntactic analysis (parser sees the call in AST)
+        // 2. One from semantic analysis (import resolution)
+        // Both are correct but redundant. We should have exactly one unique target.
+
+        // Deduplicate by target_id to handle this
+        let unique_targets: std::collections::HashSet<_> = relationships
+            .iter()
+            .map(|(_, target_id, _)| *target_id)
+            .collect();
+
+        assert_eq!(
+            unique_targets.len(),
+            1,
+            "main should call exactly one unique create_config function (found {} relationships to {} unique targets)",
+            relationships.len(),
+            unique_targets.len()
+        );
+
+        // Verify it's config::create_config, not another::create_config
+        let target_id = unique_targets.iter().next().unwrap();
+        let target_symbol = indexer
+            .document_index
+            .find_symbol_by_id(*target_id)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(target_symbol.name.as_ref(), "create_config");
+        assert_eq!(
+            target_symbol.module_path.as_ref().map(|s| s.as_ref()),
+            Some("crate::config::create_config")
+        );
+    }
+
+    #[test]
+    fn test_module_proximity() {
+        // Same module
+        assert_eq!(
+            SimpleIndexer::module_proximity(Some("crate::module_a"), Some("crate::module_a")),
+            0
+        );
+
+        // Parent/child
+        assert_eq!(
+            SimpleIndexer::module_proximity(
+                Some("crate::module_a"),
+                Some("crate::module_a::submodule")
+            ),
+            1
+        );
+        assert_eq!(
+            SimpleIndexer::module_proximity(
+                Some("crate::module_a::submodule"),
+                Some("crate::module_a")
+            ),
+            1
+        );
+
+        // Siblings
+        assert_eq!(
+            SimpleIndexer::module_proximity(Some("crate::module_a"), Some("crate::module_b")),
+            2
+        );
+        assert_eq!(
+            SimpleIndexer::module_proximity(
+                Some("crate::storage::memory"),
+                Some("crate::storage::tantivy")
+            ),
+            2
+        );
+
+        // Distant
+        assert_eq!(
+            SimpleIndexer::module_proximity(
+                Some("crate::module_a::sub"),
+                Some("crate::module_b::other")
+            ),
+            3
+        );
+
+        // Missing module info
+        assert_eq!(
+            SimpleIndexer::module_proximity(None, Some("crate::module_a")),
+            4
+        );
+        assert_eq!(
+            SimpleIndexer::module_proximity(Some("crate::module_a"), None),
+            4
+        );
+        assert_eq!(SimpleIndexer::module_proximity(None, None), 4);
+    }
+
+    #[test]
+    fn test_is_compatible_relationship_calls() {
+        // Valid call relationships - executable code calling executable code
+        assert!(SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Function,
+            SymbolKind::Function,
+            RelationKind::Calls
+        ));
+        assert!(SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Method,
+            SymbolKind::Function,
+            RelationKind::Calls
+        ));
+        assert!(SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Function,
+            SymbolKind::Method,
+            RelationKind::Calls
+        ));
+        assert!(SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Macro,
+            SymbolKind::Function,
+            RelationKind::Calls
+        ));
+
+        // Constants and Variables can hold functions in dynamic languages
+        // This supports patterns like: const actions = { submit: () => {...} }
+        assert!(SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Constant,
+            SymbolKind::Function,
+            RelationKind::Calls
+        ));
+        assert!(SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Variable,
+            SymbolKind::Function,
+            RelationKind::Calls
+        ));
+
+        // Invalid call relationships - non-executable code
+        assert!(!SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Struct,
+            SymbolKind::Function,
+            RelationKind::Calls
+        ));
+        assert!(!SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Trait,
+            SymbolKind::Method,
+            RelationKind::Calls
+        ));
+        assert!(!SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Function,
+            SymbolKind::Struct,
+            RelationKind::Calls
+        ));
+    }
+
+    #[test]
+    fn test_is_compatible_relationship_implements() {
+        // Valid implements relationships - types implementing interfaces
+        assert!(SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Struct,
+            SymbolKind::Trait,
+            RelationKind::Implements
+        ));
+        assert!(SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Enum,
+            SymbolKind::Trait,
+            RelationKind::Implements
+        ));
+        assert!(SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Class,
+            SymbolKind::Interface,
+            RelationKind::Implements
+        ));
+        assert!(SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Class,
+            SymbolKind::Trait,
+            RelationKind::Implements
+        ));
+        // Methods of a trait impl block
+        assert!(SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Method,
+            SymbolKind::Trait,
+            RelationKind::Implements
+        ));
+
+        // Invalid implements relationships
+        assert!(!SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Function,
+            SymbolKind::Trait,
+            RelationKind::Implements
+        ));
+        assert!(!SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Struct,
+            SymbolKind::Function,
+            RelationKind::Implements
+        ));
+        assert!(!SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Trait,
+            SymbolKind::Struct,
+            RelationKind::Implements
+        ));
+    }
+
+    #[test]
+    fn test_is_compatible_relationship_uses() {
+        // Valid uses relationships - language agnostic
+        assert!(SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Function,
+            SymbolKind::Struct,
+            RelationKind::Uses
+        ));
+        assert!(SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Method,
+            SymbolKind::Enum,
+            RelationKind::Uses
+        ));
+        assert!(SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Class,
+            SymbolKind::Interface,
+            RelationKind::Uses
+        ));
+        assert!(SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Module,
+            SymbolKind::TypeAlias,
+            RelationKind::Uses
+        ));
+        assert!(SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Function,
+            SymbolKind::Constant,
+            RelationKind::Uses
+        ));
+        assert!(SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Method,
+            SymbolKind::Variable,
+            RelationKind::Uses
+        ));
+        assert!(SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Function,
+            SymbolKind::EnumVariant,
+            RelationKind::Uses
+        ));
+
+        // Invalid uses relationships - what can't use things
+        assert!(!SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Constant,
+            SymbolKind::Struct,
+            RelationKind::Uses
+        ));
+        assert!(!SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Variable,
+            SymbolKind::Class,
+            RelationKind::Uses
+        ));
+        assert!(!SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Field,
+            SymbolKind::Function,
+            RelationKind::Uses
+        ));
     }
 
-    /// Get the path for the symbol cache file
-    fn get_cache_path(&self) -> PathBuf {
-        let index_base = if let Some(ref workspace_root) = self.settings.workspace_root {
-            workspace_root.join(&self.settings.index_path)
-        } else {
-            self.settings.index_path.clone()
-        };
+    #[test]
+    fn test_is_compatible_relationship_defines() {
+        // Valid defines relationships - containers defining members
+        assert!(SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Trait,
+            SymbolKind::Method,
+            RelationKind::Defines
+        ));
+        assert!(SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Module,
+            SymbolKind::Function,
+            RelationKind::Defines
+        ));
+        assert!(SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Struct,
+            SymbolKind::Field,
+            RelationKind::Defines
+        ));
+        assert!(SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Class,
+            SymbolKind::Method,
+            RelationKind::Defines
+        ));
+        assert!(SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Interface,
+            SymbolKind::Method,
+            RelationKind::Defines
+        ));
+        assert!(SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Enum,
+            SymbolKind::Constant,
+            RelationKind::Defines
+        ));
+        assert!(SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Enum,
+            SymbolKind::EnumVariant,
+            RelationKind::Defines
+        ));
 
-        index_base.join("symbol_cache.bin")
+        // Invalid defines relationships - non-containers
+        assert!(!SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Function,
+            SymbolKind::Method,
+            RelationKind::Defines
+        ));
+        assert!(!SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Method,
+            SymbolKind::Function,
+            RelationKind::Defines
+        ));
+        assert!(!SimpleIndexer::is_compatible_relationship(
+            SymbolKind::Variable,
+            SymbolKind::Field,
+            RelationKind::Defines
+        ));
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs;
-    use std::path::PathBuf;
-    use tempfile::TempDir;
 
-    use crate::types::SymbolCounter;
-    use crate::{FileId, RelationKind, Symbol, SymbolKind, Visibility};
+    // ===== Stage 3 Baseline Tests =====
+    // These tests capture the CURRENT behavior of configure_symbol
+    // to ensure refactoring doesn't change functionality
 
     #[test]
-    fn test_indexer_skips_child_path_when_parent_tracked() {
-        let temp_dir = TempDir::new().unwrap();
-        let parent = temp_dir.path().join("parent");
-        let child = parent.join("child");
-        fs::create_dir_all(&child).unwrap();
+    fn test_configure_symbol_baseline_rust() {
+        use crate::parsing::RustBehavior;
+        use tempfile::TempDir;
 
-        let settings = Arc::new(Settings {
-            index_path: temp_dir.path().join("index"),
+        // Create temp directory for test
+        let temp_dir = TempDir::new().unwrap();
+        let settings = Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
             ..Settings::default()
-        });
-        let mut indexer = SimpleIndexer::with_settings(settings);
+        };
 
-        indexer.add_indexed_path(&parent).unwrap();
-        let tracked_after_parent = indexer.get_indexed_paths().len();
+        let indexer = SimpleIndexer::with_settings(Arc::new(settings));
+        let behavior = RustBehavior::new();
+        let mut symbol_counter = SymbolCounter::new();
 
-        // Adding child should be a no-op because parent already covers it
-        indexer.add_indexed_path(&child).unwrap();
+        // Test case 1: Rust with public function
+        let mut symbol = Symbol {
+            id: symbol_counter.next_id(),
+            name: "test_function".into(),
+            kind: SymbolKind::Function,
+            signature: Some("pub fn test_function() -> Result<()>".into()),
+            module_path: None,
+            file_id: FileId(1),
+            range: crate::Range::new(0, 10, 0, 20),
+            file_path: "<unknown>".into(),
+            visibility: Visibility::Private,
+            doc_comment: None,
+            scope_context: None,
+            parent: None,
+            attributes: Vec::new(),
+            language_id: None,
+        };
+
+        let module_path = Some("crate::module".to_string());
+        indexer.configure_symbol(&mut symbol, &module_path, &behavior);
+
+        // CURRENT BEHAVIOR: Rust adds symbol name to module path
         assert_eq!(
-            indexer.get_indexed_paths().len(),
-            tracked_after_parent,
-            "child directory should not be tracked when parent already exists"
+            symbol.module_path.as_deref(),
+            Some("crate::module::test_function")
         );
+
+        // CURRENT BEHAVIOR: Updates visibility to Public because signature contains "pub "
+        assert_eq!(symbol.visibility, Visibility::Public);
     }
 
     #[test]
-    fn test_indexer_replaces_children_when_parent_added() {
-        let temp_dir = TempDir::new().unwrap();
-        let parent = temp_dir.path().join("parent");
-        let child = parent.join("child");
-        fs::create_dir_all(&child).unwrap();
+    fn test_configure_symbol_baseline_python() {
+        use crate::parsing::PythonBehavior;
+        use tempfile::TempDir;
 
-        let settings = Arc::new(Settings {
-            index_path: temp_dir.path().join("index"),
+        // Create temp directory for test
+        let temp_dir = TempDir::new().unwrap();
+        let settings = Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
             ..Settings::default()
-        });
-        let mut indexer = SimpleIndexer::with_settings(settings);
+        };
 
-        indexer.add_indexed_path(&child).unwrap();
-        assert_eq!(indexer.get_indexed_paths().len(), 1);
+        let indexer = SimpleIndexer::with_settings(Arc::new(settings));
+        let behavior = PythonBehavior::new();
+        let mut symbol_counter = SymbolCounter::new();
 
-        indexer.add_indexed_path(&parent).unwrap();
-        assert_eq!(indexer.get_indexed_paths().len(), 1);
+        let mut symbol = Symbol {
+            id: symbol_counter.next_id(),
+            name: "test_function".into(),
+            kind: SymbolKind::Function,
+            signature: Some("def test_function():".into()),
+            module_path: None,
+            file_id: FileId(1),
+            range: crate::Range::new(0, 10, 0, 20),
+            file_path: "<unknown>".into(),
+            visibility: Visibility::Public,
+            doc_comment: None,
+            scope_context: None,
+            parent: None,
+            attributes: Vec::new(),
+            language_id: None,
+        };
 
-        let stored_paths: Vec<_> = indexer.get_indexed_paths().iter().cloned().collect();
-        assert_eq!(stored_paths[0], parent.canonicalize().unwrap());
+        let module_path = Some("test_module".to_string());
+        indexer.configure_symbol(&mut symbol, &module_path, &behavior);
+
+        // Python doesn't add symbol name to module path
+        assert_eq!(symbol.module_path.as_deref(), Some("test_module"));
+
+        // Python doesn't have visibility parsing in configure_symbol
+        assert_eq!(symbol.visibility, Visibility::Public);
     }
 
     #[test]
-    fn test_trait_implementations_resolution() {
-        // Test the relationship resolution bug directly by creating symbols manually
+    fn test_configure_symbol_php_baseline() {
+        use crate::parsing::PhpBehavior;
         use tempfile::TempDir;
 
-        // Create a temporary directory for the test index
+        // Create temp directory for test
         let temp_dir = TempDir::new().unwrap();
-        let index_path = temp_dir.path().join("test_index");
-
         let settings = Settings {
-            debug: true,
-            index_path,
-            ..Default::default()
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            ..Settings::default()
         };
-        let mut indexer = SimpleIndexer::with_settings(Arc::new(settings));
 
-        let file_id = FileId(1);
-        let file_path = "test.rs";
+        let indexer = SimpleIndexer::with_settings(Arc::new(settings));
+        let behavior = PhpBehavior::new();
+        let mut symbol_counter = SymbolCounter::new();
 
-        // Set up language behavior for Rust
-        let language_id = LanguageId::new("rust");
-        indexer.file_languages.insert(file_id, language_id);
+        let mut symbol = Symbol {
+            id: symbol_counter.next_id(),
+            name: "testFunction".into(),
+            kind: SymbolKind::Function,
+            signature: Some("public function testFunction()".into()),
+            module_path: None,
+            file_id: FileId(1),
+            range: crate::Range::new(0, 10, 0, 20),
+            file_path: "<unknown>".into(),
+            visibility: Visibility::Public,
+            doc_comment: None,
+            scope_context: None,
+            parent: None,
+            attributes: Vec::new(),
+            language_id: None,
+        };
 
-        // Also need to store the behavior for resolution to work
-        let behavior = indexer
-            .parser_factory
-            .create_behavior_from_registry(language_id);
-        indexer.file_behaviors.insert(file_id, behavior);
+        let module_path = Some("App\\Utils".to_string());
+        indexer.configure_symbol(&mut symbol, &module_path, &behavior);
 
-        // Start transaction to get proper symbol IDs
-        indexer.start_tantivy_batch().unwrap();
+        // PHP doesn't add symbol name to module path
+        assert_eq!(symbol.module_path.as_deref(), Some("App\\Utils"));
 
-        // Get proper symbol IDs using the counter
-        let mut counter = indexer.get_next_symbol_counter().unwrap();
-        let trait_id = counter.next_id();
-        let struct_id = counter.next_id();
+        // PHP doesn't have visibility parsing in configure_symbol
+        assert_eq!(symbol.visibility, Visibility::Public);
+    }
+
+    #[test]
+    fn test_configure_symbol_different_languages() {
+        use crate::parsing::{PhpBehavior, PythonBehavior, RustBehavior};
+
+        use tempfile::TempDir;
+
+        // Create temp directory for test
+        let temp_dir = TempDir::new().unwrap();
+        let settings = Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            ..Settings::default()
+        };
+
+        let indexer = SimpleIndexer::with_settings(Arc::new(settings));
+        let rust_behavior = RustBehavior::new();
+        let python_behavior = PythonBehavior::new();
+        let php_behavior = PhpBehavior::new();
+        let module_path = Some("test_module".to_string());
+
+        // Use SymbolCounter for proper ID generation
+        let mut symbol_counter = SymbolCounter::new();
 
-        // Create symbols with proper IDs
-        let trait_symbol = Symbol {
-            id: trait_id,
-            name: "MyTrait".into(),
-            kind: SymbolKind::Trait,
-            range: crate::Range::new(0, 0, 0, 0),
-            file_id,
+        // Test symbols with language-appropriate signatures
+        let mut rust_symbol = Symbol {
+            id: symbol_counter.next_id(),
+            name: "test".into(),
+            kind: SymbolKind::Function,
+            signature: Some("pub fn test()".into()),
+            module_path: None,
+            file_id: FileId(1),
+            range: crate::Range::new(0, 10, 0, 20),
             file_path: "<unknown>".into(),
-            visibility: Visibility::Public,
+            visibility: Visibility::Private,
             doc_comment: None,
-            signature: None,
-            module_path: Some("test".into()),
             scope_context: None,
+            parent: None,
+            attributes: Vec::new(),
             language_id: None,
         };
 
-        let struct_symbol = Symbol {
-            id: struct_id,
-            name: "MyStruct".into(),
-            kind: SymbolKind::Struct,
-            range: crate::Range::new(1, 0, 1, 0),
-            file_id,
+        let mut python_symbol = Symbol {
+            id: symbol_counter.next_id(),
+            name: "test".into(),
+            kind: SymbolKind::Function,
+            signature: Some("def test():".into()), // Python signature
+            module_path: None,
+            file_id: FileId(2),
+            range: crate::Range::new(0, 10, 0, 20),
             file_path: "<unknown>".into(),
-            visibility: Visibility::Public,
+            visibility: Visibility::Private,
             doc_comment: None,
-            signature: None,
-            module_path: Some("test".into()),
             scope_context: None,
+            parent: None,
+            attributes: Vec::new(),
             language_id: None,
         };
 
-        // Store symbols
-        indexer
-            .store_symbol(trait_symbol.clone(), file_path)
-            .unwrap();
-        indexer
-            .store_symbol(struct_symbol.clone(), file_path)
-            .unwrap();
-
-        // Register the implementation relationship
-        indexer
-            .add_relationships_by_name(
-                None,
-                "MyStruct",
-                "MyTrait",
-                file_id,
-                RelationKind::Implements,
-                None,
-            )
-            .unwrap();
-
-        // Commit
-        indexer.commit_tantivy_batch().unwrap();
-
-        // Debug: Check unresolved relationships
-        eprintln!(
-            "Unresolved relationships before resolution: {:?}",
-            indexer.unresolved_relationships
-        );
-        assert_eq!(indexer.unresolved_relationships.len(), 1);
-        let unresolved = &indexer.unresolved_relationships[0];
-        assert_eq!(unresolved.from_name.as_ref(), "MyStruct");
-        assert_eq!(unresolved.to_name.as_ref(), "MyTrait");
-
-        // Resolve relationships - THIS IS WHERE THE BUG HAPPENS
-        // First, let's see what symbols are in the index
-        if indexer.settings.debug {
-            let all_symbols = indexer.document_index.get_all_symbols(100).unwrap();
-            for sym in &all_symbols {
-                debug_print!(
-                    indexer,
-                    "Symbol in index - ID {:?}: name='{}', kind={:?}",
-                    sym.id,
-                    sym.name,
-                    sym.kind
-                );
-            }
-        }
-
-        indexer.resolve_cross_file_relationships().unwrap();
-
-        // Find the trait
-        let found_trait = indexer
-            .find_symbols_by_name("MyTrait", None)
-            .into_iter()
-            .find(|s| s.kind == SymbolKind::Trait)
-            .expect("Should find MyTrait");
-
-        eprintln!("Created symbols: trait_id={trait_id:?}, struct_id={struct_id:?}");
-        eprintln!("Found trait with ID: {:?}", found_trait.id);
-
-        // THIS SHOULD WORK: get_implementations should return MyStruct
-        let implementations = indexer.get_implementations(found_trait.id);
+        let mut php_symbol = Symbol {
+            id: symbol_counter.next_id(),
+            name: "test".into(),
+            kind: SymbolKind::Function,
+            signature: Some("public function test()".into()), // PHP signature
+            module_path: None,
+            file_id: FileId(3),
+            range: crate::Range::new(0, 10, 0, 20),
+            file_path: "<unknown>".into(),
+            visibility: Visibility::Private,
+            doc_comment: None,
+            scope_context: None,
+            parent: None,
+            attributes: Vec::new(),
+            language_id: None,
+        };
 
-        eprintln!("Found {} implementations", implementations.len());
-        for impl_sym in &implementations {
-            eprintln!("  Implementation: {} ({:?})", impl_sym.name, impl_sym.kind);
-        }
+        // Configure each symbol with its behavior
+        indexer.configure_symbol(&mut rust_symbol, &module_path, &rust_behavior);
+        indexer.configure_symbol(&mut python_symbol, &module_path, &python_behavior);
+        indexer.configure_symbol(&mut php_symbol, &module_path, &php_behavior);
 
-        // This assertion will fail, exposing the bug
+        // Verify different behaviors
         assert_eq!(
-            implementations.len(),
-            1,
-            "Should find MyStruct implements MyTrait"
+            rust_symbol.module_path.as_deref(),
+            Some("test_module::test")
         );
-        if !implementations.is_empty() {
-            assert_eq!(implementations[0].name.as_ref(), "MyStruct");
-        }
+        assert_eq!(python_symbol.module_path.as_deref(), Some("test_module"));
+        assert_eq!(php_symbol.module_path.as_deref(), Some("test_module"));
+
+        // Visibility parsed according to each language's rules
+        assert_eq!(rust_symbol.visibility, Visibility::Public); // Rust: "pub " means public
+        assert_eq!(python_symbol.visibility, Visibility::Public); // Python: no underscore prefix means public
+        assert_eq!(php_symbol.visibility, Visibility::Public); // PHP: "public function" means public
     }
 
     #[test]
-    fn test_symbol_module_paths() {
+    fn test_symbols_get_language_id_during_indexing() {
+        use crate::parsing::registry::LanguageId;
         use std::fs;
         use tempfile::TempDir;
 
-        // Create temporary directory
+        // Create a temporary directory and test files
         let temp_dir = TempDir::new().unwrap();
-        let project_root = temp_dir.path();
+        let rust_file = temp_dir.path().join("test.rs");
+        let python_file = temp_dir.path().join("test.py");
+        let ts_file = temp_dir.path().join("test.ts");
 
-        // Create src directory
-        let src_dir = project_root.join("src");
-        fs::create_dir_all(&src_dir).unwrap();
+        // Write simple test content
+        fs::write(&rust_file, "fn main() {}").unwrap();
+        fs::write(&python_file, "def main(): pass").unwrap();
+        fs::write(&ts_file, "function main() {}").unwrap();
 
-        // Create a simple Rust file
-        let test_file = src_dir.join("test.rs");
-        fs::write(
-            &test_file,
-            r#"
-pub fn hello() {}
-pub struct World;
-"#,
-        )
-        .unwrap();
+        // Create indexer with temp directory as root
+        let settings = Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        let mut indexer = SimpleIndexer::with_settings(Arc::new(settings));
 
-        // Create indexer with proper settings
-        let settings = Arc::new(Settings {
-            workspace_root: Some(project_root.to_path_buf()),
-            index_path: project_root.join(".test_index"),
-            ..Settings::default()
-        });
+        // Index the files
+        let rust_result = indexer
+            .index_file(&rust_file)
+            .expect("Failed to index Rust file");
+        let python_result = indexer
+            .index_file(&python_file)
+            .expect("Failed to index Python file");
+        let ts_result = indexer
+            .index_file(&ts_file)
+            .expect("Failed to index TypeScript file");
 
-        let mut indexer = SimpleIndexer::with_settings(settings);
+        // Verify files were indexed successfully
+        assert!(matches!(rust_result, crate::IndexingResult::Indexed(_)));
+        assert!(matches!(python_result, crate::IndexingResult::Indexed(_)));
+        assert!(matches!(ts_result, crate::IndexingResult::Indexed(_)));
 
-        // Index the file
-        indexer.index_file(&test_file).unwrap();
+        // Find the symbols - be more specific since multiple files have 'main'
+        // Get all symbols named 'main' and find the one from the Rust file
+        let all_main_symbols = indexer.find_symbols_by_name("main", None);
+        assert!(
+            !all_main_symbols.is_empty(),
+            "Should find 'main' symbols after indexing"
+        );
 
-        // Find symbols and check their module paths
-        let hello_symbols = indexer
-            .document_index
-            .find_symbols_by_name("hello", None)
-            .unwrap();
-        assert_eq!(hello_symbols.len(), 1);
+        // Debug: Print all main symbols found
+        println!("\n=== All 'main' symbols found ===");
+        for (i, symbol) in all_main_symbols.iter().enumerate() {
+            println!(
+                "Symbol {}: name='{}', language_id={:?}, file_id={:?}",
+                i, symbol.name, symbol.language_id, symbol.file_id
+            );
+        }
+
+        // Debug: Print file_languages HashMap
+        println!("\n=== File -> Language mapping ===");
+        for (file_id, lang_id) in &indexer.file_languages {
+            println!(
+                "FileId({:?}) -> LanguageId({:?})",
+                file_id.0,
+                lang_id.as_str()
+            );
+        }
+
+        let rust_symbol = all_main_symbols
+            .iter()
+            .find(|s| s.language_id == Some(LanguageId::new("rust")))
+            .expect("Should find a Rust 'main' symbol");
+
+        println!("\n=== Selected Rust symbol ===");
+        println!(
+            "Symbol: name='{}', language_id={:?}",
+            rust_symbol.name, rust_symbol.language_id
+        );
+
+        // Verify the symbol has the correct language_id
         assert_eq!(
-            hello_symbols[0].module_path.as_ref().map(|s| s.as_ref()),
-            Some("crate::test::hello")
+            rust_symbol.language_id,
+            Some(LanguageId::new("rust")),
+            "Symbol from Rust file should have language_id set to 'rust'"
         );
 
-        let world_symbols = indexer
-            .document_index
-            .find_symbols_by_name("World", None)
-            .unwrap();
-        assert_eq!(world_symbols.len(), 1);
+        // Test Python symbol - use 'main' which we know exists
+        let python_symbols = all_main_symbols
+            .iter()
+            .filter(|s| s.language_id == Some(LanguageId::new("python")))
+            .collect::<Vec<_>>();
+        println!("\n=== Python symbols ===");
+        for symbol in &python_symbols {
+            println!(
+                "Symbol: name='{}', language_id={:?}",
+                symbol.name, symbol.language_id
+            );
+            assert_eq!(
+                symbol.language_id,
+                Some(LanguageId::new("python")),
+                "Python symbol should have language_id set to 'python'"
+            );
+        }
+        assert!(!python_symbols.is_empty(), "Should find Python main symbol");
+
+        // Test TypeScript symbol
+        let ts_symbols = all_main_symbols
+            .iter()
+            .filter(|s| s.language_id == Some(LanguageId::new("typescript")))
+            .collect::<Vec<_>>();
+        println!("\n=== TypeScript symbols ===");
+        for symbol in &ts_symbols {
+            println!(
+                "Symbol: name='{}', language_id={:?}",
+                symbol.name, symbol.language_id
+            );
+        }
+        assert!(!ts_symbols.is_empty(), "Should find TypeScript main symbol");
+
+        // Verify that language detection is working internally
         assert_eq!(
-            world_symbols[0].module_path.as_ref().map(|s| s.as_ref()),
-            Some("crate::test::World")
+            indexer.file_languages.len(),
+            3,
+            "Should have 3 files with language mappings"
         );
     }
 
     #[test]
-    fn test_symbols_in_same_module() {
-        let mut symbol_counter = SymbolCounter::new();
+    fn test_find_symbols_with_language_filter() {
+        use std::fs;
+        use tempfile::TempDir;
 
-        let sym1 = Symbol::new(
-            symbol_counter.next_id(),
-            "test1",
-            SymbolKind::Function,
-            FileId::new(1).unwrap(),
-            crate::Range::new(0, 0, 0, 0),
-        )
-        .with_module_path("crate::module_a");
+        println!("\n=== Testing SimpleIndexer with language filtering ===");
 
-        let sym2 = Symbol::new(
-            symbol_counter.next_id(),
-            "test2",
-            SymbolKind::Function,
-            FileId::new(1).unwrap(),
-            crate::Range::new(0, 0, 0, 0),
-        )
-        .with_module_path("crate::module_a");
+        // Create a temporary directory and test files
+        let temp_dir = TempDir::new().unwrap();
+        let rust_file = temp_dir.path().join("main.rs");
+        let python_file = temp_dir.path().join("main.py");
+        let ts_file = temp_dir.path().join("main.ts");
 
-        let sym3 = Symbol::new(
-            symbol_counter.next_id(),
-            "test3",
-            SymbolKind::Function,
-            FileId::new(2).unwrap(),
-            crate::Range::new(0, 0, 0, 0),
+        // Write test content with same function name in different languages
+        fs::write(&rust_file, "fn process_data() { println!(\"Rust\"); }").unwrap();
+        fs::write(&python_file, "def process_data():\n    print('Python')").unwrap();
+        fs::write(
+            &ts_file,
+            "function process_data() { console.log('TypeScript'); }",
         )
-        .with_module_path("crate::module_b");
+        .unwrap();
 
-        let sym4 = Symbol::new(
-            symbol_counter.next_id(),
-            "test4",
-            SymbolKind::Function,
-            FileId::new(2).unwrap(),
-            crate::Range::new(0, 0, 0, 0),
-        ); // No module path
+        // Create indexer
+        let settings = Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        let mut indexer = SimpleIndexer::with_settings(Arc::new(settings));
 
-        assert!(SimpleIndexer::symbols_in_same_module(&sym1, &sym2));
-        assert!(!SimpleIndexer::symbols_in_same_module(&sym1, &sym3));
-        assert!(!SimpleIndexer::symbols_in_same_module(&sym1, &sym4));
-        assert!(!SimpleIndexer::symbols_in_same_module(&sym4, &sym4)); // Both have no module
-    }
+        // Index all files
+        indexer
+            .index_file(&rust_file)
+            .expect("Failed to index Rust file");
+        indexer
+            .index_file(&python_file)
+            .expect("Failed to index Python file");
+        indexer
+            .index_file(&ts_file)
+            .expect("Failed to index TypeScript file");
 
-    #[test]
-    fn test_is_symbol_visible_from() {
-        let mut symbol_counter = SymbolCounter::new();
+        // Test 1: Find all symbols without filter
+        let all_symbols = indexer.find_symbols_by_name("process_data", None);
+        println!(
+            "Test 1 - No filter: Found {} 'process_data' symbols",
+            all_symbols.len()
+        );
+        for symbol in &all_symbols {
+            println!(
+                "  - Language: {:?}, File: {}",
+                symbol.language_id,
+                indexer.get_file_path(symbol.file_id).unwrap_or_default()
+            );
+        }
+        assert_eq!(all_symbols.len(), 3, "Should find 3 process_data functions");
 
-        let pub_sym = Symbol::new(
-            symbol_counter.next_id(),
-            "public_fn",
-            SymbolKind::Function,
-            FileId::new(1).unwrap(),
-            crate::Range::new(0, 0, 0, 0),
-        )
-        .with_module_path("crate::module_a")
-        .with_visibility(Visibility::Public);
+        // Test 2: Filter by Rust
+        let rust_symbols = indexer.find_symbols_by_name("process_data", Some("rust"));
+        println!("Test 2 - Rust filter: Found {} symbols", rust_symbols.len());
+        assert_eq!(rust_symbols.len(), 1, "Should find 1 Rust function");
+        assert_eq!(rust_symbols[0].language_id, Some(LanguageId::new("rust")));
 
-        let priv_sym = Symbol::new(
-            symbol_counter.next_id(),
-            "private_fn",
-            SymbolKind::Function,
-            FileId::new(1).unwrap(),
-            crate::Range::new(0, 0, 0, 0),
-        )
-        .with_module_path("crate::module_a")
-        .with_visibility(Visibility::Private);
+        // Test 3: Filter by Python
+        let python_symbols = indexer.find_symbols_by_name("process_data", Some("python"));
+        println!(
+            "Test 3 - Python filter: Found {} symbols",
+            python_symbols.len()
+        );
+        assert_eq!(python_symbols.len(), 1, "Should find 1 Python function");
+        assert_eq!(
+            python_symbols[0].language_id,
+            Some(LanguageId::new("python"))
+        );
 
-        let other_module_sym = Symbol::new(
-            symbol_counter.next_id(),
-            "other_fn",
-            SymbolKind::Function,
-            FileId::new(2).unwrap(),
-            crate::Range::new(0, 0, 0, 0),
-        )
-        .with_module_path("crate::module_b");
+        // Test 4: Filter by TypeScript
+        let ts_symbols = indexer.find_symbols_by_name("process_data", Some("typescript"));
+        println!(
+            "Test 4 - TypeScript filter: Found {} symbols",
+            ts_symbols.len()
+        );
+        assert_eq!(ts_symbols.len(), 1, "Should find 1 TypeScript function");
+        assert_eq!(
+            ts_symbols[0].language_id,
+            Some(LanguageId::new("typescript"))
+        );
 
-        // Same module - both visible
-        assert!(SimpleIndexer::is_symbol_visible_from(&pub_sym, &priv_sym));
-        assert!(SimpleIndexer::is_symbol_visible_from(&priv_sym, &pub_sym));
+        // Test 5: Filter by non-existent language
+        let java_symbols = indexer.find_symbols_by_name("process_data", Some("java"));
+        println!(
+            "Test 5 - Java filter (non-existent): Found {} symbols",
+            java_symbols.len()
+        );
+        assert_eq!(java_symbols.len(), 0, "Should find no Java functions");
 
-        // Different modules - only public visible
-        assert!(SimpleIndexer::is_symbol_visible_from(
-            &pub_sym,
-            &other_module_sym
-        ));
-        assert!(!SimpleIndexer::is_symbol_visible_from(
-            &priv_sym,
-            &other_module_sym
-        ));
+        println!("=== All SimpleIndexer language filter tests passed ===\n");
     }
 
-    // Test import-based resolution - should now work with our fixes
     #[test]
-    fn test_import_based_relationship_resolution() {
+    fn test_search_with_language_filter() {
         use std::fs;
         use tempfile::TempDir;
 
-        // Create temporary directory
-        let temp_dir = TempDir::new().unwrap();
-        let project_root = temp_dir.path();
-
-        // Create test files
-        let src_dir = project_root.join("src");
-        fs::create_dir_all(&src_dir).unwrap();
-
-        // config.rs with a struct
-        let config_path = src_dir.join("config.rs");
-        fs::write(
-            &config_path,
-            r#"
-pub struct Config {
-    value: String,
-}
-
-impl Config {
-    pub fn new() -> Self {
-        Config { value: String::new() }
-    }
-}
-"#,
-        )
-        .unwrap();
-
-        // another.rs with a different struct that also has new()
-        let another_path = src_dir.join("another.rs");
-        fs::write(
-            &another_path,
-            r#"
-pub struct Another {
-    data: i32,
-}
-
-impl Another {
-    pub fn new() -> Self {
-        Another { data: 0 }
-    }
-}
-"#,
-        )
-        .unwrap();
-
-        // main.rs that imports only Config - using a direct function instead
-        let main_path = src_dir.join("main.rs");
-        fs::write(
-            &main_path,
-            r#"
-use crate::config::create_config;
+        println!("\n=== Testing SimpleIndexer search with language filtering ===");
 
-fn main() {
-    let c = create_config();  // Should link to config::create_config
-}
-"#,
-        )
-        .unwrap();
+        // Create a temporary directory and test files
+        let temp_dir = TempDir::new().unwrap();
+        let rust_file = temp_dir.path().join("parser.rs");
+        let python_file = temp_dir.path().join("parser.py");
 
-        // Update config.rs to have a function
+        // Write test content with parse-related functions
         fs::write(
-            &config_path,
+            &rust_file,
             r#"
-pub fn create_config() -> Config {
-    Config { value: String::new() }
-}
-
-pub struct Config {
-    value: String,
-}
-"#,
+            fn parse_json(input: &str) -> Result<Value, Error> {
+                // Parse JSON in Rust
+            }
+            fn parse_xml(input: &str) -> Result<Document, Error> {
+                // Parse XML in Rust
+            }
+        "#,
         )
         .unwrap();
 
-        // Update another.rs to also have a create function
         fs::write(
-            &another_path,
+            &python_file,
             r#"
-pub fn create_config() -> Another {
-    Another { data: 0 }
-}
+def parse_json(input: str) -> dict:
+    """Parse JSON in Python"""
+    pass
 
-pub struct Another {
-    data: i32,
-}
-"#,
+def parse_yaml(input: str) -> dict:
+    """Parse YAML in Python"""
+    pass
+        "#,
         )
         .unwrap();
 
-        // Create indexer with debug enabled
-        let settings = Arc::new(Settings {
-            workspace_root: Some(project_root.to_path_buf()),
-            index_path: PathBuf::from(".test_import_resolution"),
-            debug: true,
-            ..Settings::default()
-        });
-
-        let mut indexer = SimpleIndexer::with_settings(settings);
+        // Create indexer
+        let settings = Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        let mut indexer = SimpleIndexer::with_settings(Arc::new(settings));
 
         // Index files
-        // Use index_file_no_resolve to batch indexing without resolving relationships
-        indexer.index_file_no_resolve(&config_path).unwrap();
-        indexer.index_file_no_resolve(&another_path).unwrap();
-        indexer.index_file_no_resolve(&main_path).unwrap();
-
-        // Debug: Check unresolved relationships before resolution
-        eprintln!(
-            "Unresolved relationships before resolution: {:?}",
-            indexer.unresolved_relationships
-        );
-
-        // Now resolve all relationships after all files are indexed
-        indexer.resolve_cross_file_relationships().unwrap();
-
-        // Verify correct resolution
-        let main_symbols = indexer
-            .document_index
-            .find_symbols_by_name("main", None)
-            .unwrap();
-        assert_eq!(main_symbols.len(), 1);
-
-        // Get relationships from main
-        let main_id = main_symbols[0].id;
-        let relationships = indexer
-            .document_index
-            .get_relationships_from(main_id, RelationKind::Calls)
-            .unwrap();
-
-        eprintln!("Relationships from main: {relationships:?}");
-
-        // The new function call tracking from PR #17 creates duplicate relationships:
-        // 1. One from syntactic analysis (parser sees the call in AST)
-        // 2. One from semantic analysis (import resolution)
-        // Both are correct but redundant. We should have exactly one unique target.
-
-        // Deduplicate by target_id to handle this
-        let unique_targets: std::collections::HashSet<_> = relationships
-            .iter()
-            .map(|(_, target_id, _)| *target_id)
-            .collect();
-
-        assert_eq!(
-            unique_targets.len(),
-            1,
-            "main should call exactly one unique create_config function (found {} relationships to {} unique targets)",
-            relationships.len(),
-            unique_targets.len()
-        );
+        indexer
+            .index_file(&rust_file)
+            .expect("Failed to index Rust file");
+        indexer
+            .index_file(&python_file)
+            .expect("Failed to index Python file");
 
-        // Verify it's config::create_config, not another::create_config
-        let target_id = unique_targets.iter().next().unwrap();
-        let target_symbol = indexer
-            .document_index
-            .find_symbol_by_id(*target_id)
-            .unwrap()
+        // Test 1: Search without language filter
+        let all_results = indexer
+            .search("parse", 10, None, None, None, &SearchFilters::default())
             .unwrap();
-
-        assert_eq!(target_symbol.name.as_ref(), "create_config");
-        assert_eq!(
-            target_symbol.module_path.as_ref().map(|s| s.as_ref()),
-            Some("crate::config::create_config")
+        println!(
+            "Test 1 - Search 'parse' no filter: Found {} results",
+            all_results.len()
         );
-    }
-
-    #[test]
-    fn test_module_proximity() {
-        // Same module
-        assert_eq!(
-            SimpleIndexer::module_proximity(Some("crate::module_a"), Some("crate::module_a")),
-            0
+        assert!(
+            all_results.len() >= 3,
+            "Should find at least 3 parse functions"
         );
 
-        // Parent/child
-        assert_eq!(
-            SimpleIndexer::module_proximity(
-                Some("crate::module_a"),
-                Some("crate::module_a::submodule")
-            ),
-            1
-        );
-        assert_eq!(
-            SimpleIndexer::module_proximity(
-                Some("crate::module_a::submodule"),
-                Some("crate::module_a")
-            ),
-            1
+        // Test 2: Search with Rust filter
+        let rust_results = indexer
+            .search(
+                "parse",
+                10,
+                None,
+                None,
+                Some("rust"),
+                &SearchFilters::default(),
+            )
+            .unwrap();
+        println!(
+            "Test 2 - Search 'parse' Rust filter: Found {} results",
+            rust_results.len()
         );
+        for result in &rust_results {
+            println!("  - {}: {}", result.name, result.file_path);
+        }
+        assert_eq!(rust_results.len(), 2, "Should find 2 Rust parse functions");
 
-        // Siblings
-        assert_eq!(
-            SimpleIndexer::module_proximity(Some("crate::module_a"), Some("crate::module_b")),
-            2
-        );
-        assert_eq!(
-            SimpleIndexer::module_proximity(
-                Some("crate::storage::memory"),
-                Some("crate::storage::tantivy")
-            ),
-            2
+        // Test 3: Search with Python filter
+        let python_results = indexer
+            .search(
+                "parse",
+                10,
+                Some(crate::types::SymbolKind::Function),
+                None,
+                Some("python"),
+                &SearchFilters::default(),
+            )
+            .unwrap();
+        println!(
+            "Test 3 - Search 'parse' Python filter: Found {} results",
+            python_results.len()
         );
-
-        // Distant
+        for result in &python_results {
+            println!("  - {}: {}", result.name, result.file_path);
+        }
         assert_eq!(
-            SimpleIndexer::module_proximity(
-                Some("crate::module_a::sub"),
-                Some("crate::module_b::other")
-            ),
-            3
+            python_results.len(),
+            2,
+            "Should find 2 Python parse functions"
         );
 
-        // Missing module info
-        assert_eq!(
-            SimpleIndexer::module_proximity(None, Some("crate::module_a")),
-            4
-        );
-        assert_eq!(
-            SimpleIndexer::module_proximity(Some("crate::module_a"), None),
-            4
+        // Test 4: Search with non-existent language
+        let java_results = indexer
+            .search(
+                "parse",
+                10,
+                None,
+                None,
+                Some("java"),
+                &SearchFilters::default(),
+            )
+            .unwrap();
+        println!(
+            "Test 4 - Search 'parse' Java filter: Found {} results",
+            java_results.len()
         );
-        assert_eq!(SimpleIndexer::module_proximity(None, None), 4);
-    }
-
-    #[test]
-    fn test_is_compatible_relationship_calls() {
-        // Valid call relationships - executable code calling executable code
-        assert!(SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Function,
-            SymbolKind::Function,
-            RelationKind::Calls
-        ));
-        assert!(SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Method,
-            SymbolKind::Function,
-            RelationKind::Calls
-        ));
-        assert!(SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Function,
-            SymbolKind::Method,
-            RelationKind::Calls
-        ));
-        assert!(SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Macro,
-            SymbolKind::Function,
-            RelationKind::Calls
-        ));
-
-        // Constants and Variables can hold functions in dynamic languages
-        // This supports patterns like: const actions = { submit: () => {...} }
-        assert!(SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Constant,
-            SymbolKind::Function,
-            RelationKind::Calls
-        ));
-        assert!(SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Variable,
-            SymbolKind::Function,
-            RelationKind::Calls
-        ));
+        assert_eq!(java_results.len(), 0, "Should find no Java functions");
 
-        // Invalid call relationships - non-executable code
-        assert!(!SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Struct,
-            SymbolKind::Function,
-            RelationKind::Calls
-        ));
-        assert!(!SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Trait,
-            SymbolKind::Method,
-            RelationKind::Calls
-        ));
-        assert!(!SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Function,
-            SymbolKind::Struct,
-            RelationKind::Calls
-        ));
+        println!("=== All SimpleIndexer search tests passed ===\n");
     }
 
     #[test]
-    fn test_is_compatible_relationship_implements() {
-        // Valid implements relationships - types implementing interfaces
-        assert!(SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Struct,
-            SymbolKind::Trait,
-            RelationKind::Implements
-        ));
-        assert!(SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Enum,
-            SymbolKind::Trait,
-            RelationKind::Implements
-        ));
-        assert!(SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Class,
-            SymbolKind::Interface,
-            RelationKind::Implements
-        ));
-        assert!(SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Class,
-            SymbolKind::Trait,
-            RelationKind::Implements
-        ));
+    fn test_export_sqlite_replaces_previous_export() {
+        use std::fs;
+        use tempfile::TempDir;
 
-        // Invalid implements relationships
-        assert!(!SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Function,
-            SymbolKind::Trait,
-            RelationKind::Implements
-        ));
-        assert!(!SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Struct,
-            SymbolKind::Function,
-            RelationKind::Implements
-        ));
-        assert!(!SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Trait,
-            SymbolKind::Struct,
-            RelationKind::Implements
-        ));
-    }
+        let temp_dir = TempDir::new().unwrap();
+        let rust_file = temp_dir.path().join("lib.rs");
+        fs::write(&rust_file, "pub fn exported() {}\n").unwrap();
+        let settings = Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            index_path: temp_dir.path().join("index"),
+            ..Default::default()
+        };
+        let mut indexer = SimpleIndexer::with_settings(Arc::new(settings));
+        indexer.index_file(&rust_file).unwrap();
 
-    #[test]
-    fn test_is_compatible_relationship_uses() {
-        // Valid uses relationships - language agnostic
-        assert!(SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Function,
-            SymbolKind::Struct,
-            RelationKind::Uses
-        ));
-        assert!(SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Method,
-            SymbolKind::Enum,
-            RelationKind::Uses
-        ));
-        assert!(SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Class,
-            SymbolKind::Interface,
-            RelationKind::Uses
-        ));
-        assert!(SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Module,
-            SymbolKind::TypeAlias,
-            RelationKind::Uses
-        ));
-        assert!(SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Function,
-            SymbolKind::Constant,
-            RelationKind::Uses
-        ));
-        assert!(SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Method,
-            SymbolKind::Variable,
-            RelationKind::Uses
-        ));
+        let out_dir = temp_dir.path().join("out");
+        fs::create_dir_all(&out_dir).unwrap();
+        let db_path = out_dir.join("index.db");
+        fs::write(&db_path, "previous export").unwrap();
 
-        // Invalid uses relationships - what can't use things
-        assert!(!SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Constant,
-            SymbolKind::Struct,
-            RelationKind::Uses
-        ));
-        assert!(!SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Variable,
-            SymbolKind::Class,
-            RelationKind::Uses
-        ));
-        assert!(!SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Field,
-            SymbolKind::Function,
-            RelationKind::Uses
-        ));
-    }
+        let stats = indexer.export_sqlite(&db_path).unwrap();
+        assert!(stats.symbols > 0);
 
-    #[test]
-    fn test_is_compatible_relationship_defines() {
-        // Valid defines relationships - containers defining members
-        assert!(SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Trait,
-            SymbolKind::Method,
-            RelationKind::Defines
-        ));
-        assert!(SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Module,
-            SymbolKind::Function,
-            RelationKind::Defines
-        ));
-        assert!(SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Struct,
-            SymbolKind::Field,
-            RelationKind::Defines
-        ));
-        assert!(SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Class,
-            SymbolKind::Method,
-            RelationKind::Defines
-        ));
-        assert!(SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Interface,
-            SymbolKind::Method,
-            RelationKind::Defines
-        ));
-        assert!(SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Enum,
-            SymbolKind::Constant,
-            RelationKind::Defines
-        ));
+        let conn = rusqlite::Connection::open(&db_path).unwrap();
+        let name: String = conn
+            .query_row("SELECT name FROM symbols", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(name, "exported");
 
-        // Invalid defines relationships - non-containers
-        assert!(!SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Function,
-            SymbolKind::Method,
-            RelationKind::Defines
-        ));
-        assert!(!SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Method,
-            SymbolKind::Function,
-            RelationKind::Defines
-        ));
-        assert!(!SimpleIndexer::is_compatible_relationship(
-            SymbolKind::Variable,
-            SymbolKind::Field,
-            RelationKind::Defines
-        ));
+        // The temporary database was renamed over the target
+        let entries: Vec<_> = fs::read_dir(&out_dir).unwrap().collect();
+        assert_eq!(entries.len(), 1);
     }
 
-    // ===== Stage 3 Baseline Tests =====
-    // These tests capture the CURRENT behavior of configure_symbol
-    // to ensure refactoring doesn't change functionality
-
     #[test]
-    fn test_configure_symbol_baseline_rust() {
-        use crate::parsing::RustBehavior;
+    fn test_export_orders_records_by_id() {
+        use std::fs;
         use tempfile::TempDir;
 
-        // Create temp directory for test
         let temp_dir = TempDir::new().unwrap();
+        let first = temp_dir.path().join("first.rs");
+        let second = temp_dir.path().join("second.rs");
+        fs::write(&first, "pub fn alpha() { beta(); }\npub fn beta() {}\n").unwrap();
+        fs::write(&second, "pub fn gamma() { delta(); }\npub fn delta() {}\n").unwrap();
         let settings = Settings {
             workspace_root: Some(temp_dir.path().to_path_buf()),
-            ..Settings::default()
+            index_path: temp_dir.path().join("index"),
+            ..Default::default()
         };
+        let mut indexer = SimpleIndexer::with_settings(Arc::new(settings));
+        indexer.index_file(&first).unwrap();
+        indexer.index_file(&second).unwrap();
 
-        let indexer = SimpleIndexer::with_settings(Arc::new(settings));
-        let behavior = RustBehavior::new();
-        let mut symbol_counter = SymbolCounter::new();
+        // Re-indexing rewrites the first file's documents after the second's
+        fs::write(
+            &first,
+            "pub fn alpha() { beta(); }\npub fn beta() {}\npub fn epsilon() {}\n",
+        )
+        .unwrap();
+        indexer.index_file(&first).unwrap();
 
-        // Test case 1: Rust with public function
-        let mut symbol = Symbol {
-            id: symbol_counter.next_id(),
-            name: "test_function".into(),
-            kind: SymbolKind::Function,
-            signature: Some("pub fn test_function() -> Result<()>".into()),
-            module_path: None,
-            file_id: FileId(1),
-            range: crate::Range::new(0, 10, 0, 20),
-            file_path: "<unknown>".into(),
-            visibility: Visibility::Private,
-            doc_comment: None,
-            scope_context: None,
-            language_id: None,
-        };
+        let mut out = Vec::new();
+        indexer
+            .export(&mut out, crate::indexing::ExportFormat::Json)
+            .unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&out).unwrap();
 
-        let module_path = Some("crate::module".to_string());
-        indexer.configure_symbol(&mut symbol, &module_path, &behavior);
+        let ids: Vec<u64> = value["symbols"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|symbol| symbol["id"].as_u64().unwrap())
+            .collect();
+        assert!(ids.len() >= 5);
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]), "{ids:?}");
 
-        // CURRENT BEHAVIOR: Rust adds symbol name to module path
-        assert_eq!(
-            symbol.module_path.as_deref(),
-            Some("crate::module::test_function")
-        );
+        let edges: Vec<(u64, u64)> = value["relationships"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|edge| (edge["from"].as_u64().unwrap(), edge["to"].as_u64().unwrap()))
+            .collect();
+        assert!(edges.windows(2).all(|pair| pair[0] <= pair[1]), "{edges:?}");
 
-        // CURRENT BEHAVIOR: Updates visibility to Public because signature contains "pub "
-        assert_eq!(symbol.visibility, Visibility::Public);
+        // A second export is identical
+        let mut again = Vec::new();
+        indexer
+            .export(&mut again, crate::indexing::ExportFormat::Json)
+            .unwrap();
+        assert_eq!(out, again);
     }
 
     #[test]
-    fn test_configure_symbol_baseline_python() {
-        use crate::parsing::PythonBehavior;
+    fn test_search_regex() {
+        use std::fs;
         use tempfile::TempDir;
 
-        // Create temp directory for test
         let temp_dir = TempDir::new().unwrap();
+        let rust_file = temp_dir.path().join("handlers.rs");
+        fs::write(
+            &rust_file,
+            r#"
+            fn handle_get_request() {}
+            fn handle_post_request() {}
+            fn handle_request_body() {}
+            struct RequestHandler;
+        "#,
+        )
+        .unwrap();
+
         let settings = Settings {
             workspace_root: Some(temp_dir.path().to_path_buf()),
-            ..Settings::default()
+            ..Default::default()
         };
+        let mut indexer = SimpleIndexer::with_settings(Arc::new(settings));
+        indexer
+            .index_file(&rust_file)
+            .expect("Failed to index Rust file");
 
-        let indexer = SimpleIndexer::with_settings(Arc::new(settings));
-        let behavior = PythonBehavior::new();
-        let mut symbol_counter = SymbolCounter::new();
-
-        let mut symbol = Symbol {
-            id: symbol_counter.next_id(),
-            name: "test_function".into(),
-            kind: SymbolKind::Function,
-            signature: Some("def test_function():".into()),
-            module_path: None,
-            file_id: FileId(1),
-            range: crate::Range::new(0, 10, 0, 20),
-            file_path: "<unknown>".into(),
-            visibility: Visibility::Public,
-            doc_comment: None,
-            scope_context: None,
-            language_id: None,
-        };
+        let mut names: Vec<String> = indexer
+            .search_regex(
+                "^handle_.*_request$",
+                10,
+                None,
+                None,
+                None,
+                &SearchFilters::default(),
+            )
+            .unwrap()
+            .into_iter()
+            .map(|r| r.name)
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["handle_get_request", "handle_post_request"]);
 
-        let module_path = Some("test_module".to_string());
-        indexer.configure_symbol(&mut symbol, &module_path, &behavior);
+        // Filters still apply in regex mode
+        let structs = indexer
+            .search_regex(
+                "Handler$",
+                10,
+                Some(crate::types::SymbolKind::Struct),
+                None,
+                None,
+                &SearchFilters::default(),
+            )
+            .unwrap();
+        assert_eq!(structs.len(), 1);
+        assert_eq!(structs[0].name, "RequestHandler");
 
-        // Python doesn't add symbol name to module path
-        assert_eq!(symbol.module_path.as_deref(), Some("test_module"));
+        // The file filter keeps symbols of files ending with the given path
+        let routes_file = temp_dir.path().join("routes.rs");
+        fs::write(&routes_file, "fn handle_put_request() {}\n").unwrap();
+        indexer
+            .index_file(&routes_file)
+            .expect("Failed to index Rust file");
+        let files = indexer.files_matching("routes.rs");
+        assert_eq!(files.len(), 1);
+        assert!(indexer.files_matching("outes.rs").is_empty());
+        let filters = SearchFilters {
+            files: Some(&files),
+            ..Default::default()
+        };
+        let names: Vec<String> = indexer
+            .search_regex("^handle_.*_request$", 10, None, None, None, &filters)
+            .unwrap()
+            .into_iter()
+            .map(|r| r.name)
+            .collect();
+        assert_eq!(names, vec!["handle_put_request"]);
 
-        // Python doesn't have visibility parsing in configure_symbol
-        assert_eq!(symbol.visibility, Visibility::Public);
+        // Invalid patterns surface the parse error
+        let err = indexer
+            .search_regex("handle_(", 10, None, None, None, &SearchFilters::default())
+            .unwrap_err();
+        assert!(matches!(err, IndexError::InvalidQuery { .. }));
     }
 
     #[test]
-    fn test_configure_symbol_php_baseline() {
-        use crate::parsing::PhpBehavior;
+    fn test_search_substring() {
+        use std::fs;
         use tempfile::TempDir;
 
-        // Create temp directory for test
         let temp_dir = TempDir::new().unwrap();
+        let rust_file = temp_dir.path().join("files.rs");
+        fs::write(
+            &rust_file,
+            r#"
+            fn process_file() {}
+            fn preprocess() {}
+            fn parse_file() {}
+            struct FileProcessor;
+        "#,
+        )
+        .unwrap();
+
         let settings = Settings {
             workspace_root: Some(temp_dir.path().to_path_buf()),
-            ..Settings::default()
+            ..Default::default()
         };
+        let mut indexer = SimpleIndexer::with_settings(Arc::new(settings));
+        indexer
+            .index_file(&rust_file)
+            .expect("Failed to index Rust file");
 
-        let indexer = SimpleIndexer::with_settings(Arc::new(settings));
-        let behavior = PhpBehavior::new();
-        let mut symbol_counter = SymbolCounter::new();
-
-        let mut symbol = Symbol {
-            id: symbol_counter.next_id(),
-            name: "testFunction".into(),
-            kind: SymbolKind::Function,
-            signature: Some("public function testFunction()".into()),
-            module_path: None,
-            file_id: FileId(1),
-            range: crate::Range::new(0, 10, 0, 20),
-            file_path: "<unknown>".into(),
-            visibility: Visibility::Public,
-            doc_comment: None,
-            scope_context: None,
-            language_id: None,
-        };
+        // Case-insensitive "contains" semantics
+        let mut names: Vec<String> = indexer
+            .search_substring("PROC", 10, None, None, None, &SearchFilters::default())
+            .unwrap()
+            .into_iter()
+            .map(|r| r.name)
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["FileProcessor", "preprocess", "process_file"]);
 
-        let module_path = Some("App\\Utils".to_string());
-        indexer.configure_symbol(&mut symbol, &module_path, &behavior);
+        // Filters still apply in substring mode
+        let functions = indexer
+            .search_substring(
+                "file",
+                10,
+                Some(crate::types::SymbolKind::Function),
+                None,
+                None,
+                &SearchFilters::default(),
+            )
+            .unwrap();
+        assert_eq!(functions.len(), 2);
 
-        // PHP doesn't add symbol name to module path
-        assert_eq!(symbol.module_path.as_deref(), Some("App\\Utils"));
+        // Regex metacharacters are matched literally
+        assert!(
+            indexer
+                .search_substring("proc.*", 10, None, None, None, &SearchFilters::default())
+                .unwrap()
+                .is_empty()
+        );
 
-        // PHP doesn't have visibility parsing in configure_symbol
-        assert_eq!(symbol.visibility, Visibility::Public);
+        assert!(matches!(
+            indexer.search_substring("", 10, None, None, None, &SearchFilters::default()),
+            Err(IndexError::InvalidQuery { .. })
+        ));
     }
 
     #[test]
-    fn test_configure_symbol_different_languages() {
-        use crate::parsing::{PhpBehavior, PythonBehavior, RustBehavior};
-
+    fn test_search_exact() {
+        use std::fs;
         use tempfile::TempDir;
 
-        // Create temp directory for test
         let temp_dir = TempDir::new().unwrap();
+        let rust_file = temp_dir.path().join("files.rs");
+        fs::write(
+            &rust_file,
+            r#"
+            fn parse() {}
+            fn parse_file() {}
+            fn reparse() {}
+            struct Parse;
+        "#,
+        )
+        .unwrap();
+
         let settings = Settings {
             workspace_root: Some(temp_dir.path().to_path_buf()),
-            ..Settings::default()
+            ..Default::default()
         };
+        let mut indexer = SimpleIndexer::with_settings(Arc::new(settings));
+        indexer
+            .index_file(&rust_file)
+            .expect("Failed to index Rust file");
 
-        let indexer = SimpleIndexer::with_settings(Arc::new(settings));
-        let rust_behavior = RustBehavior::new();
-        let python_behavior = PythonBehavior::new();
-        let php_behavior = PhpBehavior::new();
-        let module_path = Some("test_module".to_string());
+        let names = |results: Vec<SearchResult>| {
+            let mut names: Vec<String> = results.into_iter().map(|r| r.name).collect();
+            names.sort();
+            names
+        };
 
-        // Use SymbolCounter for proper ID generation
-        let mut symbol_counter = SymbolCounter::new();
+        // Whole names only; case ignored by default
+        assert_eq!(
+            names(
+                indexer
+                    .search_exact(
+                        "parse",
+                        false,
+                        10,
+                        None,
+                        None,
+                        None,
+                        &SearchFilters::default()
+                    )
+                    .unwrap()
+            ),
+            vec!["Parse", "parse"]
+        );
+        assert_eq!(
+            names(
+                indexer
+                    .search_exact(
+                        "parse",
+                        true,
+                        10,
+                        None,
+                        None,
+                        None,
+                        &SearchFilters::default()
+                    )
+                    .unwrap()
+            ),
+            vec!["parse"]
+        );
+        assert_eq!(
+            names(
+                indexer
+                    .search_exact(
+                        "parse",
+                        false,
+                        10,
+                        Some(SymbolKind::Struct),
+                        None,
+                        None,
+                        &SearchFilters::default()
+                    )
+                    .unwrap()
+            ),
+            vec!["Parse"]
+        );
+        // The name is matched literally, not as a pattern
+        assert!(
+            indexer
+                .search_exact(
+                    "pars.",
+                    false,
+                    10,
+                    None,
+                    None,
+                    None,
+                    &SearchFilters::default()
+                )
+                .unwrap()
+                .is_empty()
+        );
+        assert!(matches!(
+            indexer.search_exact(" ", false, 10, None, None, None, &SearchFilters::default()),
+            Err(IndexError::InvalidQuery { .. })
+        ));
+    }
 
-        // Test symbols with language-appropriate signatures
-        let mut rust_symbol = Symbol {
-            id: symbol_counter.next_id(),
-            name: "test".into(),
-            kind: SymbolKind::Function,
-            signature: Some("pub fn test()".into()),
-            module_path: None,
-            file_id: FileId(1),
-            range: crate::Range::new(0, 10, 0, 20),
-            file_path: "<unknown>".into(),
-            visibility: Visibility::Private,
-            doc_comment: None,
-            scope_context: None,
-            language_id: None,
-        };
+    #[test]
+    fn test_get_symbol_source() {
+        use std::fs;
+        use tempfile::TempDir;
 
-        let mut python_symbol = Symbol {
-            id: symbol_counter.next_id(),
-            name: "test".into(),
-            kind: SymbolKind::Function,
-            signature: Some("def test():".into()), // Python signature
-            module_path: None,
-            file_id: FileId(2),
-            range: crate::Range::new(0, 10, 0, 20),
-            file_path: "<unknown>".into(),
-            visibility: Visibility::Private,
-            doc_comment: None,
-            scope_context: None,
-            language_id: None,
-        };
+        let temp_dir = TempDir::new().unwrap();
+        let rust_file = temp_dir.path().join("source.rs");
+        fs::write(
+            &rust_file,
+            "use std::fmt;\n\n#[inline]\nfn answer() -> u32 {\n    42\n}\n",
+        )
+        .unwrap();
 
-        let mut php_symbol = Symbol {
-            id: symbol_counter.next_id(),
-            name: "test".into(),
-            kind: SymbolKind::Function,
-            signature: Some("public function test()".into()), // PHP signature
-            module_path: None,
-            file_id: FileId(3),
-            range: crate::Range::new(0, 10, 0, 20),
-            file_path: "<unknown>".into(),
-            visibility: Visibility::Private,
-            doc_comment: None,
-            scope_context: None,
-            language_id: None,
+        let settings = Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            ..Default::default()
         };
+        let mut indexer = SimpleIndexer::with_settings(Arc::new(settings));
+        indexer.index_file(&rust_file).unwrap();
 
-        // Configure each symbol with its behavior
-        indexer.configure_symbol(&mut rust_symbol, &module_path, &rust_behavior);
-        indexer.configure_symbol(&mut python_symbol, &module_path, &python_behavior);
-        indexer.configure_symbol(&mut php_symbol, &module_path, &php_behavior);
-
-        // Verify different behaviors
-        assert_eq!(
-            rust_symbol.module_path.as_deref(),
-            Some("test_module::test")
-        );
-        assert_eq!(python_symbol.module_path.as_deref(), Some("test_module"));
-        assert_eq!(php_symbol.module_path.as_deref(), Some("test_module"));
+        let symbol = indexer.find_symbols_by_name("answer", None)[0].clone();
+        let source = indexer.get_symbol_source(&symbol, 1).unwrap();
+        assert_eq!(source.text, "#[inline]\nfn answer() -> u32 {\n    42\n}");
+        assert!(!source.stale);
+        assert!(!source.clamped);
 
-        // Visibility parsed according to each language's rules
-        assert_eq!(rust_symbol.visibility, Visibility::Public); // Rust: "pub " means public
-        assert_eq!(python_symbol.visibility, Visibility::Public); // Python: no underscore prefix means public
-        assert_eq!(php_symbol.visibility, Visibility::Public); // PHP: "public function" means public
+        // Truncate the file without re-indexing: warn and clamp
+        fs::write(&rust_file, "fn answer() -> u32 {\n").unwrap();
+        let source = indexer.get_symbol_source(&symbol, 1).unwrap();
+        assert!(source.stale);
+        assert!(source.clamped);
+        assert_eq!(source.text, "fn answer() -> u32 {");
     }
 
     #[test]
-    fn test_symbols_get_language_id_during_indexing() {
-        use crate::parsing::registry::LanguageId;
+    fn test_stale_files() {
         use std::fs;
+        use std::time::{Duration, SystemTime};
         use tempfile::TempDir;
 
-        // Create a temporary directory and test files
         let temp_dir = TempDir::new().unwrap();
-        let rust_file = temp_dir.path().join("test.rs");
-        let python_file = temp_dir.path().join("test.py");
-        let ts_file = temp_dir.path().join("test.ts");
-
-        // Write simple test content
-        fs::write(&rust_file, "fn main() {}").unwrap();
-        fs::write(&python_file, "def main(): pass").unwrap();
-        fs::write(&ts_file, "function main() {}").unwrap();
+        let fresh = temp_dir.path().join("fresh.rs");
+        let edited = temp_dir.path().join("edited.rs");
+        fs::write(&fresh, "fn fresh() {}\n").unwrap();
+        fs::write(&edited, "fn edited() {}\n").unwrap();
 
-        // Create indexer with temp directory as root
         let settings = Settings {
             workspace_root: Some(temp_dir.path().to_path_buf()),
             ..Default::default()
         };
         let mut indexer = SimpleIndexer::with_settings(Arc::new(settings));
+        indexer.index_file(&fresh).unwrap();
+        indexer.index_file(&edited).unwrap();
+        assert!(indexer.stale_files().is_empty());
+
+        // Simulate an edit after indexing
+        fs::File::options()
+            .write(true)
+            .open(&edited)
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(60))
+            .unwrap();
 
-        // Index the files
-        let rust_result = indexer
-            .index_file(&rust_file)
-            .expect("Failed to index Rust file");
-        let python_result = indexer
-            .index_file(&python_file)
-            .expect("Failed to index Python file");
-        let ts_result = indexer
-            .index_file(&ts_file)
-            .expect("Failed to index TypeScript file");
+        let stale = indexer.stale_files();
+        assert_eq!(stale.len(), 1);
+        assert!(stale[0].ends_with("edited.rs"));
 
-        // Verify files were indexed successfully
-        assert!(matches!(rust_result, crate::IndexingResult::Indexed(_)));
-        assert!(matches!(python_result, crate::IndexingResult::Indexed(_)));
-        assert!(matches!(ts_result, crate::IndexingResult::Indexed(_)));
+        // Re-indexing a touched but unchanged file clears its staleness
+        assert!(indexer.index_file(&edited).unwrap().is_cached());
+        assert!(indexer.stale_files().is_empty());
 
-        // Find the symbols - be more specific since multiple files have 'main'
-        // Get all symbols named 'main' and find the one from the Rust file
-        let all_main_symbols = indexer.find_symbols_by_name("main", None);
-        assert!(
-            !all_main_symbols.is_empty(),
-            "Should find 'main' symbols after indexing"
-        );
+        // Deleted files are not reported as stale
+        fs::remove_file(&edited).unwrap();
+        assert!(indexer.stale_files().is_empty());
+    }
 
-        // Debug: Print all main symbols found
-        println!("\n=== All 'main' symbols found ===");
-        for (i, symbol) in all_main_symbols.iter().enumerate() {
-            println!(
-                "Symbol {}: name='{}', language_id={:?}, file_id={:?}",
-                i, symbol.name, symbol.language_id, symbol.file_id
-            );
-        }
+    #[test]
+    fn test_index_archive_reads_entries_without_extracting() {
+        use std::io::Write;
 
-        // Debug: Print file_languages HashMap
-        println!("\n=== File -> Language mapping ===");
-        for (file_id, lang_id) in &indexer.file_languages {
-            println!(
-                "FileId({:?}) -> LanguageId({:?})",
-                file_id.0,
-                lang_id.as_str()
-            );
+        let temp_dir = TempDir::new().unwrap();
+        let entries: [(&str, &[u8]); 4] = [
+            ("./pkg/src/lib.rs", b"pub fn from_tarball() {}\n"),
+            ("./pkg/src/blob.rs", b"fn a() {}\0\0"),
+            ("./pkg/README.md", b"# pkg\n"),
+            ("./pkg/src/api_generated.rs", b"pub fn generated() {}\n"),
+        ];
+
+        let tarball = temp_dir.path().join("pkg-1.0.0.tar.gz");
+        let encoder = flate2::write::GzEncoder::new(
+            fs::File::create(&tarball).unwrap(),
+            flate2::Compression::default(),
+        );
+        let mut builder = tar::Builder::new(encoder);
+        for (name, data) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            builder.append_data(&mut header, name, data).unwrap();
         }
+        builder.into_inner().unwrap().finish().unwrap();
 
-        let rust_symbol = all_main_symbols
-            .iter()
-            .find(|s| s.language_id == Some(LanguageId::new("rust")))
-            .expect("Should find a Rust 'main' symbol");
+        let zipped = temp_dir.path().join("other.zip");
+        let mut writer = zip::ZipWriter::new(fs::File::create(&zipped).unwrap());
+        writer
+            .start_file("other/main.py", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(b"def from_zip():\n    pass\n").unwrap();
+        writer.finish().unwrap();
+
+        let settings = Arc::new(Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        });
+        let mut indexer = SimpleIndexer::with_settings(settings);
 
-        println!("\n=== Selected Rust symbol ===");
-        println!(
-            "Symbol: name='{}', language_id={:?}",
-            rust_symbol.name, rust_symbol.language_id
+        let stats = indexer.index_archive(&tarball, false, false).unwrap();
+        assert_eq!(stats.files_indexed, 1);
+        assert_eq!(stats.files_binary, 1);
+        assert_eq!(stats.files_generated, 1);
+        let symbol = indexer
+            .find_symbols_by_name("from_tarball", None)
+            .pop()
+            .unwrap();
+        assert_eq!(&*symbol.file_path, "pkg-1.0.0.tar.gz!/pkg/src/lib.rs");
+        assert!(indexer.find_symbols_by_name("generated", None).is_empty());
+
+        let stats = indexer.index_archive(&zipped, false, false).unwrap();
+        assert_eq!(stats.files_indexed, 1);
+        let symbol = indexer
+            .find_symbols_by_name("from_zip", None)
+            .pop()
+            .unwrap();
+        assert_eq!(&*symbol.file_path, "other.zip!/other/main.py");
+
+        // Nothing was extracted next to the archives
+        assert!(!temp_dir.path().join("pkg").exists());
+        assert!(!temp_dir.path().join("other").exists());
+
+        // Entries are read back out of their archive
+        let verification = indexer.verify_against_disk();
+        assert!(verification.is_in_sync(), "{verification:?}");
+        assert_eq!(verification.ok, 2);
+        let source = indexer.get_symbol_source(&symbol, 0).unwrap();
+        assert_eq!(source.text, "def from_zip():\n    pass");
+
+        // Re-indexing an updated archive drops the entries it no longer has
+        let mut writer = zip::ZipWriter::new(fs::File::create(&zipped).unwrap());
+        writer
+            .start_file("other/next.py", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(b"def from_next():\n    pass\n").unwrap();
+        writer.finish().unwrap();
+        indexer.index_archive(&zipped, false, false).unwrap();
+        assert!(indexer.find_symbols_by_name("from_zip", None).is_empty());
+        assert!(!indexer.find_symbols_by_name("from_next", None).is_empty());
+        assert!(
+            !indexer
+                .find_symbols_by_name("from_tarball", None)
+                .is_empty()
         );
 
-        // Verify the symbol has the correct language_id
-        assert_eq!(
-            rust_symbol.language_id,
-            Some(LanguageId::new("rust")),
-            "Symbol from Rust file should have language_id set to 'rust'"
+        // Dropping an archive from the config purges its entries
+        let (_, removed, _, _) = indexer
+            .sync_with_config(
+                Some(vec![tarball.clone(), zipped.clone()]),
+                std::slice::from_ref(&zipped),
+                false,
+            )
+            .unwrap();
+        assert_eq!(removed, 1);
+        assert!(
+            indexer
+                .find_symbols_by_name("from_tarball", None)
+                .is_empty()
         );
+        assert!(!indexer.find_symbols_by_name("from_next", None).is_empty());
+    }
 
-        // Test Python symbol - use 'main' which we know exists
-        let python_symbols = all_main_symbols
-            .iter()
-            .filter(|s| s.language_id == Some(LanguageId::new("python")))
-            .collect::<Vec<_>>();
-        println!("\n=== Python symbols ===");
-        for symbol in &python_symbols {
-            println!(
-                "Symbol: name='{}', language_id={:?}",
-                symbol.name, symbol.language_id
-            );
-            assert_eq!(
-                symbol.language_id,
-                Some(LanguageId::new("python")),
-                "Python symbol should have language_id set to 'python'"
-            );
+    #[test]
+    fn test_verify_against_disk_reports_changed_and_missing_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let kept = temp_dir.path().join("kept.rs");
+        let edited = temp_dir.path().join("edited.rs");
+        let deleted = temp_dir.path().join("deleted.rs");
+        fs::write(&kept, "fn kept() {}\n").unwrap();
+        fs::write(&edited, "fn edited() {}\n").unwrap();
+        fs::write(&deleted, "fn deleted() {}\n").unwrap();
+        let settings = Arc::new(Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        });
+        let mut indexer = SimpleIndexer::with_settings(settings);
+        for file in [&kept, &edited, &deleted] {
+            indexer.index_file(file).unwrap();
         }
-        assert!(!python_symbols.is_empty(), "Should find Python main symbol");
+        assert!(indexer.verify_against_disk().is_in_sync());
 
-        // Test TypeScript symbol
-        let ts_symbols = all_main_symbols
-            .iter()
-            .filter(|s| s.language_id == Some(LanguageId::new("typescript")))
-            .collect::<Vec<_>>();
-        println!("\n=== TypeScript symbols ===");
-        for symbol in &ts_symbols {
-            println!(
-                "Symbol: name='{}', language_id={:?}",
-                symbol.name, symbol.language_id
-            );
-        }
-        assert!(!ts_symbols.is_empty(), "Should find TypeScript main symbol");
+        fs::write(&edited, "fn edited() { todo!() }\n").unwrap();
+        fs::remove_file(&deleted).unwrap();
 
-        // Verify that language detection is working internally
-        assert_eq!(
-            indexer.file_languages.len(),
-            3,
-            "Should have 3 files with language mappings"
+        let verification = indexer.verify_against_disk();
+        assert!(!verification.is_in_sync());
+        assert_eq!(verification.ok, 1);
+        assert_eq!(verification.changed.len(), 1);
+        assert!(verification.changed[0].ends_with("edited.rs"));
+        assert_eq!(verification.missing.len(), 1);
+        assert!(verification.missing[0].ends_with("deleted.rs"));
+    }
+
+    #[test]
+    fn test_files_indexed_since() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("recent.rs");
+        fs::write(&path, "fn recent() {}\n").unwrap();
+
+        let settings = Arc::new(Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            index_path: temp_dir.path().join("index"),
+            ..Settings::default()
+        });
+        let mut indexer = SimpleIndexer::with_settings(settings);
+        let before = get_utc_timestamp();
+        indexer.index_file(&path).unwrap();
+
+        let recent = indexer.files_indexed_since(before);
+        assert_eq!(recent.len(), 1);
+        assert!(recent.iter().all(|file| file.ends_with("recent.rs")));
+        assert!(
+            indexer
+                .files_indexed_since(get_utc_timestamp() + 60)
+                .is_empty()
         );
     }
 
     #[test]
-    fn test_find_symbols_with_language_filter() {
+    fn test_get_source_snippet() {
         use std::fs;
         use tempfile::TempDir;
 
-        println!("\n=== Testing SimpleIndexer with language filtering ===");
-
-        // Create a temporary directory and test files
         let temp_dir = TempDir::new().unwrap();
-        let rust_file = temp_dir.path().join("main.rs");
-        let python_file = temp_dir.path().join("main.py");
-        let ts_file = temp_dir.path().join("main.ts");
-
-        // Write test content with same function name in different languages
-        fs::write(&rust_file, "fn process_data() { println!(\"Rust\"); }").unwrap();
-        fs::write(&python_file, "def process_data():\n    print('Python')").unwrap();
+        let rust_file = temp_dir.path().join("snippet.rs");
         fs::write(
-            &ts_file,
-            "function process_data() { console.log('TypeScript'); }",
+            &rust_file,
+            "use std::fmt;\n\n#[inline]\nfn answer() -> u32 {\n    42\n}\n",
         )
         .unwrap();
 
-        // Create indexer
         let settings = Settings {
             workspace_root: Some(temp_dir.path().to_path_buf()),
             ..Default::default()
         };
         let mut indexer = SimpleIndexer::with_settings(Arc::new(settings));
+        indexer.index_file(&rust_file).unwrap();
 
-        // Index all files
-        indexer
-            .index_file(&rust_file)
-            .expect("Failed to index Rust file");
-        indexer
-            .index_file(&python_file)
-            .expect("Failed to index Python file");
-        indexer
-            .index_file(&ts_file)
-            .expect("Failed to index TypeScript file");
-
-        // Test 1: Find all symbols without filter
-        let all_symbols = indexer.find_symbols_by_name("process_data", None);
-        println!(
-            "Test 1 - No filter: Found {} 'process_data' symbols",
-            all_symbols.len()
-        );
-        for symbol in &all_symbols {
-            println!(
-                "  - Language: {:?}, File: {}",
-                symbol.language_id,
-                indexer.get_file_path(symbol.file_id).unwrap_or_default()
-            );
-        }
-        assert_eq!(all_symbols.len(), 3, "Should find 3 process_data functions");
-
-        // Test 2: Filter by Rust
-        let rust_symbols = indexer.find_symbols_by_name("process_data", Some("rust"));
-        println!("Test 2 - Rust filter: Found {} symbols", rust_symbols.len());
-        assert_eq!(rust_symbols.len(), 1, "Should find 1 Rust function");
-        assert_eq!(rust_symbols[0].language_id, Some(LanguageId::new("rust")));
-
-        // Test 3: Filter by Python
-        let python_symbols = indexer.find_symbols_by_name("process_data", Some("python"));
-        println!(
-            "Test 3 - Python filter: Found {} symbols",
-            python_symbols.len()
+        let symbol = indexer.find_symbols_by_name("answer", None)[0].clone();
+        let snippet = indexer.get_source_snippet(&symbol, 1).unwrap();
+        assert_eq!(snippet.start_line, 3);
+        assert_eq!(snippet.symbol_line, 4);
+        assert_eq!(
+            snippet.lines,
+            vec!["#[inline]", "fn answer() -> u32 {", "    42"]
         );
-        assert_eq!(python_symbols.len(), 1, "Should find 1 Python function");
         assert_eq!(
-            python_symbols[0].language_id,
-            Some(LanguageId::new("python"))
+            snippet.format_rg("snippet.rs"),
+            "snippet.rs-3-#[inline]\nsnippet.rs:4:fn answer() -> u32 {\nsnippet.rs-5-    42\n"
         );
 
-        // Test 4: Filter by TypeScript
-        let ts_symbols = indexer.find_symbols_by_name("process_data", Some("typescript"));
-        println!(
-            "Test 4 - TypeScript filter: Found {} symbols",
-            ts_symbols.len()
-        );
-        assert_eq!(ts_symbols.len(), 1, "Should find 1 TypeScript function");
+        // Context is clipped at the end of the file
         assert_eq!(
-            ts_symbols[0].language_id,
-            Some(LanguageId::new("typescript"))
+            indexer.get_source_snippet(&symbol, 10).unwrap().lines.len(),
+            6
         );
 
-        // Test 5: Filter by non-existent language
-        let java_symbols = indexer.find_symbols_by_name("process_data", Some("java"));
-        println!(
-            "Test 5 - Java filter (non-existent): Found {} symbols",
-            java_symbols.len()
-        );
-        assert_eq!(java_symbols.len(), 0, "Should find no Java functions");
+        // A file now shorter than the symbol's line has no snippet
+        fs::write(&rust_file, "fn answer() -> u32 {\n").unwrap();
+        assert!(indexer.get_source_snippet(&symbol, 1).is_err());
 
-        println!("=== All SimpleIndexer language filter tests passed ===\n");
+        fs::remove_file(&rust_file).unwrap();
+        assert!(indexer.get_source_snippet(&symbol, 1).is_err());
     }
 
     #[test]
-    fn test_search_with_language_filter() {
+    fn test_reindex_reanchors_unchanged_symbols() {
         use std::fs;
         use tempfile::TempDir;
 
-        println!("\n=== Testing SimpleIndexer search with language filtering ===");
-
-        // Create a temporary directory and test files
         let temp_dir = TempDir::new().unwrap();
-        let rust_file = temp_dir.path().join("parser.rs");
-        let python_file = temp_dir.path().join("parser.py");
-
-        // Write test content with parse-related functions
+        let rust_file = temp_dir.path().join("anchors.rs");
         fs::write(
             &rust_file,
-            r#"
-            fn parse_json(input: &str) -> Result<Value, Error> {
-                // Parse JSON in Rust
-            }
-            fn parse_xml(input: &str) -> Result<Document, Error> {
-                // Parse XML in Rust
-            }
-        "#,
+            "fn helper() -> u32 {\n    1\n}\n\nfn caller() -> u32 {\n    helper()\n}\n",
         )
         .unwrap();
 
-        fs::write(
-            &python_file,
-            r#"
-def parse_json(input: str) -> dict:
-    """Parse JSON in Python"""
-    pass
+        let settings = Settings {
+            workspace_root: Some(temp_dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        let mut indexer = SimpleIndexer::with_settings(Arc::new(settings));
+        indexer.index_file(&rust_file).unwrap();
 
-def parse_yaml(input: str) -> dict:
-    """Parse YAML in Python"""
-    pass
-        "#,
+        let before = indexer.find_symbols_by_name("helper", None)[0].clone();
+        let caller_before = indexer.find_symbols_by_name("caller", None)[0].clone();
+
+        // Shift everything down and change only the caller's body
+        fs::write(
+            &rust_file,
+            "// header\n\nfn helper() -> u32 {\n    1\n}\n\nfn caller() -> u32 {\n    helper() + 1\n}\n",
         )
         .unwrap();
+        indexer.index_file(&rust_file).unwrap();
+
+        let after = indexer.find_symbols_by_name("helper", None);
+        assert_eq!(after.len(), 1);
+        assert_eq!(after[0].id, before.id, "unchanged symbol keeps its id");
+        assert_eq!(after[0].range.start_line, before.range.start_line + 2);
+
+        let caller_after = indexer.find_symbols_by_name("caller", None);
+        assert_eq!(caller_after.len(), 1);
+        assert_ne!(caller_after[0].id, caller_before.id);
+    }
+
+    #[test]
+    fn test_reindex_doc_only_edit_keeps_relationships() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let rust_file = temp_dir.path().join("docs.rs");
+        let source = |doc: &str, body: &str| {
+            format!(
+                "/// {doc}\nfn helper() -> u32 {{\n    1\n}}\n\nfn caller() -> u32 {{\n    {body}\n}}\n"
+            )
+        };
+        fs::write(&rust_file, source("Returns one", "helper()")).unwrap();
 
-        // Create indexer
         let settings = Settings {
             workspace_root: Some(temp_dir.path().to_path_buf()),
             ..Default::default()
         };
         let mut indexer = SimpleIndexer::with_settings(Arc::new(settings));
-
-        // Index files
-        indexer
-            .index_file(&rust_file)
-            .expect("Failed to index Rust file");
-        indexer
-            .index_file(&python_file)
-            .expect("Failed to index Python file");
-
-        // Test 1: Search without language filter
-        let all_results = indexer.search("parse", 10, None, None, None).unwrap();
-        println!(
-            "Test 1 - Search 'parse' no filter: Found {} results",
-            all_results.len()
-        );
+        indexer.index_file(&rust_file).unwrap();
         assert!(
-            all_results.len() >= 3,
-            "Should find at least 3 parse functions"
+            indexer.symbol_updates().is_empty(),
+            "new files are not counted"
         );
 
-        // Test 2: Search with Rust filter
-        let rust_results = indexer
-            .search("parse", 10, None, None, Some("rust"))
-            .unwrap();
-        println!(
-            "Test 2 - Search 'parse' Rust filter: Found {} results",
-            rust_results.len()
-        );
-        for result in &rust_results {
-            println!("  - {}: {}", result.name, result.file_path);
-        }
-        assert_eq!(rust_results.len(), 2, "Should find 2 Rust parse functions");
+        let helper = indexer.find_symbols_by_name("helper", None)[0].clone();
+        assert_eq!(indexer.get_calling_functions(helper.id).len(), 1);
 
-        // Test 3: Search with Python filter
-        let python_results = indexer
-            .search(
-                "parse",
-                10,
-                Some(crate::types::SymbolKind::Function),
-                None,
-                Some("python"),
-            )
-            .unwrap();
-        println!(
-            "Test 3 - Search 'parse' Python filter: Found {} results",
-            python_results.len()
-        );
-        for result in &python_results {
-            println!("  - {}: {}", result.name, result.file_path);
-        }
-        assert_eq!(
-            python_results.len(),
-            2,
-            "Should find 2 Python parse functions"
-        );
+        // Only the doc comment changes
+        fs::write(&rust_file, source("Returns the number one", "helper()")).unwrap();
+        indexer.index_file(&rust_file).unwrap();
 
-        // Test 4: Search with non-existent language
-        let java_results = indexer
-            .search("parse", 10, None, None, Some("java"))
-            .unwrap();
-        println!(
-            "Test 4 - Search 'parse' Java filter: Found {} results",
-            java_results.len()
+        let updates = indexer.symbol_updates();
+        assert_eq!(updates.doc_only, 1);
+        assert_eq!(updates.unchanged, 1);
+        assert_eq!(updates.relationships_kept, 1);
+        let helper_after = indexer.find_symbols_by_name("helper", None)[0].clone();
+        assert_eq!(helper_after.id, helper.id);
+        assert_eq!(
+            helper_after.doc_comment.as_deref(),
+            Some("Returns the number one")
         );
-        assert_eq!(java_results.len(), 0, "Should find no Java functions");
-
-        println!("=== All SimpleIndexer search tests passed ===\n");
+        assert_eq!(indexer.get_calling_functions(helper.id).len(), 1);
+
+        // A body change re-resolves the file's relationships
+        fs::write(&rust_file, source("Returns the number one", "helper() + 1")).unwrap();
+        indexer.index_file(&rust_file).unwrap();
+
+        let updates = indexer.symbol_updates();
+        assert_eq!(updates.code_changed, 1);
+        assert_eq!(updates.relationships_kept, 1);
+        let callers = indexer.get_calling_functions(helper.id);
+        assert_eq!(callers.len(), 1);
+        assert_eq!(callers[0].name.as_str(), "caller");
     }
 
     /// REAL TDD Integration Test - Parse code, index it, and test relationship resolution
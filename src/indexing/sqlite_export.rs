@@ -0,0 +1,223 @@
+//! SQLite export of the whole index
+//!
+//! Writes files, symbols and relationships into a fresh SQLite database so the
+//! index can be explored with plain SQL. All rows are inserted inside a single
+//! transaction using cached prepared statements.
+
+use super::export::{ExportStats, ExportedRelationship};
+use crate::relationship::RelationshipMetadata;
+use crate::{FileId, Symbol};
+use rusqlite::{Connection, params};
+use std::path::Path;
+
+const SCHEMA: &str = "
+CREATE TABLE files (
+    id INTEGER PRIMARY KEY,
+    path TEXT NOT NULL,
+    language TEXT
+);
+CREATE TABLE symbols (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    file_id INTEGER NOT NULL,
+    start_line INTEGER NOT NULL,
+    start_column INTEGER NOT NULL,
+    end_line INTEGER NOT NULL,
+    end_column INTEGER NOT NULL,
+    file_path TEXT NOT NULL,
+    module_path TEXT,
+    signature TEXT,
+    doc TEXT,
+    visibility TEXT NOT NULL,
+    language TEXT,
+    parent_id INTEGER,
+    attributes TEXT
+);
+CREATE TABLE relationships (
+    from_id INTEGER NOT NULL,
+    to_id INTEGER NOT NULL,
+    kind TEXT NOT NULL,
+    metadata TEXT
+);
+";
+
+/// Lookup indexes, created after the bulk insert so inserts stay fast
+const INDEXES: &str = "
+CREATE INDEX idx_symbols_name ON symbols(name);
+CREATE INDEX idx_symbols_file_id ON symbols(file_id);
+CREATE INDEX idx_symbols_module_path ON symbols(module_path);
+CREATE INDEX idx_symbols_parent_id ON symbols(parent_id);
+CREATE INDEX idx_relationships_from ON relationships(from_id, kind);
+CREATE INDEX idx_relationships_to ON relationships(to_id, kind);
+";
+
+const INSERT_FILE: &str = "INSERT INTO files (id, path, language) VALUES (?1, ?2, ?3)";
+
+const INSERT_SYMBOL: &str = "INSERT INTO symbols (
+    id, name, kind, file_id, start_line, start_column, end_line, end_column,
+    file_path, module_path, signature, doc, visibility, language, parent_id, attributes
+) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)";
+
+const INSERT_RELATIONSHIP: &str =
+    "INSERT INTO relationships (from_id, to_id, kind, metadata) VALUES (?1, ?2, ?3, ?4)";
+
+/// Incremental writer for a SQLite export
+///
+/// `create` expects a path with no existing database and opens a transaction;
+/// `finish` builds the lookup indexes and commits.
+pub struct SqliteExporter {
+    conn: Connection,
+    stats: ExportStats,
+}
+
+impl SqliteExporter {
+    pub fn create(path: &Path) -> rusqlite::Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch("PRAGMA journal_mode = OFF; PRAGMA synchronous = OFF;")?;
+        conn.execute_batch(SCHEMA)?;
+        conn.execute_batch("BEGIN")?;
+
+        Ok(Self {
+            conn,
+            stats: ExportStats::default(),
+        })
+    }
+
+    pub fn write_file(
+        &mut self,
+        file_id: FileId,
+        path: &str,
+        language: Option<&str>,
+    ) -> rusqlite::Result<()> {
+        self.conn
+            .prepare_cached(INSERT_FILE)?
+            .execute(params![file_id.value(), path, language])?;
+        Ok(())
+    }
+
+    pub fn write_symbol(&mut self, symbol: &Symbol) -> rusqlite::Result<()> {
+        self.conn.prepare_cached(INSERT_SYMBOL)?.execute(params![
+            symbol.id.value(),
+            &*symbol.name,
+            format!("{:?}", symbol.kind),
+            symbol.file_id.value(),
+            symbol.range.start_line,
+            symbol.range.start_column,
+            symbol.range.end_line,
+            symbol.range.end_column,
+            &*symbol.file_path,
+            symbol.module_path.as_deref(),
+            symbol.signature.as_deref(),
+            symbol.doc_comment.as_deref(),
+            format!("{:?}", symbol.visibility),
+            symbol.language_id.as_ref().map(|id| id.as_str()),
+            symbol.parent.map(|id| id.value()),
+            (!symbol.attributes.is_empty()).then(|| symbol.attributes.join("\n")),
+        ])?;
+        self.stats.symbols += 1;
+        Ok(())
+    }
+
+    pub fn write_relationship(
+        &mut self,
+        relationship: &ExportedRelationship,
+        metadata: Option<&RelationshipMetadata>,
+    ) -> rusqlite::Result<()> {
+        let metadata = metadata
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+
+        self.conn
+            .prepare_cached(INSERT_RELATIONSHIP)?
+            .execute(params![
+                relationship.from.value(),
+                relationship.to.value(),
+                format!("{:?}", relationship.kind),
+                metadata,
+            ])?;
+        self.stats.relationships += 1;
+        Ok(())
+    }
+
+    pub fn finish(self) -> rusqlite::Result<ExportStats> {
+        self.conn.execute_batch(INDEXES)?;
+        self.conn.execute_batch("COMMIT")?;
+        Ok(self.stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Range, RelationKind, SymbolId, SymbolKind};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_sqlite_export_round_trip() {
+        let temp = tempdir().unwrap();
+        let db_path = temp.path().join("out.db");
+
+        let file_id = FileId::new(1).unwrap();
+        let caller = Symbol::new(
+            SymbolId::new(1).unwrap(),
+            "main",
+            SymbolKind::Function,
+            file_id,
+            Range::new(1, 0, 3, 1),
+        )
+        .with_module_path("crate");
+        let callee = Symbol::new(
+            SymbolId::new(2).unwrap(),
+            "helper",
+            SymbolKind::Function,
+            file_id,
+            Range::new(5, 0, 7, 1),
+        );
+
+        let mut exporter = SqliteExporter::create(&db_path).unwrap();
+        exporter
+            .write_file(file_id, "src/main.rs", Some("rust"))
+            .unwrap();
+        exporter.write_symbol(&caller).unwrap();
+        exporter.write_symbol(&callee).unwrap();
+        exporter
+            .write_relationship(
+                &ExportedRelationship {
+                    from: caller.id,
+                    to: callee.id,
+                    kind: RelationKind::Calls,
+                },
+                Some(&RelationshipMetadata::new().at_position(2, 4)),
+            )
+            .unwrap();
+        let stats = exporter.finish().unwrap();
+        assert_eq!(stats.symbols, 2);
+        assert_eq!(stats.relationships, 1);
+
+        let conn = Connection::open(&db_path).unwrap();
+        let most_called: (String, i64) = conn
+            .query_row(
+                "SELECT s.name, COUNT(*) FROM relationships r
+                 JOIN symbols s ON s.id = r.to_id
+                 WHERE r.kind = 'Calls' GROUP BY s.id",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(most_called, ("helper".to_string(), 1));
+
+        let language: String = conn
+            .query_row("SELECT language FROM files WHERE id = 1", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(language, "rust");
+
+        let metadata: String = conn
+            .query_row("SELECT metadata FROM relationships", [], |row| row.get(0))
+            .unwrap();
+        assert!(metadata.contains("\"line\":2"));
+    }
+}
@@ -7,7 +7,8 @@
 //! - Hidden file handling
 
 use crate::Settings;
-use crate::parsing::get_registry;
+use crate::indexing::LanguageOverrides;
+use crate::parsing::{LanguageId, get_registry};
 use ignore::WalkBuilder;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -51,6 +52,10 @@ impl FileWalker {
 
         // Get enabled extensions from the registry
         let enabled_extensions = self.get_enabled_extensions();
+        // Files forced to a language by [languages.overrides] are kept whatever their
+        // extension when that language is enabled, and skipped when it is disabled
+        let language_overrides = LanguageOverrides::from_settings(&self.settings);
+        let enabled_languages = self.get_enabled_languages();
 
         // Build and filter the walker
         builder
@@ -69,6 +74,12 @@ impl FileWalker {
                     }
                 }
 
+                if let Some(language_id) = language_overrides.language_for(path) {
+                    return enabled_languages
+                        .contains(&language_id)
+                        .then(|| path.to_path_buf());
+                }
+
                 // Check if this file extension is enabled
                 if let Some(extension) = path.extension() {
                     if let Some(ext_str) = extension.to_str() {
@@ -96,6 +107,19 @@ impl FileWalker {
         }
     }
 
+    /// Get list of enabled languages from the registry
+    fn get_enabled_languages(&self) -> Vec<LanguageId> {
+        let registry = get_registry();
+        if let Ok(registry) = registry.lock() {
+            registry
+                .iter_enabled(&self.settings)
+                .map(|def| def.id())
+                .collect()
+        } else {
+            Vec::new()
+        }
+    }
+
     /// Count files that would be indexed (useful for dry runs)
     pub fn count_files(&self, root: &Path) -> usize {
         self.walk(root).count()
@@ -179,4 +203,48 @@ mod tests {
         assert_eq!(files.len(), 1);
         assert!(files[0].ends_with("included.rs"));
     }
+
+    #[test]
+    fn test_language_override_includes_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        fs::write(root.join("main.rs"), "fn main() {}").unwrap();
+        fs::write(root.join("helpers.inc"), "fn helper() {}").unwrap();
+        fs::write(root.join("notes.txt"), "notes").unwrap();
+
+        let mut settings = (*create_test_settings()).clone();
+        settings
+            .languages
+            .overrides
+            .insert("*.inc".to_string(), "rust".to_string());
+        let walker = FileWalker::new(Arc::new(settings));
+
+        let files: Vec<_> = walker.walk(root).collect();
+
+        assert_eq!(files.len(), 2);
+        assert!(files.iter().any(|p| p.ends_with("helpers.inc")));
+    }
+
+    #[test]
+    fn test_language_override_to_disabled_language_excludes_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        fs::write(root.join("main.rs"), "fn main() {}").unwrap();
+        fs::write(root.join("build_script.rs"), "def build(): pass").unwrap();
+
+        // Python is disabled in the test settings
+        let mut settings = (*create_test_settings()).clone();
+        settings
+            .languages
+            .overrides
+            .insert("build_*.rs".to_string(), "python".to_string());
+        let walker = FileWalker::new(Arc::new(settings));
+
+        let files: Vec<_> = walker.walk(root).collect();
+
+        assert_eq!(files.len(), 1);
+        assert!(files[0].ends_with("main.rs"));
+    }
 }
@@ -0,0 +1,107 @@
+//! GitHub Actions annotations for analysis findings.
+//!
+//! Renders findings as workflow commands (`::warning file=...,line=...::message`)
+//! so a CI step shows them inline on pull requests.
+
+use std::fmt;
+
+/// Severity of an annotation, shown as its icon on the pull request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnotationLevel {
+    Notice,
+    Warning,
+    Error,
+}
+
+impl fmt::Display for AnnotationLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let command = match self {
+            Self::Notice => "notice",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        };
+        write!(f, "{command}")
+    }
+}
+
+/// One finding attached to a file and line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Annotation {
+    pub level: AnnotationLevel,
+    pub file: String,
+    /// 1-based line number
+    pub line: u32,
+    pub title: String,
+    pub message: String,
+}
+
+impl Annotation {
+    /// Build an annotation from a `path:line` location as reported by analyses
+    ///
+    /// Returns `None` when the location has no line number.
+    pub fn at_location(
+        level: AnnotationLevel,
+        location: &str,
+        title: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Option<Self> {
+        let (file, line) = location.rsplit_once(':')?;
+        Some(Self {
+            level,
+            file: file.to_string(),
+            line: line.parse().ok()?,
+            title: title.into(),
+            message: message.into(),
+        })
+    }
+}
+
+impl fmt::Display for Annotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "::{} file={},line={},title={}::{}",
+            self.level,
+            escape_property(&self.file),
+            self.line,
+            escape_property(&self.title),
+            escape_data(&self.message)
+        )
+    }
+}
+
+/// Escape a workflow command message
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a workflow command property, which also ends at `:` and `,`
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_annotation_escapes_workflow_command() {
+        let annotation = Annotation::at_location(
+            AnnotationLevel::Warning,
+            "src/a,b.rs:12",
+            "Ambiguous: helper",
+            "100% sure?\nno",
+        )
+        .unwrap();
+
+        assert_eq!(annotation.line, 12);
+        assert_eq!(
+            annotation.to_string(),
+            "::warning file=src/a%2Cb.rs,line=12,title=Ambiguous%3A helper::100%25 sure?%0Ano"
+        );
+        assert!(Annotation::at_location(AnnotationLevel::Notice, "script.py", "t", "m").is_none());
+    }
+}
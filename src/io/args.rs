@@ -110,6 +110,28 @@ pub fn get_string_param(params: &HashMap<String, String>, key: &str) -> Option<S
     params.get(key).cloned()
 }
 
+/// Parse a duration like `90`, `30s`, `10m`, `2h` or `1d` into seconds
+///
+/// A bare number counts seconds.
+pub fn parse_duration(value: &str) -> Result<u64, String> {
+    let value = value.trim();
+    let (amount, unit_secs) = match value.char_indices().last() {
+        Some((i, 's')) => (&value[..i], 1),
+        Some((i, 'm')) => (&value[..i], 60),
+        Some((i, 'h')) => (&value[..i], 60 * 60),
+        Some((i, 'd')) => (&value[..i], 24 * 60 * 60),
+        _ => (value, 1),
+    };
+    amount
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .and_then(|amount| amount.checked_mul(unit_secs))
+        .ok_or_else(|| {
+            format!("Invalid duration '{value}'. Use a number with s, m, h or d, e.g. 10m")
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -164,4 +186,16 @@ mod tests {
         );
         assert_eq!(params.get("limit"), Some(&"3".to_string()));
     }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90"), Ok(90));
+        assert_eq!(parse_duration("30s"), Ok(30));
+        assert_eq!(parse_duration("10m"), Ok(600));
+        assert_eq!(parse_duration("2h"), Ok(7200));
+        assert_eq!(parse_duration(" 1d "), Ok(86400));
+        assert!(parse_duration("m").is_err());
+        assert!(parse_duration("10w").is_err());
+        assert!(parse_duration("-5m").is_err());
+    }
 }
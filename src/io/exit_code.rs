@@ -44,6 +44,11 @@ pub enum ExitCode {
 
     /// Operation not supported (code 8)
     UnsupportedOperation = 8,
+
+    /// Analysis succeeded and reported findings with `--fail-on-findings`, fell
+    /// below a `--min` threshold, a benchmark regressed past `--tolerance`, or
+    /// `verify` found files that diverged from the index (code 9)
+    FindingsReported = 9,
 }
 
 impl From<ExitCode> for i32 {
@@ -74,14 +79,18 @@ impl ExitCode {
                 ExitCode::NotFound
             }
 
-            // Index corruption is a blocking error
-            IndexError::IndexCorrupted { .. } => ExitCode::BlockingError,
+            // Index corruption or an outdated format is a blocking error
+            IndexError::IndexCorrupted { .. } | IndexError::IncompatibleIndex { .. } => {
+                ExitCode::BlockingError
+            }
 
             // Specific recoverable errors
             IndexError::ParseError { .. } => ExitCode::ParseError,
             IndexError::FileRead { .. } | IndexError::FileWrite { .. } => ExitCode::IoError,
             IndexError::ConfigError { .. } => ExitCode::ConfigError,
-            IndexError::UnsupportedFileType { .. } => ExitCode::UnsupportedOperation,
+            IndexError::UnsupportedFileType { .. }
+            | IndexError::BinaryContent { .. }
+            | IndexError::ParseTimeout { .. } => ExitCode::UnsupportedOperation,
 
             // ID exhaustion errors are blocking
             IndexError::FileIdExhausted | IndexError::SymbolIdExhausted => ExitCode::BlockingError,
@@ -117,6 +126,7 @@ impl ExitCode {
             ExitCode::ConfigError => "Configuration error",
             ExitCode::IndexCorrupted => "Index corrupted",
             ExitCode::UnsupportedOperation => "Unsupported operation",
+            ExitCode::FindingsReported => "Analysis reported findings",
         }
     }
 }
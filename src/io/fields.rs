@@ -0,0 +1,182 @@
+//! Field selection for search output (`--fields name,file,line`)
+//!
+//! Scripts piping search results into other tools usually want a few columns,
+//! not the full record. A selection renders each result as one row: values are
+//! tab-separated in text mode, and JSON objects carry only the chosen keys.
+
+use crate::paths::display_path;
+use crate::storage::SearchResult;
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use std::fmt;
+
+/// A selectable column of a search result
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchField {
+    Name,
+    Kind,
+    File,
+    Line,
+    Column,
+    Module,
+    Doc,
+    Signature,
+    Score,
+}
+
+impl SearchField {
+    /// Every field, in the order valid names are listed
+    pub const ALL: [SearchField; 9] = [
+        SearchField::Name,
+        SearchField::Kind,
+        SearchField::File,
+        SearchField::Line,
+        SearchField::Column,
+        SearchField::Module,
+        SearchField::Doc,
+        SearchField::Signature,
+        SearchField::Score,
+    ];
+
+    /// Name used on the command line and as the JSON key
+    pub fn name(self) -> &'static str {
+        match self {
+            SearchField::Name => "name",
+            SearchField::Kind => "kind",
+            SearchField::File => "file",
+            SearchField::Line => "line",
+            SearchField::Column => "column",
+            SearchField::Module => "module",
+            SearchField::Doc => "doc",
+            SearchField::Signature => "signature",
+            SearchField::Score => "score",
+        }
+    }
+
+    /// Parse a field name, case-insensitively
+    pub fn parse(name: &str) -> Result<Self, String> {
+        Self::ALL
+            .into_iter()
+            .find(|field| field.name().eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| {
+                let valid: Vec<&str> = Self::ALL.iter().map(|field| field.name()).collect();
+                format!(
+                    "Unknown field '{}'. Valid fields: {}",
+                    name.trim(),
+                    valid.join(", ")
+                )
+            })
+    }
+
+    /// Text rendering; docs are cut to their first line to keep one row per result
+    pub fn text(self, result: &SearchResult) -> String {
+        match self {
+            SearchField::Name => result.name.clone(),
+            SearchField::Kind => format!("{:?}", result.kind),
+            SearchField::File => display_path(&result.file_path).into_owned(),
+            SearchField::Line => result.line.to_string(),
+            SearchField::Column => result.column.to_string(),
+            SearchField::Module => result.module_path.clone(),
+            SearchField::Doc => result
+                .doc_comment
+                .as_deref()
+                .and_then(|doc| doc.lines().next())
+                .unwrap_or("")
+                .to_string(),
+            SearchField::Signature => result.signature.clone().unwrap_or_default(),
+            SearchField::Score => format!("{:.2}", result.score),
+        }
+    }
+
+    fn json(self, result: &SearchResult) -> serde_json::Value {
+        match self {
+            SearchField::Name => result.name.clone().into(),
+            SearchField::Kind => serde_json::to_value(result.kind).unwrap_or_default(),
+            SearchField::File => display_path(&result.file_path).into_owned().into(),
+            SearchField::Line => result.line.into(),
+            SearchField::Column => result.column.into(),
+            SearchField::Module => result.module_path.clone().into(),
+            SearchField::Doc => result.doc_comment.clone().into(),
+            SearchField::Signature => result.signature.clone().into(),
+            SearchField::Score => result.score.into(),
+        }
+    }
+}
+
+/// Parse field lists such as `["name,file", "line"]`
+///
+/// Entries may hold several comma-separated names; blanks are ignored and
+/// duplicates are kept once, in first-seen order.
+pub fn parse_fields<S: AsRef<str>>(lists: &[S]) -> Result<Vec<SearchField>, String> {
+    let mut fields = Vec::new();
+    for name in lists
+        .iter()
+        .flat_map(|list| list.as_ref().split(','))
+        .filter(|name| !name.trim().is_empty())
+    {
+        let field = SearchField::parse(name)?;
+        if !fields.contains(&field) {
+            fields.push(field);
+        }
+    }
+    Ok(fields)
+}
+
+/// One search result restricted to the selected fields
+#[derive(Debug, Clone)]
+pub struct FieldRow {
+    fields: Vec<SearchField>,
+    result: SearchResult,
+}
+
+impl FieldRow {
+    pub fn new(fields: &[SearchField], result: SearchResult) -> Self {
+        Self {
+            fields: fields.to_vec(),
+            result,
+        }
+    }
+}
+
+impl fmt::Display for FieldRow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let values: Vec<String> = self
+            .fields
+            .iter()
+            .map(|field| field.text(&self.result))
+            .collect();
+        write!(f, "{}", values.join("\t"))
+    }
+}
+
+impl Serialize for FieldRow {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.fields.len()))?;
+        for field in &self.fields {
+            map.serialize_entry(field.name(), &field.json(&self.result))?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fields_accepts_lists_and_dedupes() {
+        let fields = parse_fields(&["name, FILE", "line,name", ""]).unwrap();
+        assert_eq!(
+            fields,
+            vec![SearchField::Name, SearchField::File, SearchField::Line]
+        );
+    }
+
+    #[test]
+    fn test_unknown_field_lists_valid_names() {
+        let err = parse_fields(&["name,path"]).unwrap_err();
+        assert_eq!(
+            err,
+            "Unknown field 'path'. Valid fields: name, kind, file, line, column, module, doc, signature, score"
+        );
+    }
+}
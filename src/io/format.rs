@@ -9,15 +9,36 @@ use chrono::Utc;
 use serde::{Deserialize, Serialize};
 
 /// Output format for CLI commands.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum OutputFormat {
     /// Human-readable text (default)
     Text,
     /// JSON for tool integration
     Json,
+    /// Text, with call hierarchies drawn as an ASCII tree
+    Tree,
+    /// Text, with analysis findings as GitHub Actions annotations
+    GithubAnnotations,
     // Future: Yaml, Xml, etc.
 }
 
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "tree" => Ok(Self::Tree),
+            "github-annotations" => Ok(Self::GithubAnnotations),
+            other => Err(format!(
+                "Unknown output format '{other}'. Expected 'text', 'json', 'tree' or 'github-annotations'"
+            )),
+        }
+    }
+}
+
 impl OutputFormat {
     /// Create format from JSON flag.
     #[must_use]
@@ -30,13 +51,43 @@ impl OutputFormat {
     pub fn is_json(&self) -> bool {
         matches!(self, Self::Json)
     }
+
+    /// Name as written on the command line.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Text => "text",
+            Self::Json => "json",
+            Self::Tree => "tree",
+            Self::GithubAnnotations => "github-annotations",
+        }
+    }
+
+    /// Fail unless the format is one a command supports.
+    ///
+    /// Commands take `--format` values that only make sense for other
+    /// commands; rejecting them beats silently printing text.
+    pub fn supported_by(self, command: &str, supported: &[Self]) -> Result<Self, String> {
+        if supported.contains(&self) {
+            return Ok(self);
+        }
+        let names: Vec<String> = supported
+            .iter()
+            .map(|format| format!("'{}'", format.as_str()))
+            .collect();
+        Err(format!(
+            "Output format '{}' is not supported by '{command}'. Expected {}",
+            self.as_str(),
+            names.join(", ")
+        ))
+    }
 }
 
 /// Standard JSON response format.
 ///
 /// Compatible with JSON-RPC 2.0 structure for future tool integration.
 /// Provides consistent structure for both success and error responses.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct JsonResponse<T = serde_json::Value>
 where
     T: Serialize,
@@ -71,7 +122,7 @@ where
 }
 
 /// Error details for JSON responses.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct ErrorDetails {
     /// Recovery suggestions
     pub suggestions: Vec<String>,
@@ -81,7 +132,7 @@ pub struct ErrorDetails {
 }
 
 /// Response metadata.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct ResponseMeta {
     /// Version of the tool
     pub version: String,
@@ -202,6 +253,29 @@ pub fn format_utc_timestamp() -> String {
     now.format("%Y-%m-%d %H:%M:%S UTC").to_string()
 }
 
+/// Format a byte count with a binary unit.
+///
+/// # Example
+/// ```
+/// use codanna::io::format::format_bytes;
+///
+/// assert_eq!(format_bytes(512), "512 B");
+/// assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+/// ```
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -212,6 +286,21 @@ mod tests {
         assert_eq!(OutputFormat::from_json_flag(false), OutputFormat::Text);
     }
 
+    #[test]
+    fn test_output_format_supported_by() {
+        let tree = [OutputFormat::Text, OutputFormat::Json, OutputFormat::Tree];
+        assert_eq!(
+            OutputFormat::Tree.supported_by("retrieve calls", &tree),
+            Ok(OutputFormat::Tree)
+        );
+        assert_eq!(
+            OutputFormat::GithubAnnotations
+                .supported_by("retrieve calls", &tree)
+                .unwrap_err(),
+            "Output format 'github-annotations' is not supported by 'retrieve calls'. Expected 'text', 'json', 'tree'"
+        );
+    }
+
     #[test]
     fn test_json_response_success() {
         #[derive(Serialize)]
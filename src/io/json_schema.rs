@@ -0,0 +1,132 @@
+//! JSON Schemas for `--json` output (`codanna schema`)
+//!
+//! The schemas are derived from the serde types that produce the output, so
+//! they change together with it. `codanna mcp <tool> --json` wraps each tool's
+//! data in a [`JsonResponse`]; `retrieve` commands emit a [`UnifiedOutput`].
+//! Only the success shape is described: not-found and error responses carry
+//! no `data`, and empty results are empty lists.
+
+use crate::Symbol;
+use crate::indexing::{ImportResolution, MethodImplementation, OccurrenceLocation, SymbolAt};
+use crate::io::format::JsonResponse;
+use crate::io::schema::UnifiedOutput;
+use crate::relationship::{Provenance, RelationshipMetadata};
+use crate::semantic::{SemanticSearchResult, SemanticSearchWithContextResult};
+use crate::storage::SearchResult;
+use crate::symbol::context::{SymbolContext, TypeHierarchy};
+use schemars::schema_for;
+use serde_json::{Map, Value};
+
+/// A `get_calls` or `find_callers` entry: `[symbol, metadata, provenance]`
+type CallEdge = (Symbol, Option<RelationshipMetadata>, Provenance);
+
+/// MCP tools whose `--json` output has a schema, alphabetically
+pub const SCHEMA_TOOLS: &[&str] = &[
+    "analyze_impact",
+    "find_callers",
+    "find_implementors_of_method",
+    "find_occurrences",
+    "find_symbol",
+    "get_calls",
+    "get_type_hierarchy",
+    "resolve_import",
+    "search_symbols",
+    "semantic_search_docs",
+    "semantic_search_with_context",
+    "symbol_at",
+];
+
+/// Schema of `codanna mcp <tool> --json` output, `None` for tools not in [`SCHEMA_TOOLS`]
+pub fn tool_response_schema(tool: &str) -> Option<Value> {
+    let schema = match tool {
+        "find_symbol" => schema_for!(JsonResponse<Vec<SymbolContext>>),
+        "get_calls" | "find_callers" => schema_for!(JsonResponse<Vec<CallEdge>>),
+        "analyze_impact" => schema_for!(JsonResponse<Vec<Symbol>>),
+        "search_symbols" => schema_for!(JsonResponse<Vec<SearchResult>>),
+        "semantic_search_docs" => schema_for!(JsonResponse<Vec<SemanticSearchResult>>),
+        "semantic_search_with_context" => {
+            schema_for!(JsonResponse<Vec<SemanticSearchWithContextResult>>)
+        }
+        "find_occurrences" => schema_for!(JsonResponse<Vec<OccurrenceLocation>>),
+        "get_type_hierarchy" => schema_for!(JsonResponse<TypeHierarchy>),
+        "resolve_import" => schema_for!(JsonResponse<ImportResolution>),
+        "symbol_at" => schema_for!(JsonResponse<SymbolAt>),
+        "find_implementors_of_method" => schema_for!(JsonResponse<Vec<MethodImplementation>>),
+        _ => return None,
+    };
+    Some(schema.to_value())
+}
+
+/// Schemas of the output data model, keyed by type name
+///
+/// `JsonResponse` leaves `data` open; `UnifiedOutput` is shown with the
+/// [`SymbolContext`] items that `retrieve symbol`, `describe` and
+/// `implementations` emit.
+pub fn data_model_schemas() -> Map<String, Value> {
+    let mut schemas = Map::new();
+    schemas.insert("JsonResponse".into(), schema_for!(JsonResponse).to_value());
+    schemas.insert(
+        "UnifiedOutput".into(),
+        schema_for!(UnifiedOutput<'static, SymbolContext>).to_value(),
+    );
+    schemas.insert(
+        "SymbolContext".into(),
+        schema_for!(SymbolContext).to_value(),
+    );
+    schemas.insert("SearchResult".into(), schema_for!(SearchResult).to_value());
+    schemas.insert("Symbol".into(), schema_for!(Symbol).to_value());
+    schemas
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FileId, Range, SymbolId, SymbolKind};
+
+    fn property_names(schema: &Value) -> Vec<&str> {
+        let mut names: Vec<&str> = schema["properties"]
+            .as_object()
+            .map(|properties| properties.keys().map(String::as_str).collect())
+            .unwrap_or_default();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn test_tool_response_schema() {
+        for tool in SCHEMA_TOOLS {
+            let schema = tool_response_schema(tool)
+                .unwrap_or_else(|| panic!("{tool} should have a response schema"));
+            for field in ["status", "code", "message", "data", "exit_code"] {
+                assert!(
+                    property_names(&schema).contains(&field),
+                    "{tool} response should describe '{field}'"
+                );
+            }
+        }
+        assert!(tool_response_schema("get_index_info").is_none());
+        assert!(tool_response_schema("unknown").is_none());
+    }
+
+    #[test]
+    fn test_symbol_schema_matches_serialized_fields() {
+        let mut symbol = Symbol::new(
+            SymbolId::new(1).unwrap(),
+            "parse",
+            SymbolKind::Function,
+            FileId::new(1).unwrap(),
+            Range::new(1, 0, 3, 1),
+        );
+        symbol.attributes = vec!["#[inline]".into()];
+        let serialized = serde_json::to_value(&symbol).unwrap();
+
+        let schemas = data_model_schemas();
+        let described = property_names(&schemas["Symbol"]);
+        for field in serialized.as_object().unwrap().keys() {
+            assert!(
+                described.contains(&field.as_str()),
+                "Symbol schema should describe '{field}'"
+            );
+        }
+    }
+}
@@ -0,0 +1,184 @@
+//! Operational log output for long-running commands.
+//!
+//! By default messages are printed to stderr as plain text. With
+//! `--log-format json` (or `server.log_format = "json"`), a `tracing`
+//! subscriber writes one JSON object per line to stderr with the timestamp,
+//! level, target and fields of each event, for ingestion by log collectors.
+//!
+//! Print sites use the `log_info!`, `log_warn!`, `log_error!` and `log_debug!`
+//! macros, which pick the active format at runtime. Structured fields go
+//! before the message, separated by `;`:
+//!
+//! ```ignore
+//! log_info!(file = path.display().to_string(), duration_ms = ms; "Re-indexed {}", path.display());
+//! ```
+//!
+//! JSON output carries them as separate fields next to `message`; text output
+//! prints only the message.
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::Level;
+
+static JSON_LOGS: AtomicBool = AtomicBool::new(false);
+
+/// Format of operational log output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// Human-readable lines on stderr (default)
+    #[default]
+    Text,
+    /// JSON lines on stderr via `tracing`
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            other => Err(format!(
+                "Unknown log format '{other}'. Expected 'text' or 'json'"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Text => write!(f, "text"),
+            Self::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// Install the log output for this process.
+///
+/// For JSON, events from codanna at `max_level` or more severe are written to
+/// stderr. Text output needs no setup. Calling this more than once keeps the
+/// first subscriber.
+pub fn init(format: LogFormat, max_level: Level) {
+    use tracing_subscriber::filter::Targets;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    if format != LogFormat::Json {
+        return;
+    }
+
+    let subscriber = tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer()
+                .json()
+                .with_writer(std::io::stderr),
+        )
+        .with(Targets::new().with_target("codanna", max_level));
+
+    if tracing::subscriber::set_global_default(subscriber).is_ok() {
+        JSON_LOGS.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Whether log macros emit JSON through `tracing`.
+pub fn is_json() -> bool {
+    JSON_LOGS.load(Ordering::Relaxed)
+}
+
+/// Log an informational message; suppressed by `--quiet`.
+#[macro_export]
+macro_rules! log_info {
+    () => {
+        if !$crate::io::logging::is_json() {
+            $crate::info_eprintln!();
+        }
+    };
+    ($($field:ident = $value:expr),+ ; $($arg:tt)+) => {
+        if $crate::io::logging::is_json() {
+            ::tracing::info!($($field = $value),+, $($arg)+);
+        } else {
+            $crate::info_eprintln!($($arg)+);
+        }
+    };
+    ($($arg:tt)*) => {
+        if $crate::io::logging::is_json() {
+            ::tracing::info!($($arg)*);
+        } else {
+            $crate::info_eprintln!($($arg)*);
+        }
+    };
+}
+
+/// Log a warning.
+#[macro_export]
+macro_rules! log_warn {
+    ($($field:ident = $value:expr),+ ; $($arg:tt)+) => {
+        if $crate::io::logging::is_json() {
+            ::tracing::warn!($($field = $value),+, $($arg)+);
+        } else {
+            eprintln!($($arg)+);
+        }
+    };
+    ($($arg:tt)*) => {
+        if $crate::io::logging::is_json() {
+            ::tracing::warn!($($arg)*);
+        } else {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/// Log an error.
+#[macro_export]
+macro_rules! log_error {
+    ($($field:ident = $value:expr),+ ; $($arg:tt)+) => {
+        if $crate::io::logging::is_json() {
+            ::tracing::error!($($field = $value),+, $($arg)+);
+        } else {
+            eprintln!($($arg)+);
+        }
+    };
+    ($($arg:tt)*) => {
+        if $crate::io::logging::is_json() {
+            ::tracing::error!($($arg)*);
+        } else {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/// Log a debug message; text output is prefixed with `DEBUG:`.
+///
+/// Callers still decide whether debug output is enabled.
+#[macro_export]
+macro_rules! log_debug {
+    ($($field:ident = $value:expr),+ ; $($arg:tt)+) => {
+        if $crate::io::logging::is_json() {
+            ::tracing::debug!($($field = $value),+, $($arg)+);
+        } else {
+            eprintln!("DEBUG: {}", format_args!($($arg)+));
+        }
+    };
+    ($($arg:tt)*) => {
+        if $crate::io::logging::is_json() {
+            ::tracing::debug!($($arg)*);
+        } else {
+            eprintln!("DEBUG: {}", format_args!($($arg)*));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_format_parsing() {
+        assert_eq!("text".parse::<LogFormat>().unwrap(), LogFormat::Text);
+        assert_eq!("json".parse::<LogFormat>().unwrap(), LogFormat::Json);
+        assert!("yaml".parse::<LogFormat>().is_err());
+        assert_eq!(LogFormat::default().to_string(), "text");
+    }
+}
@@ -0,0 +1,125 @@
+//! Markdown rendering for reports meant to be pasted into PRs and docs.
+
+use crate::Symbol;
+use std::collections::BTreeMap;
+
+/// Build a link to a symbol's definition line.
+///
+/// Uses `repo_url` as the base when set, otherwise the indexed (relative) path.
+pub fn symbol_link(symbol: &Symbol, repo_url: Option<&str>) -> String {
+    let path = symbol.file_path.trim_start_matches("./");
+    let line = symbol.range.start_line + 1;
+    match repo_url {
+        Some(base) => format!("{}/{path}#L{line}", base.trim_end_matches('/')),
+        None => format!("{path}#L{line}"),
+    }
+}
+
+/// Render an impact analysis as Markdown.
+///
+/// Produces a summary table of impacted symbol counts by kind followed by
+/// the impacted symbols grouped by kind, each linked to its definition.
+pub fn impact_report(
+    target: &Symbol,
+    impacted: &[Symbol],
+    max_depth: u32,
+    repo_url: Option<&str>,
+) -> String {
+    let mut out = format!(
+        "## Impact of `{}`\n\n{:?} [`{}`]({})",
+        target.name,
+        target.kind,
+        target.name,
+        symbol_link(target, repo_url)
+    );
+
+    if impacted.is_empty() {
+        out.push_str(" has no impacted symbols.\n");
+        return out;
+    }
+
+    out.push_str(&format!(
+        " impacts **{}** symbol(s) (max depth: {max_depth}).\n",
+        impacted.len()
+    ));
+
+    let mut by_kind: BTreeMap<String, Vec<&Symbol>> = BTreeMap::new();
+    for symbol in impacted {
+        by_kind
+            .entry(format!("{:?}", symbol.kind))
+            .or_default()
+            .push(symbol);
+    }
+
+    out.push_str("\n| Kind | Count |\n|------|------:|\n");
+    for (kind, symbols) in &by_kind {
+        out.push_str(&format!("| {kind} | {} |\n", symbols.len()));
+    }
+
+    for (kind, mut symbols) in by_kind {
+        symbols.sort_by(|a, b| {
+            a.file_path
+                .cmp(&b.file_path)
+                .then(a.range.start_line.cmp(&b.range.start_line))
+        });
+        out.push_str(&format!("\n### {kind}\n\n"));
+        for symbol in symbols {
+            out.push_str(&format!(
+                "- [`{}`]({})\n",
+                symbol.name,
+                symbol_link(symbol, repo_url)
+            ));
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FileId, Range, SymbolId, SymbolKind};
+
+    fn symbol(id: u32, name: &str, kind: SymbolKind, file: &str, line: u32) -> Symbol {
+        Symbol::new(
+            SymbolId::new(id).unwrap(),
+            name,
+            kind,
+            FileId::new(1).unwrap(),
+            Range::new(line, 0, line + 2, 1),
+        )
+        .with_file_path(file)
+    }
+
+    #[test]
+    fn test_impact_report_markdown() {
+        let target = symbol(1, "Config", SymbolKind::Struct, "src/config.rs", 9);
+        let impacted = vec![
+            symbol(2, "load", SymbolKind::Function, "src/load.rs", 4),
+            symbol(3, "save", SymbolKind::Function, "src/save.rs", 0),
+            symbol(4, "App", SymbolKind::Struct, "src/app.rs", 19),
+        ];
+
+        let report = impact_report(&target, &impacted, 3, None);
+        assert!(report.starts_with("## Impact of `Config`"));
+        assert!(report.contains("[`Config`](src/config.rs#L10)"));
+        assert!(report.contains("impacts **3** symbol(s) (max depth: 3)"));
+        assert!(report.contains("| Function | 2 |"));
+        assert!(report.contains("| Struct | 1 |"));
+        assert!(
+            report
+                .contains("### Function\n\n- [`load`](src/load.rs#L5)\n- [`save`](src/save.rs#L1)")
+        );
+
+        let linked = impact_report(
+            &target,
+            &impacted,
+            3,
+            Some("https://github.com/org/repo/blob/main/"),
+        );
+        assert!(linked.contains("[`App`](https://github.com/org/repo/blob/main/src/app.rs#L20)"));
+
+        let empty = impact_report(&target, &[], 3, None);
+        assert!(empty.contains("has no impacted symbols"));
+    }
+}
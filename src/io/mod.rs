@@ -1,26 +1,35 @@
 //! Input/Output handling for CLI and tool integration.
 //!
 //! This module provides:
-//! - Unified output formatting (text, JSON)
+//! - Unified output formatting (text, JSON, Markdown reports)
 //! - Consistent error handling and exit codes
 //! - Future: JSON-RPC 2.0 support for IDE integration
 
+pub mod annotations;
 pub mod args;
 pub mod exit_code;
+pub mod fields;
 pub mod format;
 pub mod guidance;
 pub mod guidance_engine;
 pub mod input;
+pub mod json_schema;
+pub mod logging;
+pub mod markdown;
 pub mod output;
 pub mod parse;
+pub mod query_socket;
 pub mod schema;
 pub mod status_line;
 #[cfg(test)]
 mod test;
 
+pub use annotations::{Annotation, AnnotationLevel};
 pub use exit_code::ExitCode;
+pub use fields::{FieldRow, SearchField, parse_fields};
 pub use format::{ErrorDetails, JsonResponse, OutputFormat, ResponseMeta};
-pub use output::OutputManager;
+pub use logging::LogFormat;
+pub use output::{CapturedOutput, OutputManager, capture_output};
 pub use schema::{EntityType, OutputData, OutputStatus, UnifiedOutput, UnifiedOutputBuilder};
 pub use status_line::{ProgressBar, ProgressBarOptions, ProgressBarStyle, Spinner, SpinnerOptions};
 // Future: pub use input::{JsonRpcRequest, JsonRpcResponse};
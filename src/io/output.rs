@@ -4,12 +4,80 @@
 //! providing a unified interface for text and JSON output.
 
 use crate::error::IndexError;
+use crate::io::annotations::Annotation;
 use crate::io::exit_code::ExitCode;
 use crate::io::format::{JsonResponse, OutputFormat};
 use crate::io::schema::{OutputData, UnifiedOutput};
 use serde::Serialize;
-use std::fmt::Display;
+use std::cell::RefCell;
+use std::fmt::{self, Display};
 use std::io::{self, Write};
+use std::rc::Rc;
+
+thread_local! {
+    /// Buffers that replace stdout and stderr while `capture_output` runs
+    static CAPTURE: RefCell<Option<(SharedBuffer, SharedBuffer)>> = const { RefCell::new(None) };
+}
+
+/// A byte buffer shared between the capture scope and the writers it hands out
+#[derive(Clone, Default)]
+struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+impl SharedBuffer {
+    fn take_string(&self) -> String {
+        String::from_utf8_lossy(&self.0.take()).into_owned()
+    }
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Output written by a command run under `capture_output`
+#[derive(Debug, Clone, Default)]
+pub struct CapturedOutput {
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// Run `f` with `OutputManager` output and `output_eprintln!` diagnostics
+/// collected in memory instead of written to the process streams.
+///
+/// Used to answer queries on behalf of another process. Capture applies to
+/// the current thread only.
+pub fn capture_output<R>(f: impl FnOnce() -> R) -> (R, CapturedOutput) {
+    let (stdout, stderr) = (SharedBuffer::default(), SharedBuffer::default());
+    let previous = CAPTURE.with(|capture| capture.replace(Some((stdout.clone(), stderr.clone()))));
+    let result = f();
+    CAPTURE.with(|capture| *capture.borrow_mut() = previous);
+
+    let captured = CapturedOutput {
+        stdout: stdout.take_string(),
+        stderr: stderr.take_string(),
+    };
+    (result, captured)
+}
+
+/// Write a diagnostic line to stderr, or to the capture buffer when capturing.
+///
+/// Prefer the `output_eprintln!` macro.
+#[doc(hidden)]
+pub fn write_stderr_line(args: fmt::Arguments<'_>) {
+    let stderr = CAPTURE.with(|capture| capture.borrow().as_ref().map(|(_, err)| err.clone()));
+    match stderr {
+        Some(mut stderr) => {
+            let _ = writeln!(stderr, "{args}");
+        }
+        None => eprintln!("{args}"),
+    }
+}
 
 /// Manages output formatting and display.
 ///
@@ -23,11 +91,19 @@ pub struct OutputManager {
 
 impl OutputManager {
     /// Create a new output manager with the specified format.
+    ///
+    /// Inside `capture_output`, writes go to the capture buffers.
     pub fn new(format: OutputFormat) -> Self {
-        Self {
-            format,
-            stdout: Box::new(io::stdout()),
-            stderr: Box::new(io::stderr()),
+        let captured = CAPTURE.with(|capture| capture.borrow().clone());
+        match captured {
+            Some((stdout, stderr)) => {
+                Self::new_with_writers(format, Box::new(stdout), Box::new(stderr))
+            }
+            None => Self {
+                format,
+                stdout: Box::new(io::stdout()),
+                stderr: Box::new(io::stderr()),
+            },
         }
     }
 
@@ -77,7 +153,7 @@ impl OutputManager {
                 let json_str = serde_json::to_string_pretty(&response)?;
                 Self::write_ignoring_broken_pipe(&mut *self.stdout, &json_str)?;
             }
-            OutputFormat::Text => {
+            OutputFormat::Text | OutputFormat::Tree | OutputFormat::GithubAnnotations => {
                 let text = format!("{data}");
                 Self::write_ignoring_broken_pipe(&mut *self.stdout, &text)?;
             }
@@ -109,7 +185,7 @@ impl OutputManager {
                 let json_str = serde_json::to_string_pretty(&response)?;
                 Self::write_ignoring_broken_pipe(&mut *self.stdout, &json_str)?;
             }
-            OutputFormat::Text => {
+            OutputFormat::Text | OutputFormat::Tree | OutputFormat::GithubAnnotations => {
                 let text = format!("{entity} '{name}' not found");
                 Self::write_ignoring_broken_pipe(&mut *self.stderr, &text)?;
             }
@@ -139,7 +215,7 @@ impl OutputManager {
                 let json_str = serde_json::to_string_pretty(&response)?;
                 Self::write_ignoring_broken_pipe(&mut *self.stdout, &json_str)?;
             }
-            OutputFormat::Text => {
+            OutputFormat::Text | OutputFormat::Tree | OutputFormat::GithubAnnotations => {
                 let header = format!("Found {} {entity_name}:", items.len());
                 Self::write_ignoring_broken_pipe(&mut *self.stdout, &header)?;
                 Self::write_ignoring_broken_pipe(&mut *self.stdout, &"=".repeat(40))?;
@@ -162,7 +238,7 @@ impl OutputManager {
                 let json_str = serde_json::to_string_pretty(&response)?;
                 Self::write_ignoring_broken_pipe(&mut *self.stderr, &json_str)?;
             }
-            OutputFormat::Text => {
+            OutputFormat::Text | OutputFormat::Tree | OutputFormat::GithubAnnotations => {
                 let error_msg = format!("Error: {error}");
                 Self::write_ignoring_broken_pipe(&mut *self.stderr, &error_msg)?;
                 for suggestion in error.recovery_suggestions() {
@@ -180,7 +256,10 @@ impl OutputManager {
     /// polluting the JSON output.
     /// Broken pipe errors are silently ignored.
     pub fn progress(&mut self, message: &str) -> io::Result<()> {
-        if matches!(self.format, OutputFormat::Text) {
+        if matches!(
+            self.format,
+            OutputFormat::Text | OutputFormat::Tree | OutputFormat::GithubAnnotations
+        ) {
             Self::write_ignoring_broken_pipe(&mut *self.stderr, message)?;
         }
         Ok(())
@@ -189,12 +268,24 @@ impl OutputManager {
     /// Output informational message (text mode only).
     /// Broken pipe errors are silently ignored.
     pub fn info(&mut self, message: &str) -> io::Result<()> {
-        if matches!(self.format, OutputFormat::Text) {
+        if matches!(
+            self.format,
+            OutputFormat::Text | OutputFormat::Tree | OutputFormat::GithubAnnotations
+        ) {
             Self::write_ignoring_broken_pipe(&mut *self.stdout, message)?;
         }
         Ok(())
     }
 
+    /// Output analysis findings as GitHub Actions annotations, one per line.
+    /// Broken pipe errors are silently ignored.
+    pub fn annotations(&mut self, annotations: &[Annotation]) -> io::Result<()> {
+        for annotation in annotations {
+            Self::write_ignoring_broken_pipe(&mut *self.stdout, &annotation.to_string())?;
+        }
+        Ok(())
+    }
+
     /// Output a collection of SymbolContext items.
     ///
     /// This method is specifically designed for SymbolContext to ensure
@@ -224,7 +315,7 @@ impl OutputManager {
                 let json_str = serde_json::to_string_pretty(&response)?;
                 Self::write_ignoring_broken_pipe(&mut *self.stdout, &json_str)?;
             }
-            OutputFormat::Text => {
+            OutputFormat::Text | OutputFormat::Tree | OutputFormat::GithubAnnotations => {
                 let header = format!("Found {} {}:", contexts.len(), entity_name);
                 Self::write_ignoring_broken_pipe(&mut *self.stdout, &header)?;
                 Self::write_ignoring_broken_pipe(&mut *self.stdout, &"=".repeat(40))?;
@@ -267,7 +358,7 @@ impl OutputManager {
                 let json_str = serde_json::to_string_pretty(&output)?;
                 Self::write_ignoring_broken_pipe(&mut *self.stdout, &json_str)?;
             }
-            OutputFormat::Text => {
+            OutputFormat::Text | OutputFormat::Tree | OutputFormat::GithubAnnotations => {
                 // For text, check if we have special handling needs
                 match &output.data {
                     OutputData::Empty => {
@@ -532,4 +623,22 @@ mod tests {
             .unwrap();
         assert_eq!(code, ExitCode::NotFound);
     }
+
+    #[test]
+    fn test_capture_output_collects_streams() {
+        let (code, captured) = capture_output(|| {
+            crate::output_eprintln!("Ambiguous: {}", "parse");
+            OutputManager::new(OutputFormat::Text)
+                .not_found("Symbol", "missing")
+                .unwrap();
+            OutputManager::new(OutputFormat::Text)
+                .success("found")
+                .unwrap()
+        });
+
+        assert_eq!(code, ExitCode::Success);
+        assert_eq!(captured.stdout, "found\n");
+        assert!(captured.stderr.starts_with("Ambiguous: parse\n"));
+        assert!(captured.stderr.contains("missing"));
+    }
 }
@@ -3,6 +3,7 @@
 //! Outputs tree-sitter AST nodes in JSON Lines format for external analysis.
 
 use crate::io::ExitCode;
+use crate::parsing::parser::parse_tree;
 use serde::Serialize;
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
@@ -268,7 +269,7 @@ pub fn execute_parse(
         .map_err(|e| ParseError::LanguageSetupError { source: e })?;
 
     // Parse the code
-    let tree = parser.parse(&code, None).ok_or(ParseError::ParseFailure)?;
+    let tree = parse_tree(&mut parser, &code).ok_or(ParseError::ParseFailure)?;
 
     // Create output handler
     let mut output_handler = ParseOutput::new(output_path)?;
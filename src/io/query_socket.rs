@@ -0,0 +1,225 @@
+//! Query socket: answer retrieve queries from a process that keeps the index loaded
+//!
+//! `codanna serve --query-socket <path>` loads the index once and serves
+//! queries on a Unix socket, so a shell loop of `codanna retrieve` calls does
+//! not reload the index for every call. The protocol is one JSON line per
+//! request and one per response, one exchange per connection. The CLI sends
+//! retrieve commands to the socket named by `CODANNA_SOCKET` and falls back
+//! to loading the index itself when no daemon answers.
+
+use crate::paths::PathStyle;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Environment variable naming the socket the CLI should try first
+pub const SOCKET_ENV: &str = "CODANNA_SOCKET";
+
+/// How long the daemon waits on a client to send its request or read the answer
+pub const CLIENT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A query sent to the daemon
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryRequest {
+    /// Index the client would load; the daemon rejects queries for another index
+    pub index_path: PathBuf,
+    /// The command to run, as serialized by the CLI
+    pub query: serde_json::Value,
+    /// The client's output settings, applied while the query runs
+    #[serde(default)]
+    pub display: QueryDisplay,
+}
+
+/// Output flags of the client, which the daemon's own flags must not replace
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueryDisplay {
+    /// `--quiet`: drop informational stderr lines
+    pub quiet: bool,
+    /// `--color` resolved against the client's terminal and `NO_COLOR`
+    pub color: bool,
+    /// `display.path_style` after `--absolute-paths` / `--relative-paths`
+    pub path_style: PathStyle,
+}
+
+/// The daemon's answer to a query
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum QueryResponse {
+    /// The query ran; replay its output and exit code
+    Ok {
+        exit_code: i32,
+        stdout: String,
+        stderr: String,
+    },
+    /// The daemon cannot answer this query; the client should run it itself
+    Rejected { reason: String },
+}
+
+/// Serve queries on `path` until the process exits
+///
+/// A stale socket file left by a daemon that is no longer running is
+/// replaced; a live one is an `AddrInUse` error. The socket is made readable
+/// and writable by its owner only (mode 0600), since queries can read any
+/// indexed source. Connections are handled one
+/// at a time, so `handle` never runs concurrently; a client that does not
+/// send its request within [`CLIENT_TIMEOUT`] is dropped so it cannot hold
+/// up the others.
+#[cfg(unix)]
+pub fn serve(path: &Path, mut handle: impl FnMut(QueryRequest) -> QueryResponse) -> io::Result<()> {
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::fs::PermissionsExt;
+    use std::os::unix::net::{UnixListener, UnixStream};
+
+    if path.exists() {
+        if UnixStream::connect(path).is_ok() {
+            return Err(io::Error::new(
+                io::ErrorKind::AddrInUse,
+                format!("a query daemon is already listening on {}", path.display()),
+            ));
+        }
+        std::fs::remove_file(path)?;
+    }
+    let listener = UnixListener::bind(path)?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+
+    for stream in listener.incoming() {
+        let mut stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("Warning: Failed to accept query connection: {e}");
+                continue;
+            }
+        };
+
+        if let Err(e) = stream
+            .set_read_timeout(Some(CLIENT_TIMEOUT))
+            .and_then(|()| stream.set_write_timeout(Some(CLIENT_TIMEOUT)))
+        {
+            eprintln!("Warning: Failed to set query connection timeout: {e}");
+            continue;
+        }
+
+        let mut line = String::new();
+        if let Err(e) = BufReader::new(&stream).read_line(&mut line) {
+            eprintln!("Warning: Failed to read query: {e}");
+            continue;
+        }
+        let response = match serde_json::from_str::<QueryRequest>(&line) {
+            Ok(request) => handle(request),
+            Err(e) => QueryResponse::Rejected {
+                reason: format!("invalid request: {e}"),
+            },
+        };
+
+        let mut payload = serde_json::to_string(&response).map_err(io::Error::other)?;
+        payload.push('\n');
+        if let Err(e) = stream.write_all(payload.as_bytes()) {
+            eprintln!("Warning: Failed to send query response: {e}");
+        }
+    }
+    Ok(())
+}
+
+/// Send one query to the daemon listening on `path`
+#[cfg(unix)]
+pub fn send(path: &Path, request: &QueryRequest) -> io::Result<QueryResponse> {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::Shutdown;
+    use std::os::unix::net::UnixStream;
+
+    let mut stream = UnixStream::connect(path)?;
+    let mut payload = serde_json::to_string(request).map_err(io::Error::other)?;
+    payload.push('\n');
+    stream.write_all(payload.as_bytes())?;
+    stream.shutdown(Shutdown::Write)?;
+
+    let mut line = String::new();
+    BufReader::new(&stream).read_line(&mut line)?;
+    serde_json::from_str(&line).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+#[cfg(not(unix))]
+pub fn serve(_path: &Path, _handle: impl FnMut(QueryRequest) -> QueryResponse) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "the query socket requires Unix domain sockets",
+    ))
+}
+
+#[cfg(not(unix))]
+pub fn send(_path: &Path, _request: &QueryRequest) -> io::Result<QueryResponse> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "the query socket requires Unix domain sockets",
+    ))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_query_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let socket = temp_dir.path().join("query.sock");
+
+        let server_socket = socket.clone();
+        std::thread::spawn(move || {
+            serve(&server_socket, |request| {
+                if request.index_path != Path::new("/index") {
+                    return QueryResponse::Rejected {
+                        reason: "different index".to_string(),
+                    };
+                }
+                QueryResponse::Ok {
+                    exit_code: 3,
+                    stdout: request.query.to_string(),
+                    stderr: String::new(),
+                }
+            })
+        });
+
+        let request = |index: &str| QueryRequest {
+            index_path: PathBuf::from(index),
+            query: serde_json::json!({"symbol": "main"}),
+            display: QueryDisplay::default(),
+        };
+        let mut response = send(&socket, &request("/index"));
+        for _ in 0..100 {
+            if response.is_ok() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            response = send(&socket, &request("/index"));
+        }
+
+        // Only the owner can connect
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&socket).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        assert_eq!(
+            response.unwrap(),
+            QueryResponse::Ok {
+                exit_code: 3,
+                stdout: r#"{"symbol":"main"}"#.to_string(),
+                stderr: String::new(),
+            }
+        );
+        assert!(matches!(
+            send(&socket, &request("/other")).unwrap(),
+            QueryResponse::Rejected { .. }
+        ));
+
+        // A client that connects and never sends a request times out
+        let _stalled = std::os::unix::net::UnixStream::connect(&socket).unwrap();
+        let started = std::time::Instant::now();
+        assert!(matches!(
+            send(&socket, &request("/index")).unwrap(),
+            QueryResponse::Ok { .. }
+        ));
+        assert!(started.elapsed() < CLIENT_TIMEOUT * 3);
+    }
+}
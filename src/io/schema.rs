@@ -14,7 +14,7 @@ use std::fmt;
 /// Unified output structure that adapts to different data shapes
 ///
 /// Uses borrowed types to avoid allocations when piping output
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
 pub struct UnifiedOutput<'a, T> {
     /// Status of the operation
     pub status: OutputStatus,
@@ -43,7 +43,7 @@ pub struct UnifiedOutput<'a, T> {
 }
 
 /// Status of the operation
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum OutputStatus {
     Success,
@@ -53,7 +53,7 @@ pub enum OutputStatus {
 }
 
 /// Type of entities being output
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum EntityType {
     Symbol,
@@ -72,7 +72,7 @@ pub enum EntityType {
 /// Different shapes of output data
 ///
 /// Uses generic lifetime 'a to borrow strings without allocation
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 #[serde(untagged)]
 pub enum OutputData<'a, T> {
     /// Simple list of items (most common case)
@@ -97,7 +97,7 @@ pub enum OutputData<'a, T> {
 }
 
 /// Item with additional context information
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct ContextualItem<'a, T> {
     /// The main item
     pub item: T,
@@ -113,7 +113,7 @@ pub struct ContextualItem<'a, T> {
 }
 
 /// Ranked item with score and optional metadata
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct RankedItem<'a, T> {
     /// The main item
     pub item: T,
@@ -131,7 +131,7 @@ pub struct RankedItem<'a, T> {
 }
 
 /// Relationships between items (zero-cost when not used)
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct ItemRelationships<'a> {
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub calls: Vec<RelatedItem<'a>>,
@@ -153,7 +153,7 @@ pub struct ItemRelationships<'a> {
 }
 
 /// A related item with minimal information
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct RelatedItem<'a> {
     pub id: SymbolId,
     pub name: Cow<'a, str>,
@@ -164,7 +164,7 @@ pub struct RelatedItem<'a> {
 }
 
 /// Optional metadata about the output
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct OutputMetadata<'a> {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub query: Option<Cow<'a, str>>,
@@ -17,12 +17,14 @@
 //! Both helpers are thread-safe and fit naturally with [`StatusLine`]'s refresh loop.
 //!
 //! ## Configuration
-//! - Use [`ProgressBarOptions`] to customize glyph style, bar width, and whether rate and
-//!   elapsed timing are shown. Options are cheap to copy and can be reused across bars.
+//! - Use [`ProgressBarOptions`] to customize glyph style, bar width, and whether rate,
+//!   elapsed timing and an estimated time remaining are shown. Options are cheap to copy
+//!   and can be reused across bars.
 //! - [`SpinnerOptions`] exposes the animation frame period (defaults to 100 ms) so callers
 //!   can smooth out the braille spinner without tying it to work updates.
 
 use super::ExitCode;
+use std::borrow::Cow;
 use std::fmt::Display;
 use std::io::Write;
 use std::ops::Deref;
@@ -35,6 +37,16 @@ use std::time::Instant;
 
 const DEFAULT_PROGRESS_BAR_WIDTH: usize = 24;
 
+/// Longest message shown under a progress bar; longer ones keep their tail so
+/// the line does not wrap and break in-place redraws.
+const MAX_PROGRESS_MESSAGE_CHARS: usize = 72;
+
+/// Shortest span a throughput sample for the ETA covers
+const ETA_SAMPLE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Weight of the newest throughput sample in the smoothed ETA rate
+const ETA_SMOOTHING: f64 = 0.3;
+
 const CURSOR_HIDE: &str = "\x1b[?25l";
 const CURSOR_SHOW: &str = "\x1b[?25h";
 const ERASE_DOWN: &str = "\x1b[J";
@@ -109,18 +121,25 @@ pub struct Options {
     pub enable_ansi_escapes: bool,
 }
 
-impl Default for Options {
-    fn default() -> Self {
-        let is_tty = is_terminal::is_terminal(std::io::stderr());
-        let refresh_period_ms = if is_tty { 100 } else { 1000 };
+impl Options {
+    /// Options for redrawing in place (`ansi = true`) or printing a plain line
+    /// per refresh, e.g. when output is redirected or colors are disabled.
+    pub fn for_terminal(ansi: bool) -> Self {
+        let refresh_period_ms = if ansi { 100 } else { 1000 };
         Options {
             refresh_period: Duration::from_millis(refresh_period_ms),
             initially_visible: true,
-            enable_ansi_escapes: is_tty,
+            enable_ansi_escapes: ansi,
         }
     }
 }
 
+impl Default for Options {
+    fn default() -> Self {
+        Self::for_terminal(is_terminal::is_terminal(std::io::stderr()))
+    }
+}
+
 /// Wraps arbitrary data and displays it periodically on the screen.
 pub struct StatusLine<D: Display> {
     state: Arc<State<D>>,
@@ -264,6 +283,8 @@ pub struct ProgressBarOptions {
     pub width: usize,
     pub show_rate: bool,
     pub show_elapsed: bool,
+    /// Estimated time remaining from smoothed recent throughput
+    pub show_eta: bool,
 }
 
 impl ProgressBarOptions {
@@ -273,6 +294,7 @@ impl ProgressBarOptions {
             width: width.max(1),
             show_rate: true,
             show_elapsed: true,
+            show_eta: false,
         }
     }
 
@@ -295,6 +317,11 @@ impl ProgressBarOptions {
         self.show_elapsed = show;
         self
     }
+
+    pub fn show_eta(mut self, show: bool) -> Self {
+        self.show_eta = show;
+        self
+    }
 }
 
 impl Default for ProgressBarOptions {
@@ -312,10 +339,51 @@ pub struct ProgressBar {
     extra1: AtomicU64,
     extra2: AtomicU64,
     labels: (&'static str, &'static str, &'static str),
+    /// Secondary work counter reported as a rate, e.g. symbols per second
+    units: AtomicU64,
+    units_label: &'static str,
+    /// Current item, shown on its own line when non-empty
+    message: Mutex<String>,
+    /// Throughput behind the ETA, sampled on redraw
+    eta_rate: Mutex<SmoothedRate>,
     start_time: Instant,
     options: ProgressBarOptions,
 }
 
+/// Exponentially weighted work rate, updated at most every
+/// [`ETA_SAMPLE_INTERVAL`] so one slow or fast item does not swing the ETA
+struct SmoothedRate {
+    sampled_at: Instant,
+    sampled: u64,
+    rate: Option<f64>,
+}
+
+impl SmoothedRate {
+    fn new(now: Instant) -> Self {
+        Self {
+            sampled_at: now,
+            sampled: 0,
+            rate: None,
+        }
+    }
+
+    /// Fold in the work done since the last sample; `None` until the first
+    /// sample interval has passed
+    fn update(&mut self, now: Instant, work: u64) -> Option<f64> {
+        let span = now.duration_since(self.sampled_at);
+        if span >= ETA_SAMPLE_INTERVAL {
+            let recent = work.saturating_sub(self.sampled) as f64 / span.as_secs_f64();
+            self.rate = Some(match self.rate {
+                Some(rate) => rate + ETA_SMOOTHING * (recent - rate),
+                None => recent,
+            });
+            self.sampled_at = now;
+            self.sampled = work;
+        }
+        self.rate
+    }
+}
+
 impl ProgressBar {
     /// Create a progress bar with the provided total count and label.
     pub fn new(total: u64, label: &'static str) -> Self {
@@ -331,17 +399,29 @@ impl ProgressBar {
         mut options: ProgressBarOptions,
     ) -> Self {
         options.width = options.width.max(1);
+        let start_time = Instant::now();
         Self {
             current: AtomicU64::new(0),
             total,
             extra1: AtomicU64::new(0),
             extra2: AtomicU64::new(0),
             labels: (label, extra1_label, extra2_label),
-            start_time: Instant::now(),
+            units: AtomicU64::new(0),
+            units_label: "",
+            message: Mutex::new(String::new()),
+            eta_rate: Mutex::new(SmoothedRate::new(start_time)),
+            start_time,
             options,
         }
     }
 
+    /// Also report the rate of a secondary counter advanced with
+    /// [`ProgressBar::add_units`], e.g. `with_units("symbols")`.
+    pub fn with_units(mut self, label: &'static str) -> Self {
+        self.units_label = label;
+        self
+    }
+
     /// Create a progress bar with extra counters and quick style override.
     pub fn with_extras(
         total: u64,
@@ -385,13 +465,45 @@ impl ProgressBar {
         self.extra2.fetch_add(n, Ordering::Relaxed);
     }
 
+    /// Increase the secondary counter reported by [`ProgressBar::with_units`].
+    pub fn add_units(&self, n: u64) {
+        self.units.fetch_add(n, Ordering::Relaxed);
+    }
+
+    /// Show the item currently being processed, e.g. a file path.
+    pub fn set_message(&self, message: impl Into<String>) {
+        if let Ok(mut current) = self.message.lock() {
+            *current = message.into();
+        }
+    }
+
     /// Update the total expected count (resets elapsed timer).
     pub fn reset_total(&mut self, total: u64) {
         self.total = total;
         self.current.store(0, Ordering::Relaxed);
         self.extra1.store(0, Ordering::Relaxed);
         self.extra2.store(0, Ordering::Relaxed);
+        self.units.store(0, Ordering::Relaxed);
         self.start_time = Instant::now();
+        self.eta_rate = Mutex::new(SmoothedRate::new(self.start_time));
+    }
+
+    /// Seconds left at the smoothed rate. With units, the remaining units are
+    /// projected from the share of items done, so the estimate follows units
+    /// (e.g. symbols) rather than item count.
+    fn eta_secs(&self, current: u64) -> Option<f64> {
+        if current == 0 || current >= self.total {
+            return None;
+        }
+        let (done, remaining) = if self.units_label.is_empty() {
+            (current, (self.total - current) as f64)
+        } else {
+            let units = self.units.load(Ordering::Relaxed);
+            let projected = units as f64 * self.total as f64 / current as f64;
+            (units, projected - units as f64)
+        };
+        let rate = self.eta_rate.lock().ok()?.update(Instant::now(), done)?;
+        (rate > 0.0).then(|| remaining / rate)
     }
 }
 
@@ -436,16 +548,56 @@ impl Display for ProgressBar {
 
         if self.options.show_rate {
             write!(f, " | {rate:.0}/s")?;
+            if !self.units_label.is_empty() && elapsed > 0.0 {
+                let units = self.units.load(Ordering::Relaxed) as f64 / elapsed;
+                write!(f, " | {units:.0} {}/s", self.units_label)?;
+            }
         }
 
         if self.options.show_elapsed {
             write!(f, " | {elapsed:.1}s")?;
         }
 
+        if self.options.show_eta {
+            if let Some(eta) = self.eta_secs(current) {
+                write!(f, " | ETA {}", format_eta(eta))?;
+            }
+        }
+
+        if let Ok(message) = self.message.lock() {
+            if !message.is_empty() {
+                write!(
+                    f,
+                    "\n{}",
+                    truncate_start(&message, MAX_PROGRESS_MESSAGE_CHARS)
+                )?;
+            }
+        }
+
         Ok(())
     }
 }
 
+/// `42s`, `3m05s` or `1h20m`
+fn format_eta(secs: f64) -> String {
+    let secs = secs.round() as u64;
+    match secs {
+        0..60 => format!("{secs}s"),
+        60..3600 => format!("{}m{:02}s", secs / 60, secs % 60),
+        _ => format!("{}h{:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+/// Keep the last `max` characters of `text`, marking the cut with `…`.
+fn truncate_start(text: &str, max: usize) -> Cow<'_, str> {
+    let count = text.chars().count();
+    if count <= max {
+        return Cow::Borrowed(text);
+    }
+    let tail: String = text.chars().skip(count - max + 1).collect();
+    Cow::Owned(format!("…{tail}"))
+}
+
 /// Configuration options for [`Spinner`].
 #[derive(Clone, Copy, Debug)]
 pub struct SpinnerOptions {
@@ -640,6 +792,7 @@ impl Spinner {
             6 => ExitCode::ConfigError,
             7 => ExitCode::IndexCorrupted,
             8 => ExitCode::UnsupportedOperation,
+            9 => ExitCode::FindingsReported,
             _ => ExitCode::GeneralError,
         }
     }
@@ -128,5 +128,11 @@ mod tests {
         assert_eq!(OutputFormat::from_json_flag(false), OutputFormat::Text);
         assert!(OutputFormat::Json.is_json());
         assert!(!OutputFormat::Text.is_json());
+        assert_eq!("tree".parse::<OutputFormat>(), Ok(OutputFormat::Tree));
+        assert_eq!(
+            "github-annotations".parse::<OutputFormat>(),
+            Ok(OutputFormat::GithubAnnotations)
+        );
+        assert!("yaml".parse::<OutputFormat>().is_err());
     }
 }
@@ -14,6 +14,27 @@ macro_rules! debug_print {
     };
 }
 
+// Informational stderr output, suppressed by the global `--quiet` flag and
+// redirected like `output_eprintln!` while output is captured.
+// Use plain eprintln! for errors and warnings, which must always print.
+#[macro_export]
+macro_rules! info_eprintln {
+    ($($arg:tt)*) => {
+        if !$crate::config::is_global_quiet() {
+            $crate::io::output::write_stderr_line(format_args!($($arg)*));
+        }
+    };
+}
+
+// Diagnostic stderr output that `io::output::capture_output` can redirect,
+// so queries answered for another process report their errors there.
+#[macro_export]
+macro_rules! output_eprintln {
+    ($($arg:tt)*) => {
+        $crate::io::output::write_stderr_line(format_args!($($arg)*))
+    };
+}
+
 pub mod config;
 pub mod display;
 pub mod error;
@@ -22,6 +43,7 @@ pub mod init;
 pub mod io;
 pub mod mcp;
 pub mod parsing;
+pub mod paths;
 pub mod plugins;
 pub mod profiles;
 pub mod project_resolver;
@@ -41,7 +63,7 @@ pub use error::{
 };
 pub use indexing::{SimpleIndexer, calculate_hash};
 pub use parsing::RustParser;
-pub use relationship::{RelationKind, Relationship, RelationshipEdge};
+pub use relationship::{Provenance, RelationKind, Relationship, RelationshipEdge};
 pub use storage::IndexPersistence;
 pub use symbol::{CompactSymbol, ScopeContext, StringTable, Symbol, Visibility};
 pub use types::{
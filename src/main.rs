@@ -4,7 +4,7 @@
 //! Main components: Cli parser, Commands enum, and async runtime with MCP server support.
 
 use clap::{
-    Parser, Subcommand,
+    CommandFactory, FromArgMatches, Parser, Subcommand,
     builder::styling::{AnsiColor, Effects, Styles},
 };
 use codanna::FileId;
@@ -16,8 +16,9 @@ use codanna::project_resolver::{
 };
 use codanna::storage::IndexMetadata;
 use codanna::types::SymbolCounter;
-use codanna::{IndexPersistence, Settings, SimpleIndexer, Symbol, SymbolKind};
-use serde::Serialize;
+use codanna::{IndexPersistence, Provenance, Settings, SimpleIndexer, SymbolKind};
+use codanna::{info_eprintln, log_debug, log_error, log_info, log_warn};
+use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Instant;
@@ -28,8 +29,11 @@ struct IndexInfo {
     symbol_count: usize,
     file_count: usize,
     relationship_count: usize,
+    /// False when the index was built with relationship resolution disabled
+    relationships_indexed: bool,
     symbol_kinds: SymbolKindBreakdown,
     semantic_search: SemanticSearchInfo,
+    disk_usage: DiskUsageInfo,
 }
 
 #[derive(Debug, Serialize)]
@@ -40,6 +44,15 @@ struct SymbolKindBreakdown {
     traits: usize,
 }
 
+#[derive(Debug, Serialize)]
+struct DiskUsageInfo {
+    #[serde(flatten)]
+    usage: codanna::storage::IndexDiskUsage,
+    bytes_per_symbol: Option<u64>,
+    /// Vector file size implied by the embedding count and dimension
+    expected_semantic_vectors_bytes: Option<u64>,
+}
+
 #[derive(Debug, Serialize)]
 struct SemanticSearchInfo {
     enabled: bool,
@@ -100,6 +113,7 @@ fn create_custom_help() -> String {
     help.push_str("  add-dir       Add a directory to be indexed\n");
     help.push_str("  remove-dir    Remove a directory from indexed paths\n");
     help.push_str("  list-dirs     List all directories that are being indexed\n");
+    help.push_str("  clear         Delete the index, keeping settings.toml\n");
     help.push_str("  retrieve      Query symbols, relationships, and dependencies\n");
     help.push_str("  serve         Start MCP server\n");
     help.push_str("  config        Display active settings\n");
@@ -120,6 +134,14 @@ fn create_custom_help() -> String {
     }
     help.push_str("  -c, --config <CONFIG>  Path to custom settings.toml file\n");
     help.push_str("      --info             Show detailed loading information\n");
+    help.push_str("  -q, --quiet            Suppress informational messages on stderr\n");
+    help.push_str("      --color <WHEN>     Coloring: auto, always, never\n");
+    help.push_str("      --log-format <FORMAT>\n");
+    help.push_str("                         Log output format: text, json\n");
+    help.push_str("      --index-profile <NAME>\n");
+    help.push_str("                         Use a named index profile (default: default)\n");
+    help.push_str("      --absolute-paths   Print file paths as absolute paths\n");
+    help.push_str("      --relative-paths   Print file paths relative to the workspace root\n");
     help.push_str("  -h, --help             Print help\n");
     help.push_str("  -V, --version          Print version\n\n");
 
@@ -154,10 +176,66 @@ struct Cli {
     #[arg(long, global = true)]
     info: bool,
 
+    /// Suppress informational messages on stderr (errors and warnings still print)
+    #[arg(short, long, global = true, conflicts_with = "info")]
+    quiet: bool,
+
+    /// Coloring: auto, always, never
+    #[arg(long, global = true, value_name = "WHEN", default_value = "auto")]
+    color: codanna::display::ColorChoice,
+
+    /// Log output format: text, json (overrides server.log_format)
+    #[arg(long, global = true, value_name = "FORMAT")]
+    log_format: Option<codanna::io::LogFormat>,
+
+    /// Use a named index profile from [index_profiles] instead of the default index
+    #[arg(long, global = true, value_name = "NAME")]
+    index_profile: Option<String>,
+
+    /// Print file paths as absolute paths (overrides display.path_style)
+    #[arg(long, global = true, conflicts_with = "relative_paths")]
+    absolute_paths: bool,
+
+    /// Print file paths relative to the workspace root (overrides display.path_style)
+    #[arg(long, global = true)]
+    relative_paths: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
 
+/// Parse the command line, honoring `--color` for clap's own output.
+///
+/// The custom help text is styled while the parser is built and clap strips
+/// ANSI codes from non-terminal output under `auto`, so the choice is read
+/// from the raw arguments before parsing.
+fn parse_cli() -> Cli {
+    use codanna::display::{ColorChoice, Theme};
+
+    let args: Vec<String> = std::env::args().collect();
+    let value = args.iter().enumerate().find_map(|(i, arg)| {
+        if arg == "--color" {
+            args.get(i + 1).cloned()
+        } else {
+            arg.strip_prefix("--color=").map(str::to_string)
+        }
+    });
+    if let Some(choice) = value.and_then(|value| value.parse().ok()) {
+        Theme::set_color_choice(choice);
+    }
+
+    let clap_color = match Theme::color_choice() {
+        ColorChoice::Auto => clap::ColorChoice::Auto,
+        ColorChoice::Always => clap::ColorChoice::Always,
+        ColorChoice::Never => clap::ColorChoice::Never,
+    };
+    let matches = Cli::command().color(clap_color).get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    Theme::set_color_choice(cli.color);
+    codanna::config::set_global_quiet(cli.quiet);
+    cli
+}
+
 /// Available CLI commands
 #[derive(Subcommand)]
 enum Commands {
@@ -172,7 +250,8 @@ enum Commands {
     /// Index source files or directories
     #[command(about = "Build searchable index from codebase")]
     Index {
-        /// Paths to files or directories to index (multiple paths allowed)
+        /// Paths to files, directories or .tar, .tar.gz, .tgz and .zip archives
+        /// to index (multiple paths allowed)
         #[arg(value_name = "PATH")]
         paths: Vec<PathBuf>,
 
@@ -195,6 +274,18 @@ enum Commands {
         /// Maximum number of files to index
         #[arg(long)]
         max_files: Option<usize>,
+
+        /// Skip files larger than this many bytes (overrides indexing.max_file_size_bytes, 0 = no limit)
+        #[arg(long, value_name = "BYTES")]
+        max_file_size: Option<u64>,
+
+        /// Also index generated code such as *.pb.go or files with an @generated header
+        #[arg(long)]
+        include_generated: bool,
+
+        /// Skip relationship resolution for a faster, symbol-only index
+        #[arg(long)]
+        no_relationships: bool,
     },
 
     /// Add a directory to the indexed paths list
@@ -215,6 +306,18 @@ enum Commands {
     #[command(about = "List all directories that are being indexed")]
     ListDirs,
 
+    /// Delete the index
+    #[command(
+        about = "Delete the index, keeping settings.toml",
+        long_about = "Delete all index artifacts (Tantivy index, semantic vectors, metadata, symbol cache and occurrence index) from the index directory.\n\nsettings.toml and other files in .codanna are left untouched. With --index-profile, only that profile's index is deleted.",
+        after_help = "Examples:\n  codanna clear\n  codanna clear --yes\n  codanna --index-profile tests clear --yes"
+    )]
+    Clear {
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+
     /// Query code relationships and dependencies
     #[command(
         about = "Search symbols, find callers/callees, analyze impact",
@@ -226,9 +329,43 @@ enum Commands {
         query: RetrieveQuery,
     },
 
+    /// Export all symbols and relationships
+    #[command(
+        about = "Export the whole index as JSON or JSON Lines",
+        long_about = "Export every indexed symbol (id, kind, range, module path, signature, doc comment, file path) and every relationship (from/to symbol ids and kind).\n\nJSON formats stream to stdout unless an output file is given; SQLite requires one.",
+        after_help = "Examples:\n  codanna export --format json > symbols.json\n  codanna export --format jsonl | jq -c 'select(.type == \"relationship\")'\n  codanna export --format sqlite out.db\n\nFormats:\n  json:   {\"symbols\": [...], \"relationships\": [...]}\n  jsonl:  one record per line, tagged with \"type\": \"symbol\" or \"relationship\"\n  sqlite: tables files, symbols and relationships"
+    )]
+    Export {
+        /// Output format (json, jsonl or sqlite)
+        #[arg(long, default_value = "json")]
+        format: codanna::indexing::ExportFormat,
+
+        /// Output file (defaults to stdout; required for sqlite)
+        output: Option<PathBuf>,
+    },
+
+    /// Check the index against the source files on disk
+    #[command(
+        about = "Re-hash indexed files and report those changed or missing since indexing",
+        long_about = "Re-hash every indexed file on disk and compare it with the content hash recorded when it was indexed.\n\nUnlike the modification-time staleness warning, this reads each file, so it gives a definitive answer to whether the index matches the source. Exits with code 9 when any file changed or is missing.",
+        after_help = "Examples:\n  codanna verify\n  codanna verify --json\n  codanna verify || codanna index"
+    )]
+    Verify {
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+
     /// Show current configuration settings
-    #[command(about = "Display active settings from .codanna/settings.toml")]
-    Config,
+    #[command(
+        about = "Display active settings from .codanna/settings.toml",
+        after_help = "Examples:\n  codanna config\n  codanna config --json | jq '._source'"
+    )]
+    Config {
+        /// Output resolved settings as JSON, with a `_source` map per top-level section
+        #[arg(long)]
+        json: bool,
+    },
 
     /// Start MCP server
     #[command(
@@ -268,6 +405,15 @@ enum Commands {
             help = "Address to bind HTTP/HTTPS server to"
         )]
         bind: String,
+
+        /// Serve retrieve queries on a Unix socket instead of running the MCP server
+        #[arg(
+            long,
+            value_name = "PATH",
+            conflicts_with_all = ["http", "https"],
+            help = "Keep the index loaded and answer retrieve queries on this Unix socket (clients opt in with CODANNA_SOCKET)"
+        )]
+        query_socket: Option<PathBuf>,
     },
 
     /// Test MCP connection
@@ -298,7 +444,7 @@ enum Commands {
     #[command(
         about = "Execute MCP tools directly",
         long_about = "Execute MCP tools directly without spawning a server.\n\nSupports positional arguments, key=value pairs, and JSON arguments.",
-        after_help = "Examples:\n  codanna mcp find_symbol main\n  codanna mcp get_calls process_file\n  codanna mcp semantic_search_docs query:\"error handling\" limit:5\n  codanna mcp search_symbols query:parse kind:function\n  codanna mcp find_symbol Parser --json | jq '.data[].symbol.name'\n  codanna mcp search_symbols query:Parser --json | jq '.data[].name'\n\nTools:\n  find_symbol                  Find symbol by exact name\n  search_symbols               Full-text search with fuzzy matching\n  semantic_search_docs         Natural language search\n  semantic_search_with_context Natural language search with relationships\n  get_calls                    Functions called by a function\n  find_callers                 Functions that call a function\n  analyze_impact               Impact radius of symbol changes\n  get_index_info               Index statistics"
+        after_help = "Examples:\n  codanna mcp find_symbol main\n  codanna mcp get_calls process_file\n  codanna mcp semantic_search_docs query:\"error handling\" limit:5\n  codanna mcp semantic_search_docs query:\"parse config\" --explain\n  codanna mcp search_symbols query:parse kind:function\n  codanna mcp symbol_at src/main.rs line:342 column:10\n  codanna mcp find_implementors_of_method Parser::parse\n  codanna mcp find_symbol Parser --json | jq '.data[].symbol.name'\n  codanna mcp search_symbols query:Parser --json | jq '.data[].name'\n\nTools:\n  find_symbol                  Find symbol by exact name\n  search_symbols               Full-text search with fuzzy matching\n  semantic_search_docs         Natural language search\n  semantic_search_with_context Natural language search with relationships\n  get_calls                    Functions called by a function\n  find_callers                 Functions that call a function\n  analyze_impact               Impact radius of symbol changes\n  get_symbol_source            Source code of a symbol\n  resolve_import               Definition behind an imported name\n  symbol_at                    Symbol at a file:line:column position\n  find_implementors_of_method  Implementations of a trait method\n  get_type_hierarchy           Ancestors and descendants of a type\n  find_occurrences             Textual occurrences of an identifier\n  get_index_info               Index statistics\n\nList all tools with their parameters:\n  codanna mcp list-tools\n  codanna mcp list-tools --json"
     )]
     Mcp {
         /// Tool to call
@@ -315,10 +461,17 @@ enum Commands {
         /// Output in JSON format
         #[arg(long)]
         json: bool,
+
+        /// Show which query terms each semantic_search_docs result shares with its doc and signature
+        #[arg(long)]
+        explain: bool,
     },
 
     /// Benchmark parser performance
-    #[command(about = "Benchmark parser performance")]
+    #[command(
+        about = "Benchmark parser performance",
+        after_help = "Examples:\n  codanna benchmark all --save bench.json\n  codanna benchmark all --baseline bench.json\n  codanna benchmark rust --baseline bench.json --tolerance 5\n\nWith --baseline, each language's symbols/second is compared to the saved run. A drop larger than --tolerance percent is a regression and exits with code 9."
+    )]
     Benchmark {
         /// Language to benchmark (rust, python, php, typescript, go, csharp, all)
         #[arg(default_value = "all")]
@@ -327,6 +480,18 @@ enum Commands {
         /// Custom file to benchmark
         #[arg(short, long)]
         file: Option<PathBuf>,
+
+        /// Write the results as a JSON baseline
+        #[arg(long, value_name = "PATH")]
+        save: Option<PathBuf>,
+
+        /// Compare throughput against a baseline written with --save
+        #[arg(long, value_name = "PATH")]
+        baseline: Option<PathBuf>,
+
+        /// Largest throughput drop, in percent, not counted as a regression
+        #[arg(long, default_value_t = 10.0, requires = "baseline")]
+        tolerance: f64,
     },
 
     /// Parse a file and output AST nodes in JSONL format
@@ -369,6 +534,45 @@ enum Commands {
         #[command(subcommand)]
         action: codanna::profiles::commands::ProfileAction,
     },
+
+    /// Diagnostics for contributors and power users
+    #[command(
+        about = "Inspect indexer decisions, such as how a call resolves",
+        after_help = "Examples:\n  codanna debug resolve src/indexing/simple.rs:42"
+    )]
+    Debug {
+        #[command(subcommand)]
+        action: DebugAction,
+    },
+
+    /// Print JSON Schemas for `--json` output
+    #[command(
+        about = "Print JSON Schemas describing the --json output of retrieve and mcp commands",
+        after_help = "Examples:\n  codanna schema\n  codanna schema --tool find_symbol\n\nWithout --tool, prints an object of schemas keyed by type name (JsonResponse, UnifiedOutput, SymbolContext, SearchResult, Symbol). With --tool, prints the schema of `codanna mcp <tool> --json` output."
+    )]
+    Schema {
+        /// MCP tool whose response schema to print
+        #[arg(long)]
+        tool: Option<String>,
+    },
+}
+
+/// Diagnostic actions
+#[derive(Subcommand)]
+enum DebugAction {
+    /// Explain how the calls on a line resolve
+    #[command(
+        about = "Show how the calls at PATH:LINE resolve: imports, lookups, candidates and the chosen target",
+        after_help = "Examples:\n  codanna debug resolve src/foo.rs:42\n  codanna debug resolve src/foo.rs:42 --json\n\nThe file is re-parsed as it is on disk and each call on the line is replayed against the index. Candidates are listed even when no target is chosen. Exits with code 3 when the line has no calls."
+    )]
+    Resolve {
+        /// Call site as PATH:LINE (1-based line)
+        location: String,
+
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 /// Plugin management actions
@@ -377,7 +581,7 @@ enum PluginAction {
     /// Install a plugin from a marketplace
     #[command(
         about = "Install a plugin from a marketplace repository",
-        after_help = "Examples:\n  codanna plugin add https://github.com/user/marketplace plugin-name\n  codanna plugin add ./local-marketplace my-plugin --ref v1.0"
+        after_help = "Examples:\n  codanna plugin add https://github.com/user/marketplace plugin-name\n  codanna plugin add ./local-marketplace my-plugin --ref v1.0\n  codanna plugin add ./my-plugin my-plugin --from-file"
     )]
     Add {
         /// Marketplace repository URL or local path
@@ -390,6 +594,10 @@ enum PluginAction {
         #[arg(long)]
         r#ref: Option<String>,
 
+        /// Read a local marketplace or plugin directory from disk without git
+        #[arg(long, conflicts_with = "ref")]
+        from_file: bool,
+
         /// Force installation even if conflicts exist
         #[arg(short, long)]
         force: bool,
@@ -457,7 +665,7 @@ enum PluginAction {
     /// Verify plugin integrity
     #[command(
         about = "Verify that a plugin's files match their expected checksums",
-        after_help = "Examples:\n  codanna plugin verify plugin-name\n  codanna plugin verify --all"
+        after_help = "Examples:\n  codanna plugin verify plugin-name\n  codanna plugin verify --all\n  codanna plugin verify plugin-name --repair"
     )]
     Verify {
         /// Plugin name to verify (omit to verify all)
@@ -467,6 +675,10 @@ enum PluginAction {
         #[arg(long)]
         all: bool,
 
+        /// Restore the locked files from the recorded commit if verification fails
+        #[arg(long, conflicts_with = "all")]
+        repair: bool,
+
         /// Show detailed verification results
         #[arg(short, long)]
         verbose: bool,
@@ -476,11 +688,11 @@ enum PluginAction {
 /// Query types for retrieving indexed information.
 ///
 /// Supports symbol lookups, relationship queries, impact analysis, and full-text search.
-#[derive(Subcommand)]
+#[derive(Subcommand, Serialize, Deserialize)]
 enum RetrieveQuery {
     /// Find a symbol by name
     #[command(
-        after_help = "Examples:\n  codanna retrieve symbol main\n  codanna retrieve symbol symbol_id:1771\n  codanna retrieve symbol name:main --json\n  codanna retrieve symbol MyStruct --json | jq '.file'"
+        after_help = "Examples:\n  codanna retrieve symbol main\n  codanna retrieve symbol symbol_id:1771\n  codanna retrieve symbol name:main --json\n  codanna retrieve symbol MyStruct --json | jq '.file'\n  codanna retrieve search handler --json | jq -r '.items[].symbol.name' | codanna retrieve symbol --from-stdin --json"
     )]
     Symbol {
         /// Positional arguments (symbol name and/or key:value pairs)
@@ -489,11 +701,14 @@ enum RetrieveQuery {
         /// Output in JSON format
         #[arg(long)]
         json: bool,
+        /// Read names or symbol_id:N from stdin, one per line, and emit one entry per input
+        #[arg(long)]
+        from_stdin: bool,
     },
 
     /// Show what functions a given function calls
     #[command(
-        after_help = "Examples:\n  codanna retrieve calls process_file\n  codanna retrieve calls symbol_id:1771\n  codanna retrieve calls function:process_file --json"
+        after_help = "Examples:\n  codanna retrieve calls process_file\n  codanna retrieve calls symbol_id:1771\n  codanna retrieve calls function:process_file --json\n  codanna retrieve calls process_file --format tree --depth 3\n  codanna retrieve calls process_file --resolved-only\n  codanna retrieve search handler --json | jq -r '.items[].symbol.name' | codanna retrieve calls --from-stdin --json\n\nWith --format tree, callees of callees are drawn to --depth levels; a function already shown is marked (see above) instead of being expanded again, and at most 200 functions are drawn.\nCalls resolution could not pin down are marked (ambiguous) or (heuristic); --resolved-only leaves them out."
     )]
     Calls {
         /// Positional arguments (function name and/or key:value pairs)
@@ -502,11 +717,23 @@ enum RetrieveQuery {
         /// Output in JSON format
         #[arg(long)]
         json: bool,
+        /// Read names or symbol_id:N from stdin, one per line, and emit one entry per input
+        #[arg(long, conflicts_with = "format")]
+        from_stdin: bool,
+        /// Output format: text, json or tree
+        #[arg(long, value_name = "FORMAT", conflicts_with = "json")]
+        format: Option<codanna::io::OutputFormat>,
+        /// Levels drawn with --format tree
+        #[arg(long, default_value_t = 3)]
+        depth: usize,
+        /// Only follow calls resolved with certainty, dropping ambiguous and heuristic ones
+        #[arg(long, conflicts_with = "from_stdin")]
+        resolved_only: bool,
     },
 
     /// Show what functions call a given function
     #[command(
-        after_help = "Examples:\n  codanna retrieve callers main\n  codanna retrieve callers symbol_id:1771\n  codanna retrieve callers function:main --json"
+        after_help = "Examples:\n  codanna retrieve callers main\n  codanna retrieve callers symbol_id:1771\n  codanna retrieve callers function:main --json\n  codanna retrieve callers parse_file --transitive --depth 4\n  codanna retrieve callers parse_file --format tree\n  codanna retrieve search handler --json | jq -r '.items[].symbol.name' | codanna retrieve callers --from-stdin --json\n\nWith --transitive, every function reaching the target through calls is listed once, marked with its distance (1 = direct caller) and sorted by distance, then name.\nWith --format tree, callers of callers are drawn to --depth levels; a function already shown is marked (see above) instead of being expanded again, and at most 200 functions are drawn.\nCalls resolution could not pin down are marked (ambiguous) or (heuristic); --resolved-only leaves them out."
     )]
     Callers {
         /// Positional arguments (function name and/or key:value pairs)
@@ -515,6 +742,21 @@ enum RetrieveQuery {
         /// Output in JSON format
         #[arg(long)]
         json: bool,
+        /// Read names or symbol_id:N from stdin, one per line, and emit one entry per input
+        #[arg(long, conflicts_with_all = ["transitive", "format"])]
+        from_stdin: bool,
+        /// Output format: text, json or tree
+        #[arg(long, value_name = "FORMAT", conflicts_with = "json")]
+        format: Option<codanna::io::OutputFormat>,
+        /// Include indirect callers, following call chains up to --depth
+        #[arg(long)]
+        transitive: bool,
+        /// Longest call chain followed with --transitive, or levels drawn with --format tree
+        #[arg(long, default_value_t = 3)]
+        depth: usize,
+        /// Only follow calls resolved with certainty, dropping ambiguous and heuristic ones
+        #[arg(long, conflicts_with = "from_stdin")]
+        resolved_only: bool,
     },
 
     /// Show what types implement a given trait
@@ -530,6 +772,19 @@ enum RetrieveQuery {
         json: bool,
     },
 
+    /// Show each type's implementation of a trait method
+    #[command(
+        after_help = "Examples:\n  codanna retrieve method-implementations Parser::parse\n  codanna retrieve method-implementations Shape.area lang:python --json"
+    )]
+    MethodImplementations {
+        /// Positional arguments (Trait::method and/or key:value pairs)
+        #[arg(num_args = 0..)]
+        args: Vec<String>,
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+
     /// Show what types a given symbol uses
     Uses {
         /// Name of the symbol
@@ -563,7 +818,7 @@ enum RetrieveQuery {
     // },
     /// Search for symbols using full-text search
     #[command(
-        after_help = "Examples:\n  # Traditional flag format\n  codanna retrieve search \"parse\" --limit 5 --kind function\n  \n  # Key:value format (Unix-style)\n  codanna retrieve search query:parse limit:5 kind:function\n  \n  # Mixed format\n  codanna retrieve search \"parse\" limit:5 --json"
+        after_help = "Examples:\n  # Traditional flag format\n  codanna retrieve search \"parse\" --limit 5 --kind function\n  \n  # Key:value format (Unix-style)\n  codanna retrieve search query:parse limit:5 kind:function\n  \n  # Mixed format\n  codanna retrieve search \"parse\" limit:5 --json\n  \n  # Case-insensitive substring match on names\n  codanna retrieve search proc --substring\n  \n  # Whole-name match, no fuzzy hits\n  codanna retrieve search id --exact --case-sensitive\n  \n  # Three lines of source around each result\n  codanna retrieve search \"parse\" --context-lines 3\n  \n  # Only symbols carrying a matching attribute or decorator\n  codanna retrieve search handler attribute:tokio::main\n  \n  # Which files have the most handlers\n  codanna retrieve search handler --group-by file --limit 200\n  \n  # Only symbols in files indexed in the last 10 minutes\n  codanna retrieve search handler --since-index 10m"
     )]
     Search {
         /// Positional arguments (query and/or key:value pairs)
@@ -574,7 +829,7 @@ enum RetrieveQuery {
         #[arg(short, long)]
         limit: Option<usize>,
 
-        /// Filter by symbol kind (flag format)
+        /// Filter by symbol kind, e.g. function, fn, struct, cls (flag format)
         #[arg(short, long)]
         kind: Option<String>,
 
@@ -582,15 +837,76 @@ enum RetrieveQuery {
         #[arg(short, long)]
         module: Option<String>,
 
+        /// Filter by attribute, decorator or annotation name, e.g. deprecated,
+        /// tokio::main; exact or prefix match, ignoring case (flag format)
+        #[arg(long)]
+        attribute: Option<String>,
+
+        /// Only symbols in files indexed within this window, e.g. 30s, 10m,
+        /// 2h, 1d (flag format)
+        #[arg(long, value_name = "DURATION")]
+        since_index: Option<String>,
+
+        /// Print result counts per file, module or kind, largest first,
+        /// instead of the results. Counts cover the results within --limit
+        /// (flag format)
+        #[arg(long, value_name = "KEY")]
+        group_by: Option<String>,
+
+        /// Drop results scoring below this value (flag format)
+        #[arg(long)]
+        min_score: Option<f32>,
+
+        /// Match names containing the query, ignoring case (flag format)
+        #[arg(long, conflicts_with = "exact")]
+        substring: bool,
+
+        /// Match whole names equal to the query, with no fuzzy matching (flag format)
+        #[arg(long)]
+        exact: bool,
+
+        /// With --exact, match case as well (flag format)
+        #[arg(long, requires = "exact")]
+        case_sensitive: bool,
+
+        /// Show N lines of source around each result, like ripgrep (flag format)
+        #[arg(long, value_name = "N")]
+        context_lines: Option<u32>,
+
+        /// Print only these fields, one row per result: name, kind, file, line,
+        /// column, module, doc, signature, score (flag format)
+        #[arg(long, value_delimiter = ',', value_name = "FIELDS")]
+        fields: Vec<String>,
+
         /// Output in JSON format
         #[arg(long)]
         json: bool,
     },
 
-    /// Show what methods a type or trait defines
+    /// Show what methods a type or trait defines, inherent and per implemented trait
+    #[command(
+        after_help = "Examples:\n  codanna retrieve defines Parser\n  codanna retrieve defines type:Shape lang:rust --json\n\nTrait impl methods list the trait they implement; inherent methods list none."
+    )]
     Defines {
-        /// Name of the type or trait
-        symbol: String,
+        /// Positional arguments (type name and/or key:value pairs)
+        #[arg(num_args = 0..)]
+        args: Vec<String>,
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show functions taking a type as a parameter or returning it
+    #[command(
+        after_help = "Examples:\n  codanna retrieve uses-in-signature Settings\n  codanna retrieve uses-in-signature type:Settings lang:rust --json\n\nEach result is tagged parameter or return. Struct fields and local variables of the type are not listed."
+    )]
+    UsesInSignature {
+        /// Positional arguments (type name and/or key:value pairs)
+        #[arg(num_args = 0..)]
+        args: Vec<String>,
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
     },
 
     /// Show dependency analysis for a symbol
@@ -599,14 +915,130 @@ enum RetrieveQuery {
         symbol: String,
     },
 
+    /// Show files with the highest fan-in and fan-out across relationships
+    #[command(
+        after_help = "Examples:\n  codanna retrieve coupling\n  codanna retrieve coupling --top 20\n  codanna retrieve coupling --json | jq '.items[0]'"
+    )]
+    Coupling {
+        /// Number of files to show
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// List likely entry points: mains, FFI exports, HTTP handlers, test mains and uncalled public functions
+    #[command(
+        after_help = "Examples:\n  codanna retrieve entrypoints\n  codanna retrieve entrypoints --limit 50\n  codanna retrieve entrypoints --json | jq -r '.items[] | select(.category == \"http_handler\") | .entry_points[].name'\n  codanna retrieve entrypoints --format github-annotations --fail-on-findings\n\nEntry points are detected heuristically from names, signatures and attributes (#[tokio::main], #[no_mangle], @app.route, ...) plus Python `if __name__ == \"__main__\"` blocks. Public functions without callers are listed only when relationships were indexed; `[analysis]` ignore_names and ignore_attributes in settings leave matching symbols out.\nWith --format github-annotations, public functions without callers become warnings and other entry points notices."
+    )]
+    Entrypoints {
+        /// Most entry points listed per category
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
+        /// Output format: text, json or github-annotations
+        #[arg(long, value_name = "FORMAT", conflicts_with = "json")]
+        format: Option<codanna::io::OutputFormat>,
+        /// Exit with code 9 when public functions without callers are found
+        #[arg(long)]
+        fail_on_findings: bool,
+    },
+
+    /// List call and type sites where resolution picked among same-named symbols
+    #[command(
+        after_help = "Examples:\n  codanna retrieve ambiguities\n  codanna retrieve ambiguities --json | jq -r '.items[] | \"\\(.location) \\(.target.name)\"'\n  codanna retrieve ambiguities --format github-annotations --fail-on-findings\n\nEach site shows where the relationship starts and every indexed symbol sharing the target's name and kind; the one resolution chose is marked *. Importing or qualifying the name makes such sites resolve exactly. Indexes built before relationship provenance was recorded need `codanna index --force`."
+    )]
+    Ambiguities {
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
+        /// Output format: text, json or github-annotations
+        #[arg(long, value_name = "FORMAT", conflicts_with = "json")]
+        format: Option<codanna::io::OutputFormat>,
+        /// Exit with code 9 when any ambiguous site is found
+        #[arg(long)]
+        fail_on_findings: bool,
+    },
+
+    /// Report the share of public symbols with doc comments, per module
+    #[command(
+        after_help = "Examples:\n  codanna retrieve doc-coverage\n  codanna retrieve doc-coverage --min 0.8\n  codanna retrieve doc-coverage --json | jq '.metadata.coverage'\n\nPublic means the visibility each language assigns (Rust `pub`, exported Go names, Python names without a leading underscore, ...). Parameters, fields, enum variants and variables are not counted. Modules are listed from lowest coverage up, each with its undocumented symbols."
+    )]
+    DocCoverage {
+        /// Exit with code 9 when overall coverage is below this fraction (0 to 1)
+        #[arg(long, value_name = "FRACTION")]
+        min: Option<f64>,
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Compare public function signatures against a baseline index
+    #[command(
+        after_help = "Examples:\n  codanna retrieve signature-changes --baseline old_index/\n  codanna retrieve signature-changes --baseline ../main-checkout/.codanna --json | jq '.items[] | select(.severity == \"breaking\")'\n\nPublic functions and methods are matched by qualified name. Removed, reordered or retyped parameters and return type changes are breaking; an added parameter is non-breaking only when it has a default or is marked optional. Symbols missing from either index are not reported."
+    )]
+    SignatureChanges {
+        /// Index directory to compare against (holding tantivy/), or the settings directory containing it
+        #[arg(long, value_name = "PATH")]
+        baseline: PathBuf,
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show the last git commit touching a symbol's lines
+    #[command(
+        after_help = "Examples:\n  codanna retrieve history SimpleIndexer\n  codanna retrieve history symbol_id:1771 --json\n\nReports history as unavailable when the workspace is not a git repository."
+    )]
+    History {
+        /// Positional arguments (symbol name and/or key:value pairs)
+        #[arg(num_args = 0..)]
+        args: Vec<String>,
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show the module hierarchy with symbol counts
+    #[command(
+        after_help = "Examples:\n  codanna retrieve modules\n  codanna retrieve modules lang:python\n  codanna retrieve modules --json"
+    )]
+    Modules {
+        /// Positional key:value pairs (e.g. lang:rust)
+        #[arg(num_args = 0..)]
+        args: Vec<String>,
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// List the symbols defined directly in a module
+    #[command(
+        after_help = "Examples:\n  codanna retrieve module crate::indexing\n  codanna retrieve module path:app.models --json\n  codanna retrieve module src/drivers/usb.c"
+    )]
+    Module {
+        /// Positional arguments (module path and/or key:value pairs)
+        #[arg(num_args = 0..)]
+        args: Vec<String>,
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+
     /// Show information about a symbol
     #[command(
-        after_help = "Examples:\n  codanna retrieve describe SimpleIndexer\n  codanna retrieve describe symbol:SimpleIndexer --json"
+        after_help = "Examples:\n  codanna retrieve describe SimpleIndexer\n  codanna retrieve describe symbol:SimpleIndexer --json\n  codanna retrieve describe process_file --depth 3\n\nWith --depth above 1, callers and callees are shown as nested trees (at most 20 per symbol; the rest are counted as truncated)."
     )]
     Describe {
         /// Positional arguments (symbol name and/or key:value pairs)
         #[arg(num_args = 0..)]
         args: Vec<String>,
+        /// Levels of callers and callees to include (flag format, default: 1)
+        #[arg(short, long)]
+        depth: Option<usize>,
         /// Output as JSON
         #[arg(long)]
         json: bool,
@@ -757,10 +1189,12 @@ fn seed_indexer_with_config_paths(
             continue;
         }
 
-        if !path.is_dir() {
+        let is_archive =
+            path.is_file() && codanna::indexing::archive::ArchiveFormat::from_path(path).is_some();
+        if !path.is_dir() && !is_archive {
             if debug {
                 eprintln!(
-                    "DEBUG: Skipping configured path (not a directory): {}",
+                    "DEBUG: Skipping configured path (not a directory or archive): {}",
                     path.display()
                 );
             }
@@ -802,17 +1236,21 @@ fn add_paths_to_settings(
     paths: &[PathBuf],
     config_path: &Path,
     strict: bool,
+    index_profile: Option<&str>,
 ) -> Result<(Settings, Vec<PathBuf>, Vec<SkippedPath>), String> {
     // Load settings from file
     let mut settings = Settings::load_from(config_path)
         .map_err(|e| format!("Error loading configuration: {e}"))?;
+    if let Some(name) = index_profile {
+        settings.select_index_profile(name)?;
+    }
 
     let mut added_paths = Vec::new();
     let mut skipped_paths = Vec::new();
 
     // Add each path (Settings::add_indexed_path handles deduplication)
     for path in paths {
-        if path.is_file() {
+        if path.is_file() && codanna::indexing::archive::ArchiveFormat::from_path(path).is_none() {
             if strict {
                 return Err(format!(
                     "Path must be a directory (got file): {}",
@@ -873,16 +1311,16 @@ fn add_paths_to_settings(
 /// Auto-initializes config for index command. Persists index after modifications.
 #[tokio::main]
 async fn main() {
-    let cli = Cli::parse();
+    let cli = parse_cli();
 
     // For index command, auto-initialize if needed (but not when using --config)
     if matches!(cli.command, Commands::Index { .. }) && cli.config.is_none() {
         if Settings::check_init().is_err() {
             // Auto-initialize for index command
-            eprintln!("Initializing project configuration...");
+            info_eprintln!("Initializing project configuration...");
             match Settings::init_config_file(false) {
                 Ok(path) => {
-                    eprintln!("Created configuration file at: {}", path.display());
+                    info_eprintln!("Created configuration file at: {}", path.display());
                 }
                 Err(e) => {
                     eprintln!("Warning: Could not create config file: {e}");
@@ -915,6 +1353,37 @@ async fn main() {
         })
     };
 
+    if let Some(name) = &cli.index_profile {
+        if let Err(e) = config.select_index_profile(name) {
+            eprintln!("Error: {e}");
+            std::process::exit(1);
+        }
+    }
+
+    // Route operational messages through the configured log format
+    let log_format = cli.log_format.unwrap_or_else(|| {
+        config.server.log_format.parse().unwrap_or_else(|e| {
+            eprintln!("Warning: server.log_format: {e}; using text");
+            codanna::io::LogFormat::Text
+        })
+    });
+    let log_level = if cli.quiet {
+        tracing::Level::WARN
+    } else if config.debug {
+        tracing::Level::DEBUG
+    } else {
+        tracing::Level::INFO
+    };
+    codanna::io::logging::init(log_format, log_level);
+
+    // Render file paths in output consistently
+    if cli.absolute_paths {
+        config.display.path_style = codanna::paths::PathStyle::Absolute;
+    } else if cli.relative_paths {
+        config.display.path_style = codanna::paths::PathStyle::Relative;
+    }
+    codanna::paths::set_display_style(config.display.path_style, config.workspace_root.clone());
+
     match &cli.command {
         Commands::Init { force } => {
             let config_path = PathBuf::from(".codanna/settings.toml");
@@ -941,7 +1410,25 @@ async fn main() {
             return;
         }
 
-        Commands::Config => {
+        Commands::Config { json: true } => {
+            let config_path = cli
+                .config
+                .clone()
+                .unwrap_or_else(Settings::config_file_path);
+            match config
+                .to_json_with_sources(&config_path)
+                .and_then(|value| serde_json::to_string_pretty(&value))
+            {
+                Ok(json) => println!("{json}"),
+                Err(e) => {
+                    eprintln!("Error displaying config: {e}");
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+
+        Commands::Config { json: false } => {
             println!("Current Configuration:");
             println!("{}", "=".repeat(50));
             match toml::to_string_pretty(&config) {
@@ -974,6 +1461,30 @@ async fn main() {
         _ => {}
     }
 
+    if let Commands::Index {
+        max_file_size: Some(limit),
+        ..
+    } = &cli.command
+    {
+        config.indexing.max_file_size_bytes = *limit;
+    }
+
+    if let Commands::Index {
+        include_generated: true,
+        ..
+    } = &cli.command
+    {
+        config.indexing.include_generated = true;
+    }
+
+    if let Commands::Index {
+        no_relationships: true,
+        ..
+    } = &cli.command
+    {
+        config.indexing.resolve_relationships = false;
+    }
+
     // Early return for parse command - it needs no indexing infrastructure
     if let Commands::Parse {
         ref file,
@@ -986,6 +1497,11 @@ async fn main() {
         // run_parse_command already calls std::process::exit
     }
 
+    // Schemas come from the output types alone
+    if let Commands::Schema { ref tool } = cli.command {
+        run_schema_command(tool.as_deref());
+    }
+
     // Set up persistence based on config
     // Use global path resolution that handles --config properly
     let index_path = codanna::init::resolve_index_path(&config, cli.config.as_deref());
@@ -995,13 +1511,40 @@ async fn main() {
 
     let persistence = IndexPersistence::new(index_path.clone());
 
+    // Clear deletes the index files directly and never loads them
+    if let Commands::Clear { yes } = cli.command {
+        run_clear_command(&persistence, &index_path, yes);
+    }
+
+    // Indexing writes to Tantivy long before the final save, so hold the index
+    // lock for the whole run; a concurrent `codanna index` fails fast instead
+    let _index_lock = if matches!(cli.command, Commands::Index { .. }) {
+        match persistence.lock_exclusive() {
+            Ok(lock) => Some(lock),
+            Err(e) => {
+                eprintln!("Error: {e}");
+                std::process::exit(codanna::io::ExitCode::GeneralError as i32);
+            }
+        }
+    } else {
+        None
+    };
+
+    // Listing MCP tools only needs the tool definitions
+    if let Commands::Mcp { ref tool, json, .. } = cli.command {
+        if tool == "list-tools" {
+            run_list_tools_command(&config.mcp, json);
+        }
+    }
+
     // Skip loading index for commands that don't need it
     let skip_index_load = matches!(
         cli.command,
         Commands::McpTest { .. }
             | Commands::Parse { .. }
+            | Commands::Clear { .. }
             | Commands::Init { .. }
-            | Commands::Config
+            | Commands::Config { .. }
             | Commands::Benchmark { .. }
             | Commands::Plugin { .. }
     );
@@ -1011,12 +1554,22 @@ async fn main() {
     let needs_trait_resolver = matches!(
         cli.command,
         Commands::Retrieve {
-            query: RetrieveQuery::Implementations { .. },
+            query: RetrieveQuery::Implementations { .. }
+                | RetrieveQuery::MethodImplementations { .. },
             ..
         } | Commands::Index { .. }
             | Commands::Serve { .. }
     );
 
+    // A query daemon already holds the index; let it answer instead of loading
+    let daemon_exit_code = match &cli.command {
+        Commands::Retrieve { query } => retrieve_via_query_socket(query, &config),
+        _ => None,
+    };
+    if let Some(exit_code) = daemon_exit_code {
+        std::process::exit(exit_code);
+    }
+
     // Load existing index or create new one (unless we're in thin client mode)
     let settings = Arc::new(config.clone());
     let mut indexer = if skip_index_load {
@@ -1037,11 +1590,27 @@ async fn main() {
                 eprintln!("DEBUG: Using lazy initialization (skipping trait resolver)");
             }
 
-            match persistence.load_with_settings_lazy(
-                settings.clone(),
-                cli.info,
-                skip_trait_resolver,
-            ) {
+            // Pure queries never write, so open Tantivy without a writer unless
+            // the configured paths changed and the sync below must index them
+            let read_only = matches!(
+                cli.command,
+                Commands::Retrieve { .. }
+                    | Commands::Mcp { .. }
+                    | Commands::Verify { .. }
+                    | Commands::Debug { .. }
+            ) && IndexMetadata::load(&config.index_path).is_ok_and(|metadata| {
+                !SimpleIndexer::needs_sync(metadata.indexed_paths, &config.indexing.indexed_paths)
+            });
+            if read_only && config.debug {
+                eprintln!("DEBUG: Opening index read-only");
+            }
+
+            let loaded = if read_only {
+                persistence.load_read_only(settings.clone(), cli.info)
+            } else {
+                persistence.load_with_settings_lazy(settings.clone(), cli.info, skip_trait_resolver)
+            };
+            match loaded {
                 Ok(loaded) => {
                     if config.debug {
                         eprintln!("DEBUG: Successfully loaded index from disk");
@@ -1054,6 +1623,15 @@ async fn main() {
                     }
                     loaded
                 }
+                Err(e @ codanna::IndexError::IncompatibleIndex { .. }) => {
+                    eprintln!("Error: {e}");
+                    eprintln!("\nRecovery steps:");
+                    for suggestion in e.recovery_suggestions() {
+                        eprintln!("  • {suggestion}");
+                    }
+                    use codanna::io::ExitCode;
+                    std::process::exit(ExitCode::from_error(&e) as i32);
+                }
                 Err(e) => {
                     eprintln!("Warning: Could not load index: {e}. Creating new index.");
                     SimpleIndexer::with_settings(settings.clone())
@@ -1061,7 +1639,7 @@ async fn main() {
             }
         } else {
             if force_recreate_index && persistence.exists() {
-                eprintln!("Force re-indexing requested, creating new index");
+                log_info!("Force re-indexing requested, creating new index");
             } else if !persistence.exists() && config.debug {
                 eprintln!(
                     "DEBUG: No existing index found at {}",
@@ -1087,6 +1665,9 @@ async fn main() {
                 if let Err(e) = new_indexer.clear_symbol_cache(true) {
                     eprintln!("Warning: Failed to clear symbol cache: {e}");
                 }
+                if let Err(e) = new_indexer.clear_occurrences() {
+                    eprintln!("Warning: Failed to clear occurrence index: {e}");
+                }
             }
             new_indexer
         }
@@ -1106,7 +1687,7 @@ async fn main() {
     if config.semantic_search.enabled && !indexer.has_semantic_search() {
         if let Err(e) = indexer.enable_semantic_search() {
             eprintln!("Warning: Failed to enable semantic search: {e}");
-        } else {
+        } else if !cli.quiet {
             eprintln!(
                 "Semantic search enabled (model: {}, threshold: {})",
                 config.semantic_search.model, config.semantic_search.threshold
@@ -1114,6 +1695,26 @@ async fn main() {
         }
     }
 
+    // Report effective thread counts per indexing phase
+    if cli.info && matches!(cli.command, Commands::Index { .. }) {
+        eprintln!(
+            "Threads: parsing 1 (sequential), index writer {} (indexing.parallel_threads = {})",
+            indexer.writer_threads(),
+            config.indexing.parallel_threads
+        );
+        if indexer.has_semantic_search() {
+            match indexer.embedding_threads() {
+                Some(threads) => eprintln!(
+                    "Threads: embeddings {threads} (ONNX Runtime pool, indexing.parallel_threads)"
+                ),
+                None => {
+                    let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+                    eprintln!("Threads: embeddings {cores} (ONNX Runtime pool, all cores)");
+                }
+            }
+        }
+    }
+
     // Sync indexed paths with config - auto-index new directories
     // This handles changes made while the index was not in use (e.g., add-dir command)
     // Skip sync if force flag is present (force means fresh start, not incremental)
@@ -1188,12 +1789,12 @@ async fn main() {
                         if added > 0 || removed > 0 {
                             sync_made_changes = Some(true);
                             if added > 0 {
-                                eprintln!(
+                                log_info!(
                                     "  ✓ Added {added} new directories ({files} files, {symbols} symbols)"
                                 );
                             }
                             if removed > 0 {
-                                eprintln!("  ✓ Removed {removed} directories from index");
+                                log_info!("  ✓ Removed {removed} directories from index");
                             }
 
                             // Save updated index
@@ -1245,17 +1846,25 @@ async fn main() {
     }
 
     match cli.command {
-        Commands::Init { .. } | Commands::Config => {
+        Commands::Init { .. } | Commands::Config { .. } => {
             // Already handled above
             unreachable!()
         }
 
         Commands::Serve {
-            watch,
+            query_socket: Some(socket),
+            ..
+        } => {
+            run_query_daemon(indexer, &persistence, settings.clone(), &socket);
+        }
+
+        Commands::Serve {
+            watch,
             watch_interval,
             http,
             https,
             bind,
+            query_socket: None,
         } => {
             // Determine server mode:
             // 1. CLI --https flag takes highest precedence
@@ -1323,10 +1932,10 @@ async fn main() {
                 }
                 "http" => {
                     // HTTP mode - persistent server with event-driven file watching
-                    eprintln!("Starting MCP server in HTTP mode");
-                    eprintln!("Bind address: {bind_address}");
+                    log_info!("Starting MCP server in HTTP mode");
+                    log_info!("Bind address: {bind_address}");
                     if watch || config.file_watch.enabled {
-                        eprintln!(
+                        log_info!(
                             "File watching: ENABLED (event-driven with {}ms debounce)",
                             config.file_watch.debounce_ms
                         );
@@ -1335,22 +1944,22 @@ async fn main() {
                     // Use the HTTP server implementation
                     use codanna::mcp::http_server::serve_http;
                     if let Err(e) = serve_http(config, watch, bind_address).await {
-                        eprintln!("HTTP server error: {e}");
+                        log_error!("HTTP server error: {e}");
                         std::process::exit(1);
                     }
                 }
                 _ => {
                     // stdio mode - current implementation
-                    eprintln!("Starting MCP server on stdio transport");
+                    log_info!("Starting MCP server on stdio transport");
                     if watch {
-                        eprintln!("Index watching enabled (interval: {actual_watch_interval}s)");
+                        log_info!("Index watching enabled (interval: {actual_watch_interval}s)");
                     }
-                    eprintln!("To test: npx @modelcontextprotocol/inspector cargo run -- serve");
+                    log_info!("To test: npx @modelcontextprotocol/inspector cargo run -- serve");
 
                     // Create MCP server using the already-loaded indexer
                     if config.mcp.debug {
-                        eprintln!(
-                            "MCP DEBUG: Creating server with indexer - symbols: {}, semantic: {}",
+                        log_debug!(
+                            "MCP: Creating server with indexer - symbols: {}, semantic: {}",
                             indexer.symbol_count(),
                             indexer.has_semantic_search()
                         );
@@ -1376,7 +1985,7 @@ async fn main() {
                             watcher.watch().await;
                         });
 
-                        eprintln!("Index watcher started with notification support");
+                        log_info!("Index watcher started with notification support");
                     }
 
                     // Create notification broadcaster for file/config watchers
@@ -1388,8 +1997,8 @@ async fn main() {
                     if config.file_watch.enabled {
                         use codanna::indexing::FileSystemWatcher;
 
-                        eprintln!("Starting file system watcher for indexed files");
-                        eprintln!("  Debounce interval: {}ms", config.file_watch.debounce_ms);
+                        log_info!("Starting file system watcher for indexed files");
+                        log_info!("  Debounce interval: {}ms", config.file_watch.debounce_ms);
 
                         let watcher_indexer = server.get_indexer_arc();
                         let watcher = FileSystemWatcher::new(
@@ -1399,8 +2008,8 @@ async fn main() {
                             &index_path,
                         )
                         .map_err(|e| {
-                            eprintln!("Failed to create file system watcher: {e}");
-                            eprintln!("File watching disabled for this session");
+                            log_warn!("Failed to create file system watcher: {e}");
+                            log_warn!("File watching disabled for this session");
                             e
                         });
 
@@ -1409,10 +2018,10 @@ async fn main() {
                             // Spawn file watcher in background
                             tokio::spawn(async move {
                                 if let Err(e) = watcher.watch().await {
-                                    eprintln!("File watcher error: {e}");
+                                    log_error!("File watcher error: {e}");
                                 }
                             });
-                            eprintln!(
+                            log_info!(
                                 "File system watcher started - monitoring indexed files for changes"
                             );
                         }
@@ -1436,6 +2045,7 @@ async fn main() {
                         match ConfigFileWatcher::new(
                             settings_path.clone(),
                             config_watcher_indexer,
+                            config.index_profile().map(str::to_string),
                             config.mcp.debug,
                         ) {
                             Ok(config_watcher) => {
@@ -1443,16 +2053,16 @@ async fn main() {
                                     config_watcher.with_broadcaster(config_watcher_broadcaster);
                                 tokio::spawn(async move {
                                     if let Err(e) = config_watcher.watch().await {
-                                        eprintln!("Config watcher error: {e}");
+                                        log_error!("Config watcher error: {e}");
                                     }
                                 });
-                                eprintln!(
+                                log_info!(
                                     "Config watcher started - monitoring {}",
                                     settings_path.display()
                                 );
                             }
                             Err(e) => {
-                                eprintln!("Failed to start config watcher: {e}");
+                                log_warn!("Failed to start config watcher: {e}");
                             }
                         }
                     }
@@ -1463,7 +2073,7 @@ async fn main() {
                         .serve(stdio())
                         .await
                         .map_err(|e| {
-                            eprintln!("Failed to start MCP server: {e}");
+                            log_error!("Failed to start MCP server: {e}");
                             std::process::exit(1);
                         })
                         .unwrap();
@@ -1501,10 +2111,10 @@ async fn main() {
                     })
                 };
 
-                match add_paths_to_settings(&paths, &config_path, false) {
+                match add_paths_to_settings(&paths, &config_path, false, config.index_profile()) {
                     Ok((updated_settings, added_paths, skipped_paths)) => {
                         if !added_paths.is_empty() {
-                            eprintln!("Added {} path(s) to settings.toml", added_paths.len());
+                            log_info!("Added {} path(s) to settings.toml", added_paths.len());
                         }
                         for skipped in &skipped_paths {
                             match &skipped.reason {
@@ -1592,7 +2202,39 @@ async fn main() {
 
             // Process each path
             for path in &paths_to_index {
-                if path.is_file() {
+                if path.is_file()
+                    && codanna::indexing::archive::ArchiveFormat::from_path(path).is_some()
+                {
+                    // Archive indexing: entries are read in memory, never extracted
+                    println!("Indexing archive: {}", path.display());
+
+                    // Track the archive like a directory so sync can purge its entries
+                    if let Err(e) = indexer.add_indexed_path(path) {
+                        eprintln!("Warning: Failed to track indexed archive: {e}");
+                    }
+                    match indexer.index_archive(path, dry_run, force) {
+                        Ok(stats) => {
+                            stats.display();
+                            if cli.info && !stats.symbol_updates.is_empty() {
+                                stats.symbol_updates.display();
+                            }
+                        }
+                        Err(e) => {
+                            eprintln!("Error indexing archive {}: {e}", path.display());
+
+                            // Display recovery suggestions
+                            let suggestions = e.recovery_suggestions();
+                            if !suggestions.is_empty() {
+                                eprintln!("\nSuggestions:");
+                                for suggestion in suggestions {
+                                    eprintln!("  • {suggestion}");
+                                }
+                            }
+
+                            std::process::exit(1);
+                        }
+                    }
+                } else if path.is_file() {
                     // Single file indexing
                     match indexer.index_file_with_force(path, force) {
                         Ok(result) => {
@@ -1649,6 +2291,18 @@ async fn main() {
                             println!("  Methods: {methods}");
                             println!("  Structs: {structs}");
                             println!("  Traits: {traits}");
+
+                            let updates = indexer.symbol_updates();
+                            if cli.info && !updates.is_empty() {
+                                updates.display();
+                            }
+                        }
+                        Err(
+                            e @ (codanna::IndexError::BinaryContent { .. }
+                            | codanna::IndexError::ParseTimeout { .. }),
+                        ) => {
+                            // Not an error: the file is simply left out of the index
+                            eprintln!("{e}");
                         }
                         Err(e) => {
                             eprintln!("Error indexing file {}: {e}", path.display());
@@ -1687,6 +2341,9 @@ async fn main() {
                     {
                         Ok(stats) => {
                             stats.display();
+                            if cli.info && !stats.symbol_updates.is_empty() {
+                                stats.symbol_updates.display();
+                            }
                         }
                         Err(e) => {
                             eprintln!("Error indexing directory {}: {e}", path.display());
@@ -1717,10 +2374,13 @@ async fn main() {
                 }
 
                 // Save the index
-                eprintln!(
-                    "\nSaving index with {} total symbols, {} total relationships...",
-                    indexer.symbol_count(),
-                    indexer.relationship_count()
+                log_info!();
+                let symbols = indexer.symbol_count() as u64;
+                let relationships = indexer.relationship_count() as u64;
+                log_info!(
+                    symbols = symbols,
+                    relationships = relationships;
+                    "Saving index with {symbols} total symbols, {relationships} total relationships..."
                 );
                 match persistence.save(&indexer) {
                     Ok(_) => {
@@ -1746,7 +2406,12 @@ async fn main() {
             };
 
             // Use helper to add path and save (strict mode for add-dir)
-            match add_paths_to_settings(std::slice::from_ref(&path), &config_path, true) {
+            match add_paths_to_settings(
+                std::slice::from_ref(&path),
+                &config_path,
+                true,
+                config.index_profile(),
+            ) {
                 Ok((settings, added_paths, skipped_paths)) => {
                     // In strict mode, we know exactly one path was added (or error)
                     assert_eq!(
@@ -1788,6 +2453,12 @@ async fn main() {
                 eprintln!("Error loading configuration: {e}");
                 std::process::exit(1);
             });
+            if let Some(name) = config.index_profile() {
+                if let Err(e) = settings.select_index_profile(name) {
+                    eprintln!("Error: {e}");
+                    std::process::exit(1);
+                }
+            }
 
             // Remove the directory
             match settings.remove_indexed_path(&path) {
@@ -1819,7 +2490,10 @@ async fn main() {
         }
 
         Commands::ListDirs => {
-            println!("Indexed directories:");
+            match config.index_profile() {
+                Some(name) => println!("Indexed directories (index profile '{name}'):"),
+                None => println!("Indexed directories:"),
+            }
             if config.indexing.indexed_paths.is_empty() {
                 println!("  (none configured)");
                 println!("\nTo add directories: codanna add-dir <path>");
@@ -1830,221 +2504,70 @@ async fn main() {
             }
         }
 
-        Commands::Retrieve { query } => {
-            use codanna::io::OutputFormat;
-            use codanna::retrieve;
-
-            let exit_code = match query {
-                RetrieveQuery::Symbol { args, json } => {
-                    use codanna::io::args::parse_positional_args;
-
-                    // Parse positional arguments for symbol name and key:value pairs
-                    let (positional_name, params) = parse_positional_args(&args);
-
-                    // Determine symbol name or symbol_id (priority: positional > key:value)
-                    let final_name = positional_name
-                        .or_else(|| params.get("name").cloned())
-                        .or_else(|| params.get("symbol_id").map(|id| format!("symbol_id:{id}")))
-                        .unwrap_or_else(|| {
-                            eprintln!("Error: symbol requires a name or symbol_id");
-                            eprintln!("Usage: codanna retrieve symbol main");
-                            eprintln!("   or: codanna retrieve symbol name:main");
-                            eprintln!("   or: codanna retrieve symbol symbol_id:1771");
-                            std::process::exit(1);
-                        });
-
-                    // Extract language filter
-                    let language = params.get("lang").map(|s| s.as_str());
+        Commands::Export { format, output } => {
+            use codanna::indexing::ExportFormat;
 
-                    let format = OutputFormat::from_json_flag(json);
-                    retrieve::retrieve_symbol(&indexer, &final_name, language, format)
+            let result = match (format, output) {
+                (ExportFormat::Sqlite, Some(path)) => indexer.export_sqlite(&path),
+                (ExportFormat::Sqlite, None) => {
+                    eprintln!("Error: SQLite export requires an output file");
+                    eprintln!("  Example: codanna export --format sqlite out.db");
+                    std::process::exit(1);
                 }
-                RetrieveQuery::Callers { args, json } => {
-                    use codanna::io::args::parse_positional_args;
-
-                    // Parse positional arguments for function name and key:value pairs
-                    let (positional_function, params) = parse_positional_args(&args);
-
-                    // Determine function name or symbol_id (priority: positional > key:value)
-                    let final_function = positional_function
-                        .or_else(|| params.get("function").cloned())
-                        .or_else(|| params.get("symbol_id").map(|id| format!("symbol_id:{id}")))
-                        .unwrap_or_else(|| {
-                            eprintln!("Error: callers requires a function name or symbol_id");
-                            eprintln!("Usage: codanna retrieve callers main");
-                            eprintln!("   or: codanna retrieve callers function:main");
-                            eprintln!("   or: codanna retrieve callers symbol_id:1771");
-                            std::process::exit(1);
-                        });
-
-                    // Extract language filter
-                    let language = params.get("lang").map(|s| s.as_str());
-
-                    let format = OutputFormat::from_json_flag(json);
-                    retrieve::retrieve_callers(&indexer, &final_function, language, format)
+                (format, Some(path)) => match std::fs::File::create(&path) {
+                    Ok(file) => indexer.export(std::io::BufWriter::new(file), format),
+                    Err(e) => {
+                        eprintln!("Error: Failed to create {}: {e}", path.display());
+                        std::process::exit(1);
+                    }
+                },
+                (format, None) => {
+                    let stdout = std::io::stdout();
+                    indexer.export(std::io::BufWriter::new(stdout.lock()), format)
                 }
-                RetrieveQuery::Calls { args, json } => {
-                    use codanna::io::args::parse_positional_args;
-
-                    // Parse positional arguments for function name and key:value pairs
-                    let (positional_function, params) = parse_positional_args(&args);
-
-                    // Determine function name or symbol_id (priority: positional > key:value)
-                    let final_function = positional_function
-                        .or_else(|| params.get("function").cloned())
-                        .or_else(|| params.get("symbol_id").map(|id| format!("symbol_id:{id}")))
-                        .unwrap_or_else(|| {
-                            eprintln!("Error: calls requires a function name or symbol_id");
-                            eprintln!("Usage: codanna retrieve calls process_file");
-                            eprintln!("   or: codanna retrieve calls function:process_file");
-                            eprintln!("   or: codanna retrieve calls symbol_id:1771");
-                            std::process::exit(1);
-                        });
-
-                    // Extract language filter
-                    let language = params.get("lang").map(|s| s.as_str());
-
-                    let format = OutputFormat::from_json_flag(json);
-                    retrieve::retrieve_calls(&indexer, &final_function, language, format)
+            };
+            match result {
+                Ok(stats) => {
+                    if config.debug {
+                        eprintln!(
+                            "DEBUG: Exported {} symbols and {} relationships",
+                            stats.symbols, stats.relationships
+                        );
+                    }
                 }
-                RetrieveQuery::Implementations { args, json } => {
-                    use codanna::io::args::parse_positional_args;
-
-                    // Parse positional arguments for trait name and key:value pairs
-                    let (positional_trait, params) = parse_positional_args(&args);
-
-                    // Determine trait name (priority: positional > key:value)
-                    let final_trait = positional_trait
-                        .or_else(|| params.get("trait").cloned())
-                        .unwrap_or_else(|| {
-                            eprintln!("Error: implementations requires a trait name");
-                            eprintln!("Usage: codanna retrieve implementations Parser");
-                            eprintln!("   or: codanna retrieve implementations trait:Parser");
-                            std::process::exit(1);
-                        });
-
-                    // Extract language filter
-                    let language = params.get("lang").map(|s| s.as_str());
-
-                    let format = OutputFormat::from_json_flag(json);
-                    retrieve::retrieve_implementations(&indexer, &final_trait, language, format)
+                Err(e) => {
+                    eprintln!("Error: {e}");
+                    std::process::exit(1);
                 }
-                RetrieveQuery::Search {
-                    args,
-                    limit,
-                    json,
-                    kind,
-                    module,
-                } => {
-                    use codanna::io::args::parse_positional_args;
-
-                    // Parse positional arguments for query and key:value pairs
-                    let (positional_query, params) = parse_positional_args(&args);
-
-                    // Determine query source (priority: positional > key:value)
-                    let final_query = positional_query
-                        .or_else(|| params.get("query").cloned())
-                        .unwrap_or_else(|| {
-                            eprintln!("Error: search requires a query");
-                            eprintln!("Usage: codanna retrieve search \"query\" [options]");
-                            eprintln!(
-                                "   or: codanna retrieve search query:\"search text\" [options]"
-                            );
-                            std::process::exit(1);
-                        });
+            }
+        }
 
-                    // Merge parameters (flags take precedence over key:value)
-                    let final_limit = limit.unwrap_or_else(|| {
-                        params
-                            .get("limit")
-                            .and_then(|s| s.parse::<usize>().ok())
-                            .unwrap_or(10)
-                    });
+        Commands::Verify { json } => {
+            let exit_code = run_verify_command(&indexer, json);
+            std::process::exit(exit_code as i32);
+        }
 
-                    let final_kind = kind.or_else(|| params.get("kind").cloned());
-                    let final_module = module.or_else(|| params.get("module").cloned());
-
-                    // Extract language filter
-                    let language = params.get("lang").map(|s| s.as_str());
-
-                    // Call retrieve function with merged parameters
-                    let format = OutputFormat::from_json_flag(json);
-                    retrieve::retrieve_search(
-                        &indexer,
-                        &final_query,
-                        final_limit,
-                        final_kind.as_deref(),
-                        final_module.as_deref(),
-                        language,
-                        format,
-                    )
+        Commands::Debug { action } => {
+            let exit_code = match action {
+                DebugAction::Resolve { location, json } => {
+                    run_debug_resolve_command(&indexer, &location, json)
                 }
-                // DISABLED: Impact command handler commented out
-                // See the RetrieveQuery enum for deprecation details
-                // RetrieveQuery::Impact { args, depth, json } => {
-                //     use codanna::io::args::parse_positional_args;
-                //
-                //     // Parse positional arguments for symbol name and key:value pairs
-                //     let (positional_symbol, params) = parse_positional_args(&args);
-                //
-                //     // Determine symbol name (priority: positional > key:value)
-                //     let final_symbol = positional_symbol
-                //         .or_else(|| params.get("symbol").cloned())
-                //         .unwrap_or_else(|| {
-                //             eprintln!("Error: impact requires a symbol name");
-                //             eprintln!("Usage: codanna retrieve impact MyStruct");
-                //             eprintln!("   or: codanna retrieve impact symbol:MyStruct depth:3");
-                //             std::process::exit(1);
-                //         });
-                //
-                //     // Merge depth parameter (flags take precedence over key:value)
-                //     let final_depth = depth.unwrap_or_else(|| {
-                //         params
-                //             .get("depth")
-                //             .and_then(|s| s.parse::<usize>().ok())
-                //             .unwrap_or(5)
-                //     });
-                //
-                //     let format = OutputFormat::from_json_flag(json);
-                //     retrieve::retrieve_impact(&indexer, &final_symbol, final_depth, format)
-                // }
-                RetrieveQuery::Describe { args, json } => {
-                    use codanna::io::args::parse_positional_args;
-
-                    // Parse positional arguments for symbol name and key:value pairs
-                    let (positional_symbol, params) = parse_positional_args(&args);
-
-                    // Determine symbol name or symbol_id (priority: positional > key:value)
-                    let final_symbol = positional_symbol
-                        .or_else(|| params.get("symbol").cloned())
-                        .or_else(|| params.get("symbol_id").map(|id| format!("symbol_id:{id}")))
-                        .unwrap_or_else(|| {
-                            eprintln!("Error: describe requires a symbol name or symbol_id");
-                            eprintln!("Usage: codanna retrieve describe SimpleIndexer");
-                            eprintln!("   or: codanna retrieve describe symbol:SimpleIndexer");
-                            eprintln!("   or: codanna retrieve describe symbol_id:1771");
-                            std::process::exit(1);
-                        });
-
-                    // Extract language filter
-                    let language = params.get("lang").map(|s| s.as_str());
+            };
+            std::process::exit(exit_code as i32);
+        }
 
-                    let format = OutputFormat::from_json_flag(json);
-                    retrieve::retrieve_describe(&indexer, &final_symbol, language, format)
-                }
-                RetrieveQuery::Uses { symbol } => {
-                    eprintln!("'retrieve uses' command not yet implemented for: {symbol}");
-                    codanna::io::ExitCode::GeneralError
-                }
-                RetrieveQuery::Defines { symbol } => {
-                    eprintln!("'retrieve defines' command not yet implemented for: {symbol}");
-                    codanna::io::ExitCode::GeneralError
-                }
-                RetrieveQuery::Dependencies { symbol } => {
-                    eprintln!("'retrieve dependencies' command not yet implemented for: {symbol}");
-                    codanna::io::ExitCode::GeneralError
+        Commands::Retrieve { query } => {
+            if config.indexing.stale_check && !cli.quiet {
+                let stale = indexer.stale_files();
+                if !stale.is_empty() {
+                    eprintln!(
+                        "Warning: {} indexed file(s) changed since indexing; results may be stale. Run 'codanna index' to refresh.",
+                        stale.len()
+                    );
                 }
-            };
+            }
+
+            let exit_code = run_retrieve_query(&indexer, query);
 
             std::process::exit(exit_code as i32);
         }
@@ -2082,6 +2605,7 @@ async fn main() {
             positional,
             args,
             json,
+            explain,
         } => {
             use codanna::io::args::parse_positional_args;
 
@@ -2104,9 +2628,27 @@ async fn main() {
                 Some(serde_json::Map::new())
             };
 
+            if explain {
+                if let Some(ref mut args_map) = arguments {
+                    args_map.insert("explain".to_string(), serde_json::Value::Bool(true));
+                }
+            }
+
             // Process positional arguments using unified parser
             if !positional.is_empty() {
                 if let Some(ref mut args_map) = arguments {
+                    // `Trait::method` would otherwise be read as a key:value pair
+                    let (method_paths, positional): (Vec<String>, Vec<String>) =
+                        if tool == "find_implementors_of_method" {
+                            positional.into_iter().partition(|arg| arg.contains("::"))
+                        } else {
+                            (Vec::new(), positional)
+                        };
+                    if let Some(method_path) = method_paths.into_iter().next() {
+                        args_map
+                            .insert("method".to_string(), serde_json::Value::String(method_path));
+                    }
+
                     // Use the unified parser from args.rs
                     let (first_positional, params) = parse_positional_args(&positional);
 
@@ -2125,7 +2667,7 @@ async fn main() {
                                     serde_json::Value::String(pos_arg.clone()),
                                 );
                             }
-                            "analyze_impact" => {
+                            "analyze_impact" | "get_symbol_source" => {
                                 args_map.insert(
                                     "symbol_name".to_string(),
                                     serde_json::Value::String(pos_arg.clone()),
@@ -2143,6 +2685,24 @@ async fn main() {
                                     serde_json::Value::String(pos_arg.clone()),
                                 );
                             }
+                            "find_occurrences" | "get_type_hierarchy" => {
+                                args_map.insert(
+                                    "name".to_string(),
+                                    serde_json::Value::String(pos_arg.clone()),
+                                );
+                            }
+                            "resolve_import" | "symbol_at" => {
+                                args_map.insert(
+                                    "file_path".to_string(),
+                                    serde_json::Value::String(pos_arg.clone()),
+                                );
+                            }
+                            "find_implementors_of_method" => {
+                                args_map.insert(
+                                    "method".to_string(),
+                                    serde_json::Value::String(pos_arg.clone()),
+                                );
+                            }
                             _ => {
                                 eprintln!(
                                     "Warning: Unknown tool '{tool}', ignoring positional argument"
@@ -2185,13 +2745,21 @@ async fn main() {
                     .and_then(|m| m.get("lang"))
                     .and_then(|v| v.as_str());
 
+                let limit = arguments
+                    .as_ref()
+                    .and_then(|m| m.get("limit"))
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(20) as usize;
+
                 if let Some(symbol_name) = name {
                     let symbols = indexer.find_symbols_by_name(symbol_name, language);
+                    let total = symbols.len();
                     if !symbols.is_empty() {
                         use codanna::symbol::context::ContextIncludes;
                         let mut results = Vec::new();
 
-                        for symbol in symbols {
+                        // Context is only built for the symbols shown
+                        for symbol in symbols.into_iter().take(limit) {
                             // Get full context with callers using the same approach as MCP
                             let context = indexer.get_symbol_context(
                                 symbol.id,
@@ -2207,6 +2775,7 @@ async fn main() {
                                 // Fallback: create minimal context
                                 let file_path = indexer
                                     .get_file_path(symbol.file_id)
+                                    .map(|path| codanna::paths::display_path(&path).into_owned())
                                     .unwrap_or_else(|| "unknown".to_string());
 
                                 results.push(codanna::symbol::context::SymbolContext {
@@ -2216,9 +2785,9 @@ async fn main() {
                                 });
                             }
                         }
-                        Some(results)
+                        Some((results, total))
                     } else {
-                        Some(Vec::new())
+                        Some((Vec::new(), 0))
                     }
                 } else {
                     None
@@ -2227,6 +2796,33 @@ async fn main() {
                 None
             };
 
+            // Call graph tools fail clearly on an index built without relationships
+            if json
+                && matches!(
+                    tool.as_str(),
+                    "get_calls" | "find_callers" | "analyze_impact"
+                )
+            {
+                if let Err(e) = indexer.ensure_relationships_indexed() {
+                    use codanna::io::exit_code::ExitCode;
+                    use codanna::io::format::JsonResponse;
+                    let response = JsonResponse::error(
+                        ExitCode::GeneralError,
+                        &e.to_string(),
+                        vec!["Set indexing.resolve_relationships = true and re-index with --force"],
+                    );
+                    println!("{}", serde_json::to_string_pretty(&response).unwrap());
+                    std::process::exit(1);
+                }
+            }
+
+            // Calls and impact JSON output can be limited to resolved edges
+            let resolved_only = arguments
+                .as_ref()
+                .and_then(|m| m.get("resolved_only"))
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+
             // Collect data for get_calls if JSON output is requested
             let get_calls_data = if json && tool == "get_calls" {
                 let symbol_id = arguments
@@ -2253,8 +2849,10 @@ async fn main() {
                         let context = indexer.get_symbol_context(symbol.id, ContextIncludes::CALLS);
                         if let Some(ctx) = context {
                             if let Some(calls) = ctx.relationships.calls {
-                                for (called, metadata) in calls {
-                                    all_calls.push((called, metadata));
+                                for (called, metadata, provenance) in calls {
+                                    if !resolved_only || provenance == Provenance::Resolved {
+                                        all_calls.push((called, metadata, provenance));
+                                    }
                                 }
                             }
                         }
@@ -2291,10 +2889,13 @@ async fn main() {
                                 indexer.get_symbol_context(symbol.id, ContextIncludes::CALLS);
                             if let Some(ctx) = context {
                                 if let Some(calls) = ctx.relationships.calls {
-                                    for (called, metadata) in calls {
+                                    for (called, metadata, provenance) in calls {
+                                        if resolved_only && provenance != Provenance::Resolved {
+                                            continue;
+                                        }
                                         // Deduplicate by symbol ID
                                         if seen_ids.insert(called.id) {
-                                            all_calls.push((called, metadata));
+                                            all_calls.push((called, metadata, provenance));
                                         }
                                     }
                                 }
@@ -2330,7 +2931,12 @@ async fn main() {
                     // Direct lookup by symbol ID
                     if let Some(symbol) = indexer.get_symbol(codanna::SymbolId(id)) {
                         let callers = indexer.get_calling_functions_with_metadata(symbol.id);
-                        let all_callers: Vec<_> = callers.into_iter().collect();
+                        let all_callers: Vec<_> = callers
+                            .into_iter()
+                            .filter(|(_, _, provenance)| {
+                                !resolved_only || *provenance == Provenance::Resolved
+                            })
+                            .collect();
                         Some(all_callers)
                     } else {
                         None // Symbol not found
@@ -2347,10 +2953,13 @@ async fn main() {
                         // Check all symbols with this name and deduplicate (same as MCP handler)
                         for symbol in &symbols {
                             let callers = indexer.get_calling_functions_with_metadata(symbol.id);
-                            for (caller, metadata) in callers {
+                            for (caller, metadata, provenance) in callers {
+                                if resolved_only && provenance != Provenance::Resolved {
+                                    continue;
+                                }
                                 // Deduplicate by symbol ID
                                 if seen_ids.insert(caller.id) {
-                                    all_callers.push((caller, metadata));
+                                    all_callers.push((caller, metadata, provenance));
                                 }
                             }
                         }
@@ -2391,7 +3000,9 @@ async fn main() {
                             .and_then(|v| v.as_u64())
                             .unwrap_or(3) as usize;
 
-                        let impacted_ids = indexer.get_impact_radius(symbol.id, Some(max_depth));
+                        let impacted_ids = indexer
+                            .get_impact_radius(symbol.id, Some(max_depth), resolved_only)
+                            .symbols;
 
                         // Convert SymbolIds to full Symbols
                         let mut impacted_symbols = Vec::new();
@@ -2423,9 +3034,12 @@ async fn main() {
                         // Aggregate impact from ALL symbols with this name (same as MCP handler)
                         let mut all_impacted_ids = HashSet::new();
                         for symbol in &symbols {
-                            let impacted_ids =
-                                indexer.get_impact_radius(symbol.id, Some(max_depth));
-                            all_impacted_ids.extend(impacted_ids);
+                            let impacted_ids = indexer.get_impact_radius(
+                                symbol.id,
+                                Some(max_depth),
+                                resolved_only,
+                            );
+                            all_impacted_ids.extend(impacted_ids.symbols);
                         }
 
                         // Convert SymbolIds to full Symbols
@@ -2471,23 +3085,161 @@ async fn main() {
                         .as_ref()
                         .and_then(|m| m.get("lang"))
                         .and_then(|v| v.as_str());
+                    let regex = arguments
+                        .as_ref()
+                        .and_then(|m| m.get("regex"))
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+                    let substring = arguments
+                        .as_ref()
+                        .and_then(|m| m.get("substring"))
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+                    let exact = arguments
+                        .as_ref()
+                        .and_then(|m| m.get("exact"))
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+                    let case_sensitive = arguments
+                        .as_ref()
+                        .and_then(|m| m.get("case_sensitive"))
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
 
                     // Parse the kind filter if provided
-                    let kind_filter = kind.as_ref().and_then(|k| match k.to_lowercase().as_str() {
-                        "function" => Some(crate::SymbolKind::Function),
-                        "struct" => Some(crate::SymbolKind::Struct),
-                        "trait" => Some(crate::SymbolKind::Trait),
-                        "method" => Some(crate::SymbolKind::Method),
-                        "field" => Some(crate::SymbolKind::Field),
-                        "module" => Some(crate::SymbolKind::Module),
-                        "constant" => Some(crate::SymbolKind::Constant),
-                        _ => None,
-                    });
+                    let kind_filter = kind
+                        .as_deref()
+                        .map(crate::SymbolKind::parse_filter)
+                        .transpose()
+                        .unwrap_or_else(|e| {
+                            eprintln!("Error: {e}");
+                            std::process::exit(1);
+                        });
+                    let visibility = arguments
+                        .as_ref()
+                        .and_then(|m| m.get("visibility"))
+                        .and_then(|v| v.as_str())
+                        .map(codanna::Visibility::parse_filter)
+                        .transpose()
+                        .unwrap_or_else(|e| {
+                            eprintln!("Error: {e}");
+                            std::process::exit(1);
+                        });
 
-                    match indexer.search(q, limit as usize, kind_filter, module, language) {
-                        Ok(results) => Some(results),
-                        Err(_) => Some(Vec::new()),
-                    }
+                    let fields =
+                        codanna::io::parse_fields(&field_list_argument(arguments.as_ref()))
+                            .unwrap_or_else(|e| {
+                                eprintln!("Error: {e}");
+                                std::process::exit(1);
+                            });
+
+                    let fallback_semantic = arguments
+                        .as_ref()
+                        .and_then(|m| m.get("fallback_semantic"))
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+
+                    let files = arguments
+                        .as_ref()
+                        .and_then(|m| m.get("file"))
+                        .and_then(|v| v.as_str())
+                        .map(|file| indexer.files_matching(file));
+                    let filters = codanna::storage::SearchFilters {
+                        attribute: arguments
+                            .as_ref()
+                            .and_then(|m| m.get("attribute"))
+                            .and_then(|v| v.as_str()),
+                        visibility,
+                        files: files.as_ref(),
+                    };
+                    let results = if regex {
+                        indexer.search_regex(
+                            q,
+                            limit as usize,
+                            kind_filter,
+                            module,
+                            language,
+                            &filters,
+                        )
+                    } else if exact {
+                        indexer.search_exact(
+                            q,
+                            case_sensitive,
+                            limit as usize,
+                            kind_filter,
+                            module,
+                            language,
+                            &filters,
+                        )
+                    } else if substring {
+                        indexer.search_substring(
+                            q,
+                            limit as usize,
+                            kind_filter,
+                            module,
+                            language,
+                            &filters,
+                        )
+                    } else {
+                        indexer.search(q, limit as usize, kind_filter, module, language, &filters)
+                    };
+                    let results = match results {
+                        // Results carry a source tag, so semantic fallbacks are distinguishable
+                        Ok(results)
+                            if results.is_empty()
+                                && fallback_semantic
+                                && indexer.has_semantic_search() =>
+                        {
+                            indexer
+                                .search_semantic_fallback(
+                                    q,
+                                    limit as usize,
+                                    kind_filter,
+                                    module,
+                                    language,
+                                    &filters,
+                                )
+                                .unwrap_or_default()
+                        }
+                        Ok(results) => results,
+                        // An invalid pattern is a usage error, not an empty result
+                        Err(e) if regex => {
+                            eprintln!("Error: {e}");
+                            std::process::exit(codanna::io::ExitCode::GeneralError as i32);
+                        }
+                        Err(_) => Vec::new(),
+                    };
+                    let group_by = arguments
+                        .as_ref()
+                        .and_then(|m| m.get("group_by"))
+                        .and_then(|v| v.as_str())
+                        .map(codanna::storage::SearchGroupBy::parse_filter)
+                        .transpose()
+                        .unwrap_or_else(|e| {
+                            eprintln!("Error: {e}");
+                            std::process::exit(1);
+                        });
+                    // With `group_by`, counts per group replace the results;
+                    // with `fields`, each result keeps only the selected keys
+                    Some(match group_by {
+                        Some(group_by) => codanna::storage::group_results(&results, group_by)
+                            .into_iter()
+                            .map(|group| serde_json::to_value(group).unwrap_or_default())
+                            .collect::<Vec<_>>(),
+                        None => results
+                            .into_iter()
+                            .map(|result| {
+                                if fields.is_empty() {
+                                    serde_json::to_value(result)
+                                } else {
+                                    serde_json::to_value(codanna::io::FieldRow::new(
+                                        &fields, result,
+                                    ))
+                                }
+                                .unwrap_or_default()
+                            })
+                            .collect::<Vec<_>>(),
+                    })
                 } else {
                     None
                 }
@@ -2495,19 +3247,7 @@ async fn main() {
                 None
             };
 
-            // Collect data for semantic_search_docs if JSON output is requested
-            #[derive(serde::Serialize)]
-            struct SemanticSearchResult {
-                symbol: Symbol,
-                score: f32,
-            }
-
-            #[derive(serde::Serialize)]
-            struct SemanticSearchWithContextResult {
-                symbol: Symbol,
-                score: f32,
-                context: codanna::symbol::context::SymbolContext,
-            }
+            use codanna::semantic::{SemanticSearchResult, SemanticSearchWithContextResult};
 
             // Get guidance config before moving indexer
             let guidance_config = indexer.settings().guidance.clone();
@@ -2536,6 +3276,11 @@ async fn main() {
                             .as_ref()
                             .and_then(|m| m.get("lang"))
                             .and_then(|v| v.as_str());
+                        let explain = arguments
+                            .as_ref()
+                            .and_then(|m| m.get("explain"))
+                            .and_then(|v| v.as_bool())
+                            .unwrap_or(false);
 
                         let results = match threshold {
                             Some(t) => indexer.semantic_search_docs_with_threshold_and_language(
@@ -2548,7 +3293,12 @@ async fn main() {
                             Ok(results) => {
                                 let semantic_results: Vec<SemanticSearchResult> = results
                                     .into_iter()
-                                    .map(|(symbol, score)| SemanticSearchResult { symbol, score })
+                                    .map(|(symbol, score)| SemanticSearchResult {
+                                        explanation: explain
+                                            .then(|| codanna::semantic::explain_match(q, &symbol)),
+                                        symbol,
+                                        score,
+                                    })
                                     .collect();
                                 Some(semantic_results)
                             }
@@ -2638,6 +3388,105 @@ async fn main() {
                 None
             };
 
+            // If we need JSON output for find_occurrences, collect before moving indexer
+            let find_occurrences_data = if json && tool == "find_occurrences" {
+                let limit = arguments
+                    .as_ref()
+                    .and_then(|m| m.get("limit"))
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(100) as usize;
+                arguments
+                    .as_ref()
+                    .and_then(|m| m.get("name"))
+                    .and_then(|v| v.as_str())
+                    .map(|name| indexer.find_occurrences(name, limit))
+            } else {
+                None
+            };
+
+            // If we need JSON output for resolve_import, resolve before moving indexer
+            let resolve_import_data = if json && tool == "resolve_import" {
+                let get_str = |key: &str| {
+                    arguments
+                        .as_ref()
+                        .and_then(|m| m.get(key))
+                        .and_then(|v| v.as_str())
+                };
+                match (get_str("file_path"), get_str("name")) {
+                    (Some(file_path), Some(name)) => Some(indexer.resolve_import(file_path, name)),
+                    _ => None,
+                }
+            } else {
+                None
+            };
+
+            // If we need JSON output for symbol_at, look it up before moving indexer
+            let symbol_at_data = if json && tool == "symbol_at" {
+                symbol_at_request(arguments.as_ref()).map(|request| {
+                    let position =
+                        format!("{}:{}:{}", request.file_path, request.line, request.column);
+                    let found = request.position().and_then(|(line, column)| {
+                        indexer
+                            .symbol_at(&request.file_path, line, column)
+                            .map_err(|e| e.to_string())
+                    });
+                    (position, found)
+                })
+            } else {
+                None
+            };
+
+            // If we need JSON output for find_implementors_of_method, collect before moving indexer
+            let method_implementations_data = if json && tool == "find_implementors_of_method" {
+                let get_str = |key: &str| {
+                    arguments
+                        .as_ref()
+                        .and_then(|m| m.get(key))
+                        .and_then(|v| v.as_str())
+                };
+                get_str("method").map(|method| {
+                    (
+                        method.to_string(),
+                        indexer.find_method_implementations(method, get_str("lang")),
+                    )
+                })
+            } else {
+                None
+            };
+
+            // If we need JSON output for get_type_hierarchy, collect before moving indexer
+            let type_hierarchy_data = if json && tool == "get_type_hierarchy" {
+                use codanna::symbol::context::HierarchyDirection;
+                let get_str = |key: &str| {
+                    arguments
+                        .as_ref()
+                        .and_then(|m| m.get(key))
+                        .and_then(|v| v.as_str())
+                };
+                let max_depth = arguments
+                    .as_ref()
+                    .and_then(|m| m.get("max_depth"))
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(3) as usize;
+                get_str("name").map(|name| {
+                    let hierarchy = get_str("direction")
+                        .map(HierarchyDirection::parse_filter)
+                        .transpose()
+                        .map_err(codanna::IndexError::General)
+                        .and_then(|direction| {
+                            indexer.find_type_hierarchy(
+                                name,
+                                get_str("lang"),
+                                direction.unwrap_or(HierarchyDirection::Both),
+                                max_depth,
+                            )
+                        });
+                    (name.to_string(), hierarchy)
+                })
+            } else {
+                None
+            };
+
             // Check semantic search status before moving indexer
             let has_semantic_search = indexer.has_semantic_search();
 
@@ -2679,10 +3528,25 @@ async fn main() {
                     }
                 };
 
+                // Measure on-disk size of each index part
+                let usage =
+                    codanna::storage::IndexDiskUsage::measure(&indexer.settings().index_path);
+                let disk_usage = DiskUsageInfo {
+                    bytes_per_symbol: usage.bytes_per_symbol(symbol_count),
+                    expected_semantic_vectors_bytes: semantic_search
+                        .embeddings
+                        .zip(semantic_search.dimensions)
+                        .map(|(count, dimension)| {
+                            codanna::vector::MmapVectorStorage::expected_file_size(dimension, count)
+                        }),
+                    usage,
+                };
+
                 Some(IndexInfo {
                     symbol_count,
                     file_count: file_count as usize,
                     relationship_count,
+                    relationships_indexed: indexer.relationships_indexed(),
                     symbol_kinds: SymbolKindBreakdown {
                         functions,
                         methods,
@@ -2690,6 +3554,7 @@ async fn main() {
                         traits,
                     },
                     semantic_search,
+                    disk_usage,
                 })
             } else {
                 None
@@ -2717,10 +3582,16 @@ async fn main() {
                         .and_then(|m| m.get("lang"))
                         .and_then(|v| v.as_str())
                         .map(|s| s.to_string());
+                    let limit = arguments
+                        .as_ref()
+                        .and_then(|m| m.get("limit"))
+                        .and_then(|v| v.as_u64())
+                        .unwrap_or(20) as u32;
                     server
                         .find_symbol(Parameters(FindSymbolRequest {
                             name: name.to_string(),
                             lang,
+                            limit,
                         }))
                         .await
                 }
@@ -2745,10 +3616,17 @@ async fn main() {
                         std::process::exit(1);
                     }
 
+                    let resolved_only = arguments
+                        .as_ref()
+                        .and_then(|m| m.get("resolved_only"))
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+
                     server
                         .get_calls(Parameters(GetCallsRequest {
                             function_name,
                             symbol_id,
+                            resolved_only,
                         }))
                         .await
                 }
@@ -2773,10 +3651,17 @@ async fn main() {
                         std::process::exit(1);
                     }
 
+                    let resolved_only = arguments
+                        .as_ref()
+                        .and_then(|m| m.get("resolved_only"))
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+
                     server
                         .find_callers(Parameters(FindCallersRequest {
                             function_name,
                             symbol_id,
+                            resolved_only,
                         }))
                         .await
                 }
@@ -2806,17 +3691,169 @@ async fn main() {
                         .and_then(|m| m.get("max_depth"))
                         .and_then(|v| v.as_u64())
                         .unwrap_or(3) as u32;
+
+                    let format = arguments
+                        .as_ref()
+                        .and_then(|m| m.get("format"))
+                        .and_then(|v| v.as_str())
+                        .map(|s| s.to_string());
+                    let resolved_only = arguments
+                        .as_ref()
+                        .and_then(|m| m.get("resolved_only"))
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
                     server
                         .analyze_impact(Parameters(AnalyzeImpactRequest {
                             symbol_name,
                             symbol_id,
                             max_depth,
+                            format,
+                            resolved_only,
                         }))
                         .await
                 }
-                "get_index_info" => {
-                    use codanna::mcp::GetIndexInfoRequest;
-                    use rmcp::handler::server::wrapper::Parameters;
+                "get_symbol_source" => {
+                    let get_str = |key: &str| {
+                        arguments
+                            .as_ref()
+                            .and_then(|m| m.get(key))
+                            .and_then(|v| v.as_str())
+                            .map(|s| s.to_string())
+                    };
+
+                    let symbol_name = get_str("symbol_name");
+                    let symbol_id = arguments
+                        .as_ref()
+                        .and_then(|m| m.get("symbol_id"))
+                        .and_then(|v| v.as_u64())
+                        .map(|id| id as u32);
+
+                    // Require either symbol_name or symbol_id
+                    if symbol_name.is_none() && symbol_id.is_none() {
+                        eprintln!(
+                            "Error: get_symbol_source requires either 'symbol_name' or 'symbol_id' parameter"
+                        );
+                        std::process::exit(1);
+                    }
+
+                    let context_lines = arguments
+                        .as_ref()
+                        .and_then(|m| m.get("context_lines"))
+                        .and_then(|v| v.as_u64())
+                        .unwrap_or(3) as u32;
+
+                    server
+                        .get_symbol_source(Parameters(GetSymbolSourceRequest {
+                            symbol_name,
+                            symbol_id,
+                            file: get_str("file"),
+                            module: get_str("module"),
+                            lang: get_str("lang"),
+                            context_lines,
+                        }))
+                        .await
+                }
+                "find_occurrences" => {
+                    let name = arguments
+                        .as_ref()
+                        .and_then(|m| m.get("name"))
+                        .and_then(|v| v.as_str())
+                        .unwrap_or_else(|| {
+                            eprintln!("Error: find_occurrences requires 'name' parameter");
+                            std::process::exit(1);
+                        });
+                    let limit = arguments
+                        .as_ref()
+                        .and_then(|m| m.get("limit"))
+                        .and_then(|v| v.as_u64())
+                        .unwrap_or(100) as u32;
+                    server
+                        .find_occurrences(Parameters(FindOccurrencesRequest {
+                            name: name.to_string(),
+                            limit,
+                        }))
+                        .await
+                }
+                "resolve_import" => {
+                    let get_str = |key: &str| {
+                        arguments
+                            .as_ref()
+                            .and_then(|m| m.get(key))
+                            .and_then(|v| v.as_str())
+                            .map(|s| s.to_string())
+                    };
+
+                    let (Some(file_path), Some(name)) = (get_str("file_path"), get_str("name"))
+                    else {
+                        eprintln!(
+                            "Error: resolve_import requires 'file_path' and 'name' parameters"
+                        );
+                        std::process::exit(1);
+                    };
+
+                    server
+                        .resolve_import(Parameters(ResolveImportRequest { file_path, name }))
+                        .await
+                }
+                "find_implementors_of_method" => {
+                    let get_str = |key: &str| {
+                        arguments
+                            .as_ref()
+                            .and_then(|m| m.get(key))
+                            .and_then(|v| v.as_str())
+                            .map(|s| s.to_string())
+                    };
+                    let Some(method) = get_str("method") else {
+                        eprintln!(
+                            "Error: find_implementors_of_method requires 'method' parameter (Trait::method)"
+                        );
+                        std::process::exit(1);
+                    };
+                    server
+                        .find_implementors_of_method(Parameters(FindImplementorsOfMethodRequest {
+                            method,
+                            lang: get_str("lang"),
+                        }))
+                        .await
+                }
+                "get_type_hierarchy" => {
+                    let get_str = |key: &str| {
+                        arguments
+                            .as_ref()
+                            .and_then(|m| m.get(key))
+                            .and_then(|v| v.as_str())
+                            .map(|s| s.to_string())
+                    };
+                    let Some(name) = get_str("name") else {
+                        eprintln!("Error: get_type_hierarchy requires 'name' parameter");
+                        std::process::exit(1);
+                    };
+                    let max_depth = arguments
+                        .as_ref()
+                        .and_then(|m| m.get("max_depth"))
+                        .and_then(|v| v.as_u64())
+                        .unwrap_or(3) as u32;
+                    server
+                        .get_type_hierarchy(Parameters(GetTypeHierarchyRequest {
+                            name,
+                            direction: get_str("direction"),
+                            max_depth,
+                            lang: get_str("lang"),
+                        }))
+                        .await
+                }
+                "symbol_at" => {
+                    let Some(request) = symbol_at_request(arguments.as_ref()) else {
+                        eprintln!(
+                            "Error: symbol_at requires 'file_path', 'line' and 'column' parameters"
+                        );
+                        std::process::exit(1);
+                    };
+                    server.symbol_at(Parameters(request)).await
+                }
+                "get_index_info" => {
+                    use codanna::mcp::GetIndexInfoRequest;
+                    use rmcp::handler::server::wrapper::Parameters;
                     server
                         .get_index_info(Parameters(GetIndexInfoRequest {}))
                         .await
@@ -2850,6 +3887,57 @@ async fn main() {
                         .and_then(|m| m.get("lang"))
                         .and_then(|v| v.as_str())
                         .map(|s| s.to_string());
+                    let regex = arguments
+                        .as_ref()
+                        .and_then(|m| m.get("regex"))
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+                    let substring = arguments
+                        .as_ref()
+                        .and_then(|m| m.get("substring"))
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+                    let exact = arguments
+                        .as_ref()
+                        .and_then(|m| m.get("exact"))
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+                    let case_sensitive = arguments
+                        .as_ref()
+                        .and_then(|m| m.get("case_sensitive"))
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+                    let fallback_semantic = arguments
+                        .as_ref()
+                        .and_then(|m| m.get("fallback_semantic"))
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+                    let min_score = arguments
+                        .as_ref()
+                        .and_then(|m| m.get("min_score"))
+                        .and_then(|v| v.as_f64())
+                        .map(|v| v as f32);
+                    let fields = field_list_argument(arguments.as_ref());
+                    let visibility = arguments
+                        .as_ref()
+                        .and_then(|m| m.get("visibility"))
+                        .and_then(|v| v.as_str())
+                        .map(|s| s.to_string());
+                    let attribute = arguments
+                        .as_ref()
+                        .and_then(|m| m.get("attribute"))
+                        .and_then(|v| v.as_str())
+                        .map(|s| s.to_string());
+                    let group_by = arguments
+                        .as_ref()
+                        .and_then(|m| m.get("group_by"))
+                        .and_then(|v| v.as_str())
+                        .map(|s| s.to_string());
+                    let file = arguments
+                        .as_ref()
+                        .and_then(|m| m.get("file"))
+                        .and_then(|v| v.as_str())
+                        .map(|s| s.to_string());
                     server
                         .search_symbols(Parameters(SearchSymbolsRequest {
                             query: query.to_string(),
@@ -2857,6 +3945,17 @@ async fn main() {
                             kind,
                             module,
                             lang,
+                            file,
+                            regex,
+                            substring,
+                            exact,
+                            case_sensitive,
+                            fallback_semantic,
+                            min_score,
+                            fields,
+                            visibility,
+                            attribute,
+                            group_by,
                         }))
                         .await
                 }
@@ -2884,12 +3983,18 @@ async fn main() {
                         .and_then(|m| m.get("lang"))
                         .and_then(|v| v.as_str())
                         .map(|s| s.to_string());
+                    let explain = arguments
+                        .as_ref()
+                        .and_then(|m| m.get("explain"))
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
                     server
                         .semantic_search_docs(Parameters(SemanticSearchRequest {
                             query: query.to_string(),
                             limit,
                             threshold,
                             lang,
+                            explain,
                         }))
                         .await
                 }
@@ -2938,14 +4043,14 @@ async fn main() {
                             ExitCode::GeneralError,
                             &format!("Unknown tool: {tool}"),
                             vec![
-                                "Available tools: find_symbol, get_calls, find_callers, analyze_impact, get_index_info, search_symbols, semantic_search_docs, semantic_search_with_context",
+                                "Available tools: find_symbol, get_calls, find_callers, analyze_impact, get_symbol_source, resolve_import, symbol_at, find_implementors_of_method, get_type_hierarchy, find_occurrences, get_index_info, search_symbols, semantic_search_docs, semantic_search_with_context",
                             ],
                         );
                         println!("{}", serde_json::to_string_pretty(&response).unwrap());
                     } else {
                         eprintln!("Unknown tool: {tool}");
                         eprintln!(
-                            "Available tools: find_symbol, get_calls, find_callers, analyze_impact, get_index_info, search_symbols, semantic_search_docs, semantic_search_with_context"
+                            "Available tools: find_symbol, get_calls, find_callers, analyze_impact, get_symbol_source, resolve_import, symbol_at, find_implementors_of_method, get_type_hierarchy, find_occurrences, get_index_info, search_symbols, semantic_search_docs, semantic_search_with_context"
                         );
                     }
                     std::process::exit(1);
@@ -2975,11 +4080,188 @@ async fn main() {
 
                             println!("{}", serde_json::to_string_pretty(&response).unwrap());
                         }
+                    } else if json && tool == "find_occurrences" {
+                        // Use pre-collected data for JSON output
+                        if let Some(occurrences) = find_occurrences_data {
+                            use codanna::io::exit_code::ExitCode;
+                            use codanna::io::format::JsonResponse;
+                            match occurrences {
+                                Ok(occurrences) if occurrences.is_empty() => {
+                                    let name = arguments
+                                        .as_ref()
+                                        .and_then(|m| m.get("name"))
+                                        .and_then(|v| v.as_str())
+                                        .unwrap_or("unknown");
+                                    let response = JsonResponse::not_found("Occurrence", name);
+                                    println!(
+                                        "{}",
+                                        serde_json::to_string_pretty(&response).unwrap()
+                                    );
+                                    std::process::exit(3);
+                                }
+                                Ok(occurrences) => {
+                                    let response = JsonResponse::success(occurrences);
+                                    println!(
+                                        "{}",
+                                        serde_json::to_string_pretty(&response).unwrap()
+                                    );
+                                }
+                                Err(e) => {
+                                    let response = JsonResponse::error(
+                                        ExitCode::GeneralError,
+                                        &e.to_string(),
+                                        vec![
+                                            "Set indexing.index_occurrences = true and re-index with --force",
+                                        ],
+                                    );
+                                    println!(
+                                        "{}",
+                                        serde_json::to_string_pretty(&response).unwrap()
+                                    );
+                                    std::process::exit(1);
+                                }
+                            }
+                        }
+                    } else if json && tool == "resolve_import" {
+                        // Use pre-collected data for JSON output
+                        if let Some(resolution) = resolve_import_data {
+                            use codanna::io::exit_code::ExitCode;
+                            use codanna::io::format::JsonResponse;
+                            match resolution {
+                                Ok(resolution) => {
+                                    let response = JsonResponse::success(resolution);
+                                    println!(
+                                        "{}",
+                                        serde_json::to_string_pretty(&response).unwrap()
+                                    );
+                                }
+                                Err(e) => {
+                                    let response = JsonResponse::error(
+                                        ExitCode::GeneralError,
+                                        &e.to_string(),
+                                        vec!["Check the file path and imported name"],
+                                    );
+                                    println!(
+                                        "{}",
+                                        serde_json::to_string_pretty(&response).unwrap()
+                                    );
+                                    std::process::exit(1);
+                                }
+                            }
+                        }
+                    } else if json && tool == "symbol_at" {
+                        // Use pre-collected data for JSON output
+                        if let Some((position, found)) = symbol_at_data {
+                            use codanna::io::exit_code::ExitCode;
+                            use codanna::io::format::JsonResponse;
+                            match found {
+                                Ok(Some(found)) => {
+                                    let response = JsonResponse::success(found);
+                                    println!(
+                                        "{}",
+                                        serde_json::to_string_pretty(&response).unwrap()
+                                    );
+                                }
+                                Ok(None) => {
+                                    let response = JsonResponse::not_found("Symbol at", &position);
+                                    println!(
+                                        "{}",
+                                        serde_json::to_string_pretty(&response).unwrap()
+                                    );
+                                    std::process::exit(3);
+                                }
+                                Err(e) => {
+                                    let response = JsonResponse::error(
+                                        ExitCode::GeneralError,
+                                        &e,
+                                        vec![
+                                            "Check the file path; line and column are 1-based unless zero_based:true",
+                                        ],
+                                    );
+                                    println!(
+                                        "{}",
+                                        serde_json::to_string_pretty(&response).unwrap()
+                                    );
+                                    std::process::exit(1);
+                                }
+                            }
+                        }
+                    } else if json && tool == "find_implementors_of_method" {
+                        // Use pre-collected data for JSON output
+                        if let Some((method, implementations)) = method_implementations_data {
+                            use codanna::io::exit_code::ExitCode;
+                            use codanna::io::format::JsonResponse;
+                            match implementations {
+                                Ok(implementations) if implementations.is_empty() => {
+                                    let response =
+                                        JsonResponse::not_found("Method implementation", &method);
+                                    println!(
+                                        "{}",
+                                        serde_json::to_string_pretty(&response).unwrap()
+                                    );
+                                    std::process::exit(3);
+                                }
+                                Ok(implementations) => {
+                                    let response = JsonResponse::success(implementations);
+                                    println!(
+                                        "{}",
+                                        serde_json::to_string_pretty(&response).unwrap()
+                                    );
+                                }
+                                Err(e) => {
+                                    let response = JsonResponse::error(
+                                        ExitCode::GeneralError,
+                                        &e.to_string(),
+                                        vec!["Pass the method as Trait::method"],
+                                    );
+                                    println!(
+                                        "{}",
+                                        serde_json::to_string_pretty(&response).unwrap()
+                                    );
+                                    std::process::exit(1);
+                                }
+                            }
+                        }
+                    } else if json && tool == "get_type_hierarchy" {
+                        // Use pre-collected data for JSON output
+                        if let Some((name, hierarchy)) = type_hierarchy_data {
+                            use codanna::io::exit_code::ExitCode;
+                            use codanna::io::format::JsonResponse;
+                            match hierarchy {
+                                Ok(hierarchy) => {
+                                    let response = JsonResponse::success(hierarchy);
+                                    println!(
+                                        "{}",
+                                        serde_json::to_string_pretty(&response).unwrap()
+                                    );
+                                }
+                                Err(codanna::IndexError::SymbolNotFound { .. }) => {
+                                    let response = JsonResponse::not_found("Type", &name);
+                                    println!(
+                                        "{}",
+                                        serde_json::to_string_pretty(&response).unwrap()
+                                    );
+                                    std::process::exit(3);
+                                }
+                                Err(e) => {
+                                    let response = JsonResponse::error(
+                                        ExitCode::GeneralError,
+                                        &e.to_string(),
+                                        vec!["direction accepts ancestors, descendants or both"],
+                                    );
+                                    println!(
+                                        "{}",
+                                        serde_json::to_string_pretty(&response).unwrap()
+                                    );
+                                    std::process::exit(1);
+                                }
+                            }
+                        }
                     } else if json && tool == "find_symbol" {
                         // Use pre-collected data for JSON output
-                        if let Some(symbol_contexts) = find_symbol_data {
+                        if let Some((symbol_contexts, total)) = find_symbol_data {
                             use codanna::io::format::JsonResponse;
-                            if symbol_contexts.is_empty() {
+                            if total == 0 {
                                 let name = arguments
                                     .as_ref()
                                     .and_then(|m| m.get("name"))
@@ -2990,21 +4272,32 @@ async fn main() {
                                 std::process::exit(3);
                             } else {
                                 use codanna::io::guidance_engine::generate_guidance_from_config;
+                                let shown = symbol_contexts.len();
                                 let mut response = JsonResponse::success(symbol_contexts);
 
                                 // Add system guidance
-                                let result_count =
-                                    response.data.as_ref().map(|d| d.len()).unwrap_or(0);
-                                if let Some(guidance) = generate_guidance_from_config(
+                                let guidance = generate_guidance_from_config(
                                     &guidance_config,
                                     "find_symbol",
                                     arguments
                                         .as_ref()
                                         .and_then(|m| m.get("name"))
                                         .and_then(|v| v.as_str()),
-                                    result_count,
-                                ) {
-                                    response = response.with_system_message(&guidance);
+                                    total,
+                                );
+                                let truncated = (shown < total).then(|| {
+                                    format!(
+                                        "Showing {shown} of {total} matching symbols; raise limit or pass lang to narrow the search"
+                                    )
+                                });
+                                let message = match (truncated, guidance) {
+                                    (Some(note), Some(guidance)) => {
+                                        Some(format!("{note}\n{guidance}"))
+                                    }
+                                    (note, guidance) => note.or(guidance),
+                                };
+                                if let Some(message) = message {
+                                    response = response.with_system_message(&message);
                                 }
 
                                 println!("{}", serde_json::to_string_pretty(&response).unwrap());
@@ -3116,25 +4409,11 @@ async fn main() {
                                 };
                                 use codanna::io::guidance_engine::generate_guidance_from_config;
 
-                                // Create a proper struct for the empty case
-                                #[derive(serde::Serialize)]
-                                struct EmptyImpactResult {
-                                    symbol: String,
-                                    impacted_count: usize,
-                                    impacted_symbols: Vec<String>,
-                                    message: String,
-                                }
-
-                                let impact_result = EmptyImpactResult {
-                                    symbol: identifier.clone(),
-                                    impacted_count: 0,
-                                    impacted_symbols: vec![],
-                                    message:
-                                        "No symbols would be impacted by changes to this symbol"
-                                            .to_string(),
-                                };
-
-                                let mut response = JsonResponse::success(impact_result);
+                                // Same shape as a non-empty result
+                                let mut response = JsonResponse::success(impacted);
+                                response.message =
+                                    "No symbols would be impacted by changes to this symbol"
+                                        .to_string();
 
                                 // Add guidance for no results case
                                 if let Some(guidance) = generate_guidance_from_config(
@@ -3194,29 +4473,15 @@ async fn main() {
                             use codanna::io::format::JsonResponse;
                             use codanna::io::guidance_engine::generate_guidance_from_config;
                             if results.is_empty() {
-                                // Create proper struct for empty search results
-                                #[derive(serde::Serialize)]
-                                struct EmptySearchResult {
-                                    query: String,
-                                    result_count: usize,
-                                    results: Vec<String>,
-                                    message: String,
-                                }
-
                                 let query = arguments
                                     .as_ref()
                                     .and_then(|m| m.get("query"))
                                     .and_then(|v| v.as_str())
                                     .unwrap_or("unknown");
 
-                                let search_result = EmptySearchResult {
-                                    query: query.to_string(),
-                                    result_count: 0,
-                                    results: vec![],
-                                    message: "No results found for query".to_string(),
-                                };
-
-                                let mut response = JsonResponse::success(search_result);
+                                // Same shape as a non-empty result
+                                let mut response = JsonResponse::success(results);
+                                response.message = "No results found for query".to_string();
 
                                 // Add guidance for no results
                                 if let Some(guidance) = generate_guidance_from_config(
@@ -3267,30 +4532,16 @@ async fn main() {
                             use codanna::io::format::JsonResponse;
                             use codanna::io::guidance_engine::generate_guidance_from_config;
                             if results.is_empty() {
-                                // Create proper struct for empty semantic search
-                                #[derive(serde::Serialize)]
-                                struct EmptySemanticResult {
-                                    query: String,
-                                    result_count: usize,
-                                    results: Vec<String>,
-                                    message: String,
-                                }
-
                                 let query = arguments
                                     .as_ref()
                                     .and_then(|m| m.get("query"))
                                     .and_then(|v| v.as_str())
                                     .unwrap_or("unknown");
 
-                                let semantic_result = EmptySemanticResult {
-                                    query: query.to_string(),
-                                    result_count: 0,
-                                    results: vec![],
-                                    message: "No semantically similar documentation found"
-                                        .to_string(),
-                                };
-
-                                let mut response = JsonResponse::success(semantic_result);
+                                // Same shape as a non-empty result
+                                let mut response = JsonResponse::success(results);
+                                response.message =
+                                    "No semantically similar documentation found".to_string();
 
                                 // Add guidance for no results
                                 if let Some(guidance) = generate_guidance_from_config(
@@ -3352,30 +4603,16 @@ async fn main() {
                             use codanna::io::format::JsonResponse;
                             use codanna::io::guidance_engine::generate_guidance_from_config;
                             if results.is_empty() {
-                                // Create proper struct for empty semantic search with context
-                                #[derive(serde::Serialize)]
-                                struct EmptyContextResult {
-                                    query: String,
-                                    result_count: usize,
-                                    results: Vec<String>,
-                                    message: String,
-                                }
-
                                 let query = arguments
                                     .as_ref()
                                     .and_then(|m| m.get("query"))
                                     .and_then(|v| v.as_str())
                                     .unwrap_or("unknown");
 
-                                let context_result = EmptyContextResult {
-                                    query: query.to_string(),
-                                    result_count: 0,
-                                    results: vec![],
-                                    message: "No semantically similar documentation found"
-                                        .to_string(),
-                                };
-
-                                let mut response = JsonResponse::success(context_result);
+                                // Same shape as a non-empty result
+                                let mut response = JsonResponse::success(results);
+                                response.message =
+                                    "No semantically similar documentation found".to_string();
 
                                 // Add guidance for no results
                                 if let Some(guidance) = generate_guidance_from_config(
@@ -3465,8 +4702,23 @@ async fn main() {
             }
         }
 
-        Commands::Benchmark { language, file } => {
-            run_benchmark_command(&language, file);
+        Commands::Benchmark {
+            language,
+            file,
+            save,
+            baseline,
+            tolerance,
+        } => {
+            let exit_code = run_benchmark_command(
+                &language,
+                file,
+                save.as_deref(),
+                baseline.as_deref(),
+                tolerance,
+            );
+            if exit_code != codanna::io::ExitCode::Success {
+                std::process::exit(exit_code as i32);
+            }
         }
 
         Commands::Parse { .. } => {
@@ -3474,6 +4726,16 @@ async fn main() {
             unreachable!("Parse command should have been handled earlier");
         }
 
+        Commands::Clear { .. } => {
+            // Already handled with early return above
+            unreachable!("Clear command should have been handled earlier");
+        }
+
+        Commands::Schema { .. } => {
+            // Already handled with early return above
+            unreachable!("Schema command should have been handled earlier");
+        }
+
         Commands::Plugin { action } => {
             // Execute plugin management command
             use codanna::plugins;
@@ -3482,16 +4744,29 @@ async fn main() {
                     marketplace,
                     plugin_name,
                     r#ref,
+                    from_file,
                     force,
                     dry_run,
-                } => plugins::add_plugin(
-                    &config,
-                    &marketplace,
-                    &plugin_name,
-                    r#ref.as_deref(),
-                    force,
-                    dry_run,
-                ),
+                } => {
+                    if from_file {
+                        plugins::add_plugin_from_dir(
+                            &config,
+                            Path::new(&marketplace),
+                            &plugin_name,
+                            force,
+                            dry_run,
+                        )
+                    } else {
+                        plugins::add_plugin(
+                            &config,
+                            &marketplace,
+                            &plugin_name,
+                            r#ref.as_deref(),
+                            force,
+                            dry_run,
+                        )
+                    }
+                }
                 PluginAction::Remove {
                     plugin_name,
                     force,
@@ -3511,12 +4786,14 @@ async fn main() {
                 PluginAction::Verify {
                     plugin_name,
                     all,
+                    repair,
                     verbose,
                 } => {
                     if all {
                         plugins::verify_all_plugins(&config, verbose)
                     } else {
                         match plugin_name {
+                            Some(name) if repair => plugins::repair_plugin(&config, &name, verbose),
                             Some(name) => plugins::verify_plugin(&config, &name, verbose),
                             None => {
                                 eprintln!(
@@ -3546,7 +4823,14 @@ async fn main() {
                     profile_name,
                     source,
                     force,
-                } => profiles::init_profile(&profile_name, source.as_deref(), force),
+                    set,
+                } => profiles::variables::parse_assignments(&set).and_then(|variables| {
+                    profiles::init_profile(&profile_name, source.as_deref(), force, &variables)
+                }),
+                ProfileAction::Show {
+                    profile_name,
+                    source,
+                } => profiles::show_profile(&profile_name, source.as_deref()),
                 ProfileAction::Install {
                     profile_name,
                     source,
@@ -3575,6 +4859,7 @@ async fn main() {
                     profile_name,
                     force,
                 } => profiles::update_profile(&profile_name, force),
+                ProfileAction::Diff { profile_name } => profiles::diff_profile(&profile_name),
                 ProfileAction::Provider { action } => match action {
                     ProviderAction::Add { source, id } => {
                         profiles::add_provider(&source, id.as_deref())
@@ -3615,6 +4900,150 @@ async fn main() {
     }
 }
 
+/// Build a `symbol_at` request from `codanna mcp` arguments
+fn symbol_at_request(
+    arguments: Option<&serde_json::Map<String, serde_json::Value>>,
+) -> Option<codanna::mcp::SymbolAtRequest> {
+    serde_json::from_value(serde_json::Value::Object(arguments?.clone())).ok()
+}
+
+/// `fields` from `codanna mcp` arguments: `fields:name,file` or a JSON array of names
+fn field_list_argument(
+    arguments: Option<&serde_json::Map<String, serde_json::Value>>,
+) -> Vec<String> {
+    match arguments.and_then(|m| m.get("fields")) {
+        Some(serde_json::Value::String(list)) => vec![list.clone()],
+        Some(serde_json::Value::Array(names)) => names
+            .iter()
+            .filter_map(|v| v.as_str())
+            .map(|s| s.to_string())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Print the embedded MCP tools with their parameters
+///
+/// JSON output is the `tools/list` result an MCP client receives.
+fn run_list_tools_command(mcp: &codanna::config::McpConfig, json: bool) {
+    use codanna::mcp::CodeIntelligenceServer;
+    use rmcp::model::ListToolsResult;
+
+    let tools = CodeIntelligenceServer::tool_definitions(mcp);
+
+    if json {
+        let result = ListToolsResult::with_all_items(tools);
+        match serde_json::to_string_pretty(&result) {
+            Ok(payload) => println!("{payload}"),
+            Err(e) => {
+                eprintln!("Error serializing tool list: {e}");
+                std::process::exit(1);
+            }
+        }
+        std::process::exit(0);
+    }
+
+    for tool in &tools {
+        println!("{}", tool.name);
+        if let Some(description) = &tool.description {
+            println!("  {description}");
+        }
+
+        let required: Vec<&str> = tool
+            .input_schema
+            .get("required")
+            .and_then(|v| v.as_array())
+            .map(|names| names.iter().filter_map(|v| v.as_str()).collect())
+            .unwrap_or_default();
+        let properties = tool
+            .input_schema
+            .get("properties")
+            .and_then(|v| v.as_object());
+
+        match properties {
+            Some(properties) if !properties.is_empty() => {
+                println!("  Parameters:");
+                for (name, schema) in properties {
+                    let ty = match schema.get("type") {
+                        Some(serde_json::Value::String(ty)) => ty.clone(),
+                        Some(serde_json::Value::Array(types)) => types
+                            .iter()
+                            .filter_map(|v| v.as_str())
+                            .filter(|ty| *ty != "null")
+                            .collect::<Vec<_>>()
+                            .join("|"),
+                        _ => "any".to_string(),
+                    };
+                    let flag = if required.contains(&name.as_str()) {
+                        ", required"
+                    } else {
+                        ""
+                    };
+                    match schema.get("description").and_then(|v| v.as_str()) {
+                        Some(description) => {
+                            println!("    {name} ({ty}{flag}) - {description}")
+                        }
+                        None => println!("    {name} ({ty}{flag})"),
+                    }
+                }
+            }
+            _ => println!("  Parameters: none"),
+        }
+        println!();
+    }
+    std::process::exit(0);
+}
+
+/// Delete the index artifacts after confirmation and report the freed space
+fn run_clear_command(persistence: &IndexPersistence, index_path: &std::path::Path, yes: bool) {
+    use codanna::io::format::format_bytes;
+    use std::io::{IsTerminal, Write};
+
+    let artifacts = persistence.artifacts();
+    if artifacts.is_empty() {
+        println!("No index found at {}", index_path.display());
+        std::process::exit(0);
+    }
+
+    if !yes {
+        if !std::io::stdin().is_terminal() {
+            eprintln!("Error: Refusing to delete the index without confirmation");
+            eprintln!("Run 'codanna clear --yes' to delete it non-interactively");
+            std::process::exit(1);
+        }
+
+        println!("This will delete from {}:", index_path.display());
+        for path in &artifacts {
+            println!("  {}", path.display());
+        }
+        print!("Continue? [y/N] ");
+        let _ = std::io::stdout().flush();
+
+        let mut answer = String::new();
+        if std::io::stdin().read_line(&mut answer).is_err()
+            || !matches!(answer.trim(), "y" | "Y" | "yes" | "Yes")
+        {
+            println!("Aborted");
+            std::process::exit(1);
+        }
+    }
+
+    match persistence.remove_all() {
+        Ok(removed) => {
+            let freed: u64 = removed.iter().map(|(_, size)| size).sum();
+            for (path, size) in &removed {
+                println!("Removed {} ({})", path.display(), format_bytes(*size));
+            }
+            println!("Freed {}", format_bytes(freed));
+            std::process::exit(0);
+        }
+        Err(e) => {
+            eprintln!("Error: Failed to delete index: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
 /// Run parse command to output AST as JSONL
 fn run_parse_command(
     file_path: &Path,
@@ -3662,7 +5091,7 @@ mod add_paths_tests {
 
         // Add parent to config
         let (settings, added, skipped) =
-            add_paths_to_settings(std::slice::from_ref(&parent), &config_path, false)
+            add_paths_to_settings(std::slice::from_ref(&parent), &config_path, false, None)
                 .expect("parent addition should succeed");
         assert_eq!(added.len(), 1);
         assert!(skipped.is_empty());
@@ -3672,7 +5101,7 @@ mod add_paths_tests {
 
         // Attempt to add child - should be skipped and report parent coverage
         let (_, added_again, skipped_paths) =
-            add_paths_to_settings(std::slice::from_ref(&child), &config_path, false)
+            add_paths_to_settings(std::slice::from_ref(&child), &config_path, false, None)
                 .expect("child addition should be skipped gracefully");
         assert!(added_again.is_empty(), "child path should not be added");
         assert_eq!(skipped_paths.len(), 1);
@@ -3765,7 +5194,7 @@ mod add_paths_tests {
             .expect("failed to write initial config");
 
         let (settings, added, skipped) =
-            add_paths_to_settings(std::slice::from_ref(&file_path), &config_path, false)
+            add_paths_to_settings(std::slice::from_ref(&file_path), &config_path, false, None)
                 .expect("file addition should succeed");
         assert!(added.is_empty(), "file should not be persisted in config");
         assert_eq!(skipped.len(), 1);
@@ -3779,12 +5208,50 @@ mod add_paths_tests {
         );
         drop(settings); // ensure no unused warnings
     }
+
+    #[test]
+    fn test_add_paths_to_settings_persists_archives() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("settings.toml");
+        let archive_path = temp_dir.path().join("vendor.tar.gz");
+        fs::write(&archive_path, b"").unwrap();
+
+        Settings::default()
+            .save(&config_path)
+            .expect("failed to write initial config");
+
+        let (_, added, skipped) = add_paths_to_settings(
+            std::slice::from_ref(&archive_path),
+            &config_path,
+            true,
+            None,
+        )
+        .expect("archive addition should succeed");
+        assert_eq!(added, vec![archive_path]);
+        assert!(skipped.is_empty());
+    }
 }
 /// Run parser performance benchmarks
-fn run_benchmark_command(language: &str, custom_file: Option<PathBuf>) {
+fn run_benchmark_command(
+    language: &str,
+    custom_file: Option<PathBuf>,
+    save: Option<&Path>,
+    baseline: Option<&Path>,
+    tolerance: f64,
+) -> codanna::io::ExitCode {
     use codanna::display::theme::Theme;
+    use codanna::io::ExitCode;
     use console::style;
 
+    // Read the baseline first so a bad path fails before the slow part
+    let baseline = match baseline.map(BenchmarkBaseline::load).transpose() {
+        Ok(baseline) => baseline,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            return ExitCode::IoError;
+        }
+    };
+
     // Print styled header
     if Theme::should_disable_colors() {
         println!("\n=== Codanna Parser Benchmarks ===\n");
@@ -3795,32 +5262,33 @@ fn run_benchmark_command(language: &str, custom_file: Option<PathBuf>) {
         );
     }
 
-    match language.to_lowercase().as_str() {
-        "rust" => benchmark_rust_parser(custom_file),
-        "python" => benchmark_python_parser(custom_file),
-        "php" => benchmark_php_parser(custom_file),
-        "typescript" | "ts" => benchmark_typescript_parser(custom_file),
-        "go" => benchmark_go_parser(custom_file),
-        "csharp" | "c#" | "cs" => benchmark_csharp_parser(custom_file),
+    let results = match language.to_lowercase().as_str() {
+        "rust" => vec![benchmark_rust_parser(custom_file)],
+        "python" => vec![benchmark_python_parser(custom_file)],
+        "php" => vec![benchmark_php_parser(custom_file)],
+        "typescript" | "ts" => vec![benchmark_typescript_parser(custom_file)],
+        "go" => vec![benchmark_go_parser(custom_file)],
+        "csharp" | "c#" | "cs" => vec![benchmark_csharp_parser(custom_file)],
         "all" => {
-            benchmark_rust_parser(None);
+            let mut results = vec![benchmark_rust_parser(None)];
             println!();
-            benchmark_python_parser(None);
+            results.push(benchmark_python_parser(None));
             println!();
-            benchmark_php_parser(None);
+            results.push(benchmark_php_parser(None));
             println!();
-            benchmark_typescript_parser(None);
+            results.push(benchmark_typescript_parser(None));
             println!();
-            benchmark_go_parser(None);
+            results.push(benchmark_go_parser(None));
             println!();
-            benchmark_csharp_parser(None);
+            results.push(benchmark_csharp_parser(None));
+            results
         }
         _ => {
             eprintln!("Unknown language: {language}");
             eprintln!("Available languages: rust, python, php, typescript, go, csharp, all");
             std::process::exit(1);
         }
-    }
+    };
 
     // Print target info with styling
     if Theme::should_disable_colors() {
@@ -3832,9 +5300,278 @@ fn run_benchmark_command(language: &str, custom_file: Option<PathBuf>) {
             style(">10,000 symbols/second").dim()
         );
     }
+
+    let mut exit_code = ExitCode::Success;
+    let index_format = codanna::storage::INDEX_FORMAT_VERSION;
+    let baseline = match baseline {
+        Some(baseline) if baseline.index_format != index_format => {
+            eprintln!(
+                "\nBaseline was saved with index format {} (current: {index_format}); not compared",
+                baseline.index_format
+            );
+            None
+        }
+        baseline => baseline,
+    };
+    if let Some(baseline) = baseline {
+        let deltas = compare_benchmarks(&baseline.results, &results, tolerance);
+        println!("\nAgainst baseline (tolerance {tolerance}%):");
+        for delta in &deltas {
+            println!("  {delta}");
+        }
+        for result in &results {
+            if deltas.iter().any(|delta| delta.language == result.language) {
+                continue;
+            }
+            if baseline
+                .results
+                .iter()
+                .any(|saved| saved.language == result.language)
+            {
+                println!(
+                    "  {}: benchmarked different code than the baseline; not compared",
+                    result.language
+                );
+            } else {
+                println!("  {}: not in baseline", result.language);
+            }
+        }
+        let regressions = deltas.iter().filter(|delta| delta.regressed).count();
+        if regressions > 0 {
+            eprintln!("{regressions} language(s) regressed beyond {tolerance}%");
+            exit_code = ExitCode::FindingsReported;
+        }
+    }
+
+    if let Some(path) = save {
+        let saved = BenchmarkBaseline {
+            version: BenchmarkBaseline::VERSION,
+            index_format,
+            results,
+        };
+        if let Err(e) = saved.save(path) {
+            eprintln!("Error: {e}");
+            return ExitCode::IoError;
+        }
+        println!("\nSaved baseline to {}", path.display());
+    }
+
+    exit_code
 }
 
-fn benchmark_rust_parser(custom_file: Option<PathBuf>) {
+/// Compare the index with the files on disk; findings exit with code 9
+fn run_verify_command(indexer: &SimpleIndexer, json: bool) -> codanna::io::ExitCode {
+    use codanna::io::ExitCode;
+
+    let verification = indexer.verify_against_disk();
+    let exit_code = if verification.is_in_sync() {
+        ExitCode::Success
+    } else {
+        ExitCode::FindingsReported
+    };
+
+    if json {
+        match serde_json::to_string_pretty(&verification) {
+            Ok(output) => println!("{output}"),
+            Err(e) => {
+                eprintln!("Error: {e}");
+                return ExitCode::GeneralError;
+            }
+        }
+        return exit_code;
+    }
+
+    for path in &verification.changed {
+        println!("changed  {}", path.display());
+    }
+    for path in &verification.missing {
+        println!("missing  {}", path.display());
+    }
+    println!(
+        "{} ok, {} changed, {} missing",
+        verification.ok,
+        verification.changed.len(),
+        verification.missing.len()
+    );
+    if !verification.is_in_sync() {
+        eprintln!("Index is out of sync with the source. Run 'codanna index' to refresh.");
+    }
+    exit_code
+}
+
+/// Replay resolution of the calls at `PATH:LINE` and print each step
+fn run_debug_resolve_command(
+    indexer: &SimpleIndexer,
+    location: &str,
+    json: bool,
+) -> codanna::io::ExitCode {
+    use codanna::io::ExitCode;
+
+    let Some((path, line)) = location
+        .rsplit_once(':')
+        .and_then(|(path, line)| Some((path, line.parse::<u32>().ok().filter(|&l| l > 0)?)))
+    else {
+        eprintln!("Error: expected a call site as PATH:LINE, got '{location}'");
+        eprintln!("Usage: codanna debug resolve src/foo.rs:42");
+        return ExitCode::GeneralError;
+    };
+
+    let traces = match indexer.explain_call_resolution(path, line) {
+        Ok(traces) => traces,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            return ExitCode::from_error(&e);
+        }
+    };
+    if traces.is_empty() {
+        eprintln!("No calls found at {path}:{line}");
+        return ExitCode::NotFound;
+    }
+
+    if json {
+        match serde_json::to_string_pretty(&traces) {
+            Ok(output) => println!("{output}"),
+            Err(e) => {
+                eprintln!("Error: {e}");
+                return ExitCode::GeneralError;
+            }
+        }
+    } else {
+        let rendered: Vec<String> = traces.iter().map(|trace| trace.to_string()).collect();
+        println!("{}", rendered.join("\n\n"));
+    }
+    ExitCode::Success
+}
+
+/// Print the JSON Schema of one MCP tool's `--json` output, or of the whole data model
+fn run_schema_command(tool: Option<&str>) {
+    use codanna::io::ExitCode;
+    use codanna::io::json_schema::{SCHEMA_TOOLS, data_model_schemas, tool_response_schema};
+
+    let schema = match tool {
+        Some(tool) => match tool_response_schema(tool) {
+            Some(schema) => schema,
+            None => {
+                eprintln!("Error: no response schema for tool '{tool}'");
+                eprintln!("Tools with a schema: {}", SCHEMA_TOOLS.join(", "));
+                std::process::exit(ExitCode::NotFound as i32);
+            }
+        },
+        None => serde_json::Value::Object(data_model_schemas()),
+    };
+    match serde_json::to_string_pretty(&schema) {
+        Ok(output) => println!("{output}"),
+        Err(e) => {
+            eprintln!("Error: {e}");
+            std::process::exit(ExitCode::GeneralError as i32);
+        }
+    }
+    std::process::exit(ExitCode::Success as i32);
+}
+
+/// One language's parser throughput, as stored by `benchmark --save`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BenchmarkResult {
+    language: String,
+    symbols: usize,
+    avg_time_ms: f64,
+    symbols_per_second: f64,
+    /// Content hash of the parsed code, so runs on different input are not compared
+    input_hash: String,
+}
+
+/// Results file written by `benchmark --save` and read by `--baseline`
+#[derive(Debug, Serialize, Deserialize)]
+struct BenchmarkBaseline {
+    version: u32,
+    /// `INDEX_FORMAT_VERSION` of the build that saved the baseline
+    index_format: u32,
+    results: Vec<BenchmarkResult>,
+}
+
+impl BenchmarkBaseline {
+    const VERSION: u32 = 2;
+
+    fn load(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("failed to read baseline {}: {e}", path.display()))?;
+        let version = serde_json::from_str::<serde_json::Value>(&content)
+            .ok()
+            .and_then(|value| value.get("version")?.as_u64());
+        if version.is_some_and(|version| version != u64::from(Self::VERSION)) {
+            return Err(format!(
+                "baseline {} was saved in another format; save it again with --save",
+                path.display()
+            ));
+        }
+        serde_json::from_str(&content)
+            .map_err(|e| format!("invalid baseline {}: {e}", path.display()))
+    }
+
+    fn save(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, json + "\n")
+            .map_err(|e| format!("failed to write baseline {}: {e}", path.display()))
+    }
+}
+
+/// Throughput of one language against its baseline
+#[derive(Debug)]
+struct BenchmarkDelta<'a> {
+    language: &'a str,
+    baseline: f64,
+    current: f64,
+    /// Change in symbols/second, negative when slower
+    percent: f64,
+    regressed: bool,
+}
+
+impl std::fmt::Display for BenchmarkDelta<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {:.0} -> {:.0} symbols/second ({:+.1}%)",
+            self.language, self.baseline, self.current, self.percent
+        )?;
+        if self.regressed {
+            write!(f, " REGRESSION")?;
+        }
+        Ok(())
+    }
+}
+
+/// Pair current results with baseline ones by language; a drop of more than
+/// `tolerance` percent is a regression. Languages missing from either side,
+/// or benchmarked on different code, are left out.
+fn compare_benchmarks<'a>(
+    baseline: &[BenchmarkResult],
+    current: &'a [BenchmarkResult],
+    tolerance: f64,
+) -> Vec<BenchmarkDelta<'a>> {
+    current
+        .iter()
+        .filter_map(|result| {
+            let before = baseline.iter().find(|saved| {
+                saved.language == result.language && saved.input_hash == result.input_hash
+            })?;
+            if before.symbols_per_second <= 0.0 {
+                return None;
+            }
+            let percent = (result.symbols_per_second - before.symbols_per_second)
+                / before.symbols_per_second
+                * 100.0;
+            Some(BenchmarkDelta {
+                language: &result.language,
+                baseline: before.symbols_per_second,
+                current: result.symbols_per_second,
+                percent,
+                regressed: percent < -tolerance,
+            })
+        })
+        .collect()
+}
+
+fn benchmark_rust_parser(custom_file: Option<PathBuf>) -> BenchmarkResult {
     let (code, file_path) = if let Some(path) = custom_file {
         let content = std::fs::read_to_string(&path).unwrap_or_else(|e| {
             eprintln!("Failed to read {}: {e}", path.display());
@@ -3847,10 +5584,10 @@ fn benchmark_rust_parser(custom_file: Option<PathBuf>) {
     };
 
     let mut parser = RustParser::new().expect("Failed to create Rust parser");
-    benchmark_parser("Rust", &mut parser, &code, file_path);
+    benchmark_parser("Rust", &mut parser, &code, file_path)
 }
 
-fn benchmark_python_parser(custom_file: Option<PathBuf>) {
+fn benchmark_python_parser(custom_file: Option<PathBuf>) -> BenchmarkResult {
     let (code, file_path) = if let Some(path) = custom_file {
         let content = std::fs::read_to_string(&path).unwrap_or_else(|e| {
             eprintln!("Failed to read {}: {e}", path.display());
@@ -3872,10 +5609,10 @@ fn benchmark_python_parser(custom_file: Option<PathBuf>) {
     };
 
     let mut parser = PythonParser::new().expect("Failed to create Python parser");
-    benchmark_parser("Python", &mut parser, &code, file_path);
+    benchmark_parser("Python", &mut parser, &code, file_path)
 }
 
-fn benchmark_php_parser(custom_file: Option<PathBuf>) {
+fn benchmark_php_parser(custom_file: Option<PathBuf>) -> BenchmarkResult {
     let (code, file_path) = if let Some(path) = custom_file {
         let content = std::fs::read_to_string(&path).unwrap_or_else(|e| {
             eprintln!("Failed to read {}: {e}", path.display());
@@ -3888,10 +5625,10 @@ fn benchmark_php_parser(custom_file: Option<PathBuf>) {
     };
 
     let mut parser = PhpParser::new().expect("Failed to create PHP parser");
-    benchmark_parser("PHP", &mut parser, &code, file_path);
+    benchmark_parser("PHP", &mut parser, &code, file_path)
 }
 
-fn benchmark_typescript_parser(custom_file: Option<PathBuf>) {
+fn benchmark_typescript_parser(custom_file: Option<PathBuf>) -> BenchmarkResult {
     let (code, file_path) = if let Some(path) = custom_file {
         let content = std::fs::read_to_string(&path).unwrap_or_else(|e| {
             eprintln!("Failed to read {}: {e}", path.display());
@@ -3904,10 +5641,10 @@ fn benchmark_typescript_parser(custom_file: Option<PathBuf>) {
     };
 
     let mut parser = TypeScriptParser::new().expect("Failed to create TypeScript parser");
-    benchmark_parser("TypeScript", &mut parser, &code, file_path);
+    benchmark_parser("TypeScript", &mut parser, &code, file_path)
 }
 
-fn benchmark_go_parser(custom_file: Option<PathBuf>) {
+fn benchmark_go_parser(custom_file: Option<PathBuf>) -> BenchmarkResult {
     let (code, file_path) = if let Some(path) = custom_file {
         let content = std::fs::read_to_string(&path).unwrap_or_else(|e| {
             eprintln!("Failed to read {}: {e}", path.display());
@@ -3920,10 +5657,10 @@ fn benchmark_go_parser(custom_file: Option<PathBuf>) {
     };
 
     let mut parser = GoParser::new().expect("Failed to create Go parser");
-    benchmark_parser("Go", &mut parser, &code, file_path);
+    benchmark_parser("Go", &mut parser, &code, file_path)
 }
 
-fn benchmark_csharp_parser(custom_file: Option<PathBuf>) {
+fn benchmark_csharp_parser(custom_file: Option<PathBuf>) -> BenchmarkResult {
     let (code, file_path) = if let Some(path) = custom_file {
         let content = std::fs::read_to_string(&path).unwrap_or_else(|e| {
             eprintln!("Failed to read {}: {e}", path.display());
@@ -3936,7 +5673,7 @@ fn benchmark_csharp_parser(custom_file: Option<PathBuf>) {
     };
 
     let mut parser = CSharpParser::new().expect("Failed to create C# parser");
-    benchmark_parser("C#", &mut parser, &code, file_path);
+    benchmark_parser("C#", &mut parser, &code, file_path)
 }
 
 fn benchmark_parser(
@@ -3944,7 +5681,7 @@ fn benchmark_parser(
     parser: &mut dyn LanguageParser,
     code: &str,
     file_path: Option<PathBuf>,
-) {
+) -> BenchmarkResult {
     let file_id = FileId::new(1).expect("Failed to create file ID");
     let mut counter = SymbolCounter::new();
 
@@ -3994,6 +5731,14 @@ fn benchmark_parser(
             println!("\n⚠️  Warning: String allocation detected!");
         }
     }
+
+    BenchmarkResult {
+        language: language.to_string(),
+        symbols: symbols_count,
+        avg_time_ms: avg_duration.as_secs_f64() * 1000.0,
+        symbols_per_second: rate,
+        input_hash: codanna::calculate_hash(code),
+    }
 }
 
 fn generate_rust_benchmark_code() -> String {
@@ -4466,4 +6211,784 @@ mod tests {
         // This test ensures the CLI structure is valid
         Cli::command().debug_assert();
     }
+
+    #[test]
+    fn compare_benchmarks_flags_drops_beyond_tolerance() {
+        let result = |language: &str, symbols_per_second| BenchmarkResult {
+            language: language.to_string(),
+            symbols: 1000,
+            avg_time_ms: 1.0,
+            symbols_per_second,
+            input_hash: "generated".to_string(),
+        };
+        let baseline = [
+            result("Rust", 100_000.0),
+            result("Go", 50_000.0),
+            result("PHP", 80_000.0),
+        ];
+        let mut other_input = result("PHP", 40_000.0);
+        other_input.input_hash = "custom".to_string();
+        let current = [
+            result("Rust", 85_000.0),
+            result("Go", 47_500.0),
+            result("C#", 10_000.0),
+            other_input,
+        ];
+
+        let deltas = compare_benchmarks(&baseline, &current, 10.0);
+        let summary: Vec<(&str, i64, bool)> = deltas
+            .iter()
+            .map(|delta| {
+                (
+                    delta.language,
+                    delta.percent.round() as i64,
+                    delta.regressed,
+                )
+            })
+            .collect();
+        assert_eq!(summary, vec![("Rust", -15, true), ("Go", -5, false)]);
+        assert!(deltas[0].to_string().ends_with("(-15.0%) REGRESSION"));
+    }
+
+    #[test]
+    fn benchmark_baseline_without_fingerprint_is_rejected() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("bench.json");
+        std::fs::write(
+            &path,
+            r#"{"version": 1, "results": [{"language": "Rust", "symbols": 10, "avg_time_ms": 1.0, "symbols_per_second": 10000.0}]}"#,
+        )
+        .unwrap();
+        let error = BenchmarkBaseline::load(&path).unwrap_err();
+        assert!(error.contains("save it again"), "{error}");
+
+        let saved = BenchmarkBaseline {
+            version: BenchmarkBaseline::VERSION,
+            index_format: codanna::storage::INDEX_FORMAT_VERSION,
+            results: Vec::new(),
+        };
+        saved.save(&path).unwrap();
+        let loaded = BenchmarkBaseline::load(&path).unwrap();
+        assert_eq!(loaded.index_format, codanna::storage::INDEX_FORMAT_VERSION);
+    }
+}
+
+/// Send a retrieve query to the daemon named by `CODANNA_SOCKET` and replay its output
+///
+/// Returns the exit code when the daemon answered, or `None` to load the
+/// index directly: no socket configured, no daemon listening, a daemon
+/// serving another index, a `--from-stdin` batch (stdin belongs to this
+/// process) or a signature comparison (the baseline path is relative to this
+/// process).
+fn retrieve_via_query_socket(query: &RetrieveQuery, config: &Settings) -> Option<i32> {
+    use codanna::display::Theme;
+    use codanna::io::query_socket::{self, QueryDisplay, QueryRequest, QueryResponse, SOCKET_ENV};
+    use std::io::Write;
+
+    let socket = std::env::var_os(SOCKET_ENV).filter(|socket| !socket.is_empty())?;
+    if matches!(
+        query,
+        RetrieveQuery::Symbol {
+            from_stdin: true,
+            ..
+        } | RetrieveQuery::Calls {
+            from_stdin: true,
+            ..
+        } | RetrieveQuery::Callers {
+            from_stdin: true,
+            ..
+        } | RetrieveQuery::SignatureChanges { .. }
+    ) {
+        return None;
+    }
+
+    let request = QueryRequest {
+        index_path: config
+            .index_path
+            .canonicalize()
+            .unwrap_or_else(|_| config.index_path.clone()),
+        query: serde_json::to_value(query).ok()?,
+        display: QueryDisplay {
+            quiet: codanna::config::is_global_quiet(),
+            color: !Theme::should_disable_colors(),
+            path_style: config.display.path_style,
+        },
+    };
+    match query_socket::send(Path::new(&socket), &request) {
+        Ok(QueryResponse::Ok {
+            exit_code,
+            stdout,
+            stderr,
+        }) => {
+            // Broken pipes are expected when piping to head and friends
+            let _ = std::io::stdout().write_all(stdout.as_bytes());
+            let _ = std::io::stderr().write_all(stderr.as_bytes());
+            Some(exit_code)
+        }
+        Ok(QueryResponse::Rejected { reason }) => {
+            if config.debug {
+                eprintln!("DEBUG: Query daemon declined the query ({reason}); loading the index");
+            }
+            None
+        }
+        Err(e) => {
+            if config.debug {
+                eprintln!(
+                    "DEBUG: No query daemon at {} ({e}); loading the index",
+                    Path::new(&socket).display()
+                );
+            }
+            None
+        }
+    }
+}
+
+/// Answer retrieve queries on a Unix socket until the process is stopped
+///
+/// The index is reloaded when `index.meta` records a newer save, so queries
+/// see the results of `codanna index` runs made while the daemon is up. Each
+/// query runs with its client's `--quiet`, `--color` and path style.
+fn run_query_daemon(
+    mut indexer: SimpleIndexer,
+    persistence: &IndexPersistence,
+    settings: Arc<Settings>,
+    socket: &Path,
+) {
+    use codanna::display::{ColorChoice, Theme};
+    use codanna::io::capture_output;
+    use codanna::io::query_socket::{self, QueryResponse};
+
+    let index_path = settings
+        .index_path
+        .canonicalize()
+        .unwrap_or_else(|_| settings.index_path.clone());
+    let saved_at = || IndexMetadata::load(&index_path).map(|metadata| metadata.last_modified);
+    let mut loaded_at = saved_at().ok();
+
+    eprintln!(
+        "Serving retrieve queries on {} (index: {})",
+        socket.display(),
+        index_path.display()
+    );
+    eprintln!(
+        "Set {}={} to use it",
+        query_socket::SOCKET_ENV,
+        socket.display()
+    );
+
+    let result = query_socket::serve(socket, |request| {
+        if request.index_path != index_path {
+            return QueryResponse::Rejected {
+                reason: format!("serving {}", index_path.display()),
+            };
+        }
+        let query: RetrieveQuery = match serde_json::from_value(request.query) {
+            Ok(query) => query,
+            Err(e) => {
+                return QueryResponse::Rejected {
+                    reason: format!("unsupported query: {e}"),
+                };
+            }
+        };
+        if matches!(
+            query,
+            RetrieveQuery::Symbol {
+                from_stdin: true,
+                ..
+            } | RetrieveQuery::Calls {
+                from_stdin: true,
+                ..
+            } | RetrieveQuery::Callers {
+                from_stdin: true,
+                ..
+            }
+        ) {
+            return QueryResponse::Rejected {
+                reason: "--from-stdin reads the client's stdin".to_string(),
+            };
+        }
+        if matches!(query, RetrieveQuery::SignatureChanges { .. }) {
+            return QueryResponse::Rejected {
+                reason: "--baseline is a path relative to the client".to_string(),
+            };
+        }
+
+        let current = saved_at().ok();
+        if current != loaded_at {
+            match persistence.load_with_settings(settings.clone(), false) {
+                Ok(reloaded) => {
+                    indexer = reloaded;
+                    loaded_at = current;
+                }
+                Err(e) => eprintln!("Warning: Failed to reload index: {e}"),
+            }
+        }
+
+        let display = request.display;
+        let (quiet, color) = (codanna::config::is_global_quiet(), Theme::color_choice());
+        codanna::config::set_global_quiet(display.quiet);
+        Theme::set_color_choice(if display.color {
+            ColorChoice::Always
+        } else {
+            ColorChoice::Never
+        });
+        let (exit_code, output) = codanna::paths::with_path_style(display.path_style, || {
+            capture_output(|| run_retrieve_query(&indexer, query))
+        });
+        codanna::config::set_global_quiet(quiet);
+        Theme::set_color_choice(color);
+
+        QueryResponse::Ok {
+            exit_code: exit_code as i32,
+            stdout: output.stdout,
+            stderr: output.stderr,
+        }
+    });
+
+    if let Err(e) = result {
+        eprintln!("Error: Query socket failed: {e}");
+        std::process::exit(1);
+    }
+}
+
+/// `--format` values accepted by `retrieve calls` and `retrieve callers`
+const CALL_GRAPH_FORMATS: [codanna::io::OutputFormat; 3] = [
+    codanna::io::OutputFormat::Text,
+    codanna::io::OutputFormat::Json,
+    codanna::io::OutputFormat::Tree,
+];
+
+/// `--format` values accepted by the analysis commands
+const ANALYSIS_FORMATS: [codanna::io::OutputFormat; 3] = [
+    codanna::io::OutputFormat::Text,
+    codanna::io::OutputFormat::Json,
+    codanna::io::OutputFormat::GithubAnnotations,
+];
+
+/// Run a retrieve query against a loaded index
+///
+/// Errors go through `output_eprintln!` so a query daemon can capture them
+/// for its client.
+fn run_retrieve_query(indexer: &SimpleIndexer, query: RetrieveQuery) -> codanna::io::ExitCode {
+    use codanna::io::{ExitCode, OutputFormat};
+    use codanna::output_eprintln;
+    use codanna::retrieve;
+
+    match query {
+        RetrieveQuery::Symbol {
+            args,
+            json,
+            from_stdin: true,
+        } => run_retrieve_batch(indexer, retrieve::BatchCommand::Symbol, &args, json),
+        RetrieveQuery::Calls {
+            args,
+            json,
+            from_stdin: true,
+            ..
+        } => run_retrieve_batch(indexer, retrieve::BatchCommand::Calls, &args, json),
+        RetrieveQuery::Callers {
+            args,
+            json,
+            from_stdin: true,
+            ..
+        } => run_retrieve_batch(indexer, retrieve::BatchCommand::Callers, &args, json),
+        RetrieveQuery::Symbol {
+            args,
+            json,
+            from_stdin: false,
+        } => {
+            use codanna::io::args::parse_positional_args;
+
+            // Parse positional arguments for symbol name and key:value pairs
+            let (positional_name, params) = parse_positional_args(&args);
+
+            // Determine symbol name or symbol_id (priority: positional > key:value)
+            let Some(final_name) = positional_name
+                .or_else(|| params.get("name").cloned())
+                .or_else(|| params.get("symbol_id").map(|id| format!("symbol_id:{id}")))
+            else {
+                output_eprintln!("Error: symbol requires a name or symbol_id");
+                output_eprintln!("Usage: codanna retrieve symbol main");
+                output_eprintln!("   or: codanna retrieve symbol name:main");
+                output_eprintln!("   or: codanna retrieve symbol symbol_id:1771");
+                return ExitCode::GeneralError;
+            };
+
+            // Extract language filter
+            let language = params.get("lang").map(|s| s.as_str());
+
+            let format = OutputFormat::from_json_flag(json);
+            retrieve::retrieve_symbol(indexer, &final_name, language, format)
+        }
+        RetrieveQuery::Callers {
+            args,
+            json,
+            from_stdin: false,
+            format,
+            transitive,
+            depth,
+            resolved_only,
+        } => {
+            use codanna::io::args::parse_positional_args;
+
+            // Parse positional arguments for function name and key:value pairs
+            let (positional_function, params) = parse_positional_args(&args);
+
+            // Determine function name or symbol_id (priority: positional > key:value)
+            let Some(final_function) = positional_function
+                .or_else(|| params.get("function").cloned())
+                .or_else(|| params.get("symbol_id").map(|id| format!("symbol_id:{id}")))
+            else {
+                output_eprintln!("Error: callers requires a function name or symbol_id");
+                output_eprintln!("Usage: codanna retrieve callers main");
+                output_eprintln!("   or: codanna retrieve callers function:main");
+                output_eprintln!("   or: codanna retrieve callers symbol_id:1771");
+                return ExitCode::GeneralError;
+            };
+
+            // Extract language filter
+            let language = params.get("lang").map(|s| s.as_str());
+
+            let format = match format
+                .unwrap_or(OutputFormat::from_json_flag(json))
+                .supported_by("retrieve callers", &CALL_GRAPH_FORMATS)
+            {
+                Ok(format) => format,
+                Err(e) => {
+                    output_eprintln!("Error: {e}");
+                    return ExitCode::GeneralError;
+                }
+            };
+            retrieve::retrieve_callers(
+                indexer,
+                &final_function,
+                language,
+                transitive,
+                depth,
+                resolved_only,
+                format,
+            )
+        }
+        RetrieveQuery::Calls {
+            args,
+            json,
+            from_stdin: false,
+            format,
+            depth,
+            resolved_only,
+        } => {
+            use codanna::io::args::parse_positional_args;
+
+            // Parse positional arguments for function name and key:value pairs
+            let (positional_function, params) = parse_positional_args(&args);
+
+            // Determine function name or symbol_id (priority: positional > key:value)
+            let Some(final_function) = positional_function
+                .or_else(|| params.get("function").cloned())
+                .or_else(|| params.get("symbol_id").map(|id| format!("symbol_id:{id}")))
+            else {
+                output_eprintln!("Error: calls requires a function name or symbol_id");
+                output_eprintln!("Usage: codanna retrieve calls process_file");
+                output_eprintln!("   or: codanna retrieve calls function:process_file");
+                output_eprintln!("   or: codanna retrieve calls symbol_id:1771");
+                return ExitCode::GeneralError;
+            };
+
+            // Extract language filter
+            let language = params.get("lang").map(|s| s.as_str());
+
+            let format = match format
+                .unwrap_or(OutputFormat::from_json_flag(json))
+                .supported_by("retrieve calls", &CALL_GRAPH_FORMATS)
+            {
+                Ok(format) => format,
+                Err(e) => {
+                    output_eprintln!("Error: {e}");
+                    return ExitCode::GeneralError;
+                }
+            };
+            retrieve::retrieve_calls(
+                indexer,
+                &final_function,
+                language,
+                depth,
+                resolved_only,
+                format,
+            )
+        }
+        RetrieveQuery::Implementations { args, json } => {
+            use codanna::io::args::parse_positional_args;
+
+            // Parse positional arguments for trait name and key:value pairs
+            let (positional_trait, params) = parse_positional_args(&args);
+
+            // Determine trait name (priority: positional > key:value)
+            let Some(final_trait) = positional_trait.or_else(|| params.get("trait").cloned())
+            else {
+                output_eprintln!("Error: implementations requires a trait name");
+                output_eprintln!("Usage: codanna retrieve implementations Parser");
+                output_eprintln!("   or: codanna retrieve implementations trait:Parser");
+                return ExitCode::GeneralError;
+            };
+
+            // Extract language filter
+            let language = params.get("lang").map(|s| s.as_str());
+
+            let format = OutputFormat::from_json_flag(json);
+            retrieve::retrieve_implementations(indexer, &final_trait, language, format)
+        }
+        RetrieveQuery::MethodImplementations { args, json } => {
+            use codanna::io::args::parse_positional_args;
+
+            // `Trait::method` would otherwise be read as a key:value pair
+            let (path_args, rest): (Vec<String>, Vec<String>) =
+                args.into_iter().partition(|arg| arg.contains("::"));
+            let (positional_method, params) = parse_positional_args(&rest);
+
+            // Determine method path (priority: positional > key:value)
+            let Some(final_method) = path_args
+                .into_iter()
+                .next()
+                .or(positional_method)
+                .or_else(|| params.get("method").cloned())
+            else {
+                output_eprintln!("Error: method-implementations requires a trait method");
+                output_eprintln!("Usage: codanna retrieve method-implementations Parser::parse");
+                output_eprintln!("   or: codanna retrieve method-implementations Parser.parse");
+                return ExitCode::GeneralError;
+            };
+
+            // Extract language filter
+            let language = params.get("lang").map(|s| s.as_str());
+
+            let format = OutputFormat::from_json_flag(json);
+            retrieve::retrieve_method_implementations(indexer, &final_method, language, format)
+        }
+        RetrieveQuery::Search {
+            args,
+            limit,
+            json,
+            kind,
+            module,
+            attribute,
+            since_index,
+            group_by,
+            min_score,
+            substring,
+            exact,
+            case_sensitive,
+            context_lines,
+            fields,
+        } => {
+            use codanna::io::args::parse_positional_args;
+
+            // Parse positional arguments for query and key:value pairs
+            let (positional_query, params) = parse_positional_args(&args);
+
+            // Determine query source (priority: positional > key:value)
+            let Some(final_query) = positional_query.or_else(|| params.get("query").cloned())
+            else {
+                output_eprintln!("Error: search requires a query");
+                output_eprintln!("Usage: codanna retrieve search \"query\" [options]");
+                output_eprintln!("   or: codanna retrieve search query:\"search text\" [options]");
+                return ExitCode::GeneralError;
+            };
+
+            // Merge parameters (flags take precedence over key:value)
+            let final_limit = limit.unwrap_or_else(|| {
+                params
+                    .get("limit")
+                    .and_then(|s| s.parse::<usize>().ok())
+                    .unwrap_or(10)
+            });
+
+            let final_kind = kind.or_else(|| params.get("kind").cloned());
+            let final_module = module.or_else(|| params.get("module").cloned());
+            let final_attribute = attribute.or_else(|| params.get("attribute").cloned());
+            let final_since_index = since_index.or_else(|| params.get("since_index").cloned());
+            let final_group_by = group_by.or_else(|| params.get("group_by").cloned());
+            let final_min_score =
+                min_score.or_else(|| params.get("min_score").and_then(|s| s.parse::<f32>().ok()));
+            let final_substring = substring
+                || params
+                    .get("substring")
+                    .is_some_and(|s| s.parse::<bool>().unwrap_or(false));
+            let final_exact = exact
+                || params
+                    .get("exact")
+                    .is_some_and(|s| s.parse::<bool>().unwrap_or(false));
+            let final_case_sensitive = case_sensitive
+                || params
+                    .get("case_sensitive")
+                    .is_some_and(|s| s.parse::<bool>().unwrap_or(false));
+            if final_exact && final_substring {
+                output_eprintln!("Error: exact and substring cannot be combined");
+                return ExitCode::GeneralError;
+            }
+            let final_context_lines = context_lines.or_else(|| {
+                params
+                    .get("context_lines")
+                    .and_then(|s| s.parse::<u32>().ok())
+            });
+
+            let final_fields = if fields.is_empty() {
+                params.get("fields").cloned().into_iter().collect()
+            } else {
+                fields
+            };
+
+            // Extract language filter
+            let language = params.get("lang").map(|s| s.as_str());
+
+            // Call retrieve function with merged parameters
+            let format = OutputFormat::from_json_flag(json);
+            retrieve::retrieve_search(
+                indexer,
+                &final_query,
+                final_limit,
+                final_kind.as_deref(),
+                final_module.as_deref(),
+                language,
+                final_attribute.as_deref(),
+                final_since_index.as_deref(),
+                final_group_by.as_deref(),
+                final_min_score,
+                final_substring,
+                final_exact,
+                final_case_sensitive,
+                final_context_lines,
+                &final_fields,
+                format,
+            )
+        }
+        // DISABLED: Impact command handler commented out
+        // See the RetrieveQuery enum for deprecation details
+        // RetrieveQuery::Impact { args, depth, json } => {
+        //     use codanna::io::args::parse_positional_args;
+        //
+        //     // Parse positional arguments for symbol name and key:value pairs
+        //     let (positional_symbol, params) = parse_positional_args(&args);
+        //
+        //     // Determine symbol name (priority: positional > key:value)
+        //     let final_symbol = positional_symbol
+        //         .or_else(|| params.get("symbol").cloned())
+        //         .unwrap_or_else(|| {
+        //             output_eprintln!("Error: impact requires a symbol name");
+        //             output_eprintln!("Usage: codanna retrieve impact MyStruct");
+        //             output_eprintln!("   or: codanna retrieve impact symbol:MyStruct depth:3");
+        //             std::process::exit(1);
+        //         });
+        //
+        //     // Merge depth parameter (flags take precedence over key:value)
+        //     let final_depth = depth.unwrap_or_else(|| {
+        //         params
+        //             .get("depth")
+        //             .and_then(|s| s.parse::<usize>().ok())
+        //             .unwrap_or(5)
+        //     });
+        //
+        //     let format = OutputFormat::from_json_flag(json);
+        //     retrieve::retrieve_impact(indexer, &final_symbol, final_depth, format)
+        // }
+        RetrieveQuery::Describe { args, depth, json } => {
+            use codanna::io::args::parse_positional_args;
+
+            // Parse positional arguments for symbol name and key:value pairs
+            let (positional_symbol, params) = parse_positional_args(&args);
+
+            // Determine symbol name or symbol_id (priority: positional > key:value)
+            let Some(final_symbol) = positional_symbol
+                .or_else(|| params.get("symbol").cloned())
+                .or_else(|| params.get("symbol_id").map(|id| format!("symbol_id:{id}")))
+            else {
+                output_eprintln!("Error: describe requires a symbol name or symbol_id");
+                output_eprintln!("Usage: codanna retrieve describe SimpleIndexer");
+                output_eprintln!("   or: codanna retrieve describe symbol:SimpleIndexer");
+                output_eprintln!("   or: codanna retrieve describe symbol_id:1771");
+                return ExitCode::GeneralError;
+            };
+
+            // Extract language filter
+            let language = params.get("lang").map(|s| s.as_str());
+
+            // Relationship depth (priority: flag > key:value > default 1)
+            let depth = depth
+                .or_else(|| params.get("depth").and_then(|s| s.parse().ok()))
+                .unwrap_or(1);
+
+            let format = OutputFormat::from_json_flag(json);
+            retrieve::retrieve_describe(indexer, &final_symbol, language, depth, format)
+        }
+        RetrieveQuery::Coupling { top, json } => {
+            let format = OutputFormat::from_json_flag(json);
+            retrieve::retrieve_coupling(indexer, top, format)
+        }
+        RetrieveQuery::Entrypoints {
+            limit,
+            json,
+            format,
+            fail_on_findings,
+        } => {
+            let format = match format
+                .unwrap_or(OutputFormat::from_json_flag(json))
+                .supported_by("retrieve entrypoints", &ANALYSIS_FORMATS)
+            {
+                Ok(format) => format,
+                Err(e) => {
+                    output_eprintln!("Error: {e}");
+                    return ExitCode::GeneralError;
+                }
+            };
+            retrieve::retrieve_entrypoints(indexer, limit, format, fail_on_findings)
+        }
+        RetrieveQuery::Ambiguities {
+            json,
+            format,
+            fail_on_findings,
+        } => {
+            let format = match format
+                .unwrap_or(OutputFormat::from_json_flag(json))
+                .supported_by("retrieve ambiguities", &ANALYSIS_FORMATS)
+            {
+                Ok(format) => format,
+                Err(e) => {
+                    output_eprintln!("Error: {e}");
+                    return ExitCode::GeneralError;
+                }
+            };
+            retrieve::retrieve_ambiguities(indexer, format, fail_on_findings)
+        }
+        RetrieveQuery::DocCoverage { min, json } => {
+            let format = OutputFormat::from_json_flag(json);
+            retrieve::retrieve_doc_coverage(indexer, min, format)
+        }
+        RetrieveQuery::SignatureChanges { baseline, json } => {
+            let format = OutputFormat::from_json_flag(json);
+            retrieve::retrieve_signature_changes(indexer, &baseline, format)
+        }
+        RetrieveQuery::History { args, json } => {
+            use codanna::io::args::parse_positional_args;
+
+            let (positional_symbol, params) = parse_positional_args(&args);
+
+            let Some(final_symbol) = positional_symbol
+                .or_else(|| params.get("symbol").cloned())
+                .or_else(|| params.get("symbol_id").map(|id| format!("symbol_id:{id}")))
+            else {
+                output_eprintln!("Error: history requires a symbol name or symbol_id");
+                output_eprintln!("Usage: codanna retrieve history SimpleIndexer");
+                output_eprintln!("   or: codanna retrieve history symbol_id:1771");
+                return ExitCode::GeneralError;
+            };
+            let language = params.get("lang").map(|s| s.as_str());
+
+            let format = OutputFormat::from_json_flag(json);
+            retrieve::retrieve_history(indexer, &final_symbol, language, format)
+        }
+        RetrieveQuery::Modules { args, json } => {
+            use codanna::io::args::parse_positional_args;
+
+            let (_, params) = parse_positional_args(&args);
+            let language = params.get("lang").map(|s| s.as_str());
+
+            let format = OutputFormat::from_json_flag(json);
+            retrieve::retrieve_modules(indexer, language, format)
+        }
+        RetrieveQuery::Module { args, json } => {
+            use codanna::io::args::parse_positional_args;
+
+            // Parse positional arguments for module path and key:value pairs.
+            // A leading `a::b` path would otherwise be read as key `a`.
+            let (positional_path, params) = match args.split_first() {
+                Some((first, rest)) if first.contains("::") => {
+                    (Some(first.clone()), parse_positional_args(rest).1)
+                }
+                _ => parse_positional_args(&args),
+            };
+
+            // Determine module path (priority: positional > key:value)
+            let Some(final_path) = positional_path.or_else(|| params.get("path").cloned()) else {
+                output_eprintln!("Error: module requires a module path");
+                output_eprintln!("Usage: codanna retrieve module crate::indexing");
+                output_eprintln!("   or: codanna retrieve module path:app.models");
+                return ExitCode::GeneralError;
+            };
+
+            // Extract language filter
+            let language = params.get("lang").map(|s| s.as_str());
+
+            let format = OutputFormat::from_json_flag(json);
+            retrieve::retrieve_module(indexer, &final_path, language, format)
+        }
+        RetrieveQuery::Uses { symbol } => {
+            output_eprintln!("'retrieve uses' command not yet implemented for: {symbol}");
+            ExitCode::GeneralError
+        }
+        RetrieveQuery::Defines { args, json } => {
+            use codanna::io::args::parse_positional_args;
+
+            let (positional_type, params) = parse_positional_args(&args);
+
+            // Determine type name (priority: positional > key:value)
+            let Some(final_type) = positional_type.or_else(|| params.get("type").cloned()) else {
+                output_eprintln!("Error: defines requires a type or trait name");
+                output_eprintln!("Usage: codanna retrieve defines Parser");
+                output_eprintln!("   or: codanna retrieve defines type:Parser");
+                return ExitCode::GeneralError;
+            };
+
+            // Extract language filter
+            let language = params.get("lang").map(|s| s.as_str());
+
+            let format = OutputFormat::from_json_flag(json);
+            retrieve::retrieve_defines(indexer, &final_type, language, format)
+        }
+        RetrieveQuery::UsesInSignature { args, json } => {
+            use codanna::io::args::parse_positional_args;
+
+            let (positional_type, params) = parse_positional_args(&args);
+
+            let Some(final_type) = positional_type.or_else(|| params.get("type").cloned()) else {
+                output_eprintln!("Error: uses-in-signature requires a type name");
+                output_eprintln!("Usage: codanna retrieve uses-in-signature Settings");
+                output_eprintln!("   or: codanna retrieve uses-in-signature type:Settings");
+                return ExitCode::GeneralError;
+            };
+
+            let language = params.get("lang").map(|s| s.as_str());
+
+            let format = OutputFormat::from_json_flag(json);
+            retrieve::retrieve_uses_in_signature(indexer, &final_type, language, format)
+        }
+        RetrieveQuery::Dependencies { symbol } => {
+            output_eprintln!("'retrieve dependencies' command not yet implemented for: {symbol}");
+            ExitCode::GeneralError
+        }
+    }
+}
+
+/// Run a retrieve command for each symbol name or symbol_id read from stdin
+fn run_retrieve_batch(
+    indexer: &SimpleIndexer,
+    command: codanna::retrieve::BatchCommand,
+    args: &[String],
+    json: bool,
+) -> codanna::io::ExitCode {
+    use codanna::io::args::parse_positional_args;
+    use codanna::io::{ExitCode, OutputFormat};
+    use codanna::retrieve;
+
+    let inputs = match retrieve::read_batch_inputs(std::io::stdin().lock()) {
+        Ok(inputs) => inputs,
+        Err(e) => {
+            eprintln!("Error: failed to read stdin: {e}");
+            return ExitCode::IoError;
+        }
+    };
+
+    let (_, params) = parse_positional_args(args);
+    let language = params.get("lang").map(|s| s.as_str());
+
+    let format = OutputFormat::from_json_flag(json);
+    retrieve::retrieve_batch(indexer, command, &inputs, language, format)
 }
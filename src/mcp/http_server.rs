@@ -17,7 +17,7 @@ pub async fn serve_http(config: crate::Settings, watch: bool, bind: String) -> a
     use tokio::sync::RwLock;
     use tokio_util::sync::CancellationToken;
 
-    eprintln!("Starting HTTP MCP server on {bind}");
+    crate::log_info!("Starting HTTP MCP server on {bind}");
 
     // Create notification broadcaster for file change events
     let broadcaster = Arc::new(NotificationBroadcaster::new(100).with_debug(config.mcp.debug));
@@ -36,15 +36,15 @@ pub async fn serve_http(config: crate::Settings, watch: bool, bind: String) -> a
                 *indexer_guard = loaded;
                 let symbol_count = indexer_guard.symbol_count();
                 drop(indexer_guard);
-                eprintln!("Loaded index with {symbol_count} symbols");
+                crate::log_info!("Loaded index with {symbol_count} symbols");
             }
             Err(e) => {
-                eprintln!("Failed to load existing index: {e}");
-                eprintln!("Starting with empty index");
+                crate::log_warn!("Failed to load existing index: {e}");
+                crate::log_warn!("Starting with empty index");
             }
         }
     } else {
-        eprintln!("No existing index found, starting fresh");
+        crate::log_info!("No existing index found, starting fresh");
     }
 
     // Create cancellation token for coordinated shutdown
@@ -70,15 +70,15 @@ pub async fn serve_http(config: crate::Settings, watch: bool, bind: String) -> a
         tokio::spawn(async move {
             tokio::select! {
                 _ = index_watcher.watch() => {
-                    eprintln!("Index watcher ended");
+                    crate::log_info!("Index watcher ended");
                 }
                 _ = index_watcher_ct.cancelled() => {
-                    eprintln!("Index watcher stopped by cancellation token");
+                    crate::log_info!("Index watcher stopped by cancellation token");
                 }
             }
         });
 
-        eprintln!(
+        crate::log_info!(
             "Index watcher started (checks every {watch_interval} seconds for index changes)"
         );
     }
@@ -104,21 +104,21 @@ pub async fn serve_http(config: crate::Settings, watch: bool, bind: String) -> a
                     tokio::select! {
                         result = watcher.watch() => {
                             if let Err(e) = result {
-                                eprintln!("File watcher error: {e}");
+                                crate::log_error!("File watcher error: {e}");
                             }
                         }
                         _ = watcher_ct.cancelled() => {
-                            eprintln!("File watcher stopped by cancellation token");
+                            crate::log_info!("File watcher stopped by cancellation token");
                         }
                     }
                 });
-                eprintln!(
+                crate::log_info!(
                     "File system watcher started (event-driven with {debounce_ms}ms debounce)"
                 );
             }
             Err(e) => {
-                eprintln!("Failed to start file watcher: {e}");
-                eprintln!("Continuing without file watching");
+                crate::log_warn!("Failed to start file watcher: {e}");
+                crate::log_warn!("Continuing without file watching");
             }
         }
 
@@ -136,6 +136,7 @@ pub async fn serve_http(config: crate::Settings, watch: bool, bind: String) -> a
         match ConfigFileWatcher::new(
             settings_path.clone(),
             config_watcher_indexer,
+            config.index_profile().map(str::to_string),
             config.mcp.debug,
         ) {
             Ok(config_watcher) => {
@@ -145,21 +146,21 @@ pub async fn serve_http(config: crate::Settings, watch: bool, bind: String) -> a
                     tokio::select! {
                         result = config_watcher.watch() => {
                             if let Err(e) = result {
-                                eprintln!("Config watcher error: {e}");
+                                crate::log_error!("Config watcher error: {e}");
                             }
                         }
                         _ = config_watcher_ct.cancelled() => {
-                            eprintln!("Config watcher stopped by cancellation token");
+                            crate::log_info!("Config watcher stopped by cancellation token");
                         }
                     }
                 });
-                eprintln!(
+                crate::log_info!(
                     "Config watcher started - monitoring {}",
                     settings_path.display()
                 );
             }
             Err(e) => {
-                eprintln!("Failed to start config watcher: {e}");
+                crate::log_warn!("Failed to start config watcher: {e}");
             }
         }
     }
@@ -188,7 +189,7 @@ pub async fn serve_http(config: crate::Settings, watch: bool, bind: String) -> a
     sse_server.with_service(move || {
         let mcp_debug = config_for_service.mcp.debug;
         if mcp_debug {
-            eprintln!("DEBUG: Creating new MCP server instance for SSE connection");
+            crate::log_debug!("Creating new MCP server instance for SSE connection");
         }
         let server = CodeIntelligenceServer::new_with_indexer(
             indexer_for_service.clone(),
@@ -201,25 +202,25 @@ pub async fn serve_http(config: crate::Settings, watch: bool, bind: String) -> a
         let receiver = broadcaster_for_service.subscribe();
         let listener_ct = ct_for_service.clone();
         if mcp_debug {
-            eprintln!("DEBUG: Subscribing to broadcaster for notifications");
+            crate::log_debug!("Subscribing to broadcaster for notifications");
         }
         tokio::spawn(async move {
             // Wait a bit for the MCP handshake to complete
             tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
             if mcp_debug {
-                eprintln!("DEBUG: Starting notification listener");
+                crate::log_debug!("Starting notification listener");
             }
 
             // Run listener until cancelled
             tokio::select! {
                 _ = server_clone.start_notification_listener(receiver, mcp_debug) => {
                     if mcp_debug {
-                        eprintln!("DEBUG: Notification listener ended");
+                        crate::log_debug!("Notification listener ended");
                     }
                 }
                 _ = listener_ct.cancelled() => {
                     if mcp_debug {
-                        eprintln!("DEBUG: Notification listener stopped by cancellation token");
+                        crate::log_debug!("Notification listener stopped by cancellation token");
                     }
                 }
             }
@@ -236,7 +237,7 @@ pub async fn serve_http(config: crate::Settings, watch: bool, bind: String) -> a
     // Create OAuth metadata handler with the bind address
     let bind_for_metadata = bind.clone();
     let oauth_metadata = move || async move {
-        eprintln!("OAuth metadata endpoint called");
+        crate::log_info!("OAuth metadata endpoint called");
         // Return OAuth metadata that supports authorization code flow
         axum::Json(serde_json::json!({
             "issuer": format!("http://{}", bind_for_metadata.clone()),
@@ -255,7 +256,7 @@ pub async fn serve_http(config: crate::Settings, watch: bool, bind: String) -> a
     async fn oauth_register(
         axum::Json(payload): axum::Json<serde_json::Value>,
     ) -> axum::Json<serde_json::Value> {
-        eprintln!("OAuth register endpoint called with: {payload:?}");
+        crate::log_info!("OAuth register endpoint called with: {payload:?}");
         // Return a dummy client registration response that matches the request
         // Use empty string for public clients (Claude Code expects a string, not null)
         axum::Json(serde_json::json!({
@@ -272,13 +273,13 @@ pub async fn serve_http(config: crate::Settings, watch: bool, bind: String) -> a
 
     // OAuth token endpoint - exchanges authorization code for access token
     async fn oauth_token(body: String) -> axum::Json<serde_json::Value> {
-        eprintln!("OAuth token endpoint called with body: {body}");
+        crate::log_info!("OAuth token endpoint called with body: {body}");
 
         // Parse form-encoded data (OAuth uses application/x-www-form-urlencoded)
         let params: std::collections::HashMap<String, String> =
             serde_urlencoded::from_str(&body).unwrap_or_default();
 
-        eprintln!("Token request params: {params:?}");
+        crate::log_info!("Token request params: {params:?}");
 
         // Check grant type
         let grant_type = params.get("grant_type").cloned().unwrap_or_default();
@@ -286,7 +287,7 @@ pub async fn serve_http(config: crate::Settings, watch: bool, bind: String) -> a
 
         // IMPORTANT: Reject refresh_token grant type (like the SDK example)
         if grant_type == "refresh_token" {
-            eprintln!("Rejecting refresh_token grant type");
+            crate::log_warn!("Rejecting refresh_token grant type");
             return axum::Json(serde_json::json!({
                 "error": "unsupported_grant_type",
                 "error_description": "only authorization_code is supported"
@@ -304,7 +305,7 @@ pub async fn serve_http(config: crate::Settings, watch: bool, bind: String) -> a
             }))
         } else {
             // Invalid request
-            eprintln!("Invalid token request: grant_type={grant_type}, code={code}");
+            crate::log_warn!("Invalid token request: grant_type={grant_type}, code={code}");
             axum::Json(serde_json::json!({
                 "error": "invalid_grant",
                 "error_description": "Invalid authorization code or grant type"
@@ -318,7 +319,7 @@ pub async fn serve_http(config: crate::Settings, watch: bool, bind: String) -> a
             std::collections::HashMap<String, String>,
         >,
     ) -> impl axum::response::IntoResponse {
-        eprintln!("OAuth authorize endpoint called with params: {params:?}");
+        crate::log_info!("OAuth authorize endpoint called with params: {params:?}");
 
         // Extract redirect_uri and state from query params
         let redirect_uri = params
@@ -399,7 +400,7 @@ pub async fn serve_http(config: crate::Settings, watch: bool, bind: String) -> a
         tokio::signal::ctrl_c()
             .await
             .expect("failed to listen for ctrl+c");
-        eprintln!("Received shutdown signal");
+        crate::log_info!("Received shutdown signal");
     }
 
     // Bearer token validation middleware - only for MCP endpoints
@@ -416,7 +417,7 @@ pub async fn serve_http(config: crate::Settings, watch: bool, bind: String) -> a
                 if let Ok(auth_str) = auth_header.to_str() {
                     // Accept our dummy token
                     if auth_str == "Bearer mcp-access-token-dummy" {
-                        eprintln!("MCP request authorized with Bearer token");
+                        crate::log_info!("MCP request authorized with Bearer token");
                         return Ok(next.run(req).await);
                     }
                 }
@@ -427,7 +428,7 @@ pub async fn serve_http(config: crate::Settings, watch: bool, bind: String) -> a
                 return Ok(next.run(req).await);
             }
 
-            eprintln!("MCP request rejected - invalid or missing Bearer token");
+            crate::log_warn!("MCP request rejected - invalid or missing Bearer token");
             return Err(axum::http::StatusCode::UNAUTHORIZED);
         }
 
@@ -455,11 +456,11 @@ pub async fn serve_http(config: crate::Settings, watch: bool, bind: String) -> a
 
     // Bind and serve
     let listener = tokio::net::TcpListener::bind(&bind).await?;
-    eprintln!("HTTP MCP server listening on http://{bind}");
-    eprintln!("SSE endpoint: http://{bind}/mcp/sse");
-    eprintln!("POST endpoint: http://{bind}/mcp/message");
-    eprintln!("Health check: http://{bind}/health");
-    eprintln!("Press Ctrl+C to stop the server");
+    crate::log_info!("HTTP MCP server listening on http://{bind}");
+    crate::log_info!("SSE endpoint: http://{bind}/mcp/sse");
+    crate::log_info!("POST endpoint: http://{bind}/mcp/message");
+    crate::log_info!("Health check: http://{bind}/health");
+    crate::log_info!("Press Ctrl+C to stop the server");
 
     // Create server future
     let server = axum::serve(listener, router);
@@ -470,12 +471,12 @@ pub async fn serve_http(config: crate::Settings, watch: bool, bind: String) -> a
             result?;
         }
         _ = shutdown_signal() => {
-            eprintln!("Shutting down HTTP server...");
+            crate::log_info!("Shutting down HTTP server...");
             ct.cancel();
         }
     }
 
-    eprintln!("HTTP server shut down gracefully");
+    crate::log_info!("HTTP server shut down gracefully");
     Ok(())
 }
 
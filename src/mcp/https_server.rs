@@ -21,7 +21,7 @@ pub async fn serve_https(config: crate::Settings, watch: bool, bind: String) ->
     use tokio_util::sync::CancellationToken;
 
     if config.mcp.debug {
-        eprintln!("Starting HTTPS MCP server on {bind}");
+        crate::log_info!("Starting HTTPS MCP server on {bind}");
     }
 
     // Create notification broadcaster for file change events
@@ -42,18 +42,18 @@ pub async fn serve_https(config: crate::Settings, watch: bool, bind: String) ->
                 let symbol_count = indexer_guard.symbol_count();
                 drop(indexer_guard);
                 if config.mcp.debug {
-                    eprintln!("Loaded index with {symbol_count} symbols");
+                    crate::log_info!("Loaded index with {symbol_count} symbols");
                 }
             }
             Err(e) => {
                 if config.mcp.debug {
-                    eprintln!("Failed to load existing index: {e}");
-                    eprintln!("Starting with empty index");
+                    crate::log_warn!("Failed to load existing index: {e}");
+                    crate::log_warn!("Starting with empty index");
                 }
             }
         }
     } else if config.mcp.debug {
-        eprintln!("No existing index found, starting fresh");
+        crate::log_info!("No existing index found, starting fresh");
     }
 
     // Parse bind address for SSE server early
@@ -83,23 +83,23 @@ pub async fn serve_https(config: crate::Settings, watch: bool, bind: String) ->
                     tokio::select! {
                         result = watcher.watch() => {
                             if let Err(e) = result {
-                                eprintln!("File watcher error: {e}");
+                                crate::log_error!("File watcher error: {e}");
                             }
                         }
                         _ = watcher_ct.cancelled() => {
-                            eprintln!("File watcher stopped by cancellation token");
+                            crate::log_info!("File watcher stopped by cancellation token");
                         }
                     }
                 });
                 if config.mcp.debug {
-                    eprintln!(
+                    crate::log_info!(
                         "File system watcher started (event-driven with {debounce_ms}ms debounce)"
                     );
                 }
             }
             Err(e) => {
-                eprintln!("Failed to start file watcher: {e}");
-                eprintln!("Continuing without file watching");
+                crate::log_warn!("Failed to start file watcher: {e}");
+                crate::log_warn!("Continuing without file watching");
             }
         }
 
@@ -117,6 +117,7 @@ pub async fn serve_https(config: crate::Settings, watch: bool, bind: String) ->
         match ConfigFileWatcher::new(
             settings_path.clone(),
             config_watcher_indexer,
+            config.index_profile().map(str::to_string),
             config.mcp.debug,
         ) {
             Ok(config_watcher) => {
@@ -126,21 +127,21 @@ pub async fn serve_https(config: crate::Settings, watch: bool, bind: String) ->
                     tokio::select! {
                         result = config_watcher.watch() => {
                             if let Err(e) = result {
-                                eprintln!("Config watcher error: {e}");
+                                crate::log_error!("Config watcher error: {e}");
                             }
                         }
                         _ = config_watcher_ct.cancelled() => {
-                            eprintln!("Config watcher stopped by cancellation token");
+                            crate::log_info!("Config watcher stopped by cancellation token");
                         }
                     }
                 });
-                eprintln!(
+                crate::log_info!(
                     "Config watcher started - monitoring {}",
                     settings_path.display()
                 );
             }
             Err(e) => {
-                eprintln!("Failed to start config watcher: {e}");
+                crate::log_warn!("Failed to start config watcher: {e}");
             }
         }
     }
@@ -165,16 +166,16 @@ pub async fn serve_https(config: crate::Settings, watch: bool, bind: String) ->
         tokio::spawn(async move {
             tokio::select! {
                 _ = index_watcher.watch() => {
-                    eprintln!("Index watcher ended");
+                    crate::log_info!("Index watcher ended");
                 }
                 _ = index_watcher_ct.cancelled() => {
-                    eprintln!("Index watcher stopped by cancellation token");
+                    crate::log_info!("Index watcher stopped by cancellation token");
                 }
             }
         });
 
         if config.mcp.debug {
-            eprintln!(
+            crate::log_info!(
                 "Index watcher started (checks every {watch_interval} seconds for index changes)"
             );
         }
@@ -214,7 +215,7 @@ pub async fn serve_https(config: crate::Settings, watch: bool, bind: String) ->
     // Create OAuth metadata handler with the bind address
     let bind_for_metadata = bind.clone();
     let oauth_metadata = move || async move {
-        eprintln!("OAuth metadata endpoint called");
+        crate::log_info!("OAuth metadata endpoint called");
         axum::Json(serde_json::json!({
             "issuer": format!("https://{}", bind_for_metadata.clone()),
             "authorization_endpoint": format!("https://{}/oauth/authorize", bind_for_metadata.clone()),
@@ -234,13 +235,13 @@ pub async fn serve_https(config: crate::Settings, watch: bool, bind: String) ->
         next: axum::middleware::Next,
     ) -> Result<axum::response::Response, axum::http::StatusCode> {
         let path = req.uri().path();
-        eprintln!("Request to: {path}");
+        crate::log_info!("Request to: {path}");
 
         // Debug: Print all headers
-        eprintln!("Headers received:");
+        crate::log_info!("Headers received:");
         for (name, value) in req.headers() {
             if let Ok(v) = value.to_str() {
-                eprintln!("  {name}: {v}");
+                crate::log_info!("  {name}: {v}");
             }
         }
 
@@ -279,15 +280,15 @@ pub async fn serve_https(config: crate::Settings, watch: bool, bind: String) ->
     // Parse bind address
     let addr: SocketAddr = bind.parse().context("Failed to parse bind address")?;
 
-    eprintln!("🔒 HTTPS SSE MCP server listening on https://{bind}");
-    eprintln!("📍 SSE endpoint: https://{bind}/mcp/sse");
-    eprintln!("📍 POST endpoint: https://{bind}/mcp/message");
-    eprintln!("🏥 Health check: https://{bind}/health");
-    eprintln!();
-    eprintln!("⚠️  Using self-signed certificate. Clients will show security warnings.");
-    eprintln!("📝 To trust the certificate, visit https://{bind} in your browser first");
-    eprintln!();
-    eprintln!("Press Ctrl+C to stop the server");
+    crate::log_info!("🔒 HTTPS SSE MCP server listening on https://{bind}");
+    crate::log_info!("📍 SSE endpoint: https://{bind}/mcp/sse");
+    crate::log_info!("📍 POST endpoint: https://{bind}/mcp/message");
+    crate::log_info!("🏥 Health check: https://{bind}/health");
+    crate::log_info!();
+    crate::log_warn!("⚠️  Using self-signed certificate. Clients will show security warnings.");
+    crate::log_info!("📝 To trust the certificate, visit https://{bind} in your browser first");
+    crate::log_info!();
+    crate::log_info!("Press Ctrl+C to stop the server");
 
     // Serve with TLS
     let server = axum_server::bind_rustls(addr, tls_config).serve(router.into_make_service());
@@ -298,19 +299,19 @@ pub async fn serve_https(config: crate::Settings, watch: bool, bind: String) ->
             result?;
         }
         _ = shutdown_signal() => {
-            eprintln!("Shutting down HTTPS server...");
+            crate::log_info!("Shutting down HTTPS server...");
             ct.cancel();
         }
     }
 
-    eprintln!("HTTPS server shut down gracefully");
+    crate::log_info!("HTTPS server shut down gracefully");
     Ok(())
 }
 
 /// Helper function for health check endpoint
 #[cfg(feature = "https-server")]
 async fn health_check() -> &'static str {
-    eprintln!("Health check endpoint called");
+    crate::log_info!("Health check endpoint called");
     "OK"
 }
 
@@ -319,7 +320,7 @@ async fn health_check() -> &'static str {
 async fn oauth_register(
     axum::Json(payload): axum::Json<serde_json::Value>,
 ) -> axum::Json<serde_json::Value> {
-    eprintln!("OAuth register endpoint called with: {payload:?}");
+    crate::log_info!("OAuth register endpoint called with: {payload:?}");
     // Return a dummy client registration response that matches the request
     // Use empty string for public clients (Claude Code expects a string, not null)
     axum::Json(serde_json::json!({
@@ -337,13 +338,13 @@ async fn oauth_register(
 /// OAuth token endpoint - exchanges authorization code for access token
 #[cfg(feature = "https-server")]
 async fn oauth_token(body: String) -> axum::Json<serde_json::Value> {
-    eprintln!("OAuth token endpoint called with body: {body}");
+    crate::log_info!("OAuth token endpoint called with body: {body}");
 
     // Parse form-encoded data (OAuth uses application/x-www-form-urlencoded)
     let params: std::collections::HashMap<String, String> =
         serde_urlencoded::from_str(&body).unwrap_or_default();
 
-    eprintln!("Token request params: {params:?}");
+    crate::log_info!("Token request params: {params:?}");
 
     // Check grant type
     let grant_type = params.get("grant_type").cloned().unwrap_or_default();
@@ -351,7 +352,7 @@ async fn oauth_token(body: String) -> axum::Json<serde_json::Value> {
 
     // IMPORTANT: Reject refresh_token grant type (like the SDK example)
     if grant_type == "refresh_token" {
-        eprintln!("Rejecting refresh_token grant type");
+        crate::log_warn!("Rejecting refresh_token grant type");
         return axum::Json(serde_json::json!({
             "error": "unsupported_grant_type",
             "error_description": "only authorization_code is supported"
@@ -369,7 +370,7 @@ async fn oauth_token(body: String) -> axum::Json<serde_json::Value> {
         }))
     } else {
         // Invalid request
-        eprintln!("Invalid token request: grant_type={grant_type}, code={code}");
+        crate::log_warn!("Invalid token request: grant_type={grant_type}, code={code}");
         axum::Json(serde_json::json!({
             "error": "invalid_grant",
             "error_description": "Invalid authorization code or grant type"
@@ -382,7 +383,7 @@ async fn oauth_token(body: String) -> axum::Json<serde_json::Value> {
 async fn oauth_authorize(
     axum::extract::Query(params): axum::extract::Query<std::collections::HashMap<String, String>>,
 ) -> impl axum::response::IntoResponse {
-    eprintln!("OAuth authorize endpoint called with params: {params:?}");
+    crate::log_info!("OAuth authorize endpoint called with params: {params:?}");
 
     // Extract redirect_uri and state from query params
     let redirect_uri = params
@@ -486,7 +487,7 @@ async fn shutdown_signal() {
     tokio::signal::ctrl_c()
         .await
         .expect("failed to listen for ctrl+c");
-    eprintln!("Received shutdown signal");
+    crate::log_info!("Received shutdown signal");
 }
 
 /// Get or create self-signed certificate for HTTPS
@@ -511,7 +512,7 @@ async fn get_or_create_certificate(bind: &str) -> anyhow::Result<(Vec<u8>, Vec<u
 
     // Check if server certificate already exists
     if cert_path.exists() && key_path.exists() {
-        eprintln!("Loading existing certificates from {cert_dir:?}");
+        crate::log_info!("Loading existing certificates from {cert_dir:?}");
         let cert = tokio::fs::read(&cert_path)
             .await
             .context("Failed to read certificate file")?;
@@ -521,7 +522,7 @@ async fn get_or_create_certificate(bind: &str) -> anyhow::Result<(Vec<u8>, Vec<u
         return Ok((cert, key));
     }
 
-    eprintln!("Generating new enhanced self-signed certificate...");
+    crate::log_info!("Generating new enhanced self-signed certificate...");
 
     // Build list of Subject Alternative Names
     let mut subject_alt_names = vec![
@@ -533,7 +534,7 @@ async fn get_or_create_certificate(bind: &str) -> anyhow::Result<(Vec<u8>, Vec<u
     // If binding to 0.0.0.0, include local network IP
     if bind.starts_with("0.0.0.0") {
         if let Ok(local_ip) = local_ip_address::local_ip() {
-            eprintln!("Including local network IP in certificate: {local_ip}");
+            crate::log_info!("Including local network IP in certificate: {local_ip}");
             subject_alt_names.push(local_ip.to_string());
         }
     }
@@ -562,34 +563,34 @@ async fn get_or_create_certificate(bind: &str) -> anyhow::Result<(Vec<u8>, Vec<u
     let fingerprint = hasher.finish();
     let fingerprint_hex = format!("{fingerprint:016X}");
 
-    eprintln!();
-    eprintln!("🔐 Certificate Details:");
-    eprintln!("   - Type: Self-Signed TLS Certificate");
-    eprintln!("   - Location: {}", cert_path.display());
-    eprintln!("   - Fingerprint: {fingerprint_hex}");
-    eprintln!("   - Valid for: {}", subject_alt_names.join(", "));
-    eprintln!();
-    eprintln!("🔧 To trust this certificate on macOS:");
-    eprintln!();
-    eprintln!("   Option 1: Command line (requires sudo):");
-    eprintln!(
+    crate::log_info!();
+    crate::log_info!("🔐 Certificate Details:");
+    crate::log_info!("   - Type: Self-Signed TLS Certificate");
+    crate::log_info!("   - Location: {}", cert_path.display());
+    crate::log_info!("   - Fingerprint: {fingerprint_hex}");
+    crate::log_info!("   - Valid for: {}", subject_alt_names.join(", "));
+    crate::log_info!();
+    crate::log_info!("🔧 To trust this certificate on macOS:");
+    crate::log_info!();
+    crate::log_info!("   Option 1: Command line (requires sudo):");
+    crate::log_info!(
         "   sudo security add-trusted-cert -d -r trustRoot -k /Library/Keychains/System.keychain {}",
         cert_path.display()
     );
-    eprintln!();
-    eprintln!("   Option 2: GUI (recommended):");
-    eprintln!("   1. Open Finder and navigate to: {}", cert_dir.display());
-    eprintln!("   2. Double-click 'server.pem'");
-    eprintln!("   3. Add to 'System' keychain");
-    eprintln!("   4. Set to 'Always Trust' for SSL");
-    eprintln!();
-    eprintln!("   Option 3: Open in browser first:");
-    eprintln!("   1. Visit https://127.0.0.1:8443/health in Safari/Chrome");
-    eprintln!("   2. Click 'Advanced' and proceed anyway");
-    eprintln!("   3. This may help some clients accept the certificate");
-    eprintln!();
-    eprintln!("⚠️  After trusting the certificate, restart Claude Code to reconnect");
-    eprintln!();
+    crate::log_info!();
+    crate::log_info!("   Option 2: GUI (recommended):");
+    crate::log_info!("   1. Open Finder and navigate to: {}", cert_dir.display());
+    crate::log_info!("   2. Double-click 'server.pem'");
+    crate::log_info!("   3. Add to 'System' keychain");
+    crate::log_info!("   4. Set to 'Always Trust' for SSL");
+    crate::log_info!();
+    crate::log_info!("   Option 3: Open in browser first:");
+    crate::log_info!("   1. Visit https://127.0.0.1:8443/health in Safari/Chrome");
+    crate::log_info!("   2. Click 'Advanced' and proceed anyway");
+    crate::log_info!("   3. This may help some clients accept the certificate");
+    crate::log_info!();
+    crate::log_info!("⚠️  After trusting the certificate, restart Claude Code to reconnect");
+    crate::log_info!();
 
     Ok((cert_pem, key_pem))
 }
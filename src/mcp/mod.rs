@@ -36,6 +36,10 @@ use serde_json;
 use std::sync::Arc;
 use tokio::sync::{Mutex, RwLock};
 
+use crate::config::McpConfig;
+use crate::io::format::format_bytes;
+use crate::paths::display_path;
+use crate::relationship::{Provenance, RelationshipMetadata};
 use crate::{Settings, SimpleIndexer, Symbol};
 
 /// Generate guidance for MCP tool responses
@@ -70,6 +74,32 @@ pub fn format_relative_time(timestamp: u64) -> String {
     }
 }
 
+/// Drop call edges that are not resolved when `resolved_only` is set
+///
+/// Returns a note for the response header naming how many were hidden.
+fn retain_resolved(
+    calls: &mut Vec<(Symbol, Option<RelationshipMetadata>, Provenance)>,
+    resolved_only: bool,
+) -> String {
+    if !resolved_only {
+        return String::new();
+    }
+    let before = calls.len();
+    calls.retain(|(_, _, provenance)| *provenance == Provenance::Resolved);
+    match before - calls.len() {
+        0 => String::new(),
+        hidden => format!(" ({hidden} ambiguous or heuristic hidden)"),
+    }
+}
+
+/// Suffix flagging a call edge that resolution did not pin down
+fn provenance_note(provenance: Provenance) -> String {
+    match provenance {
+        Provenance::Resolved => String::new(),
+        other => format!(" ({other})"),
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
 pub struct FindSymbolRequest {
     /// Name of the symbol to find
@@ -77,6 +107,10 @@ pub struct FindSymbolRequest {
     /// Filter by programming language (e.g., "rust", "python", "typescript", "php")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub lang: Option<String>,
+    /// Maximum number of symbols shown with full context (default: 20); the
+    /// total match count is always reported
+    #[serde(default = "default_find_symbol_limit")]
+    pub limit: u32,
 }
 
 #[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
@@ -87,6 +121,9 @@ pub struct GetCallsRequest {
     /// Symbol ID for direct lookup (recommended to avoid ambiguity)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub symbol_id: Option<u32>,
+    /// Only list calls resolved with certainty, dropping ambiguous and heuristic ones
+    #[serde(default)]
+    pub resolved_only: bool,
 }
 
 #[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
@@ -97,6 +134,9 @@ pub struct FindCallersRequest {
     /// Symbol ID for direct lookup (recommended to avoid ambiguity)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub symbol_id: Option<u32>,
+    /// Only list calls resolved with certainty, dropping ambiguous and heuristic ones
+    #[serde(default)]
+    pub resolved_only: bool,
 }
 
 #[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
@@ -110,16 +150,117 @@ pub struct AnalyzeImpactRequest {
     /// Maximum depth to search (default: 3)
     #[serde(default = "default_depth")]
     pub max_depth: u32,
+    /// Output format: "text" (default) or "markdown" for a report with file links
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// Only follow relationships resolved with certainty, skipping ambiguous and heuristic ones
+    #[serde(default)]
+    pub resolved_only: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct GetSymbolSourceRequest {
+    /// Name of the symbol to read (use symbol_id for unambiguous lookup)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol_name: Option<String>,
+    /// Symbol ID for direct lookup (recommended to avoid ambiguity)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol_id: Option<u32>,
+    /// Only consider symbols whose file path contains this string
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// Only consider symbols whose module path contains this string
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub module: Option<String>,
+    /// Filter by programming language (e.g., "rust", "python", "typescript", "php")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+    /// Lines of leading context to include, e.g. attributes and decorators (default: 3)
+    #[serde(default = "default_source_context_lines")]
+    pub context_lines: u32,
+}
+
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct ResolveImportRequest {
+    /// File containing the import, as shown in other tool results
+    pub file_path: String,
+    /// Imported name as used in the file (the alias, or the last path segment)
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct SymbolAtRequest {
+    /// File to look in, as shown in other tool results
+    pub file_path: String,
+    /// Line number (1-based, as shown in other tool results)
+    pub line: u32,
+    /// Column (1-based byte offset within the line)
+    pub column: u32,
+    /// Treat line and column as 0-based, as editors using LSP positions send them
+    #[serde(default)]
+    pub zero_based: bool,
+}
+
+impl SymbolAtRequest {
+    /// The position as 0-based line and column, as stored in symbol ranges
+    pub fn position(&self) -> Result<(u32, u16), String> {
+        let (line, column) = if self.zero_based {
+            (self.line, self.column)
+        } else if self.line == 0 || self.column == 0 {
+            return Err(
+                "line and column are 1-based; pass zero_based:true for 0-based positions"
+                    .to_string(),
+            );
+        } else {
+            (self.line - 1, self.column - 1)
+        };
+        Ok((line, u16::try_from(column).unwrap_or(u16::MAX)))
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct FindImplementorsOfMethodRequest {
+    /// Trait or interface method as `Trait::method` (or `Trait.method`)
+    pub method: String,
+    /// Filter by programming language (e.g., "rust", "python", "typescript", "php")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct GetTypeHierarchyRequest {
+    /// Name of the type, class, trait or interface
+    pub name: String,
+    /// "ancestors" (what it extends or implements), "descendants" (what extends or
+    /// implements it) or "both" (default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub direction: Option<String>,
+    /// Maximum levels to walk in each direction (default: 3)
+    #[serde(default = "default_depth")]
+    pub max_depth: u32,
+    /// Filter by programming language (e.g., "rust", "python", "typescript", "php")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct FindOccurrencesRequest {
+    /// Exact identifier to look for (case-sensitive)
+    pub name: String,
+    /// Maximum number of occurrences (default: 100)
+    #[serde(default = "default_occurrence_limit")]
+    pub limit: u32,
 }
 
 #[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
 pub struct SearchSymbolsRequest {
-    /// Search query (supports fuzzy matching)
+    /// Search query (supports fuzzy matching). Prefix terms with name:, doc:, sig: or
+    /// attribute: to search only that field, e.g. name:parse doc:"error handling"
     pub query: String,
     /// Maximum number of results (default: 10)
     #[serde(default = "default_limit")]
     pub limit: u32,
-    /// Filter by symbol kind (e.g., "Function", "Struct", "Trait")
+    /// Filter by symbol kind (e.g., "Function", "Struct", "Trait"; aliases such as "fn", "cls", "iface" and "ty" also work)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub kind: Option<String>,
     /// Filter by module path
@@ -128,6 +269,47 @@ pub struct SearchSymbolsRequest {
     /// Filter by programming language (e.g., "rust", "python", "typescript", "php")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub lang: Option<String>,
+    /// Filter by file: keep symbols from indexed files whose path ends with
+    /// this one, compared by whole components (e.g., "src/parser.rs")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// Treat the query as a regex matched against symbol names (e.g., "^handle_.*_request$")
+    #[serde(default)]
+    pub regex: bool,
+    /// Match names containing the query, ignoring case, without tokenization
+    #[serde(default)]
+    pub substring: bool,
+    /// Match whole names equal to the query, ignoring case, with no fuzzy matching
+    #[serde(default)]
+    pub exact: bool,
+    /// With exact, match case as well
+    #[serde(default)]
+    pub case_sensitive: bool,
+    /// When nothing matches, fall back to semantic search (if enabled); those
+    /// results are tagged with source "semantic" and scored by similarity (0-1)
+    #[serde(default)]
+    pub fallback_semantic: bool,
+    /// Drop results scoring below this value. Scores are unnormalized relevance:
+    /// strong name matches usually score several points above fuzzy-only matches (~1.0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_score: Option<f32>,
+    /// Print only these fields, one tab-separated row per result. Valid names:
+    /// name, kind, file, line, column, module, doc, signature, score
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<String>,
+    /// Filter by visibility: public, crate, module or private ("protected"
+    /// matches TypeScript protected members, which are recorded as module)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<String>,
+    /// Filter by attribute, decorator or annotation name, matched exactly or
+    /// by prefix and ignoring case (e.g., "deprecated", "tokio::main", "pytest")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attribute: Option<String>,
+    /// Return result counts per "file", "module" or "kind", largest first,
+    /// instead of the results. Counts cover only the results within limit,
+    /// not every match in the index
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_by: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
@@ -143,6 +325,9 @@ pub struct SemanticSearchRequest {
     /// Filter by programming language (e.g., "rust", "python", "typescript", "php")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub lang: Option<String>,
+    /// Show which query terms each result shares with its name, doc and signature
+    #[serde(default)]
+    pub explain: bool,
 }
 
 #[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
@@ -167,6 +352,10 @@ fn default_depth() -> u32 {
     3
 }
 
+fn default_source_context_lines() -> u32 {
+    3
+}
+
 fn default_limit() -> u32 {
     10
 }
@@ -175,40 +364,88 @@ fn default_context_limit() -> u32 {
     5
 }
 
+fn default_occurrence_limit() -> u32 {
+    100
+}
+
+fn default_find_symbol_limit() -> u32 {
+    20
+}
+
+/// Workflow guidance sent at `initialize` unless `mcp.instructions` replaces it
+const SERVER_INSTRUCTIONS: &str = "This server provides code intelligence tools for analyzing this codebase. \
+    WORKFLOW: Start with 'semantic_search_with_context' or 'semantic_search_docs' to anchor on the right files and APIs - they provide the highest-quality context. \
+    Then use 'find_symbol' and 'search_symbols' to lock onto exact files and kinds. \
+    Treat 'get_calls', 'find_callers', and 'analyze_impact' as hints; confirm with code reading or tighter queries (unique names, kind filters). \
+    Use 'get_index_info' to understand what's indexed.";
+
 #[derive(Clone)]
 pub struct CodeIntelligenceServer {
     pub indexer: Arc<RwLock<SimpleIndexer>>,
     tool_router: ToolRouter<Self>,
     peer: Arc<Mutex<Option<Peer<RoleServer>>>>,
+    /// `mcp.instructions`, when set
+    instructions: Option<String>,
 }
 
 #[tool_router]
 impl CodeIntelligenceServer {
     pub fn new(indexer: SimpleIndexer) -> Self {
-        Self {
-            indexer: Arc::new(RwLock::new(indexer)),
-            tool_router: Self::tool_router(),
-            peer: Arc::new(Mutex::new(None)),
-        }
+        let config = indexer.settings().mcp.clone();
+        Self::with_config(Arc::new(RwLock::new(indexer)), &config)
     }
 
     /// Create server from an already-loaded indexer (most efficient)
     pub fn from_indexer(indexer: Arc<RwLock<SimpleIndexer>>) -> Self {
-        Self {
-            indexer,
-            tool_router: Self::tool_router(),
-            peer: Arc::new(Mutex::new(None)),
-        }
+        let config = indexer
+            .try_read()
+            .map(|indexer| indexer.settings().mcp.clone())
+            .unwrap_or_default();
+        Self::with_config(indexer, &config)
     }
 
     /// Create server with existing indexer and settings (for HTTP server)
-    pub fn new_with_indexer(indexer: Arc<RwLock<SimpleIndexer>>, _settings: Arc<Settings>) -> Self {
-        // For now, settings is unused but might be needed for future enhancements
+    pub fn new_with_indexer(indexer: Arc<RwLock<SimpleIndexer>>, settings: Arc<Settings>) -> Self {
+        Self::with_config(indexer, &settings.mcp)
+    }
+
+    fn with_config(indexer: Arc<RwLock<SimpleIndexer>>, config: &McpConfig) -> Self {
         Self {
             indexer,
-            tool_router: Self::tool_router(),
+            tool_router: Self::configured_tool_router(config),
             peer: Arc::new(Mutex::new(None)),
+            instructions: config.instructions.clone(),
+        }
+    }
+
+    /// The tool router with descriptions from `mcp.tool_guidance` in place of
+    /// the built-in ones
+    ///
+    /// Names that match no tool are reported once per process.
+    fn configured_tool_router(config: &McpConfig) -> ToolRouter<Self> {
+        static REPORTED_UNKNOWN: std::sync::Once = std::sync::Once::new();
+
+        let mut router = Self::tool_router();
+        for route in router.map.values_mut() {
+            if let Some(description) = config.tool_guidance.get(route.attr.name.as_ref()) {
+                route.attr.description = Some(description.clone().into());
+            }
+        }
+
+        let unknown = unknown_tool_guidance(config, &router);
+        if !unknown.is_empty() {
+            REPORTED_UNKNOWN.call_once(|| {
+                for name in unknown {
+                    eprintln!("Warning: Unknown tool '{name}' in [mcp.tool_guidance]; ignored");
+                }
+            });
         }
+        router
+    }
+
+    /// Tool definitions as advertised in the MCP `tools/list` response
+    pub fn tool_definitions(config: &McpConfig) -> Vec<Tool> {
+        Self::configured_tool_router(config).list_all()
     }
 
     /// Get a reference to the indexer Arc for external management (e.g., hot-reload)
@@ -241,10 +478,25 @@ impl CodeIntelligenceServer {
         }
     }
 
+    /// Send one summary after a batch of re-indexed files, so clients know
+    /// when fresh results are available
+    pub async fn notify_reindex_batch(&self, batch: &notifications::ReindexBatch) {
+        let peer_guard = self.peer.lock().await;
+        if let Some(peer) = peer_guard.as_ref() {
+            let _ = peer
+                .notify_logging_message(LoggingMessageNotificationParam {
+                    level: LoggingLevel::Info,
+                    logger: Some("codanna".to_string()),
+                    data: batch.log_data(),
+                })
+                .await;
+        }
+    }
+
     #[tool(description = "Find a symbol by name in the indexed codebase")]
     pub async fn find_symbol(
         &self,
-        Parameters(FindSymbolRequest { name, lang }): Parameters<FindSymbolRequest>,
+        Parameters(FindSymbolRequest { name, lang, limit }): Parameters<FindSymbolRequest>,
     ) -> Result<CallToolResult, McpError> {
         use crate::symbol::context::ContextIncludes;
 
@@ -262,9 +514,19 @@ impl CodeIntelligenceServer {
             return Ok(CallToolResult::success(vec![Content::text(output)]));
         }
 
-        let mut result = format!("Found {} symbol(s) named '{}':\n\n", symbols.len(), name);
+        // Context is only built for the symbols shown; common names like
+        // `new` can match hundreds
+        let shown = symbols.len().min(limit as usize);
+        let mut result = if shown < symbols.len() {
+            format!(
+                "Found {} symbol(s) named '{name}', showing the first {shown}:\n\n",
+                symbols.len()
+            )
+        } else {
+            format!("Found {} symbol(s) named '{name}':\n\n", symbols.len())
+        };
 
-        for (idx, symbol) in symbols.iter().enumerate() {
+        for (idx, symbol) in symbols.iter().take(shown).enumerate() {
             if idx > 0 {
                 result.push_str("\n---\n\n");
             }
@@ -285,6 +547,19 @@ impl CodeIntelligenceServer {
                     result.push_str(&format!("Module: {module}\n"));
                 }
 
+                if let Some(parent) = &ctx.relationships.parent {
+                    result.push_str(&format!(
+                        "Parent: {:?} {} [symbol_id:{}]\n",
+                        parent.kind,
+                        parent.name,
+                        parent.id.value()
+                    ));
+                }
+
+                if !symbol.attributes.is_empty() {
+                    result.push_str(&format!("Attributes: {}\n", symbol.attributes.join(" ")));
+                }
+
                 // Add signature if available
                 if let Some(sig) = symbol.as_signature() {
                     result.push_str(&format!("Signature: {sig}\n"));
@@ -339,7 +614,7 @@ impl CodeIntelligenceServer {
                 result.push_str(&format!(
                     "{:?} at {}:{}\n",
                     symbol.kind,
-                    symbol.file_path,
+                    display_path(&symbol.file_path),
                     symbol.range.start_line + 1
                 ));
 
@@ -359,6 +634,13 @@ impl CodeIntelligenceServer {
             }
         }
 
+        if shown < symbols.len() {
+            result.push_str(&format!(
+                "\n... and {} more; raise limit or pass lang to narrow the search\n",
+                symbols.len() - shown
+            ));
+        }
+
         // Add system guidance
         if let Some(guidance) =
             generate_mcp_guidance(indexer.settings(), "find_symbol", symbols.len())
@@ -372,16 +654,20 @@ impl CodeIntelligenceServer {
     }
 
     #[tool(
-        description = "Get functions that a given function CALLS (invokes with parentheses).\n\nShows: function_name() → what it calls\nDoes NOT show: Type usage, component rendering, or who calls this function.\n\nUse analyze_impact for: Type dependencies, component usage (JSX), or reverse lookups."
+        description = "Get functions that a given function CALLS (invokes with parentheses).\n\nShows: function_name() → what it calls\nDoes NOT show: Type usage, component rendering, or who calls this function.\n\nCalls resolution could not pin down are marked (ambiguous) or (heuristic); set resolved_only: true to hide them.\n\nUse analyze_impact for: Type dependencies, component usage (JSX), or reverse lookups."
     )]
     pub async fn get_calls(
         &self,
         Parameters(GetCallsRequest {
             function_name,
             symbol_id,
+            resolved_only,
         }): Parameters<GetCallsRequest>,
     ) -> Result<CallToolResult, McpError> {
         let indexer = self.indexer.read().await;
+        if let Err(e) = indexer.ensure_relationships_indexed() {
+            return Ok(CallToolResult::error(vec![Content::text(e.to_string())]));
+        }
 
         // Get the symbol either by ID or by name
         let (symbol, identifier) = if let Some(id) = symbol_id {
@@ -417,7 +703,7 @@ impl CodeIntelligenceServer {
                         i + 1,
                         sym.id.value(),
                         sym.kind,
-                        sym.file_path,
+                        display_path(&sym.file_path),
                         sym.range.start_line + 1
                     ));
                 }
@@ -437,10 +723,11 @@ impl CodeIntelligenceServer {
         };
 
         // Get calls for this specific symbol
-        let all_called_with_metadata = indexer.get_called_functions_with_metadata(symbol.id);
+        let mut all_called_with_metadata = indexer.get_called_functions_with_metadata(symbol.id);
+        let hidden_note = retain_resolved(&mut all_called_with_metadata, resolved_only);
 
         if all_called_with_metadata.is_empty() {
-            let mut output = format!("{identifier} doesn't call any functions");
+            let mut output = format!("{identifier} doesn't call any functions{hidden_note}");
             // Add guidance for no results
             if let Some(guidance) = generate_mcp_guidance(indexer.settings(), "get_calls", 0) {
                 output.push_str("\n\n---\n💡 ");
@@ -451,40 +738,15 @@ impl CodeIntelligenceServer {
         }
 
         let result_count = all_called_with_metadata.len();
-        let mut result = format!("{identifier} calls {result_count} function(s):\n");
-        for (callee, metadata) in all_called_with_metadata {
+        let mut result = format!("{identifier} calls {result_count} function(s){hidden_note}:\n");
+        for (callee, metadata, provenance) in all_called_with_metadata {
             // Parse metadata to extract receiver info and call site location
             let (call_display, call_line) = if let Some(ref meta) = metadata {
-                let display = if let Some(context) = &meta.context {
-                    if context.contains("receiver:") && context.contains("static:") {
-                        // Parse "receiver:{receiver},static:{is_static}"
-                        let parts: Vec<&str> = context.split(',').collect();
-                        let mut receiver = "";
-                        let mut is_static = false;
-
-                        for part in parts {
-                            if let Some(r) = part.strip_prefix("receiver:") {
-                                receiver = r;
-                            } else if let Some(s) = part.strip_prefix("static:") {
-                                is_static = s == "true";
-                            }
-                        }
-
-                        if !receiver.is_empty() {
-                            if is_static {
-                                format!("{}::{}", receiver, callee.name)
-                            } else {
-                                format!("{}.{}", receiver, callee.name)
-                            }
-                        } else {
-                            callee.name.to_string()
-                        }
-                    } else {
-                        callee.name.to_string()
-                    }
-                } else {
-                    callee.name.to_string()
-                };
+                let display = meta
+                    .call
+                    .as_ref()
+                    .and_then(|call| call.qualified_name(&callee.name))
+                    .unwrap_or_else(|| callee.name.to_string());
 
                 // Use call site line if available, otherwise definition line
                 let line = meta
@@ -497,8 +759,12 @@ impl CodeIntelligenceServer {
             };
 
             result.push_str(&format!(
-                "  -> {:?} {} at {}:{}\n",
-                callee.kind, call_display, callee.file_path, call_line
+                "  -> {:?} {} at {}:{}{}\n",
+                callee.kind,
+                call_display,
+                display_path(&callee.file_path),
+                call_line,
+                provenance_note(provenance)
             ));
             if let Some(ref sig) = callee.signature {
                 result.push_str(&format!("     Signature: {sig}\n"));
@@ -517,16 +783,20 @@ impl CodeIntelligenceServer {
     }
 
     #[tool(
-        description = "Find functions that CALL a given function (invoke it with parentheses).\n\nShows: what calls → function_name()\nDoes NOT show: Type references, component rendering, or what this function calls.\n\nUse analyze_impact for: Complete dependency graph including type usage and composition."
+        description = "Find functions that CALL a given function (invoke it with parentheses).\n\nShows: what calls → function_name()\nDoes NOT show: Type references, component rendering, or what this function calls.\n\nCalls resolution could not pin down are marked (ambiguous) or (heuristic); set resolved_only: true to hide them.\n\nUse analyze_impact for: Complete dependency graph including type usage and composition."
     )]
     pub async fn find_callers(
         &self,
         Parameters(FindCallersRequest {
             function_name,
             symbol_id,
+            resolved_only,
         }): Parameters<FindCallersRequest>,
     ) -> Result<CallToolResult, McpError> {
         let indexer = self.indexer.read().await;
+        if let Err(e) = indexer.ensure_relationships_indexed() {
+            return Ok(CallToolResult::error(vec![Content::text(e.to_string())]));
+        }
 
         // Get the symbol either by ID or by name
         let (symbol, identifier) = if let Some(id) = symbol_id {
@@ -561,7 +831,7 @@ impl CodeIntelligenceServer {
                         i + 1,
                         sym.id.value(),
                         sym.kind,
-                        sym.file_path,
+                        display_path(&sym.file_path),
                         sym.range.start_line + 1
                     ));
                 }
@@ -581,10 +851,11 @@ impl CodeIntelligenceServer {
         };
 
         // Get callers for THIS SPECIFIC symbol only (no aggregation)
-        let all_callers_with_metadata = indexer.get_calling_functions_with_metadata(symbol.id);
+        let mut all_callers_with_metadata = indexer.get_calling_functions_with_metadata(symbol.id);
+        let hidden_note = retain_resolved(&mut all_callers_with_metadata, resolved_only);
 
         if all_callers_with_metadata.is_empty() {
-            let mut output = format!("No functions call {identifier}");
+            let mut output = format!("No functions call {identifier}{hidden_note}");
             // Add guidance for no results
             if let Some(guidance) = generate_mcp_guidance(indexer.settings(), "find_callers", 0) {
                 output.push_str("\n\n---\n💡 ");
@@ -596,42 +867,17 @@ impl CodeIntelligenceServer {
 
         // Build structured text response with rich metadata
         let result_count = all_callers_with_metadata.len();
-        let mut result = format!("{result_count} function(s) call {identifier}:\n");
+        let mut result = format!("{result_count} function(s) call {identifier}{hidden_note}:\n");
 
-        for (caller, metadata) in all_callers_with_metadata {
+        for (caller, metadata, provenance) in all_callers_with_metadata {
             // Parse metadata to extract receiver info and call site location
             let (call_info, call_line) = if let Some(ref meta) = metadata {
-                let info = if let Some(context) = &meta.context {
-                    if context.contains("receiver:") && context.contains("static:") {
-                        // Parse "receiver:{receiver},static:{is_static}"
-                        let parts: Vec<&str> = context.split(',').collect();
-                        let mut receiver = "";
-                        let mut is_static = false;
-
-                        for part in parts {
-                            if let Some(r) = part.strip_prefix("receiver:") {
-                                receiver = r;
-                            } else if let Some(s) = part.strip_prefix("static:") {
-                                is_static = s == "true";
-                            }
-                        }
-
-                        if !receiver.is_empty() {
-                            let qualified_name = if is_static {
-                                format!("{receiver}::{}", symbol.name)
-                            } else {
-                                format!("{receiver}.{}", symbol.name)
-                            };
-                            format!(" (calls {qualified_name})")
-                        } else {
-                            String::new()
-                        }
-                    } else {
-                        String::new()
-                    }
-                } else {
-                    String::new()
-                };
+                let info = meta
+                    .call
+                    .as_ref()
+                    .and_then(|call| call.qualified_name(&symbol.name))
+                    .map(|qualified_name| format!(" (calls {qualified_name})"))
+                    .unwrap_or_default();
 
                 // Use call site line if available, otherwise definition line
                 let line = meta
@@ -644,8 +890,13 @@ impl CodeIntelligenceServer {
             };
 
             result.push_str(&format!(
-                "  <- {:?} {} at {}:{}{}\n",
-                caller.kind, caller.name, caller.file_path, call_line, call_info
+                "  <- {:?} {} at {}:{}{}{}\n",
+                caller.kind,
+                caller.name,
+                display_path(&caller.file_path),
+                call_line,
+                call_info,
+                provenance_note(provenance)
             ));
 
             if let Some(ref sig) = caller.signature {
@@ -666,7 +917,7 @@ impl CodeIntelligenceServer {
     }
 
     #[tool(
-        description = "Analyze complete impact of changing a symbol. Shows ALL relationships: function calls, type usage, composition.\n\nShows:\n- What CALLS this function\n- What USES this as a type (fields, parameters, returns)\n- What RENDERS/COMPOSES this (JSX: <Component>, Rust: struct fields, etc.)\n- Full dependency graph across files\n\nUse this when: You need to see everything that depends on a symbol."
+        description = "Analyze complete impact of changing a symbol. Shows ALL relationships: function calls, type usage, composition.\n\nShows:\n- What CALLS this function\n- What USES this as a type (fields, parameters, returns)\n- What RENDERS/COMPOSES this (JSX: <Component>, Rust: struct fields, etc.)\n- Full dependency graph across files\n\nUse this when: You need to see everything that depends on a symbol.\nSet format: \"markdown\" for a report with file links and counts by kind, ready to paste into a PR.\nSet resolved_only: true to follow only relationships resolved with certainty."
     )]
     pub async fn analyze_impact(
         &self,
@@ -674,11 +925,26 @@ impl CodeIntelligenceServer {
             symbol_name,
             symbol_id,
             max_depth,
+            format,
+            resolved_only,
         }): Parameters<AnalyzeImpactRequest>,
     ) -> Result<CallToolResult, McpError> {
         use crate::symbol::context::ContextIncludes;
 
+        let markdown = match format.as_deref() {
+            None | Some("text") => false,
+            Some("markdown") => true,
+            Some(other) => {
+                return Ok(CallToolResult::success(vec![Content::text(format!(
+                    "Error: unsupported format '{other}' (expected 'text' or 'markdown')"
+                ))]));
+            }
+        };
+
         let indexer = self.indexer.read().await;
+        if let Err(e) = indexer.ensure_relationships_indexed() {
+            return Ok(CallToolResult::error(vec![Content::text(e.to_string())]));
+        }
 
         // Get the symbol either by ID or by name
         let (symbol, identifier) = if let Some(id) = symbol_id {
@@ -713,7 +979,7 @@ impl CodeIntelligenceServer {
                         i + 1,
                         sym.id.value(),
                         sym.kind,
-                        sym.file_path,
+                        display_path(&sym.file_path),
                         sym.range.start_line + 1
                     ));
                 }
@@ -733,7 +999,30 @@ impl CodeIntelligenceServer {
         };
 
         // Analyze impact for THIS SPECIFIC symbol only (no aggregation)
-        let impacted = indexer.get_impact_radius(symbol.id, Some(max_depth as usize));
+        let impact = indexer.get_impact_radius(symbol.id, Some(max_depth as usize), resolved_only);
+        let truncated_note = if impact.truncated {
+            format!(
+                " (truncated at {} symbols; raise mcp.max_impact_nodes to see more)",
+                impact.symbols.len()
+            )
+        } else {
+            String::new()
+        };
+        let impacted = impact.symbols;
+
+        if markdown {
+            let impacted_symbols: Vec<Symbol> = impacted
+                .into_iter()
+                .filter_map(|id| indexer.get_symbol(id))
+                .collect();
+            let report = crate::io::markdown::impact_report(
+                &symbol,
+                &impacted_symbols,
+                max_depth,
+                indexer.settings().display.repo_url.as_deref(),
+            );
+            return Ok(CallToolResult::success(vec![Content::text(report)]));
+        }
 
         if impacted.is_empty() {
             let mut output = format!("No symbols would be impacted by changing {identifier}");
@@ -764,8 +1053,13 @@ impl CodeIntelligenceServer {
         }
 
         let impact_count = impacted.len();
+        let scope = if resolved_only {
+            ", resolved relationships only"
+        } else {
+            ""
+        };
         result.push_str(&format!(
-            "Total impact: {impact_count} symbol(s) would be affected (max depth: {max_depth})\n"
+            "Total impact: {impact_count} symbol(s) would be affected (max depth: {max_depth}{scope}){truncated_note}\n"
         ));
 
         // Group by symbol kind
@@ -785,7 +1079,7 @@ impl CodeIntelligenceServer {
                 result.push_str(&format!(
                     "  - {} at {}:{}\n",
                     sym.name,
-                    sym.file_path,
+                    display_path(&sym.file_path),
                     sym.range.start_line + 1
                 ));
             }
@@ -803,6 +1097,396 @@ impl CodeIntelligenceServer {
         Ok(CallToolResult::success(vec![Content::text(result)]))
     }
 
+    #[tool(
+        description = "Get the source code of a symbol, read from disk.\n\nReturns the symbol's implementation plus a few leading lines (attributes, decorators, doc comments). Disambiguate with symbol_id, or filter by file, module and lang.\n\nWarns when the file changed since indexing, since line ranges may be stale."
+    )]
+    pub async fn get_symbol_source(
+        &self,
+        Parameters(GetSymbolSourceRequest {
+            symbol_name,
+            symbol_id,
+            file,
+            module,
+            lang,
+            context_lines,
+        }): Parameters<GetSymbolSourceRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let indexer = self.indexer.read().await;
+
+        let symbol = if let Some(id) = symbol_id {
+            match indexer.get_symbol(crate::SymbolId(id)) {
+                Some(sym) => sym,
+                None => {
+                    return Ok(CallToolResult::success(vec![Content::text(format!(
+                        "Symbol not found: symbol_id:{id}"
+                    ))]));
+                }
+            }
+        } else if let Some(name) = symbol_name {
+            let symbols: Vec<Symbol> = indexer
+                .find_symbols_by_name(&name, lang.as_deref())
+                .into_iter()
+                .filter(|s| {
+                    file.as_deref()
+                        .is_none_or(|f| crate::paths::path_contains(&*s.file_path, f))
+                })
+                .filter(|s| {
+                    module
+                        .as_deref()
+                        .is_none_or(|m| s.as_module_path().is_some_and(|p| p.contains(m)))
+                })
+                .collect();
+
+            if symbols.is_empty() {
+                return Ok(CallToolResult::success(vec![Content::text(format!(
+                    "Symbol not found: {name}"
+                ))]));
+            }
+
+            if symbols.len() > 1 {
+                let mut msg = format!(
+                    "Ambiguous: found {} symbol(s) named '{}':\n",
+                    symbols.len(),
+                    name
+                );
+                for (i, sym) in symbols.iter().take(10).enumerate() {
+                    msg.push_str(&format!(
+                        "  {}. symbol_id:{} - {:?} at {}:{}\n",
+                        i + 1,
+                        sym.id.value(),
+                        sym.kind,
+                        display_path(&sym.file_path),
+                        sym.range.start_line + 1
+                    ));
+                }
+                if symbols.len() > 10 {
+                    msg.push_str(&format!("  ... and {} more\n", symbols.len() - 10));
+                }
+                msg.push_str(
+                    "\nUse: get_symbol_source symbol_id:<id>, or narrow with file/module filters",
+                );
+                return Ok(CallToolResult::success(vec![Content::text(msg)]));
+            }
+
+            symbols.into_iter().next().unwrap()
+        } else {
+            return Ok(CallToolResult::success(vec![Content::text(
+                "Error: Either symbol_name or symbol_id must be provided".to_string(),
+            )]));
+        };
+
+        let source = match indexer.get_symbol_source(&symbol, context_lines) {
+            Ok(source) => source,
+            Err(e) => {
+                return Ok(CallToolResult::success(vec![Content::text(format!(
+                    "Error: failed to read source for {}: {e}",
+                    symbol.name
+                ))]));
+            }
+        };
+
+        let mut result = format!(
+            "{:?} {} at {}:{}-{} [symbol_id:{}]\n",
+            symbol.kind,
+            symbol.name,
+            display_path(&symbol.file_path),
+            source.start_line + 1,
+            source.end_line + 1,
+            symbol.id.value()
+        );
+        if source.stale {
+            result.push_str(
+                "Warning: file changed since indexing; line ranges may be stale (re-index to refresh)\n",
+            );
+        }
+        if source.clamped {
+            result.push_str("Warning: indexed range extends past end of file; output clamped\n");
+        }
+
+        let fence_lang = symbol.language_id.map(|id| id.as_str()).unwrap_or("");
+        result.push_str(&format!("\n```{fence_lang}\n{}\n```\n", source.text));
+
+        Ok(CallToolResult::success(vec![Content::text(result)]))
+    }
+
+    #[tool(
+        description = "Find where a name imported by a file is defined.\n\nReports the resolved symbol, the candidates when several definitions match, or that the import is unresolved (external when it comes from a dependency)."
+    )]
+    pub async fn resolve_import(
+        &self,
+        Parameters(ResolveImportRequest { file_path, name }): Parameters<ResolveImportRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        use crate::indexing::ImportResolution;
+
+        let indexer = self.indexer.read().await;
+
+        let resolution = match indexer.resolve_import(&file_path, &name) {
+            Ok(resolution) => resolution,
+            Err(e) => {
+                return Ok(CallToolResult::success(vec![Content::text(format!(
+                    "Error: {e}"
+                ))]));
+            }
+        };
+
+        let mut result = format!(
+            "Import '{name}' in {file_path} ({}): {resolution}\n",
+            resolution.import_path()
+        );
+        match &resolution {
+            ImportResolution::Resolved { symbol, .. }
+            | ImportResolution::Heuristic { symbol, .. } => {
+                result.push_str(&format!(
+                    "  symbol_id:{} - {:?} {} at {}:{}\n",
+                    symbol.id.value(),
+                    symbol.kind,
+                    symbol.name,
+                    display_path(&symbol.file_path),
+                    symbol.range.start_line + 1
+                ));
+            }
+            ImportResolution::Ambiguous { candidates, .. } => {
+                for (i, sym) in candidates.iter().take(10).enumerate() {
+                    result.push_str(&format!(
+                        "  {}. symbol_id:{} - {:?} {} at {}:{}\n",
+                        i + 1,
+                        sym.id.value(),
+                        sym.kind,
+                        sym.name,
+                        display_path(&sym.file_path),
+                        sym.range.start_line + 1
+                    ));
+                }
+                if candidates.len() > 10 {
+                    result.push_str(&format!("  ... and {} more\n", candidates.len() - 10));
+                }
+            }
+            ImportResolution::Unresolved { external: true, .. } => {
+                result.push_str(
+                    "  Defined outside the indexed code (dependency or standard library)\n",
+                );
+            }
+            ImportResolution::Unresolved {
+                external: false, ..
+            } => {
+                result.push_str("  No matching definition in the index\n");
+            }
+        }
+
+        Ok(CallToolResult::success(vec![Content::text(result)]))
+    }
+
+    #[tool(
+        description = "Find the symbol at a file position, for editor integrations.\n\nReturns the innermost symbol whose range contains the position, plus the chain of symbols enclosing it (e.g. method -> type -> module). Line and column are 1-based unless zero_based is set."
+    )]
+    pub async fn symbol_at(
+        &self,
+        Parameters(request): Parameters<SymbolAtRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        use crate::symbol::context::SymbolContext;
+
+        let (line, column) = match request.position() {
+            Ok(position) => position,
+            Err(e) => {
+                return Ok(CallToolResult::success(vec![Content::text(format!(
+                    "Error: {e}"
+                ))]));
+            }
+        };
+
+        let indexer = self.indexer.read().await;
+        let found = match indexer.symbol_at(&request.file_path, line, column) {
+            Ok(found) => found,
+            Err(e) => {
+                return Ok(CallToolResult::success(vec![Content::text(format!(
+                    "Error: {e}"
+                ))]));
+            }
+        };
+
+        let position = format!(
+            "{}:{}:{}",
+            display_path(&request.file_path),
+            line + 1,
+            u32::from(column) + 1
+        );
+        let Some(found) = found else {
+            return Ok(CallToolResult::success(vec![Content::text(format!(
+                "No symbol at {position}"
+            ))]));
+        };
+
+        let symbol = &found.symbol;
+        let mut result = format!("Symbol at {position}:\n");
+        result.push_str(&format!(
+            "{:?} {} at {} [symbol_id:{}]\n",
+            symbol.kind,
+            symbol.name,
+            SymbolContext::symbol_location(symbol),
+            symbol.id.value()
+        ));
+        if let Some(module) = symbol.as_module_path() {
+            result.push_str(&format!("Module: {module}\n"));
+        }
+        if let Some(sig) = symbol.as_signature() {
+            result.push_str(&format!("Signature: {sig}\n"));
+        }
+        if let Some(doc) = symbol.as_doc_comment() {
+            let doc_preview: Vec<&str> = doc.lines().take(3).collect();
+            let preview = if doc.lines().count() > 3 {
+                format!("{}...", doc_preview.join(" "))
+            } else {
+                doc_preview.join(" ")
+            };
+            result.push_str(&format!("Documentation: {preview}\n"));
+        }
+
+        if !found.enclosing.is_empty() {
+            result.push_str("\nEnclosed by (innermost first):\n");
+            for outer in &found.enclosing {
+                result.push_str(&format!(
+                    "  - {:?} {} at {} [symbol_id:{}]\n",
+                    outer.kind,
+                    outer.name,
+                    SymbolContext::symbol_location(outer),
+                    outer.id.value()
+                ));
+            }
+        }
+
+        Ok(CallToolResult::success(vec![Content::text(result)]))
+    }
+
+    #[tool(
+        description = "Find the concrete implementations of a trait or interface method.\n\nGiven `Trait::method`, returns each implementing type's own version of that method with its location. Types that keep a default body are not listed."
+    )]
+    pub async fn find_implementors_of_method(
+        &self,
+        Parameters(FindImplementorsOfMethodRequest { method, lang }): Parameters<
+            FindImplementorsOfMethodRequest,
+        >,
+    ) -> Result<CallToolResult, McpError> {
+        use crate::symbol::context::SymbolContext;
+
+        let indexer = self.indexer.read().await;
+        let implementations = match indexer.find_method_implementations(&method, lang.as_deref()) {
+            Ok(implementations) => implementations,
+            Err(e) => {
+                return Ok(CallToolResult::success(vec![Content::text(format!(
+                    "Error: {e}"
+                ))]));
+            }
+        };
+
+        if implementations.is_empty() {
+            return Ok(CallToolResult::success(vec![Content::text(format!(
+                "No implementations of '{method}' found"
+            ))]));
+        }
+
+        let mut result = format!(
+            "Found {} implementation(s) of '{method}':\n",
+            implementations.len()
+        );
+        for implementation in &implementations {
+            let method = &implementation.method;
+            result.push_str(&format!(
+                "  - {}::{} at {} [symbol_id:{}]\n",
+                implementation.implementor.name,
+                method.name,
+                SymbolContext::symbol_location(method),
+                method.id.value()
+            ));
+        }
+
+        Ok(CallToolResult::success(vec![Content::text(result)]))
+    }
+
+    #[tool(
+        description = "Get the inheritance hierarchy of a type, class, trait or interface as nested JSON.\n\nAncestors are what it extends or implements, then theirs; descendants are what extends or implements it, then theirs. Each node carries its location and relation. Types reached twice (diamonds, cycles) are marked repeated and not expanded again."
+    )]
+    pub async fn get_type_hierarchy(
+        &self,
+        Parameters(GetTypeHierarchyRequest {
+            name,
+            direction,
+            max_depth,
+            lang,
+        }): Parameters<GetTypeHierarchyRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        use crate::symbol::context::HierarchyDirection;
+
+        let direction = match direction
+            .as_deref()
+            .map(HierarchyDirection::parse_filter)
+            .transpose()
+        {
+            Ok(direction) => direction.unwrap_or(HierarchyDirection::Both),
+            Err(e) => {
+                return Ok(CallToolResult::error(vec![Content::text(e.to_string())]));
+            }
+        };
+
+        let indexer = self.indexer.read().await;
+        let hierarchy = match indexer.find_type_hierarchy(
+            &name,
+            lang.as_deref(),
+            direction,
+            max_depth as usize,
+        ) {
+            Ok(hierarchy) => hierarchy,
+            Err(e) => {
+                return Ok(CallToolResult::error(vec![Content::text(e.to_string())]));
+            }
+        };
+
+        let json = serde_json::to_string_pretty(&hierarchy)
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        Ok(CallToolResult::success(vec![Content::text(json)]))
+    }
+
+    #[tool(
+        description = "Find every textual occurrence of an identifier, like grep over indexed files.\n\nReturns file:line:col for each token, including uses the relationship graph misses (strings, reflection, dynamic dispatch). Requires indexing.index_occurrences."
+    )]
+    pub async fn find_occurrences(
+        &self,
+        Parameters(FindOccurrencesRequest { name, limit }): Parameters<FindOccurrencesRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let indexer = self.indexer.read().await;
+
+        let occurrences = match indexer.find_occurrences(&name, limit as usize) {
+            Ok(occurrences) => occurrences,
+            Err(e) => {
+                return Ok(CallToolResult::success(vec![Content::text(format!(
+                    "Error: {e}"
+                ))]));
+            }
+        };
+
+        if occurrences.is_empty() {
+            return Ok(CallToolResult::success(vec![Content::text(format!(
+                "No occurrences of '{name}' found"
+            ))]));
+        }
+
+        let mut result = format!("Found {} occurrence(s) of '{name}':\n", occurrences.len());
+        for occ in &occurrences {
+            result.push_str(&format!(
+                "  {}:{}:{}\n",
+                display_path(&occ.file_path),
+                occ.line,
+                occ.column
+            ));
+        }
+        if occurrences.len() == limit as usize {
+            result.push_str(&format!(
+                "\nShowing the first {limit}; raise limit to see more\n"
+            ));
+        }
+
+        Ok(CallToolResult::success(vec![Content::text(result)]))
+    }
+
     #[tool(description = "Get information about the indexed codebase")]
     pub async fn get_index_info(
         &self,
@@ -844,8 +1528,60 @@ impl CodeIntelligenceServer {
             "\n\nSemantic Search:\n  - Status: Disabled".to_string()
         };
 
+        // Measure on-disk size of each index part
+        let usage = crate::storage::IndexDiskUsage::measure(&indexer.settings().index_path);
+        let mut disk_info = format!(
+            "\n\nDisk Usage:\n  - Total: {}",
+            format_bytes(usage.total_bytes)
+        );
+        if let Some(per_symbol) = usage.bytes_per_symbol(symbol_count) {
+            disk_info.push_str(&format!(" ({} per symbol)", format_bytes(per_symbol)));
+        }
+        disk_info.push_str(&format!(
+            "\n  - Tantivy (symbols, relationships, text index): {}\n  - Symbol cache: {}\n  - Semantic: {}\n  - Metadata: {}",
+            format_bytes(usage.tantivy_bytes),
+            format_bytes(usage.symbol_cache_bytes),
+            format_bytes(usage.semantic_bytes),
+            format_bytes(usage.metadata_bytes)
+        ));
+        if usage.occurrences_bytes > 0 {
+            disk_info.push_str(&format!(
+                "\n  - Occurrences: {}",
+                format_bytes(usage.occurrences_bytes)
+            ));
+        }
+        if let Some(metadata) = indexer.get_semantic_metadata() {
+            let expected = crate::vector::MmapVectorStorage::expected_file_size(
+                metadata.dimension,
+                metadata.embedding_count,
+            );
+            disk_info.push_str(&format!(
+                "\n  - Vectors: {} (expected {} for {} embeddings x {} dimensions)",
+                format_bytes(usage.semantic_vectors_bytes),
+                format_bytes(expected),
+                metadata.embedding_count,
+                metadata.dimension
+            ));
+        }
+
+        // Files edited on disk since they were indexed
+        let stale = indexer.stale_files();
+        let mut freshness_info = format!("\n\nFreshness:\n  - Stale files: {}", stale.len());
+        for path in stale.iter().take(5) {
+            freshness_info.push_str(&format!("\n    - {}", path.display()));
+        }
+        if stale.len() > 5 {
+            freshness_info.push_str(&format!("\n    - ... and {} more", stale.len() - 5));
+        }
+
+        let relationships_display = if indexer.relationships_indexed() {
+            relationship_count.to_string()
+        } else {
+            format!("{relationship_count} (resolution disabled; call graph tools unavailable)")
+        };
+
         let result = format!(
-            "Index contains {symbol_count} symbols across {file_count} files.\n\nBreakdown:\n  - Symbols: {symbol_count}\n  - Relationships: {relationship_count}\n\nSymbol Kinds:{kinds_display}{semantic_info}"
+            "Index contains {symbol_count} symbols across {file_count} files.\n\nBreakdown:\n  - Symbols: {symbol_count}\n  - Relationships: {relationships_display}\n\nSymbol Kinds:{kinds_display}{semantic_info}{disk_info}{freshness_info}"
         );
 
         Ok(CallToolResult::success(vec![Content::text(result)]))
@@ -859,6 +1595,7 @@ impl CodeIntelligenceServer {
             limit,
             threshold,
             lang,
+            explain,
         }): Parameters<SemanticSearchRequest>,
     ) -> Result<CallToolResult, McpError> {
         let indexer = self.indexer.read().await;
@@ -924,10 +1661,14 @@ impl CodeIntelligenceServer {
                 }
 
                 let mut result = format!(
-                    "Found {} semantically similar result(s) for '{}':\n\n",
+                    "Found {} semantically similar result(s) for '{}':\n",
                     results.len(),
                     query
                 );
+                if let Some(mode) = indexer.semantic_search_mode(lang.as_deref()) {
+                    result.push_str(&format!("Search: {mode}\n"));
+                }
+                result.push('\n');
 
                 for (i, (symbol, score)) in results.iter().enumerate() {
                     result.push_str(&format!(
@@ -939,7 +1680,7 @@ impl CodeIntelligenceServer {
                     ));
                     result.push_str(&format!(
                         "   File: {}:{}\n",
-                        symbol.file_path,
+                        display_path(&symbol.file_path),
                         symbol.range.start_line + 1
                     ));
 
@@ -958,6 +1699,11 @@ impl CodeIntelligenceServer {
                         result.push_str(&format!("   Signature: {sig}\n"));
                     }
 
+                    if explain {
+                        let explanation = crate::semantic::explain_match(&query, symbol);
+                        result.push_str(&format!("   Why: {}\n", explanation.summary()));
+                    }
+
                     result.push('\n');
                 }
 
@@ -1092,46 +1838,24 @@ impl CodeIntelligenceServer {
                                 symbol.name,
                                 called_with_metadata.len()
                             ));
-                            for (i, (called, metadata)) in
+                            for (i, (called, metadata, provenance)) in
                                 called_with_metadata.iter().take(10).enumerate()
                             {
                                 // Parse receiver information from metadata and get call site location
                                 let (call_display, call_line) = if let Some(meta) = metadata {
-                                    let display = if let Some(context) = &meta.context {
-                                        if context.contains("receiver:")
-                                            && context.contains("static:")
+                                    let display = match meta.call.as_ref() {
+                                        // `self::` is not a meaningful call form
+                                        Some(call)
+                                            if call.is_static
+                                                && call.receiver.as_deref() == Some("self") =>
                                         {
-                                            let parts: Vec<&str> = context.split(',').collect();
-                                            let mut receiver = None;
-                                            let mut is_static = false;
-
-                                            for part in parts {
-                                                if let Some(recv) = part.strip_prefix("receiver:") {
-                                                    receiver = Some(recv.trim());
-                                                } else if let Some(static_val) =
-                                                    part.strip_prefix("static:")
-                                                {
-                                                    is_static = static_val.trim() == "true";
-                                                }
-                                            }
-
-                                            match (receiver, is_static) {
-                                                (Some("self"), false) => {
-                                                    format!("(self.{})", called.name)
-                                                }
-                                                (Some(recv), true) if recv != "self" => {
-                                                    format!("({}::{})", recv, called.name)
-                                                }
-                                                (Some(recv), false) if recv != "self" => {
-                                                    format!("({}.{})", recv, called.name)
-                                                }
-                                                _ => called.name.to_string(),
-                                            }
-                                        } else {
                                             called.name.to_string()
                                         }
-                                    } else {
-                                        called.name.to_string()
+                                        Some(call) => call
+                                            .qualified_name(&called.name)
+                                            .map(|qualified| format!("({qualified})"))
+                                            .unwrap_or_else(|| called.name.to_string()),
+                                        None => called.name.to_string(),
                                     };
 
                                     // Use call site line if available
@@ -1145,12 +1869,13 @@ impl CodeIntelligenceServer {
                                 };
 
                                 output.push_str(&format!(
-                                    "     -> {:?} {} at {}:{} [symbol_id:{}]\n",
+                                    "     -> {:?} {} at {}:{} [symbol_id:{}]{}\n",
                                     called.kind,
                                     call_display,
-                                    called.file_path,
+                                    display_path(&called.file_path),
                                     call_line,
-                                    called.id.value()
+                                    called.id.value(),
+                                    provenance_note(*provenance)
                                 ));
                                 if i == 9 && called_with_metadata.len() > 10 {
                                     output.push_str(&format!(
@@ -1170,45 +1895,17 @@ impl CodeIntelligenceServer {
                                 calling_functions_with_metadata.len(),
                                 symbol.name
                             ));
-                            for (i, (caller, metadata)) in
+                            for (i, (caller, metadata, provenance)) in
                                 calling_functions_with_metadata.iter().take(10).enumerate()
                             {
                                 // Parse metadata to extract receiver info and call site location
                                 let (call_info, call_line) = if let Some(meta) = metadata {
-                                    let info = if let Some(context) = &meta.context {
-                                        if context.contains("receiver:")
-                                            && context.contains("static:")
-                                        {
-                                            // Parse "receiver:{receiver},static:{is_static}"
-                                            let parts: Vec<&str> = context.split(',').collect();
-                                            let mut receiver = "";
-                                            let mut is_static = false;
-
-                                            for part in parts {
-                                                if let Some(r) = part.strip_prefix("receiver:") {
-                                                    receiver = r;
-                                                } else if let Some(s) = part.strip_prefix("static:")
-                                                {
-                                                    is_static = s == "true";
-                                                }
-                                            }
-
-                                            if !receiver.is_empty() {
-                                                let qualified_name = if is_static {
-                                                    format!("{}::{}", receiver, symbol.name)
-                                                } else {
-                                                    format!("{}.{}", receiver, symbol.name)
-                                                };
-                                                format!(" (calls {qualified_name})")
-                                            } else {
-                                                String::new()
-                                            }
-                                        } else {
-                                            String::new()
-                                        }
-                                    } else {
-                                        String::new()
-                                    };
+                                    let info = meta
+                                        .call
+                                        .as_ref()
+                                        .and_then(|call| call.qualified_name(&symbol.name))
+                                        .map(|qualified_name| format!(" (calls {qualified_name})"))
+                                        .unwrap_or_default();
 
                                     // Use call site line if available
                                     let line = meta
@@ -1221,13 +1918,14 @@ impl CodeIntelligenceServer {
                                 };
 
                                 output.push_str(&format!(
-                                    "     <- {:?} {} at {}:{}{} [symbol_id:{}]\n",
+                                    "     <- {:?} {} at {}:{}{} [symbol_id:{}]{}\n",
                                     caller.kind,
                                     caller.name,
-                                    caller.file_path,
+                                    display_path(&caller.file_path),
                                     call_line,
                                     call_info,
-                                    caller.id.value()
+                                    caller.id.value(),
+                                    provenance_note(*provenance)
                                 ));
                                 if i == 9 && calling_functions_with_metadata.len() > 10 {
                                     output.push_str(&format!(
@@ -1239,7 +1937,7 @@ impl CodeIntelligenceServer {
                         }
 
                         // Impact analysis - using logic from analyze_impact
-                        let impacted = indexer.get_impact_radius(symbol.id, Some(2));
+                        let impacted = indexer.get_impact_radius(symbol.id, Some(2), false).symbols;
                         if !impacted.is_empty() {
                             output.push_str(&format!(
                                 "\n   Changing {} would impact {} symbol(s) (max depth: 2):\n",
@@ -1339,7 +2037,9 @@ impl CodeIntelligenceServer {
         }
     }
 
-    #[tool(description = "Search for symbols using full-text search with fuzzy matching")]
+    #[tool(
+        description = "Search for symbols using full-text search with fuzzy matching (name:, doc:, sig: and attribute: prefixes target one field), regex matching on names with regex: true, case-insensitive substring matching on names with substring: true, or whole-name matching with exact: true (case_sensitive: true to match case). Set fallback_semantic: true to get semantic results when nothing matches"
+    )]
     pub async fn search_symbols(
         &self,
         Parameters(SearchSymbolsRequest {
@@ -1348,32 +2048,155 @@ impl CodeIntelligenceServer {
             kind,
             module,
             lang,
+            file,
+            regex,
+            substring,
+            exact,
+            case_sensitive,
+            fallback_semantic,
+            min_score,
+            fields,
+            visibility,
+            attribute,
+            group_by,
         }): Parameters<SearchSymbolsRequest>,
     ) -> Result<CallToolResult, McpError> {
+        if usize::from(regex) + usize::from(substring) + usize::from(exact) > 1 {
+            return Ok(CallToolResult::error(vec![Content::text(
+                "Search failed: regex, substring and exact cannot be combined".to_string(),
+            )]));
+        }
+        let fields = match crate::io::parse_fields(&fields) {
+            Ok(fields) => fields,
+            Err(e) => {
+                return Ok(CallToolResult::error(vec![Content::text(format!(
+                    "Search failed: {e}"
+                ))]));
+            }
+        };
+
+        let group_by = match group_by
+            .as_deref()
+            .map(crate::storage::SearchGroupBy::parse_filter)
+            .transpose()
+        {
+            Ok(group_by) => group_by,
+            Err(e) => {
+                return Ok(CallToolResult::error(vec![Content::text(format!(
+                    "Search failed: {e}"
+                ))]));
+            }
+        };
+
         let indexer = self.indexer.read().await;
 
         // Parse the kind filter if provided
-        let kind_filter = kind.as_ref().and_then(|k| match k.to_lowercase().as_str() {
-            "function" => Some(crate::SymbolKind::Function),
-            "struct" => Some(crate::SymbolKind::Struct),
-            "trait" => Some(crate::SymbolKind::Trait),
-            "method" => Some(crate::SymbolKind::Method),
-            "field" => Some(crate::SymbolKind::Field),
-            "module" => Some(crate::SymbolKind::Module),
-            "constant" => Some(crate::SymbolKind::Constant),
-            _ => None,
-        });
-
-        match indexer.search(
-            &query,
-            limit as usize,
-            kind_filter,
-            module.as_deref(),
-            lang.as_deref(),
-        ) {
-            Ok(results) => {
+        let kind_filter = match kind
+            .as_deref()
+            .map(crate::SymbolKind::parse_filter)
+            .transpose()
+        {
+            Ok(kind_filter) => kind_filter,
+            Err(e) => {
+                return Ok(CallToolResult::error(vec![Content::text(format!(
+                    "Search failed: {e}"
+                ))]));
+            }
+        };
+        let visibility = match visibility
+            .as_deref()
+            .map(crate::Visibility::parse_filter)
+            .transpose()
+        {
+            Ok(visibility) => visibility,
+            Err(e) => {
+                return Ok(CallToolResult::error(vec![Content::text(format!(
+                    "Search failed: {e}"
+                ))]));
+            }
+        };
+
+        let files = file.as_deref().map(|file| indexer.files_matching(file));
+        let filters = crate::storage::SearchFilters {
+            attribute: attribute.as_deref(),
+            visibility,
+            files: files.as_ref(),
+        };
+        let search_results = if regex {
+            indexer.search_regex(
+                &query,
+                limit as usize,
+                kind_filter,
+                module.as_deref(),
+                lang.as_deref(),
+                &filters,
+            )
+        } else if exact {
+            indexer.search_exact(
+                &query,
+                case_sensitive,
+                limit as usize,
+                kind_filter,
+                module.as_deref(),
+                lang.as_deref(),
+                &filters,
+            )
+        } else if substring {
+            indexer.search_substring(
+                &query,
+                limit as usize,
+                kind_filter,
+                module.as_deref(),
+                lang.as_deref(),
+                &filters,
+            )
+        } else {
+            indexer.search(
+                &query,
+                limit as usize,
+                kind_filter,
+                module.as_deref(),
+                lang.as_deref(),
+                &filters,
+            )
+        };
+
+        match search_results {
+            Ok(mut results) => {
+                let dropped = min_score
+                    .map(|min| crate::storage::retain_min_score(&mut results, min))
+                    .unwrap_or(0);
+                let mut dropped_note = match min_score {
+                    Some(min) if dropped > 0 => {
+                        format!(" ({dropped} below min_score {min} dropped)")
+                    }
+                    _ => String::new(),
+                };
+
+                if results.is_empty() && fallback_semantic {
+                    if indexer.has_semantic_search() {
+                        match indexer.search_semantic_fallback(
+                            &query,
+                            limit as usize,
+                            kind_filter,
+                            module.as_deref(),
+                            lang.as_deref(),
+                            &filters,
+                        ) {
+                            Ok(semantic) => results = semantic,
+                            Err(e) => {
+                                dropped_note.push_str(&format!(" (semantic fallback failed: {e})"))
+                            }
+                        }
+                    } else {
+                        dropped_note.push_str(
+                            " (semantic fallback skipped: semantic search is not enabled)",
+                        );
+                    }
+                }
+
                 if results.is_empty() {
-                    let mut output = format!("No results found for query: {query}");
+                    let mut output = format!("No results found for query: {query}{dropped_note}");
                     // Add guidance for no results
                     if let Some(guidance) =
                         generate_mcp_guidance(indexer.settings(), "search_symbols", 0)
@@ -1385,11 +2208,41 @@ impl CodeIntelligenceServer {
                     return Ok(CallToolResult::success(vec![Content::text(output)]));
                 }
 
-                let mut result = format!(
-                    "Found {} result(s) for query '{}':\n\n",
-                    results.len(),
-                    query
-                );
+                let mut result = if results[0].source == crate::storage::SearchSource::Semantic {
+                    format!(
+                        "No full-text matches for query '{}'{}; showing {} semantic result(s):\n\n",
+                        query,
+                        dropped_note,
+                        results.len()
+                    )
+                } else {
+                    format!(
+                        "Found {} result(s) for query '{}'{}:\n\n",
+                        results.len(),
+                        query,
+                        dropped_note
+                    )
+                };
+
+                if let Some(group_by) = group_by {
+                    for group in crate::storage::group_results(&results, group_by) {
+                        result.push_str(&format!("{group}\n"));
+                    }
+                    if results.len() == limit as usize {
+                        result.push_str(&format!(
+                            "\nCounts cover the first {limit} results; raise limit to count more\n"
+                        ));
+                    }
+                    return Ok(CallToolResult::success(vec![Content::text(result)]));
+                }
+
+                if !fields.is_empty() {
+                    for search_result in results {
+                        let row = crate::io::FieldRow::new(&fields, search_result);
+                        result.push_str(&format!("{row}\n"));
+                    }
+                    return Ok(CallToolResult::success(vec![Content::text(result)]));
+                }
 
                 for (i, search_result) in results.iter().enumerate() {
                     result.push_str(&format!(
@@ -1400,7 +2253,8 @@ impl CodeIntelligenceServer {
                     ));
                     result.push_str(&format!(
                         "   File: {}:{}\n",
-                        search_result.file_path, search_result.line
+                        display_path(&search_result.file_path),
+                        search_result.line
                     ));
 
                     if !search_result.module_path.is_empty() {
@@ -1417,7 +2271,14 @@ impl CodeIntelligenceServer {
                         result.push_str(&format!("   Signature: {sig}\n"));
                     }
 
-                    result.push_str(&format!("   Score: {:.2}\n", search_result.score));
+                    if search_result.source == crate::storage::SearchSource::Semantic {
+                        result.push_str(&format!(
+                            "   Similarity: {:.3} (semantic fallback)\n",
+                            search_result.score
+                        ));
+                    } else {
+                        result.push_str(&format!("   Score: {:.2}\n", search_result.score));
+                    }
                     result.push('\n');
                 }
 
@@ -1439,14 +2300,25 @@ impl CodeIntelligenceServer {
     }
 }
 
+/// `mcp.tool_guidance` keys that name no tool of `router`
+fn unknown_tool_guidance<'a>(
+    config: &'a McpConfig,
+    router: &ToolRouter<CodeIntelligenceServer>,
+) -> Vec<&'a str> {
+    config
+        .tool_guidance
+        .keys()
+        .map(String::as_str)
+        .filter(|name| !router.map.values().any(|route| route.attr.name == *name))
+        .collect()
+}
+
 #[tool_handler]
 impl ServerHandler for CodeIntelligenceServer {
     fn get_info(&self) -> ServerInfo {
         ServerInfo {
             protocol_version: ProtocolVersion::V_2024_11_05,
-            capabilities: ServerCapabilities::builder()
-                .enable_tools()
-                .build(),
+            capabilities: ServerCapabilities::builder().enable_tools().build(),
             server_info: Implementation {
                 name: "codanna".to_string(),
                 version: env!("CARGO_PKG_VERSION").to_string(),
@@ -1455,12 +2327,9 @@ impl ServerHandler for CodeIntelligenceServer {
                 icons: None,
             },
             instructions: Some(
-                "This server provides code intelligence tools for analyzing this codebase. \
-                WORKFLOW: Start with 'semantic_search_with_context' or 'semantic_search_docs' to anchor on the right files and APIs - they provide the highest-quality context. \
-                Then use 'find_symbol' and 'search_symbols' to lock onto exact files and kinds. \
-                Treat 'get_calls', 'find_callers', and 'analyze_impact' as hints; confirm with code reading or tighter queries (unique names, kind filters). \
-                Use 'get_index_info' to understand what's indexed."
-                .to_string()
+                self.instructions
+                    .clone()
+                    .unwrap_or_else(|| SERVER_INSTRUCTIONS.to_string()),
             ),
         }
     }
@@ -11,7 +11,33 @@ pub enum FileChangeEvent {
     FileReindexed { path: PathBuf },
     FileCreated { path: PathBuf },
     FileDeleted { path: PathBuf },
-    IndexReloaded, // Entire index was reloaded from disk
+    IndexReloaded,                       // Entire index was reloaded from disk
+    ReindexBatchCompleted(ReindexBatch), // Changed source files finished re-indexing
+}
+
+/// Outcome of one watcher pass that re-indexed changed source files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReindexBatch {
+    /// Files re-indexed with new content
+    pub files_changed: usize,
+    /// Files that failed to re-index
+    pub files_failed: usize,
+    pub symbols_before: usize,
+    pub symbols_after: usize,
+}
+
+impl ReindexBatch {
+    /// Structured data for the `notifications/message` sent to MCP clients
+    pub fn log_data(&self) -> serde_json::Value {
+        serde_json::json!({
+            "action": "reindex-complete",
+            "files_changed": self.files_changed,
+            "files_failed": self.files_failed,
+            "symbols_before": self.symbols_before,
+            "symbols_after": self.symbols_after,
+            "symbol_delta": self.symbols_after as i64 - self.symbols_before as i64,
+        })
+    }
 }
 
 /// Manages notification broadcasting to multiple MCP server instances
@@ -138,6 +164,18 @@ impl super::CodeIntelligenceServer {
                                     eprintln!("DEBUG: Sent resource list changed for index reload");
                                 }
                             }
+                            FileChangeEvent::ReindexBatchCompleted(batch) => {
+                                let _ = peer
+                                    .notify_logging_message(LoggingMessageNotificationParam {
+                                        level: LoggingLevel::Info,
+                                        logger: Some("codanna".to_string()),
+                                        data: batch.log_data(),
+                                    })
+                                    .await;
+                                if mcp_debug {
+                                    eprintln!("DEBUG: Sent re-index batch summary: {batch:?}");
+                                }
+                            }
                         }
                     } else if mcp_debug {
                         eprintln!("DEBUG: No peer available yet - notification dropped");
@@ -12,7 +12,7 @@ use tracing::{debug, error, info, warn};
 
 use super::{
     CodeIntelligenceServer,
-    notifications::{FileChangeEvent, NotificationBroadcaster},
+    notifications::{FileChangeEvent, NotificationBroadcaster, ReindexBatch},
 };
 use crate::{IndexPersistence, Settings, SimpleIndexer};
 
@@ -119,7 +119,7 @@ impl IndexWatcher {
 
         info!("Index file changed, reloading from {:?}", self.index_path);
         if self.settings.mcp.debug {
-            eprintln!("DEBUG: IndexWatcher is reloading the index!");
+            crate::log_debug!("IndexWatcher is reloading the index!");
         }
 
         // Load the new index
@@ -149,8 +149,8 @@ impl IndexWatcher {
                             }
                             Ok(false) => {
                                 if self.settings.debug {
-                                    eprintln!(
-                                        "DEBUG: Semantic metadata present but reload returned false"
+                                    crate::log_debug!(
+                                        "Semantic metadata present but reload returned false"
                                     );
                                 }
                             }
@@ -162,8 +162,8 @@ impl IndexWatcher {
                             }
                         }
                     } else if self.settings.debug {
-                        eprintln!(
-                            "DEBUG: Semantic metadata missing when attempting reload at {}",
+                        crate::log_debug!(
+                            "Semantic metadata missing when attempting reload at {}",
                             semantic_path.display()
                         );
                     }
@@ -174,18 +174,18 @@ impl IndexWatcher {
                 if restored_semantic && self.settings.debug {
                     match indexer_guard.semantic_search_embedding_count() {
                         Ok(count) => {
-                            eprintln!("DEBUG: Restored semantic search with {count} embeddings");
+                            crate::log_debug!("Restored semantic search with {count} embeddings");
                         }
                         Err(e) => {
-                            eprintln!(
-                                "DEBUG: Restored semantic search but failed to count embeddings: {e}"
+                            crate::log_debug!(
+                                "Restored semantic search but failed to count embeddings: {e}"
                             );
                         }
                     }
                 }
                 info!("Index successfully reloaded with {symbol_count} symbols");
                 if self.settings.mcp.debug {
-                    eprintln!("DEBUG: After reload, has_semantic_search: {has_semantic}");
+                    crate::log_debug!("After reload, has_semantic_search: {has_semantic}");
                 }
 
                 // Send notification that index was reloaded
@@ -248,7 +248,9 @@ impl IndexWatcher {
             );
 
             let mut indexer = self.indexer.write().await;
+            let symbols_before = indexer.symbol_count();
             let mut reindexed_count = 0;
+            let mut failed_count = 0;
 
             for path in files_to_reindex {
                 debug!("Re-indexing: {:?}", path);
@@ -275,6 +277,7 @@ impl IndexWatcher {
                         }
                     }
                     Err(e) => {
+                        failed_count += 1;
                         warn!("  ✗ Failed to re-index {:?}: {}", path, e);
                     }
                 }
@@ -288,6 +291,23 @@ impl IndexWatcher {
                 if let Err(e) = persistence.save(&indexer) {
                     error!("Failed to persist index after re-indexing: {}", e);
                 }
+
+                // One summary for the whole batch, after the per-file notifications
+                let batch = ReindexBatch {
+                    files_changed: reindexed_count,
+                    files_failed: failed_count,
+                    symbols_before,
+                    symbols_after: indexer.symbol_count(),
+                };
+                if let Some(ref server) = self.mcp_server {
+                    let server_clone = server.clone();
+                    tokio::spawn(async move {
+                        server_clone.notify_reindex_batch(&batch).await;
+                    });
+                }
+                if let Some(ref broadcaster) = self.broadcaster {
+                    broadcaster.send(FileChangeEvent::ReindexBatchCompleted(batch));
+                }
             }
         }
 
@@ -312,3 +332,55 @@ pub struct IndexStats {
     pub last_modified: Option<SystemTime>,
     pub index_path: PathBuf,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_reindex_batch_notifies_subscribers() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("lib.rs");
+        fs::write(&source, "pub fn one() {}\n").unwrap();
+
+        let settings = Arc::new(Settings {
+            index_path: temp_dir.path().join("index"),
+            ..Default::default()
+        });
+        let mut indexer = SimpleIndexer::with_settings(settings.clone());
+        indexer.index_file(&source).unwrap();
+
+        // Edit the file after the index was written
+        fs::write(&source, "pub fn one() {}\npub fn two() {}\n").unwrap();
+        fs::File::options()
+            .write(true)
+            .open(&source)
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(60))
+            .unwrap();
+
+        let broadcaster = Arc::new(NotificationBroadcaster::new(16));
+        let mut events = broadcaster.subscribe();
+        let mut watcher = IndexWatcher::new(
+            Arc::new(RwLock::new(indexer)),
+            settings,
+            Duration::from_secs(1),
+        )
+        .with_broadcaster(broadcaster);
+        watcher.check_and_reindex_source_files().await.unwrap();
+
+        match events.try_recv().unwrap() {
+            FileChangeEvent::ReindexBatchCompleted(batch) => {
+                assert_eq!(batch.files_changed, 1);
+                assert_eq!(batch.files_failed, 0);
+                assert_eq!(batch.symbols_after, batch.symbols_before + 1);
+                assert_eq!(batch.log_data()["action"], "reindex-complete");
+                assert_eq!(batch.log_data()["symbol_delta"], 1);
+            }
+            other => panic!("expected a re-index batch summary, got {other:?}"),
+        }
+        assert!(events.try_recv().is_err());
+    }
+}
@@ -1,7 +1,7 @@
 //! C language parser implementation
 
 use crate::parsing::method_call::MethodCall;
-use crate::parsing::parser::check_recursion_depth;
+use crate::parsing::parser::{check_recursion_depth, parse_tree, recovered_children};
 use crate::parsing::{
     HandledNode, Import, Language, LanguageParser, NodeTracker, NodeTrackingState, ParserContext,
     ScopeType,
@@ -360,7 +360,7 @@ impl CParser {
 
                 // Process enum values
                 if let Some(body) = node.child_by_field_name("body") {
-                    for child in body.children(&mut body.walk()) {
+                    for child in recovered_children(body) {
                         if child.kind() == "enumerator" {
                             self.register_handled_node("enumerator", child.kind_id());
                             if let Some(name_node) = child.child_by_field_name("name") {
@@ -937,7 +937,7 @@ impl LanguageParser for CParser {
         // Reset context for each file
         self.context = ParserContext::new();
 
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -961,7 +961,7 @@ impl LanguageParser for CParser {
     }
 
     fn find_calls<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -974,7 +974,7 @@ impl LanguageParser for CParser {
     }
 
     fn find_method_calls(&mut self, code: &str) -> Vec<MethodCall> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -993,7 +993,7 @@ impl LanguageParser for CParser {
     }
 
     fn find_uses<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -1006,7 +1006,7 @@ impl LanguageParser for CParser {
     }
 
     fn find_defines<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -1019,7 +1019,7 @@ impl LanguageParser for CParser {
     }
 
     fn find_imports(&mut self, code: &str, file_id: FileId) -> Vec<Import> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -3,8 +3,9 @@
 //! This module provides scope tracking utilities that all language parsers
 //! can use to communicate proper scope information to resolvers.
 
+use crate::Symbol;
 use crate::symbol::ScopeContext;
-use crate::types::SymbolKind;
+use crate::types::{Range, SymbolKind};
 
 /// Scope types that parsers track during AST traversal
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -209,6 +210,61 @@ impl ParserContext {
     }
 }
 
+/// Fill in [`Symbol::parent`] for symbols a parser left without one
+///
+/// The parent is the innermost function, type or module whose range encloses
+/// the symbol. Parsers set `parent` themselves only where nesting in the
+/// source does not show ownership, such as methods in Rust `impl` blocks.
+pub fn assign_parents(symbols: &mut [Symbol]) {
+    fn start(range: &Range) -> (u32, u16) {
+        (range.start_line, range.start_column)
+    }
+    fn end(range: &Range) -> (u32, u16) {
+        (range.end_line, range.end_column)
+    }
+
+    // Outer symbols sort before the symbols they enclose
+    let mut order: Vec<usize> = (0..symbols.len()).collect();
+    order.sort_by_key(|&i| {
+        let range = &symbols[i].range;
+        (start(range), std::cmp::Reverse(end(range)))
+    });
+
+    // Containers enclosing the current position, innermost last
+    let mut open: Vec<usize> = Vec::new();
+    for i in order {
+        let range = symbols[i].range;
+        while let Some(&top) = open.last() {
+            if end(&range) <= end(&symbols[top].range) {
+                break;
+            }
+            open.pop();
+        }
+
+        if symbols[i].parent.is_none() {
+            symbols[i].parent = open
+                .iter()
+                .rev()
+                .find(|&&j| symbols[j].range != range)
+                .map(|&j| symbols[j].id);
+        }
+
+        if matches!(
+            symbols[i].kind,
+            SymbolKind::Function
+                | SymbolKind::Method
+                | SymbolKind::Struct
+                | SymbolKind::Enum
+                | SymbolKind::Trait
+                | SymbolKind::Interface
+                | SymbolKind::Class
+                | SymbolKind::Module
+        ) {
+            open.push(i);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -305,4 +361,48 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_assign_parents() {
+        use crate::{FileId, SymbolId};
+
+        let symbol = |id: u32, kind: SymbolKind, range: (u32, u16, u32, u16)| {
+            Symbol::new(
+                SymbolId(id),
+                format!("s{id}"),
+                kind,
+                FileId(1),
+                Range::new(range.0, range.1, range.2, range.3),
+            )
+        };
+        let mut symbols = vec![
+            // Declared out of source order on purpose
+            symbol(3, SymbolKind::Method, (2, 4, 6, 5)),
+            symbol(1, SymbolKind::Module, (0, 0, 20, 1)),
+            symbol(2, SymbolKind::Class, (1, 0, 10, 1)),
+            symbol(4, SymbolKind::Variable, (3, 8, 3, 20)),
+            symbol(5, SymbolKind::Function, (12, 0, 14, 1)),
+            symbol(6, SymbolKind::Variable, (13, 4, 13, 9)),
+            symbol(7, SymbolKind::Function, (22, 0, 23, 1)),
+            symbol(8, SymbolKind::Method, (30, 0, 31, 1)).with_parent(SymbolId(7)),
+        ];
+        assign_parents(&mut symbols);
+
+        let parent = |id: u32| {
+            symbols
+                .iter()
+                .find(|s| s.id == SymbolId(id))
+                .and_then(|s| s.parent)
+                .map(|p| p.value())
+        };
+        assert_eq!(parent(1), None);
+        assert_eq!(parent(2), Some(1));
+        assert_eq!(parent(3), Some(2));
+        assert_eq!(parent(4), Some(3));
+        assert_eq!(parent(5), Some(1));
+        assert_eq!(parent(6), Some(5));
+        assert_eq!(parent(7), None);
+        // Parents set by the parser are kept
+        assert_eq!(parent(8), Some(7));
+    }
 }
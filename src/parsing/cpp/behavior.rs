@@ -72,6 +72,10 @@ impl LanguageBehavior for CppBehavior {
         self.language.clone()
     }
 
+    fn attribute_node_kinds(&self) -> &'static [&'static str] {
+        &["attribute_declaration"]
+    }
+
     fn module_path_from_file(&self, file_path: &Path, project_root: &Path) -> Option<String> {
         // Get relative path from project root
         let relative_path = file_path.strip_prefix(project_root).ok()?;
@@ -2,7 +2,7 @@
 
 use crate::parsing::context::ParserContext;
 use crate::parsing::method_call::MethodCall;
-use crate::parsing::parser::check_recursion_depth;
+use crate::parsing::parser::{check_recursion_depth, parse_tree};
 use crate::parsing::{Import, Language, LanguageParser, NodeTracker, NodeTrackingState};
 use crate::types::{Range, SymbolCounter};
 use crate::{FileId, Symbol, SymbolKind, Visibility};
@@ -726,7 +726,7 @@ impl LanguageParser for CppParser {
         file_id: FileId,
         symbol_counter: &mut SymbolCounter,
     ) -> Vec<Symbol> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -786,7 +786,7 @@ impl LanguageParser for CppParser {
     }
 
     fn find_calls<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -800,7 +800,7 @@ impl LanguageParser for CppParser {
     }
 
     fn find_method_calls(&mut self, code: &str) -> Vec<MethodCall> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -814,7 +814,7 @@ impl LanguageParser for CppParser {
     }
 
     fn find_implementations<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -827,7 +827,7 @@ impl LanguageParser for CppParser {
     }
 
     fn find_extends<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -840,7 +840,7 @@ impl LanguageParser for CppParser {
     }
 
     fn find_uses<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -853,7 +853,7 @@ impl LanguageParser for CppParser {
     }
 
     fn find_defines<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -866,7 +866,7 @@ impl LanguageParser for CppParser {
     }
 
     fn find_imports(&mut self, code: &str, file_id: FileId) -> Vec<Import> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -884,7 +884,7 @@ impl LanguageParser for CppParser {
     }
 
     fn find_variable_types<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -897,7 +897,7 @@ impl LanguageParser for CppParser {
     }
 
     fn find_inherent_methods(&mut self, code: &str) -> Vec<(String, String, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
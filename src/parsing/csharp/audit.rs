@@ -6,6 +6,7 @@
 use super::CSharpParser;
 use crate::io::format::format_utc_timestamp;
 use crate::parsing::NodeTracker;
+use crate::parsing::parser::parse_tree;
 use crate::types::FileId;
 use std::collections::{HashMap, HashSet};
 use thiserror::Error;
@@ -51,7 +52,7 @@ impl CSharpParserAudit {
             .set_language(&language)
             .map_err(|e| AuditError::LanguageSetup(e.to_string()))?;
 
-        let tree = parser.parse(code, None).ok_or(AuditError::ParseFailure)?;
+        let tree = parse_tree(&mut parser, code).ok_or(AuditError::ParseFailure)?;
 
         let mut grammar_nodes = HashMap::new();
         discover_nodes(tree.root_node(), &mut grammar_nodes);
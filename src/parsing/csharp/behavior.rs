@@ -75,6 +75,10 @@ impl LanguageBehavior for CSharpBehavior {
         tree_sitter_c_sharp::LANGUAGE.into()
     }
 
+    fn attribute_node_kinds(&self) -> &'static [&'static str] {
+        &["attribute_list"]
+    }
+
     fn module_separator(&self) -> &'static str {
         "." // C# uses dots for namespace separation
     }
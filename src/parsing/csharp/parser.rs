@@ -25,7 +25,7 @@
 //! - External framework references (e.g., System.Console) require special handling
 
 use crate::parsing::Import;
-use crate::parsing::parser::check_recursion_depth;
+use crate::parsing::parser::{check_recursion_depth, parse_tree, recovered_children};
 use crate::parsing::{
     HandledNode, LanguageParser, MethodCall, NodeTracker, NodeTrackingState, ParserContext,
     ScopeType,
@@ -107,7 +107,7 @@ impl CSharpParser {
         self.context = ParserContext::new();
         let mut symbols = Vec::new();
 
-        match self.parser.parse(code, None) {
+        match parse_tree(&mut self.parser, code) {
             Some(tree) => {
                 let root_node = tree.root_node();
                 self.extract_symbols_from_node(
@@ -1296,8 +1296,7 @@ impl CSharpParser {
     ) {
         // Find the class body
         if let Some(body_node) = class_node.child_by_field_name("body") {
-            let mut cursor = body_node.walk();
-            for child in body_node.children(&mut cursor) {
+            for child in recovered_children(body_node) {
                 match child.kind() {
                     "method_declaration" => {
                         if let Some(symbol) =
@@ -1381,8 +1380,7 @@ impl CSharpParser {
     ) {
         // Find the interface body
         if let Some(body_node) = interface_node.child_by_field_name("body") {
-            let mut cursor = body_node.walk();
-            for child in body_node.children(&mut cursor) {
+            for child in recovered_children(body_node) {
                 match child.kind() {
                     "method_declaration" => {
                         if let Some(symbol) =
@@ -1433,8 +1431,7 @@ impl CSharpParser {
     ) {
         // Find the enum body
         if let Some(body_node) = enum_node.child_by_field_name("body") {
-            let mut cursor = body_node.walk();
-            for child in body_node.children(&mut cursor) {
+            for child in recovered_children(body_node) {
                 if child.kind() == "enum_member_declaration" {
                     if let Some(name_node) = child.child_by_field_name("name") {
                         let name = code[name_node.byte_range()].to_string();
@@ -1782,7 +1779,7 @@ impl LanguageParser for CSharpParser {
     }
 
     fn find_calls<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => {
                 eprintln!("Failed to parse C# file for calls");
@@ -1805,7 +1802,7 @@ impl LanguageParser for CSharpParser {
         // Reset context to ensure clean state
         self.context = ParserContext::new();
 
-        match self.parser.parse(code, None) {
+        match parse_tree(&mut self.parser, code) {
             Some(tree) => {
                 let root_node = tree.root_node();
                 self.extract_method_calls_from_node(root_node, code, &mut method_calls);
@@ -1821,7 +1818,7 @@ impl LanguageParser for CSharpParser {
     fn find_implementations<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
         let mut implementations = Vec::new();
 
-        match self.parser.parse(code, None) {
+        match parse_tree(&mut self.parser, code) {
             Some(tree) => {
                 let root_node = tree.root_node();
                 Self::extract_implementations_from_node(root_node, code, &mut implementations);
@@ -1910,7 +1907,7 @@ impl LanguageParser for CSharpParser {
     fn find_variable_types<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
         let mut bindings = Vec::new();
 
-        if let Some(tree) = self.parser.parse(code, None) {
+        if let Some(tree) = parse_tree(&mut self.parser, code) {
             let root = tree.root_node();
             self.find_variable_types_in_node(&root, code, &mut bindings);
         }
@@ -1921,7 +1918,7 @@ impl LanguageParser for CSharpParser {
     fn find_imports(&mut self, code: &str, file_id: FileId) -> Vec<Import> {
         let mut imports = Vec::new();
 
-        match self.parser.parse(code, None) {
+        match parse_tree(&mut self.parser, code) {
             Some(tree) => {
                 let root_node = tree.root_node();
                 Self::extract_imports_from_node(root_node, code, file_id, &mut imports);
@@ -2127,4 +2124,26 @@ mod tests {
         );
         assert!(imports.iter().any(|i| i.path == "MyApp.Services"));
     }
+
+    #[test]
+    fn test_parse_recovers_after_syntax_errors() {
+        let mut parser = CSharpParser::new().unwrap();
+        let manifest_dir = env!("CARGO_MANIFEST_DIR");
+        let test_file =
+            std::path::Path::new(manifest_dir).join("tests/fixtures/csharp/malformed.cs");
+        let code = std::fs::read_to_string(test_file).unwrap();
+
+        let mut counter = SymbolCounter::new();
+        let symbols = parser.parse(&code, FileId::new(1).unwrap(), &mut counter);
+        let names: Vec<&str> = symbols.iter().map(|s| s.name.as_ref()).collect();
+
+        // Before the broken method
+        for name in ["Before", "Value", "Compute", "Broken", "First"] {
+            assert!(names.contains(&name), "missing {name} in {names:?}");
+        }
+        // After it
+        for name in ["Last", "After", "One", "Two"] {
+            assert!(names.contains(&name), "missing {name} in {names:?}");
+        }
+    }
 }
@@ -485,7 +485,7 @@ mod tests {
             },
         );
 
-        settings.languages = languages;
+        settings.languages = languages.into();
         let factory = ParserFactory::new(Arc::new(settings));
 
         // Test Rust
@@ -599,7 +599,7 @@ mod tests {
                 config_files: Vec::new(),
             },
         );
-        settings.languages = languages;
+        settings.languages = languages.into();
 
         let factory = ParserFactory::new(Arc::new(settings));
         let parser = factory.create_parser(Language::Python);
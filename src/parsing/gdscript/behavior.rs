@@ -174,7 +174,7 @@ impl LanguageBehavior for GdscriptBehavior {
 
     fn module_path_from_file(&self, file_path: &Path, project_root: &Path) -> Option<String> {
         let relative = file_path.strip_prefix(project_root).ok()?;
-        let mut path = relative.to_string_lossy().replace('\\', "/");
+        let mut path = crate::paths::normalize_path(relative);
 
         if path.ends_with(".gd") {
             path.truncate(path.len() - 3);
@@ -189,6 +189,10 @@ impl LanguageBehavior for GdscriptBehavior {
         self.language.clone()
     }
 
+    fn attribute_node_kinds(&self) -> &'static [&'static str] {
+        &["annotation"]
+    }
+
     // Override import tracking methods to use state
     fn register_file(&self, path: PathBuf, file_id: FileId, module_path: String) {
         self.register_file_with_state(path, file_id, module_path);
@@ -3,7 +3,7 @@
 //! Provides basic symbol extraction for Godot's GDScript using tree-sitter.
 
 use crate::parsing::Import;
-use crate::parsing::parser::check_recursion_depth;
+use crate::parsing::parser::{check_recursion_depth, format_constant_value, parse_tree};
 use crate::parsing::{
     HandledNode, Language, LanguageParser, NodeTracker, NodeTrackingState, ParserContext, ScopeType,
 };
@@ -859,7 +859,17 @@ impl GdscriptParser {
             SymbolKind::Constant
         };
 
-        let signature = self.text_for_node(code, node).trim();
+        let signature = match node.child_by_field_name("value") {
+            Some(value_node) => {
+                let declaration = code[node.start_byte()..value_node.start_byte()]
+                    .trim_end()
+                    .trim_end_matches(['=', ':'])
+                    .trim_end();
+                let value = format_constant_value(self.text_for_node(code, value_node));
+                format!("{declaration} = {value}")
+            }
+            None => self.text_for_node(code, node).trim().to_string(),
+        };
 
         let symbol_id = counter.next_id();
         let mut symbol = Symbol::new(
@@ -977,7 +987,7 @@ impl LanguageParser for GdscriptParser {
         file_id: FileId,
         symbol_counter: &mut SymbolCounter,
     ) -> Vec<Symbol> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -1020,7 +1030,7 @@ impl LanguageParser for GdscriptParser {
     }
 
     fn find_calls<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -1035,7 +1045,7 @@ impl LanguageParser for GdscriptParser {
     }
 
     fn find_extends<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -1046,7 +1056,7 @@ impl LanguageParser for GdscriptParser {
     }
 
     fn find_uses<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -1061,7 +1071,7 @@ impl LanguageParser for GdscriptParser {
     }
 
     fn find_imports(&mut self, code: &str, file_id: FileId) -> Vec<Import> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -1,5 +1,6 @@
 //! Go-specific language behavior implementation
 
+use crate::indexing::EntryPointCategory;
 use crate::parsing::LanguageBehavior;
 use crate::parsing::behavior_state::{BehaviorState, StatefulBehavior};
 use crate::parsing::resolution::{InheritanceResolver, ResolutionScope};
@@ -48,6 +49,35 @@ impl LanguageBehavior for GoBehavior {
     fn get_language(&self) -> Language {
         tree_sitter_go::LANGUAGE.into()
     }
+
+    fn records_type_use_positions(&self) -> bool {
+        true
+    }
+
+    fn entry_point_category(
+        &self,
+        symbol: &crate::Symbol,
+        _attributes: &[&str],
+    ) -> Option<EntryPointCategory> {
+        if symbol.kind != crate::types::SymbolKind::Function {
+            return None;
+        }
+        match symbol.name.as_ref() {
+            "main" => Some(EntryPointCategory::Main),
+            "TestMain" => Some(EntryPointCategory::TestMain),
+            // net/http handlers: func(w http.ResponseWriter, r *http.Request)
+            _ if symbol.visibility == Visibility::Public
+                && symbol
+                    .signature
+                    .as_deref()
+                    .is_some_and(|sig| sig.contains("http.ResponseWriter")) =>
+            {
+                Some(EntryPointCategory::HttpHandler)
+            }
+            _ => None,
+        }
+    }
+
     fn module_separator(&self) -> &'static str {
         "/"
     }
@@ -795,6 +825,8 @@ mod tests {
             doc_comment: None,
             visibility: Visibility::Private, // Will be updated by configure_symbol
             scope_context: None,
+            parent: None,
+            attributes: Vec::new(),
             language_id: Some(LanguageId::new("go")),
         };
 
@@ -824,6 +856,8 @@ mod tests {
             doc_comment: None,
             visibility: Visibility::Public, // Will be updated by configure_symbol
             scope_context: None,
+            parent: None,
+            attributes: Vec::new(),
             language_id: Some(LanguageId::new("go")),
         };
 
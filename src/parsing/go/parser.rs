@@ -6,11 +6,14 @@
 //! When migrating or updating the parser, ensure compatibility with ABI-15 features.
 
 use crate::parsing::Import;
-use crate::parsing::parser::check_recursion_depth;
+use crate::parsing::parser::{
+    check_recursion_depth, format_constant_value, parse_tree, push_positioned, recovered_children,
+};
 use crate::parsing::{
     HandledNode, LanguageParser, MethodCall, NodeTracker, NodeTrackingState, ParserContext,
-    ScopeType,
+    PositionedUse, ScopeType,
 };
+use crate::relationship::TypeUsePosition;
 use crate::types::SymbolCounter;
 use crate::{FileId, Range, Symbol, SymbolKind, Visibility};
 use std::any::Any;
@@ -46,7 +49,7 @@ impl GoParser {
         self.resolution_context = Some(GoResolutionContext::new(file_id));
         let mut symbols = Vec::new();
 
-        match self.parser.parse(code, None) {
+        match parse_tree(&mut self.parser, code) {
             Some(tree) => {
                 let root_node = tree.root_node();
                 self.extract_symbols_from_node(
@@ -674,7 +677,7 @@ impl GoParser {
         // Look for field_declaration_list
         for child in struct_node.children(&mut struct_node.walk()) {
             if child.kind() == "field_declaration_list" {
-                for field_child in child.children(&mut child.walk()) {
+                for field_child in recovered_children(child) {
                     if field_child.kind() == "field_declaration" {
                         self.register_handled_node("field_declaration", field_child.kind_id());
                         self.process_struct_field(
@@ -764,7 +767,7 @@ impl GoParser {
         interface_name: &str, // Used for generating qualified method names for interface methods
     ) {
         // Look for method_elem nodes
-        for child in interface_node.children(&mut interface_node.walk()) {
+        for child in recovered_children(interface_node) {
             if child.kind() == "method_elem" {
                 self.process_interface_method(
                     child,
@@ -960,6 +963,7 @@ impl GoParser {
     ) {
         let mut const_names = Vec::new();
         let mut const_type = None;
+        let mut const_values = Vec::new();
 
         for child in node.children(&mut node.walk()) {
             match child.kind() {
@@ -970,17 +974,28 @@ impl GoParser {
                 | "channel_type" => {
                     const_type = Some(&code[child.byte_range()]);
                 }
+                "expression_list" => {
+                    // Values pair positionally with names: `const A, B = 1, 2`
+                    for value in child.named_children(&mut child.walk()) {
+                        const_values.push(&code[value.byte_range()]);
+                    }
+                }
                 _ => {}
             }
         }
 
         // Create symbols for each constant name
-        for const_name in const_names {
+        for (index, const_name) in const_names.into_iter().enumerate() {
             let visibility = self.determine_go_visibility(const_name);
-            let signature = match const_type {
+            let mut signature = match const_type {
                 Some(typ) => format!("const {const_name} {typ}"),
                 None => format!("const {const_name}"),
             };
+            // Specs relying on implicit repetition (iota blocks) have no value
+            if let Some(value) = const_values.get(index) {
+                signature.push_str(" = ");
+                signature.push_str(&format_constant_value(value));
+            }
 
             let symbol = self.create_symbol(
                 counter.next_id(),
@@ -1533,8 +1548,10 @@ impl GoParser {
         &self,
         node: &tree_sitter::Node,
         code: &'a str,
-        uses: &mut Vec<(&'a str, &'a str, Range)>,
+        uses: &mut Vec<PositionedUse<'a>>,
     ) {
+        // Uses found at this node; signature and field types are tagged as such
+        let mut found = Vec::new();
         match node.kind() {
             // Go function and method declarations with parameters and return types
             "function_declaration" | "method_declaration" => {
@@ -1545,13 +1562,20 @@ impl GoParser {
 
                 // Check parameters
                 if let Some(params) = node.child_by_field_name("parameters") {
-                    self.extract_go_parameter_types(params, code, context_name, uses);
+                    self.extract_go_parameter_types(params, code, context_name, &mut found);
                 }
+                push_positioned(uses, &mut found, Some(TypeUsePosition::Parameter));
 
-                // Check return type (Go uses "result" field)
+                // Check return type (Go uses "result" field); multiple results
+                // are a parameter list
                 if let Some(result) = node.child_by_field_name("result") {
-                    self.extract_go_type_reference(&result, code, context_name, uses);
+                    if result.kind() == "parameter_list" {
+                        self.extract_go_parameter_types(result, code, context_name, &mut found);
+                    } else {
+                        self.extract_go_type_reference(&result, code, context_name, &mut found);
+                    }
                 }
+                push_positioned(uses, &mut found, Some(TypeUsePosition::Return));
             }
 
             // Go struct types
@@ -1561,11 +1585,17 @@ impl GoParser {
                     if child.kind() == "field_declaration_list" {
                         for field_child in child.children(&mut child.walk()) {
                             if field_child.kind() == "field_declaration" {
-                                self.extract_go_field_types(&field_child, code, "struct", uses);
+                                self.extract_go_field_types(
+                                    &field_child,
+                                    code,
+                                    "struct",
+                                    &mut found,
+                                );
                             }
                         }
                     }
                 }
+                push_positioned(uses, &mut found, Some(TypeUsePosition::Field));
             }
 
             // Go variable declarations
@@ -1587,7 +1617,7 @@ impl GoParser {
                                 | "map_type"
                                 | "channel_type"
                         ) {
-                            self.extract_go_type_reference(&child, code, var_name, uses);
+                            self.extract_go_type_reference(&child, code, var_name, &mut found);
                         }
                     }
                 }
@@ -1614,7 +1644,7 @@ impl GoParser {
                                         | "map_type"
                                 ) {
                                     self.extract_go_type_reference(
-                                        &type_arg, code, func_name, uses,
+                                        &type_arg, code, func_name, &mut found,
                                     );
                                 }
                             }
@@ -1625,6 +1655,7 @@ impl GoParser {
 
             _ => {}
         }
+        push_positioned(uses, &mut found, None);
 
         // Recurse to children
         for child in node.children(&mut node.walk()) {
@@ -2023,7 +2054,7 @@ impl LanguageParser for GoParser {
     /// Returns tuples of (caller_context, called_function, range) for all function calls
     /// including method calls via dot notation and package-qualified calls.
     fn find_calls<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -2042,7 +2073,7 @@ impl LanguageParser for GoParser {
     /// Returns MethodCall structs containing caller, method name, and position information
     /// for all method invocations including pointer receiver calls and chained calls.
     fn find_method_calls(&mut self, code: &str) -> Vec<MethodCall> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -2083,7 +2114,7 @@ impl LanguageParser for GoParser {
     fn find_imports(&mut self, code: &str, file_id: FileId) -> Vec<Import> {
         let mut imports = Vec::new();
 
-        if let Some(tree) = self.parser.parse(code, None) {
+        if let Some(tree) = parse_tree(&mut self.parser, code) {
             let root = tree.root_node();
             self.extract_imports_from_node(root, code, file_id, &mut imports);
         }
@@ -2096,7 +2127,14 @@ impl LanguageParser for GoParser {
     /// Returns tuples of (context, type_name, range) for all type references
     /// including struct field types, function parameters, and return types.
     fn find_uses<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        self.find_uses_with_position(code)
+            .into_iter()
+            .map(|(context, used_type, range, _)| (context, used_type, range))
+            .collect()
+    }
+
+    fn find_uses_with_position<'a>(&mut self, code: &'a str) -> Vec<PositionedUse<'a>> {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -2114,7 +2152,7 @@ impl LanguageParser for GoParser {
     /// Returns tuples of (receiver_type, method_name, range) for all method definitions
     /// with explicit receivers, distinguishing them from standalone functions.
     fn find_defines<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -2619,4 +2657,55 @@ func (p *privateStruct) privateMethod() {
 
         println!("✅ Go visibility variations handled correctly");
     }
+
+    #[test]
+    fn test_find_uses_with_position() {
+        let mut parser = GoParser::new().unwrap();
+        let code = r#"
+package main
+
+type App struct {
+    config Config
+}
+
+func Load(path string) (*Config, error) {
+    return nil, nil
+}
+
+func Apply(config Config) {}
+"#;
+
+        let positions: Vec<_> = parser
+            .find_uses_with_position(code)
+            .into_iter()
+            .filter(|(_, used, _, _)| *used == "Config")
+            .map(|(user, _, _, position)| (user, position))
+            .collect();
+
+        assert!(positions.contains(&("struct", Some(TypeUsePosition::Field))));
+        assert!(positions.contains(&("Load", Some(TypeUsePosition::Return))));
+        assert!(positions.contains(&("Apply", Some(TypeUsePosition::Parameter))));
+        assert!(!positions.contains(&("Load", Some(TypeUsePosition::Parameter))));
+    }
+
+    #[test]
+    fn test_parse_recovers_after_syntax_errors() {
+        let mut parser = GoParser::new().unwrap();
+        let manifest_dir = env!("CARGO_MANIFEST_DIR");
+        let test_file = std::path::Path::new(manifest_dir).join("tests/fixtures/go/malformed.go");
+        let code = std::fs::read_to_string(test_file).unwrap();
+
+        let mut counter = SymbolCounter::new();
+        let symbols = parser.parse(&code, FileId::new(1).unwrap(), &mut counter);
+        let names: Vec<&str> = symbols.iter().map(|s| s.name.as_ref()).collect();
+
+        // Before the broken function
+        for name in ["Before", "Before.Value", "before"] {
+            assert!(names.contains(&name), "missing {name} in {names:?}");
+        }
+        // After it
+        for name in ["Shape", "After", "After.First", "After.Second", "after"] {
+            assert!(names.contains(&name), "missing {name} in {names:?}");
+        }
+    }
 }
@@ -101,7 +101,7 @@ impl LanguageBehavior for KotlinBehavior {
 
     fn module_path_from_file(&self, file_path: &Path, project_root: &Path) -> Option<String> {
         let relative = file_path.strip_prefix(project_root).ok()?;
-        let mut path = relative.to_string_lossy().replace('\\', "/");
+        let mut path = crate::paths::normalize_path(relative);
 
         // Remove .kt or .kts extension
         if path.ends_with(".kt") {
@@ -129,6 +129,10 @@ impl LanguageBehavior for KotlinBehavior {
         self.language.clone()
     }
 
+    fn attribute_node_kinds(&self) -> &'static [&'static str] {
+        &["annotation"]
+    }
+
     fn supports_traits(&self) -> bool {
         true // Kotlin has interfaces
     }
@@ -3,7 +3,7 @@
 //! Provides symbol extraction for Kotlin using tree-sitter.
 
 use crate::parsing::Import;
-use crate::parsing::parser::check_recursion_depth;
+use crate::parsing::parser::{check_recursion_depth, parse_tree, recovered_children};
 use crate::parsing::{
     HandledNode, Language, LanguageParser, NodeTracker, NodeTrackingState, ParserContext, ScopeType,
 };
@@ -18,6 +18,7 @@ use tree_sitter::{Node, Parser};
 const FILE_SCOPE: &str = "<file>";
 const NODE_CLASS_DECLARATION: &str = "class_declaration";
 const NODE_OBJECT_DECLARATION: &str = "object_declaration";
+const NODE_COMPANION_OBJECT: &str = "companion_object";
 const NODE_FUNCTION_DECLARATION: &str = "function_declaration";
 const NODE_PROPERTY_DECLARATION: &str = "property_declaration";
 const NODE_SECONDARY_CONSTRUCTOR: &str = "secondary_constructor";
@@ -43,6 +44,9 @@ const NODE_PARAMETER: &str = "parameter";
 const NODE_CLASS_PARAMETER: &str = "class_parameter";
 const NODE_FUNCTION_VALUE_PARAMETERS: &str = "function_value_parameters";
 const NODE_PRIMARY_CONSTRUCTOR: &str = "primary_constructor";
+const NODE_RECEIVER_TYPE: &str = "receiver_type";
+const NODE_CLASS_MODIFIER: &str = "class_modifier";
+const NODE_BINDING_PATTERN_KIND: &str = "binding_pattern_kind";
 
 // Lazy-initialized HashSet for primitive types
 static KOTLIN_PRIMITIVE_TYPES: OnceLock<HashSet<&'static str>> = OnceLock::new();
@@ -295,10 +299,18 @@ impl KotlinParser {
             match child_kind {
                 NODE_SIMPLE_IDENTIFIER if func_name.is_none() => {
                     func_name = Some(self.text_for_node(code, child).trim().to_string());
+                    if !signature.is_empty() && !signature.ends_with('.') {
+                        signature.push(' ');
+                    }
+                    signature.push_str(self.text_for_node(code, child));
+                }
+                NODE_RECEIVER_TYPE => {
+                    // Extension function: `fun String.shout()` keeps `String.` in the signature
                     if !signature.is_empty() {
                         signature.push(' ');
                     }
                     signature.push_str(self.text_for_node(code, child));
+                    signature.push('.');
                 }
                 NODE_MODIFIERS => {
                     let modifiers_text = self.text_for_node(code, child);
@@ -358,7 +370,7 @@ impl KotlinParser {
                 );
                 return;
             }
-            NODE_OBJECT_DECLARATION => {
+            NODE_OBJECT_DECLARATION | NODE_COMPANION_OBJECT => {
                 self.handle_object_declaration(
                     node, code, file_id, symbols, counter, context, depth,
                 );
@@ -424,6 +436,8 @@ impl KotlinParser {
             }
         }
 
+        let is_data = self.is_data_class(node, code);
+
         // Extract class/interface name - find the type_identifier child
         let mut class_name = None;
         let mut cursor = node.walk();
@@ -472,6 +486,23 @@ impl KotlinParser {
         context.set_current_class(Some(class_name.clone()));
         symbols.push(symbol);
 
+        // Properties declared in the primary constructor, plus data class members
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            if child.kind() == NODE_PRIMARY_CONSTRUCTOR {
+                self.handle_primary_constructor(
+                    child,
+                    code,
+                    file_id,
+                    symbols,
+                    counter,
+                    &class_name,
+                    is_data,
+                );
+                break;
+            }
+        }
+
         // Process class/interface/enum body
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
@@ -519,6 +550,8 @@ impl KotlinParser {
     ) {
         self.register_node(&node);
 
+        let is_companion = node.kind() == NODE_COMPANION_OBJECT;
+
         // Extract object name - find the type_identifier child
         let mut object_name = None;
         let mut cursor = node.walk();
@@ -529,8 +562,21 @@ impl KotlinParser {
             }
         }
 
+        // Signature before defaulting the name, so an unnamed companion reads `companion object`
+        let signature = if is_companion {
+            match &object_name {
+                Some(name) => format!("companion object {name}"),
+                None => "companion object".to_string(),
+            }
+        } else {
+            self.extract_signature(node, code)
+        };
+
+        // An unnamed companion object is called `Companion`, as in Kotlin itself
         let object_name = if let Some(name) = object_name {
             name
+        } else if is_companion {
+            "Companion".to_string()
         } else {
             return;
         };
@@ -538,7 +584,6 @@ impl KotlinParser {
         let symbol_id = counter.next_id();
         let range = self.node_to_range(node);
         let visibility = self.determine_visibility(node, code);
-        let signature = self.extract_signature(node, code);
         let doc_comment = self.doc_comment_for(&node, code);
 
         let mut symbol = Symbol::new(
@@ -615,8 +660,14 @@ impl KotlinParser {
         let range = self.node_to_range(node);
         let doc_comment = self.doc_comment_for(&node, code);
 
+        // Extension functions count as methods of their receiver type, like members
+        let receiver = node.child_by_field_name("receiver");
+        if let Some(receiver) = receiver {
+            self.register_node(&receiver);
+        }
+
         // Determine if it's a method or top-level function
-        let kind = if context.is_in_class() {
+        let kind = if context.is_in_class() || receiver.is_some() {
             SymbolKind::Method
         } else {
             SymbolKind::Function
@@ -667,8 +718,7 @@ impl KotlinParser {
     /// This allows us to skip traversing bodies that only contain expressions
     #[inline]
     fn body_contains_declarations(&self, body: Node) -> bool {
-        let mut cursor = body.walk();
-        for child in body.children(&mut cursor) {
+        for child in recovered_children(body) {
             let kind = child.kind();
             // Quick check for common declaration types
             if matches!(
@@ -739,6 +789,152 @@ impl KotlinParser {
         symbols.push(symbol);
     }
 
+    /// Whether a class declaration carries the `data` modifier
+    fn is_data_class(&self, node: Node, code: &str) -> bool {
+        let mut cursor = node.walk();
+        node.children(&mut cursor)
+            .filter(|child| child.kind() == NODE_MODIFIERS)
+            .any(|modifiers| {
+                let mut mod_cursor = modifiers.walk();
+                modifiers.children(&mut mod_cursor).any(|modifier| {
+                    modifier.kind() == NODE_CLASS_MODIFIER
+                        && self.text_for_node(code, modifier).trim() == "data"
+                })
+            })
+    }
+
+    /// Extract `val`/`var` constructor parameters as fields
+    ///
+    /// For a data class, also adds the members the compiler generates from
+    /// those properties: `componentN`, `copy`, `equals`, `hashCode` and
+    /// `toString`. They have no source of their own, so they point at the
+    /// primary constructor.
+    #[allow(clippy::too_many_arguments)]
+    fn handle_primary_constructor(
+        &mut self,
+        node: Node,
+        code: &str,
+        file_id: FileId,
+        symbols: &mut Vec<Symbol>,
+        counter: &mut SymbolCounter,
+        class_name: &str,
+        is_data: bool,
+    ) {
+        self.register_node(&node);
+
+        // (name, type) of each property, in declaration order
+        let mut properties = Vec::new();
+        let mut cursor = node.walk();
+        for param in node.children(&mut cursor) {
+            if param.kind() != NODE_CLASS_PARAMETER {
+                continue;
+            }
+            self.register_node(&param);
+
+            let mut is_property = false;
+            let mut name = None;
+            let mut type_text = None;
+            let mut param_cursor = param.walk();
+            for child in param.children(&mut param_cursor) {
+                match child.kind() {
+                    NODE_BINDING_PATTERN_KIND => is_property = true,
+                    NODE_SIMPLE_IDENTIFIER if name.is_none() => {
+                        name = Some(self.text_for_node(code, child).trim());
+                    }
+                    NODE_USER_TYPE | "nullable_type" | "function_type" | "not_nullable_type"
+                    | "parenthesized_type"
+                        if type_text.is_none() =>
+                    {
+                        type_text = Some(self.text_for_node(code, child).trim());
+                    }
+                    _ => {}
+                }
+            }
+
+            let Some(name) = name else {
+                continue;
+            };
+            if !is_property {
+                continue;
+            }
+
+            let mut symbol = Symbol::new(
+                counter.next_id(),
+                name,
+                SymbolKind::Field,
+                file_id,
+                self.node_to_range(param),
+            );
+            symbol.visibility = self.determine_visibility(param, code);
+            symbol.signature = Some(self.text_for_node(code, param).trim().into());
+            symbols.push(symbol);
+
+            properties.push((name, type_text.unwrap_or("Any")));
+        }
+
+        if !is_data {
+            return;
+        }
+
+        let mut generated: Vec<(String, String)> = properties
+            .iter()
+            .enumerate()
+            .map(|(i, (_, ty))| {
+                let name = format!("component{}", i + 1);
+                let signature = format!("operator fun {name}(): {ty}");
+                (name, signature)
+            })
+            .collect();
+        let copy_params: Vec<String> = properties
+            .iter()
+            .map(|(name, ty)| format!("{name}: {ty} = this.{name}"))
+            .collect();
+        generated.push((
+            "copy".to_string(),
+            format!("fun copy({}): {class_name}", copy_params.join(", ")),
+        ));
+        generated.push((
+            "equals".to_string(),
+            "override fun equals(other: Any?): Boolean".to_string(),
+        ));
+        generated.push((
+            "hashCode".to_string(),
+            "override fun hashCode(): Int".to_string(),
+        ));
+        generated.push((
+            "toString".to_string(),
+            "override fun toString(): String".to_string(),
+        ));
+
+        let range = self.node_to_range(node);
+        for (name, signature) in generated {
+            let mut symbol = Symbol::new(
+                counter.next_id(),
+                name.as_str(),
+                SymbolKind::Method,
+                file_id,
+                range,
+            );
+            symbol.signature = Some(signature.into());
+            symbol.doc_comment = Some(format!("Generated for data class {class_name}").into());
+            symbols.push(symbol);
+        }
+    }
+
+    /// Name of the type an extension function extends (`List` for `List<T>.foo`)
+    fn receiver_type_name<'a>(&self, receiver: Node, code: &'a str) -> Option<&'a str> {
+        if receiver.kind() == NODE_TYPE_IDENTIFIER {
+            return Some(self.text_for_node(code, receiver).trim());
+        }
+        let mut cursor = receiver.walk();
+        for child in receiver.children(&mut cursor) {
+            if let Some(name) = self.receiver_type_name(child, code) {
+                return Some(name);
+            }
+        }
+        None
+    }
+
     fn handle_secondary_constructor(
         &mut self,
         node: Node,
@@ -1140,6 +1336,24 @@ impl KotlinParser {
                     }
                 }
             }
+            NODE_FUNCTION_DECLARATION => {
+                // Extension functions are defined on their receiver type; member
+                // extensions are already defined by the enclosing class
+                let is_member = node.parent().is_some_and(|p| p.kind() == NODE_CLASS_BODY);
+                if !is_member {
+                    if let Some(receiver) = node.child_by_field_name("receiver") {
+                        let receiver_name = self.receiver_type_name(receiver, code);
+                        let mut cursor = node.walk();
+                        let func_name = node
+                            .children(&mut cursor)
+                            .find(|child| child.kind() == NODE_SIMPLE_IDENTIFIER)
+                            .map(|child| self.text_for_node(code, child).trim());
+                        if let (Some(receiver_name), Some(func_name)) = (receiver_name, func_name) {
+                            defines.push((receiver_name, func_name, self.node_to_range(node)));
+                        }
+                    }
+                }
+            }
             _ => {}
         }
 
@@ -1163,7 +1377,7 @@ impl LanguageParser for KotlinParser {
         file_id: FileId,
         symbol_counter: &mut SymbolCounter,
     ) -> Vec<Symbol> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -1206,7 +1420,7 @@ impl LanguageParser for KotlinParser {
     }
 
     fn find_calls<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -1223,7 +1437,7 @@ impl LanguageParser for KotlinParser {
     }
 
     fn find_extends<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -1234,7 +1448,7 @@ impl LanguageParser for KotlinParser {
     }
 
     fn find_uses<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -1245,7 +1459,7 @@ impl LanguageParser for KotlinParser {
     }
 
     fn find_defines<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -1256,7 +1470,7 @@ impl LanguageParser for KotlinParser {
     }
 
     fn find_imports(&mut self, code: &str, file_id: FileId) -> Vec<Import> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -57,6 +57,7 @@
 //! 3. Register both in `ParserFactory`
 //! 4. (Future) Register in the language registry for auto-discovery
 
+use crate::indexing::EntryPointCategory;
 use crate::parsing::resolution::{
     GenericInheritanceResolver, GenericResolutionContext, ImportBinding, ImportOrigin,
     InheritanceResolver, ResolutionScope, ScopeLevel,
@@ -65,7 +66,7 @@ use crate::relationship::RelationKind;
 use crate::storage::DocumentIndex;
 use crate::{FileId, IndexError, IndexResult, Symbol, SymbolId, Visibility};
 use std::path::{Path, PathBuf};
-use tree_sitter::Language;
+use tree_sitter::{Language, Node, Point};
 
 /// Debug macro honoring global settings debug flag
 macro_rules! debug_global {
@@ -130,6 +131,12 @@ pub trait LanguageBehavior: Send + Sync {
         false
     }
 
+    /// Whether the parser tags type uses with where the type appears
+    /// (parameter, return, field), see `LanguageParser::find_uses_with_position`
+    fn records_type_use_positions(&self) -> bool {
+        false
+    }
+
     /// Get the tree-sitter Language for ABI-15 metadata access
     fn get_language(&self) -> Language;
 
@@ -153,6 +160,59 @@ pub trait LanguageBehavior: Send + Sync {
         name.to_string()
     }
 
+    /// Classify `symbol` as a likely entry point (`retrieve entrypoints`)
+    ///
+    /// `attributes` are the attribute or decorator lines attached to the
+    /// symbol, trimmed and in source order. Default: a function named `main`.
+    fn entry_point_category(
+        &self,
+        symbol: &Symbol,
+        _attributes: &[&str],
+    ) -> Option<EntryPointCategory> {
+        (symbol.kind == crate::SymbolKind::Function && symbol.name.as_ref() == "main")
+            .then_some(EntryPointCategory::Main)
+    }
+
+    /// Grammar node kinds of this language's attributes, decorators or
+    /// annotations, such as Rust's `attribute_item`. Default: none.
+    fn attribute_node_kinds(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Record the attributes attached to each of `symbols`, parsed from `source`
+    ///
+    /// Attributes are the [`attribute_node_kinds`](Self::attribute_node_kinds)
+    /// nodes among the symbol node's children (directly or inside a modifier
+    /// list) and right before it, comments aside. Each is stored as its source
+    /// text with whitespace collapsed, so multi-line forms such as
+    /// `#[derive(\n Debug,\n)]` become one line.
+    fn attach_attributes(&self, source: &str, symbols: &mut [Symbol]) {
+        let kinds = self.attribute_node_kinds();
+        if kinds.is_empty() || symbols.is_empty() {
+            return;
+        }
+        let mut parser = tree_sitter::Parser::new();
+        if parser.set_language(&self.get_language()).is_err() {
+            return;
+        }
+        let Some(tree) = crate::parsing::parser::parse_tree(&mut parser, source) else {
+            return;
+        };
+        for symbol in symbols {
+            symbol.attributes = symbol_attribute_nodes(tree.root_node(), symbol, kinds)
+                .into_iter()
+                .filter_map(|node| node.utf8_text(source.as_bytes()).ok())
+                .map(|text| text.split_whitespace().collect::<Vec<_>>().join(" ").into())
+                .collect();
+        }
+    }
+
+    /// Line (0-based) of a file-level entry point in `source`, such as
+    /// Python's `if __name__ == "__main__":` block. Default: none.
+    fn script_entry_line(&self, _source: &str) -> Option<usize> {
+        None
+    }
+
     /// Configure a symbol with language-specific rules
     ///
     /// This is the main entry point for applying language-specific
@@ -996,6 +1056,71 @@ impl LanguageMetadata {
     }
 }
 
+/// Attribute nodes of `kinds` attached to the node spanning `symbol`, in
+/// source order (see [`LanguageBehavior::attach_attributes`])
+fn symbol_attribute_nodes<'tree>(
+    root: Node<'tree>,
+    symbol: &Symbol,
+    kinds: &[&str],
+) -> Vec<Node<'tree>> {
+    let point = |row: u32, column: u16| Point::new(row as usize, column as usize);
+    let range = &symbol.range;
+    let Some(mut node) = root.descendant_for_point_range(
+        point(range.start_line, range.start_column),
+        point(range.end_line, range.end_column),
+    ) else {
+        return Vec::new();
+    };
+    // Symbols ranged over their name or declarator stand for the declaration
+    while node.kind().ends_with("identifier")
+        || node.kind().ends_with("declarator")
+        || matches!(node.kind(), "name" | "variable_declaration")
+    {
+        match node.parent() {
+            Some(parent) => node = parent,
+            None => return Vec::new(),
+        }
+    }
+
+    // The attributes a node is, or holds as a modifier list
+    let attributes_of = |node: Node<'tree>| -> Option<Vec<Node<'tree>>> {
+        if kinds.contains(&node.kind()) {
+            return Some(vec![node]);
+        }
+        matches!(node.kind(), "modifiers" | "attribute_list" | "annotations").then(|| {
+            let mut cursor = node.walk();
+            node.children(&mut cursor)
+                .filter(|child| kinds.contains(&child.kind()))
+                .collect()
+        })
+    };
+
+    // Preceding siblings, skipping comments and keywords such as `export`
+    let mut preceding = Vec::new();
+    let mut sibling = node.prev_sibling();
+    while let Some(previous) = sibling {
+        match attributes_of(previous) {
+            Some(attributes) => preceding.push(attributes),
+            None if previous.is_named() && !previous.is_extra() => break,
+            None => {}
+        }
+        sibling = previous.prev_sibling();
+    }
+
+    let mut cursor = node.walk();
+    let within: Vec<_> = node
+        .children(&mut cursor)
+        .filter_map(attributes_of)
+        .flatten()
+        .collect();
+    preceding
+        .into_iter()
+        .rev()
+        .flatten()
+        .chain(within)
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
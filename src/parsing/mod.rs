@@ -20,7 +20,7 @@ pub mod rust;
 pub mod typescript;
 
 pub use c::{CBehavior, CParser};
-pub use context::{ParserContext, ScopeType};
+pub use context::{ParserContext, ScopeType, assign_parents};
 pub use cpp::{CppBehavior, CppParser};
 pub use csharp::{CSharpBehavior, CSharpParser};
 pub use factory::{ParserFactory, ParserWithBehavior};
@@ -32,8 +32,8 @@ pub use language::Language;
 pub use language_behavior::{LanguageBehavior, LanguageMetadata};
 pub use method_call::MethodCall;
 pub use parser::{
-    HandledNode, LanguageParser, NodeTracker, NodeTrackingState, safe_substring_window,
-    safe_truncate_str, truncate_for_display,
+    HandledNode, LanguageParser, NodeTracker, NodeTrackingState, ParseDeadline, PositionedUse,
+    format_constant_value, safe_substring_window, safe_truncate_str, truncate_for_display,
 };
 pub use php::{PhpBehavior, PhpParser};
 pub use python::{PythonBehavior, PythonParser};
@@ -4,11 +4,31 @@
 //! must implement to work with the indexing system.
 
 use crate::parsing::method_call::MethodCall;
+use crate::relationship::TypeUsePosition;
 use crate::types::SymbolCounter;
 use crate::{FileId, Range, Symbol};
 use std::any::Any;
+use std::cell::Cell;
 use std::collections::HashSet;
-use tree_sitter::Node;
+use std::time::{Duration, Instant};
+use tree_sitter::{Node, ParseOptions, ParseState, Parser, Tree};
+
+/// A type use with where the type appears, see
+/// [`LanguageParser::find_uses_with_position`]
+pub type PositionedUse<'a> = (&'a str, &'a str, Range, Option<TypeUsePosition>);
+
+/// Move the type uses in `found` to `uses`, tagged with `position`
+pub fn push_positioned<'a>(
+    uses: &mut Vec<PositionedUse<'a>>,
+    found: &mut Vec<(&'a str, &'a str, Range)>,
+    position: Option<TypeUsePosition>,
+) {
+    uses.extend(
+        found
+            .drain(..)
+            .map(|(context, used_type, range)| (context, used_type, range, position)),
+    );
+}
 
 /// Common interface for all language parsers
 pub trait LanguageParser: Send + Sync {
@@ -73,6 +93,18 @@ pub trait LanguageParser: Send + Sync {
     /// Zero-cost: Returns string slices into the source code
     fn find_uses<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)>;
 
+    /// Find type usage like [`Self::find_uses`], with where each type appears
+    ///
+    /// Returns tuples of (context_name, used_type, range, position). The
+    /// default reports no positions; languages that can tell parameters,
+    /// returns, fields and locals apart override it.
+    fn find_uses_with_position<'a>(&mut self, code: &'a str) -> Vec<PositionedUse<'a>> {
+        self.find_uses(code)
+            .into_iter()
+            .map(|(context, used_type, range)| (context, used_type, range, None))
+            .collect()
+    }
+
     /// Find method definitions (in traits/interfaces or types)
     ///
     /// Returns tuples of (definer_name, method_name, range)
@@ -106,6 +138,34 @@ pub trait LanguageParser: Send + Sync {
     fn find_inherent_methods(&mut self, _code: &str) -> Vec<(String, String, Range)> {
         Vec::new()
     }
+
+    /// Find methods defined in trait implementation blocks
+    /// Returns tuples of (type_name, trait_name, method_name, range)
+    ///
+    /// Complements `find_inherent_methods` so each method can be linked both to
+    /// the type that owns it and to the trait it implements.
+    /// Default implementation returns empty - languages can override.
+    fn find_trait_impl_methods(&mut self, _code: &str) -> Vec<(String, String, String, Range)> {
+        Vec::new()
+    }
+
+    /// Find trait bounds on generic type parameters (`<T: Bound>`, `where T: Bound`)
+    /// Returns tuples of (parameter_name, bound_name, parameter_range)
+    ///
+    /// The range is the parameter's declaration, so bounds can be attached to
+    /// the parameter symbol. Default implementation returns empty.
+    fn find_type_parameter_bounds(&mut self, _code: &str) -> Vec<(String, String, Range)> {
+        Vec::new()
+    }
+
+    /// Find traits a type gets from derive macros (Rust `#[derive(...)]`)
+    /// Returns tuples of (type_name, trait_name, attribute_range)
+    ///
+    /// The generated impls are not in source, so these are recorded as
+    /// `Implements` edges marked as derived. Default implementation returns empty.
+    fn find_derives<'a>(&mut self, _code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
+        Vec::new()
+    }
 }
 
 /// Trait for creating language parsers
@@ -212,7 +272,8 @@ pub const MAX_AST_DEPTH: usize = 500;
 ///
 /// # Returns
 ///
-/// `true` if depth is safe to continue, `false` if limit exceeded
+/// `true` if depth is safe to continue, `false` if limit exceeded or the
+/// current [`ParseDeadline`] has passed
 ///
 /// # Example
 ///
@@ -241,6 +302,9 @@ pub const MAX_AST_DEPTH: usize = 500;
 /// ```
 #[inline]
 pub fn check_recursion_depth(depth: usize, node: Node) -> bool {
+    if deadline_passed() {
+        return false;
+    }
     if depth > MAX_AST_DEPTH {
         if crate::config::is_global_debug_enabled() {
             eprintln!(
@@ -255,6 +319,87 @@ pub fn check_recursion_depth(depth: usize, node: Node) -> bool {
     true
 }
 
+thread_local! {
+    /// End of the [`ParseDeadline`] active on this thread
+    static PARSE_DEADLINE: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// Time limit for the parse running on the current thread
+///
+/// While the guard is alive, [`parse_tree`] cancels tree-sitter once the
+/// deadline passes and [`check_recursion_depth`] stops symbol traversal, so
+/// `LanguageParser::parse` and the `find_*` passes return early with partial
+/// results. Callers check [`ParseDeadline::expired`] afterwards and discard them.
+pub struct ParseDeadline {
+    previous: Option<Instant>,
+}
+
+impl ParseDeadline {
+    /// Start a deadline `timeout` from now, replacing any active one until dropped
+    pub fn start(timeout: Duration) -> Self {
+        let previous = PARSE_DEADLINE.replace(Some(Instant::now() + timeout));
+        Self { previous }
+    }
+
+    /// Whether the deadline has passed
+    pub fn expired(&self) -> bool {
+        deadline_passed()
+    }
+}
+
+impl Drop for ParseDeadline {
+    fn drop(&mut self) {
+        PARSE_DEADLINE.set(self.previous);
+    }
+}
+
+fn deadline_passed() -> bool {
+    PARSE_DEADLINE
+        .get()
+        .is_some_and(|deadline| Instant::now() >= deadline)
+}
+
+/// Parse `code` with tree-sitter, giving up at the current [`ParseDeadline`]
+///
+/// Returns `None` when the deadline passed; the parser is reset so its next
+/// parse starts from scratch instead of resuming the cancelled one.
+pub fn parse_tree(parser: &mut Parser, code: &str) -> Option<Tree> {
+    if PARSE_DEADLINE.get().is_none() {
+        return parser.parse(code, None);
+    }
+
+    let bytes = code.as_bytes();
+    let mut past_deadline = |_: &ParseState| deadline_passed();
+    let tree = parser.parse_with_options(
+        &mut |offset, _| bytes.get(offset..).unwrap_or_default(),
+        None,
+        Some(ParseOptions::new().progress_callback(&mut past_deadline)),
+    );
+    if tree.is_none() {
+        parser.reset();
+    }
+    tree
+}
+
+/// Children of a node, with `ERROR` nodes replaced by their own children.
+///
+/// Tree-sitter's error recovery can wrap the items following a syntax error
+/// in an `ERROR` node. Loops that pick members out of a body by node kind
+/// would pass over that node and lose every item in it; iterating the
+/// recovered children instead skips the error itself and keeps visiting the
+/// valid siblings, so one broken member doesn't drop the rest.
+pub fn recovered_children(node: Node) -> Vec<Node> {
+    let mut children = Vec::new();
+    for child in node.children(&mut node.walk()) {
+        if child.is_error() {
+            children.extend(recovered_children(child));
+        } else {
+            children.push(child);
+        }
+    }
+    children
+}
+
 /// Safely extract a substring window from source code, respecting UTF-8 boundaries.
 ///
 /// This function creates a window of up to `window_size` bytes before the `end_byte` position,
@@ -309,10 +454,48 @@ pub fn truncate_for_display(s: &str, max_bytes: usize) -> String {
     }
 }
 
+/// Maximum length of a constant's initializer kept in its signature
+pub const MAX_CONSTANT_VALUE_LEN: usize = 80;
+
+/// Format a constant initializer for display in a signature.
+///
+/// Simple literals (numbers, strings, booleans) come through unchanged.
+/// Computed or multi-line initializers are collapsed onto one line and
+/// truncated to [`MAX_CONSTANT_VALUE_LEN`] bytes.
+pub fn format_constant_value(value: &str) -> String {
+    let value = value.trim();
+    if value.contains('\n') {
+        let collapsed = value.split_whitespace().collect::<Vec<_>>().join(" ");
+        return truncate_for_display(&collapsed, MAX_CONSTANT_VALUE_LEN);
+    }
+    truncate_for_display(value, MAX_CONSTANT_VALUE_LEN)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_tree_stops_at_deadline() {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_rust::LANGUAGE.into())
+            .unwrap();
+        let code: String = (0..20_000).map(|i| format!("fn f{i}() {{}}\n")).collect();
+
+        let deadline = ParseDeadline::start(Duration::ZERO);
+        assert!(deadline.expired());
+        assert!(parse_tree(&mut parser, &code).is_none());
+        let root = parser.parse("fn main() {}", None).unwrap().root_node();
+        assert!(!check_recursion_depth(0, root));
+        drop(deadline);
+
+        // Cleared with the guard, and the cancelled parse is not resumed
+        let tree = parse_tree(&mut parser, "fn main() {}").unwrap();
+        assert!(check_recursion_depth(0, tree.root_node()));
+        assert_eq!(tree.root_node().child_count(), 1);
+    }
+
     #[test]
     fn test_safe_truncate_with_emoji_panic() {
         // This test reproduces issue #29 - emoji at bytes 8-12
@@ -369,6 +552,24 @@ mod tests {
         assert_eq!(result, "Short");
     }
 
+    #[test]
+    fn test_format_constant_value() {
+        assert_eq!(format_constant_value("20000"), "20000");
+        assert_eq!(
+            format_constant_value("\"api.example.com\""),
+            "\"api.example.com\""
+        );
+        assert_eq!(format_constant_value(" true "), "true");
+
+        let multi_line = "[\n    1,\n    2,\n]";
+        assert_eq!(format_constant_value(multi_line), "[ 1, 2, ]");
+
+        let long = format!("compute({})", "x".repeat(200));
+        let formatted = format_constant_value(&long);
+        assert!(formatted.ends_with("..."));
+        assert_eq!(formatted.len(), MAX_CONSTANT_VALUE_LEN + 3);
+    }
+
     #[test]
     fn test_issue_29_exact_case() {
         // Exact case from issue #29
@@ -10,7 +10,9 @@
 //! version, verify compatibility with node type names used in this implementation.
 
 use crate::parsing::Import;
-use crate::parsing::parser::check_recursion_depth;
+use crate::parsing::parser::{
+    check_recursion_depth, format_constant_value, parse_tree, recovered_children,
+};
 use crate::parsing::{
     Language, LanguageParser, MethodCall, NodeTracker, NodeTrackingState, ParserContext, ScopeType,
 };
@@ -70,7 +72,7 @@ impl PhpParser {
         // Reset context for each file
         self.context = ParserContext::new();
 
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -129,7 +131,7 @@ impl PhpParser {
 
     #[cfg(test)]
     fn debug_parse(&mut self, code: &str) {
-        let tree = self.parser.parse(code, None).unwrap();
+        let tree = parse_tree(&mut self.parser, code).unwrap();
         let root = tree.root_node();
         eprintln!("=== PHP Parse Debug ===");
         self.debug_node(root, code, 0);
@@ -371,7 +373,7 @@ impl PhpParser {
 
                             // Try to get the value (third child after name and =)
                             if let Some(value_node) = node.child(2) {
-                                let value = &code[value_node.byte_range()];
+                                let value = format_constant_value(&code[value_node.byte_range()]);
                                 symbol.signature = Some(format!("const {name} = {value}").into());
                             }
 
@@ -789,7 +791,7 @@ impl PhpParser {
                         name_str = arg_text.trim_matches('"').trim_matches('\'').to_string();
                     } else if arg_count == 1 {
                         // Second argument is the value
-                        value_str = arg_text.to_string();
+                        value_str = format_constant_value(arg_text);
                     }
                     arg_count += 1;
                 }
@@ -923,7 +925,7 @@ impl LanguageParser for PhpParser {
     }
 
     fn find_calls<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -941,7 +943,7 @@ impl LanguageParser for PhpParser {
     }
 
     fn find_implementations<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -952,7 +954,7 @@ impl LanguageParser for PhpParser {
     }
 
     fn find_uses<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -963,7 +965,7 @@ impl LanguageParser for PhpParser {
     }
 
     fn find_defines<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -974,7 +976,7 @@ impl LanguageParser for PhpParser {
     }
 
     fn find_imports(&mut self, code: &str, file_id: FileId) -> Vec<Import> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -989,7 +991,7 @@ impl LanguageParser for PhpParser {
     }
 
     fn find_variable_types<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -1148,8 +1150,7 @@ impl PhpParser {
                     for child in node.children(&mut cursor) {
                         if child.kind() == "declaration_list" {
                             // Methods are inside declaration_list, not direct children
-                            let mut decl_cursor = child.walk();
-                            for decl_child in child.children(&mut decl_cursor) {
+                            for decl_child in recovered_children(child) {
                                 if decl_child.kind() == "method_declaration" {
                                     if let Some(method_name_node) =
                                         decl_child.child_by_field_name("name")
@@ -1,5 +1,7 @@
 //! Python-specific language behavior implementation
 
+use crate::indexing::EntryPointCategory;
+use crate::indexing::entrypoints::{HTTP_VERBS, attribute_path};
 use crate::parsing::LanguageBehavior;
 use crate::parsing::ResolutionScope;
 use crate::parsing::behavior_state::{BehaviorState, StatefulBehavior};
@@ -101,6 +103,35 @@ impl LanguageBehavior for PythonBehavior {
             symbol.visibility = self.parse_visibility(sig);
         }
     }
+
+    fn entry_point_category(
+        &self,
+        symbol: &crate::Symbol,
+        attributes: &[&str],
+    ) -> Option<EntryPointCategory> {
+        if symbol.kind != crate::types::SymbolKind::Function {
+            return None;
+        }
+        // Route decorators of Flask and FastAPI: @app.route("/"), @router.get("/")
+        let routed = attributes.iter().any(|attribute| {
+            attribute_path(attribute)
+                .rsplit_once('.')
+                .is_some_and(|(_, verb)| HTTP_VERBS.contains(&verb))
+        });
+        if routed {
+            return Some(EntryPointCategory::HttpHandler);
+        }
+        (symbol.name.as_ref() == "main").then_some(EntryPointCategory::Main)
+    }
+
+    fn script_entry_line(&self, source: &str) -> Option<usize> {
+        source.lines().position(|line| {
+            let compact: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+            compact.starts_with("if__name__==\"__main__\"")
+                || compact.starts_with("if__name__=='__main__'")
+        })
+    }
+
     fn create_resolution_context(&self, file_id: FileId) -> Box<dyn ResolutionScope> {
         Box::new(crate::parsing::python::PythonResolutionContext::new(
             file_id,
@@ -156,6 +187,10 @@ impl LanguageBehavior for PythonBehavior {
         self.language.clone()
     }
 
+    fn attribute_node_kinds(&self) -> &'static [&'static str] {
+        &["decorator"]
+    }
+
     fn normalize_caller_name(&self, name: &str, file_id: FileId) -> String {
         if name == "<module>" {
             if let Some(module_path) = self.get_module_path_for_file(file_id) {
@@ -689,6 +724,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_entry_points() {
+        use crate::types::{Range, SymbolKind};
+        use crate::{FileId, Symbol, SymbolId};
+
+        let behavior = PythonBehavior::new();
+        let function = |name: &str| {
+            Symbol::new(
+                SymbolId::new(1).unwrap(),
+                name,
+                SymbolKind::Function,
+                FileId::new(1).unwrap(),
+                Range::new(0, 0, 1, 0),
+            )
+        };
+
+        assert_eq!(
+            behavior.entry_point_category(&function("index"), &["@app.route(\"/\")"]),
+            Some(EntryPointCategory::HttpHandler)
+        );
+        assert_eq!(
+            behavior.entry_point_category(&function("cached"), &["@functools.cache"]),
+            None
+        );
+        assert_eq!(
+            behavior.entry_point_category(&function("main"), &[]),
+            Some(EntryPointCategory::Main)
+        );
+
+        let script =
+            "import sys\n\ndef main():\n    pass\n\nif __name__ == '__main__':\n    main()\n";
+        assert_eq!(behavior.script_entry_line(script), Some(5));
+        assert_eq!(behavior.script_entry_line("def main():\n    pass\n"), None);
+    }
+
     #[test]
     fn test_module_separator() {
         let behavior = PythonBehavior::new();
@@ -12,7 +12,9 @@
 //! verify compatibility with node type names used in this implementation.
 
 use crate::parsing::Import;
-use crate::parsing::parser::check_recursion_depth;
+use crate::parsing::parser::{
+    check_recursion_depth, format_constant_value, parse_tree, recovered_children,
+};
 use crate::parsing::{
     HandledNode, Language, LanguageParser, MethodCall, NodeTracker, NodeTrackingState,
     ParserContext, ScopeType,
@@ -70,7 +72,7 @@ impl PythonParser {
         file_id: FileId,
         symbol_counter: &mut SymbolCounter,
     ) -> Vec<Symbol> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -406,6 +408,32 @@ impl PythonParser {
         // Handle simple identifier assignments (not tuple unpacking for now)
         if left.kind() == "identifier" {
             let name = &code[left.byte_range()];
+
+            // Lambdas bound to a name are callable: index them as functions
+            if let Some(lambda) = node
+                .child_by_field_name("right")
+                .filter(|right| right.kind() == "lambda")
+            {
+                let head_end = lambda
+                    .child_by_field_name("body")
+                    .map(|body| body.start_byte())
+                    .unwrap_or(lambda.end_byte());
+                let head = code[lambda.start_byte()..head_end]
+                    .trim_end()
+                    .trim_end_matches(':');
+
+                let mut symbol = Symbol::new(
+                    counter.next_id(),
+                    name,
+                    SymbolKind::Function,
+                    file_id,
+                    self.node_to_range(lambda),
+                );
+                symbol.scope_context = Some(context.current_scope_context());
+                symbol.signature = Some(format!("{name} = {head}").into());
+                return Some(symbol);
+            }
+
             let range = self.node_to_range(node);
             let symbol_id = counter.next_id();
 
@@ -427,8 +455,13 @@ impl PythonParser {
             // Try to extract the value as a simple signature
             if let Some(right) = node.child_by_field_name("right") {
                 let value_preview = &code[right.byte_range()];
-                // Store full signature for semantic quality
-                symbol.signature = Some(format!("{name} = {value_preview}").into());
+                // Store full signature for semantic quality; constants keep a
+                // shortened value so config-like literals stay readable
+                symbol.signature = Some(if kind == SymbolKind::Constant {
+                    format!("{name} = {}", format_constant_value(value_preview)).into()
+                } else {
+                    format!("{name} = {value_preview}").into()
+                });
             }
 
             return Some(symbol);
@@ -1105,87 +1138,66 @@ impl PythonParser {
     }
 
     /// Find class inheritance relationships in AST node recursively
-    fn find_implementations_in_node<'a>(
+    fn find_extends_in_node<'a>(
         &self,
         node: Node,
         code: &'a str,
-        implementations: &mut Vec<(&'a str, &'a str, Range)>,
+        extends: &mut Vec<(&'a str, &'a str, Range)>,
     ) {
-        match node.kind() {
-            "class_definition" => {
-                self.process_class_inheritance(node, code, implementations);
-                // Continue processing children for nested classes
-                self.process_children_for_implementations(node, code, implementations);
-            }
-            _ => {
-                self.process_children_for_implementations(node, code, implementations);
-            }
+        if node.kind() == "class_definition" {
+            self.process_class_inheritance(node, code, extends);
+        }
+        // Continue processing children for nested classes
+        for child in node.children(&mut node.walk()) {
+            self.find_extends_in_node(child, code, extends);
         }
     }
 
     /// Process class definition for inheritance relationships
+    ///
+    /// Bases are pushed in declaration order, each with its own range, so the
+    /// order the method resolution order starts from survives indexing.
     fn process_class_inheritance<'a>(
         &self,
         node: Node,
         code: &'a str,
-        implementations: &mut Vec<(&'a str, &'a str, Range)>,
+        extends: &mut Vec<(&'a str, &'a str, Range)>,
     ) {
         if let Some(class_name) = self.extract_class_name(node, code) {
-            let range = self.node_to_range(node);
-            let base_classes = self.extract_base_classes(node, code);
-
-            for base_class in base_classes {
-                implementations.push((class_name, base_class, range));
+            for base_class in self.extract_base_classes(node) {
+                extends.push((
+                    class_name,
+                    &code[base_class.byte_range()],
+                    self.node_to_range(base_class),
+                ));
             }
         }
     }
 
-    /// Extract base class names from class definition
-    fn extract_base_classes<'a>(&self, node: Node, code: &'a str) -> Vec<&'a str> {
-        let mut base_classes = Vec::new();
-
-        // Look for argument_list node which contains the base classes
-        if let Some(superclasses_node) = node.child_by_field_name("superclasses") {
-            Self::extract_base_class_names(superclasses_node, code, &mut base_classes);
-        }
-
-        base_classes
-    }
-
-    /// Extract individual base class names from superclasses list
-    fn extract_base_class_names<'a>(node: Node, code: &'a str, base_classes: &mut Vec<&'a str>) {
-        for child in node.children(&mut node.walk()) {
-            match child.kind() {
-                "identifier" => {
-                    // Simple base class: class Dog(Animal)
-                    base_classes.push(&code[child.byte_range()]);
-                }
-                "attribute" => {
-                    // Qualified base class: class Child(parent.Base)
-                    base_classes.push(&code[child.byte_range()]);
-                }
-                "argument_list" => {
-                    // Nested argument list - recurse
-                    Self::extract_base_class_names(child, code, base_classes);
-                }
-                _ => {
-                    // Continue processing children for other node types
-                    Self::extract_base_class_names(child, code, base_classes);
-                }
-            }
-        }
-    }
+    /// Extract base class nodes from class definition
+    ///
+    /// `class Child(Base, pkg.Mixin, Generic[T], metaclass=ABCMeta)` yields
+    /// `Base`, `pkg.Mixin` and `Generic`; keyword arguments and `*bases`
+    /// splats are not base classes.
+    fn extract_base_classes<'t>(&self, node: Node<'t>) -> Vec<Node<'t>> {
+        let Some(superclasses) = node.child_by_field_name("superclasses") else {
+            return Vec::new();
+        };
 
-    /// Process child nodes for inheritance detection
-    fn process_children_for_implementations<'a>(
-        &self,
-        node: Node,
-        code: &'a str,
-        implementations: &mut Vec<(&'a str, &'a str, Range)>,
-    ) {
-        for child in node.children(&mut node.walk()) {
-            self.find_implementations_in_node(child, code, implementations);
-        }
+        let mut cursor = superclasses.walk();
+        superclasses
+            .named_children(&mut cursor)
+            .filter_map(|base| match base.kind() {
+                // Simple base class: class Dog(Animal)
+                // Qualified base class: class Child(parent.Base)
+                "identifier" | "attribute" => Some(base),
+                // Parameterized base class: class Stack(Generic[T])
+                "subscript" => base
+                    .child_by_field_name("value")
+                    .filter(|value| matches!(value.kind(), "identifier" | "attribute")),
+                _ => None,
+            })
+            .collect()
     }
 
     /// Find variable type annotations in AST node recursively
@@ -1271,7 +1283,7 @@ impl PythonParser {
 
                     // Find all methods defined in this class
                     if let Some(body) = node.child_by_field_name("body") {
-                        for child in body.children(&mut body.walk()) {
+                        for child in recovered_children(body) {
                             if child.kind() == "function_definition" {
                                 if let Some(method_name_node) = child.child_by_field_name("name") {
                                     let method_name = &code[method_name_node.byte_range()];
@@ -1346,7 +1358,7 @@ impl LanguageParser for PythonParser {
     }
 
     fn find_calls<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -1360,7 +1372,7 @@ impl LanguageParser for PythonParser {
     }
 
     fn find_method_calls(&mut self, code: &str) -> Vec<MethodCall> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -1373,17 +1385,22 @@ impl LanguageParser for PythonParser {
         method_calls
     }
 
-    fn find_implementations<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+    fn find_implementations<'a>(&mut self, _code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
+        // Python has no interfaces; base classes are reported by find_extends
+        Vec::new()
+    }
+
+    fn find_extends<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
 
         let root_node = tree.root_node();
-        let mut implementations = Vec::new();
+        let mut extends = Vec::new();
 
-        self.find_implementations_in_node(root_node, code, &mut implementations);
-        implementations
+        self.find_extends_in_node(root_node, code, &mut extends);
+        extends
     }
 
     fn find_uses<'a>(&mut self, _code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
@@ -1392,7 +1409,7 @@ impl LanguageParser for PythonParser {
     }
 
     fn find_defines<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -1405,7 +1422,7 @@ impl LanguageParser for PythonParser {
     }
 
     fn find_imports(&mut self, code: &str, file_id: FileId) -> Vec<Import> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -1418,7 +1435,7 @@ impl LanguageParser for PythonParser {
     }
 
     fn find_variable_types<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -2077,7 +2094,7 @@ class Dog(Animal):
 "#;
         println!("Finding class inheritance relationships...");
         println!("---");
-        let implementations = parser.find_implementations(code);
+        let implementations = parser.find_extends(code);
 
         assert_eq!(implementations.len(), 1);
         assert_eq!(implementations[0].0, "Dog");
@@ -2378,7 +2395,7 @@ class Duck(Animal, Flyable, Swimmable):
 "#;
         println!("Finding multiple inheritance...");
         println!("---");
-        let implementations = parser.find_implementations(code);
+        let implementations = parser.find_extends(code);
 
         assert!(
             implementations
@@ -2702,7 +2719,7 @@ async def process_batch(items: List[str]) -> Dict[str, Any]:
 class SimpleClass:
     pass
 "#;
-        let implementations1 = parser.find_implementations(code1);
+        let implementations1 = parser.find_extends(code1);
         assert_eq!(implementations1.len(), 0);
 
         // Qualified base class names
@@ -2710,7 +2727,7 @@ class SimpleClass:
 class Child(parent.Base):
     pass
 "#;
-        let implementations2 = parser.find_implementations(code2);
+        let implementations2 = parser.find_extends(code2);
         assert_eq!(implementations2.len(), 1);
         assert_eq!(implementations2[0].0, "Child");
         assert_eq!(implementations2[0].1, "parent.Base");
@@ -2720,7 +2737,7 @@ class Child(parent.Base):
 class Complex(SimpleBase, module.QualifiedBase, another.pkg.DeepBase):
     pass
 "#;
-        let implementations3 = parser.find_implementations(code3);
+        let implementations3 = parser.find_extends(code3);
         assert_eq!(implementations3.len(), 3);
         assert!(
             implementations3
@@ -2739,6 +2756,22 @@ class Complex(SimpleBase, module.QualifiedBase, another.pkg.DeepBase):
         );
     }
 
+    // Bases keep declaration order; keyword arguments are not bases
+    #[test]
+    fn test_base_class_order_and_keywords() {
+        let mut parser = PythonParser::new().unwrap();
+        let code = r#"
+class Stack(Base, mixins.Sized, Generic[T], metaclass=ABCMeta):
+    pass
+"#;
+        let extends = parser.find_extends(code);
+        let bases: Vec<&str> = extends.iter().map(|(_, base, _)| *base).collect();
+        assert_eq!(bases, vec!["Base", "mixins.Sized", "Generic"]);
+        assert!(extends.iter().all(|(class, _, _)| *class == "Stack"));
+        assert!(extends[0].2.start_column < extends[1].2.start_column);
+        assert!(parser.find_implementations(code).is_empty());
+    }
+
     // Test nested classes with inheritance
     #[test]
     fn test_nested_class_inheritance() {
@@ -2751,7 +2784,7 @@ class Outer:
     class AnotherInner(Outer.Inner):
         pass
 "#;
-        let implementations = parser.find_implementations(code);
+        let implementations = parser.find_extends(code);
 
         // Debug what we actually found
         println!("Found implementations:");
@@ -3143,4 +3176,64 @@ def process_data():
 
         println!("SUCCESS: Python now tracks cross-module calls correctly!");
     }
+
+    #[test]
+    fn test_named_lambdas() {
+        let code = r#"
+square = lambda x: x * x
+add = lambda a, b=1: a + b
+DEFAULT = 10
+
+def main():
+    return square(DEFAULT)
+
+items = sorted(values, key=lambda v: v.name)
+"#;
+
+        let mut parser = PythonParser::new().unwrap();
+        let file_id = FileId::new(1).unwrap();
+        let mut counter = SymbolCounter::new();
+        let symbols = parser.parse(code, file_id, &mut counter);
+
+        let square = symbols.iter().find(|s| &*s.name == "square").unwrap();
+        assert_eq!(square.kind, SymbolKind::Function);
+        assert_eq!(square.signature.as_deref(), Some("square = lambda x"));
+
+        let add = symbols.iter().find(|s| &*s.name == "add").unwrap();
+        assert_eq!(add.kind, SymbolKind::Function);
+        assert_eq!(add.signature.as_deref(), Some("add = lambda a, b=1"));
+
+        // Inline lambdas don't make the assigned name callable
+        let items = symbols.iter().find(|s| &*s.name == "items").unwrap();
+        assert_eq!(items.kind, SymbolKind::Variable);
+
+        let calls = parser.find_calls(code);
+        assert!(
+            calls
+                .iter()
+                .any(|(caller, callee, _)| *caller == "main" && *callee == "square")
+        );
+    }
+
+    #[test]
+    fn test_find_defines_recovers_after_syntax_errors() {
+        let mut parser = PythonParser::new().unwrap();
+        let code = r#"
+class Shape:
+    def area(self):
+        return 1
+
+    def broken(self):
+        return 1 + * (
+
+    def name(self):
+        return "shape"
+"#;
+
+        let defines = parser.find_defines(code);
+        let methods: Vec<&str> = defines.iter().map(|(_, method, _)| *method).collect();
+
+        assert!(methods.contains(&"area"), "missing area in {methods:?}");
+        assert!(methods.contains(&"name"), "missing name in {methods:?}");
+    }
 }
@@ -93,6 +93,17 @@ impl<'de> Deserialize<'de> for LanguageId {
     }
 }
 
+/// Serialized as its plain string key, like `"rust"`
+impl schemars::JsonSchema for LanguageId {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "LanguageId".into()
+    }
+
+    fn json_schema(_generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({ "type": "string" })
+    }
+}
+
 /// Registry errors with actionable suggestions
 #[derive(Error, Debug)]
 pub enum RegistryError {
@@ -3,6 +3,8 @@
 use super::resolution::{RustResolutionContext, RustTraitResolver};
 use crate::FileId;
 use crate::Visibility;
+use crate::indexing::EntryPointCategory;
+use crate::indexing::entrypoints::{HTTP_VERBS, attribute_path};
 use crate::parsing::behavior_state::{BehaviorState, StatefulBehavior};
 use crate::parsing::{InheritanceResolver, LanguageBehavior, ResolutionScope};
 use std::path::{Path, PathBuf};
@@ -69,6 +71,49 @@ impl LanguageBehavior for RustBehavior {
         "::"
     }
 
+    fn configure_symbol(&self, symbol: &mut crate::Symbol, module_path: Option<&str>) {
+        if let Some(path) = module_path {
+            let full_path = self.format_module_path(path, &symbol.name);
+            symbol.module_path = Some(full_path.into());
+        }
+
+        // Enum variants carry no visibility modifier of their own; keep the
+        // visibility the parser inherited from the enclosing enum
+        if symbol.kind == crate::SymbolKind::EnumVariant {
+            return;
+        }
+
+        if let Some(ref sig) = symbol.signature {
+            symbol.visibility = self.parse_visibility(sig);
+        }
+    }
+
+    fn entry_point_category(
+        &self,
+        symbol: &crate::Symbol,
+        attributes: &[&str],
+    ) -> Option<EntryPointCategory> {
+        if symbol.kind != crate::SymbolKind::Function {
+            return None;
+        }
+        for attribute in attributes {
+            let path = attribute_path(attribute);
+            let last = path.rsplit("::").next().unwrap_or(path);
+            if last == "no_mangle" || last == "export_name" {
+                return Some(EntryPointCategory::FfiExport);
+            }
+            // #[tokio::main], #[async_std::main], #[actix_web::main]
+            if last == "main" && path.contains("::") {
+                return Some(EntryPointCategory::Main);
+            }
+            // Route attributes of actix-web and rocket: #[get("/")]
+            if HTTP_VERBS.contains(&last) {
+                return Some(EntryPointCategory::HttpHandler);
+            }
+        }
+        (symbol.name.as_ref() == "main").then_some(EntryPointCategory::Main)
+    }
+
     fn supports_traits(&self) -> bool {
         true
     }
@@ -81,6 +126,14 @@ impl LanguageBehavior for RustBehavior {
         self.language.clone()
     }
 
+    fn records_type_use_positions(&self) -> bool {
+        true
+    }
+
+    fn attribute_node_kinds(&self) -> &'static [&'static str] {
+        &["attribute_item"]
+    }
+
     fn module_path_from_file(&self, file_path: &Path, project_root: &Path) -> Option<String> {
         // Get relative path from project root
         let relative_path = file_path.strip_prefix(project_root).ok()?;
@@ -135,7 +188,9 @@ impl LanguageBehavior for RustBehavior {
         if let Some(ref scope_context) = symbol.scope_context {
             match scope_context {
                 ScopeContext::Module | ScopeContext::Global | ScopeContext::Package => true,
-                ScopeContext::Local { .. } | ScopeContext::Parameter => false,
+                // Named closures and nested fns are callable from within their file
+                ScopeContext::Local { .. } => matches!(symbol.kind, SymbolKind::Function),
+                ScopeContext::Parameter => false,
                 ScopeContext::ClassMember => {
                     // Rust-specific: trait methods and impl methods should be resolvable
                     // even if they're private, for within-file resolution
@@ -546,6 +601,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_entry_point_category() {
+        use crate::{FileId, Range, Symbol, SymbolId, SymbolKind};
+
+        let behavior = RustBehavior::new();
+        let function = |name: &str| {
+            Symbol::new(
+                SymbolId::new(1).unwrap(),
+                name,
+                SymbolKind::Function,
+                FileId::new(1).unwrap(),
+                Range::new(0, 0, 1, 0),
+            )
+        };
+
+        assert_eq!(
+            behavior.entry_point_category(&function("main"), &[]),
+            Some(EntryPointCategory::Main)
+        );
+        assert_eq!(
+            behavior.entry_point_category(&function("run"), &["#[tokio::main]"]),
+            Some(EntryPointCategory::Main)
+        );
+        assert_eq!(
+            behavior.entry_point_category(&function("init"), &["#[unsafe(no_mangle)]"]),
+            Some(EntryPointCategory::FfiExport)
+        );
+        assert_eq!(
+            behavior.entry_point_category(&function("index"), &["#[get(\"/\")]"]),
+            Some(EntryPointCategory::HttpHandler)
+        );
+        assert_eq!(
+            behavior.entry_point_category(&function("helper"), &["#[inline]"]),
+            None
+        );
+    }
+
     #[test]
     fn test_module_separator() {
         let behavior = RustBehavior::new();
@@ -32,7 +32,10 @@ impl LanguageDefinition for RustLanguage {
     }
 
     fn create_parser(&self, settings: &Settings) -> IndexResult<Box<dyn LanguageParser>> {
-        let parser = RustParser::with_debug(settings.debug).map_err(crate::IndexError::General)?;
+        let parser = RustParser::with_debug(settings.debug)
+            .map_err(crate::IndexError::General)?
+            .with_type_parameters(settings.indexing.index_type_parameters)
+            .with_item_macros(settings.indexing.item_macro_kinds());
         Ok(Box::new(parser))
     }
 
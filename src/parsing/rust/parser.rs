@@ -32,12 +32,17 @@
 
 use crate::parsing::Import;
 use crate::parsing::method_call::MethodCall;
-use crate::parsing::parser::check_recursion_depth;
+use crate::parsing::parser::{
+    check_recursion_depth, format_constant_value, parse_tree, recovered_children,
+};
 use crate::parsing::{
-    HandledNode, Language, LanguageParser, NodeTracker, NodeTrackingState, ParserContext, ScopeType,
+    HandledNode, Language, LanguageParser, NodeTracker, NodeTrackingState, ParserContext,
+    PositionedUse, ScopeType,
 };
+use crate::relationship::TypeUsePosition;
 use crate::types::SymbolCounter;
-use crate::{FileId, Range, Symbol, SymbolKind};
+use crate::{FileId, Range, Symbol, SymbolId, SymbolKind};
+use std::collections::BTreeMap;
 use tree_sitter::{Node, Parser};
 
 /// Debug print macro that respects the debug setting
@@ -64,6 +69,12 @@ pub struct RustParser {
     debug: bool,
     context: ParserContext,
     node_tracker: NodeTrackingState,
+    /// Direct members of `impl` blocks with the implemented type's name
+    impl_members: Vec<(SymbolId, String)>,
+    /// Emit generic type parameters as `Parameter` symbols
+    index_type_parameters: bool,
+    /// Item-defining macros from `indexing.item_macros`, by macro name
+    item_macros: BTreeMap<String, SymbolKind>,
 }
 
 impl std::fmt::Debug for RustParser {
@@ -90,12 +101,28 @@ impl RustParser {
             debug,
             context: ParserContext::new(),
             node_tracker: NodeTrackingState::new(),
+            impl_members: Vec::new(),
+            index_type_parameters: false,
+            item_macros: BTreeMap::new(),
         })
     }
 
+    /// Emit generic type parameters (`<T: Bound>`) as `Parameter` symbols
+    /// nested in the item declaring them (`indexing.index_type_parameters`)
+    pub fn with_type_parameters(mut self, enabled: bool) -> Self {
+        self.index_type_parameters = enabled;
+        self
+    }
+
+    /// Index invocations of these macros as the symbol kind they map to
+    pub fn with_item_macros(mut self, item_macros: BTreeMap<String, SymbolKind>) -> Self {
+        self.item_macros = item_macros;
+        self
+    }
+
     /// Extract import statements from the code
     pub fn extract_imports(&mut self, code: &str, file_id: FileId) -> Vec<Import> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -284,7 +311,7 @@ impl RustParser {
         // Reset context for each file
         self.context = ParserContext::new();
 
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -293,11 +320,40 @@ impl RustParser {
         let mut symbols = Vec::new();
 
         // Walk the tree manually to find symbols
+        self.impl_members.clear();
         self.extract_symbols_from_node(root_node, code, file_id, &mut symbols, symbol_counter, 0);
+        self.link_impl_members(&mut symbols);
 
         symbols
     }
 
+    /// Make types declared in this file the parent of their `impl` members
+    ///
+    /// Impl blocks are not symbols, so range nesting cannot show that a
+    /// method belongs to its type.
+    fn link_impl_members(&mut self, symbols: &mut [Symbol]) {
+        for (member_id, type_name) in std::mem::take(&mut self.impl_members) {
+            let type_id = symbols
+                .iter()
+                .find(|s| {
+                    s.name.as_str() == type_name
+                        && matches!(
+                            s.kind,
+                            SymbolKind::Struct
+                                | SymbolKind::Enum
+                                | SymbolKind::Trait
+                                | SymbolKind::TypeAlias
+                        )
+                })
+                .map(|s| s.id);
+            if let (Some(type_id), Some(member)) =
+                (type_id, symbols.iter_mut().find(|s| s.id == member_id))
+            {
+                member.parent = Some(type_id);
+            }
+        }
+    }
+
     fn extract_symbols_from_node(
         &mut self,
         node: Node,
@@ -354,6 +410,7 @@ impl RustParser {
                         symbols.push(symbol);
                     }
                 }
+                self.extract_type_parameters(node, code, file_id, symbols, counter);
 
                 // Enter function scope for nested items
                 // Rust doesn't have hoisting like JS/TS
@@ -414,6 +471,7 @@ impl RustParser {
                         symbols.push(sym);
                     }
                 }
+                self.extract_type_parameters(node, code, file_id, symbols, counter);
 
                 // Structs can have nested items in Rust (though rare)
                 // Enter struct scope for potential nested items
@@ -428,7 +486,7 @@ impl RustParser {
 
                 // Process struct fields
                 if let Some(field_list) = node.child_by_field_name("body") {
-                    for child in field_list.children(&mut field_list.walk()) {
+                    for child in recovered_children(field_list) {
                         if child.kind() == "field_declaration" {
                             self.register_handled_node("field_declaration", child.kind_id());
                             if let Some(name_node) = child.child_by_field_name("name") {
@@ -470,6 +528,11 @@ impl RustParser {
             }
             "enum_item" => {
                 self.register_handled_node("enum_item", node.kind_id());
+                let enum_name = node
+                    .child_by_field_name("name")
+                    .map(|n| code[n.byte_range()].to_string());
+                let mut enum_visibility = crate::Visibility::Private;
+
                 if let Some(name_node) = node.child_by_field_name("name") {
                     let symbol = self.create_symbol(
                         counter,
@@ -484,13 +547,22 @@ impl RustParser {
                         // Extract and add enum signature
                         let signature = self.extract_enum_signature(node, code);
                         sym = sym.with_signature(signature);
+                        enum_visibility = sym.visibility;
                         symbols.push(sym);
                     }
                 }
+                self.extract_type_parameters(node, code, file_id, symbols, counter);
+
+                // Variants are members of the enum, like fields of a struct
+                self.context.enter_scope(ScopeType::Class);
+
+                let saved_function = self.context.current_function().map(|s| s.to_string());
+                let saved_class = self.context.current_class().map(|s| s.to_string());
+                self.context.set_current_class(enum_name);
 
                 // Process enum variants
                 if let Some(body) = node.child_by_field_name("body") {
-                    for child in body.children(&mut body.walk()) {
+                    for child in recovered_children(body) {
                         if child.kind() == "enum_variant" {
                             self.register_handled_node("enum_variant", child.kind_id());
                             if let Some(name_node) = child.child_by_field_name("name") {
@@ -498,16 +570,26 @@ impl RustParser {
                                     counter,
                                     child,
                                     name_node,
-                                    SymbolKind::Constant,
+                                    SymbolKind::EnumVariant,
                                     file_id,
                                     code,
                                 ) {
-                                    symbols.push(symbol);
+                                    // Variants share the visibility of their enum
+                                    let signature = code[child.byte_range()].trim().to_string();
+                                    symbols.push(
+                                        symbol
+                                            .with_signature(signature)
+                                            .with_visibility(enum_visibility),
+                                    );
                                 }
                             }
                         }
                     }
                 }
+
+                self.context.exit_scope();
+                self.context.set_current_function(saved_function);
+                self.context.set_current_class(saved_class);
             }
             "type_item" => {
                 self.register_handled_node("type_item", node.kind_id());
@@ -528,6 +610,7 @@ impl RustParser {
                         symbols.push(sym);
                     }
                 }
+                self.extract_type_parameters(node, code, file_id, symbols, counter);
             }
             "const_item" => {
                 self.register_handled_node("const_item", node.kind_id());
@@ -588,12 +671,13 @@ impl RustParser {
                         sym = sym.with_signature(signature);
                         symbols.push(sym);
                     }
+                    self.extract_type_parameters(node, code, file_id, symbols, counter);
 
                     // Enter trait scope for method signatures
                     self.context.enter_scope(ScopeType::Class); // Traits are like classes
                     // Also extract method signatures from the trait
                     if let Some(body) = node.child_by_field_name("body") {
-                        for child in body.children(&mut body.walk()) {
+                        for child in recovered_children(body) {
                             if child.kind() == "function_signature_item"
                                 || child.kind() == "function_item"
                             {
@@ -613,6 +697,9 @@ impl RustParser {
                                         method_symbol = method_symbol.with_signature(signature);
                                         symbols.push(method_symbol);
                                     }
+                                    self.extract_type_parameters(
+                                        child, code, file_id, symbols, counter,
+                                    );
                                 }
                             }
                         }
@@ -641,7 +728,9 @@ impl RustParser {
                     self.context.set_current_class(Some(type_name.to_string()));
                 }
 
-                // Process children
+                // Process children; `impl<T>` parameters belong to the type
+                let first_member = symbols.len();
+                self.extract_type_parameters(node, code, file_id, symbols, counter);
                 for child in node.children(&mut node.walk()) {
                     self.extract_symbols_from_node(
                         child,
@@ -653,6 +742,25 @@ impl RustParser {
                     );
                 }
 
+                // Remember direct members (not items nested in them) for the type
+                if let Some(type_name) = impl_type_name {
+                    let members = &symbols[first_member..];
+                    for member in members {
+                        let nested = members.iter().any(|outer| {
+                            outer.id != member.id
+                                && outer
+                                    .range
+                                    .contains(member.range.start_line, member.range.start_column)
+                                && outer
+                                    .range
+                                    .contains(member.range.end_line, member.range.end_column)
+                        });
+                        if !nested {
+                            self.impl_members.push((member.id, type_name.to_string()));
+                        }
+                    }
+                }
+
                 // CRITICAL: Exit scope first (this clears the current context)
                 self.context.exit_scope();
 
@@ -694,6 +802,35 @@ impl RustParser {
                 }
                 return; // Skip default traversal since we handled children
             }
+            "let_declaration" => {
+                // Closures bound to a name (`let add = |a, b| a + b;`) are callable
+                // symbols; anonymous closures passed inline stay unindexed.
+                if let (Some(pattern), Some(value)) = (
+                    node.child_by_field_name("pattern"),
+                    node.child_by_field_name("value"),
+                ) {
+                    if pattern.kind() == "identifier" && value.kind() == "closure_expression" {
+                        self.register_handled_node("let_declaration", node.kind_id());
+                        self.register_handled_node("closure_expression", value.kind_id());
+                        if let Some(mut sym) = self.create_symbol(
+                            counter,
+                            value,
+                            pattern,
+                            SymbolKind::Function,
+                            file_id,
+                            code,
+                        ) {
+                            let head_end = value
+                                .child_by_field_name("body")
+                                .map(|body| body.start_byte())
+                                .unwrap_or(value.end_byte());
+                            let head = code[value.start_byte()..head_end].trim_end();
+                            sym = sym.with_signature(format!("let {} = {head}", sym.name));
+                            symbols.push(sym);
+                        }
+                    }
+                }
+            }
             "macro_definition" => {
                 self.register_handled_node("macro_definition", node.kind_id());
                 if let Some(name_node) = node.child_by_field_name("name") {
@@ -711,6 +848,11 @@ impl RustParser {
                     }
                 }
             }
+            "macro_invocation" if !self.item_macros.is_empty() => {
+                if let Some(symbol) = self.item_macro_symbol(node, code, file_id, counter) {
+                    symbols.push(symbol);
+                }
+            }
             _ => {}
         }
 
@@ -721,7 +863,7 @@ impl RustParser {
     }
 
     pub fn find_calls<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -744,7 +886,7 @@ impl RustParser {
     }
 
     pub fn find_implementations<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -767,7 +909,17 @@ impl RustParser {
     }
 
     pub fn find_uses<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        self.find_uses_with_position(code)
+            .into_iter()
+            .map(|(context, used_type, range, _)| (context, used_type, range))
+            .collect()
+    }
+
+    /// Type uses tagged with where the type appears: field, parameter,
+    /// return or local variable type. Enum variant references carry no
+    /// position.
+    pub fn find_uses_with_position<'a>(&mut self, code: &'a str) -> Vec<PositionedUse<'a>> {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -777,20 +929,11 @@ impl RustParser {
 
         self.find_uses_in_node(root_node, code, &mut uses);
 
-        // Debug output commented out for cleaner benchmark display
-        // TODO: Enable via settings.debug flag in future enhancement
-        // if !uses.is_empty() {
-        //     eprintln!("DEBUG [find_uses]: Found {} type uses", uses.len());
-        //     for (context, used_type, range) in &uses {
-        //         eprintln!("  - '{}' uses type '{}' at line {}", context, used_type, range.start_line);
-        //     }
-        // }
-
         uses
     }
 
     pub fn find_defines<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -815,7 +958,7 @@ impl RustParser {
     /// Find inherent methods (methods in impl blocks without traits)
     /// Returns Vec<(type_name, method_name, range)>
     pub fn find_inherent_methods(&mut self, code: &str) -> Vec<(String, String, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -828,6 +971,56 @@ impl RustParser {
         methods
     }
 
+    /// Find methods of trait impl blocks (`impl Trait for Type`)
+    /// Returns Vec<(type_name, trait_name, method_name, range)>
+    pub fn find_trait_impl_methods(&mut self, code: &str) -> Vec<(String, String, String, Range)> {
+        let tree = match parse_tree(&mut self.parser, code) {
+            Some(tree) => tree,
+            None => return Vec::new(),
+        };
+
+        let root_node = tree.root_node();
+        let mut methods = Vec::new();
+
+        self.find_trait_impl_methods_in_node(root_node, code, &mut methods);
+
+        methods
+    }
+
+    /// Find trait bounds of generic type parameters, inline or in `where` clauses
+    /// Returns Vec<(parameter_name, bound_name, parameter_range)>
+    pub fn find_type_parameter_bounds(&mut self, code: &str) -> Vec<(String, String, Range)> {
+        let tree = match parse_tree(&mut self.parser, code) {
+            Some(tree) => tree,
+            None => return Vec::new(),
+        };
+
+        let root_node = tree.root_node();
+        let mut bounds = Vec::new();
+
+        self.find_type_parameter_bounds_in_node(root_node, code, &mut bounds);
+
+        bounds
+    }
+
+    /// Traits named in `#[derive(...)]` on structs, enums and unions
+    ///
+    /// Returns (type_name, trait_name, attribute_range) per derived trait, with
+    /// paths kept as written (`serde::Serialize`).
+    pub fn find_derives<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
+        let tree = match parse_tree(&mut self.parser, code) {
+            Some(tree) => tree,
+            None => return Vec::new(),
+        };
+
+        let root_node = tree.root_node();
+        let mut derives = Vec::new();
+
+        self.find_derives_in_node(root_node, code, &mut derives);
+
+        derives
+    }
+
     fn find_calls_in_node<'a>(
         &self,
         node: Node,
@@ -1051,6 +1244,46 @@ impl RustParser {
         }
     }
 
+    fn find_derives_in_node<'a>(
+        &self,
+        node: Node,
+        code: &'a str,
+        derives: &mut Vec<(&'a str, &'a str, Range)>,
+    ) {
+        if matches!(node.kind(), "struct_item" | "enum_item" | "union_item") {
+            if let Some(name_node) = node.child_by_field_name("name") {
+                let type_name = &code[name_node.byte_range()];
+                // Outer attributes are the preceding siblings, possibly
+                // interleaved with doc comments
+                let mut current = node.prev_sibling();
+                while let Some(sibling) = current {
+                    match sibling.kind() {
+                        "attribute_item" => {
+                            let range = Range::new(
+                                sibling.start_position().row as u32,
+                                sibling.start_position().column as u16,
+                                sibling.end_position().row as u32,
+                                sibling.end_position().column as u16,
+                            );
+                            let attribute = &code[sibling.byte_range()];
+                            for trait_name in crate::symbol::derived_traits(attribute) {
+                                derives.push((type_name, trait_name, range));
+                            }
+                        }
+                        "line_comment" | "block_comment" => {}
+                        _ => break,
+                    }
+                    current = sibling.prev_sibling();
+                }
+            }
+        }
+
+        // Recurse into children
+        for child in node.children(&mut node.walk()) {
+            self.find_derives_in_node(child, code, derives);
+        }
+    }
+
     fn find_variable_types_in_node<'a>(
         &self,
         node: Node,
@@ -1294,10 +1527,22 @@ impl RustParser {
         code[node.byte_range()].trim().to_string()
     }
 
-    /// Extract constant signature
+    /// Extract constant signature including its value
+    ///
+    /// Long or multi-line initializers are shortened via `format_constant_value`,
+    /// e.g. `pub const MAX_TOKENS: usize = 20000`.
     fn extract_const_signature(&self, node: Node, code: &str) -> String {
-        // For constants, we want the entire definition including the value
-        code[node.byte_range()].trim().to_string()
+        let Some(value_node) = node.child_by_field_name("value") else {
+            // Extern statics and similar items have no initializer
+            return code[node.byte_range()].trim().to_string();
+        };
+
+        let declaration = code[node.start_byte()..value_node.start_byte()]
+            .trim_end()
+            .trim_end_matches('=')
+            .trim_end();
+        let value = format_constant_value(&code[value_node.byte_range()]);
+        format!("{declaration} = {value}")
     }
 
     /// Recursive type extraction from AST nodes requires &self for traversal context
@@ -1330,12 +1575,7 @@ impl RustParser {
         }
     }
 
-    fn find_uses_in_node<'a>(
-        &self,
-        node: Node,
-        code: &'a str,
-        uses: &mut Vec<(&'a str, &'a str, Range)>,
-    ) {
+    fn find_uses_in_node<'a>(&self, node: Node, code: &'a str, uses: &mut Vec<PositionedUse<'a>>) {
         match node.kind() {
             "struct_item" => {
                 if let Some(name_node) = node.child_by_field_name("name") {
@@ -1354,7 +1594,12 @@ impl RustParser {
                                             type_node.end_position().row as u32,
                                             type_node.end_position().column as u16,
                                         );
-                                        uses.push((struct_name, type_name, range));
+                                        uses.push((
+                                            struct_name,
+                                            type_name,
+                                            range,
+                                            Some(TypeUsePosition::Field),
+                                        ));
                                     }
                                 }
                             }
@@ -1383,7 +1628,12 @@ impl RustParser {
                                             type_node.end_position().row as u32,
                                             type_node.end_position().column as u16,
                                         );
-                                        uses.push((context_name, type_name, range));
+                                        uses.push((
+                                            context_name,
+                                            type_name,
+                                            range,
+                                            Some(TypeUsePosition::Parameter),
+                                        ));
                                     }
                                 }
                             }
@@ -1399,11 +1649,53 @@ impl RustParser {
                                 return_type_node.end_position().row as u32,
                                 return_type_node.end_position().column as u16,
                             );
-                            uses.push((context_name, type_name, range));
+                            uses.push((
+                                context_name,
+                                type_name,
+                                range,
+                                Some(TypeUsePosition::Return),
+                            ));
                         }
                     }
                 }
             }
+            "let_declaration" => {
+                if let (Some(type_node), Some(fn_name)) = (
+                    node.child_by_field_name("type"),
+                    self.find_containing_function(node, code),
+                ) {
+                    if let Some(type_name) = self.extract_type_name(type_node, code) {
+                        let range = Range::new(
+                            type_node.start_position().row as u32,
+                            type_node.start_position().column as u16,
+                            type_node.end_position().row as u32,
+                            type_node.end_position().column as u16,
+                        );
+                        uses.push((fn_name, type_name, range, Some(TypeUsePosition::Local)));
+                    }
+                }
+            }
+            "scoped_identifier" | "scoped_type_identifier" => {
+                // Enum variant construction and match patterns (e.g., `Color::Red`,
+                // `Shape::Circle { .. }`) are recorded as uses of the variant
+                if let (Some(variant_path), Some(caller)) = (
+                    self.extract_variant_path(node, code),
+                    self.find_containing_function(node, code),
+                ) {
+                    let already_recorded = uses
+                        .iter()
+                        .any(|(from, to, _, _)| *from == caller && *to == variant_path);
+                    if !already_recorded {
+                        let range = Range::new(
+                            node.start_position().row as u32,
+                            node.start_position().column as u16,
+                            node.end_position().row as u32,
+                            node.end_position().column as u16,
+                        );
+                        uses.push((caller, variant_path, range, None));
+                    }
+                }
+            }
             _ => {}
         }
 
@@ -1413,6 +1705,46 @@ impl RustParser {
         }
     }
 
+    /// Extract `Enum::Variant` from a scoped path that looks like a variant reference
+    ///
+    /// Both segments must be capitalized, which filters out module paths and
+    /// associated functions (`String::new`). Upper-case names such as
+    /// `u32::MAX` or `Config::DEFAULT_PORT` are associated constants, not
+    /// variants. Longer paths such as `crate::shapes::Shape::Circle` are
+    /// trimmed to their last two segments.
+    fn extract_variant_path<'a>(&self, node: Node, code: &'a str) -> Option<&'a str> {
+        let name_node = node.child_by_field_name("name")?;
+        let path_node = node.child_by_field_name("path")?;
+
+        let type_node = match path_node.kind() {
+            "identifier" | "type_identifier" => path_node,
+            "scoped_identifier" => path_node.child_by_field_name("name")?,
+            _ => return None,
+        };
+
+        let is_capitalized = |n: Node| {
+            code[n.byte_range()]
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_uppercase())
+        };
+        if !is_capitalized(type_node) || !is_capitalized(name_node) {
+            return None;
+        }
+
+        let name = &code[name_node.byte_range()];
+        if name.len() > 1 && !name.chars().any(|c| c.is_ascii_lowercase()) {
+            return None;
+        }
+
+        // `Self::Variant` cannot be resolved without knowing the impl target
+        if &code[type_node.byte_range()] == "Self" {
+            return None;
+        }
+
+        Some(&code[type_node.start_byte()..name_node.end_byte()])
+    }
+
     fn find_defines_in_node<'a>(
         &self,
         node: Node,
@@ -1445,6 +1777,27 @@ impl RustParser {
                     }
                 }
             }
+            "enum_item" => {
+                if let Some(enum_name_node) = node.child_by_field_name("name") {
+                    let enum_name = &code[enum_name_node.byte_range()];
+                    if let Some(body) = node.child_by_field_name("body") {
+                        for child in body.children(&mut body.walk()) {
+                            if child.kind() == "enum_variant" {
+                                if let Some(variant_name_node) = child.child_by_field_name("name") {
+                                    let variant_name = &code[variant_name_node.byte_range()];
+                                    let range = Range::new(
+                                        child.start_position().row as u32,
+                                        child.start_position().column as u16,
+                                        child.end_position().row as u32,
+                                        child.end_position().column as u16,
+                                    );
+                                    defines.push((enum_name, variant_name, range));
+                                }
+                            }
+                        }
+                    }
+                }
+            }
             "impl_item" => {
                 // NOTE: This method extracts ALL impl methods (inherent + trait)
                 // For trait-only methods, use find_implementations + trait method tracking
@@ -1526,6 +1879,210 @@ impl RustParser {
         }
     }
 
+    fn find_trait_impl_methods_in_node(
+        &self,
+        node: Node,
+        code: &str,
+        methods: &mut Vec<(String, String, String, Range)>,
+    ) {
+        if node.kind() == "impl_item" {
+            if let (Some(trait_node), Some(type_node)) = (
+                node.child_by_field_name("trait"),
+                node.child_by_field_name("type"),
+            ) {
+                let trait_name = self.extract_type_name(trait_node, code);
+                let type_name = self.extract_type_name(type_node, code);
+                if let (Some(trait_name), Some(type_name), Some(body_node)) =
+                    (trait_name, type_name, node.child_by_field_name("body"))
+                {
+                    for child in body_node.children(&mut body_node.walk()) {
+                        if child.kind() == "function_item" {
+                            if let Some(method_name_node) = child.child_by_field_name("name") {
+                                let range = Range::new(
+                                    child.start_position().row as u32,
+                                    child.start_position().column as u16,
+                                    child.end_position().row as u32,
+                                    child.end_position().column as u16,
+                                );
+                                methods.push((
+                                    type_name.to_string(),
+                                    trait_name.to_string(),
+                                    code[method_name_node.byte_range()].to_string(),
+                                    range,
+                                ));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // Recurse into children
+        for child in node.children(&mut node.walk()) {
+            self.find_trait_impl_methods_in_node(child, code, methods);
+        }
+    }
+
+    fn find_type_parameter_bounds_in_node(
+        &self,
+        node: Node,
+        code: &str,
+        bounds: &mut Vec<(String, String, Range)>,
+    ) {
+        if let Some(params) = node.child_by_field_name("type_parameters") {
+            // Declared parameters, so `where` predicates map back to them
+            let mut declared: Vec<(&str, Range)> = Vec::new();
+            for param in params.children(&mut params.walk()) {
+                if param.kind() != "type_parameter" {
+                    continue;
+                }
+                if let Some(name_node) = param.child_by_field_name("name") {
+                    let name = &code[name_node.byte_range()];
+                    let range = Range::new(
+                        param.start_position().row as u32,
+                        param.start_position().column as u16,
+                        param.end_position().row as u32,
+                        param.end_position().column as u16,
+                    );
+                    if let Some(bounds_node) = param.child_by_field_name("bounds") {
+                        self.push_trait_bounds(name, bounds_node, range, code, bounds);
+                    }
+                    declared.push((name, range));
+                }
+            }
+
+            for child in node.children(&mut node.walk()) {
+                if child.kind() != "where_clause" {
+                    continue;
+                }
+                for predicate in child.children(&mut child.walk()) {
+                    if let (Some(left), Some(bounds_node)) = (
+                        predicate.child_by_field_name("left"),
+                        predicate.child_by_field_name("bounds"),
+                    ) {
+                        let left = &code[left.byte_range()];
+                        if let Some(&(name, range)) = declared.iter().find(|(n, _)| *n == left) {
+                            self.push_trait_bounds(name, bounds_node, range, code, bounds);
+                        }
+                    }
+                }
+            }
+        }
+
+        // Recurse into children
+        for child in node.children(&mut node.walk()) {
+            self.find_type_parameter_bounds_in_node(child, code, bounds);
+        }
+    }
+
+    /// Push each trait named in a `trait_bounds` node, skipping lifetimes
+    fn push_trait_bounds(
+        &self,
+        param_name: &str,
+        bounds_node: Node,
+        range: Range,
+        code: &str,
+        bounds: &mut Vec<(String, String, Range)>,
+    ) {
+        for bound in bounds_node.named_children(&mut bounds_node.walk()) {
+            if bound.kind() == "lifetime" {
+                continue;
+            }
+            if let Some(trait_name) = self.extract_type_name(bound, code) {
+                bounds.push((param_name.to_string(), trait_name.to_string(), range));
+            }
+        }
+    }
+
+    /// Create `Parameter` symbols for the generic type parameters of an item
+    ///
+    /// Only runs with `index_type_parameters`. Lifetimes and const generics
+    /// are skipped; the signature keeps bounds and defaults (`T: Clone = ()`).
+    fn extract_type_parameters(
+        &mut self,
+        node: Node,
+        code: &str,
+        file_id: FileId,
+        symbols: &mut Vec<Symbol>,
+        counter: &mut SymbolCounter,
+    ) {
+        if !self.index_type_parameters {
+            return;
+        }
+        let Some(params) = node.child_by_field_name("type_parameters") else {
+            return;
+        };
+        for param in params.children(&mut params.walk()) {
+            if param.kind() != "type_parameter" {
+                continue;
+            }
+            self.register_handled_node("type_parameter", param.kind_id());
+            if let Some(name_node) = param.child_by_field_name("name") {
+                if let Some(symbol) = self.create_symbol(
+                    counter,
+                    param,
+                    name_node,
+                    SymbolKind::Parameter,
+                    file_id,
+                    code,
+                ) {
+                    let signature = code[param.byte_range()].trim().to_string();
+                    symbols.push(symbol.with_signature(signature));
+                }
+            }
+        }
+    }
+
+    /// Symbol defined by an invocation of one of the `item_macros`
+    ///
+    /// The first identifier at the top level of the arguments is the name, so
+    /// attributes like `#[derive(Debug)]` are passed over. The signature runs
+    /// from the macro name to that identifier.
+    fn item_macro_symbol(
+        &self,
+        node: Node,
+        code: &str,
+        file_id: FileId,
+        counter: &mut SymbolCounter,
+    ) -> Option<Symbol> {
+        // `define_id!` and `crate::define_id!` both match `define_id`
+        let name_node = node.child_by_field_name("macro")?;
+        let macro_name = &code[name_node
+            .child_by_field_name("name")
+            .unwrap_or(name_node)
+            .byte_range()];
+        let kind = *self.item_macros.get(macro_name)?;
+        let args = node
+            .children(&mut node.walk())
+            .find(|child| child.kind() == "token_tree")?;
+        let ident = args
+            .children(&mut args.walk())
+            .find(|child| child.kind() == "identifier")?;
+        // `pub` (or `pub(crate)`) ahead of the name, as in `bitflags! { pub struct X ... }`
+        let is_public = args
+            .children(&mut args.walk())
+            .take_while(|child| child.id() != ident.id())
+            .any(|child| child.kind() == "pub");
+
+        let range = Range::new(
+            node.start_position().row as u32,
+            node.start_position().column as u16,
+            node.end_position().row as u32,
+            node.end_position().column as u16,
+        );
+        let name = &code[ident.byte_range()];
+        let signature = code[node.start_byte()..ident.end_byte()]
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut symbol =
+            Symbol::new(counter.next_id(), name, kind, file_id, range).with_signature(signature);
+        if is_public {
+            symbol = symbol.with_visibility(crate::Visibility::Public);
+        }
+        Some(symbol)
+    }
+
     fn create_symbol(
         &mut self,
         counter: &mut SymbolCounter,
@@ -1760,7 +2317,7 @@ impl LanguageParser for RustParser {
             "RustParser::find_method_calls override called with enhanced AST detection"
         );
 
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -1786,6 +2343,10 @@ impl LanguageParser for RustParser {
         self.find_uses(code)
     }
 
+    fn find_uses_with_position<'a>(&mut self, code: &'a str) -> Vec<PositionedUse<'a>> {
+        self.find_uses_with_position(code)
+    }
+
     fn find_defines<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
         self.find_defines(code)
     }
@@ -1799,7 +2360,7 @@ impl LanguageParser for RustParser {
     }
 
     fn find_variable_types<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -1815,6 +2376,18 @@ impl LanguageParser for RustParser {
     fn find_inherent_methods(&mut self, code: &str) -> Vec<(String, String, Range)> {
         self.find_inherent_methods(code)
     }
+
+    fn find_trait_impl_methods(&mut self, code: &str) -> Vec<(String, String, String, Range)> {
+        self.find_trait_impl_methods(code)
+    }
+
+    fn find_type_parameter_bounds(&mut self, code: &str) -> Vec<(String, String, Range)> {
+        self.find_type_parameter_bounds(code)
+    }
+
+    fn find_derives<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
+        self.find_derives(code)
+    }
 }
 
 impl NodeTracker for RustParser {
@@ -1831,6 +2404,28 @@ impl NodeTracker for RustParser {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_relationship_passes_stop_at_deadline() {
+        use crate::parsing::ParseDeadline;
+
+        let mut parser = RustParser::new().unwrap();
+        let code: String = (0..2_000)
+            .map(|i| format!("use m{i}::T{i};\nimpl T{i} for S {{}}\nfn f{i}() {{ g{i}(); }}\n"))
+            .collect();
+        assert!(!parser.find_calls(&code).is_empty());
+
+        // Every pass parses again, so each one gives up at the deadline
+        let _deadline = ParseDeadline::start(std::time::Duration::ZERO);
+        assert!(parser.find_calls(&code).is_empty());
+        assert!(parser.find_method_calls(&code).is_empty());
+        assert!(parser.find_implementations(&code).is_empty());
+        assert!(
+            parser
+                .find_imports(&code, FileId::new(1).unwrap())
+                .is_empty()
+        );
+    }
+
     #[test]
     fn test_parse_simple_function() {
         let mut parser = RustParser::new().unwrap();
@@ -2029,6 +2624,27 @@ mod tests {
         assert!(function_names.contains(&"multiply"));
     }
 
+    #[test]
+    fn test_parse_recovers_after_syntax_errors() {
+        let mut parser = RustParser::new().unwrap();
+        let manifest_dir = env!("CARGO_MANIFEST_DIR");
+        let test_file = std::path::Path::new(manifest_dir).join("tests/fixtures/malformed.rs");
+        let code = std::fs::read_to_string(test_file).unwrap();
+
+        let mut counter = SymbolCounter::new();
+        let symbols = parser.parse(&code, FileId::new(1).unwrap(), &mut counter);
+        let names: Vec<&str> = symbols.iter().map(|s| s.name.as_ref()).collect();
+
+        // Before the broken function
+        for name in ["Before", "value", "before"] {
+            assert!(names.contains(&name), "missing {name} in {names:?}");
+        }
+        // After it, including trait members following a broken signature
+        for name in ["Shape", "area", "name", "After", "First", "Second", "after"] {
+            assert!(names.contains(&name), "missing {name} in {names:?}");
+        }
+    }
+
     #[test]
     fn test_find_uses() {
         let mut parser = RustParser::new().unwrap();
@@ -2103,6 +2719,40 @@ mod tests {
         assert!(center_uses.iter().any(|(_, used, _)| *used == "Point"));
     }
 
+    #[test]
+    fn test_find_uses_with_position() {
+        let mut parser = RustParser::new().unwrap();
+        let code = r#"
+            struct Config {
+                name: String,
+            }
+
+            struct App {
+                config: Config,
+            }
+
+            fn load(path: &str) -> Config {
+                let fallback: Config = Config { name: path.to_string() };
+                fallback
+            }
+
+            fn apply(config: &Config) {}
+        "#;
+
+        let positions: Vec<_> = parser
+            .find_uses_with_position(code)
+            .into_iter()
+            .filter(|(_, used, _, _)| *used == "Config")
+            .map(|(user, _, _, position)| (user, position))
+            .collect();
+
+        assert!(positions.contains(&("App", Some(TypeUsePosition::Field))));
+        assert!(positions.contains(&("load", Some(TypeUsePosition::Return))));
+        assert!(positions.contains(&("load", Some(TypeUsePosition::Local))));
+        assert!(positions.contains(&("apply", Some(TypeUsePosition::Parameter))));
+        assert!(!positions.contains(&("load", Some(TypeUsePosition::Parameter))));
+    }
+
     #[test]
     fn test_find_defines() {
         let mut parser = RustParser::new().unwrap();
@@ -2188,6 +2838,160 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_enum_variants() {
+        let mut parser = RustParser::new().unwrap();
+        let code = r#"
+            pub enum Shape {
+                Circle { radius: f64 },
+                Square(f64),
+                Empty,
+            }
+
+            fn area(shape: &Shape) -> f64 {
+                match shape {
+                    Shape::Circle { radius } => radius * radius,
+                    Shape::Square(side) => side * side,
+                    Shape::Empty => 0.0,
+                }
+            }
+
+            fn make() -> Shape {
+                let s = String::new();
+                let limit = Shape::MAX_SIDES;
+                crate::shapes::Shape::Square(1.0)
+            }
+        "#;
+        let file_id = FileId::new(1).unwrap();
+        let mut counter = SymbolCounter::new();
+        let symbols = parser.parse(code, file_id, &mut counter);
+
+        let variants: Vec<_> = symbols
+            .iter()
+            .filter(|s| s.kind == SymbolKind::EnumVariant)
+            .collect();
+        assert_eq!(variants.len(), 3);
+        assert!(
+            variants
+                .iter()
+                .all(|v| v.visibility == crate::Visibility::Public)
+        );
+        let square = variants.iter().find(|v| &*v.name == "Square").unwrap();
+        assert_eq!(square.signature.as_deref(), Some("Square(f64)"));
+
+        let defines = parser.find_defines(code);
+        let shape_defines: Vec<_> = defines
+            .iter()
+            .filter(|(definer, _, _)| *definer == "Shape")
+            .map(|(_, defined, _)| *defined)
+            .collect();
+        assert_eq!(shape_defines, vec!["Circle", "Square", "Empty"]);
+
+        let uses = parser.find_uses(code);
+        let area_variants: Vec<_> = uses
+            .iter()
+            .filter(|(user, used, _)| *user == "area" && used.contains("::"))
+            .map(|(_, used, _)| *used)
+            .collect();
+        assert_eq!(
+            area_variants,
+            vec!["Shape::Circle", "Shape::Square", "Shape::Empty"]
+        );
+
+        // Qualified paths are trimmed; associated functions are ignored
+        let make_uses: Vec<_> = uses
+            .iter()
+            .filter(|(user, _, _)| *user == "make")
+            .map(|(_, used, _)| *used)
+            .collect();
+        assert!(make_uses.contains(&"Shape::Square"));
+        assert!(!make_uses.contains(&"String::new"));
+        assert!(!make_uses.contains(&"Shape::MAX_SIDES"));
+    }
+
+    #[test]
+    fn test_named_closures() {
+        let mut parser = RustParser::new().unwrap();
+        let code = r#"
+            fn main() {
+                let add = |a: u32, b: u32| -> u32 { a + b };
+                let mut double = |x| x * 2;
+                let total = add(1, 2);
+                items.iter().map(|x| x + 1);
+            }
+        "#;
+        let file_id = FileId::new(1).unwrap();
+        let mut counter = SymbolCounter::new();
+        let symbols = parser.parse(code, file_id, &mut counter);
+
+        let add = symbols.iter().find(|s| &*s.name == "add").unwrap();
+        assert_eq!(add.kind, SymbolKind::Function);
+        assert_eq!(
+            add.signature.as_deref(),
+            Some("let add = |a: u32, b: u32| -> u32")
+        );
+        assert_eq!(add.range.start_line, 2);
+
+        let double = symbols.iter().find(|s| &*s.name == "double").unwrap();
+        assert_eq!(double.signature.as_deref(), Some("let double = |x|"));
+
+        // Plain bindings and inline closures are not callable symbols
+        assert!(!symbols.iter().any(|s| &*s.name == "total"));
+        assert_eq!(
+            symbols
+                .iter()
+                .filter(|s| s.kind == SymbolKind::Function)
+                .count(),
+            3
+        );
+
+        let calls = parser.find_calls(code);
+        assert!(
+            calls
+                .iter()
+                .any(|(caller, callee, _)| *caller == "main" && *callee == "add")
+        );
+    }
+
+    #[test]
+    fn test_constant_values_in_signature() {
+        let mut parser = RustParser::new().unwrap();
+        let code = r#"
+            pub const MAX_TOKENS: usize = 20000;
+            static GREETING: &str = "hello";
+            const TABLE: [u8; 4] = [
+                1,
+                2,
+                3,
+                4,
+            ];
+        "#;
+        let file_id = FileId::new(1).unwrap();
+        let mut counter = SymbolCounter::new();
+        let symbols = parser.parse(code, file_id, &mut counter);
+
+        let signature_of = |name: &str| {
+            symbols
+                .iter()
+                .find(|s| &*s.name == name)
+                .and_then(|s| s.signature.as_deref())
+                .map(str::to_string)
+        };
+
+        assert_eq!(
+            signature_of("MAX_TOKENS").as_deref(),
+            Some("pub const MAX_TOKENS: usize = 20000")
+        );
+        assert_eq!(
+            signature_of("GREETING").as_deref(),
+            Some("static GREETING: &str = \"hello\"")
+        );
+        assert_eq!(
+            signature_of("TABLE").as_deref(),
+            Some("const TABLE: [u8; 4] = [ 1, 2, 3, 4, ]")
+        );
+    }
+
     #[test]
     fn test_find_implementations() {
         let mut parser = RustParser::new().unwrap();
@@ -2238,6 +3042,43 @@ mod tests {
         assert_eq!(debug_impl.1, "std::fmt::Debug");
     }
 
+    #[test]
+    fn test_find_derives() {
+        let mut parser = RustParser::new().unwrap();
+        let code = r#"
+            /// A point
+            #[derive(Debug, Clone, PartialEq)]
+            #[derive(serde::Serialize)]
+            #[repr(C)]
+            struct Point {
+                x: i32,
+            }
+
+            #[derive(Copy,)]
+            enum Mode {
+                Fast,
+            }
+
+            #[cfg(test)]
+            struct Plain;
+        "#;
+
+        let derives: Vec<(&str, &str)> = parser
+            .find_derives(code)
+            .into_iter()
+            .map(|(type_name, trait_name, _)| (type_name, trait_name))
+            .collect();
+
+        assert_eq!(derives.len(), 5);
+        for trait_name in ["Debug", "Clone", "PartialEq", "serde::Serialize"] {
+            assert!(
+                derives.contains(&("Point", trait_name)),
+                "missing {trait_name}"
+            );
+        }
+        assert!(derives.contains(&("Mode", "Copy")));
+    }
+
     #[test]
     fn test_find_inherent_methods() {
         let mut parser = RustParser::new().unwrap();
@@ -2309,6 +3150,94 @@ mod tests {
         assert!(!methods.iter().any(|(_, m, _)| m == "fmt"));
     }
 
+    #[test]
+    fn test_type_parameters() {
+        let code = r#"
+            fn show<'a, T: Render + Clone, const N: usize>(item: &'a T) {}
+
+            struct Wrapper<W>
+            where
+                W: Named,
+            {
+                inner: W,
+            }
+        "#;
+        let file_id = FileId::new(1).unwrap();
+
+        // Off by default
+        let mut parser = RustParser::new().unwrap();
+        let symbols = parser.parse(code, file_id, &mut SymbolCounter::new());
+        assert!(!symbols.iter().any(|s| s.kind == SymbolKind::Parameter));
+
+        let mut parser = RustParser::new().unwrap().with_type_parameters(true);
+        let symbols = parser.parse(code, file_id, &mut SymbolCounter::new());
+        let params: Vec<(&str, Option<&str>)> = symbols
+            .iter()
+            .filter(|s| s.kind == SymbolKind::Parameter)
+            .map(|s| (s.name.as_ref(), s.signature.as_deref()))
+            .collect();
+        assert_eq!(
+            params,
+            vec![("T", Some("T: Render + Clone")), ("W", Some("W"))]
+        );
+
+        let bounds: Vec<(String, String)> = parser
+            .find_type_parameter_bounds(code)
+            .into_iter()
+            .map(|(param, bound, _)| (param, bound))
+            .collect();
+        assert_eq!(
+            bounds,
+            vec![
+                ("T".to_string(), "Render".to_string()),
+                ("T".to_string(), "Clone".to_string()),
+                ("W".to_string(), "Named".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_item_macro_symbols() {
+        let code = r#"
+bitflags! {
+    #[derive(Debug)]
+    pub struct Permissions: u32 {
+        const READ = 1;
+    }
+}
+
+fn setup() {
+    define_id!(SessionId);
+    println!("{}", value);
+}
+"#;
+        let file_id = FileId::new(1).unwrap();
+
+        // Nothing configured, nothing extracted
+        let mut parser = RustParser::new().unwrap();
+        let symbols = parser.parse(code, file_id, &mut SymbolCounter::new());
+        assert!(symbols.iter().all(|s| s.kind != SymbolKind::Struct));
+
+        let mut parser = RustParser::new().unwrap().with_item_macros(BTreeMap::from([
+            ("bitflags".to_string(), SymbolKind::Struct),
+            ("define_id".to_string(), SymbolKind::Struct),
+        ]));
+        let symbols = parser.parse(code, file_id, &mut SymbolCounter::new());
+        let structs: Vec<&Symbol> = symbols
+            .iter()
+            .filter(|s| s.kind == SymbolKind::Struct)
+            .collect();
+        let names: Vec<&str> = structs.iter().map(|s| s.name.as_ref()).collect();
+        assert_eq!(names, ["Permissions", "SessionId"]);
+        assert_eq!(
+            structs[0].signature.as_deref(),
+            Some("bitflags! { #[derive(Debug)] pub struct Permissions")
+        );
+        assert_eq!(structs[0].range.start_line, 1);
+        assert_eq!(structs[0].visibility, crate::Visibility::Public);
+        assert_eq!(structs[1].visibility, crate::Visibility::Private);
+    }
+
     #[test]
     fn test_doc_comment_extraction() {
         let mut parser = RustParser::new().unwrap();
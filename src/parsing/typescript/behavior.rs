@@ -1,6 +1,8 @@
 //! TypeScript-specific language behavior implementation
 
 use crate::debug_print;
+use crate::indexing::EntryPointCategory;
+use crate::indexing::entrypoints::{HTTP_VERBS, attribute_path};
 use crate::parsing::LanguageBehavior;
 use crate::parsing::behavior_state::{BehaviorState, StatefulBehavior};
 use crate::parsing::resolution::{InheritanceResolver, ResolutionScope};
@@ -99,6 +101,34 @@ impl LanguageBehavior for TypeScriptBehavior {
         }
     }
 
+    fn entry_point_category(
+        &self,
+        symbol: &crate::Symbol,
+        attributes: &[&str],
+    ) -> Option<EntryPointCategory> {
+        use crate::types::SymbolKind;
+
+        // NestJS-style route decorators on controller methods: @Get(), @Post(":id")
+        let routed = attributes.iter().any(|attribute| {
+            HTTP_VERBS.contains(&attribute_path(attribute).to_ascii_lowercase().as_str())
+        });
+        if routed && matches!(symbol.kind, SymbolKind::Function | SymbolKind::Method) {
+            return Some(EntryPointCategory::HttpHandler);
+        }
+        if symbol.kind != SymbolKind::Function {
+            return None;
+        }
+        // Route handlers exported by name: export async function GET(request)
+        let name = symbol.name.as_ref();
+        if symbol.visibility == Visibility::Public
+            && name.bytes().all(|b| b.is_ascii_uppercase())
+            && HTTP_VERBS.contains(&name.to_ascii_lowercase().as_str())
+        {
+            return Some(EntryPointCategory::HttpHandler);
+        }
+        (name == "main").then_some(EntryPointCategory::Main)
+    }
+
     fn format_module_path(&self, base_path: &str, _symbol_name: &str) -> String {
         // TypeScript uses file paths as module paths, not including the symbol name
         // All symbols in the same file share the same module path for visibility
@@ -108,6 +138,14 @@ impl LanguageBehavior for TypeScriptBehavior {
     fn get_language(&self) -> Language {
         tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into()
     }
+
+    fn records_type_use_positions(&self) -> bool {
+        true
+    }
+
+    fn attribute_node_kinds(&self) -> &'static [&'static str] {
+        &["decorator"]
+    }
     fn module_separator(&self) -> &'static str {
         "."
     }
@@ -6,10 +6,14 @@
 //! When migrating or updating the parser, ensure compatibility with ABI-14 features.
 
 use crate::parsing::Import;
-use crate::parsing::parser::check_recursion_depth;
+use crate::parsing::parser::{
+    check_recursion_depth, format_constant_value, parse_tree, push_positioned, recovered_children,
+};
 use crate::parsing::{
-    LanguageParser, MethodCall, NodeTracker, NodeTrackingState, ParserContext, ScopeType,
+    LanguageParser, MethodCall, NodeTracker, NodeTrackingState, ParserContext, PositionedUse,
+    ScopeType,
 };
+use crate::relationship::TypeUsePosition;
 use crate::types::SymbolCounter;
 use crate::{FileId, Range, Symbol, SymbolKind, Visibility};
 use std::any::Any;
@@ -75,7 +79,7 @@ impl TypeScriptParser {
         self.component_usages.clear();
         let mut symbols = Vec::new();
 
-        match self.parser.parse(code, None) {
+        match parse_tree(&mut self.parser, code) {
             Some(tree) => {
                 let root_node = tree.root_node();
                 self.extract_symbols_from_node(
@@ -263,7 +267,17 @@ impl TypeScriptParser {
                 // Register ALL child nodes for audit
                 self.register_node_recursively(node);
                 if let Some(symbol) = self.process_enum(node, code, file_id, counter, module_path) {
+                    let visibility = symbol.visibility;
                     symbols.push(symbol);
+                    self.process_enum_members(
+                        node,
+                        code,
+                        file_id,
+                        counter,
+                        symbols,
+                        module_path,
+                        visibility,
+                    );
                 }
             }
             "lexical_declaration" | "variable_declaration" => {
@@ -555,8 +569,7 @@ impl TypeScriptParser {
         depth: usize,
     ) {
         if let Some(body) = class_node.child_by_field_name("body") {
-            let mut cursor = body.walk();
-            for child in body.children(&mut cursor) {
+            for child in recovered_children(body) {
                 match child.kind() {
                     "method_definition" => {
                         self.register_handled_node(child.kind(), child.kind_id());
@@ -722,6 +735,60 @@ impl TypeScriptParser {
         ))
     }
 
+    /// Process enum members as individual variant symbols
+    #[allow(clippy::too_many_arguments)]
+    fn process_enum_members(
+        &mut self,
+        node: Node,
+        code: &str,
+        file_id: FileId,
+        counter: &mut SymbolCounter,
+        symbols: &mut Vec<Symbol>,
+        module_path: &str,
+        visibility: Visibility,
+    ) {
+        let Some(body) = node.child_by_field_name("body") else {
+            return;
+        };
+
+        for member in recovered_children(body)
+            .into_iter()
+            .filter(|n| n.is_named())
+        {
+            let name_node = match member.kind() {
+                "property_identifier" => member,
+                // `Red = "red"` - the member name is the first named child
+                "enum_assignment" => match member.named_child(0) {
+                    Some(name) => name,
+                    None => continue,
+                },
+                _ => continue,
+            };
+            let name = code[name_node.byte_range()].trim_matches(['"', '\'']);
+            if name.is_empty() {
+                continue;
+            }
+
+            let symbol = self.create_symbol(
+                counter.next_id(),
+                name.to_string(),
+                SymbolKind::EnumVariant,
+                file_id,
+                Range::new(
+                    member.start_position().row as u32,
+                    member.start_position().column as u16,
+                    member.end_position().row as u32,
+                    member.end_position().column as u16,
+                ),
+                Some(code[member.byte_range()].to_string()),
+                self.extract_doc_comment(&member, code),
+                module_path,
+                visibility,
+            );
+            symbols.push(symbol);
+        }
+    }
+
     /// Process variable declarations
     fn process_variable_declaration(
         &mut self,
@@ -762,6 +829,20 @@ impl TypeScriptParser {
                         // Extract JSDoc comment for const declarations
                         let doc_comment = self.extract_doc_comment(&node, code);
 
+                        // Constants carry their initializer, e.g. `const MAX_RETRIES = 3`
+                        let signature = if kind == SymbolKind::Constant {
+                            child.child_by_field_name("value").map(|value_node| {
+                                let declaration = code[child.start_byte()..value_node.start_byte()]
+                                    .trim_end()
+                                    .trim_end_matches('=')
+                                    .trim_end();
+                                let value = format_constant_value(&code[value_node.byte_range()]);
+                                format!("const {declaration} = {value}")
+                            })
+                        } else {
+                            None
+                        };
+
                         let mut symbol = self.create_symbol(
                             counter.next_id(),
                             name.to_string(),
@@ -773,7 +854,7 @@ impl TypeScriptParser {
                                 child.end_position().row as u32,
                                 child.end_position().column as u16,
                             ),
-                            None,
+                            signature,
                             doc_comment,
                             module_path,
                             visibility,
@@ -923,6 +1004,7 @@ impl TypeScriptParser {
 
         let visibility = self.determine_method_visibility(node, code);
         let doc_comment = self.extract_doc_comment(&node, code);
+        let signature = self.extract_property_signature(node, code);
 
         Some(self.create_symbol(
             counter.next_id(),
@@ -935,7 +1017,7 @@ impl TypeScriptParser {
                 node.end_position().row as u32,
                 node.end_position().column as u16,
             ),
-            None,
+            Some(signature),
             doc_comment,
             module_path,
             visibility,
@@ -956,6 +1038,23 @@ impl TypeScriptParser {
         code[start..end].trim().to_string()
     }
 
+    /// Extract a property declaration with its modifiers and type, without the initializer
+    ///
+    /// `private static readonly max: number = 10;` becomes
+    /// `private static readonly max: number`.
+    fn extract_property_signature(&self, node: Node, code: &str) -> String {
+        let end = node
+            .child_by_field_name("value")
+            .map_or(node.end_byte(), |value| value.start_byte());
+
+        code[node.start_byte()..end]
+            .trim()
+            .trim_end_matches(';')
+            .trim_end_matches('=')
+            .trim_end()
+            .to_string()
+    }
+
     /// Extract class signature (with extends/implements)
     fn extract_class_signature(&self, node: Node, code: &str) -> String {
         let start = node.start_byte();
@@ -1017,16 +1116,25 @@ impl TypeScriptParser {
         Visibility::Private
     }
 
-    /// Determine method/property visibility
+    /// Determine method/property visibility from its modifier nodes
+    ///
+    /// `#name` members (ECMAScript private) are private regardless of modifiers.
     fn determine_method_visibility(&self, node: Node, code: &str) -> Visibility {
-        let signature = &code[node.byte_range()];
+        if node
+            .child_by_field_name("name")
+            .is_some_and(|name| name.kind() == "private_property_identifier")
+        {
+            return Visibility::Private;
+        }
 
-        if signature.contains("private ") || signature.starts_with("#") {
-            Visibility::Private
-        } else if signature.contains("protected ") {
-            Visibility::Module // Map TypeScript protected to Module visibility
-        } else {
-            Visibility::Public // Default for class members
+        let mut cursor = node.walk();
+        let modifier = node
+            .children(&mut cursor)
+            .find(|child| child.kind() == "accessibility_modifier");
+        match modifier.map(|m| &code[m.byte_range()]) {
+            Some("private") => Visibility::Private,
+            Some("protected") => Visibility::Module, // Map TypeScript protected to Module visibility
+            _ => Visibility::Public,                 // Default for class members
         }
     }
 
@@ -1751,8 +1859,10 @@ impl TypeScriptParser {
         &self,
         node: &tree_sitter::Node,
         code: &'a str,
-        uses: &mut Vec<(&'a str, &'a str, Range)>,
+        uses: &mut Vec<PositionedUse<'a>>,
     ) {
+        // Uses found at this node; signature and field types are tagged as such
+        let mut found = Vec::new();
         match node.kind() {
             // Function declarations with parameters and return types
             "function_declaration"
@@ -1767,14 +1877,15 @@ impl TypeScriptParser {
 
                 // Check parameters
                 if let Some(params) = node.child_by_field_name("parameters") {
-                    self.extract_parameter_types(params, code, context_name, uses);
+                    self.extract_parameter_types(params, code, context_name, &mut found);
                 }
+                push_positioned(uses, &mut found, Some(TypeUsePosition::Parameter));
 
                 // Check return type - try both "type" and "return_type" fields
                 if let Some(return_type) = node.child_by_field_name("type") {
-                    self.extract_type_from_annotation(&return_type, code, context_name, uses);
+                    self.extract_type_from_annotation(&return_type, code, context_name, &mut found);
                 } else if let Some(return_type) = node.child_by_field_name("return_type") {
-                    self.extract_type_from_annotation(&return_type, code, context_name, uses);
+                    self.extract_type_from_annotation(&return_type, code, context_name, &mut found);
                 } else {
                     // Also look for type_annotation as a direct child (not a field)
                     let mut cursor = node.walk();
@@ -1783,11 +1894,17 @@ impl TypeScriptParser {
                             // Make sure it's the return type (comes after parameters)
                             if child.start_position().column > 30 {
                                 // Heuristic: return types are usually after column 30
-                                self.extract_type_from_annotation(&child, code, context_name, uses);
+                                self.extract_type_from_annotation(
+                                    &child,
+                                    code,
+                                    context_name,
+                                    &mut found,
+                                );
                             }
                         }
                     }
                 }
+                push_positioned(uses, &mut found, Some(TypeUsePosition::Return));
             }
 
             // Class declarations with fields
@@ -1809,19 +1926,27 @@ impl TypeScriptParser {
                                     &heritage_child,
                                     code,
                                     class_name,
-                                    uses,
+                                    &mut found,
                                 );
                             } else if heritage_child.kind() == "extends_clause" {
-                                self.extract_extends_types(&heritage_child, code, class_name, uses);
+                                self.extract_extends_types(
+                                    &heritage_child,
+                                    code,
+                                    class_name,
+                                    &mut found,
+                                );
                             }
                         }
                     }
                 }
 
+                push_positioned(uses, &mut found, None);
+
                 // Check class body for field types
                 if let Some(body) = node.child_by_field_name("body") {
-                    self.extract_class_field_types(&body, code, class_name, uses);
+                    self.extract_class_field_types(&body, code, class_name, &mut found);
                 }
+                push_positioned(uses, &mut found, Some(TypeUsePosition::Field));
             }
 
             // Variable declarations with type annotations
@@ -1831,7 +1956,7 @@ impl TypeScriptParser {
 
                     // Look for type annotation
                     if let Some(type_ann) = node.child_by_field_name("type") {
-                        self.extract_type_from_annotation(&type_ann, code, var_name, uses);
+                        self.extract_type_from_annotation(&type_ann, code, var_name, &mut found);
                     }
                 }
             }
@@ -1847,7 +1972,7 @@ impl TypeScriptParser {
                 let mut cursor = node.walk();
                 for child in node.children(&mut cursor) {
                     if child.kind() == "extends_clause" || child.kind() == "extends_type_clause" {
-                        self.extract_extends_types(&child, code, interface_name, uses);
+                        self.extract_extends_types(&child, code, interface_name, &mut found);
                     }
                 }
             }
@@ -1872,7 +1997,12 @@ impl TypeScriptParser {
 
                 // Check for type_arguments field
                 if let Some(type_args) = node.child_by_field_name("type_arguments") {
-                    self.extract_types_from_type_arguments(&type_args, code, context_name, uses);
+                    self.extract_types_from_type_arguments(
+                        &type_args,
+                        code,
+                        context_name,
+                        &mut found,
+                    );
                 }
             }
 
@@ -1887,12 +2017,13 @@ impl TypeScriptParser {
 
                 // Check for type_arguments field
                 if let Some(type_args) = node.child_by_field_name("type_arguments") {
-                    self.extract_types_from_type_arguments(&type_args, code, func_name, uses);
+                    self.extract_types_from_type_arguments(&type_args, code, func_name, &mut found);
                 }
             }
 
             _ => {}
         }
+        push_positioned(uses, &mut found, None);
 
         // Recurse to children
         for child in node.children(&mut node.walk()) {
@@ -2161,6 +2292,35 @@ impl TypeScriptParser {
                 }
             }
 
+            // Enum members
+            "enum_declaration" => {
+                let enum_name = node
+                    .child_by_field_name("name")
+                    .map(|n| &code[n.byte_range()])
+                    .unwrap_or("anonymous");
+
+                if let Some(body) = node.child_by_field_name("body") {
+                    for child in body.named_children(&mut body.walk()) {
+                        let name_node = match child.kind() {
+                            "property_identifier" => Some(child),
+                            "enum_assignment" => child.named_child(0),
+                            _ => None,
+                        };
+                        if let Some(name_node) = name_node {
+                            let member_name =
+                                code[name_node.byte_range()].trim_matches(['"', '\'']);
+                            let range = Range::new(
+                                child.start_position().row as u32,
+                                child.start_position().column as u16,
+                                child.end_position().row as u32,
+                                child.end_position().column as u16,
+                            );
+                            defines.push((enum_name, member_name, range));
+                        }
+                    }
+                }
+            }
+
             // Type aliases with object types (method signatures in type literals)
             "type_alias_declaration" => {
                 let type_name = node
@@ -2528,7 +2688,7 @@ impl LanguageParser for TypeScriptParser {
     }
 
     fn find_calls<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -2543,7 +2703,7 @@ impl LanguageParser for TypeScriptParser {
     }
 
     fn find_method_calls(&mut self, code: &str) -> Vec<MethodCall> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -2559,7 +2719,7 @@ impl LanguageParser for TypeScriptParser {
     fn find_implementations<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
         let mut implementations = Vec::new();
 
-        if let Some(tree) = self.parser.parse(code, None) {
+        if let Some(tree) = parse_tree(&mut self.parser, code) {
             self.find_implementations_in_node(tree.root_node(), code, &mut implementations, false);
         }
 
@@ -2569,7 +2729,7 @@ impl LanguageParser for TypeScriptParser {
     fn find_extends<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
         let mut extends = Vec::new();
 
-        if let Some(tree) = self.parser.parse(code, None) {
+        if let Some(tree) = parse_tree(&mut self.parser, code) {
             self.find_implementations_in_node(tree.root_node(), code, &mut extends, true);
         }
 
@@ -2579,7 +2739,7 @@ impl LanguageParser for TypeScriptParser {
     fn find_imports(&mut self, code: &str, file_id: FileId) -> Vec<Import> {
         let mut imports = Vec::new();
 
-        if let Some(tree) = self.parser.parse(code, None) {
+        if let Some(tree) = parse_tree(&mut self.parser, code) {
             let root = tree.root_node();
             self.extract_imports_from_node(root, code, file_id, &mut imports);
         }
@@ -2588,7 +2748,14 @@ impl LanguageParser for TypeScriptParser {
     }
 
     fn find_uses<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        self.find_uses_with_position(code)
+            .into_iter()
+            .map(|(context, used_type, range, _)| (context, used_type, range))
+            .collect()
+    }
+
+    fn find_uses_with_position<'a>(&mut self, code: &'a str) -> Vec<PositionedUse<'a>> {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -2599,13 +2766,15 @@ impl LanguageParser for TypeScriptParser {
         self.extract_type_uses_recursive(&root, code, &mut uses);
 
         // Extract JSX component usages during find_uses traversal
-        Self::extract_jsx_uses_recursive(&root, code, None, &mut uses);
+        let mut jsx_uses = Vec::new();
+        Self::extract_jsx_uses_recursive(&root, code, None, &mut jsx_uses);
+        push_positioned(&mut uses, &mut jsx_uses, None);
 
         uses
     }
 
     fn find_defines<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
-        let tree = match self.parser.parse(code, None) {
+        let tree = match parse_tree(&mut self.parser, code) {
             Some(tree) => tree,
             None => return Vec::new(),
         };
@@ -2625,7 +2794,7 @@ impl LanguageParser for TypeScriptParser {
     fn find_variable_types<'a>(&mut self, code: &'a str) -> Vec<(&'a str, &'a str, Range)> {
         // Basic TS variable type inference for `const/let/var x = new Type()` patterns
         let mut bindings = Vec::new();
-        if let Some(tree) = self.parser.parse(code, None) {
+        if let Some(tree) = parse_tree(&mut self.parser, code) {
             let root = tree.root_node();
 
             fn walk<'a>(
@@ -3039,6 +3208,44 @@ export { default as MyButton } from './Button';
         );
     }
 
+    #[test]
+    fn test_typescript_member_modifiers_visibility() {
+        let mut parser = TypeScriptParser::new().unwrap();
+        let file_id = FileId::new(1).unwrap();
+        let code = r#"
+export class Counter {
+    private static readonly max: number = 10;
+    protected step = 1;
+    #count = 0;
+    label: string;
+    public reset(): void { this.#count = 0; }
+    private bump() { /* public for tests */ }
+    #log() {}
+}
+"#;
+
+        let mut counter = SymbolCounter::new();
+        let symbols = parser.parse(code, file_id, &mut counter);
+        let member = |name: &str| {
+            symbols
+                .iter()
+                .find(|s| s.name.as_ref() == name)
+                .unwrap_or_else(|| panic!("missing {name}"))
+        };
+
+        assert_eq!(member("max").visibility, Visibility::Private);
+        assert_eq!(
+            member("max").signature.as_deref(),
+            Some("private static readonly max: number")
+        );
+        assert_eq!(member("step").visibility, Visibility::Module);
+        assert_eq!(member("#count").visibility, Visibility::Private);
+        assert_eq!(member("label").visibility, Visibility::Public);
+        assert_eq!(member("reset").visibility, Visibility::Public);
+        assert_eq!(member("bump").visibility, Visibility::Private);
+        assert_eq!(member("#log").visibility, Visibility::Private);
+    }
+
     #[test]
     fn test_typescript_find_variable_types_new_expression() {
         let mut parser = TypeScriptParser::new().unwrap();
@@ -3211,4 +3418,54 @@ export function AnotherComponent() {
 
         println!("✅ JSX component usage tracking working");
     }
+
+    #[test]
+    fn test_find_uses_with_position() {
+        let mut parser = TypeScriptParser::new().unwrap();
+        let code = r#"
+class App {
+    config: Config;
+}
+
+function load(path: string): Config {
+    return new Config();
+}
+
+function apply(config: Config): void {}
+"#;
+
+        let positions: Vec<_> = parser
+            .find_uses_with_position(code)
+            .into_iter()
+            .filter(|(_, used, _, _)| *used == "Config")
+            .map(|(user, _, _, position)| (user, position))
+            .collect();
+
+        assert!(positions.contains(&("App", Some(TypeUsePosition::Field))));
+        assert!(positions.contains(&("load", Some(TypeUsePosition::Return))));
+        assert!(positions.contains(&("apply", Some(TypeUsePosition::Parameter))));
+        assert!(!positions.contains(&("load", Some(TypeUsePosition::Parameter))));
+    }
+
+    #[test]
+    fn test_parse_recovers_after_syntax_errors() {
+        let mut parser = TypeScriptParser::new().unwrap();
+        let manifest_dir = env!("CARGO_MANIFEST_DIR");
+        let test_file =
+            std::path::Path::new(manifest_dir).join("tests/fixtures/typescript/malformed.ts");
+        let code = std::fs::read_to_string(test_file).unwrap();
+
+        let mut counter = SymbolCounter::new();
+        let symbols = parser.parse(&code, FileId::new(1).unwrap(), &mut counter);
+        let names: Vec<&str> = symbols.iter().map(|s| s.name.as_ref()).collect();
+
+        // Before the broken method
+        for name in ["Before", "value", "compute", "Broken", "first"] {
+            assert!(names.contains(&name), "missing {name} in {names:?}");
+        }
+        // After it
+        for name in ["last", "After", "after"] {
+            assert!(names.contains(&name), "missing {name} in {names:?}");
+        }
+    }
 }
@@ -0,0 +1,349 @@
+//! Path normalization shared by indexing, MCP tools, plugins and profiles.
+//!
+//! Paths are stored and compared as strings with forward slashes so results
+//! match across platforms. Windows inputs may also carry verbatim prefixes
+//! (`\\?\C:\...`, `\\?\UNC\server\share`) from `canonicalize()` and differ
+//! only in case, so comparisons go through the helpers here instead of
+//! ad hoc `replace('\\', "/")` calls.
+//!
+//! Output goes through [`display_path`], which renders stored paths in the
+//! [`PathStyle`] selected by `display.path_style` or the CLI flags.
+
+use serde::{Deserialize, Serialize, Serializer};
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// How file paths are rendered in retrieve and MCP output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PathStyle {
+    /// Relative to the workspace root; paths outside it stay absolute
+    #[default]
+    Relative,
+    /// Absolute, resolved against the workspace root
+    Absolute,
+}
+
+impl FromStr for PathStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "relative" => Ok(Self::Relative),
+            "absolute" => Ok(Self::Absolute),
+            other => Err(format!(
+                "Unknown path style '{other}'. Expected 'relative' or 'absolute'"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for PathStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Relative => write!(f, "relative"),
+            Self::Absolute => write!(f, "absolute"),
+        }
+    }
+}
+
+/// Process-wide style used by [`display_path`], set once at startup
+static DISPLAY_STYLE: OnceLock<(PathStyle, Option<PathBuf>)> = OnceLock::new();
+
+/// Normalize a path for storage and comparison.
+///
+/// - Backslashes become forward slashes
+/// - Verbatim prefixes are stripped: `\\?\C:\x` becomes `C:/x` and
+///   `\\?\UNC\server\share` becomes `//server/share`
+/// - Drive letters are uppercased
+///
+/// Case is preserved; use [`paths_equal`] or [`path_contains`] to compare.
+///
+/// ```
+/// use codanna::paths::normalize_path;
+///
+/// assert_eq!(normalize_path(r"\\?\c:\work\src\lib.rs"), "C:/work/src/lib.rs");
+/// assert_eq!(normalize_path("src/lib.rs"), "src/lib.rs");
+/// ```
+pub fn normalize_path(path: impl AsRef<Path>) -> String {
+    let mut normalized = path.as_ref().to_string_lossy().replace('\\', "/");
+
+    if let Some(rest) = normalized.strip_prefix("//?/UNC/") {
+        normalized = format!("//{rest}");
+    } else if let Some(rest) = normalized.strip_prefix("//?/") {
+        normalized = rest.to_string();
+    }
+
+    let bytes = normalized.as_bytes();
+    if bytes.len() >= 2 && bytes[1] == b':' && bytes[0].is_ascii_lowercase() {
+        normalized[..1].make_ascii_uppercase();
+    }
+
+    normalized
+}
+
+/// Whether two paths refer to the same location after normalization.
+///
+/// Case-insensitive on Windows, where the file system is.
+pub fn paths_equal(a: impl AsRef<Path>, b: impl AsRef<Path>) -> bool {
+    equal_with_case(&normalize_path(a), &normalize_path(b), cfg!(windows))
+}
+
+/// Whether `path` contains `needle` after normalizing both.
+///
+/// Used for file filters, so `src\parser` matches `src/parser/mod.rs`.
+/// Case-insensitive on Windows.
+pub fn path_contains(path: impl AsRef<Path>, needle: impl AsRef<Path>) -> bool {
+    contains_with_case(
+        &normalize_path(path),
+        &normalize_path(needle),
+        cfg!(windows),
+    )
+}
+
+/// `path` relative to `root`, normalized, or `None` if it lies outside.
+///
+/// Works on normalized strings, so Windows-style inputs resolve on any
+/// platform. Case-insensitive on Windows.
+pub fn strip_root(path: impl AsRef<Path>, root: impl AsRef<Path>) -> Option<String> {
+    strip_root_with_case(&normalize_path(path), &normalize_path(root), cfg!(windows))
+}
+
+/// Render a stored path in `style`.
+///
+/// Relative stored paths are taken as relative to `workspace_root`. Without a
+/// root, the path is only normalized.
+///
+/// ```
+/// use codanna::paths::{PathStyle, render_path};
+/// use std::path::Path;
+///
+/// let root = Some(Path::new("/work/repo"));
+/// assert_eq!(render_path("src/lib.rs", PathStyle::Absolute, root), "/work/repo/src/lib.rs");
+/// assert_eq!(render_path("/work/repo/src/lib.rs", PathStyle::Relative, root), "src/lib.rs");
+/// ```
+pub fn render_path(stored: &str, style: PathStyle, workspace_root: Option<&Path>) -> String {
+    let normalized = normalize_path(stored);
+    let Some(root) = workspace_root else {
+        return normalized;
+    };
+
+    match style {
+        PathStyle::Relative => strip_root(&normalized, root)
+            .filter(|relative| !relative.is_empty())
+            .unwrap_or_else(|| trim_current_dir(normalized)),
+        PathStyle::Absolute => {
+            if is_absolute(&normalized) {
+                normalized
+            } else {
+                let relative = trim_current_dir(normalized);
+                format!("{}/{relative}", normalize_path(root).trim_end_matches('/'))
+            }
+        }
+    }
+}
+
+/// Set the style used by [`display_path`] for the rest of the process.
+///
+/// Called once after settings and CLI flags are resolved; later calls are
+/// ignored.
+pub fn set_display_style(style: PathStyle, workspace_root: Option<PathBuf>) {
+    let _ = DISPLAY_STYLE.set((style, workspace_root));
+}
+
+thread_local! {
+    /// Style replacing the process-wide one while [`with_path_style`] runs
+    static STYLE_OVERRIDE: Cell<Option<PathStyle>> = const { Cell::new(None) };
+}
+
+/// Run `f` with paths displayed in `style` on this thread.
+///
+/// Used by the query daemon to answer in the style its client asked for.
+pub fn with_path_style<R>(style: PathStyle, f: impl FnOnce() -> R) -> R {
+    let previous = STYLE_OVERRIDE.replace(Some(style));
+    let result = f();
+    STYLE_OVERRIDE.set(previous);
+    result
+}
+
+/// Render a stored path for output in the configured style.
+///
+/// Returns the path unchanged until [`set_display_style`] has been called.
+pub fn display_path(stored: &str) -> Cow<'_, str> {
+    match DISPLAY_STYLE.get() {
+        Some((style, root)) => {
+            let style = STYLE_OVERRIDE.get().unwrap_or(*style);
+            Cow::Owned(render_path(stored, style, root.as_deref()))
+        }
+        None => Cow::Borrowed(stored),
+    }
+}
+
+/// Serde helper that writes a stored path through [`display_path`].
+///
+/// Used on `file_path` fields so JSON output follows `display.path_style`.
+pub fn serialize_display_path<T, S>(path: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: AsRef<str>,
+    S: Serializer,
+{
+    serializer.serialize_str(&display_path(path.as_ref()))
+}
+
+/// Absolute on any platform, so Windows-style paths are recognized on Unix
+fn is_absolute(normalized: &str) -> bool {
+    let bytes = normalized.as_bytes();
+    normalized.starts_with('/') || (bytes.len() >= 2 && bytes[1] == b':')
+}
+
+fn trim_current_dir(path: String) -> String {
+    match path.strip_prefix("./") {
+        Some(rest) => rest.to_string(),
+        None => path,
+    }
+}
+
+fn equal_with_case(a: &str, b: &str, ignore_case: bool) -> bool {
+    if ignore_case {
+        a.eq_ignore_ascii_case(b)
+    } else {
+        a == b
+    }
+}
+
+fn contains_with_case(path: &str, needle: &str, ignore_case: bool) -> bool {
+    if ignore_case {
+        path.to_ascii_lowercase()
+            .contains(&needle.to_ascii_lowercase())
+    } else {
+        path.contains(needle)
+    }
+}
+
+fn strip_root_with_case(path: &str, root: &str, ignore_case: bool) -> Option<String> {
+    let root = root.trim_end_matches('/');
+    let head = path.get(..root.len())?;
+    if !equal_with_case(head, root, ignore_case) {
+        return None;
+    }
+
+    match &path[root.len()..] {
+        "" => Some(String::new()),
+        rest => rest.strip_prefix('/').map(str::to_string),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_windows_paths() {
+        assert_eq!(
+            normalize_path(r"C:\Users\dev\project\src\main.rs"),
+            "C:/Users/dev/project/src/main.rs"
+        );
+        assert_eq!(normalize_path(r"d:\work"), "D:/work");
+        assert_eq!(
+            normalize_path(r"\\?\C:\Users\dev\project"),
+            "C:/Users/dev/project"
+        );
+        assert_eq!(
+            normalize_path(r"\\?\UNC\server\share\repo\lib.rs"),
+            "//server/share/repo/lib.rs"
+        );
+        assert_eq!(
+            normalize_path(r"\\server\share\repo"),
+            "//server/share/repo"
+        );
+        assert_eq!(normalize_path("src/lib.rs"), "src/lib.rs");
+    }
+
+    #[test]
+    fn test_compare_paths() {
+        assert!(paths_equal(r"src\parser\mod.rs", "src/parser/mod.rs"));
+        assert!(paths_equal(r"\\?\C:\repo\a.rs", "C:/repo/a.rs"));
+        assert!(equal_with_case("C:/Repo/A.rs", "c:/repo/a.rs", true));
+        assert!(!equal_with_case("C:/Repo/A.rs", "C:/repo/a.rs", false));
+
+        assert!(path_contains("src/parser/mod.rs", r"src\parser"));
+        assert!(!path_contains("src/parser/mod.rs", "src/lexer"));
+        assert!(contains_with_case("Src/Parser/mod.rs", "src/parser", true));
+        assert!(!contains_with_case(
+            "Src/Parser/mod.rs",
+            "src/parser",
+            false
+        ));
+    }
+
+    #[test]
+    fn test_strip_root() {
+        assert_eq!(
+            strip_root(r"C:\repo\.claude\commands\a.md", r"C:\repo").as_deref(),
+            Some(".claude/commands/a.md")
+        );
+        assert_eq!(
+            strip_root(r"\\?\C:\repo\x.md", "C:/repo/").as_deref(),
+            Some("x.md")
+        );
+        assert_eq!(strip_root("C:/repo", "C:/repo").as_deref(), Some(""));
+        // A sibling sharing the prefix is outside the root
+        assert_eq!(strip_root("C:/repository/x.md", "C:/repo"), None);
+        assert_eq!(strip_root("D:/other/x.md", "C:/repo"), None);
+        assert_eq!(
+            strip_root_with_case("c:/Repo/x.md", "C:/repo", true).as_deref(),
+            Some("x.md")
+        );
+        assert_eq!(strip_root_with_case("c:/Repo/x.md", "C:/repo", false), None);
+    }
+
+    #[test]
+    fn test_render_path() {
+        let root = Some(Path::new("/work/repo"));
+
+        assert_eq!(
+            render_path("./src/lib.rs", PathStyle::Absolute, root),
+            "/work/repo/src/lib.rs"
+        );
+        assert_eq!(
+            render_path("/elsewhere/x.rs", PathStyle::Absolute, root),
+            "/elsewhere/x.rs"
+        );
+        assert_eq!(
+            render_path("./src/lib.rs", PathStyle::Relative, root),
+            "src/lib.rs"
+        );
+        // Paths outside the workspace stay absolute
+        assert_eq!(
+            render_path("/elsewhere/x.rs", PathStyle::Relative, root),
+            "/elsewhere/x.rs"
+        );
+        assert_eq!(
+            render_path(
+                r"src\lib.rs",
+                PathStyle::Absolute,
+                Some(Path::new(r"C:\repo\"))
+            ),
+            "C:/repo/src/lib.rs"
+        );
+        assert_eq!(
+            render_path(
+                r"C:\repo\src\lib.rs",
+                PathStyle::Relative,
+                Some(Path::new("C:/repo"))
+            ),
+            "src/lib.rs"
+        );
+        assert_eq!(
+            render_path("src/lib.rs", PathStyle::Absolute, None),
+            "src/lib.rs"
+        );
+
+        assert_eq!("absolute".parse::<PathStyle>(), Ok(PathStyle::Absolute));
+        assert!("full".parse::<PathStyle>().is_err());
+    }
+}
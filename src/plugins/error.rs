@@ -49,6 +49,16 @@ pub enum PluginError {
     )]
     LockfileCorrupted,
 
+    #[error(
+        "Lockfile version {version} is newer than this codanna supports\nSuggestion: Upgrade codanna to manage plugins in this workspace"
+    )]
+    UnsupportedLockfileVersion { version: String },
+
+    #[error(
+        "Lockfile version {version} is not recognized\nSuggestion: The lockfile may be corrupted; restore it from version control or remove .codanna/plugins/lockfile.json and reinstall plugins"
+    )]
+    UnrecognizedLockfileVersion { version: String },
+
     #[error(
         "Permission denied accessing {path}\nSuggestion: Check file permissions and ensure codanna has write access"
     )]
@@ -122,7 +132,9 @@ impl PluginError {
             | PluginError::InvalidPluginManifest { .. }
             | PluginError::JsonError(_)
             | PluginError::MissingArgument(_)
-            | PluginError::LockfileCorrupted => ExitCode::ConfigError,
+            | PluginError::LockfileCorrupted
+            | PluginError::UnsupportedLockfileVersion { .. }
+            | PluginError::UnrecognizedLockfileVersion { .. } => ExitCode::ConfigError,
             PluginError::FileConflict { .. }
             | PluginError::IntegrityCheckFailed { .. }
             | PluginError::HasDependents { .. }
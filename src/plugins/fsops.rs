@@ -1,6 +1,7 @@
 //! File system operations for plugin installation
 
 use super::error::{PluginError, PluginResult};
+use ignore::overrides::OverrideBuilder;
 use std::io;
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
@@ -36,7 +37,7 @@ pub fn copy_plugin_files(
 
         // Copy file
         std::fs::copy(&source_path, &dest_path)?;
-        let dest_str = dest_path.to_string_lossy().replace('\\', "/");
+        let dest_str = crate::paths::normalize_path(&dest_path);
         copied_files.push(dest_str);
     }
 
@@ -71,7 +72,7 @@ pub fn copy_plugin_payload(
             continue;
         }
 
-        let normalized = relative.to_string_lossy().replace('\\', "/");
+        let normalized = crate::paths::normalize_path(relative);
         if already.contains(&normalized)
             || normalized.starts_with("commands/")
             || normalized.starts_with("agents/")
@@ -96,7 +97,7 @@ pub fn copy_plugin_payload(
         }
 
         std::fs::copy(entry.path(), &dest_path)?;
-        let dest_str = dest_path.to_string_lossy().replace('\\', "/");
+        let dest_str = crate::paths::normalize_path(&dest_path);
         copied_files.push(dest_str);
     }
 
@@ -189,6 +190,53 @@ pub fn calculate_integrity(file_paths: &[String]) -> PluginResult<String> {
     Ok(format!("{result:x}"))
 }
 
+/// Check whether a manifest path is a glob pattern rather than a literal path
+pub fn is_glob_pattern(path: &str) -> bool {
+    path.contains(['*', '?', '['])
+}
+
+/// Expand a glob pattern against the plugin root
+///
+/// Patterns use gitignore syntax, the same as `.codannaignore`: `*` matches within
+/// a single path segment and `**` spans directories. Returns sorted relative paths.
+pub fn expand_glob(base: &Path, pattern: &str) -> PluginResult<Vec<String>> {
+    let mut builder = OverrideBuilder::new(base);
+    builder
+        .add(pattern)
+        .map_err(|e| PluginError::InvalidPluginManifest {
+            reason: format!("Invalid glob pattern '{pattern}': {e}"),
+        })?;
+    let overrides = builder
+        .build()
+        .map_err(|e| PluginError::InvalidPluginManifest {
+            reason: format!("Invalid glob pattern '{pattern}': {e}"),
+        })?;
+
+    let mut matches = Vec::new();
+    for entry in WalkDir::new(base).into_iter() {
+        let entry = entry.map_err(|e| PluginError::IoError(io::Error::other(e)))?;
+        if entry.file_type().is_dir() {
+            continue;
+        }
+
+        let relative = entry
+            .path()
+            .strip_prefix(base)
+            .expect("walkdir entry should be under base");
+
+        if relative.components().any(|c| c.as_os_str() == ".git") {
+            continue;
+        }
+
+        if overrides.matched(relative, false).is_whitelist() {
+            matches.push(crate::paths::normalize_path(relative));
+        }
+    }
+
+    matches.sort();
+    Ok(matches)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -254,4 +302,32 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_expand_glob() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let root = temp_dir.path();
+
+        fs::create_dir_all(root.join("extra/nested/deep"))?;
+        fs::write(root.join("extra/top.md"), "top")?;
+        fs::write(root.join("extra/notes.txt"), "notes")?;
+        fs::write(root.join("extra/nested/inner.md"), "inner")?;
+        fs::write(root.join("extra/nested/deep/deeper.md"), "deeper")?;
+
+        assert!(is_glob_pattern("extra/*.md"));
+        assert!(!is_glob_pattern("extra/top.md"));
+
+        assert_eq!(expand_glob(root, "extra/*.md")?, vec!["extra/top.md"]);
+        assert_eq!(
+            expand_glob(root, "extra/**/*.md")?,
+            vec![
+                "extra/nested/deep/deeper.md",
+                "extra/nested/inner.md",
+                "extra/top.md"
+            ]
+        );
+        assert!(expand_glob(root, "missing/**/*.md")?.is_empty());
+
+        Ok(())
+    }
 }
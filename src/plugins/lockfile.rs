@@ -2,9 +2,19 @@
 
 use super::error::{PluginError, PluginResult};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::collections::HashMap;
 use std::path::Path;
 
+/// Lockfile schema version written by this build
+///
+/// Bump it when `PluginLockEntry` changes shape and add a step to
+/// `PluginLockfile::migrate` that upgrades the previous version.
+pub const LOCKFILE_VERSION: &str = "1.0.0";
+
+/// Version assumed for lockfiles written before the `version` field existed
+const LEGACY_LOCKFILE_VERSION: &str = "0";
+
 /// Lockfile structure for tracking installed plugins
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub struct PluginLockfile {
@@ -37,6 +47,9 @@ pub struct PluginLockEntry {
     pub updated_at: String,
 
     /// Integrity checksum
+    ///
+    /// Empty for entries migrated from lockfiles that did not record one; the
+    /// plugin commands fill it in from the installed files on load.
     pub integrity: String,
 
     /// List of installed files
@@ -71,24 +84,74 @@ impl PluginLockfile {
     /// Create a new empty lockfile
     pub fn new() -> Self {
         Self {
-            version: "1.0.0".to_string(),
+            version: LOCKFILE_VERSION.to_string(),
             plugins: HashMap::new(),
         }
     }
 
     /// Load lockfile from disk
+    ///
+    /// Lockfiles written by older versions are upgraded in memory; they are
+    /// rewritten in the current format the next time the lockfile is saved.
     pub fn load(path: &Path) -> PluginResult<Self> {
         if !path.exists() {
             return Ok(Self::new());
         }
 
         let content = std::fs::read_to_string(path)?;
-        let lockfile: Self =
+        let raw: Value =
             serde_json::from_str(&content).map_err(|_| PluginError::LockfileCorrupted)?;
+        let lockfile: Self = serde_json::from_value(Self::migrate(raw)?)
+            .map_err(|_| PluginError::LockfileCorrupted)?;
 
         Ok(lockfile)
     }
 
+    /// Upgrade a raw lockfile to the current schema
+    ///
+    /// A version above [`LOCKFILE_VERSION`] was written by a newer codanna;
+    /// any other version this build does not know, or one that is not a
+    /// dotted version string, is reported as unrecognized.
+    fn migrate(mut raw: Value) -> PluginResult<Value> {
+        let object = raw.as_object_mut().ok_or(PluginError::LockfileCorrupted)?;
+        let version = match object.get("version") {
+            None => LEGACY_LOCKFILE_VERSION.to_string(),
+            Some(Value::String(version)) => version.clone(),
+            Some(other) => {
+                return Err(PluginError::UnrecognizedLockfileVersion {
+                    version: other.to_string(),
+                });
+            }
+        };
+
+        match version.as_str() {
+            LOCKFILE_VERSION => {}
+            LEGACY_LOCKFILE_VERSION => {
+                // v0 entries may predate MCP tracking and integrity checksums
+                if let Some(plugins) = object.get_mut("plugins").and_then(Value::as_object_mut) {
+                    for entry in plugins.values_mut().filter_map(Value::as_object_mut) {
+                        entry
+                            .entry("mcp_keys")
+                            .or_insert_with(|| Value::Array(Vec::new()));
+                        entry
+                            .entry("integrity")
+                            .or_insert_with(|| Value::String(String::new()));
+                    }
+                }
+                object.insert(
+                    "version".to_string(),
+                    Value::String(LOCKFILE_VERSION.to_string()),
+                );
+            }
+            _ if version_parts(&version) > version_parts(LOCKFILE_VERSION) => {
+                return Err(PluginError::UnsupportedLockfileVersion { version });
+            }
+            _ => return Err(PluginError::UnrecognizedLockfileVersion { version }),
+        }
+
+        Ok(raw)
+    }
+
     /// Save lockfile to disk
     pub fn save(&self, path: &Path) -> PluginResult<()> {
         // Ensure parent directory exists
@@ -124,7 +187,11 @@ impl PluginLockfile {
     /// Find which plugin owns a file
     pub fn find_file_owner(&self, file_path: &str) -> Option<&str> {
         for (name, entry) in &self.plugins {
-            if entry.files.contains(&file_path.to_string()) {
+            if entry
+                .files
+                .iter()
+                .any(|file| crate::paths::paths_equal(file, file_path))
+            {
                 return Some(name);
             }
         }
@@ -132,6 +199,14 @@ impl PluginLockfile {
     }
 }
 
+/// Numeric components of a dotted version such as `1.0.0`
+///
+/// `None` unless every component is a number, so it orders below any
+/// parsed version.
+fn version_parts(version: &str) -> Option<Vec<u64>> {
+    version.split('.').map(|part| part.parse().ok()).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -139,7 +214,7 @@ mod tests {
     #[test]
     fn test_new_lockfile() {
         let lockfile = PluginLockfile::new();
-        assert_eq!(lockfile.version, "1.0.0");
+        assert_eq!(lockfile.version, LOCKFILE_VERSION);
         assert!(lockfile.plugins.is_empty());
     }
 
@@ -185,5 +260,81 @@ mod tests {
             Some("test-plugin")
         );
         assert_eq!(lockfile.find_file_owner(".claude/commands/other.md"), None);
+        // Windows-style separators resolve to the same entry
+        assert_eq!(
+            lockfile.find_file_owner(r".claude\commands\test.md"),
+            Some("test-plugin")
+        );
+    }
+
+    #[test]
+    fn test_load_migrates_v0_lockfile() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("lockfile.json");
+        std::fs::write(
+            &path,
+            r#"{
+    "plugins": {
+        "old-plugin": {
+            "name": "old-plugin",
+            "version": "0.1.0",
+            "commit": "abc123",
+            "marketplace_url": "https://example.com",
+            "installed_at": "2025-01-11",
+            "updated_at": "2025-01-11",
+            "files": [".claude/commands/old-plugin/old.md"]
+        }
+    }
+}"#,
+        )
+        .unwrap();
+
+        let lockfile = PluginLockfile::load(&path).unwrap();
+        assert_eq!(lockfile.version, LOCKFILE_VERSION);
+        let entry = lockfile.get_plugin("old-plugin").unwrap();
+        assert!(entry.mcp_keys.is_empty());
+        assert!(entry.integrity.is_empty());
+        assert!(entry.source.is_none());
+
+        lockfile.save(&path).unwrap();
+        let saved: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved["version"], LOCKFILE_VERSION);
+        assert_eq!(
+            saved["plugins"]["old-plugin"]["mcp_keys"],
+            Value::Array(vec![])
+        );
+        assert_eq!(saved["plugins"]["old-plugin"]["integrity"], "");
+    }
+
+    #[test]
+    fn test_load_rejects_newer_lockfile() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("lockfile.json");
+        std::fs::write(&path, r#"{"version": "9.0.0", "plugins": {}}"#).unwrap();
+
+        let err = PluginLockfile::load(&path).unwrap_err();
+        assert!(matches!(
+            err,
+            PluginError::UnsupportedLockfileVersion { version } if version == "9.0.0"
+        ));
+    }
+
+    #[test]
+    fn test_load_rejects_unrecognized_lockfile_version() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("lockfile.json");
+
+        for version in [r#""garbage""#, r#""0.5.0""#, "2"] {
+            std::fs::write(
+                &path,
+                format!(r#"{{"version": {version}, "plugins": {{}}}}"#),
+            )
+            .unwrap();
+            let err = PluginLockfile::load(&path).unwrap_err();
+            assert!(
+                matches!(err, PluginError::UnrecognizedLockfileVersion { .. }),
+                "{version}: {err}"
+            );
+        }
     }
 }
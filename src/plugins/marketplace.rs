@@ -422,10 +422,10 @@ fn combine_paths(base: Option<&str>, child: &str) -> PluginResult<String> {
     }
     let child_sanitized = sanitize(child);
     if child_sanitized.is_empty() || child_sanitized == "." {
-        return Ok(path.to_string_lossy().replace('\\', "/"));
+        return Ok(crate::paths::normalize_path(&path));
     }
     path.push(child_sanitized);
-    Ok(path.to_string_lossy().replace('\\', "/"))
+    Ok(crate::paths::normalize_path(&path))
 }
 
 fn sanitize(path: &str) -> String {
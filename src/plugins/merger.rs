@@ -92,8 +92,21 @@ pub fn check_mcp_conflicts(
     force: bool,
     allowed_keys: &HashSet<String>,
 ) -> PluginResult<()> {
+    let conflicts = find_mcp_conflicts(project_mcp_path, plugin_servers, allowed_keys)?;
+    match conflicts.into_iter().next() {
+        Some(key) if !force => Err(PluginError::McpServerConflict { key }),
+        _ => Ok(()),
+    }
+}
+
+/// List plugin server keys already present in the project's .mcp.json
+pub fn find_mcp_conflicts(
+    project_mcp_path: &Path,
+    plugin_servers: &Value,
+    allowed_keys: &HashSet<String>,
+) -> PluginResult<Vec<String>> {
     if !project_mcp_path.exists() {
-        return Ok(());
+        return Ok(Vec::new());
     }
 
     let content = std::fs::read_to_string(project_mcp_path)?;
@@ -106,15 +119,16 @@ pub fn check_mcp_conflicts(
             reason: "Invalid .mcp.json structure".to_string(),
         })?;
 
+    let mut conflicts = Vec::new();
     if let Some(plugin_servers_obj) = plugin_servers.as_object() {
         for key in plugin_servers_obj.keys() {
-            if servers.contains_key(key) && !force && !allowed_keys.contains(key) {
-                return Err(PluginError::McpServerConflict { key: key.clone() });
+            if servers.contains_key(key) && !allowed_keys.contains(key) {
+                conflicts.push(key.clone());
             }
         }
     }
 
-    Ok(())
+    Ok(conflicts)
 }
 
 /// Remove plugin MCP servers from project's .mcp.json
@@ -239,6 +253,9 @@ mod tests {
         let check = check_mcp_conflicts(&mcp_path, &plugin_servers, false, &HashSet::new());
         assert!(matches!(check, Err(PluginError::McpServerConflict { .. })));
 
+        let conflicts = find_mcp_conflicts(&mcp_path, &plugin_servers, &HashSet::new())?;
+        assert_eq!(conflicts, vec!["conflicting".to_string()]);
+
         Ok(())
     }
 
@@ -20,7 +20,7 @@ use marketplace::{MarketplaceManifest, ResolvedPluginSource};
 use plugin::{HookSpec, PathSpec, PluginManifest};
 use resolver::{clone_repository, extract_subdirectory};
 use serde_json::Value;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
@@ -46,6 +46,59 @@ struct PreparedPlugin {
     commit_sha: String,
     mcp_servers: Option<Value>,
     source: LockfilePluginSource,
+    planned_writes: Vec<PlannedWrite>,
+    mcp_conflicts: Vec<String>,
+}
+
+/// Lockfile commit prefix for plugins installed from local directories
+const LOCAL_COMMIT_PREFIX: &str = "local:";
+
+/// Where a marketplace is read from
+enum MarketplaceSource {
+    /// Cloned with git
+    Git(String),
+    /// Read directly from a directory on disk
+    Local(PathBuf),
+}
+
+impl MarketplaceSource {
+    /// Classify a marketplace URL given on the command line
+    ///
+    /// `file://` URLs and paths pointing at a directory that is not a git
+    /// repository are read from disk; everything else is cloned, so local git
+    /// checkouts keep supporting `--ref`.
+    fn for_url(url: &str) -> Self {
+        let path = Path::new(url.strip_prefix("file://").unwrap_or(url));
+        if path.is_dir() && !path.join(".git").exists() {
+            let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+            Self::Local(path)
+        } else {
+            Self::Git(url.to_string())
+        }
+    }
+
+    /// The source an installed plugin was fetched from
+    fn for_entry(entry: &PluginLockEntry) -> Self {
+        if is_local_commit(&entry.commit) {
+            Self::Local(PathBuf::from(&entry.marketplace_url))
+        } else {
+            Self::for_url(&entry.marketplace_url)
+        }
+    }
+
+    /// The URL recorded in the lockfile
+    fn url(&self) -> String {
+        match self {
+            Self::Git(url) => url.clone(),
+            Self::Local(dir) => dir.display().to_string(),
+        }
+    }
+}
+
+/// Destination a plugin install would write, with the owner of any file it would replace
+struct PlannedWrite {
+    dest: PathBuf,
+    conflict_owner: Option<String>,
 }
 
 struct ExistingPluginBackup {
@@ -80,6 +133,9 @@ impl WorkspacePaths {
 }
 
 /// Install a plugin from a marketplace
+///
+/// `marketplace_url` is a git URL, or a local directory: `file://` URLs and paths
+/// that are not git repositories are read straight from disk without cloning.
 pub fn add_plugin(
     settings: &Settings,
     marketplace_url: &str,
@@ -88,6 +144,52 @@ pub fn add_plugin(
     force: bool,
     dry_run: bool,
 ) -> Result<(), PluginError> {
+    let marketplace = MarketplaceSource::for_url(marketplace_url);
+    install_plugin(settings, &marketplace, plugin_name, git_ref, force, dry_run)
+}
+
+/// Install a plugin from a local marketplace or plugin directory, without git
+///
+/// `dir` holds either `.claude-plugin/marketplace.json` or, for a single plugin,
+/// `.claude-plugin/plugin.json`. Its files are read as they are on disk, even when
+/// it is a git checkout; the lockfile records a content hash instead of a commit.
+pub fn add_plugin_from_dir(
+    settings: &Settings,
+    dir: &Path,
+    plugin_name: &str,
+    force: bool,
+    dry_run: bool,
+) -> Result<(), PluginError> {
+    let dir = if dir.is_absolute() {
+        dir.to_path_buf()
+    } else {
+        std::env::current_dir()?.join(dir)
+    };
+    if !dir.is_dir() {
+        return Err(PluginError::MarketplaceNotFound {
+            url: dir.display().to_string(),
+        });
+    }
+    install_plugin(
+        settings,
+        &MarketplaceSource::Local(dir),
+        plugin_name,
+        None,
+        force,
+        dry_run,
+    )
+}
+
+fn install_plugin(
+    settings: &Settings,
+    marketplace: &MarketplaceSource,
+    plugin_name: &str,
+    git_ref: Option<&str>,
+    force: bool,
+    dry_run: bool,
+) -> Result<(), PluginError> {
+    let marketplace_url = marketplace.url();
+    let marketplace_url = marketplace_url.as_str();
     let workspace_root = resolve_workspace_root(settings)?;
     let paths = WorkspacePaths::for_root(workspace_root.clone());
 
@@ -106,9 +208,8 @@ pub fn add_plugin(
         &paths,
         &lockfile,
         plugin_name,
-        marketplace_url,
+        marketplace,
         git_ref,
-        force,
         previous_entry.as_ref(),
     )?;
 
@@ -121,10 +222,12 @@ pub fn add_plugin(
             println!("  Force mode: would overwrite conflicts");
         }
         println!("  Target workspace: {}", paths.root.display());
-        print_dry_run_summary(&plan);
+        print_dry_run_summary(&paths, &plan, force);
         return Ok(());
     }
 
+    check_plan_conflicts(&plan, force)?;
+
     ensure_workspace_layout(&paths)?;
 
     let entry = execute_install_with_plan(
@@ -245,9 +348,8 @@ pub fn update_plugin(
         &paths,
         &lockfile,
         plugin_name,
-        &existing.marketplace_url,
+        &MarketplaceSource::for_entry(&existing),
         git_ref,
-        force,
         Some(&existing),
     )?;
 
@@ -260,10 +362,12 @@ pub fn update_plugin(
             println!("  Force mode: would overwrite local changes");
         }
         println!("  Target workspace: {}", paths.root.display());
-        print_dry_run_summary(&plan);
+        print_dry_run_summary(&paths, &plan, force);
         return Ok(());
     }
 
+    check_plan_conflicts(&plan, force)?;
+
     if !force && plan.commit_sha == existing.commit {
         match verify_entry(&paths, &existing, false) {
             Ok(_) => {
@@ -340,6 +444,11 @@ pub fn list_plugins(settings: &Settings, verbose: bool, json: bool) -> Result<()
 }
 
 fn resolve_remote_commit(existing: &PluginLockEntry, override_ref: Option<&str>) -> Option<String> {
+    // Local installs have no remote; prepare_plugin re-hashes the directory instead
+    if is_local_commit(&existing.commit) {
+        return None;
+    }
+
     match existing.source.as_ref() {
         Some(LockfilePluginSource::Git { url, git_ref, .. }) => {
             let reference = override_ref.or(git_ref.as_deref()).unwrap_or("HEAD");
@@ -385,6 +494,56 @@ pub fn verify_plugin(
     Ok(())
 }
 
+/// Verify a plugin and restore its locked files if the integrity check fails
+///
+/// Re-fetches the plugin at the commit recorded in the lockfile and restores only
+/// the files tracked by the lockfile entry, then re-merges any drifted MCP servers.
+pub fn repair_plugin(
+    settings: &Settings,
+    plugin_name: &str,
+    verbose: bool,
+) -> Result<(), PluginError> {
+    let workspace_root = resolve_workspace_root(settings)?;
+    let paths = WorkspacePaths::for_root(workspace_root.clone());
+    let lockfile = load_lockfile(&paths)?;
+
+    let entry = match lockfile.get_plugin(plugin_name) {
+        Some(entry) => entry,
+        None => {
+            return Err(PluginError::NotInstalled {
+                name: plugin_name.to_string(),
+            });
+        }
+    };
+
+    match verify_entry(&paths, entry, verbose) {
+        Ok(()) => {
+            println!("Plugin '{plugin_name}' verified successfully");
+            return Ok(());
+        }
+        Err(PluginError::IntegrityCheckFailed {
+            expected, actual, ..
+        }) => {
+            if verbose {
+                println!("  Integrity mismatch: expected {expected}, got {actual}");
+                println!("  Re-fetching commit {} to repair...", entry.commit);
+            }
+        }
+        Err(e) => return Err(e),
+    }
+
+    let plugin_dir = fetch_locked_plugin(entry)?;
+    let restored = restore_locked_files(&paths, entry, plugin_dir.path(), verbose)?;
+    let remerged = restore_locked_mcp_servers(&paths, entry, plugin_dir.path())?;
+
+    verify_entry(&paths, entry, verbose)?;
+
+    println!(
+        "Plugin '{plugin_name}' repaired ({restored} files restored, {remerged} MCP servers re-merged) and verified"
+    );
+    Ok(())
+}
+
 /// Verify all installed plugins
 pub fn verify_all_plugins(settings: &Settings, verbose: bool) -> Result<(), PluginError> {
     let workspace_root = resolve_workspace_root(settings)?;
@@ -421,7 +580,16 @@ fn ensure_workspace_layout(paths: &WorkspacePaths) -> PluginResult<()> {
 }
 
 fn load_lockfile(paths: &WorkspacePaths) -> PluginResult<PluginLockfile> {
-    PluginLockfile::load(&paths.lockfile_path)
+    let mut lockfile = PluginLockfile::load(&paths.lockfile_path)?;
+
+    // Entries migrated from lockfiles without checksums trust the files installed now
+    for entry in lockfile.plugins.values_mut() {
+        if entry.integrity.is_empty() {
+            entry.integrity = installed_integrity(paths, entry)?;
+        }
+    }
+
+    Ok(lockfile)
 }
 
 fn save_lockfile(paths: &WorkspacePaths, lockfile: &PluginLockfile) -> PluginResult<()> {
@@ -431,13 +599,7 @@ fn save_lockfile(paths: &WorkspacePaths, lockfile: &PluginLockfile) -> PluginRes
 fn to_absolute_paths(paths: &WorkspacePaths, files: &[String]) -> Vec<String> {
     files
         .iter()
-        .map(|relative| {
-            paths
-                .root
-                .join(relative)
-                .to_string_lossy()
-                .replace('\\', "/")
-        })
+        .map(|relative| crate::paths::normalize_path(paths.root.join(relative)))
         .collect()
 }
 
@@ -446,10 +608,8 @@ fn resolve_file_owner(
     lockfile: &PluginLockfile,
     path: &Path,
 ) -> Option<String> {
-    let relative = path
-        .strip_prefix(&paths.root)
-        .map(|p| p.to_string_lossy().replace('\\', "/"))
-        .unwrap_or_else(|_| path.to_string_lossy().replace('\\', "/"));
+    let relative = crate::paths::strip_root(path, &paths.root)
+        .unwrap_or_else(|| crate::paths::normalize_path(path));
     lockfile
         .find_file_owner(&relative)
         .map(|name| name.to_string())
@@ -513,19 +673,23 @@ fn restore_existing_plugin(
     Ok(())
 }
 
-fn verify_entry(
-    paths: &WorkspacePaths,
-    entry: &PluginLockEntry,
-    verbose: bool,
-) -> PluginResult<()> {
+/// Integrity checksum of a plugin's files as currently installed
+fn installed_integrity(paths: &WorkspacePaths, entry: &PluginLockEntry) -> PluginResult<String> {
     let filtered: Vec<String> = entry
         .files
         .iter()
         .filter(|path| path.as_str() != ".mcp.json")
         .cloned()
         .collect();
-    let absolute_files = to_absolute_paths(paths, &filtered);
-    let actual = calculate_integrity(&absolute_files)?;
+    calculate_integrity(&to_absolute_paths(paths, &filtered))
+}
+
+fn verify_entry(
+    paths: &WorkspacePaths,
+    entry: &PluginLockEntry,
+    verbose: bool,
+) -> PluginResult<()> {
+    let actual = installed_integrity(paths, entry)?;
 
     if actual != entry.integrity {
         return Err(PluginError::IntegrityCheckFailed {
@@ -662,6 +826,17 @@ fn add_single_path(
     files: &mut HashSet<String>,
 ) -> PluginResult<()> {
     let sanitized = sanitize_manifest_path(path);
+    if fsops::is_glob_pattern(&sanitized) {
+        let matches = fsops::expand_glob(plugin_root, &sanitized)?;
+        if matches.is_empty() {
+            return Err(PluginError::InvalidPluginManifest {
+                reason: format!("Glob pattern '{path}' did not match any files"),
+            });
+        }
+        files.extend(matches);
+        return Ok(());
+    }
+
     if sanitized == "." {
         return Err(PluginError::InvalidPluginManifest {
             reason: format!("Referenced path '{path}' must not point to plugin root"),
@@ -682,8 +857,8 @@ fn add_single_path(
 
 fn collect_files_for_path(base: &Path, target: &Path) -> PluginResult<Vec<String>> {
     if target.is_file() {
-        let rel = target.strip_prefix(base).unwrap_or(target).to_path_buf();
-        let rel_str = rel.to_string_lossy().replace('\\', "/");
+        let rel_str = crate::paths::strip_root(target, base)
+            .unwrap_or_else(|| crate::paths::normalize_path(target));
         return Ok(vec![rel_str]);
     }
 
@@ -694,12 +869,10 @@ fn collect_files_for_path(base: &Path, target: &Path) -> PluginResult<Vec<String
             if entry.file_type().is_dir() {
                 continue;
             }
-            let rel = entry
-                .path()
-                .strip_prefix(base)
-                .unwrap_or(entry.path())
-                .to_path_buf();
-            files.push(rel.to_string_lossy().replace('\\', "/"));
+            files.push(
+                crate::paths::strip_root(entry.path(), base)
+                    .unwrap_or_else(|| crate::paths::normalize_path(entry.path())),
+            );
         }
         return Ok(files);
     }
@@ -722,6 +895,10 @@ fn load_plugin_mcp(plugin_root: &Path, manifest: &PluginManifest) -> PluginResul
         return merger::load_plugin_mcp_servers(plugin_root, spec).map(Some);
     }
 
+    load_default_mcp(plugin_root)
+}
+
+fn load_default_mcp(plugin_root: &Path) -> PluginResult<Option<Value>> {
     let default_mcp = plugin_root.join(".mcp.json");
     if default_mcp.exists() {
         let content = fs::read_to_string(&default_mcp)?;
@@ -739,12 +916,10 @@ fn load_plugin_mcp(plugin_root: &Path, manifest: &PluginManifest) -> PluginResul
 fn normalize_paths(workspace_root: &Path, files: Vec<String>) -> Vec<String> {
     let mut unique = HashSet::new();
     for file in files {
-        let path = PathBuf::from(&file);
-        let rel = path
-            .strip_prefix(workspace_root)
-            .unwrap_or(&path)
-            .to_path_buf();
-        unique.insert(rel.to_string_lossy().replace('\\', "/"));
+        unique.insert(
+            crate::paths::strip_root(&file, workspace_root)
+                .unwrap_or_else(|| crate::paths::normalize_path(&file)),
+        );
     }
     let mut list: Vec<_> = unique.into_iter().collect();
     list.sort();
@@ -768,95 +943,129 @@ fn prepare_plugin(
     paths: &WorkspacePaths,
     lockfile: &PluginLockfile,
     plugin_name: &str,
-    marketplace_url: &str,
+    marketplace: &MarketplaceSource,
     git_ref: Option<&str>,
-    force: bool,
     previous_entry: Option<&PluginLockEntry>,
 ) -> Result<PreparedPlugin, PluginError> {
-    let marketplace_dir = tempdir()?;
-    let commit_sha = clone_repository(marketplace_url, marketplace_dir.path(), git_ref)?;
-
-    let marketplace_manifest_path = marketplace_dir
-        .path()
-        .join(".claude-plugin/marketplace.json");
-    let marketplace_manifest = MarketplaceManifest::from_file(&marketplace_manifest_path)?;
-    let plugin_entry = marketplace_manifest
-        .find_plugin(plugin_name)
-        .ok_or_else(|| PluginError::PluginNotFound {
-            name: plugin_name.to_string(),
-        })?;
-
-    let resolved_source = plugin_entry.resolve_source(marketplace_manifest.metadata.as_ref())?;
-    let mut effective_commit = commit_sha.clone();
-
-    let (plugin_dir, source_for_lockfile) = match &resolved_source {
-        ResolvedPluginSource::MarketplacePath { relative } => {
-            let plugin_dir = tempdir()?;
-            extract_subdirectory(marketplace_dir.path(), relative, plugin_dir.path())?;
-            (
-                plugin_dir,
-                LockfilePluginSource::MarketplacePath {
-                    relative: relative.clone(),
-                },
-            )
+    // The clone must outlive extraction of the plugin payload below
+    let mut marketplace_clone = None;
+    let (marketplace_root, mut effective_commit) = match marketplace {
+        MarketplaceSource::Git(url) => {
+            let marketplace_dir = tempdir()?;
+            let commit_sha = clone_repository(url, marketplace_dir.path(), git_ref)?;
+            let root = marketplace_dir.path().to_path_buf();
+            marketplace_clone = Some(marketplace_dir);
+            (root, Some(commit_sha))
         }
-        ResolvedPluginSource::Git {
-            url,
-            git_ref,
-            subdir,
-        } => {
-            let repo_dir = tempdir()?;
-            let repo_commit = resolver::clone_repository(url, repo_dir.path(), git_ref.as_deref())?;
-            effective_commit = repo_commit;
-            let plugin_dir = if let Some(path) = subdir {
-                let plugin_dir = tempdir()?;
-                extract_subdirectory(repo_dir.path(), path, plugin_dir.path())?;
-                plugin_dir
-            } else {
-                repo_dir
-            };
-            (
-                plugin_dir,
-                LockfilePluginSource::Git {
-                    url: url.clone(),
-                    git_ref: git_ref.clone(),
-                    subdir: subdir.clone(),
-                },
-            )
+        MarketplaceSource::Local(dir) => {
+            if let Some(reference) = git_ref {
+                return Err(PluginError::InvalidReference {
+                    ref_name: reference.to_string(),
+                    reason: "local plugin sources are read from disk and have no git history"
+                        .to_string(),
+                });
+            }
+            (dir.clone(), None)
         }
     };
 
-    let plugin_manifest_path = plugin_dir.path().join(".claude-plugin/plugin.json");
-    let manifest = if plugin_manifest_path.exists() {
-        PluginManifest::from_file(&plugin_manifest_path)?
-    } else if plugin_entry.strict {
-        return Err(PluginError::InvalidPluginManifest {
-            reason: format!(
-                "Plugin '{plugin_name}' requires .claude-plugin/plugin.json but none was found"
-            ),
-        });
+    let marketplace_manifest_path = marketplace_root.join(".claude-plugin/marketplace.json");
+    let (plugin_dir, source_for_lockfile, manifest) = if matches!(
+        marketplace,
+        MarketplaceSource::Local(_)
+    ) && !marketplace_manifest_path.exists()
+    {
+        prepare_local_plugin_dir(&marketplace_root, plugin_name)?
     } else {
-        plugin_entry.to_plugin_manifest()?
+        let marketplace_manifest = MarketplaceManifest::from_file(&marketplace_manifest_path)?;
+        let plugin_entry = marketplace_manifest
+            .find_plugin(plugin_name)
+            .ok_or_else(|| PluginError::PluginNotFound {
+                name: plugin_name.to_string(),
+            })?;
+
+        let resolved_source =
+            plugin_entry.resolve_source(marketplace_manifest.metadata.as_ref())?;
+
+        let (plugin_dir, source_for_lockfile) = match &resolved_source {
+            ResolvedPluginSource::MarketplacePath { relative } => {
+                let plugin_dir = tempdir()?;
+                extract_subdirectory(&marketplace_root, relative, plugin_dir.path())?;
+                (
+                    plugin_dir,
+                    LockfilePluginSource::MarketplacePath {
+                        relative: relative.clone(),
+                    },
+                )
+            }
+            ResolvedPluginSource::Git {
+                url,
+                git_ref,
+                subdir,
+            } => {
+                let repo_dir = tempdir()?;
+                let repo_commit =
+                    resolver::clone_repository(url, repo_dir.path(), git_ref.as_deref())?;
+                effective_commit = Some(repo_commit);
+                let plugin_dir = if let Some(path) = subdir {
+                    let plugin_dir = tempdir()?;
+                    extract_subdirectory(repo_dir.path(), path, plugin_dir.path())?;
+                    plugin_dir
+                } else {
+                    repo_dir
+                };
+                (
+                    plugin_dir,
+                    LockfilePluginSource::Git {
+                        url: url.clone(),
+                        git_ref: git_ref.clone(),
+                        subdir: subdir.clone(),
+                    },
+                )
+            }
+        };
+
+        let plugin_manifest_path = plugin_dir.path().join(".claude-plugin/plugin.json");
+        let manifest = if plugin_manifest_path.exists() {
+            PluginManifest::from_file(&plugin_manifest_path)?
+        } else if plugin_entry.strict {
+            return Err(PluginError::InvalidPluginManifest {
+                reason: format!(
+                    "Plugin '{plugin_name}' requires .claude-plugin/plugin.json but none was found"
+                ),
+            });
+        } else {
+            plugin_entry.to_plugin_manifest()?
+        };
+        (plugin_dir, source_for_lockfile, manifest)
     };
+    drop(marketplace_clone);
+
     let component_files = collect_component_files(plugin_dir.path(), &manifest)?;
+    let effective_commit = match effective_commit {
+        Some(commit) => commit,
+        None => local_content_commit(plugin_dir.path())?,
+    };
 
-    check_file_conflicts(
+    let planned_writes = plan_file_writes(
         paths,
         lockfile,
         plugin_name,
         &component_files,
         plugin_dir.path(),
-        force,
     )?;
 
     let mcp_servers = load_plugin_mcp(plugin_dir.path(), &manifest)?;
 
-    if let Some(servers) = &mcp_servers {
-        let allowed_keys: HashSet<String> = previous_entry
-            .map(|entry| entry.mcp_keys.iter().cloned().collect())
-            .unwrap_or_default();
-        merger::check_mcp_conflicts(&paths.mcp_path, servers, force, &allowed_keys)?;
-    }
+    let mcp_conflicts = match &mcp_servers {
+        Some(servers) => {
+            let allowed_keys: HashSet<String> = previous_entry
+                .map(|entry| entry.mcp_keys.iter().cloned().collect())
+                .unwrap_or_default();
+            merger::find_mcp_conflicts(&paths.mcp_path, servers, &allowed_keys)?
+        }
+        None => Vec::new(),
+    };
 
     Ok(PreparedPlugin {
         plugin_dir,
@@ -865,33 +1074,86 @@ fn prepare_plugin(
         commit_sha: effective_commit,
         mcp_servers,
         source: source_for_lockfile,
+        planned_writes,
+        mcp_conflicts,
     })
 }
 
-fn check_file_conflicts(
+/// Copy a standalone local plugin directory (one with `.claude-plugin/plugin.json`)
+///
+/// The directory is recorded as a marketplace path of `.` so repair and update
+/// read it back from the same location.
+fn prepare_local_plugin_dir(
+    dir: &Path,
+    plugin_name: &str,
+) -> PluginResult<(TempDir, LockfilePluginSource, PluginManifest)> {
+    let plugin_manifest_path = dir.join(".claude-plugin/plugin.json");
+    if !plugin_manifest_path.exists() {
+        return Err(PluginError::MarketplaceNotFound {
+            url: dir.display().to_string(),
+        });
+    }
+
+    let manifest = PluginManifest::from_file(&plugin_manifest_path)?;
+    if manifest.name != plugin_name {
+        return Err(PluginError::PluginNotFound {
+            name: plugin_name.to_string(),
+        });
+    }
+
+    let plugin_dir = tempdir()?;
+    extract_subdirectory(dir, ".", plugin_dir.path())?;
+    Ok((
+        plugin_dir,
+        LockfilePluginSource::MarketplacePath {
+            relative: ".".to_string(),
+        },
+        manifest,
+    ))
+}
+
+/// Content hash standing in for a commit SHA on plugins installed from local directories
+fn local_content_commit(plugin_root: &Path) -> PluginResult<String> {
+    let mut files = Vec::new();
+    for entry in WalkDir::new(plugin_root).sort_by_file_name() {
+        let entry = entry.map_err(|e| PluginError::IoError(io::Error::other(e)))?;
+        let relative = entry
+            .path()
+            .strip_prefix(plugin_root)
+            .unwrap_or(entry.path());
+        if entry.file_type().is_file() && !relative.components().any(|c| c.as_os_str() == ".git") {
+            files.push(entry.path().to_string_lossy().into_owned());
+        }
+    }
+    Ok(format!(
+        "{LOCAL_COMMIT_PREFIX}{}",
+        calculate_integrity(&files)?
+    ))
+}
+
+/// Whether a lockfile commit was produced by `local_content_commit`
+fn is_local_commit(commit: &str) -> bool {
+    commit.starts_with(LOCAL_COMMIT_PREFIX)
+}
+
+/// Compute every destination the install would write, mirroring the copy order of
+/// `copy_plugin_files` followed by `copy_plugin_payload`
+fn plan_file_writes(
     paths: &WorkspacePaths,
     lockfile: &PluginLockfile,
     plugin_name: &str,
     component_files: &[String],
     plugin_dir: &Path,
-    force: bool,
-) -> PluginResult<()> {
+) -> PluginResult<Vec<PlannedWrite>> {
+    let mut writes = Vec::new();
+
     for relative in component_files {
         let dest = calculate_dest_path(&paths.root, plugin_name, relative);
-        if dest.exists() {
-            match resolve_file_owner(paths, lockfile, &dest) {
-                Some(owner) if owner != plugin_name && !force => {
-                    return Err(PluginError::FileConflict { path: dest, owner });
-                }
-                None if !force => {
-                    return Err(PluginError::FileConflict {
-                        path: dest,
-                        owner: "unknown".to_string(),
-                    });
-                }
-                _ => {}
-            }
-        }
+        let conflict_owner = conflicting_owner(paths, lockfile, plugin_name, &dest);
+        writes.push(PlannedWrite {
+            dest,
+            conflict_owner,
+        });
     }
 
     for entry in WalkDir::new(plugin_dir).into_iter() {
@@ -909,7 +1171,7 @@ fn check_file_conflicts(
             continue;
         }
 
-        let relative_str = relative.to_string_lossy().replace('\\', "/");
+        let relative_str = crate::paths::normalize_path(relative);
         if component_files.contains(&relative_str)
             || relative_str.starts_with("commands/")
             || relative_str.starts_with("agents/")
@@ -920,26 +1182,57 @@ fn check_file_conflicts(
         }
 
         let dest = paths.plugins_dir.join(plugin_name).join(relative);
-        if dest.exists() {
-            match resolve_file_owner(paths, lockfile, &dest) {
-                Some(owner) if owner != plugin_name && !force => {
-                    return Err(PluginError::FileConflict { path: dest, owner });
-                }
-                None if !force => {
-                    return Err(PluginError::FileConflict {
-                        path: dest,
-                        owner: "unknown".to_string(),
-                    });
-                }
-                _ => {}
-            }
-        }
+        let conflict_owner = conflicting_owner(paths, lockfile, plugin_name, &dest);
+        writes.push(PlannedWrite {
+            dest,
+            conflict_owner,
+        });
+    }
+
+    Ok(writes)
+}
+
+/// Owner of an existing file at `dest` that this plugin may not overwrite without `--force`
+fn conflicting_owner(
+    paths: &WorkspacePaths,
+    lockfile: &PluginLockfile,
+    plugin_name: &str,
+    dest: &Path,
+) -> Option<String> {
+    if !dest.exists() {
+        return None;
+    }
+    match resolve_file_owner(paths, lockfile, dest) {
+        Some(owner) if owner == plugin_name => None,
+        Some(owner) => Some(owner),
+        None => Some("unknown".to_string()),
+    }
+}
+
+fn check_plan_conflicts(plan: &PreparedPlugin, force: bool) -> PluginResult<()> {
+    if force {
+        return Ok(());
+    }
+
+    if let Some(write) = plan
+        .planned_writes
+        .iter()
+        .find(|w| w.conflict_owner.is_some())
+    {
+        return Err(PluginError::FileConflict {
+            path: write.dest.clone(),
+            owner: write.conflict_owner.clone().unwrap_or_default(),
+        });
+    }
+
+    if let Some(key) = plan.mcp_conflicts.first() {
+        return Err(PluginError::McpServerConflict { key: key.clone() });
     }
 
     Ok(())
 }
 
-fn print_dry_run_summary(plan: &PreparedPlugin) {
+fn print_dry_run_summary(paths: &WorkspacePaths, plan: &PreparedPlugin, force: bool) {
     let command_count = plan
         .component_files
         .iter()
@@ -966,6 +1259,55 @@ fn print_dry_run_summary(plan: &PreparedPlugin) {
     println!("  Agents: {agent_count}");
     println!("  Hooks: {hook_count}");
     println!("  Scripts: {script_count}");
+
+    println!("  Files to write ({}):", plan.planned_writes.len());
+    for write in &plan.planned_writes {
+        println!("    {}", display_workspace_path(paths, &write.dest));
+    }
+
+    let server_keys: Vec<&str> = plan
+        .mcp_servers
+        .as_ref()
+        .and_then(|servers| servers.as_object())
+        .map(|servers| servers.keys().map(String::as_str).collect())
+        .unwrap_or_default();
+    if server_keys.is_empty() {
+        println!("  MCP servers to merge: none");
+    } else {
+        println!("  MCP servers to merge: {}", server_keys.join(", "));
+    }
+
+    let file_conflicts: Vec<_> = plan
+        .planned_writes
+        .iter()
+        .filter_map(|w| w.conflict_owner.as_ref().map(|owner| (&w.dest, owner)))
+        .collect();
+    let conflict_count = file_conflicts.len() + plan.mcp_conflicts.len();
+    if conflict_count == 0 {
+        println!("  Conflicts: none");
+        return;
+    }
+
+    println!("  Conflicts ({conflict_count}):");
+    for (dest, owner) in file_conflicts {
+        println!(
+            "    file {} (owned by {owner})",
+            display_workspace_path(paths, dest)
+        );
+    }
+    for key in &plan.mcp_conflicts {
+        println!("    MCP server '{key}' already defined in .mcp.json");
+    }
+    if force {
+        println!("  Conflicting entries would be overwritten (--force)");
+    } else {
+        println!("  Install would fail: re-run with --force to overwrite conflicts");
+    }
+}
+
+fn display_workspace_path(paths: &WorkspacePaths, path: &Path) -> String {
+    crate::paths::strip_root(path, &paths.root)
+        .unwrap_or_else(|| crate::paths::normalize_path(path))
 }
 
 fn execute_install_with_plan(
@@ -1106,6 +1448,171 @@ fn execute_install_with_plan(
     Ok(entry)
 }
 
+/// Fetch the plugin payload exactly as it was at the locked commit
+fn fetch_locked_plugin(entry: &PluginLockEntry) -> PluginResult<TempDir> {
+    // Local installs are re-read from their directory; verification afterwards
+    // catches sources that changed since install
+    if let (
+        MarketplaceSource::Local(dir),
+        Some(LockfilePluginSource::MarketplacePath { relative }),
+    ) = (MarketplaceSource::for_entry(entry), &entry.source)
+    {
+        let plugin_dir = tempdir()?;
+        extract_subdirectory(&dir, relative, plugin_dir.path())?;
+        return Ok(plugin_dir);
+    }
+
+    match &entry.source {
+        Some(LockfilePluginSource::MarketplacePath { relative }) => {
+            let marketplace_dir = tempdir()?;
+            resolver::clone_at_commit(
+                &entry.marketplace_url,
+                marketplace_dir.path(),
+                &entry.commit,
+            )?;
+            let plugin_dir = tempdir()?;
+            extract_subdirectory(marketplace_dir.path(), relative, plugin_dir.path())?;
+            Ok(plugin_dir)
+        }
+        Some(LockfilePluginSource::Git { url, subdir, .. }) => {
+            let repo_dir = tempdir()?;
+            resolver::clone_at_commit(url, repo_dir.path(), &entry.commit)?;
+            if let Some(path) = subdir {
+                let plugin_dir = tempdir()?;
+                extract_subdirectory(repo_dir.path(), path, plugin_dir.path())?;
+                Ok(plugin_dir)
+            } else {
+                Ok(repo_dir)
+            }
+        }
+        None => Err(PluginError::InvalidPluginManifest {
+            reason: format!(
+                "Lockfile entry for '{}' does not record its source; reinstall the plugin to enable repair",
+                entry.name
+            ),
+        }),
+    }
+}
+
+/// Restore lockfile-tracked files that are missing or differ from the fetched payload
+///
+/// Returns the number of files rewritten. Files outside the lockfile entry are never touched.
+fn restore_locked_files(
+    paths: &WorkspacePaths,
+    entry: &PluginLockEntry,
+    plugin_dir: &Path,
+    verbose: bool,
+) -> PluginResult<usize> {
+    let mut sources = HashMap::new();
+    for file in WalkDir::new(plugin_dir).into_iter() {
+        let file = file.map_err(|e| PluginError::IoError(io::Error::other(e)))?;
+        if file.file_type().is_dir() {
+            continue;
+        }
+
+        let relative = file
+            .path()
+            .strip_prefix(plugin_dir)
+            .expect("walkdir entry should be under plugin root");
+
+        if relative.components().any(|c| c.as_os_str() == ".git") {
+            continue;
+        }
+
+        let relative_str = crate::paths::normalize_path(relative);
+        let dest = calculate_dest_path(&paths.root, &entry.name, &relative_str);
+        sources.insert(
+            display_workspace_path(paths, &dest),
+            file.path().to_path_buf(),
+        );
+    }
+
+    let mut restored = 0;
+    for relative in entry
+        .files
+        .iter()
+        .filter(|path| path.as_str() != ".mcp.json")
+    {
+        let source = sources
+            .get(relative)
+            .ok_or_else(|| PluginError::IntegrityCheckFailed {
+                plugin: entry.name.clone(),
+                expected: format!("'{relative}' in commit {}", entry.commit),
+                actual: "not found in plugin source".to_string(),
+            })?;
+        let dest = paths.root.join(relative);
+
+        let expected = fs::read(source)?;
+        if fs::read(&dest).ok().as_deref() == Some(expected.as_slice()) {
+            continue;
+        }
+
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&dest, &expected)?;
+        restored += 1;
+
+        if verbose {
+            println!("  Restored {relative}");
+        }
+    }
+
+    Ok(restored)
+}
+
+/// Re-merge the plugin's MCP servers whose project configuration no longer matches
+///
+/// Only keys recorded in the lockfile entry are considered. Returns the number re-merged.
+fn restore_locked_mcp_servers(
+    paths: &WorkspacePaths,
+    entry: &PluginLockEntry,
+    plugin_dir: &Path,
+) -> PluginResult<usize> {
+    if entry.mcp_keys.is_empty() {
+        return Ok(0);
+    }
+
+    let manifest_path = plugin_dir.join(".claude-plugin/plugin.json");
+    let plugin_servers = if manifest_path.exists() {
+        let manifest = PluginManifest::from_file(&manifest_path)?;
+        load_plugin_mcp(plugin_dir, &manifest)?
+    } else {
+        load_default_mcp(plugin_dir)?
+    };
+    let Some(plugin_servers) = plugin_servers.as_ref().and_then(|v| v.as_object()) else {
+        return Ok(0);
+    };
+
+    let current = if paths.mcp_path.exists() {
+        let content = fs::read_to_string(&paths.mcp_path)?;
+        let json: Value = serde_json::from_str(&content)?;
+        json.get("mcpServers")
+            .and_then(|value| value.as_object())
+            .cloned()
+            .unwrap_or_default()
+    } else {
+        serde_json::Map::new()
+    };
+
+    let mut drifted = serde_json::Map::new();
+    for key in &entry.mcp_keys {
+        if let Some(value) = plugin_servers.get(key) {
+            if current.get(key) != Some(value) {
+                drifted.insert(key.clone(), value.clone());
+            }
+        }
+    }
+
+    if drifted.is_empty() {
+        return Ok(0);
+    }
+
+    let count = drifted.len();
+    merger::merge_mcp_servers(&paths.mcp_path, &Value::Object(drifted), true)?;
+    Ok(count)
+}
+
 fn rollback_install(
     paths: &WorkspacePaths,
     lockfile: &mut PluginLockfile,
@@ -1149,3 +1656,27 @@ fn cleanup_plugin_dirs(paths: &WorkspacePaths, plugin_name: &str) {
         let _ = fs::remove_dir_all(&script_dir);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_paths_windows_inputs() {
+        let files = vec![
+            r"C:\repo\.claude\commands\a.md".to_string(),
+            r"\\?\C:\repo\.claude\commands\a.md".to_string(),
+            r".claude\agents\b.md".to_string(),
+            "/elsewhere/c.md".to_string(),
+        ];
+
+        assert_eq!(
+            normalize_paths(Path::new(r"C:\repo"), files),
+            vec![
+                ".claude/agents/b.md".to_string(),
+                ".claude/commands/a.md".to_string(),
+                "/elsewhere/c.md".to_string(),
+            ]
+        );
+    }
+}
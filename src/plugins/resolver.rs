@@ -14,7 +14,30 @@ pub fn clone_repository(
     git_ref: Option<&str>,
 ) -> PluginResult<String> {
     let is_local = repo_url.starts_with("file://") || Path::new(repo_url).exists();
+    clone_with_depth(repo_url, target_dir, git_ref, !is_local)
+}
+
+/// Clone a repository and check out an exact commit
+///
+/// Performs a full clone because a shallow clone only contains the tip of
+/// the default branch, which may no longer be the locked commit.
+pub fn clone_at_commit(repo_url: &str, target_dir: &Path, commit: &str) -> PluginResult<String> {
+    clone_with_depth(repo_url, target_dir, None, false)?;
+
+    let repo = Repository::open(target_dir).map_err(|e| PluginError::GitOperationFailed {
+        operation: format!("open repository: {e}"),
+    })?;
+    checkout_reference(&repo, commit)?;
+
+    get_commit_sha(target_dir)
+}
 
+fn clone_with_depth(
+    repo_url: &str,
+    target_dir: &Path,
+    git_ref: Option<&str>,
+    shallow: bool,
+) -> PluginResult<String> {
     // Ensure parent directory exists
     if let Some(parent) = target_dir.parent() {
         std::fs::create_dir_all(parent)?;
@@ -31,7 +54,7 @@ pub fn clone_repository(
 
     // Set up fetch options with shallow clone
     let mut fetch_opts = FetchOptions::new();
-    if !is_local {
+    if shallow {
         fetch_opts.depth(1); // Shallow clone
     }
     fetch_opts.download_tags(AutotagOption::All);
@@ -9,7 +9,7 @@ pub enum ProfileAction {
     /// Initialize project with a profile
     #[command(
         about = "Initialize project with a profile",
-        after_help = "Examples:\n  codanna profile init claude\n  codanna profile init claude --source ~/.codanna/profiles"
+        after_help = "Examples:\n  codanna profile init claude\n  codanna profile init claude --source ~/.codanna/profiles\n  codanna profile init claude --set project_name=my-app"
     )]
     Init {
         /// Profile name to initialize
@@ -22,6 +22,24 @@ pub enum ProfileAction {
         /// Force initialization even if .codanna exists
         #[arg(short, long)]
         force: bool,
+
+        /// Override a template variable declared by the profile (repeatable)
+        #[arg(long = "set", value_name = "KEY=VALUE")]
+        set: Vec<String>,
+    },
+
+    /// Show a profile and the template variables it declares
+    #[command(
+        about = "Show a profile and its template variables",
+        after_help = "Examples:\n  codanna profile show claude\n  codanna profile show claude --source ~/.codanna/profiles"
+    )]
+    Show {
+        /// Profile name to show
+        profile_name: String,
+
+        /// Profile source directory (defaults to ~/.codanna/profiles)
+        #[arg(long)]
+        source: Option<PathBuf>,
     },
 
     /// Install a profile to current workspace
@@ -97,6 +115,16 @@ pub enum ProfileAction {
         force: bool,
     },
 
+    /// Show differences between an installed profile and its source
+    #[command(
+        about = "Compare an installed profile with its provider source",
+        after_help = "Example:\n  codanna profile diff codanna"
+    )]
+    Diff {
+        /// Profile name to compare
+        profile_name: String,
+    },
+
     /// Remove an installed profile
     #[command(
         about = "Remove an installed profile from workspace",
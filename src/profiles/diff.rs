@@ -0,0 +1,237 @@
+//! Compare an installed profile against its provider source
+//!
+//! Separates changes made upstream in the provider from edits made locally to the
+//! installed files, using the lockfile's integrity data as the baseline.
+
+use super::error::ProfileResult;
+use super::fsops::{calculate_content_hash, calculate_file_hash, collect_all_files};
+use super::installer::generate_sidecar_path;
+use super::lockfile::ProfileLockEntry;
+use super::manifest::ProfileManifest;
+use super::template::render_content;
+use super::variables::profile_variables;
+use super::verification::locally_modified_files;
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Differences between an installed profile and its current source
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ProfileDiff {
+    /// Files present in the source but not installed
+    pub added: Vec<String>,
+    /// Installed files no longer present in the source
+    pub removed: Vec<String>,
+    /// Installed files whose source content changed since installation
+    pub source_modified: Vec<String>,
+    /// Installed files edited locally since installation
+    pub locally_modified: Vec<String>,
+    /// Files that differ from the source when the lockfile has no per-file baseline
+    pub unattributed: Vec<String>,
+}
+
+impl ProfileDiff {
+    /// True when the installed profile matches its source and has no local edits
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.source_modified.is_empty()
+            && self.locally_modified.is_empty()
+            && self.unattributed.is_empty()
+    }
+
+    /// True when the source has changed since installation
+    pub fn has_source_changes(&self) -> bool {
+        !self.added.is_empty() || !self.removed.is_empty() || !self.source_modified.is_empty()
+    }
+}
+
+/// Compare an installed profile entry with the profile directory from its provider
+///
+/// Files are matched by their install path, including sidecar paths created for
+/// conflicts. The baseline for each file is its hash recorded in the lockfile; for
+/// older lockfiles without per-file hashes the installed content is used when the
+/// aggregate integrity still matches.
+pub fn diff_profile_entry(
+    workspace: &Path,
+    entry: &ProfileLockEntry,
+    profile_dir: &Path,
+) -> ProfileResult<ProfileDiff> {
+    let manifest = ProfileManifest::from_file(&profile_dir.join("profile.json"))?;
+    let mut source_files = if manifest.files.is_empty() {
+        collect_all_files(profile_dir)?
+    } else {
+        manifest.files.clone()
+    };
+    source_files.sort();
+
+    // Templated sources are compared as they would be rendered on install
+    let variables = if manifest.variables.is_empty() {
+        None
+    } else {
+        Some(profile_variables(&manifest, &entry.variables)?.merge())
+    };
+
+    let local = locally_modified_files(workspace, entry)?;
+    let installed: HashSet<&str> = entry.files.iter().map(String::as_str).collect();
+    let mut matched = HashSet::new();
+    let mut diff = ProfileDiff::default();
+
+    for source_rel in &source_files {
+        let source_path = profile_dir.join(source_rel);
+        if !source_path.exists() {
+            continue;
+        }
+
+        let sidecar = generate_sidecar_path(Path::new(source_rel), manifest.provider_name())
+            .to_string_lossy()
+            .to_string();
+        let installed_rel = if installed.contains(source_rel.as_str()) {
+            source_rel.clone()
+        } else if installed.contains(sidecar.as_str()) {
+            sidecar
+        } else {
+            diff.added.push(source_rel.clone());
+            continue;
+        };
+
+        let source_hash = match &variables {
+            Some(vars) => Some(calculate_content_hash(&render_content(
+                std::fs::read(&source_path)?,
+                vars,
+            )?)),
+            None => calculate_file_hash(&source_path)?,
+        };
+        let baseline = match entry.file_hashes.get(&installed_rel) {
+            Some(hash) => Some(hash.clone()),
+            None if local.as_ref().is_some_and(Vec::is_empty) => {
+                calculate_file_hash(&workspace.join(&installed_rel))?
+            }
+            None => None,
+        };
+
+        match baseline {
+            Some(hash) if source_hash.as_ref() != Some(&hash) => {
+                diff.source_modified.push(installed_rel.clone());
+            }
+            Some(_) => {}
+            None => {
+                if calculate_file_hash(&workspace.join(&installed_rel))? != source_hash {
+                    diff.unattributed.push(installed_rel.clone());
+                }
+            }
+        }
+
+        matched.insert(installed_rel);
+    }
+
+    diff.removed = entry
+        .files
+        .iter()
+        .filter(|rel| !matched.contains(*rel))
+        .cloned()
+        .collect();
+    diff.locally_modified = local.unwrap_or_default();
+
+    Ok(diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profiles::fsops::{calculate_file_hashes, calculate_integrity};
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_diff_separates_source_and_local_changes() {
+        let temp = tempdir().unwrap();
+        let workspace = temp.path().join("workspace");
+        let profile_dir = temp.path().join("profiles/demo");
+
+        // Installed state
+        write(&workspace.join("CLAUDE.md"), "v1 guide");
+        write(&workspace.join("notes.md"), "v1 notes");
+        write(&workspace.join("old.md"), "obsolete");
+        let files = vec![
+            "CLAUDE.md".to_string(),
+            "notes.md".to_string(),
+            "old.md".to_string(),
+        ];
+        let entry = ProfileLockEntry {
+            name: "demo".to_string(),
+            version: "1.0.0".to_string(),
+            installed_at: "2025-01-11".to_string(),
+            files: files.clone(),
+            integrity: String::new(),
+            commit: None,
+            provider_id: None,
+            source: None,
+            file_hashes: calculate_file_hashes(&workspace, &files).unwrap(),
+            variables: Default::default(),
+        };
+
+        // Source moved on: CLAUDE.md changed, old.md dropped, new.md added
+        write(
+            &profile_dir.join("profile.json"),
+            r#"{"name": "demo", "version": "1.1.0"}"#,
+        );
+        write(&profile_dir.join("CLAUDE.md"), "v2 guide");
+        write(&profile_dir.join("notes.md"), "v1 notes");
+        write(&profile_dir.join("new.md"), "brand new");
+
+        // User edited notes.md locally
+        write(&workspace.join("notes.md"), "my notes");
+
+        let diff = diff_profile_entry(&workspace, &entry, &profile_dir).unwrap();
+        assert_eq!(diff.added, vec!["new.md"]);
+        assert_eq!(diff.removed, vec!["old.md"]);
+        assert_eq!(diff.source_modified, vec!["CLAUDE.md"]);
+        assert_eq!(diff.locally_modified, vec!["notes.md"]);
+        assert!(diff.unattributed.is_empty());
+    }
+
+    #[test]
+    fn test_diff_legacy_entry_uses_aggregate_integrity() {
+        let temp = tempdir().unwrap();
+        let workspace = temp.path().join("workspace");
+        let profile_dir = temp.path().join("profiles/demo");
+
+        write(&workspace.join("CLAUDE.md"), "v1 guide");
+        let integrity =
+            calculate_integrity(&[workspace.join("CLAUDE.md").to_string_lossy().to_string()])
+                .unwrap();
+        let entry = ProfileLockEntry {
+            name: "demo".to_string(),
+            version: "1.0.0".to_string(),
+            installed_at: "2025-01-11".to_string(),
+            files: vec!["CLAUDE.md".to_string()],
+            integrity,
+            commit: None,
+            provider_id: None,
+            source: None,
+            file_hashes: Default::default(),
+            variables: Default::default(),
+        };
+
+        write(
+            &profile_dir.join("profile.json"),
+            r#"{"name": "demo", "version": "1.1.0"}"#,
+        );
+        write(&profile_dir.join("CLAUDE.md"), "v2 guide");
+
+        let diff = diff_profile_entry(&workspace, &entry, &profile_dir).unwrap();
+        assert_eq!(diff.source_modified, vec!["CLAUDE.md"]);
+        assert!(diff.locally_modified.is_empty());
+
+        // Once the installed file is edited, the change can no longer be attributed
+        write(&workspace.join("CLAUDE.md"), "edited");
+        let diff = diff_profile_entry(&workspace, &entry, &profile_dir).unwrap();
+        assert!(diff.source_modified.is_empty());
+        assert_eq!(diff.unattributed, vec!["CLAUDE.md"]);
+    }
+}
@@ -3,6 +3,7 @@
 use super::error::{ProfileError, ProfileResult};
 use super::lockfile::ProfileLockEntry;
 use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
@@ -29,6 +30,38 @@ pub fn calculate_integrity(file_paths: &[String]) -> ProfileResult<String> {
     Ok(format!("{result:x}"))
 }
 
+/// Calculate the SHA-256 hash of a single file
+///
+/// Returns `None` if the file does not exist.
+pub fn calculate_file_hash(path: &Path) -> ProfileResult<Option<String>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read(path)?;
+    Ok(Some(calculate_content_hash(&content)))
+}
+
+/// Calculate the SHA-256 hash of in-memory content
+pub fn calculate_content_hash(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+/// Calculate per-file hashes for installed files, keyed by workspace-relative path
+///
+/// Missing files are left out of the map.
+pub fn calculate_file_hashes(
+    workspace: &Path,
+    files: &[String],
+) -> ProfileResult<HashMap<String, String>> {
+    let mut hashes = HashMap::new();
+    for rel in files {
+        if let Some(hash) = calculate_file_hash(&workspace.join(rel))? {
+            hashes.insert(rel.clone(), hash);
+        }
+    }
+    Ok(hashes)
+}
+
 /// Collect all files from a profile directory
 ///
 /// Recursively walks the profile directory and returns relative paths to all files.
@@ -61,7 +94,7 @@ pub fn collect_all_files(profile_dir: &Path) -> ProfileResult<Vec<String>> {
         }
 
         // Skip profile.json manifest
-        let normalized = relative.to_string_lossy().replace('\\', "/");
+        let normalized = crate::paths::normalize_path(relative);
         if normalized == "profile.json" {
             continue;
         }
@@ -136,11 +169,8 @@ pub fn copy_profile_files(
         std::fs::copy(&source_path, &dest_path)?;
 
         // Store absolute path with normalized separators
-        let absolute_path = dest_path
-            .canonicalize()
-            .unwrap_or(dest_path)
-            .to_string_lossy()
-            .replace('\\', "/");
+        let absolute_path =
+            crate::paths::normalize_path(dest_path.canonicalize().unwrap_or(dest_path));
         copied_files.push(absolute_path);
     }
 
@@ -406,6 +436,8 @@ mod tests {
             commit: None,
             provider_id: None,
             source: None,
+            file_hashes: Default::default(),
+            variables: Default::default(),
         };
 
         let backup = backup_profile(workspace, &entry).unwrap();
@@ -440,6 +472,8 @@ mod tests {
             commit: None,
             provider_id: None,
             source: None,
+            file_hashes: Default::default(),
+            variables: Default::default(),
         };
 
         let backup = backup_profile(workspace, &entry).unwrap();
@@ -470,6 +504,8 @@ mod tests {
             commit: None,
             provider_id: None,
             source: None,
+            file_hashes: Default::default(),
+            variables: Default::default(),
         };
 
         // Create backup
@@ -508,6 +544,8 @@ mod tests {
             commit: None,
             provider_id: None,
             source: None,
+            file_hashes: Default::default(),
+            variables: Default::default(),
         };
 
         // Create backup
@@ -549,6 +587,8 @@ mod tests {
             commit: None,
             provider_id: None,
             source: None,
+            file_hashes: Default::default(),
+            variables: Default::default(),
         };
 
         // Backup
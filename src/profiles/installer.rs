@@ -1,6 +1,6 @@
 //! Profile file installation logic
 //!
-//! Plugin reference: src/plugins/mod.rs:871-940 (check_file_conflicts)
+//! Plugin reference: src/plugins/mod.rs:886-955 (plan_file_writes, conflicting_owner)
 
 use super::error::{ProfileError, ProfileResult};
 use super::lockfile::ProfileLockfile;
@@ -50,6 +50,16 @@ pub struct ProfileLockEntry {
     /// Uses default (None) for backwards compatibility with old lockfiles
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub source: Option<ProviderSource>,
+
+    /// SHA-256 hash of each installed file, keyed by its workspace-relative path
+    /// Uses default (empty) for backwards compatibility with old lockfiles
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub file_hashes: HashMap<String, String>,
+
+    /// Template variable overrides supplied at install time (`--set key=value`)
+    /// Uses default (empty) for backwards compatibility with old lockfiles
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub variables: HashMap<String, String>,
 }
 
 impl ProfileLockfile {
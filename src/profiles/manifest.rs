@@ -2,6 +2,7 @@
 
 use super::error::{ProfileError, ProfileResult};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::Path;
 
 /// Profile manifest structure
@@ -21,6 +22,22 @@ pub struct ProfileManifest {
     /// Files to install (relative to profile directory)
     #[serde(default)]
     pub files: Vec<String>,
+
+    /// Template variables referenced as {{name}} in profile files
+    #[serde(default)]
+    pub variables: BTreeMap<String, VariableSpec>,
+}
+
+/// Declared template variable
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct VariableSpec {
+    /// Value used when not overridden
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+
+    /// What the variable controls
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
 }
 
 impl ProfileManifest {
@@ -1,6 +1,7 @@
 //! Profile system for project initialization
 
 pub mod commands;
+pub mod diff;
 pub mod error;
 pub mod fsops;
 pub mod git;
@@ -20,11 +21,12 @@ pub mod variables;
 pub mod verification;
 
 use error::ProfileResult;
-use orchestrator::install_profile;
+use orchestrator::{install_profile, install_profile_with_variables};
 use provider::ProviderManifest;
 use provider_registry::{ProviderRegistry, ProviderSource};
 use reference::ProfileReference;
 use source_resolver::resolve_profile_source;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 /// Get the default profiles directory
@@ -42,7 +44,12 @@ pub fn provider_registry_path() -> PathBuf {
 /// Initialize a profile to the current workspace
 ///
 /// This is the public API for the `codanna profile init` command.
-pub fn init_profile(profile_name: &str, source: Option<&Path>, force: bool) -> ProfileResult<()> {
+pub fn init_profile(
+    profile_name: &str,
+    source: Option<&Path>,
+    force: bool,
+    variables: &HashMap<String, String>,
+) -> ProfileResult<()> {
     let workspace = std::env::current_dir()?;
     let profiles_dir = source.map(|p| p.to_path_buf()).unwrap_or_else(profiles_dir);
 
@@ -54,7 +61,7 @@ pub fn init_profile(profile_name: &str, source: Option<&Path>, force: bool) -> P
         println!("Installing profile '{profile_name}' to workspace...");
     }
 
-    install_profile(
+    install_profile_with_variables(
         profile_name,
         &profiles_dir,
         &workspace,
@@ -62,6 +69,7 @@ pub fn init_profile(profile_name: &str, source: Option<&Path>, force: bool) -> P
         None,
         None,
         None,
+        variables,
     )?;
 
     println!("\nProfile '{profile_name}' installed successfully");
@@ -73,6 +81,62 @@ pub fn init_profile(profile_name: &str, source: Option<&Path>, force: bool) -> P
     Ok(())
 }
 
+/// Show a profile's manifest and the template variables it declares
+///
+/// Looks in the local profiles directory first (or `source` when given), then
+/// falls back to registered providers.
+///
+/// This is the public API for the `codanna profile show` command.
+pub fn show_profile(profile_name: &str, source: Option<&Path>) -> ProfileResult<()> {
+    let local_dir = source
+        .map(|p| p.to_path_buf())
+        .unwrap_or_else(profiles_dir)
+        .join(profile_name);
+
+    // Keep the resolved source alive so a temporary clone is not removed early
+    let resolved;
+    let profile_dir = if local_dir.join("profile.json").exists() {
+        local_dir
+    } else {
+        let registry = ProviderRegistry::load(&provider_registry_path())?;
+        let provider = registry
+            .find_provider_for_profile(profile_name)
+            .ok_or_else(|| error::ProfileError::ProfileNotFoundInAnyProvider {
+                profile: profile_name.to_string(),
+            })?;
+        resolved = resolve_profile_source(&provider.source, profile_name)?;
+        resolved.profile_dir(profile_name)
+    };
+
+    let manifest = manifest::ProfileManifest::from_file(&profile_dir.join("profile.json"))?;
+
+    println!("Profile: {} v{}", manifest.name, manifest.version);
+    println!("Provider: {}", manifest.provider_name());
+    if !manifest.files.is_empty() {
+        println!("Files: {}", manifest.files.len());
+    }
+
+    if manifest.variables.is_empty() {
+        println!("\nNo template variables declared");
+        return Ok(());
+    }
+
+    println!("\nTemplate variables:");
+    for (name, spec) in &manifest.variables {
+        let default = match &spec.default {
+            Some(value) => format!("default: \"{value}\""),
+            None => "required".to_string(),
+        };
+        println!("  {name} ({default})");
+        if let Some(description) = &spec.description {
+            println!("      {description}");
+        }
+    }
+    println!("\nOverride with: codanna profile init {profile_name} --set <name>=<value>");
+
+    Ok(())
+}
+
 /// Add a provider to the global registry
 ///
 /// This is the public API for the `codanna profile provider add` command.
@@ -677,6 +741,91 @@ pub fn install_profile_from_registry(profile_ref: &str, force: bool) -> ProfileR
     Ok(())
 }
 
+/// Show how an installed profile differs from its provider source
+///
+/// This is the public API for the `codanna profile diff` command.
+pub fn diff_profile(profile_name: &str) -> ProfileResult<()> {
+    let workspace = std::env::current_dir()?;
+    let lockfile_path = workspace.join(".codanna/profiles.lock.json");
+    let lockfile = lockfile::ProfileLockfile::load(&lockfile_path)?;
+
+    let entry =
+        lockfile
+            .get_profile(profile_name)
+            .ok_or_else(|| error::ProfileError::NotInstalled {
+                name: profile_name.to_string(),
+            })?;
+
+    // Prefer the source recorded at install time, then fall back to the registry
+    let source = match &entry.source {
+        Some(source) => source.clone(),
+        None => {
+            let registry = ProviderRegistry::load(&provider_registry_path())?;
+            registry
+                .find_provider_for_profile(profile_name)
+                .map(|provider| provider.source.clone())
+                .ok_or_else(|| error::ProfileError::ProfileNotFoundInAnyProvider {
+                    profile: profile_name.to_string(),
+                })?
+        }
+    };
+
+    let resolved = resolve_profile_source(&source, profile_name)?;
+    let profile_dir = resolved.profile_dir(profile_name);
+    let changes = diff::diff_profile_entry(&workspace, entry, &profile_dir)?;
+
+    match resolved.commit() {
+        Some(commit) => println!(
+            "Profile '{profile_name}' (installed {}) vs source commit {}",
+            entry.version,
+            &commit[..8.min(commit.len())]
+        ),
+        None => println!(
+            "Profile '{profile_name}' (installed {}) vs local source",
+            entry.version
+        ),
+    }
+
+    if changes.is_empty() {
+        println!("  No differences");
+        return Ok(());
+    }
+
+    println!("\nSource changed:");
+    if changes.has_source_changes() {
+        for file in &changes.added {
+            println!("  + {file}");
+        }
+        for file in &changes.removed {
+            println!("  - {file}");
+        }
+        for file in &changes.source_modified {
+            println!("  ~ {file}");
+        }
+    } else {
+        println!("  (none)");
+    }
+
+    println!("\nYou modified locally:");
+    if changes.locally_modified.is_empty() {
+        println!("  (none)");
+    } else {
+        for file in &changes.locally_modified {
+            println!("  ~ {file}");
+        }
+    }
+
+    if !changes.unattributed.is_empty() {
+        println!("\nDiffers from source (lockfile has no per-file hashes, cannot attribute):");
+        for file in &changes.unattributed {
+            println!("  ? {file}");
+        }
+        println!("  Reinstall the profile to record per-file hashes");
+    }
+
+    Ok(())
+}
+
 /// Update an installed profile from its provider
 ///
 /// This is the public API for the `codanna profile update` command.
@@ -4,13 +4,17 @@
 
 use super::error::{ProfileError, ProfileResult};
 use super::fsops::{
-    ProfileBackup, backup_profile, calculate_integrity, collect_all_files, restore_profile,
+    ProfileBackup, backup_profile, calculate_file_hashes, calculate_integrity, collect_all_files,
+    restore_profile,
 };
 use super::installer::{self, ProfileInstaller};
 use super::lockfile::{ProfileLockEntry, ProfileLockfile};
 use super::manifest::ProfileManifest;
 use super::project::ProfilesConfig;
 use super::provider_registry::ProviderSource;
+use super::template::render_content;
+use super::variables::profile_variables;
+use std::collections::HashMap;
 use std::path::Path;
 
 /// Install a profile to a workspace with atomic operations
@@ -52,6 +56,34 @@ pub fn install_profile(
     commit: Option<String>,
     provider_id: Option<&str>,
     source: Option<ProviderSource>,
+) -> ProfileResult<()> {
+    install_profile_with_variables(
+        profile_name,
+        profiles_dir,
+        workspace,
+        force,
+        commit,
+        provider_id,
+        source,
+        &HashMap::new(),
+    )
+}
+
+/// Install a profile, rendering its templates with variable overrides
+///
+/// Same as [`install_profile`], but `overrides` replace the defaults of variables
+/// declared in the profile manifest. When reinstalling without overrides, the
+/// overrides recorded in the lockfile are reused so updates keep customizations.
+#[allow(clippy::too_many_arguments)]
+pub fn install_profile_with_variables(
+    profile_name: &str,
+    profiles_dir: &Path,
+    workspace: &Path,
+    force: bool,
+    commit: Option<String>,
+    provider_id: Option<&str>,
+    source: Option<ProviderSource>,
+    overrides: &HashMap<String, String>,
 ) -> ProfileResult<()> {
     let lockfile_path = workspace.join(".codanna/profiles.lock.json");
     let mut lockfile = ProfileLockfile::load(&lockfile_path)?;
@@ -73,6 +105,7 @@ pub fn install_profile(
     let manifest = ProfileManifest::from_file(&manifest_path)?;
 
     // 2. Check if already installed (unless force)
+    let mut overrides = overrides.clone();
     if let Some(existing) = lockfile.get_profile(profile_name) {
         if !force {
             return Err(ProfileError::AlreadyInstalled {
@@ -80,10 +113,17 @@ pub fn install_profile(
                 version: existing.version.clone(),
             });
         }
+        if overrides.is_empty() {
+            overrides = existing.variables.clone();
+        }
         // Backup existing before update
         backup = Some(backup_profile(workspace, existing)?);
     }
 
+    // Resolve template variables up front so unknown overrides and missing
+    // required values fail before copying
+    let variables = profile_variables(&manifest, &overrides)?.merge();
+
     // 3. Determine files to install
     // If manifest.files is empty, install all files from profile directory
     let files_to_install = if manifest.files.is_empty() {
@@ -96,11 +136,26 @@ pub fn install_profile(
     // This ensures atomic behavior - we fail fast before touching the filesystem
     installer::check_all_conflicts(workspace, &files_to_install, profile_name, &lockfile, force)?;
 
+    // Render templates into a staging directory when the profile declares
+    // variables, so a rendering failure leaves the workspace untouched
+    let staging = if manifest.variables.is_empty() {
+        None
+    } else {
+        Some(render_profile_files(
+            &profile_dir,
+            &files_to_install,
+            &variables,
+        )?)
+    };
+    let source_dir = staging
+        .as_ref()
+        .map_or(profile_dir.as_path(), |dir| dir.path());
+
     // 5. Install files (conflicts already validated, safe to proceed)
     let installer = ProfileInstaller::new();
     let provider_name = manifest.provider_name();
     let (installed_files, sidecars) = match installer.install_files(
-        &profile_dir,
+        source_dir,
         workspace,
         &files_to_install,
         profile_name,
@@ -143,6 +198,16 @@ pub fn install_profile(
         }
     };
 
+    let file_hashes = match calculate_file_hashes(workspace, &installed_files) {
+        Ok(hashes) => hashes,
+        Err(e) => {
+            if let Some(b) = backup {
+                let _ = restore_profile(&b);
+            }
+            return Err(e);
+        }
+    };
+
     // 6. Create lockfile entry
     let entry = ProfileLockEntry {
         name: profile_name.to_string(),
@@ -153,6 +218,8 @@ pub fn install_profile(
         commit,
         provider_id: provider_id.map(String::from),
         source,
+        file_hashes,
+        variables: overrides,
     };
 
     // 7. Update lockfile (with rollback on error)
@@ -192,6 +259,31 @@ pub fn install_profile(
     Ok(())
 }
 
+/// Render a profile's files with template variables into a staging directory
+///
+/// The returned directory mirrors the profile's layout and is removed when
+/// dropped. Files missing from the profile are skipped, as the installer does.
+fn render_profile_files(
+    profile_dir: &Path,
+    files: &[String],
+    variables: &HashMap<String, String>,
+) -> ProfileResult<tempfile::TempDir> {
+    let staging = tempfile::tempdir()?;
+    for rel in files {
+        let source = profile_dir.join(rel);
+        if !source.exists() {
+            continue;
+        }
+        let rendered = render_content(std::fs::read(&source)?, variables)?;
+        let target = staging.path().join(rel);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(target, rendered)?;
+    }
+    Ok(staging)
+}
+
 /// Get current timestamp in ISO 8601 format
 fn current_timestamp() -> String {
     use std::time::{SystemTime, UNIX_EPOCH};
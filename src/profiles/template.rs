@@ -33,3 +33,18 @@ pub fn substitute_variables(
 
     Ok(result)
 }
+
+/// Render profile file content with template variables
+///
+/// Only UTF-8 content containing `{{` is treated as a template; binary and plain
+/// files are returned unchanged.
+pub fn render_content(
+    content: Vec<u8>,
+    variables: &HashMap<String, String>,
+) -> ProfileResult<Vec<u8>> {
+    match String::from_utf8(content) {
+        Ok(text) if text.contains("{{") => Ok(substitute_variables(&text, variables)?.into_bytes()),
+        Ok(text) => Ok(text.into_bytes()),
+        Err(e) => Ok(e.into_bytes()),
+    }
+}
@@ -1,5 +1,7 @@
 //! Variable merging system for profile templates
 
+use super::error::{ProfileError, ProfileResult};
+use super::manifest::ProfileManifest;
 use std::collections::HashMap;
 
 /// Variable storage with tiered priority
@@ -61,3 +63,81 @@ impl Default for Variables {
         Self::new()
     }
 }
+
+/// Build the variables used to render a profile's templates
+///
+/// Declared defaults form the manifest tier and overrides form the CLI tier.
+/// Overrides for variables the manifest does not declare are rejected, as are
+/// declared variables with neither a default nor an override.
+pub fn profile_variables(
+    manifest: &ProfileManifest,
+    overrides: &HashMap<String, String>,
+) -> ProfileResult<Variables> {
+    let mut vars = Variables::new();
+
+    for (name, spec) in &manifest.variables {
+        if let Some(default) = &spec.default {
+            vars.set_manifest(name, default);
+        }
+    }
+
+    for (name, value) in overrides {
+        if !manifest.variables.contains_key(name) {
+            let declared: Vec<&str> = manifest.variables.keys().map(String::as_str).collect();
+            return Err(ProfileError::InvalidManifest {
+                reason: format!(
+                    "Profile '{}' does not declare variable '{name}'\nDeclared variables: {}",
+                    manifest.name,
+                    if declared.is_empty() {
+                        "(none)".to_string()
+                    } else {
+                        declared.join(", ")
+                    }
+                ),
+            });
+        }
+        vars.set_cli(name, value);
+    }
+
+    let mut missing: Vec<&str> = manifest
+        .variables
+        .iter()
+        .filter(|(name, spec)| spec.default.is_none() && !overrides.contains_key(*name))
+        .map(|(name, _)| name.as_str())
+        .collect();
+    if !missing.is_empty() {
+        missing.sort_unstable();
+        return Err(ProfileError::InvalidManifest {
+            reason: format!(
+                "Profile '{}' requires values for: {}\nSet them with --set <name>=<value>",
+                manifest.name,
+                missing.join(", ")
+            ),
+        });
+    }
+
+    Ok(vars)
+}
+
+/// Parse `key=value` assignments from the command line
+pub fn parse_assignments(assignments: &[String]) -> ProfileResult<HashMap<String, String>> {
+    let mut result = HashMap::new();
+    for assignment in assignments {
+        let (key, value) =
+            assignment
+                .split_once('=')
+                .ok_or_else(|| ProfileError::InvalidManifest {
+                    reason: format!(
+                        "Invalid variable assignment '{assignment}', expected key=value"
+                    ),
+                })?;
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(ProfileError::InvalidManifest {
+                reason: format!("Invalid variable assignment '{assignment}', key cannot be empty"),
+            });
+        }
+        result.insert(key.to_string(), value.to_string());
+    }
+    Ok(result)
+}
@@ -3,7 +3,7 @@
 //! Plugin reference: src/plugins/mod.rs:516-574 (verify_entry), 355-407 (verify_plugin, verify_all_plugins)
 
 use super::error::{ProfileError, ProfileResult};
-use super::fsops::calculate_integrity;
+use super::fsops::{calculate_file_hash, calculate_integrity};
 use super::lockfile::{ProfileLockEntry, ProfileLockfile};
 use std::path::Path;
 
@@ -52,6 +52,38 @@ pub fn verify_all_profiles(workspace: &Path, verbose: bool) -> ProfileResult<()>
     Ok(())
 }
 
+/// List installed files whose contents no longer match the lockfile
+///
+/// Uses the per-file hashes when the entry records them. Older entries only carry the
+/// aggregate integrity hash: a match yields `Some(vec![])`, while a mismatch (or no
+/// hash at all) yields `None` because the modified files cannot be identified.
+pub fn locally_modified_files(
+    workspace: &Path,
+    entry: &ProfileLockEntry,
+) -> ProfileResult<Option<Vec<String>>> {
+    if entry.file_hashes.is_empty() {
+        if entry.integrity.is_empty() {
+            return Ok(None);
+        }
+        let absolute_files: Vec<String> = entry
+            .files
+            .iter()
+            .map(|rel| workspace.join(rel).to_string_lossy().to_string())
+            .collect();
+        let actual = calculate_integrity(&absolute_files)?;
+        return Ok((actual == entry.integrity).then(Vec::new));
+    }
+
+    let mut modified = Vec::new();
+    for rel in &entry.files {
+        let actual = calculate_file_hash(&workspace.join(rel))?;
+        if actual.as_ref() != entry.file_hashes.get(rel) {
+            modified.push(rel.clone());
+        }
+    }
+    Ok(Some(modified))
+}
+
 /// Internal: Verify a single profile entry
 ///
 /// Plugin reference: src/plugins/mod.rs:516-574
@@ -140,6 +172,8 @@ mod tests {
             commit: None,
             provider_id: None,
             source: None,
+            file_hashes: Default::default(),
+            variables: Default::default(),
         };
 
         let mut lockfile = ProfileLockfile::new();
@@ -179,6 +213,8 @@ mod tests {
             commit: None,
             provider_id: None,
             source: None,
+            file_hashes: Default::default(),
+            variables: Default::default(),
         };
 
         let mut lockfile = ProfileLockfile::new();
@@ -227,6 +263,8 @@ mod tests {
             commit: None,
             provider_id: None,
             source: None,
+            file_hashes: Default::default(),
+            variables: Default::default(),
         };
 
         let mut lockfile = ProfileLockfile::new();
@@ -275,6 +313,8 @@ mod tests {
             commit: None,
             provider_id: None,
             source: None,
+            file_hashes: Default::default(),
+            variables: Default::default(),
         });
 
         lockfile.add_profile(ProfileLockEntry {
@@ -286,6 +326,8 @@ mod tests {
             commit: None,
             provider_id: None,
             source: None,
+            file_hashes: Default::default(),
+            variables: Default::default(),
         });
 
         lockfile.save(&lockfile_path).unwrap();
@@ -316,6 +358,8 @@ mod tests {
             commit: None,
             provider_id: None,
             source: None,
+            file_hashes: Default::default(),
+            variables: Default::default(),
         };
 
         let mut lockfile = ProfileLockfile::new();
@@ -1,5 +1,7 @@
 use crate::types::SymbolId;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::fmt;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum RelationKind {
@@ -22,13 +24,99 @@ pub struct Relationship {
     pub kind: RelationKind,
     pub weight: f32,
     pub metadata: Option<RelationshipMetadata>,
+    /// How the target was chosen during resolution
+    #[serde(default)]
+    pub provenance: Provenance,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+/// How confidently a relationship's target was resolved
+///
+/// Ordered from most to least trustworthy, so `p <= Provenance::Ambiguous`
+/// reads as "at least as confident as ambiguous".
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    schemars::JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Provenance {
+    /// The target is the only candidate in scope, e.g. through an import
+    #[default]
+    Resolved,
+    /// Several indexed symbols share the name and resolution picked one
+    Ambiguous,
+    /// Matched on the bare name after receiver or qualified lookup failed
+    Heuristic,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, schemars::JsonSchema)]
 pub struct RelationshipMetadata {
     pub line: Option<u32>,
     pub column: Option<u16>,
     pub context: Option<Box<str>>,
+    /// Receiver details for method calls
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub call: Option<CallMetadata>,
+}
+
+/// Where a used type appears in the symbol using it
+///
+/// Recorded as the context of `Uses` edges, so queries can tell the types a
+/// function's signature mentions from those it only uses internally.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TypeUsePosition {
+    /// Type of a function or method parameter
+    Parameter,
+    /// Return type of a function or method
+    Return,
+    /// Type of a struct or class field
+    Field,
+    /// Declared type of a local variable
+    Local,
+}
+
+impl TypeUsePosition {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Parameter => "parameter",
+            Self::Return => "return",
+            Self::Field => "field",
+            Self::Local => "local",
+        }
+    }
+
+    /// Parse a stored edge context written by [`Self::as_str`]
+    pub fn from_context(context: &str) -> Option<Self> {
+        [Self::Parameter, Self::Return, Self::Field, Self::Local]
+            .into_iter()
+            .find(|position| position.as_str() == context)
+    }
+
+    /// Parameter and return types, which callers depend on
+    pub fn is_signature(self) -> bool {
+        matches!(self, Self::Parameter | Self::Return)
+    }
+}
+
+/// Receiver information recorded for a call relationship
+///
+/// Persisted in the relationship context field as JSON. Indexes written before
+/// this type existed used `receiver:{receiver},static:{is_static}`, which is
+/// still accepted when loading.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default, schemars::JsonSchema)]
+pub struct CallMetadata {
+    pub receiver: Option<String>,
+    pub is_static: bool,
 }
 
 #[repr(C)]
@@ -47,6 +135,7 @@ impl Relationship {
             kind,
             weight: 1.0,
             metadata: None,
+            provenance: Provenance::Resolved,
         }
     }
 
@@ -60,6 +149,11 @@ impl Relationship {
         self
     }
 
+    pub fn with_provenance(mut self, provenance: Provenance) -> Self {
+        self.provenance = provenance;
+        self
+    }
+
     pub fn to_compact(&self) -> CompactRelationship {
         CompactRelationship {
             source_id: 0,
@@ -110,10 +204,26 @@ impl RelationKind {
 }
 
 impl RelationshipMetadata {
+    /// Context of `Implements` edges synthesized from a derive attribute
+    /// rather than a hand-written impl block
+    pub const DERIVED: &'static str = "derived";
+
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Whether the edge comes from a derive attribute, see [`Self::DERIVED`]
+    pub fn is_derived(&self) -> bool {
+        self.context.as_deref() == Some(Self::DERIVED)
+    }
+
+    /// Where the type of a `Uses` edge appears, when the parser recorded it
+    pub fn type_use_position(&self) -> Option<TypeUsePosition> {
+        self.context
+            .as_deref()
+            .and_then(TypeUsePosition::from_context)
+    }
+
     pub fn at_position(mut self, line: u32, column: u16) -> Self {
         self.line = Some(line);
         self.column = Some(column);
@@ -124,6 +234,122 @@ impl RelationshipMetadata {
         self.context = Some(context.into());
         self
     }
+
+    pub fn with_call(mut self, call: CallMetadata) -> Self {
+        self.call = Some(call);
+        self
+    }
+
+    /// Restore the context field as persisted by storage
+    ///
+    /// Encoded call metadata is decoded into `call`; anything else is kept as
+    /// free-form context.
+    pub fn with_stored_context(self, stored: &str) -> Self {
+        match CallMetadata::parse(stored) {
+            Some(call) => self.with_call(call),
+            None => self.with_context(stored),
+        }
+    }
+
+    /// The value to persist in the storage context field
+    pub fn stored_context(&self) -> Option<Cow<'_, str>> {
+        match (&self.call, &self.context) {
+            (Some(call), _) => Some(Cow::Owned(call.encode())),
+            (None, Some(context)) => Some(Cow::Borrowed(context.as_ref())),
+            (None, None) => None,
+        }
+    }
+}
+
+impl CallMetadata {
+    pub fn new(receiver: Option<String>, is_static: bool) -> Self {
+        Self {
+            receiver,
+            is_static,
+        }
+    }
+
+    /// Decode stored call metadata, accepting both the JSON and legacy forms
+    pub fn parse(stored: &str) -> Option<Self> {
+        if stored.starts_with('{') {
+            return serde_json::from_str(stored).ok();
+        }
+
+        // Legacy: "receiver:{receiver},static:{is_static}". The receiver may
+        // itself contain commas, so split on the last ",static:".
+        let rest = stored.strip_prefix("receiver:")?;
+        let (receiver, is_static) = rest.rsplit_once(",static:")?;
+        let is_static = match is_static.trim() {
+            "true" => true,
+            "false" => false,
+            _ => return None,
+        };
+        let receiver = receiver.trim();
+
+        Some(Self {
+            receiver: (!receiver.is_empty()).then(|| receiver.to_string()),
+            is_static,
+        })
+    }
+
+    /// Encode for storage in the relationship context field
+    pub fn encode(&self) -> String {
+        serde_json::to_string(self).expect("CallMetadata serializes to JSON")
+    }
+
+    /// Name the call as written at the call site, e.g. `Type::new` or `self.run`
+    ///
+    /// Returns `None` when no receiver was recorded.
+    pub fn qualified_name(&self, name: &str) -> Option<String> {
+        let receiver = self.receiver.as_deref().filter(|r| !r.is_empty())?;
+        Some(if self.is_static {
+            format!("{receiver}::{name}")
+        } else {
+            format!("{receiver}.{name}")
+        })
+    }
+}
+
+impl Provenance {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Resolved => "resolved",
+            Self::Ambiguous => "ambiguous",
+            Self::Heuristic => "heuristic",
+        }
+    }
+}
+
+impl std::str::FromStr for Provenance {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "resolved" => Ok(Self::Resolved),
+            "ambiguous" => Ok(Self::Ambiguous),
+            "heuristic" => Ok(Self::Heuristic),
+            _ => Err(format!(
+                "Invalid provenance '{s}'. Use resolved, ambiguous or heuristic"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Provenance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Display for CallMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "receiver:{},static:{}",
+            self.receiver.as_deref().unwrap_or(""),
+            self.is_static
+        )
+    }
 }
 
 pub struct RelationshipEdge {
@@ -175,6 +401,56 @@ mod tests {
         assert_eq!(meta.context.as_deref(), Some("inside main function"));
     }
 
+    #[test]
+    fn test_call_metadata_round_trip() {
+        let call = CallMetadata::new(Some("items[a, b]".to_string()), false);
+        let stored = RelationshipMetadata::new().with_call(call.clone());
+        let encoded = stored.stored_context().unwrap();
+
+        let restored = RelationshipMetadata::new().with_stored_context(&encoded);
+        assert_eq!(restored.call, Some(call));
+        assert!(restored.context.is_none());
+    }
+
+    #[test]
+    fn test_call_metadata_parses_legacy_form() {
+        let call = CallMetadata::parse("receiver:Config,static:true").unwrap();
+        assert_eq!(call.receiver.as_deref(), Some("Config"));
+        assert!(call.is_static);
+        assert_eq!(call.qualified_name("new").as_deref(), Some("Config::new"));
+
+        // A receiver containing a comma is no longer truncated
+        let call = CallMetadata::parse("receiver:pair(a, b),static:false").unwrap();
+        assert_eq!(call.receiver.as_deref(), Some("pair(a, b)"));
+        assert_eq!(
+            call.qualified_name("first").as_deref(),
+            Some("pair(a, b).first")
+        );
+
+        // Free-form context stays as context
+        let meta = RelationshipMetadata::new().with_stored_context("inside main function");
+        assert!(meta.call.is_none());
+        assert_eq!(meta.context.as_deref(), Some("inside main function"));
+    }
+
+    #[test]
+    fn test_provenance_defaults_and_ordering() {
+        let rel = Relationship::new(RelationKind::Calls);
+        assert_eq!(rel.provenance, Provenance::Resolved);
+        let rel = rel.with_provenance(Provenance::Heuristic);
+        assert_eq!(rel.provenance, Provenance::Heuristic);
+
+        assert!(Provenance::Resolved < Provenance::Ambiguous);
+        assert!(Provenance::Ambiguous < Provenance::Heuristic);
+        assert_eq!("Ambiguous".parse(), Ok(Provenance::Ambiguous));
+        assert!("guess".parse::<Provenance>().is_err());
+
+        // Relationships serialized before provenance existed still load
+        let legacy = r#"{"kind":"Calls","weight":1.0,"metadata":null}"#;
+        let rel: Relationship = serde_json::from_str(legacy).unwrap();
+        assert_eq!(rel.provenance, Provenance::Resolved);
+    }
+
     #[test]
     fn test_relation_kind_inverse() {
         assert_eq!(RelationKind::Calls.inverse(), RelationKind::CalledBy);
@@ -1,12 +1,22 @@
 //! Retrieve command implementations using UnifiedOutput schema
 
+use crate::indexing::{EntryPoint, EntryPointCategory};
 use crate::io::{
-    EntityType, ExitCode, OutputFormat, OutputManager, OutputStatus,
+    Annotation, AnnotationLevel, EntityType, ExitCode, OutputFormat, OutputManager, OutputStatus,
     schema::{OutputData, OutputMetadata, UnifiedOutput, UnifiedOutputBuilder},
 };
-use crate::symbol::context::SymbolContext;
-use crate::{SimpleIndexer, Symbol};
+use crate::output_eprintln;
+use crate::parsing::{LanguageId, get_registry};
+use crate::paths::display_path;
+use crate::relationship::TypeUsePosition;
+use crate::symbol::context::{CallDirection, CallTree, CallTreeNode, SymbolContext};
+use crate::{Provenance, SimpleIndexer, Symbol};
+use serde::Serialize;
 use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Component, Path};
+use std::sync::Arc;
 
 /// Execute retrieve symbol command
 pub fn retrieve_symbol(
@@ -54,26 +64,12 @@ pub fn retrieve_symbol(
         match output.unified(unified) {
             Ok(code) => code,
             Err(e) => {
-                eprintln!("Error writing output: {e}");
+                output_eprintln!("Error writing output: {e}");
                 ExitCode::GeneralError
             }
         }
     } else {
-        // Transform symbols to SymbolContext with file paths and relationships
-        use crate::symbol::context::ContextIncludes;
-
-        let symbols_with_path: Vec<SymbolContext> = symbols
-            .into_iter()
-            .filter_map(|symbol| {
-                // Get full context with relationships (same as MCP find_symbol)
-                indexer.get_symbol_context(
-                    symbol.id,
-                    ContextIncludes::IMPLEMENTATIONS
-                        | ContextIncludes::DEFINITIONS
-                        | ContextIncludes::CALLERS,
-                )
-            })
-            .collect();
+        let symbols_with_path = symbol_contexts(indexer, symbols);
 
         let unified = UnifiedOutputBuilder::items(symbols_with_path, EntityType::Symbol)
             .with_metadata(OutputMetadata {
@@ -88,22 +84,138 @@ pub fn retrieve_symbol(
         match output.unified(unified) {
             Ok(code) => code,
             Err(e) => {
-                eprintln!("Error writing output: {e}");
+                output_eprintln!("Error writing output: {e}");
                 ExitCode::GeneralError
             }
         }
     }
 }
 
+/// Most symbols drawn by `--format tree` before the rest are cut off
+const TREE_MAX_NODES: usize = 200;
+
+/// A symbol with its callers or callees drawn as a tree (`--format tree`)
+#[derive(Debug, Clone, Serialize)]
+pub struct CallHierarchy {
+    pub symbol: Symbol,
+    pub tree: CallTree,
+}
+
+impl CallHierarchy {
+    fn write_nodes(
+        f: &mut fmt::Formatter<'_>,
+        nodes: &[CallTreeNode],
+        truncated: usize,
+        prefix: &str,
+    ) -> fmt::Result {
+        for (i, node) in nodes.iter().enumerate() {
+            let last = i + 1 == nodes.len() && truncated == 0;
+            let branch = if last { "└── " } else { "├── " };
+            write!(
+                f,
+                "\n{prefix}{branch}{} ({:?}) at {} [symbol_id:{}]",
+                node.symbol.name,
+                node.symbol.kind,
+                SymbolContext::symbol_location(&node.symbol),
+                node.symbol.id.value()
+            )?;
+            if node.repeated {
+                write!(f, " (see above)")?;
+            }
+
+            let child_prefix = format!("{prefix}{}", if last { "    " } else { "│   " });
+            Self::write_nodes(f, &node.symbols, node.truncated, &child_prefix)?;
+        }
+        if truncated > 0 {
+            write!(f, "\n{prefix}└── ... and {truncated} more (truncated)")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for CallHierarchy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({:?}) at {} [symbol_id:{}]",
+            self.symbol.name,
+            self.symbol.kind,
+            SymbolContext::symbol_location(&self.symbol),
+            self.symbol.id.value()
+        )?;
+        Self::write_nodes(f, &self.tree.symbols, self.tree.truncated, "")
+    }
+}
+
+/// Output for `--format tree`: the symbol's call hierarchy, or not found when
+/// it has no callers or callees in that direction
+fn call_hierarchy_output<'a>(
+    indexer: &SimpleIndexer,
+    symbol: Symbol,
+    direction: CallDirection,
+    depth: usize,
+    resolved_only: bool,
+    mut metadata: OutputMetadata<'a>,
+) -> UnifiedOutput<'a, CallHierarchy> {
+    let tree =
+        indexer.get_call_hierarchy(symbol.id, direction, depth, TREE_MAX_NODES, resolved_only);
+    metadata.truncated = Some(tree.is_truncated());
+    let items = if tree.symbols.is_empty() && tree.truncated == 0 {
+        Vec::new()
+    } else {
+        vec![CallHierarchy { symbol, tree }]
+    };
+    UnifiedOutputBuilder::items(items, EntityType::Function)
+        .with_metadata(metadata)
+        .build()
+}
+
+/// A function reaching the target through a chain of calls
+#[derive(Debug, Clone, Serialize)]
+pub struct TransitiveCaller {
+    /// Calls between this function and the target (1 = direct caller)
+    pub distance: usize,
+    pub symbol: Symbol,
+    /// Location as `path:line`, like `SymbolContext::file_path`
+    pub file_path: String,
+}
+
+impl fmt::Display for TransitiveCaller {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{}] {:?} {} at {} [symbol_id:{}]",
+            self.distance,
+            self.symbol.kind,
+            self.symbol.name,
+            self.file_path,
+            self.symbol.id.value()
+        )
+    }
+}
+
 /// Execute retrieve callers command
+///
+/// With `transitive`, lists every function reaching the target through calls
+/// up to `depth` steps away, each marked with its distance. The tree format
+/// draws callers of callers to `depth` levels instead. With `resolved_only`,
+/// ambiguous and heuristic call edges are not followed.
 pub fn retrieve_callers(
     indexer: &SimpleIndexer,
     function: &str,
     language: Option<&str>,
+    transitive: bool,
+    depth: usize,
+    resolved_only: bool,
     format: OutputFormat,
 ) -> ExitCode {
     let mut output = OutputManager::new(format);
 
+    if let Err(e) = indexer.ensure_relationships_indexed() {
+        output_eprintln!("Error: {e}");
+        return ExitCode::GeneralError;
+    }
+
     // Check if function is a symbol_id (format: "symbol_id:123")
     let (symbol, query_str) = if let Some(id_str) = function.strip_prefix("symbol_id:") {
         // Direct symbol_id lookup
@@ -129,14 +241,14 @@ pub fn retrieve_callers(
                     return match output.unified(unified) {
                         Ok(code) => code,
                         Err(e) => {
-                            eprintln!("Error writing output: {e}");
+                            output_eprintln!("Error writing output: {e}");
                             ExitCode::GeneralError
                         }
                     };
                 }
             }
         } else {
-            eprintln!("Invalid symbol_id format: {id_str}");
+            output_eprintln!("Invalid symbol_id format: {id_str}");
             return ExitCode::GeneralError;
         }
     } else {
@@ -163,7 +275,7 @@ pub fn retrieve_callers(
             return match output.unified(unified) {
                 Ok(code) => code,
                 Err(e) => {
-                    eprintln!("Error writing output: {e}");
+                    output_eprintln!("Error writing output: {e}");
                     ExitCode::GeneralError
                 }
             };
@@ -171,25 +283,25 @@ pub fn retrieve_callers(
 
         if symbols.len() > 1 {
             // AMBIGUOUS - return error with list of symbol IDs
-            eprintln!(
+            output_eprintln!(
                 "Ambiguous: found {} symbol(s) named '{}':",
                 symbols.len(),
                 function
             );
             for (i, sym) in symbols.iter().take(10).enumerate() {
-                eprintln!(
+                output_eprintln!(
                     "  {}. symbol_id:{} - {:?} at {}:{}",
                     i + 1,
                     sym.id.value(),
                     sym.kind,
-                    sym.file_path,
+                    display_path(&sym.file_path),
                     sym.range.start_line + 1
                 );
             }
             if symbols.len() > 10 {
-                eprintln!("  ... and {} more", symbols.len() - 10);
+                output_eprintln!("  ... and {} more", symbols.len() - 10);
             }
-            eprintln!("\nUse: codanna retrieve callers symbol_id:<id>");
+            output_eprintln!("\nUse: codanna retrieve callers symbol_id:<id>");
             return ExitCode::GeneralError;
         }
 
@@ -197,55 +309,76 @@ pub fn retrieve_callers(
         (symbols.into_iter().next().unwrap(), function.to_string())
     };
 
-    // Get callers for THIS SPECIFIC symbol only (no aggregation)
-    let callers = indexer.get_calling_functions_with_metadata(symbol.id);
-    let all_callers: Vec<Symbol> = callers
-        .into_iter()
-        .map(|(caller, _metadata)| caller)
-        .collect();
-
-    // Transform to SymbolContext with relationships
-    use crate::symbol::context::ContextIncludes;
-
-    let callers_with_path: Vec<SymbolContext> = all_callers
-        .into_iter()
-        .filter_map(|symbol| {
-            // Get context for each caller symbol (what it calls and defines)
-            indexer.get_symbol_context(
-                symbol.id,
-                ContextIncludes::CALLS | ContextIncludes::DEFINITIONS,
+    let metadata = OutputMetadata {
+        query: Some(Cow::Owned(query_str)),
+        tool: None,
+        timing_ms: None,
+        truncated: None,
+        extra: Default::default(),
+    };
+    let result = match (format, transitive) {
+        (OutputFormat::Tree, _) => output.unified(call_hierarchy_output(
+            indexer,
+            symbol,
+            CallDirection::Callers,
+            depth,
+            resolved_only,
+            metadata,
+        )),
+        (_, true) => {
+            let callers: Vec<TransitiveCaller> = indexer
+                .get_transitive_callers(symbol.id, depth, resolved_only)
+                .into_iter()
+                .map(|(symbol, distance)| TransitiveCaller {
+                    distance,
+                    file_path: SymbolContext::symbol_location(&symbol),
+                    symbol,
+                })
+                .collect();
+            output.unified(
+                UnifiedOutputBuilder::items(callers, EntityType::Function)
+                    .with_metadata(metadata)
+                    .build(),
             )
-        })
-        .collect();
-
-    let unified = UnifiedOutputBuilder::items(callers_with_path, EntityType::Function)
-        .with_metadata(OutputMetadata {
-            query: Some(Cow::Owned(query_str)),
-            tool: None,
-            timing_ms: None,
-            truncated: None,
-            extra: Default::default(),
-        })
-        .build();
+        }
+        (_, false) => output.unified(
+            UnifiedOutputBuilder::items(
+                caller_contexts(indexer, &symbol, resolved_only),
+                EntityType::Function,
+            )
+            .with_metadata(metadata)
+            .build(),
+        ),
+    };
 
-    match output.unified(unified) {
+    match result {
         Ok(code) => code,
         Err(e) => {
-            eprintln!("Error writing output: {e}");
+            output_eprintln!("Error writing output: {e}");
             ExitCode::GeneralError
         }
     }
 }
 
 /// Execute retrieve calls command
+///
+/// The tree format draws what the callees call in turn, to `depth` levels.
+/// With `resolved_only`, ambiguous and heuristic call edges are not followed.
 pub fn retrieve_calls(
     indexer: &SimpleIndexer,
     function: &str,
     language: Option<&str>,
+    depth: usize,
+    resolved_only: bool,
     format: OutputFormat,
 ) -> ExitCode {
     let mut output = OutputManager::new(format);
 
+    if let Err(e) = indexer.ensure_relationships_indexed() {
+        output_eprintln!("Error: {e}");
+        return ExitCode::GeneralError;
+    }
+
     // Check if function is a symbol_id (format: "symbol_id:123" or just "123" if numeric)
     let (symbol, query_str) = if let Some(id_str) = function.strip_prefix("symbol_id:") {
         // Direct symbol_id lookup
@@ -271,14 +404,14 @@ pub fn retrieve_calls(
                     return match output.unified(unified) {
                         Ok(code) => code,
                         Err(e) => {
-                            eprintln!("Error writing output: {e}");
+                            output_eprintln!("Error writing output: {e}");
                             ExitCode::GeneralError
                         }
                     };
                 }
             }
         } else {
-            eprintln!("Invalid symbol_id format: {id_str}");
+            output_eprintln!("Invalid symbol_id format: {id_str}");
             return ExitCode::GeneralError;
         }
     } else {
@@ -305,7 +438,7 @@ pub fn retrieve_calls(
             return match output.unified(unified) {
                 Ok(code) => code,
                 Err(e) => {
-                    eprintln!("Error writing output: {e}");
+                    output_eprintln!("Error writing output: {e}");
                     ExitCode::GeneralError
                 }
             };
@@ -313,25 +446,25 @@ pub fn retrieve_calls(
 
         if symbols.len() > 1 {
             // AMBIGUOUS - return error with list of symbol IDs
-            eprintln!(
+            output_eprintln!(
                 "Ambiguous: found {} symbol(s) named '{}':",
                 symbols.len(),
                 function
             );
             for (i, sym) in symbols.iter().take(10).enumerate() {
-                eprintln!(
+                output_eprintln!(
                     "  {}. symbol_id:{} - {:?} at {}:{}",
                     i + 1,
                     sym.id.value(),
                     sym.kind,
-                    sym.file_path,
+                    display_path(&sym.file_path),
                     sym.range.start_line + 1
                 );
             }
             if symbols.len() > 10 {
-                eprintln!("  ... and {} more", symbols.len() - 10);
+                output_eprintln!("  ... and {} more", symbols.len() - 10);
             }
-            eprintln!("\nUse: codanna retrieve calls symbol_id:<id>");
+            output_eprintln!("\nUse: codanna retrieve calls symbol_id:<id>");
             return ExitCode::GeneralError;
         }
 
@@ -339,47 +472,272 @@ pub fn retrieve_calls(
         (symbols.into_iter().next().unwrap(), function.to_string())
     };
 
-    // Get calls for THIS SPECIFIC symbol only (no aggregation)
-    let calls = indexer.get_called_functions_with_metadata(symbol.id);
-    let all_calls: Vec<Symbol> = calls
-        .into_iter()
-        .map(|(called, _metadata)| called)
-        .collect();
+    let metadata = OutputMetadata {
+        query: Some(Cow::Owned(query_str)),
+        tool: None,
+        timing_ms: None,
+        truncated: None,
+        extra: Default::default(),
+    };
+    let result = if format == OutputFormat::Tree {
+        output.unified(call_hierarchy_output(
+            indexer,
+            symbol,
+            CallDirection::Callees,
+            depth,
+            resolved_only,
+            metadata,
+        ))
+    } else {
+        output.unified(
+            UnifiedOutputBuilder::items(
+                callee_contexts(indexer, &symbol, resolved_only),
+                EntityType::Function,
+            )
+            .with_metadata(metadata)
+            .build(),
+        )
+    };
+
+    match result {
+        Ok(code) => code,
+        Err(e) => {
+            output_eprintln!("Error writing output: {e}");
+            ExitCode::GeneralError
+        }
+    }
+}
 
-    // Transform to SymbolContext with relationships
+/// Full context for each symbol (same as MCP find_symbol)
+fn symbol_contexts(indexer: &SimpleIndexer, symbols: Vec<Symbol>) -> Vec<SymbolContext> {
     use crate::symbol::context::ContextIncludes;
 
-    let calls_with_path: Vec<SymbolContext> = all_calls
+    symbols
         .into_iter()
         .filter_map(|symbol| {
-            // Get context for each called function (who calls it, what it defines)
             indexer.get_symbol_context(
                 symbol.id,
+                ContextIncludes::IMPLEMENTATIONS
+                    | ContextIncludes::DEFINITIONS
+                    | ContextIncludes::CALLERS,
+            )
+        })
+        .collect()
+}
+
+/// Functions calling THIS SPECIFIC symbol only (no aggregation), with what each calls and defines
+fn caller_contexts(
+    indexer: &SimpleIndexer,
+    symbol: &Symbol,
+    resolved_only: bool,
+) -> Vec<SymbolContext> {
+    use crate::symbol::context::ContextIncludes;
+
+    indexer
+        .get_calling_functions_with_metadata(symbol.id)
+        .into_iter()
+        .filter(|(_, _, provenance)| !resolved_only || *provenance == Provenance::Resolved)
+        .filter_map(|(caller, _metadata, _)| {
+            indexer.get_symbol_context(
+                caller.id,
+                ContextIncludes::CALLS | ContextIncludes::DEFINITIONS,
+            )
+        })
+        .collect()
+}
+
+/// Functions THIS SPECIFIC symbol calls (no aggregation), with who calls each and what it defines
+fn callee_contexts(
+    indexer: &SimpleIndexer,
+    symbol: &Symbol,
+    resolved_only: bool,
+) -> Vec<SymbolContext> {
+    use crate::symbol::context::ContextIncludes;
+
+    indexer
+        .get_called_functions_with_metadata(symbol.id)
+        .into_iter()
+        .filter(|(_, _, provenance)| !resolved_only || *provenance == Provenance::Resolved)
+        .filter_map(|(called, _metadata, _)| {
+            indexer.get_symbol_context(
+                called.id,
                 ContextIncludes::CALLERS | ContextIncludes::DEFINITIONS,
             )
         })
+        .collect()
+}
+
+/// Retrieve commands that can run once per input line with `--from-stdin`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchCommand {
+    Symbol,
+    Calls,
+    Callers,
+}
+
+/// Result for one input of a `--from-stdin` batch, keyed by the input as given
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchEntry {
+    pub input: String,
+    pub status: OutputStatus,
+    pub items: Vec<SymbolContext>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl fmt::Display for BatchEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "== {} ==", self.input)?;
+        if let Some(error) = &self.error {
+            return write!(f, "\n{error}");
+        }
+        if self.items.is_empty() {
+            return write!(f, "\n(none)");
+        }
+        for item in &self.items {
+            write!(f, "\n{item}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Read batch inputs, one name or `symbol_id:N` per line
+///
+/// Lines are trimmed; blank lines are skipped and repeated inputs kept once,
+/// in first-seen order, so every input keys exactly one entry.
+pub fn read_batch_inputs<R: std::io::BufRead>(reader: R) -> std::io::Result<Vec<String>> {
+    let mut inputs: Vec<String> = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        let input = line.trim();
+        if !input.is_empty() && !inputs.iter().any(|seen| seen == input) {
+            inputs.push(input.to_string());
+        }
+    }
+    Ok(inputs)
+}
+
+/// Execute a retrieve command for each input against one loaded index
+///
+/// Emits one `BatchEntry` per input. Inputs that are not found, ambiguous
+/// (calls and callers need a single symbol) or malformed get an entry with
+/// that status instead of stopping the batch. Exits `NotFound` only when no
+/// input produced a match.
+pub fn retrieve_batch(
+    indexer: &SimpleIndexer,
+    command: BatchCommand,
+    inputs: &[String],
+    language: Option<&str>,
+    format: OutputFormat,
+) -> ExitCode {
+    let mut output = OutputManager::new(format);
+
+    let relationships = match command {
+        BatchCommand::Symbol => Ok(()),
+        BatchCommand::Calls | BatchCommand::Callers => indexer.ensure_relationships_indexed(),
+    };
+    if let Err(e) = relationships {
+        output_eprintln!("Error: {e}");
+        return ExitCode::GeneralError;
+    }
+
+    let entries: Vec<BatchEntry> = inputs
+        .iter()
+        .map(|input| batch_entry(indexer, command, input, language))
         .collect();
+    let any_found = entries
+        .iter()
+        .any(|entry| entry.status == OutputStatus::Success);
 
-    let unified = UnifiedOutputBuilder::items(calls_with_path, EntityType::Function)
+    let entity_type = match command {
+        BatchCommand::Symbol => EntityType::Symbol,
+        BatchCommand::Calls | BatchCommand::Callers => EntityType::Function,
+    };
+    let mut unified = UnifiedOutputBuilder::items(entries, entity_type)
         .with_metadata(OutputMetadata {
-            query: Some(Cow::Owned(query_str)),
+            query: None,
             tool: None,
             timing_ms: None,
             truncated: None,
             extra: Default::default(),
         })
         .build();
+    if !any_found {
+        unified.status = OutputStatus::NotFound;
+        unified.exit_code = ExitCode::NotFound;
+    }
 
     match output.unified(unified) {
         Ok(code) => code,
         Err(e) => {
-            eprintln!("Error writing output: {e}");
+            output_eprintln!("Error writing output: {e}");
             ExitCode::GeneralError
         }
     }
 }
 
+fn batch_entry(
+    indexer: &SimpleIndexer,
+    command: BatchCommand,
+    input: &str,
+    language: Option<&str>,
+) -> BatchEntry {
+    let entry = |status, items, error| BatchEntry {
+        input: input.to_string(),
+        status,
+        items,
+        error,
+    };
+
+    let symbols = if let Some(id_str) = input.strip_prefix("symbol_id:") {
+        match id_str.parse::<u32>() {
+            Ok(id) => indexer
+                .get_symbol(crate::SymbolId(id))
+                .into_iter()
+                .collect(),
+            Err(_) => {
+                return entry(
+                    OutputStatus::Error,
+                    Vec::new(),
+                    Some(format!("Invalid symbol_id format: {id_str}")),
+                );
+            }
+        }
+    } else {
+        indexer.find_symbols_by_name(input, language)
+    };
+
+    if symbols.is_empty() {
+        return entry(OutputStatus::NotFound, Vec::new(), None);
+    }
+
+    let items = match command {
+        BatchCommand::Symbol => symbol_contexts(indexer, symbols),
+        BatchCommand::Calls | BatchCommand::Callers if symbols.len() > 1 => {
+            let ids: Vec<String> = symbols
+                .iter()
+                .map(|sym| format!("symbol_id:{}", sym.id.value()))
+                .collect();
+            return entry(
+                OutputStatus::Error,
+                Vec::new(),
+                Some(format!(
+                    "Ambiguous: found {} symbol(s) named '{input}': {}",
+                    symbols.len(),
+                    ids.join(", ")
+                )),
+            );
+        }
+        BatchCommand::Calls => callee_contexts(indexer, &symbols[0], false),
+        BatchCommand::Callers => caller_contexts(indexer, &symbols[0], false),
+    };
+    entry(OutputStatus::Success, items, None)
+}
+
 /// Execute retrieve implementations command
+///
+/// Lists the types implementing a trait or interface, and for a class its
+/// direct subclasses.
 pub fn retrieve_implementations(
     indexer: &SimpleIndexer,
     trait_name: &str,
@@ -390,12 +748,34 @@ pub fn retrieve_implementations(
 
     // Find the trait symbol first
     let trait_symbols = indexer.find_symbols_by_name(trait_name, language);
-    let implementations = if let Some(trait_symbol) = trait_symbols.first() {
-        indexer.get_implementations(trait_symbol.id)
+    let (implementations, derived) = if let Some(trait_symbol) = trait_symbols.first() {
+        let mut implementations = indexer.get_implementations(trait_symbol.id);
+        implementations.extend(indexer.get_subclasses(trait_symbol.id));
+        (
+            implementations,
+            indexer.derived_implementations(trait_symbol.id),
+        )
     } else {
-        vec![]
+        // An unindexed trait (`serde::Serialize`) is only known from derives
+        let implementations = indexer.find_derived_implementors(trait_name, language);
+        let derived = implementations.iter().map(|symbol| symbol.id).collect();
+        (implementations, derived)
     };
 
+    // Implementations from `#[derive(...)]` have no impl block to show
+    let derived_names: Vec<&str> = implementations
+        .iter()
+        .filter(|symbol| derived.contains(&symbol.id))
+        .map(|symbol| symbol.name.as_ref())
+        .collect();
+    let mut extra = HashMap::new();
+    if !derived_names.is_empty() {
+        if !format.is_json() {
+            crate::info_eprintln!("Derived: {}", derived_names.join(", "));
+        }
+        extra.insert(Cow::Borrowed("derived"), serde_json::json!(derived_names));
+    }
+
     // Transform implementations to SymbolContext with relationships
     use crate::symbol::context::ContextIncludes;
 
@@ -416,74 +796,141 @@ pub fn retrieve_implementations(
             tool: None,
             timing_ms: None,
             truncated: None,
-            extra: Default::default(),
+            extra,
         })
         .build();
 
     match output.unified(unified) {
         Ok(code) => code,
         Err(e) => {
-            eprintln!("Error writing output: {e}");
+            output_eprintln!("Error writing output: {e}");
             ExitCode::GeneralError
         }
     }
 }
 
-/// Execute retrieve search command
-pub fn retrieve_search(
+/// Execute retrieve method-implementations command
+///
+/// Lists each type's own implementation of `Trait::method`, with the
+/// implementing type attached as the method's parent.
+pub fn retrieve_method_implementations(
     indexer: &SimpleIndexer,
-    query: &str,
-    limit: usize,
-    kind: Option<&str>,
-    module: Option<&str>,
+    method_path: &str,
     language: Option<&str>,
     format: OutputFormat,
 ) -> ExitCode {
+    use crate::symbol::context::ContextIncludes;
+
     let mut output = OutputManager::new(format);
 
-    // Parse the kind filter if provided
-    let kind_filter = kind.and_then(|k| match k.to_lowercase().as_str() {
-        "function" => Some(crate::SymbolKind::Function),
-        "struct" => Some(crate::SymbolKind::Struct),
-        "trait" => Some(crate::SymbolKind::Trait),
-        "interface" => Some(crate::SymbolKind::Interface),
-        "class" => Some(crate::SymbolKind::Class),
-        "method" => Some(crate::SymbolKind::Method),
-        "field" => Some(crate::SymbolKind::Field),
-        "variable" => Some(crate::SymbolKind::Variable),
-        "constant" => Some(crate::SymbolKind::Constant),
-        "module" => Some(crate::SymbolKind::Module),
-        "typealias" => Some(crate::SymbolKind::TypeAlias),
-        "enum" => Some(crate::SymbolKind::Enum),
-        _ => {
-            eprintln!("Warning: Unknown symbol kind '{k}', ignoring filter");
-            None
+    let implementations = match indexer.find_method_implementations(method_path, language) {
+        Ok(implementations) => implementations,
+        Err(e) => {
+            let unified = UnifiedOutput {
+                status: OutputStatus::NotFound,
+                entity_type: EntityType::Function,
+                count: 0,
+                data: OutputData::<SymbolContext>::Empty,
+                metadata: Some(OutputMetadata {
+                    query: Some(Cow::Borrowed(method_path)),
+                    tool: None,
+                    timing_ms: None,
+                    truncated: None,
+                    extra: Default::default(),
+                }),
+                guidance: Some(Cow::Owned(e.to_string())),
+                exit_code: ExitCode::NotFound,
+            };
+            return match output.unified(unified) {
+                Ok(code) => code,
+                Err(e) => {
+                    output_eprintln!("Error writing output: {e}");
+                    ExitCode::GeneralError
+                }
+            };
         }
-    });
+    };
 
-    let search_results = indexer
-        .search(query, limit, kind_filter, module, language)
-        .unwrap_or_default();
+    let methods: Vec<SymbolContext> = implementations
+        .into_iter()
+        .filter_map(|implementation| {
+            let mut context =
+                indexer.get_symbol_context(implementation.method.id, ContextIncludes::empty())?;
+            context.relationships.parent = Some(implementation.implementor);
+            Some(context)
+        })
+        .collect();
 
-    // Transform search results to SymbolContext with relationships
-    use crate::symbol::context::ContextIncludes;
+    let unified = UnifiedOutputBuilder::items(methods, EntityType::Function)
+        .with_metadata(OutputMetadata {
+            query: Some(Cow::Borrowed(method_path)),
+            tool: None,
+            timing_ms: None,
+            truncated: None,
+            extra: Default::default(),
+        })
+        .build();
 
-    let results_with_path: Vec<SymbolContext> = search_results
+    match output.unified(unified) {
+        Ok(code) => code,
+        Err(e) => {
+            output_eprintln!("Error writing output: {e}");
+            ExitCode::GeneralError
+        }
+    }
+}
+
+/// Execute retrieve defines command
+///
+/// Lists the methods a type or trait defines: inherent methods first, then
+/// trait impl methods grouped by trait, each showing the trait it implements.
+pub fn retrieve_defines(
+    indexer: &SimpleIndexer,
+    type_name: &str,
+    language: Option<&str>,
+    format: OutputFormat,
+) -> ExitCode {
+    use crate::SymbolKind;
+    use crate::symbol::context::SymbolRelationships;
+
+    let mut output = OutputManager::new(format);
+
+    let owner = indexer
+        .find_symbols_by_name(type_name, language)
         .into_iter()
-        .filter_map(|result| {
-            // Get full context for each search result
-            indexer.get_symbol_context(
-                result.symbol_id,
-                ContextIncludes::IMPLEMENTATIONS
-                    | ContextIncludes::DEFINITIONS
-                    | ContextIncludes::CALLERS,
+        .find(|s| {
+            matches!(
+                s.kind,
+                SymbolKind::Struct
+                    | SymbolKind::Enum
+                    | SymbolKind::Class
+                    | SymbolKind::Trait
+                    | SymbolKind::Interface
+                    | SymbolKind::TypeAlias
             )
+        });
+
+    let methods: Vec<SymbolContext> = owner
+        .map(|owner| {
+            indexer
+                .get_type_methods(owner.id)
+                .into_iter()
+                .map(|type_method| SymbolContext {
+                    file_path: SymbolContext::symbol_location(&type_method.method),
+                    relationships: SymbolRelationships {
+                        parent: Some(owner.clone()),
+                        implements: type_method.implements.map(|t| vec![t]),
+                        ..Default::default()
+                    },
+                    symbol: type_method.method,
+                })
+                .collect()
         })
-        .collect();
+        .unwrap_or_default();
 
-    let unified = UnifiedOutputBuilder::items(results_with_path, EntityType::SearchResult)
+    let unified = UnifiedOutputBuilder::items(methods, EntityType::Function)
         .with_metadata(OutputMetadata {
-            query: Some(Cow::Borrowed(query)),
+            query: Some(Cow::Borrowed(type_name)),
             tool: None,
             timing_ms: None,
             truncated: None,
@@ -494,37 +941,57 @@ pub fn retrieve_search(
     match output.unified(unified) {
         Ok(code) => code,
         Err(e) => {
-            eprintln!("Error writing output: {e}");
+            output_eprintln!("Error writing output: {e}");
             ExitCode::GeneralError
         }
     }
 }
 
-/// Execute retrieve impact command
-// DEPRECATED: This function has been disabled.
-// Use MCP semantic_search_with_context or slash commands instead.
-// The impact command had fundamental flaws:
-// - Only worked for functions, not structs/traits/enums
-// - Returned empty results for valid symbols
-// - Conceptually wrong (not all symbols have "impact")
-#[allow(dead_code)]
-pub fn retrieve_impact(
+/// A function whose signature mentions a type (`retrieve uses-in-signature`)
+#[derive(Debug, Clone, Serialize)]
+pub struct SignatureUser {
+    /// `parameter` or `return`
+    pub position: TypeUsePosition,
+    pub symbol: Symbol,
+    /// Location as `path:line`, like `SymbolContext::file_path`
+    pub file_path: String,
+}
+
+impl fmt::Display for SignatureUser {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{}] {:?} {} at {} [symbol_id:{}]",
+            self.position.as_str(),
+            self.symbol.kind,
+            self.symbol.name,
+            self.file_path,
+            self.symbol.id.value()
+        )
+    }
+}
+
+/// Execute retrieve uses-in-signature command
+///
+/// Lists functions and methods taking the type as a parameter or returning
+/// it. Fields and local variables of that type are left out. Types of
+/// languages that record no type-use positions are reported as unsupported.
+pub fn retrieve_uses_in_signature(
     indexer: &SimpleIndexer,
-    symbol_name: &str,
-    max_depth: usize,
+    type_name: &str,
+    language: Option<&str>,
     format: OutputFormat,
 ) -> ExitCode {
     let mut output = OutputManager::new(format);
-    let symbols = indexer.find_symbols_by_name(symbol_name, None);
 
-    if symbols.is_empty() {
+    let Some(owner) = indexer.find_type_by_name(type_name, language) else {
         let unified = UnifiedOutput {
             status: OutputStatus::NotFound,
-            entity_type: EntityType::Impact,
+            entity_type: EntityType::Function,
             count: 0,
-            data: OutputData::<SymbolContext>::Empty,
+            data: OutputData::<SignatureUser>::Empty,
             metadata: Some(OutputMetadata {
-                query: Some(Cow::Borrowed(symbol_name)),
+                query: Some(Cow::Borrowed(type_name)),
                 tool: None,
                 timing_ms: None,
                 truncated: None,
@@ -533,23 +1000,360 @@ pub fn retrieve_impact(
             guidance: None,
             exit_code: ExitCode::NotFound,
         };
-
-        match output.unified(unified) {
+        return match output.unified(unified) {
             Ok(code) => code,
             Err(e) => {
-                eprintln!("Error writing output: {e}");
+                output_eprintln!("Error writing output: {e}");
                 ExitCode::GeneralError
             }
+        };
+    };
+
+    let signature_uses = match indexer.get_signature_uses(owner.id) {
+        Ok(signature_uses) => signature_uses,
+        Err(e) => {
+            output_eprintln!("Error: {e}");
+            return ExitCode::GeneralError;
         }
-    } else {
-        // Get impact analysis for the first matching symbol
-        let symbol = &symbols[0];
-        let impact_symbol_ids = indexer.get_impact_radius(symbol.id, Some(max_depth));
+    };
+    let users: Vec<SignatureUser> = signature_uses
+        .into_iter()
+        .map(|signature_use| SignatureUser {
+            position: signature_use.position,
+            file_path: SymbolContext::symbol_location(&signature_use.symbol),
+            symbol: signature_use.symbol,
+        })
+        .collect();
 
-        // Transform impact symbols to SymbolContext with relationships
-        use crate::symbol::context::ContextIncludes;
+    let unified = UnifiedOutputBuilder::items(users, EntityType::Function)
+        .with_metadata(OutputMetadata {
+            query: Some(Cow::Borrowed(type_name)),
+            tool: None,
+            timing_ms: None,
+            truncated: None,
+            extra: Default::default(),
+        })
+        .build();
+
+    match output.unified(unified) {
+        Ok(code) => code,
+        Err(e) => {
+            output_eprintln!("Error writing output: {e}");
+            ExitCode::GeneralError
+        }
+    }
+}
+
+/// Search result with the source lines around it (`retrieve search --context-lines`)
+#[derive(Debug, Serialize)]
+pub struct SearchResultWithSnippet {
+    #[serde(flatten)]
+    pub context: SymbolContext,
+    /// Source lines centered on the symbol's start line
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<crate::indexing::SourceSnippet>,
+    /// Why the snippet is missing or may be off
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snippet_note: Option<String>,
+}
+
+impl SearchResultWithSnippet {
+    fn new(indexer: &SimpleIndexer, context: SymbolContext, context_lines: u32) -> Self {
+        let (snippet, snippet_note) =
+            match indexer.get_source_snippet(&context.symbol, context_lines) {
+                Ok(snippet) => {
+                    let note = snippet.stale.then(|| {
+                        "file changed since indexing; lines may not match the symbol".to_string()
+                    });
+                    (Some(snippet), note)
+                }
+                Err(e) => (None, Some(format!("snippet skipped: {e}"))),
+            };
+        Self {
+            context,
+            snippet,
+            snippet_note,
+        }
+    }
+}
+
+impl fmt::Display for SearchResultWithSnippet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.context)?;
+        if let Some(snippet) = &self.snippet {
+            let path = display_path(&self.context.symbol.file_path);
+            write!(f, "\n{}", snippet.format_rg(&path).trim_end())?;
+        }
+        if let Some(note) = &self.snippet_note {
+            write!(f, "\n({note})")?;
+        }
+        Ok(())
+    }
+}
+
+/// Execute retrieve search command
+///
+/// With `substring`, names containing `query` (ignoring case) are matched
+/// directly instead of running a full-text query. With `exact`, only whole
+/// names equal to `query` match (ignoring case unless `case_sensitive`). With `context_lines`, each
+/// result carries that many source lines around its start line. With `fields`,
+/// each result is printed as one row holding only those fields, and
+/// `context_lines` is ignored.
+#[allow(clippy::too_many_arguments)]
+pub fn retrieve_search(
+    indexer: &SimpleIndexer,
+    query: &str,
+    limit: usize,
+    kind: Option<&str>,
+    module: Option<&str>,
+    language: Option<&str>,
+    attribute: Option<&str>,
+    since_index: Option<&str>,
+    group_by: Option<&str>,
+    min_score: Option<f32>,
+    substring: bool,
+    exact: bool,
+    case_sensitive: bool,
+    context_lines: Option<u32>,
+    fields: &[String],
+    format: OutputFormat,
+) -> ExitCode {
+    let mut output = OutputManager::new(format);
+
+    let fields = match crate::io::parse_fields(fields) {
+        Ok(fields) => fields,
+        Err(e) => {
+            output_eprintln!("Error: {e}");
+            return ExitCode::GeneralError;
+        }
+    };
+
+    // Parse the kind filter if provided
+    let kind_filter = match kind.map(crate::SymbolKind::parse_filter).transpose() {
+        Ok(kind_filter) => kind_filter,
+        Err(e) => {
+            output_eprintln!("Error: {e}");
+            return ExitCode::GeneralError;
+        }
+    };
+    let group_by = match group_by
+        .map(crate::storage::SearchGroupBy::parse_filter)
+        .transpose()
+    {
+        Ok(group_by) => group_by,
+        Err(e) => {
+            output_eprintln!("Error: {e}");
+            return ExitCode::GeneralError;
+        }
+    };
+
+    let since_secs = match since_index.map(crate::io::args::parse_duration).transpose() {
+        Ok(since_secs) => since_secs,
+        Err(e) => {
+            output_eprintln!("Error: {e}");
+            return ExitCode::GeneralError;
+        }
+    };
+
+    let since = since_secs.map(|secs| crate::indexing::get_utc_timestamp().saturating_sub(secs));
+    let recent_files = since.map(|since| indexer.files_indexed_since(since));
+    let filters = crate::storage::SearchFilters {
+        attribute,
+        files: recent_files.as_ref(),
+        ..Default::default()
+    };
+    let mut search_results = if exact {
+        indexer.search_exact(
+            query,
+            case_sensitive,
+            limit,
+            kind_filter,
+            module,
+            language,
+            &filters,
+        )
+    } else if substring {
+        indexer.search_substring(query, limit, kind_filter, module, language, &filters)
+    } else {
+        indexer.search(query, limit, kind_filter, module, language, &filters)
+    }
+    .unwrap_or_default();
+    let dropped = min_score
+        .map(|min| crate::storage::retain_min_score(&mut search_results, min))
+        .unwrap_or(0);
+
+    let mut extra = HashMap::new();
+    if let Some(since) = since {
+        extra.insert(Cow::Borrowed("indexed_since"), serde_json::json!(since));
+    }
+    if let Some(min) = min_score {
+        extra.insert(Cow::Borrowed("min_score"), serde_json::json!(min));
+        extra.insert(Cow::Borrowed("dropped"), serde_json::json!(dropped));
+        if dropped > 0 && !format.is_json() {
+            crate::info_eprintln!("{dropped} result(s) below min_score {min} dropped");
+        }
+    }
+
+    let metadata = OutputMetadata {
+        query: Some(Cow::Borrowed(query)),
+        tool: None,
+        timing_ms: None,
+        truncated: None,
+        extra,
+    };
+
+    // Counts per group replace the individual results; they cover only the
+    // results within the limit
+    if let Some(group_by) = group_by {
+        let groups = crate::storage::group_results(&search_results, group_by);
+        let mut metadata = metadata;
+        metadata.extra.insert(
+            Cow::Borrowed("counted"),
+            serde_json::json!(search_results.len()),
+        );
+        if search_results.len() == limit && !format.is_json() {
+            crate::info_eprintln!(
+                "Counts cover the first {limit} results; raise --limit to count more"
+            );
+        }
+        return match output.unified(
+            UnifiedOutputBuilder::items(groups, EntityType::SearchResult)
+                .with_metadata(metadata)
+                .build(),
+        ) {
+            Ok(code) => code,
+            Err(e) => {
+                output_eprintln!("Error writing output: {e}");
+                ExitCode::GeneralError
+            }
+        };
+    }
+
+    if !fields.is_empty() {
+        let rows: Vec<crate::io::FieldRow> = search_results
+            .into_iter()
+            .map(|result| crate::io::FieldRow::new(&fields, result))
+            .collect();
+        return match output.unified(
+            UnifiedOutputBuilder::items(rows, EntityType::SearchResult)
+                .with_metadata(metadata)
+                .build(),
+        ) {
+            Ok(code) => code,
+            Err(e) => {
+                output_eprintln!("Error writing output: {e}");
+                ExitCode::GeneralError
+            }
+        };
+    }
+
+    // Transform search results to SymbolContext with relationships
+    use crate::symbol::context::ContextIncludes;
+
+    let results_with_path: Vec<SymbolContext> = search_results
+        .into_iter()
+        .filter_map(|result| {
+            // Get full context for each search result
+            indexer.get_symbol_context(
+                result.symbol_id,
+                ContextIncludes::IMPLEMENTATIONS
+                    | ContextIncludes::DEFINITIONS
+                    | ContextIncludes::CALLERS,
+            )
+        })
+        .collect();
+
+    let written = match context_lines {
+        Some(context_lines) => {
+            let results: Vec<SearchResultWithSnippet> = results_with_path
+                .into_iter()
+                .map(|context| SearchResultWithSnippet::new(indexer, context, context_lines))
+                .collect();
+            output.unified(
+                UnifiedOutputBuilder::items(results, EntityType::SearchResult)
+                    .with_metadata(metadata)
+                    .build(),
+            )
+        }
+        None => output.unified(
+            UnifiedOutputBuilder::items(results_with_path, EntityType::SearchResult)
+                .with_metadata(metadata)
+                .build(),
+        ),
+    };
+
+    match written {
+        Ok(code) => code,
+        Err(e) => {
+            output_eprintln!("Error writing output: {e}");
+            ExitCode::GeneralError
+        }
+    }
+}
+
+/// Execute retrieve impact command
+// DEPRECATED: This function has been disabled.
+// Use MCP semantic_search_with_context or slash commands instead.
+// The impact command had fundamental flaws:
+// - Only worked for functions, not structs/traits/enums
+// - Returned empty results for valid symbols
+// - Conceptually wrong (not all symbols have "impact")
+#[allow(dead_code)]
+pub fn retrieve_impact(
+    indexer: &SimpleIndexer,
+    symbol_name: &str,
+    max_depth: usize,
+    format: OutputFormat,
+) -> ExitCode {
+    let mut output = OutputManager::new(format);
+
+    if let Err(e) = indexer.ensure_relationships_indexed() {
+        output_eprintln!("Error: {e}");
+        return ExitCode::GeneralError;
+    }
+
+    let symbols = indexer.find_symbols_by_name(symbol_name, None);
+
+    if symbols.is_empty() {
+        let unified = UnifiedOutput {
+            status: OutputStatus::NotFound,
+            entity_type: EntityType::Impact,
+            count: 0,
+            data: OutputData::<SymbolContext>::Empty,
+            metadata: Some(OutputMetadata {
+                query: Some(Cow::Borrowed(symbol_name)),
+                tool: None,
+                timing_ms: None,
+                truncated: None,
+                extra: Default::default(),
+            }),
+            guidance: None,
+            exit_code: ExitCode::NotFound,
+        };
+
+        match output.unified(unified) {
+            Ok(code) => code,
+            Err(e) => {
+                output_eprintln!("Error writing output: {e}");
+                ExitCode::GeneralError
+            }
+        }
+    } else {
+        // Get impact analysis for the first matching symbol
+        let symbol = &symbols[0];
+        let impact = indexer.get_impact_radius(symbol.id, Some(max_depth), false);
+        if impact.truncated && !format.is_json() {
+            crate::info_eprintln!(
+                "Impact truncated at {} symbols (mcp.max_impact_nodes)",
+                impact.symbols.len()
+            );
+        }
 
-        let impact_with_path: Vec<SymbolContext> = impact_symbol_ids
+        // Transform impact symbols to SymbolContext with relationships
+        use crate::symbol::context::ContextIncludes;
+
+        let impact_with_path: Vec<SymbolContext> = impact
+            .symbols
             .into_iter()
             .filter_map(|symbol_id| {
                 // Get full context for each impacted symbol
@@ -565,7 +1369,7 @@ pub fn retrieve_impact(
                 query: Some(Cow::Borrowed(symbol_name)),
                 tool: None,
                 timing_ms: None,
-                truncated: None,
+                truncated: Some(impact.truncated),
                 extra: Default::default(),
             })
             .build();
@@ -573,22 +1377,34 @@ pub fn retrieve_impact(
         match output.unified(unified) {
             Ok(code) => code,
             Err(e) => {
-                eprintln!("Error writing output: {e}");
+                output_eprintln!("Error writing output: {e}");
                 ExitCode::GeneralError
             }
         }
     }
 }
 
+/// Most callers or callees kept under each symbol by `describe --depth`
+const DESCRIBE_MAX_CHILDREN: usize = 20;
+
 /// Execute retrieve describe command
+///
+/// With `depth` above 1, callers and callees are expanded into nested trees
+/// of that many levels.
 pub fn retrieve_describe(
     indexer: &SimpleIndexer,
     symbol_name: &str,
     language: Option<&str>,
+    depth: usize,
     format: OutputFormat,
 ) -> ExitCode {
     let mut output = OutputManager::new(format);
 
+    if let Err(e) = indexer.ensure_relationships_indexed() {
+        output_eprintln!("Error: {e}");
+        return ExitCode::GeneralError;
+    }
+
     // Check if symbol_name is a symbol_id (format: "symbol_id:123")
     let (symbol, query_str) = if let Some(id_str) = symbol_name.strip_prefix("symbol_id:") {
         // Direct symbol_id lookup
@@ -614,14 +1430,14 @@ pub fn retrieve_describe(
                     return match output.unified(unified) {
                         Ok(code) => code,
                         Err(e) => {
-                            eprintln!("Error writing output: {e}");
+                            output_eprintln!("Error writing output: {e}");
                             ExitCode::GeneralError
                         }
                     };
                 }
             }
         } else {
-            eprintln!("Invalid symbol_id format: {id_str}");
+            output_eprintln!("Invalid symbol_id format: {id_str}");
             return ExitCode::GeneralError;
         }
     } else {
@@ -648,7 +1464,7 @@ pub fn retrieve_describe(
             return match output.unified(unified) {
                 Ok(code) => code,
                 Err(e) => {
-                    eprintln!("Error writing output: {e}");
+                    output_eprintln!("Error writing output: {e}");
                     ExitCode::GeneralError
                 }
             };
@@ -656,25 +1472,25 @@ pub fn retrieve_describe(
 
         if symbols.len() > 1 {
             // AMBIGUOUS - return error with list of symbol IDs
-            eprintln!(
+            output_eprintln!(
                 "Ambiguous: found {} symbol(s) named '{}':",
                 symbols.len(),
                 symbol_name
             );
             for (i, sym) in symbols.iter().take(10).enumerate() {
-                eprintln!(
+                output_eprintln!(
                     "  {}. symbol_id:{} - {:?} at {}:{}",
                     i + 1,
                     sym.id.value(),
                     sym.kind,
-                    sym.file_path,
+                    display_path(&sym.file_path),
                     sym.range.start_line + 1
                 );
             }
             if symbols.len() > 10 {
-                eprintln!("  ... and {} more", symbols.len() - 10);
+                output_eprintln!("  ... and {} more", symbols.len() - 10);
             }
-            eprintln!("\nUse: codanna retrieve describe symbol_id:<id>");
+            output_eprintln!("\nUse: codanna retrieve describe symbol_id:<id>");
             return ExitCode::GeneralError;
         }
 
@@ -690,6 +1506,7 @@ pub fn retrieve_describe(
         file_path,
         relationships: Default::default(),
     };
+    context.relationships.parent = symbol.parent.and_then(|id| indexer.get_symbol(id));
 
     // Get calls for this specific symbol
     let calls = indexer.get_called_functions_with_metadata(symbol.id);
@@ -716,11 +1533,36 @@ pub fn retrieve_describe(
             let implementations = indexer.get_implementations(symbol.id);
             if !implementations.is_empty() {
                 context.relationships.implemented_by = Some(implementations);
+                context.relationships.derived = indexer.derived_implementations(symbol.id);
             }
         }
         _ => {}
     }
 
+    // Expand callers and callees into trees for a dependency overview
+    let mut truncated = None;
+    if depth > 1 {
+        let call_tree = indexer.get_call_tree(
+            symbol.id,
+            CallDirection::Callees,
+            depth,
+            DESCRIBE_MAX_CHILDREN,
+        );
+        let caller_tree = indexer.get_call_tree(
+            symbol.id,
+            CallDirection::Callers,
+            depth,
+            DESCRIBE_MAX_CHILDREN,
+        );
+        truncated = Some(call_tree.is_truncated() || caller_tree.is_truncated());
+        if !call_tree.symbols.is_empty() {
+            context.relationships.call_tree = Some(call_tree);
+        }
+        if !caller_tree.symbols.is_empty() {
+            context.relationships.caller_tree = Some(caller_tree);
+        }
+    }
+
     let unified = UnifiedOutput {
         status: OutputStatus::Success,
         entity_type: EntityType::Symbol,
@@ -732,7 +1574,7 @@ pub fn retrieve_describe(
             query: Some(Cow::Owned(query_str)),
             tool: None,
             timing_ms: None,
-            truncated: None,
+            truncated,
             extra: Default::default(),
         }),
         guidance: None,
@@ -742,8 +1584,841 @@ pub fn retrieve_describe(
     match output.unified(unified) {
         Ok(code) => code,
         Err(e) => {
-            eprintln!("Error writing output: {e}");
+            output_eprintln!("Error writing output: {e}");
+            ExitCode::GeneralError
+        }
+    }
+}
+
+/// Execute retrieve coupling command
+pub fn retrieve_coupling(indexer: &SimpleIndexer, top: usize, format: OutputFormat) -> ExitCode {
+    let mut output = OutputManager::new(format);
+
+    let mut coupling = match indexer.file_coupling() {
+        Ok(coupling) => coupling,
+        Err(e) => {
+            output_eprintln!("Error computing file coupling: {e}");
+            return ExitCode::GeneralError;
+        }
+    };
+    let total_files = coupling.len();
+    coupling.truncate(top);
+
+    let unified = UnifiedOutputBuilder::items(coupling, EntityType::Mixed)
+        .with_metadata(OutputMetadata {
+            query: None,
+            tool: None,
+            timing_ms: None,
+            truncated: Some(total_files > top),
+            extra: Default::default(),
+        })
+        .build();
+
+    match output.unified(unified) {
+        Ok(code) => code,
+        Err(e) => {
+            output_eprintln!("Error writing output: {e}");
+            ExitCode::GeneralError
+        }
+    }
+}
+
+/// Entry points of one category, as listed by `retrieve entrypoints`
+#[derive(Debug, Clone, Serialize)]
+pub struct EntryPointGroup {
+    pub category: EntryPointCategory,
+    /// Entry points found in this category, including those not listed
+    pub count: usize,
+    pub entry_points: Vec<EntryPoint>,
+}
+
+impl fmt::Display for EntryPointGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}):", self.category, self.count)?;
+        for entry_point in &self.entry_points {
+            write!(f, "\n  {entry_point}")?;
+        }
+        let hidden = self.count - self.entry_points.len();
+        if hidden > 0 {
+            write!(f, "\n  ... and {hidden} more")?;
+        }
+        Ok(())
+    }
+}
+
+/// Execute retrieve entrypoints command
+///
+/// Lists likely entry points grouped by category, at most `limit` per group.
+/// Public functions without callers count as findings for `fail_on_findings`.
+pub fn retrieve_entrypoints(
+    indexer: &SimpleIndexer,
+    limit: usize,
+    format: OutputFormat,
+    fail_on_findings: bool,
+) -> ExitCode {
+    let mut output = OutputManager::new(format);
+
+    let mut groups: Vec<EntryPointGroup> = Vec::new();
+    for entry_point in indexer.entry_points() {
+        match groups.last_mut() {
+            Some(group) if group.category == entry_point.category => {
+                group.count += 1;
+                if group.entry_points.len() < limit {
+                    group.entry_points.push(entry_point);
+                }
+            }
+            _ => groups.push(EntryPointGroup {
+                category: entry_point.category,
+                count: 1,
+                entry_points: vec![entry_point],
+            }),
+        }
+    }
+    let truncated = groups
+        .iter()
+        .any(|group| group.count > group.entry_points.len());
+    let findings: usize = groups
+        .iter()
+        .filter(|group| group.category == EntryPointCategory::Uncalled)
+        .map(|group| group.count)
+        .sum();
+
+    if format == OutputFormat::GithubAnnotations {
+        let annotations: Vec<Annotation> = groups
+            .iter()
+            .flat_map(|group| &group.entry_points)
+            .filter_map(|entry_point| {
+                let (level, message) = match entry_point.category {
+                    EntryPointCategory::Uncalled => (
+                        AnnotationLevel::Warning,
+                        format!(
+                            "{} is public but nothing in the index calls it",
+                            entry_point.name
+                        ),
+                    ),
+                    _ => (
+                        AnnotationLevel::Notice,
+                        format!("{} is a likely entry point", entry_point.name),
+                    ),
+                };
+                Annotation::at_location(
+                    level,
+                    &entry_point.file_path,
+                    entry_point.category.to_string(),
+                    message,
+                )
+            })
+            .collect();
+        return write_annotations(&mut output, &annotations, findings, fail_on_findings);
+    }
+
+    let unified = UnifiedOutputBuilder::items(groups, EntityType::Function)
+        .with_metadata(OutputMetadata {
+            query: None,
+            tool: None,
+            timing_ms: None,
+            truncated: Some(truncated),
+            extra: Default::default(),
+        })
+        .build();
+
+    match output.unified(unified) {
+        Ok(code) => analysis_exit_code(code, findings, fail_on_findings),
+        Err(e) => {
+            output_eprintln!("Error writing output: {e}");
+            ExitCode::GeneralError
+        }
+    }
+}
+
+/// Execute retrieve ambiguities command
+///
+/// Lists relationship sites where resolution picked one of several
+/// same-named symbols, with every candidate. Each site is a finding for
+/// `fail_on_findings`.
+pub fn retrieve_ambiguities(
+    indexer: &SimpleIndexer,
+    format: OutputFormat,
+    fail_on_findings: bool,
+) -> ExitCode {
+    let mut output = OutputManager::new(format);
+
+    if let Err(e) = indexer.ensure_relationships_indexed() {
+        output_eprintln!("Error: {e}");
+        return ExitCode::GeneralError;
+    }
+
+    let sites = match indexer.ambiguous_resolutions() {
+        Ok(sites) => sites,
+        Err(e) => {
+            output_eprintln!("Error: Failed to read relationships: {e}");
+            return ExitCode::GeneralError;
+        }
+    };
+    let findings = sites.len();
+
+    if format == OutputFormat::GithubAnnotations {
+        let annotations: Vec<Annotation> = sites
+            .iter()
+            .filter_map(|site| {
+                let candidates: Vec<String> = site
+                    .candidates
+                    .iter()
+                    .map(SymbolContext::symbol_location)
+                    .collect();
+                Annotation::at_location(
+                    AnnotationLevel::Warning,
+                    &site.location,
+                    format!("Ambiguous {:?} target: {}", site.kind, site.target.name),
+                    format!(
+                        "{} resolved {} to {} among {} candidates: {}. Import or qualify the name to resolve it exactly",
+                        site.source.name,
+                        site.target.name,
+                        SymbolContext::symbol_location(&site.target),
+                        site.candidates.len(),
+                        candidates.join(", ")
+                    ),
+                )
+            })
+            .collect();
+        return write_annotations(&mut output, &annotations, findings, fail_on_findings);
+    }
+
+    let unified = UnifiedOutputBuilder::items(sites, EntityType::Mixed)
+        .with_metadata(OutputMetadata {
+            query: None,
+            tool: None,
+            timing_ms: None,
+            truncated: None,
+            extra: Default::default(),
+        })
+        .build();
+
+    match output.unified(unified) {
+        Ok(code) => analysis_exit_code(code, findings, fail_on_findings),
+        Err(e) => {
+            output_eprintln!("Error writing output: {e}");
+            ExitCode::GeneralError
+        }
+    }
+}
+
+/// Execute retrieve signature-changes command
+///
+/// `baseline` is an index directory (the one holding `tantivy/`), or a
+/// project root or settings directory containing one.
+pub fn retrieve_signature_changes(
+    indexer: &SimpleIndexer,
+    baseline: &Path,
+    format: OutputFormat,
+) -> ExitCode {
+    let mut output = OutputManager::new(format);
+
+    let index_path = [
+        baseline.to_path_buf(),
+        baseline.join("index"),
+        baseline.join(&indexer.settings().index_path),
+    ]
+    .into_iter()
+    .find(|candidate| candidate.join("tantivy").is_dir());
+    let Some(index_path) = index_path else {
+        output_eprintln!(
+            "Error: No index found at {} (expected a directory containing tantivy/)",
+            baseline.display()
+        );
+        return ExitCode::GeneralError;
+    };
+
+    let mut settings = indexer.settings().clone();
+    settings.index_path = index_path;
+    settings.workspace_root = None;
+    let baseline_indexer = match SimpleIndexer::with_settings_read_only(Arc::new(settings)) {
+        Ok(baseline_indexer) => baseline_indexer,
+        Err(e) => {
+            output_eprintln!("Error: Failed to open baseline index: {e}");
+            return ExitCode::GeneralError;
+        }
+    };
+
+    let changes = match indexer.signature_changes(&baseline_indexer) {
+        Ok(changes) => changes,
+        Err(e) => {
+            output_eprintln!("Error: Failed to compare signatures: {e}");
+            return ExitCode::GeneralError;
+        }
+    };
+
+    let unified = UnifiedOutputBuilder::items(changes, EntityType::Function)
+        .with_metadata(OutputMetadata {
+            query: None,
+            tool: None,
+            timing_ms: None,
+            truncated: None,
+            extra: Default::default(),
+        })
+        .build();
+
+    match output.unified(unified) {
+        Ok(code) => code,
+        Err(e) => {
+            output_eprintln!("Error writing output: {e}");
+            ExitCode::GeneralError
+        }
+    }
+}
+
+/// Execute retrieve doc-coverage command
+///
+/// Lists modules from lowest coverage up with their undocumented public
+/// symbols. With `min`, coverage below that fraction exits with
+/// `FindingsReported`.
+pub fn retrieve_doc_coverage(
+    indexer: &SimpleIndexer,
+    min: Option<f64>,
+    format: OutputFormat,
+) -> ExitCode {
+    let mut output = OutputManager::new(format);
+
+    if let Some(min) = min.filter(|min| !(0.0..=1.0).contains(min)) {
+        output_eprintln!("Error: --min must be a fraction between 0 and 1, got {min}");
+        return ExitCode::GeneralError;
+    }
+
+    let coverage = match indexer.doc_coverage() {
+        Ok(coverage) => coverage,
+        Err(e) => {
+            output_eprintln!("Error: Failed to read symbols: {e}");
+            return ExitCode::GeneralError;
+        }
+    };
+    let ratio = coverage.ratio();
+    let below_min = min.filter(|&min| ratio < min);
+
+    let mut extra = HashMap::new();
+    extra.insert(
+        Cow::Borrowed("documented"),
+        serde_json::json!(coverage.documented),
+    );
+    extra.insert(Cow::Borrowed("total"), serde_json::json!(coverage.total));
+    extra.insert(Cow::Borrowed("coverage"), serde_json::json!(ratio));
+    if let Some(min) = min {
+        extra.insert(Cow::Borrowed("min"), serde_json::json!(min));
+    }
+    if !format.is_json() {
+        crate::info_eprintln!(
+            "Doc coverage: {:.1}% ({}/{} public symbols documented)",
+            ratio * 100.0,
+            coverage.documented,
+            coverage.total
+        );
+    }
+    if let Some(min) = below_min {
+        output_eprintln!(
+            "Error: Doc coverage {:.1}% is below --min {:.1}%",
+            ratio * 100.0,
+            min * 100.0
+        );
+    }
+
+    let unified = UnifiedOutputBuilder::items(coverage.modules, EntityType::Module)
+        .with_metadata(OutputMetadata {
+            query: None,
+            tool: None,
+            timing_ms: None,
+            truncated: None,
+            extra,
+        })
+        .build();
+
+    match output.unified(unified) {
+        Ok(code) if below_min.is_some() && code.is_success() => ExitCode::FindingsReported,
+        Ok(code) => code,
+        Err(e) => {
+            output_eprintln!("Error writing output: {e}");
+            ExitCode::GeneralError
+        }
+    }
+}
+
+/// Write analysis findings as GitHub Actions annotations
+fn write_annotations(
+    output: &mut OutputManager,
+    annotations: &[Annotation],
+    findings: usize,
+    fail_on_findings: bool,
+) -> ExitCode {
+    match output.annotations(annotations) {
+        Ok(()) => analysis_exit_code(ExitCode::Success, findings, fail_on_findings),
+        Err(e) => {
+            output_eprintln!("Error writing output: {e}");
+            ExitCode::GeneralError
+        }
+    }
+}
+
+/// `FindingsReported` in place of success when asked to fail on findings
+fn analysis_exit_code(code: ExitCode, findings: usize, fail_on_findings: bool) -> ExitCode {
+    if fail_on_findings && findings > 0 && code.is_success() {
+        ExitCode::FindingsReported
+    } else {
+        code
+    }
+}
+
+/// Execute retrieve history command
+///
+/// Reports the last commit touching the symbol's lines via git blame.
+pub fn retrieve_history(
+    indexer: &SimpleIndexer,
+    symbol_name: &str,
+    language: Option<&str>,
+    format: OutputFormat,
+) -> ExitCode {
+    use crate::indexing::HistoryError;
+
+    let mut output = OutputManager::new(format);
+
+    let symbols = if let Some(id_str) = symbol_name.strip_prefix("symbol_id:") {
+        match id_str.parse::<u32>() {
+            Ok(id) => indexer
+                .get_symbol(crate::SymbolId(id))
+                .into_iter()
+                .collect(),
+            Err(_) => {
+                output_eprintln!("Invalid symbol_id format: {id_str}");
+                return ExitCode::GeneralError;
+            }
+        }
+    } else {
+        indexer.find_symbols_by_name(symbol_name, language)
+    };
+
+    if symbols.len() > 1 {
+        output_eprintln!(
+            "Ambiguous: found {} symbol(s) named '{}':",
+            symbols.len(),
+            symbol_name
+        );
+        for (i, sym) in symbols.iter().take(10).enumerate() {
+            output_eprintln!(
+                "  {}. symbol_id:{} - {:?} at {}:{}",
+                i + 1,
+                sym.id.value(),
+                sym.kind,
+                display_path(&sym.file_path),
+                sym.range.start_line + 1
+            );
+        }
+        if symbols.len() > 10 {
+            output_eprintln!("  ... and {} more", symbols.len() - 10);
+        }
+        output_eprintln!("\nUse: codanna retrieve history symbol_id:<id>");
+        return ExitCode::GeneralError;
+    }
+
+    let history: Vec<_> = match symbols.first().map(|symbol| indexer.symbol_history(symbol)) {
+        Some(Ok(history)) => vec![history],
+        Some(Err(e @ (HistoryError::NotARepository { .. } | HistoryError::Untracked { .. }))) => {
+            output_eprintln!("{e}");
+            return ExitCode::NotFound;
+        }
+        Some(Err(e)) => {
+            output_eprintln!("Error: {e}");
+            return ExitCode::GeneralError;
+        }
+        None => Vec::new(),
+    };
+
+    let unified = UnifiedOutputBuilder::items(history, EntityType::Symbol)
+        .with_metadata(OutputMetadata {
+            query: Some(Cow::Borrowed(symbol_name)),
+            tool: None,
+            timing_ms: None,
+            truncated: None,
+            extra: Default::default(),
+        })
+        .build();
+
+    match output.unified(unified) {
+        Ok(code) => code,
+        Err(e) => {
+            output_eprintln!("Error writing output: {e}");
+            ExitCode::GeneralError
+        }
+    }
+}
+
+/// A node in the module hierarchy built from indexed symbol module paths
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleNode {
+    /// Last segment of the module path
+    pub name: String,
+    /// Full module path (joined with the language separator)
+    pub path: String,
+    /// Symbols defined directly in this module
+    pub symbol_count: usize,
+    /// Symbols defined in this module and all submodules
+    pub total_count: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<ModuleNode>,
+}
+
+impl ModuleNode {
+    fn write_tree(&self, f: &mut fmt::Formatter<'_>, prefix: &str, last: bool) -> fmt::Result {
+        let branch = if last { "└── " } else { "├── " };
+        write!(
+            f,
+            "\n{prefix}{branch}{} ({} symbols, {} total)",
+            self.name, self.symbol_count, self.total_count
+        )?;
+
+        let child_prefix = format!("{prefix}{}", if last { "    " } else { "│   " });
+        for (i, child) in self.children.iter().enumerate() {
+            child.write_tree(f, &child_prefix, i + 1 == self.children.len())?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ModuleNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({} symbols, {} total)",
+            self.path, self.symbol_count, self.total_count
+        )?;
+        for (i, child) in self.children.iter().enumerate() {
+            child.write_tree(f, "", i + 1 == self.children.len())?;
+        }
+        Ok(())
+    }
+}
+
+/// Resolves the module segments and separator a symbol belongs to.
+///
+/// Module paths are split on the language's separator. Languages whose
+/// `format_module_path` appends the symbol name (e.g. Rust) have that last
+/// segment stripped so the symbol lands in its containing module. Symbols
+/// without a module path are grouped by their file's directory instead.
+struct ModuleKeyResolver {
+    /// Per-language (separator, module path includes the symbol name)
+    conventions: HashMap<LanguageId, (&'static str, bool)>,
+}
+
+impl ModuleKeyResolver {
+    fn new() -> Self {
+        Self {
+            conventions: HashMap::new(),
+        }
+    }
+
+    fn convention(&mut self, language_id: Option<LanguageId>) -> (&'static str, bool) {
+        let Some(language_id) = language_id else {
+            return ("::", false);
+        };
+
+        *self.conventions.entry(language_id).or_insert_with(|| {
+            let registry = get_registry();
+            let registry = registry.lock().unwrap();
+            match registry.get(language_id) {
+                Some(definition) => {
+                    let behavior = definition.create_behavior();
+                    let includes_name = behavior.format_module_path("m", "s") != "m";
+                    (behavior.module_separator(), includes_name)
+                }
+                None => ("::", false),
+            }
+        })
+    }
+
+    fn resolve(&mut self, symbol: &Symbol) -> (Vec<String>, &'static str) {
+        if let Some(module_path) = symbol.as_module_path().filter(|p| !p.is_empty()) {
+            let (separator, includes_name) = self.convention(symbol.language_id);
+            let mut segments: Vec<String> = module_path
+                .split(separator)
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+                .collect();
+            if includes_name
+                && segments.len() > 1
+                && segments.last().map(String::as_str) == Some(symbol.name.as_str())
+            {
+                segments.pop();
+            }
+            if !segments.is_empty() {
+                return (segments, separator);
+            }
+        }
+
+        // No module system: group by file location
+        let segments = Path::new(symbol.file_path.as_ref())
+            .components()
+            .filter_map(|c| match c {
+                Component::Normal(s) => Some(s.to_string_lossy().into_owned()),
+                _ => None,
+            })
+            .collect();
+        (segments, "/")
+    }
+}
+
+#[derive(Default)]
+struct ModuleBuilder {
+    separator: &'static str,
+    symbol_count: usize,
+    children: BTreeMap<String, ModuleBuilder>,
+}
+
+impl ModuleBuilder {
+    fn into_node(self, name: String, path: String) -> ModuleNode {
+        let children: Vec<ModuleNode> = self
+            .children
+            .into_iter()
+            .map(|(child_name, child)| {
+                let child_path = if path.is_empty() {
+                    child_name.clone()
+                } else {
+                    format!("{path}{}{child_name}", child.separator)
+                };
+                child.into_node(child_name, child_path)
+            })
+            .collect();
+        let total_count = self.symbol_count + children.iter().map(|c| c.total_count).sum::<usize>();
+
+        ModuleNode {
+            name,
+            path,
+            symbol_count: self.symbol_count,
+            total_count,
+            children,
+        }
+    }
+}
+
+/// Build the module hierarchy for a set of symbols.
+///
+/// Returns the top-level modules sorted by name.
+pub fn build_module_tree(symbols: &[Symbol]) -> Vec<ModuleNode> {
+    let mut resolver = ModuleKeyResolver::new();
+    let mut root = ModuleBuilder::default();
+
+    for symbol in symbols {
+        let (segments, separator) = resolver.resolve(symbol);
+        let mut node = &mut root;
+        for segment in segments {
+            node = node.children.entry(segment).or_default();
+            node.separator = separator;
+        }
+        node.symbol_count += 1;
+    }
+
+    root.into_node(String::new(), String::new()).children
+}
+
+/// Returns true if the symbol is defined directly in `module`
+fn symbol_in_module(resolver: &mut ModuleKeyResolver, symbol: &Symbol, module: &str) -> bool {
+    let (segments, separator) = resolver.resolve(symbol);
+    let module = if separator == "/" {
+        module.trim_start_matches('/')
+    } else {
+        module
+    };
+    segments.join(separator) == module
+}
+
+/// Execute retrieve modules command
+pub fn retrieve_modules(
+    indexer: &SimpleIndexer,
+    language: Option<&str>,
+    format: OutputFormat,
+) -> ExitCode {
+    let mut output = OutputManager::new(format);
+
+    let symbols: Vec<Symbol> = indexer
+        .get_all_symbols()
+        .into_iter()
+        .filter(|s| language.is_none_or(|lang| s.language_id.is_some_and(|id| id.as_str() == lang)))
+        .collect();
+    let modules = build_module_tree(&symbols);
+
+    let unified = UnifiedOutputBuilder::items(modules, EntityType::Module)
+        .with_metadata(OutputMetadata {
+            query: None,
+            tool: None,
+            timing_ms: None,
+            truncated: None,
+            extra: Default::default(),
+        })
+        .build();
+
+    match output.unified(unified) {
+        Ok(code) => code,
+        Err(e) => {
+            output_eprintln!("Error writing output: {e}");
+            ExitCode::GeneralError
+        }
+    }
+}
+
+/// Execute retrieve module command
+pub fn retrieve_module(
+    indexer: &SimpleIndexer,
+    module: &str,
+    language: Option<&str>,
+    format: OutputFormat,
+) -> ExitCode {
+    use crate::symbol::context::ContextIncludes;
+
+    let mut output = OutputManager::new(format);
+    let mut resolver = ModuleKeyResolver::new();
+
+    let mut symbols: Vec<Symbol> = indexer
+        .get_all_symbols()
+        .into_iter()
+        .filter(|s| language.is_none_or(|lang| s.language_id.is_some_and(|id| id.as_str() == lang)))
+        .filter(|s| symbol_in_module(&mut resolver, s, module))
+        .collect();
+    symbols.sort_by(|a, b| {
+        a.file_path
+            .cmp(&b.file_path)
+            .then(a.range.start_line.cmp(&b.range.start_line))
+    });
+
+    let contexts: Vec<SymbolContext> = symbols
+        .into_iter()
+        .filter_map(|symbol| indexer.get_symbol_context(symbol.id, ContextIncludes::empty()))
+        .collect();
+
+    let unified = UnifiedOutputBuilder::items(contexts, EntityType::Module)
+        .with_metadata(OutputMetadata {
+            query: Some(Cow::Borrowed(module)),
+            tool: None,
+            timing_ms: None,
+            truncated: None,
+            extra: Default::default(),
+        })
+        .build();
+
+    match output.unified(unified) {
+        Ok(code) => code,
+        Err(e) => {
+            output_eprintln!("Error writing output: {e}");
             ExitCode::GeneralError
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FileId, Range, SymbolId, SymbolKind};
+
+    fn symbol(id: u32, name: &str, module: Option<&str>, file: &str) -> Symbol {
+        let mut symbol = Symbol::new(
+            SymbolId::new(id).unwrap(),
+            name,
+            SymbolKind::Function,
+            FileId::new(1).unwrap(),
+            Range::new(1, 0, 1, 10),
+        )
+        .with_file_path(file);
+        if let Some(module) = module {
+            symbol = symbol
+                .with_module_path(module)
+                .with_language_id(LanguageId::new("rust"));
+        }
+        symbol
+    }
+
+    #[test]
+    fn test_call_hierarchy_renders_ascii_tree() {
+        let node = |id, name: &str, symbols: Vec<CallTreeNode>, repeated| CallTreeNode {
+            symbol: symbol(id, name, None, "src/lib.rs"),
+            symbols,
+            truncated: 0,
+            repeated,
+        };
+        let hierarchy = CallHierarchy {
+            symbol: symbol(1, "process_file", None, "src/lib.rs"),
+            tree: CallTree {
+                depth: 3,
+                symbols: vec![
+                    node(
+                        2,
+                        "parse",
+                        vec![node(1, "process_file", vec![], true)],
+                        false,
+                    ),
+                    node(3, "write", vec![], false),
+                ],
+                truncated: 2,
+            },
+        };
+
+        assert_eq!(
+            hierarchy.to_string(),
+            "process_file (Function) at src/lib.rs:2 [symbol_id:1]\n\
+             ├── parse (Function) at src/lib.rs:2 [symbol_id:2]\n\
+             │   └── process_file (Function) at src/lib.rs:2 [symbol_id:1] (see above)\n\
+             ├── write (Function) at src/lib.rs:2 [symbol_id:3]\n\
+             └── ... and 2 more (truncated)"
+        );
+    }
+
+    #[test]
+    fn test_build_module_tree() {
+        let symbols = vec![
+            symbol(
+                1,
+                "parse",
+                Some("crate::parsing::parse"),
+                "src/parsing/mod.rs",
+            ),
+            symbol(
+                2,
+                "Parser",
+                Some("crate::parsing::rust::Parser"),
+                "src/parsing/rust.rs",
+            ),
+            symbol(3, "main", Some("crate::main"), "src/main.rs"),
+            symbol(4, "helper", None, "lib/util.c"),
+        ];
+
+        let tree = build_module_tree(&symbols);
+        let names: Vec<&str> = tree.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(names, vec!["crate", "lib"]);
+
+        let krate = &tree[0];
+        assert_eq!(krate.symbol_count, 1);
+        assert_eq!(krate.total_count, 3);
+
+        let parsing = &krate.children[0];
+        assert_eq!(parsing.path, "crate::parsing");
+        assert_eq!(parsing.symbol_count, 1);
+        assert_eq!(parsing.children[0].path, "crate::parsing::rust");
+
+        // Symbols without a module path fall back to file grouping
+        assert_eq!(tree[1].children[0].path, "lib/util.c");
+        assert_eq!(tree[1].total_count, 1);
+
+        let mut resolver = ModuleKeyResolver::new();
+        assert!(symbol_in_module(
+            &mut resolver,
+            &symbols[1],
+            "crate::parsing::rust"
+        ));
+        assert!(!symbol_in_module(
+            &mut resolver,
+            &symbols[1],
+            "crate::parsing"
+        ));
+        assert!(symbol_in_module(&mut resolver, &symbols[3], "lib/util.c"));
+    }
+
+    #[test]
+    fn test_read_batch_inputs_trims_and_dedupes() {
+        let stdin = "parse_file\n\n  symbol_id:42 \nparse_file\nmain\n";
+        let inputs = read_batch_inputs(stdin.as_bytes()).unwrap();
+        assert_eq!(inputs, vec!["parse_file", "symbol_id:42", "main"]);
+    }
+}
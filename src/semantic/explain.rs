@@ -0,0 +1,225 @@
+//! Match explanations for semantic search results
+//!
+//! Embedding similarity has no per-term breakdown, so an explanation is an
+//! approximation: the query terms that also appear in a result's name,
+//! documentation or signature. Explanations are computed after ranking and
+//! never change the order or scores of results.
+
+use serde::Serialize;
+
+use crate::Symbol;
+
+/// Words too common to say anything about why a result matched
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "do", "does", "for", "from", "how", "in",
+    "into", "is", "it", "its", "of", "on", "or", "that", "the", "this", "to", "what", "when",
+    "where", "which", "with",
+];
+
+/// Query terms shared with a search result, grouped by where they were found
+#[derive(Debug, Clone, Default, PartialEq, Serialize, schemars::JsonSchema)]
+pub struct MatchExplanation {
+    /// Query terms found in the symbol name
+    pub name: Vec<String>,
+    /// Query terms found in the doc comment
+    pub doc: Vec<String>,
+    /// Query terms found in the signature
+    pub signature: Vec<String>,
+    /// Fraction of distinct query terms found anywhere (0.0-1.0)
+    pub coverage: f32,
+}
+
+impl MatchExplanation {
+    /// True when no query term appears in the result
+    pub fn is_empty(&self) -> bool {
+        self.name.is_empty() && self.doc.is_empty() && self.signature.is_empty()
+    }
+
+    /// One-line, human-readable summary for text output
+    pub fn summary(&self) -> String {
+        if self.is_empty() {
+            return "no shared terms; the match is purely semantic".to_string();
+        }
+
+        let parts: Vec<String> = [
+            ("name", &self.name),
+            ("doc", &self.doc),
+            ("signature", &self.signature),
+        ]
+        .iter()
+        .filter(|(_, terms)| !terms.is_empty())
+        .map(|(source, terms)| {
+            let quoted: Vec<String> = terms.iter().map(|t| format!("'{t}'")).collect();
+            format!("{source} matches {}", quoted.join(", "))
+        })
+        .collect();
+
+        format!(
+            "{} ({:.0}% of query terms)",
+            parts.join("; "),
+            self.coverage * 100.0
+        )
+    }
+}
+
+/// Explain which parts of a symbol share terms with the query.
+///
+/// Terms are compared case-insensitively after splitting camelCase and
+/// snake_case identifiers and stripping common English suffixes, so
+/// "parsing" in a query matches `parse_config` in a signature.
+pub fn explain_match(query: &str, symbol: &Symbol) -> MatchExplanation {
+    let mut query_terms: Vec<String> = Vec::new();
+    for term in tokenize(query) {
+        if !query_terms.contains(&term) {
+            query_terms.push(term);
+        }
+    }
+
+    if query_terms.is_empty() {
+        return MatchExplanation::default();
+    }
+
+    let name_stems = stems(&symbol.name);
+    let doc_stems = symbol.doc_comment.as_deref().map(stems).unwrap_or_default();
+    let sig_stems = symbol.signature.as_deref().map(stems).unwrap_or_default();
+
+    let mut explanation = MatchExplanation::default();
+    let mut matched = 0usize;
+
+    for term in &query_terms {
+        let stem = stem(term);
+        let mut found = false;
+        if name_stems.contains(&stem) {
+            explanation.name.push(term.clone());
+            found = true;
+        }
+        if doc_stems.contains(&stem) {
+            explanation.doc.push(term.clone());
+            found = true;
+        }
+        if sig_stems.contains(&stem) {
+            explanation.signature.push(term.clone());
+            found = true;
+        }
+        if found {
+            matched += 1;
+        }
+    }
+
+    explanation.coverage = matched as f32 / query_terms.len() as f32;
+    explanation
+}
+
+/// Split text into lowercase terms, breaking identifiers at case changes and
+/// underscores, and dropping stopwords and single characters.
+fn tokenize(text: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+
+    for word in text.split(|c: char| !c.is_alphanumeric()) {
+        let mut current = String::new();
+        let mut prev_lower = false;
+        for c in word.chars() {
+            if c.is_uppercase() && prev_lower && !current.is_empty() {
+                terms.push(std::mem::take(&mut current));
+            }
+            prev_lower = c.is_lowercase() || c.is_ascii_digit();
+            current.extend(c.to_lowercase());
+        }
+        if !current.is_empty() {
+            terms.push(current);
+        }
+    }
+
+    terms.retain(|t| t.chars().count() > 1 && !STOPWORDS.contains(&t.as_str()));
+    terms
+}
+
+fn stems(text: &str) -> Vec<String> {
+    tokenize(text).iter().map(|t| stem(t)).collect()
+}
+
+/// Strip a common suffix (and a trailing 'e') so inflected forms compare
+/// equal: "parsing", "parsed" and "parse" all become "pars".
+fn stem(term: &str) -> String {
+    let mut base = term;
+    for suffix in ["ing", "ed", "es", "s"] {
+        if suffix == "s" && base.ends_with("ss") {
+            continue;
+        }
+        if let Some(stripped) = base.strip_suffix(suffix) {
+            if stripped.chars().count() >= 3 {
+                base = stripped;
+                break;
+            }
+        }
+    }
+    if let Some(stripped) = base.strip_suffix('e') {
+        if stripped.chars().count() >= 3 {
+            base = stripped;
+        }
+    }
+    base.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FileId, Range, SymbolId, SymbolKind};
+
+    fn symbol(name: &str, doc: Option<&str>, signature: Option<&str>) -> Symbol {
+        let mut symbol = Symbol::new(
+            SymbolId::new(1).unwrap(),
+            name,
+            SymbolKind::Function,
+            FileId::new(1).unwrap(),
+            Range::new(0, 0, 1, 0),
+        );
+        symbol.doc_comment = doc.map(Into::into);
+        symbol.signature = signature.map(Into::into);
+        symbol
+    }
+
+    #[test]
+    fn test_tokenize_splits_identifiers() {
+        assert_eq!(
+            tokenize("parseConfigFile and load_settings"),
+            vec!["parse", "config", "file", "load", "settings"]
+        );
+        assert_eq!(tokenize("HTTPServer"), vec!["httpserver"]);
+    }
+
+    #[test]
+    fn test_explain_match_groups_terms_by_source() {
+        let sym = symbol(
+            "parse_config",
+            Some("Reads the configuration file from disk"),
+            Some("fn parse_config(path: &Path) -> Result<Config>"),
+        );
+
+        let explanation = explain_match("parsing config files from disk", &sym);
+
+        assert_eq!(explanation.name, vec!["parsing", "config"]);
+        assert_eq!(explanation.doc, vec!["files", "disk"]);
+        assert_eq!(explanation.signature, vec!["parsing", "config"]);
+        assert!((explanation.coverage - 1.0).abs() < f32::EPSILON);
+        assert!(
+            explanation
+                .summary()
+                .starts_with("name matches 'parsing', 'config'")
+        );
+    }
+
+    #[test]
+    fn test_explain_match_without_overlap() {
+        let sym = symbol("retry", Some("Backoff between attempts"), None);
+
+        let explanation = explain_match("error handling", &sym);
+
+        assert!(explanation.is_empty());
+        assert_eq!(explanation.coverage, 0.0);
+        assert_eq!(
+            explanation.summary(),
+            "no shared terms; the match is purely semantic"
+        );
+    }
+}
@@ -3,14 +3,38 @@
 //! This module provides a simple API for semantic search on documentation,
 //! designed to integrate with the existing indexing system.
 
+mod explain;
 mod metadata;
 mod simple;
 mod storage;
 
+pub use explain::{MatchExplanation, explain_match};
 pub use metadata::SemanticMetadata;
-pub use simple::{SemanticSearchError, SimpleSemanticSearch};
+pub use simple::{SearchMode, SemanticSearchError, SimpleSemanticSearch};
 pub use storage::SemanticVectorStorage;
 
+use crate::Symbol;
+use crate::symbol::context::SymbolContext;
+use serde::Serialize;
+
+/// A `semantic_search_docs` hit in `codanna mcp ... --json` output
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+pub struct SemanticSearchResult {
+    pub symbol: Symbol,
+    pub score: f32,
+    /// Query terms found in the result, with `explain: true`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<MatchExplanation>,
+}
+
+/// A `semantic_search_with_context` hit in `codanna mcp ... --json` output
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+pub struct SemanticSearchWithContextResult {
+    pub symbol: Symbol,
+    pub score: f32,
+    pub context: SymbolContext,
+}
+
 // Re-export key types
 pub use fastembed::{EmbeddingModel, TextEmbedding};
 
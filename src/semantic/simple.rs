@@ -1,9 +1,14 @@
 //! Simple semantic search implementation for documentation comments
 
 use crate::SymbolId;
-use fastembed::{EmbeddingModel, InitOptions, TextEmbedding};
-use std::collections::HashMap;
-use std::path::Path;
+use crate::config::AnnConfig;
+use crate::vector::{IvfIndex, MappedSegment, VectorId};
+use fastembed::{
+    EmbeddingModel, InitOptions, InitOptionsUserDefined, TextEmbedding, TokenizerFiles,
+    UserDefinedEmbeddingModel,
+};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 
 /// Error type for semantic search operations
@@ -30,6 +35,194 @@ pub enum SemanticSearchError {
 
     #[error("Invalid ID: {id}\nSuggestion: {suggestion}")]
     InvalidId { id: u32, suggestion: String },
+
+    #[error(
+        "Model mismatch: semantic index was built with '{stored}', not '{configured}'\nSuggestion: {suggestion}"
+    )]
+    ModelMismatch {
+        stored: String,
+        configured: String,
+        suggestion: String,
+    },
+}
+
+/// Fail if the semantic store at `path` holds vectors from another model
+///
+/// Vectors of different models (or dimensions) are not comparable, so a store
+/// is only ever written by the model that created it. `codanna index --force`
+/// clears it for a new model.
+fn check_store_model(
+    path: &Path,
+    model_name: &str,
+    dimension: usize,
+) -> Result<(), SemanticSearchError> {
+    use crate::semantic::SemanticMetadata;
+
+    if !SemanticMetadata::exists(path) {
+        return Ok(());
+    }
+    let stored = SemanticMetadata::load(path)?;
+    let suggestion = format!(
+        "Rebuild the semantic index for '{model_name}' with: codanna index --force (or restore semantic_search.model = \"{}\")",
+        stored.model_name
+    );
+    if stored.dimension != dimension {
+        return Err(SemanticSearchError::DimensionMismatch {
+            expected: stored.dimension,
+            actual: dimension,
+            suggestion,
+        });
+    }
+    if stored.model_name != model_name {
+        return Err(SemanticSearchError::ModelMismatch {
+            stored: stored.model_name,
+            configured: model_name.to_string(),
+            suggestion,
+        });
+    }
+    Ok(())
+}
+
+/// File holding the approximate (IVF) index, next to the vector segments
+const ANN_FILE_NAME: &str = "ann.ivf";
+
+/// How a semantic query is answered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchMode {
+    /// The query is compared against every embedding
+    Exact,
+    /// The query is compared against the embeddings in its closest clusters
+    Approximate { probes: usize, clusters: usize },
+}
+
+impl std::fmt::Display for SearchMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SearchMode::Exact => write!(f, "exact"),
+            SearchMode::Approximate { probes, clusters } => {
+                write!(f, "approximate ({probes} of {clusters} clusters probed)")
+            }
+        }
+    }
+}
+
+/// Tokenizer files expected next to a local ONNX model
+const TOKENIZER_FILES: [&str; 4] = [
+    "tokenizer.json",
+    "config.json",
+    "special_tokens_map.json",
+    "tokenizer_config.json",
+];
+
+/// Files of an embedding model stored on disk
+#[derive(Debug, PartialEq)]
+struct LocalModelFiles {
+    onnx: PathBuf,
+    /// Directory holding the tokenizer files
+    dir: PathBuf,
+}
+
+/// Find the model and tokenizer files under `model_path`
+///
+/// `model_path` is either the `.onnx` file or a directory containing it, as
+/// `model_file` (the layout the model hub uses), `model.onnx` or
+/// `onnx/model.onnx`. The tokenizer files sit in the same directory.
+fn locate_model_files(
+    model_path: &Path,
+    model_file: &str,
+) -> Result<LocalModelFiles, SemanticSearchError> {
+    if !model_path.exists() {
+        return Err(SemanticSearchError::ModelInitError(format!(
+            "model_path '{}' does not exist",
+            model_path.display()
+        )));
+    }
+
+    let (onnx, dir) = if model_path.is_file() {
+        let dir = model_path.parent().unwrap_or(Path::new(".")).to_path_buf();
+        (Some(model_path.to_path_buf()), dir)
+    } else {
+        let onnx = [model_file, "model.onnx", "onnx/model.onnx"]
+            .iter()
+            .map(|file| model_path.join(file))
+            .find(|path| path.is_file());
+        (onnx, model_path.to_path_buf())
+    };
+
+    let mut missing: Vec<&str> = TOKENIZER_FILES
+        .iter()
+        .copied()
+        .filter(|file| !dir.join(file).is_file())
+        .collect();
+    if onnx.is_none() {
+        missing.insert(0, model_file);
+    }
+    match onnx {
+        Some(onnx) if missing.is_empty() => Ok(LocalModelFiles { onnx, dir }),
+        _ => Err(SemanticSearchError::ModelInitError(format!(
+            "model_path '{}' is missing {}; expected the ONNX model with {}",
+            model_path.display(),
+            missing.join(", "),
+            TOKENIZER_FILES.join(", ")
+        ))),
+    }
+}
+
+/// Options for loading `model` from the model cache
+///
+/// `threads` caps the ONNX Runtime intra-op threads; `None` uses every core.
+fn model_options(
+    model: EmbeddingModel,
+    threads: Option<usize>,
+    show_progress: bool,
+) -> InitOptions {
+    let options = InitOptions::new(model)
+        .with_cache_dir(crate::init::models_dir())
+        .with_show_download_progress(show_progress);
+    match threads {
+        Some(threads) => options.with_intra_threads(threads),
+        None => options,
+    }
+}
+
+/// Load `model` from a local copy instead of the model cache
+fn load_local_model(
+    model: &EmbeddingModel,
+    model_path: &Path,
+    threads: Option<usize>,
+) -> Result<TextEmbedding, SemanticSearchError> {
+    let info = TextEmbedding::get_model_info(model)
+        .map_err(|e| SemanticSearchError::ModelInitError(e.to_string()))?;
+    let files = locate_model_files(model_path, &info.model_file)?;
+
+    let read = |path: &Path| {
+        std::fs::read(path).map_err(|e| {
+            SemanticSearchError::ModelInitError(format!("Failed to read '{}': {e}", path.display()))
+        })
+    };
+    let tokenizer_files = TokenizerFiles {
+        tokenizer_file: read(&files.dir.join("tokenizer.json"))?,
+        config_file: read(&files.dir.join("config.json"))?,
+        special_tokens_map_file: read(&files.dir.join("special_tokens_map.json"))?,
+        tokenizer_config_file: read(&files.dir.join("tokenizer_config.json"))?,
+    };
+
+    // Pool the way the named model expects
+    let mut user_model = UserDefinedEmbeddingModel::new(read(&files.onnx)?, tokenizer_files);
+    if let Some(pooling) = TextEmbedding::get_default_pooling_method(model) {
+        user_model = user_model.with_pooling(pooling);
+    }
+
+    let options = match threads {
+        Some(threads) => InitOptionsUserDefined::default().with_intra_threads(threads),
+        None => InitOptionsUserDefined::default(),
+    };
+    TextEmbedding::try_new_from_user_defined(user_model, options).map_err(|e| {
+        SemanticSearchError::ModelInitError(format!(
+            "Failed to load model from '{}': {e}",
+            files.onnx.display()
+        ))
+    })
 }
 
 /// Advanced semantic search engine for documentation analysis
@@ -39,9 +232,15 @@ pub enum SemanticSearchError {
 /// enabling natural language queries for code discovery.
 /// Updated: Final test - embedding cleanup working correctly!
 pub struct SimpleSemanticSearch {
-    /// Embeddings indexed by symbol ID
+    /// Embeddings held in memory, indexed by symbol ID
     embeddings: HashMap<SymbolId, Vec<f32>>,
 
+    /// Saved embeddings read from a memory map; never overlaps `embeddings`
+    mapped: Option<MappedSegment>,
+
+    /// Serve saved embeddings from a memory map rather than memory
+    mmap: bool,
+
     /// Language mapping for each symbol (for language-filtered search)
     symbol_languages: HashMap<SymbolId, String>,
 
@@ -53,15 +252,39 @@ pub struct SimpleSemanticSearch {
 
     /// Metadata for tracking model info and timestamps
     metadata: Option<crate::semantic::SemanticMetadata>,
+
+    /// Approximate index over the embeddings, once the store is large enough
+    ann: Option<IvfIndex>,
+
+    /// Embeddings added or replaced since `ann` was built (always scored)
+    unindexed: HashSet<SymbolId>,
+
+    /// Additions, replacements and removals since `ann` was built
+    ann_changes: usize,
+
+    /// When to build and use `ann`
+    ann_config: AnnConfig,
+
+    /// Threads for embedding and the `ann` build, `None` for every core
+    threads: Option<usize>,
 }
 
 impl std::fmt::Debug for SimpleSemanticSearch {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("SimpleSemanticSearch")
-            .field("embeddings_count", &self.embeddings.len())
+            .field("embeddings_count", &self.embedding_count())
+            .field(
+                "mapped_count",
+                &self.mapped.as_ref().map(MappedSegment::len),
+            )
             .field("dimensions", &self.dimensions)
             .field("model", &"<TextEmbedding>")
             .field("metadata", &self.metadata)
+            .field(
+                "ann_clusters",
+                &self.ann.as_ref().map(IvfIndex::cluster_count),
+            )
+            .field("threads", &self.threads)
             .finish()
     }
 }
@@ -71,13 +294,14 @@ impl SimpleSemanticSearch {
     ///
     /// For multilingual support, use `from_model_name` with "MultilingualE5Small".
     pub fn new() -> Result<Self, SemanticSearchError> {
-        Self::with_model(EmbeddingModel::AllMiniLML6V2)
+        Self::with_model(EmbeddingModel::AllMiniLML6V2, None)
     }
 
     /// Create a semantic search instance from a model name string.
     ///
     /// # Arguments
     /// * `model_name` - Name of the model (e.g., "MultilingualE5Small", "AllMiniLML6V2")
+    /// * `threads` - Threads for embedding, `None` for every core
     ///
     /// # Supported Models
     /// - `AllMiniLML6V2` - English-only, 384 dimensions (default)
@@ -88,16 +312,43 @@ impl SimpleSemanticSearch {
     ///
     /// # Example
     /// ```ignore
-    /// let search = SimpleSemanticSearch::from_model_name("MultilingualE5Small")?;
+    /// let search = SimpleSemanticSearch::from_model_name("MultilingualE5Small", None)?;
     /// ```
-    pub fn from_model_name(model_name: &str) -> Result<Self, SemanticSearchError> {
+    pub fn from_model_name(
+        model_name: &str,
+        threads: Option<usize>,
+    ) -> Result<Self, SemanticSearchError> {
         let model = crate::vector::parse_embedding_model(model_name)
             .map_err(|e| SemanticSearchError::ModelInitError(format!("Invalid model name: {e}")))?;
-        Self::with_model(model)
+        Self::with_model(model, threads)
     }
 
-    /// Create with a specific model enum.
-    pub fn with_model(model: EmbeddingModel) -> Result<Self, SemanticSearchError> {
+    /// Create a semantic search instance from a local copy of a model.
+    ///
+    /// For machines that cannot reach the model hub. `model_name` still names
+    /// the model (it decides pooling and is recorded in the index metadata);
+    /// `model_path` holds its files, see `semantic_search.model_path`.
+    pub fn from_model_path(
+        model_name: &str,
+        model_path: &Path,
+        threads: Option<usize>,
+    ) -> Result<Self, SemanticSearchError> {
+        let model = crate::vector::parse_embedding_model(model_name)
+            .map_err(|e| SemanticSearchError::ModelInitError(format!("Invalid model name: {e}")))?;
+
+        crate::info_eprintln!(
+            "Loading embedding model '{model_name}' from {}...",
+            model_path.display()
+        );
+        let text_model = load_local_model(&model, model_path, threads)?;
+        Self::from_text_model(text_model, crate::vector::model_to_string(&model), threads)
+    }
+
+    /// Create with a specific model enum, embedding on `threads` threads.
+    pub fn with_model(
+        model: EmbeddingModel,
+        threads: Option<usize>,
+    ) -> Result<Self, SemanticSearchError> {
         let cache_dir = crate::init::models_dir();
         let model_name = crate::vector::model_to_string(&model);
 
@@ -109,22 +360,30 @@ impl SimpleSemanticSearch {
 
         // Inform user what's happening
         if has_cached_models {
-            eprintln!("Loading embedding model '{model_name}' from cache...");
+            crate::info_eprintln!("Loading embedding model '{model_name}' from cache...");
         } else {
-            eprintln!("Downloading embedding model '{model_name}' (first time only)...");
+            crate::info_eprintln!(
+                "Downloading embedding model '{model_name}' (first time only)..."
+            );
         }
 
-        let mut text_model = TextEmbedding::try_new(
-            InitOptions::new(model)
-                .with_cache_dir(cache_dir)
-                .with_show_download_progress(true), // Always show progress, but with context from message above
-        )
-        .map_err(|e| {
-            SemanticSearchError::ModelInitError(format!(
-                "Failed to initialize model '{model_name}': {e}"
-            ))
-        })?;
+        // Always show progress, but with context from message above
+        let text_model =
+            TextEmbedding::try_new(model_options(model, threads, true)).map_err(|e| {
+                SemanticSearchError::ModelInitError(format!(
+                    "Failed to initialize model '{model_name}': {e}"
+                ))
+            })?;
 
+        Self::from_text_model(text_model, model_name, threads)
+    }
+
+    /// Wrap an initialized model, probing it for its dimensions
+    fn from_text_model(
+        mut text_model: TextEmbedding,
+        model_name: String,
+        threads: Option<usize>,
+    ) -> Result<Self, SemanticSearchError> {
         // Get dimensions by generating a test embedding
         let test_embedding = text_model
             .embed(vec!["test"], None)
@@ -133,17 +392,22 @@ impl SimpleSemanticSearch {
 
         // Create initial metadata
         let metadata = crate::semantic::SemanticMetadata::new(
-            model_name.clone(),
-            dimensions,
-            0, // No embeddings yet
+            model_name, dimensions, 0, // No embeddings yet
         );
 
         Ok(Self {
             embeddings: HashMap::new(),
+            mapped: None,
+            mmap: true,
             symbol_languages: HashMap::new(),
             model: Mutex::new(text_model),
             dimensions,
             metadata: Some(metadata),
+            ann: None,
+            unindexed: HashSet::new(),
+            ann_changes: 0,
+            ann_config: AnnConfig::default(),
+            threads,
         })
     }
 
@@ -170,14 +434,23 @@ impl SimpleSemanticSearch {
 
         // Validate dimensions
         if embedding.len() != self.dimensions {
-            return Err(SemanticSearchError::EmbeddingError(format!(
-                "Embedding dimension mismatch: expected {}, got {}",
-                self.dimensions,
-                embedding.len()
-            )));
+            return Err(SemanticSearchError::DimensionMismatch {
+                expected: self.dimensions,
+                actual: embedding.len(),
+                suggestion: "Rebuild the semantic index with: codanna index --force".to_string(),
+            });
         }
 
         self.embeddings.insert(symbol_id, embedding);
+        if let (Some(mapped), Some(vector_id)) =
+            (self.mapped.as_mut(), VectorId::new(symbol_id.to_u32()))
+        {
+            mapped.remove(vector_id);
+        }
+        if self.ann.is_some() {
+            self.unindexed.insert(symbol_id);
+            self.ann_changes += 1;
+        }
         Ok(())
     }
 
@@ -209,9 +482,9 @@ impl SimpleSemanticSearch {
         limit: usize,
     ) -> Result<Vec<(SymbolId, f32)>, SemanticSearchError> {
         println!("SEARCH_DEBUG: search() called with query: '{query}', limit: {limit}");
-        println!("SEARCH_DEBUG: embeddings count: {}", self.embeddings.len());
+        println!("SEARCH_DEBUG: embeddings count: {}", self.embedding_count());
 
-        if self.embeddings.is_empty() {
+        if self.embedding_count() == 0 {
             println!("SEARCH_DEBUG: No embeddings found, returning NoEmbeddings error");
             return Err(SemanticSearchError::NoEmbeddings);
         }
@@ -225,22 +498,7 @@ impl SimpleSemanticSearch {
             .map_err(|e| SemanticSearchError::EmbeddingError(e.to_string()))?;
         let query_embedding = query_embeddings.into_iter().next().unwrap();
 
-        // Calculate similarities
-        let mut similarities: Vec<(SymbolId, f32)> = self
-            .embeddings
-            .iter()
-            .map(|(id, embedding)| {
-                let similarity = cosine_similarity(&query_embedding, embedding);
-                (*id, similarity)
-            })
-            .collect();
-
-        // Sort by similarity descending
-        similarities.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
-
-        // Return top results
-        similarities.truncate(limit);
-        Ok(similarities)
+        Ok(self.rank(&query_embedding, limit, None))
     }
 
     /// Search for similar documentation with language filtering
@@ -253,7 +511,7 @@ impl SimpleSemanticSearch {
         limit: usize,
         language: Option<&str>,
     ) -> Result<Vec<(SymbolId, f32)>, SemanticSearchError> {
-        if self.embeddings.is_empty() {
+        if self.embedding_count() == 0 {
             return Err(SemanticSearchError::NoEmbeddings);
         }
 
@@ -266,35 +524,191 @@ impl SimpleSemanticSearch {
             .map_err(|e| SemanticSearchError::EmbeddingError(e.to_string()))?;
         let query_embedding = query_embeddings.into_iter().next().unwrap();
 
-        // Filter embeddings by language BEFORE computing similarity
-        let filtered_embeddings: Vec<(&SymbolId, &Vec<f32>)> = if let Some(lang) = language {
-            self.embeddings
-                .iter()
-                .filter(|(id, _)| {
-                    self.symbol_languages
-                        .get(id)
-                        .is_some_and(|symbol_lang| symbol_lang == lang)
-                })
-                .collect()
-        } else {
-            self.embeddings.iter().collect()
-        };
+        Ok(self.rank(&query_embedding, limit, language))
+    }
 
-        // Calculate similarities only for filtered embeddings
-        let mut similarities: Vec<(SymbolId, f32)> = filtered_embeddings
-            .into_iter()
-            .map(|(id, embedding)| {
-                let similarity = cosine_similarity(&query_embedding, embedding);
-                (*id, similarity)
+    /// Score embeddings against a query embedding, best first
+    ///
+    /// Exact search scores every embedding in `language`; approximate search
+    /// scores the closest clusters plus anything embedded since the
+    /// approximate index was built.
+    fn rank(
+        &self,
+        query_embedding: &[f32],
+        limit: usize,
+        language: Option<&str>,
+    ) -> Vec<(SymbolId, f32)> {
+        let in_language = |id: &SymbolId| {
+            language.is_none_or(|lang| {
+                self.symbol_languages
+                    .get(id)
+                    .is_some_and(|symbol_lang| symbol_lang == lang)
             })
-            .collect();
+        };
+
+        let mut similarities: Vec<(SymbolId, f32)> = match (self.search_mode(language), &self.ann) {
+            (SearchMode::Approximate { probes, .. }, Some(ann)) => {
+                let mut candidates: HashSet<SymbolId> = ann
+                    .candidates(query_embedding, probes)
+                    .into_iter()
+                    .filter_map(|id| SymbolId::new(id.get()))
+                    .collect();
+                candidates.extend(self.unindexed.iter().copied());
+                candidates
+                    .into_iter()
+                    .filter(|id| in_language(id))
+                    .filter_map(|id| Some((id, self.similarity(id, query_embedding)?)))
+                    .collect()
+            }
+            _ => self
+                .stored_ids()
+                .filter(|id| in_language(id))
+                .filter_map(|id| Some((id, self.similarity(id, query_embedding)?)))
+                .collect(),
+        };
 
         // Sort by similarity descending
         similarities.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
 
         // Return top results
         similarities.truncate(limit);
-        Ok(similarities)
+        similarities
+    }
+
+    /// Similarity of a stored embedding to the query, wherever it is held
+    fn similarity(&self, id: SymbolId, query_embedding: &[f32]) -> Option<f32> {
+        if let Some(embedding) = self.embeddings.get(&id) {
+            return Some(cosine_similarity(query_embedding, embedding));
+        }
+        self.mapped
+            .as_ref()?
+            .cosine_similarity(VectorId::new(id.to_u32())?, query_embedding)
+    }
+
+    /// IDs of all stored embeddings, in memory and mapped
+    fn stored_ids(&self) -> impl Iterator<Item = SymbolId> + '_ {
+        self.embeddings.keys().copied().chain(
+            self.mapped
+                .iter()
+                .flat_map(MappedSegment::ids)
+                .filter_map(|vector_id| SymbolId::new(vector_id.get())),
+        )
+    }
+
+    /// Copies of all stored embeddings, in memory and mapped
+    fn all_embeddings(&self) -> Vec<(SymbolId, Vec<f32>)> {
+        let mut all: Vec<(SymbolId, Vec<f32>)> = self
+            .embeddings
+            .iter()
+            .map(|(id, embedding)| (*id, embedding.clone()))
+            .collect();
+        if let Some(mapped) = &self.mapped {
+            all.extend(mapped.ids().filter_map(|vector_id| {
+                Some((SymbolId::new(vector_id.get())?, mapped.read(vector_id)?))
+            }));
+        }
+        all
+    }
+
+    /// How a query over `language` (or all languages) would be answered
+    ///
+    /// Approximate search needs a built index and at least
+    /// `ann.min_vectors` embeddings to search; smaller sets are scored exactly.
+    pub fn search_mode(&self, language: Option<&str>) -> SearchMode {
+        let Some(ann) = self.ann.as_ref().filter(|_| self.ann_config.enabled) else {
+            return SearchMode::Exact;
+        };
+
+        let searched = match language {
+            Some(lang) => self
+                .symbol_languages
+                .values()
+                .filter(|symbol_lang| *symbol_lang == lang)
+                .count(),
+            None => self.embedding_count(),
+        };
+        if searched < self.ann_config.min_vectors {
+            return SearchMode::Exact;
+        }
+
+        SearchMode::Approximate {
+            probes: self.ann_config.probes.clamp(1, ann.cluster_count()),
+            clusters: ann.cluster_count(),
+        }
+    }
+
+    /// Set when to build and use the approximate index
+    pub fn set_ann_config(&mut self, config: AnnConfig) {
+        self.ann_config = config;
+    }
+
+    /// Set whether saved embeddings are read from a memory map after `save`
+    pub fn set_mmap(&mut self, enabled: bool) {
+        self.mmap = enabled;
+    }
+
+    /// Threads used for embedding and the approximate index, `None` for every core
+    pub fn threads(&self) -> Option<usize> {
+        self.threads
+    }
+
+    /// Build, rebuild or drop the approximate index to match the store
+    ///
+    /// The index is rebuilt once more than `ann.rebuild_ratio` of the
+    /// embeddings changed since the last build, and dropped when the store
+    /// falls below `ann.min_vectors`. Returns true when an index was built.
+    pub fn refresh_ann_index(&mut self) -> Result<bool, SemanticSearchError> {
+        let config = &self.ann_config;
+        let count = self.embedding_count();
+        if !config.enabled || count < config.min_vectors {
+            self.ann = None;
+            self.unindexed.clear();
+            self.ann_changes = 0;
+            return Ok(false);
+        }
+
+        if let Some(ann) = &self.ann {
+            if self.ann_changes as f32 <= config.rebuild_ratio * ann.vector_count() as f32 {
+                return Ok(false);
+            }
+        }
+
+        let clusters = match config.clusters {
+            0 => (count as f64).sqrt().round() as usize,
+            clusters => clusters,
+        };
+        // Mapped embeddings are copied for the build only
+        let embeddings = self.all_embeddings();
+        let vectors: Vec<(VectorId, &[f32])> = embeddings
+            .iter()
+            .filter_map(|(id, embedding)| {
+                VectorId::new(id.to_u32()).map(|vector_id| (vector_id, embedding.as_slice()))
+            })
+            .collect();
+
+        // The build assigns vectors in parallel; keep it within the thread budget
+        let build = || IvfIndex::build(&vectors, clusters);
+        let built = match self.threads {
+            Some(threads) => rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .map_err(|e| SemanticSearchError::StorageError {
+                    message: format!("Failed to start {threads} index build threads: {e}"),
+                    suggestion: "Lower indexing.parallel_threads".to_string(),
+                })?
+                .install(build),
+            None => build(),
+        };
+        let ann = built.map_err(|e| SemanticSearchError::StorageError {
+            message: format!("Failed to build approximate index: {e}"),
+            suggestion: "Set semantic_search.ann.enabled = false to always search exactly"
+                .to_string(),
+        })?;
+
+        self.ann = Some(ann);
+        self.unindexed.clear();
+        self.ann_changes = 0;
+        Ok(true)
     }
 
     /// Search with a similarity threshold
@@ -313,13 +727,17 @@ impl SimpleSemanticSearch {
 
     /// Get the number of indexed embeddings
     pub fn embedding_count(&self) -> usize {
-        self.embeddings.len()
+        self.embeddings.len() + self.mapped.as_ref().map_or(0, MappedSegment::len)
     }
 
     /// Clear all embeddings
     pub fn clear(&mut self) {
         self.embeddings.clear();
+        self.mapped = None;
         self.symbol_languages.clear();
+        self.ann = None;
+        self.unindexed.clear();
+        self.ann_changes = 0;
     }
 
     /// Remove embeddings for specific symbols
@@ -328,8 +746,16 @@ impl SimpleSemanticSearch {
     /// that no longer exist.
     pub fn remove_embeddings(&mut self, symbol_ids: &[SymbolId]) {
         for id in symbol_ids {
-            self.embeddings.remove(id);
+            let in_memory = self.embeddings.remove(id).is_some();
+            let in_mapped = match (self.mapped.as_mut(), VectorId::new(id.to_u32())) {
+                (Some(mapped), Some(vector_id)) => mapped.remove(vector_id),
+                _ => false,
+            };
+            if (in_memory || in_mapped) && self.ann.is_some() {
+                self.ann_changes += 1;
+            }
             self.symbol_languages.remove(id);
+            self.unindexed.remove(id);
         }
     }
 
@@ -340,9 +766,41 @@ impl SimpleSemanticSearch {
 
     /// Save embeddings to disk using the efficient vector storage
     ///
+    /// With memory mapping on, the saved embeddings are afterwards read from
+    /// the new segment file and dropped from memory.
+    ///
     /// # Arguments
     /// * `path` - Path where semantic data should be stored
-    pub fn save(&self, path: &Path) -> Result<(), SemanticSearchError> {
+    pub fn save(&mut self, path: &Path) -> Result<(), SemanticSearchError> {
+        use crate::semantic::SemanticVectorStorage;
+
+        let embeddings = self.all_embeddings();
+
+        // Release the old mapping before its file is replaced (Windows refuses
+        // to delete a mapped file)
+        self.mapped = None;
+        let result = self.write_store(path, &embeddings);
+
+        if result.is_ok() && self.mmap {
+            match SemanticVectorStorage::open_mapped(path) {
+                Ok(mapped) => {
+                    self.embeddings.clear();
+                    self.mapped = Some(mapped);
+                    return result;
+                }
+                Err(e) => eprintln!("Warning: Keeping semantic embeddings in memory: {e}"),
+            }
+        }
+        self.embeddings = embeddings.into_iter().collect();
+        result
+    }
+
+    /// Write metadata, vectors, language mappings and the approximate index
+    fn write_store(
+        &self,
+        path: &Path,
+        embeddings: &[(SymbolId, Vec<f32>)],
+    ) -> Result<(), SemanticSearchError> {
         use crate::semantic::{SemanticMetadata, SemanticVectorStorage};
         use crate::vector::VectorDimension;
 
@@ -360,7 +818,10 @@ impl SimpleSemanticSearch {
             "AllMiniLML6V2".to_string()
         };
 
-        let metadata = SemanticMetadata::new(model_name, self.dimensions, self.embeddings.len());
+        // Refuse to overwrite another model's vectors with ours
+        check_store_model(path, &model_name, self.dimensions)?;
+
+        let metadata = SemanticMetadata::new(model_name, self.dimensions, embeddings.len());
         metadata.save(path)?;
 
         // Create storage with our dimension
@@ -373,15 +834,8 @@ impl SimpleSemanticSearch {
 
         let mut storage = SemanticVectorStorage::new(path, dimension)?;
 
-        // Convert HashMap to Vec for batch save
-        let embeddings: Vec<(SymbolId, Vec<f32>)> = self
-            .embeddings
-            .iter()
-            .map(|(id, embedding)| (*id, embedding.clone()))
-            .collect();
-
         // Save all embeddings
-        storage.save_batch(&embeddings)?;
+        storage.save_batch(embeddings)?;
 
         // Save language mappings as a JSON file (convert SymbolId to u32 for serialization)
         let languages_path = path.join("languages.json");
@@ -403,6 +857,21 @@ impl SimpleSemanticSearch {
             }
         })?;
 
+        // Keep the approximate index in step with the vectors; a stale one is removed
+        let ann_path = path.join(ANN_FILE_NAME);
+        if let Some(ref ann) = self.ann {
+            ann.save(&ann_path)
+                .map_err(|e| SemanticSearchError::StorageError {
+                    message: format!("Failed to write approximate index: {e}"),
+                    suggestion: "Check disk space and file permissions".to_string(),
+                })?;
+        } else if ann_path.exists() {
+            std::fs::remove_file(&ann_path).map_err(|e| SemanticSearchError::StorageError {
+                message: format!("Failed to remove stale approximate index: {e}"),
+                suggestion: "Check file permissions".to_string(),
+            })?;
+        }
+
         Ok(())
     }
 
@@ -413,6 +882,20 @@ impl SimpleSemanticSearch {
     /// # Arguments
     /// * `path` - Path where semantic data is stored
     pub fn load(path: &Path) -> Result<Self, SemanticSearchError> {
+        Self::load_with_options(path, None, true, None)
+    }
+
+    /// Load embeddings from disk, reading the model from `model_path` if given.
+    ///
+    /// With `mmap`, embeddings are read from a memory map during search
+    /// instead of being copied into memory; if mapping fails they are loaded.
+    /// `threads` caps embedding threads as in [`Self::with_model`].
+    pub fn load_with_options(
+        path: &Path,
+        model_path: Option<&Path>,
+        mmap: bool,
+        threads: Option<usize>,
+    ) -> Result<Self, SemanticSearchError> {
         use crate::semantic::{SemanticMetadata, SemanticVectorStorage};
 
         // Load metadata first
@@ -443,36 +926,52 @@ impl SimpleSemanticSearch {
             });
         }
 
-        // Load all embeddings
-        let embeddings_vec = storage.load_all()?;
+        // Map the embeddings, or load them all when mapping is off or fails
+        let mapped = if mmap {
+            match SemanticVectorStorage::open_mapped(path) {
+                Ok(mapped) => Some(mapped),
+                Err(e) => {
+                    eprintln!("Warning: Loading semantic embeddings into memory: {e}");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        let embeddings: HashMap<SymbolId, Vec<f32>> = match mapped {
+            Some(_) => HashMap::new(),
+            None => storage.load_all()?.into_iter().collect(),
+        };
+        let stored_ids: HashSet<SymbolId> = embeddings
+            .keys()
+            .copied()
+            .chain(
+                mapped
+                    .iter()
+                    .flat_map(MappedSegment::ids)
+                    .filter_map(|vector_id| SymbolId::new(vector_id.get())),
+            )
+            .collect();
 
         // Verify count matches metadata
-        if embeddings_vec.len() != metadata.embedding_count {
+        if stored_ids.len() != metadata.embedding_count {
             eprintln!(
                 "WARNING: Expected {} embeddings but found {}",
                 metadata.embedding_count,
-                embeddings_vec.len()
+                stored_ids.len()
             );
         }
 
-        // Convert to HashMap
-        let mut embeddings = HashMap::with_capacity(embeddings_vec.len());
-        for (id, embedding) in embeddings_vec {
-            embeddings.insert(id, embedding);
-        }
-
         // Create new instance with model from metadata
-        let text_model = TextEmbedding::try_new(
-            InitOptions::new(model)
-                .with_cache_dir(crate::init::models_dir())
-                .with_show_download_progress(false),
-        )
-        .map_err(|e| {
-            SemanticSearchError::ModelInitError(format!(
-                "Failed to load model '{}': {}",
-                metadata.model_name, e
-            ))
-        })?;
+        let text_model = match model_path {
+            Some(model_path) => load_local_model(&model, model_path, threads)?,
+            None => TextEmbedding::try_new(model_options(model, threads, false)).map_err(|e| {
+                SemanticSearchError::ModelInitError(format!(
+                    "Failed to load model '{}': {}",
+                    metadata.model_name, e
+                ))
+            })?,
+        };
 
         // Load language mappings if they exist
         let languages_path = path.join("languages.json");
@@ -497,12 +996,52 @@ impl SimpleSemanticSearch {
             HashMap::new()
         };
 
+        // An unreadable approximate index only costs speed: fall back to exact search
+        let ann_path = path.join(ANN_FILE_NAME);
+        let ann = if ann_path.exists() {
+            match IvfIndex::load(&ann_path) {
+                Ok(ann) => Some(ann),
+                Err(e) => {
+                    eprintln!(
+                        "Warning: Ignoring approximate index {}: {e}",
+                        ann_path.display()
+                    );
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        // Whatever the index does not cover is scored exactly until the next rebuild
+        let mut unindexed = stored_ids;
+        let mut ann_changes = 0;
+        if let Some(ref ann) = ann {
+            for vector_id in ann.vector_ids() {
+                let covered =
+                    SymbolId::new(vector_id.get()).is_some_and(|id| unindexed.remove(&id));
+                if !covered {
+                    ann_changes += 1;
+                }
+            }
+            ann_changes += unindexed.len();
+        } else {
+            unindexed.clear();
+        }
+
         Ok(Self {
             embeddings,
+            mapped,
+            mmap,
             symbol_languages,
             model: Mutex::new(text_model),
             dimensions: metadata.dimension,
             metadata: Some(metadata),
+            ann,
+            unindexed,
+            ann_changes,
+            ann_config: AnnConfig::default(),
+            threads,
         })
     }
 }
@@ -524,6 +1063,100 @@ fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_model_options_cap_intra_threads() {
+        let capped = model_options(EmbeddingModel::AllMiniLML6V2, Some(2), false);
+        assert_eq!(capped.intra_threads, Some(2));
+
+        let uncapped = model_options(EmbeddingModel::AllMiniLML6V2, None, false);
+        assert_eq!(uncapped.intra_threads, None);
+    }
+
+    #[test]
+    fn test_check_store_model() {
+        use crate::semantic::SemanticMetadata;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+
+        // No store yet: anything may write
+        assert!(check_store_model(path, "MultilingualE5Base", 768).is_ok());
+
+        SemanticMetadata::new("AllMiniLML6V2".to_string(), 384, 10)
+            .save(path)
+            .unwrap();
+        assert!(check_store_model(path, "AllMiniLML6V2", 384).is_ok());
+
+        match check_store_model(path, "MultilingualE5Base", 768) {
+            Err(SemanticSearchError::DimensionMismatch {
+                expected,
+                actual,
+                suggestion,
+            }) => {
+                assert_eq!((expected, actual), (384, 768));
+                assert!(suggestion.contains("codanna index --force"), "{suggestion}");
+            }
+            other => panic!("expected DimensionMismatch, got {other:?}"),
+        }
+
+        match check_store_model(path, "MultilingualE5Small", 384) {
+            Err(SemanticSearchError::ModelMismatch {
+                stored, configured, ..
+            }) => {
+                assert_eq!(stored, "AllMiniLML6V2");
+                assert_eq!(configured, "MultilingualE5Small");
+            }
+            other => panic!("expected ModelMismatch, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_locate_model_files() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        for file in TOKENIZER_FILES {
+            std::fs::write(dir.join(file), "{}").unwrap();
+        }
+
+        // Tokenizer files alone are not enough
+        let err = locate_model_files(dir, "model.onnx")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("missing model.onnx"), "{err}");
+
+        std::fs::create_dir(dir.join("onnx")).unwrap();
+        std::fs::write(dir.join("onnx/model.onnx"), "onnx").unwrap();
+        let expected = LocalModelFiles {
+            onnx: dir.join("onnx/model.onnx"),
+            dir: dir.to_path_buf(),
+        };
+        assert_eq!(locate_model_files(dir, "model.onnx").unwrap(), expected);
+        // Pointing at the .onnx file finds the tokenizer files beside it only
+        assert!(locate_model_files(&dir.join("onnx/model.onnx"), "model.onnx").is_err());
+
+        std::fs::write(dir.join("model_quantized.onnx"), "onnx").unwrap();
+        assert_eq!(
+            locate_model_files(&dir.join("model_quantized.onnx"), "model.onnx")
+                .unwrap()
+                .onnx,
+            dir.join("model_quantized.onnx")
+        );
+
+        std::fs::remove_file(dir.join("tokenizer.json")).unwrap();
+        let err = locate_model_files(dir, "model.onnx")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("missing tokenizer.json"), "{err}");
+
+        let err = locate_model_files(&dir.join("absent"), "model.onnx")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("does not exist"), "{err}");
+    }
+
     #[test]
     #[ignore = "Downloads 86MB model - run with --ignored for semantic tests"]
     fn test_remove_embeddings() {
@@ -618,6 +1251,92 @@ mod tests {
 
         // The first result should be our JSON parsing function
         assert_eq!(results[0].0, SymbolId::new(1).unwrap());
+
+        // Saved embeddings are served from the memory map, or loaded when it is off
+        assert!(search.embeddings.is_empty());
+        assert_eq!(search.embedding_count(), original_count);
+        assert!(loaded.mapped.is_some());
+        let in_memory =
+            SimpleSemanticSearch::load_with_options(temp_dir.path(), None, false, None).unwrap();
+        assert!(in_memory.mapped.is_none());
+        assert_eq!(in_memory.embeddings.len(), original_count);
+        assert_eq!(in_memory.search("parse JSON", 10).unwrap(), results);
+    }
+
+    #[test]
+    fn test_approximate_search() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+
+        // Skip test if model is not available
+        let mut search = match SimpleSemanticSearch::new() {
+            Ok(s) => s,
+            Err(_) => {
+                eprintln!("Skipping test: FastEmbed model not available");
+                return;
+            }
+        };
+
+        let docs = [
+            "This function parses JSON data",
+            "Authenticates a user with credentials",
+            "Writes log messages to a file",
+            "Serialize data structure to JSON",
+        ];
+        for (i, doc) in docs.iter().enumerate() {
+            search
+                .index_doc_comment(SymbolId::new(i as u32 + 1).unwrap(), doc)
+                .unwrap();
+        }
+
+        // Below min_vectors: no index, exact search
+        assert!(!search.refresh_ann_index().unwrap());
+        assert_eq!(search.search_mode(None), SearchMode::Exact);
+        let exact = search.search("parse JSON", 4).unwrap();
+
+        // Probing every cluster must give exactly the exact results
+        search.set_ann_config(AnnConfig {
+            min_vectors: 4,
+            clusters: 2,
+            probes: 2,
+            ..AnnConfig::default()
+        });
+        assert!(search.refresh_ann_index().unwrap());
+        assert_eq!(
+            search.search_mode(None),
+            SearchMode::Approximate {
+                probes: 2,
+                clusters: 2
+            }
+        );
+        assert_eq!(search.search("parse JSON", 4).unwrap(), exact);
+
+        // Embeddings added after the build are still found
+        search
+            .index_doc_comment(SymbolId::new(5).unwrap(), "Parses JSON from a string")
+            .unwrap();
+        let results = search.search("parse JSON", 5).unwrap();
+        assert!(
+            results
+                .iter()
+                .any(|(id, _)| *id == SymbolId::new(5).unwrap())
+        );
+
+        search.save(temp_dir.path()).unwrap();
+        assert!(temp_dir.path().join(ANN_FILE_NAME).exists());
+        let loaded = SimpleSemanticSearch::load(temp_dir.path()).unwrap();
+        assert_eq!(loaded.unindexed.len(), 1);
+        assert_eq!(loaded.ann_changes, 1);
+
+        // Falling below min_vectors drops the index file on the next save
+        search.set_ann_config(AnnConfig {
+            min_vectors: 100,
+            ..AnnConfig::default()
+        });
+        assert!(!search.refresh_ann_index().unwrap());
+        search.save(temp_dir.path()).unwrap();
+        assert!(!temp_dir.path().join(ANN_FILE_NAME).exists());
     }
 
     #[test]
@@ -3,7 +3,7 @@
 //! This module provides efficient persistence for semantic embeddings by leveraging
 //! the existing MmapVectorStorage infrastructure, achieving <1μs access times.
 
-use crate::vector::{MmapVectorStorage, SegmentOrdinal, VectorDimension, VectorId};
+use crate::vector::{MappedSegment, MmapVectorStorage, SegmentOrdinal, VectorDimension, VectorId};
 use crate::{SymbolId, semantic::SemanticSearchError};
 use std::path::Path;
 
@@ -63,6 +63,20 @@ impl SemanticVectorStorage {
         Ok(Self { storage, dimension })
     }
 
+    /// Maps existing embeddings for on-demand reads instead of loading them.
+    ///
+    /// # Arguments
+    /// * `path` - Base path where storage files exist
+    pub fn open_mapped(path: &Path) -> Result<MappedSegment, SemanticSearchError> {
+        MappedSegment::open(path, SegmentOrdinal::new(0)).map_err(|e| {
+            SemanticSearchError::StorageError {
+                message: format!("Failed to map embeddings: {e}"),
+                suggestion: "Set semantic_search.mmap = false to load embeddings into memory"
+                    .to_string(),
+            }
+        })
+    }
+
     /// Opens existing storage or creates new if doesn't exist.
     pub fn open_or_create(
         path: &Path,
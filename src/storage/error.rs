@@ -37,6 +37,15 @@ pub enum StorageError {
     #[error("Lock poisoned")]
     LockPoisoned,
 
+    #[error(
+        "Index is locked by another codanna process{}. Wait for it to finish and retry",
+        .pid.map(|pid| format!(" (pid {pid})")).unwrap_or_default()
+    )]
+    Locked { pid: Option<u32> },
+
+    #[error("Index was opened read-only; {operation} needs a writable index")]
+    ReadOnly { operation: String },
+
     #[error("Directory error: {0}")]
     Directory(#[from] OpenDirectoryError),
 
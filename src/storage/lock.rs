@@ -0,0 +1,102 @@
+//! Exclusive lock on an index directory
+//!
+//! Two `codanna index` runs against the same index would interleave writes to
+//! Tantivy and the metadata files. Mutating operations hold an OS file lock on
+//! `<index>/.lock` for their duration; the holder's pid is written into the file
+//! so a second process can report who owns it (Windows locks block reading it,
+//! so the pid is unknown there). Read-only commands take no lock.
+//! The OS releases the lock when the holder exits, so a crashed run never leaves
+//! a stale lock behind.
+
+use super::{StorageError, StorageResult};
+use fs4::fs_std::FileExt;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Lock file name inside the index directory
+pub const LOCK_FILE_NAME: &str = ".lock";
+
+/// Held exclusive lock on an index directory, released on drop
+#[derive(Debug)]
+pub struct IndexLock {
+    file: File,
+    path: PathBuf,
+}
+
+impl IndexLock {
+    /// Take the exclusive lock for `index_dir` without waiting
+    ///
+    /// Fails with `StorageError::Locked` when another process holds it.
+    pub fn acquire(index_dir: &Path) -> StorageResult<Self> {
+        std::fs::create_dir_all(index_dir)?;
+        let path = index_dir.join(LOCK_FILE_NAME);
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)?;
+
+        if !FileExt::try_lock_exclusive(&file)? {
+            return Err(StorageError::Locked {
+                pid: read_pid(&mut file),
+            });
+        }
+
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        write!(file, "{}", std::process::id())?;
+        file.flush()?;
+
+        Ok(Self { file, path })
+    }
+
+    /// Path of the lock file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for IndexLock {
+    fn drop(&mut self) {
+        // Clear the pid first so a reader racing the unlock never sees a stale owner
+        let _ = self.file.set_len(0);
+        // Through the trait: std's inherent `File::unlock` needs Rust 1.89
+        let _ = FileExt::unlock(&self.file);
+    }
+}
+
+/// Pid recorded by the current holder, if it has written one yet
+fn read_pid(file: &mut File) -> Option<u32> {
+    let mut content = String::new();
+    file.seek(SeekFrom::Start(0)).ok()?;
+    file.read_to_string(&mut content).ok()?;
+    content.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_second_lock_is_rejected_until_release() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let lock = IndexLock::acquire(temp_dir.path()).unwrap();
+        assert!(lock.path().ends_with(LOCK_FILE_NAME));
+
+        match IndexLock::acquire(temp_dir.path()) {
+            Err(StorageError::Locked { pid }) => {
+                if cfg!(unix) {
+                    assert_eq!(pid, Some(std::process::id()));
+                }
+            }
+            other => panic!("expected Locked, got {other:?}"),
+        }
+
+        drop(lock);
+        IndexLock::acquire(temp_dir.path()).expect("lock is free after drop");
+    }
+}
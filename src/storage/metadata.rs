@@ -5,6 +5,20 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+/// Current on-disk index format, bumped whenever the Tantivy schema changes
+///
+/// Indexes written with another version have to be rebuilt with
+/// `codanna index --force`.
+///
+/// - 1: written before the version was recorded
+/// - 2: symbol content hashes, parent symbol ids, relationship provenance
+///   and symbol attributes
+/// - 3: `relation_provenance` moved after the attribute fields, so indexes
+///   written with version 2 read provenance from the wrong field
+/// - 4: derived trait names
+/// - 5: symbol visibility indexed for filtering
+pub const INDEX_FORMAT_VERSION: u32 = 5;
+
 /// Metadata about the index state
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IndexMetadata {
@@ -27,6 +41,15 @@ pub struct IndexMetadata {
     /// Used to detect config changes and auto-sync on load
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub indexed_paths: Option<Vec<PathBuf>>,
+
+    /// False when the index was built with relationship resolution disabled
+    /// Older metadata without the field had relationships resolved
+    #[serde(default = "default_relationships_indexed")]
+    pub relationships_indexed: bool,
+}
+
+fn default_relationships_indexed() -> bool {
+    true
 }
 
 /// Describes where the index data came from
@@ -46,12 +69,13 @@ pub enum DataSource {
 impl Default for IndexMetadata {
     fn default() -> Self {
         Self {
-            version: 1,
+            version: INDEX_FORMAT_VERSION,
             data_source: DataSource::Fresh,
             symbol_count: 0,
             file_count: 0,
             last_modified: crate::indexing::get_utc_timestamp(),
             indexed_paths: None,
+            relationships_indexed: true,
         }
     }
 }
@@ -107,6 +131,18 @@ impl IndexMetadata {
             .map_err(|e| crate::IndexError::General(format!("Failed to parse metadata: {e}")))
     }
 
+    /// Fail with `IncompatibleIndex` if the index was written in another format
+    pub fn check_format(&self) -> IndexResult<()> {
+        if self.version == INDEX_FORMAT_VERSION {
+            Ok(())
+        } else {
+            Err(crate::IndexError::IncompatibleIndex {
+                found: self.version,
+                expected: INDEX_FORMAT_VERSION,
+            })
+        }
+    }
+
     /// Display source information to the user
     pub fn display_source(&self) {
         match &self.data_source {
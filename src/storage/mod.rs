@@ -1,12 +1,19 @@
 pub mod error;
+pub mod lock;
 pub mod memory;
 pub mod metadata;
 pub mod metadata_keys;
+pub mod occurrences;
 pub mod persistence;
 pub mod symbol_cache;
 pub mod tantivy;
 pub use error::{StorageError, StorageResult};
-pub use metadata::{DataSource, IndexMetadata};
+pub use lock::IndexLock;
+pub use metadata::{DataSource, INDEX_FORMAT_VERSION, IndexMetadata};
 pub use metadata_keys::MetadataKey;
-pub use persistence::IndexPersistence;
-pub use tantivy::{DocumentIndex, SearchResult};
+pub use occurrences::{Occurrence, OccurrenceIndex};
+pub use persistence::{IndexDiskUsage, IndexPersistence};
+pub use tantivy::{
+    DocumentIndex, SearchFilters, SearchGroup, SearchGroupBy, SearchResult, SearchSource,
+    group_results, retain_min_score,
+};
@@ -0,0 +1,313 @@
+//! Identifier occurrence index for grep-like lookups
+//!
+//! Records the position of every identifier token in each indexed file,
+//! including ones the relationship graph never sees (strings used for
+//! reflection, dynamic dispatch, macros). Names are interned in a
+//! [`StringTable`] and occurrences are grouped by the interned offset, so each
+//! position costs 10 bytes on disk regardless of the name length.
+//!
+//! Enabled with `indexing.index_occurrences`; stored as `occurrences.bin`.
+
+use crate::symbol::StringTable;
+use crate::types::FileId;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+/// Magic bytes to identify occurrence index files
+const MAGIC_BYTES: &[u8; 4] = b"OCCR";
+
+/// Version of the occurrence file format
+const VERSION: u32 = 1;
+
+/// Position of an identifier token in a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Occurrence {
+    pub file_id: FileId,
+    /// Zero-based line number
+    pub line: u32,
+    /// Zero-based byte column within the line (saturates at `u16::MAX`)
+    pub column: u16,
+}
+
+/// In-memory occurrence index keyed by [`StringTable`] offsets
+#[derive(Default)]
+pub struct OccurrenceIndex {
+    names: StringTable,
+    by_name: HashMap<u32, Vec<Occurrence>>,
+    /// Set when occurrences changed since the last save
+    dirty: bool,
+}
+
+impl OccurrenceIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record every identifier token of `content` for `file_id`.
+    ///
+    /// Callers remove the file's previous occurrences first when re-indexing.
+    pub fn add_file(&mut self, file_id: FileId, content: &str) {
+        for (name, line, column) in identifier_tokens(content) {
+            let offset = self.names.intern(name);
+            self.by_name.entry(offset).or_default().push(Occurrence {
+                file_id,
+                line,
+                column,
+            });
+        }
+        self.dirty = true;
+    }
+
+    /// Drop all occurrences recorded for `file_id`.
+    pub fn remove_file(&mut self, file_id: FileId) {
+        let before = self.len();
+        self.by_name.retain(|_, occurrences| {
+            occurrences.retain(|occ| occ.file_id != file_id);
+            !occurrences.is_empty()
+        });
+        if self.len() != before {
+            self.dirty = true;
+        }
+    }
+
+    /// All occurrences of the exact identifier `name`, ordered by file and position.
+    pub fn find(&self, name: &str) -> Vec<Occurrence> {
+        let mut occurrences = self
+            .names
+            .lookup(name)
+            .and_then(|offset| self.by_name.get(&offset))
+            .cloned()
+            .unwrap_or_default();
+        occurrences.sort_unstable_by_key(|occ| (occ.file_id.value(), occ.line, occ.column));
+        occurrences
+    }
+
+    /// Total number of recorded occurrences
+    pub fn len(&self) -> usize {
+        self.by_name.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_name.is_empty()
+    }
+
+    /// Whether there are changes not yet written by [`OccurrenceIndex::save`]
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Write the index to `path`.
+    ///
+    /// Names whose occurrences were all removed are dropped from the string
+    /// table, so the file does not grow across re-indexing.
+    pub fn save(&mut self, path: &Path) -> io::Result<()> {
+        let mut live: Vec<(u32, &str)> = self
+            .by_name
+            .keys()
+            .filter_map(|&offset| self.names.get(offset).map(|name| (offset, name)))
+            .collect();
+        live.sort_unstable_by_key(|(_, name)| *name);
+
+        // (old offset, new offset) pairs in the compacted table
+        let mut names = StringTable::new();
+        let remap: Vec<(u32, u32)> = live
+            .into_iter()
+            .map(|(offset, name)| (offset, names.intern(name)))
+            .collect();
+
+        let tmp_path = path.with_extension("bin.tmp");
+        {
+            let mut writer = BufWriter::new(File::create(&tmp_path)?);
+            writer.write_all(MAGIC_BYTES)?;
+            writer.write_all(&VERSION.to_le_bytes())?;
+
+            let strings = names.as_bytes();
+            writer.write_all(&(strings.len() as u32).to_le_bytes())?;
+            writer.write_all(strings)?;
+
+            writer.write_all(&(remap.len() as u32).to_le_bytes())?;
+            for (old, new) in &remap {
+                let occurrences = &self.by_name[old];
+                writer.write_all(&new.to_le_bytes())?;
+                writer.write_all(&(occurrences.len() as u32).to_le_bytes())?;
+                for occ in occurrences {
+                    writer.write_all(&occ.file_id.value().to_le_bytes())?;
+                    writer.write_all(&occ.line.to_le_bytes())?;
+                    writer.write_all(&occ.column.to_le_bytes())?;
+                }
+            }
+            writer.flush()?;
+        }
+        std::fs::rename(&tmp_path, path)?;
+
+        let mut by_name = HashMap::with_capacity(remap.len());
+        for (old, new) in remap {
+            if let Some(occurrences) = self.by_name.remove(&old) {
+                by_name.insert(new, occurrences);
+            }
+        }
+        self.by_name = by_name;
+        self.names = names;
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Read an index written by [`OccurrenceIndex::save`].
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC_BYTES {
+            return Err(invalid_data("not an occurrence index file"));
+        }
+        let version = read_u32(&mut reader)?;
+        if version != VERSION {
+            return Err(invalid_data(&format!(
+                "unsupported occurrence index version {version}"
+            )));
+        }
+
+        let mut strings = vec![0u8; read_u32(&mut reader)? as usize];
+        reader.read_exact(&mut strings)?;
+        let names = StringTable::from_bytes(strings)
+            .ok_or_else(|| invalid_data("corrupt occurrence string table"))?;
+
+        let name_count = read_u32(&mut reader)?;
+        let mut by_name = HashMap::with_capacity(name_count as usize);
+        for _ in 0..name_count {
+            let offset = read_u32(&mut reader)?;
+            let count = read_u32(&mut reader)?;
+            let mut occurrences = Vec::with_capacity(count as usize);
+            for _ in 0..count {
+                let file_id = FileId::new(read_u32(&mut reader)?)
+                    .ok_or_else(|| invalid_data("invalid file id in occurrence index"))?;
+                let line = read_u32(&mut reader)?;
+                let mut column = [0u8; 2];
+                reader.read_exact(&mut column)?;
+                occurrences.push(Occurrence {
+                    file_id,
+                    line,
+                    column: u16::from_le_bytes(column),
+                });
+            }
+            by_name.insert(offset, occurrences);
+        }
+
+        Ok(Self {
+            names,
+            by_name,
+            dirty: false,
+        })
+    }
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+/// Identifier tokens of `content` with their zero-based line and byte column.
+///
+/// A token is a run of alphanumerics and underscores that does not start
+/// with a digit, so numeric literals such as `0x1f` are skipped.
+fn identifier_tokens(content: &str) -> impl Iterator<Item = (&str, u32, u16)> {
+    content.lines().enumerate().flat_map(|(line_no, line)| {
+        let mut tokens = Vec::new();
+        let mut start = None;
+        for (idx, ch) in line
+            .char_indices()
+            .chain(std::iter::once((line.len(), ' ')))
+        {
+            let is_ident = ch.is_alphanumeric() || ch == '_';
+            match (start, is_ident) {
+                (None, true) => start = Some(idx),
+                (Some(begin), false) => {
+                    let token = &line[begin..idx];
+                    if !token.starts_with(|c: char| c.is_numeric()) {
+                        let column = u16::try_from(begin).unwrap_or(u16::MAX);
+                        tokens.push((token, line_no as u32, column));
+                    }
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        tokens
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn file(id: u32) -> FileId {
+        FileId::new(id).unwrap()
+    }
+
+    #[test]
+    fn test_identifier_tokens() {
+        let tokens: Vec<_> =
+            identifier_tokens("let x_1 = 0x1f;\n  getattr(obj, \"handler\")").collect();
+        assert_eq!(
+            tokens,
+            vec![
+                ("let", 0, 0),
+                ("x_1", 0, 4),
+                ("getattr", 1, 2),
+                ("obj", 1, 10),
+                ("handler", 1, 16),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_add_find_and_remove() {
+        let mut index = OccurrenceIndex::new();
+        index.add_file(file(2), "fn run() {}\nrun();\n");
+        index.add_file(file(1), "use a::run;\n");
+
+        let found = index.find("run");
+        assert_eq!(found.len(), 3);
+        assert_eq!(found[0].file_id, file(1));
+        assert_eq!((found[1].line, found[1].column), (0, 3));
+        assert_eq!((found[2].line, found[2].column), (1, 0));
+        assert!(index.find("missing").is_empty());
+
+        index.remove_file(file(2));
+        assert_eq!(index.find("run").len(), 1);
+        assert!(index.find("fn").is_empty());
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("occurrences.bin");
+
+        let mut index = OccurrenceIndex::new();
+        index.add_file(file(1), "fn stale() {}\n");
+        index.add_file(file(2), "fn keep() { keep() }\n");
+        index.remove_file(file(1));
+        assert!(index.is_dirty());
+
+        index.save(&path).unwrap();
+        assert!(!index.is_dirty());
+        assert_eq!(index.find("keep").len(), 2);
+
+        let loaded = OccurrenceIndex::load(&path).unwrap();
+        assert_eq!(loaded.find("keep"), index.find("keep"));
+        assert!(loaded.find("stale").is_empty());
+        assert_eq!(loaded.len(), 3);
+
+        std::fs::write(&path, b"nope").unwrap();
+        assert!(OccurrenceIndex::load(&path).is_err());
+    }
+}
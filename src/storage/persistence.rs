@@ -3,21 +3,130 @@
 //! This module manages metadata and ensures Tantivy index exists.
 //! All actual data is stored in Tantivy.
 
-use crate::storage::{DataSource, IndexMetadata};
+use crate::storage::{
+    DataSource, INDEX_FORMAT_VERSION, IndexLock, IndexMetadata, StorageError, StorageResult,
+};
 use crate::{IndexError, IndexResult, Settings, SimpleIndexer};
-use std::path::PathBuf;
-use std::sync::Arc;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, Weak};
+
+/// Entries under the index directory that belong to the index itself
+const INDEX_ARTIFACTS: &[&str] = &[
+    "tantivy",
+    "semantic",
+    "index.meta",
+    "symbol_cache.bin",
+    "occurrences.bin",
+];
+
+/// Total size in bytes of a file or directory tree
+fn disk_usage(path: &Path) -> u64 {
+    let Ok(metadata) = std::fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    std::fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| disk_usage(&entry.path()))
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+/// On-disk size of each part of an index, in bytes
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct IndexDiskUsage {
+    /// Tantivy directory: symbols, relationships and the full-text index
+    pub tantivy_bytes: u64,
+    /// Symbol cache used for fast lookups
+    pub symbol_cache_bytes: u64,
+    /// Whole semantic directory
+    pub semantic_bytes: u64,
+    /// Embedding vectors within the semantic directory
+    pub semantic_vectors_bytes: u64,
+    /// Identifier occurrence index (`indexing.index_occurrences`)
+    pub occurrences_bytes: u64,
+    /// Index metadata file
+    pub metadata_bytes: u64,
+    /// Sum of all index artifacts
+    pub total_bytes: u64,
+}
+
+impl IndexDiskUsage {
+    /// Measure the index artifacts under `base_path`.
+    pub fn measure(base_path: &Path) -> Self {
+        let tantivy = disk_usage(&base_path.join("tantivy"));
+        let symbol_cache = disk_usage(&base_path.join("symbol_cache.bin"));
+        let semantic = disk_usage(&base_path.join("semantic"));
+        let semantic_vectors = std::fs::read_dir(base_path.join("semantic"))
+            .map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .map(|entry| entry.path())
+                    .filter(|path| path.extension().is_some_and(|ext| ext == "vec"))
+                    .map(|path| disk_usage(&path))
+                    .sum()
+            })
+            .unwrap_or(0);
+        let occurrences = disk_usage(&base_path.join("occurrences.bin"));
+        let metadata = disk_usage(&base_path.join("index.meta"));
+
+        Self {
+            tantivy_bytes: tantivy,
+            symbol_cache_bytes: symbol_cache,
+            semantic_bytes: semantic,
+            semantic_vectors_bytes: semantic_vectors,
+            occurrences_bytes: occurrences,
+            metadata_bytes: metadata,
+            total_bytes: tantivy + symbol_cache + semantic + occurrences + metadata,
+        }
+    }
+
+    /// Average bytes on disk per indexed symbol, `None` for an empty index.
+    pub fn bytes_per_symbol(&self, symbol_count: usize) -> Option<u64> {
+        (symbol_count > 0).then(|| self.total_bytes / symbol_count as u64)
+    }
+}
 
 /// Manages persistence of the index
 #[derive(Debug)]
 pub struct IndexPersistence {
     base_path: PathBuf,
+    /// Exclusive index lock while any guard from `lock_exclusive` is alive
+    write_lock: Mutex<Weak<IndexLock>>,
 }
 
 impl IndexPersistence {
     /// Create a new persistence manager
     pub fn new(base_path: PathBuf) -> Self {
-        Self { base_path }
+        Self {
+            base_path,
+            write_lock: Mutex::new(Weak::new()),
+        }
+    }
+
+    /// Take the exclusive index lock, released when the last returned guard drops
+    ///
+    /// `save`, `clear` and `remove_all` hold it for their own duration. Commands
+    /// that write through the indexer before saving keep a guard for the whole
+    /// run; the operations inside share it instead of locking again. Fails with
+    /// `StorageError::Locked` when another process holds it.
+    pub fn lock_exclusive(&self) -> StorageResult<Arc<IndexLock>> {
+        let mut held = self
+            .write_lock
+            .lock()
+            .map_err(|_| StorageError::LockPoisoned)?;
+        if let Some(lock) = held.upgrade() {
+            return Ok(lock);
+        }
+        let lock = Arc::new(IndexLock::acquire(&self.base_path)?);
+        *held = Arc::downgrade(&lock);
+        Ok(lock)
     }
 
     /// Get path for semantic search data
@@ -28,6 +137,13 @@ impl IndexPersistence {
     /// Save metadata for the index
     #[must_use = "Save errors should be handled to ensure data is persisted"]
     pub fn save(&self, indexer: &SimpleIndexer) -> IndexResult<()> {
+        let _lock = self
+            .lock_exclusive()
+            .map_err(|e| IndexError::PersistenceError {
+                path: self.base_path.clone(),
+                source: Box::new(e),
+            })?;
+
         // Update metadata
         let mut metadata =
             IndexMetadata::load(&self.base_path).unwrap_or_else(|_| IndexMetadata::new());
@@ -46,6 +162,8 @@ impl IndexPersistence {
             }
         }
         metadata.update_indexed_paths(indexed_paths);
+        metadata.relationships_indexed = indexer.relationships_indexed();
+        metadata.version = INDEX_FORMAT_VERSION;
 
         // Update metadata to reflect Tantivy
         metadata.data_source = DataSource::Tantivy {
@@ -58,7 +176,7 @@ impl IndexPersistence {
 
         // Update project registry with latest metadata
         if let Err(err) = self.update_project_registry(&metadata) {
-            eprintln!(
+            crate::info_eprintln!(
                 "Note: Skipped project registry update ({err}). The index itself was saved successfully; registry metadata will refresh once permissions allow writing to ~/.codanna."
             );
         }
@@ -101,6 +219,35 @@ impl IndexPersistence {
         settings: Arc<Settings>,
         info: bool,
         skip_trait_resolver: bool,
+    ) -> IndexResult<SimpleIndexer> {
+        // Note: skip_trait_resolver no longer needed - behaviors handle resolution now
+        self.load_from_tantivy(settings, info, |settings| {
+            Ok(if skip_trait_resolver {
+                SimpleIndexer::with_settings_lazy(settings)
+            } else {
+                SimpleIndexer::with_settings(settings)
+            })
+        })
+    }
+
+    /// Load the indexer for queries only, without any Tantivy write setup
+    ///
+    /// The returned indexer rejects indexing and removal. Callers that may need to
+    /// sync the index first should use `load_with_settings_lazy` instead.
+    #[must_use = "Load errors should be handled appropriately"]
+    pub fn load_read_only(
+        &self,
+        settings: Arc<Settings>,
+        info: bool,
+    ) -> IndexResult<SimpleIndexer> {
+        self.load_from_tantivy(settings, info, SimpleIndexer::with_settings_read_only)
+    }
+
+    fn load_from_tantivy(
+        &self,
+        settings: Arc<Settings>,
+        info: bool,
+        open: impl FnOnce(Arc<Settings>) -> IndexResult<SimpleIndexer>,
     ) -> IndexResult<SimpleIndexer> {
         // Load metadata to understand data sources
         let metadata = IndexMetadata::load(&self.base_path).ok();
@@ -108,16 +255,16 @@ impl IndexPersistence {
         // Check if Tantivy index exists
         let tantivy_path = self.base_path.join("tantivy");
         if tantivy_path.join("meta.json").exists() {
+            // Refuse indexes whose Tantivy schema differs from the current one
+            if let Some(ref meta) = metadata {
+                meta.check_format()?;
+            }
+
             // Extract debug flag before moving settings
             let debug = settings.debug;
 
             // Create indexer that will load from Tantivy
-            // Note: skip_trait_resolver no longer needed - behaviors handle resolution now
-            let mut indexer = if skip_trait_resolver {
-                SimpleIndexer::with_settings_lazy(settings)
-            } else {
-                SimpleIndexer::with_settings(settings)
-            };
+            let mut indexer = open(settings)?;
 
             // Display source info with fresh counts
             if let Some(ref meta) = metadata {
@@ -175,6 +322,7 @@ impl IndexPersistence {
 
             // Restore indexed_paths from metadata to the indexer
             if let Some(ref meta) = metadata {
+                indexer.set_relationships_indexed(meta.relationships_indexed);
                 if let Some(ref stored_paths) = meta.indexed_paths {
                     for path in stored_paths {
                         if let Err(e) = indexer.add_indexed_path(path) {
@@ -215,9 +363,22 @@ impl IndexPersistence {
         tantivy_path.join("meta.json").exists()
     }
 
-    /// Delete the persisted index
+    /// Delete the persisted index and its semantic store
+    ///
+    /// The semantic store goes too: a forced re-index may use another
+    /// embedding model, whose vectors the old store would refuse.
     pub fn clear(&self) -> Result<(), std::io::Error> {
         let tantivy_path = self.base_path.join("tantivy");
+        let semantic_path = self.semantic_path();
+        if !tantivy_path.exists() && !semantic_path.exists() {
+            return Ok(());
+        }
+        let _lock = self.lock_exclusive().map_err(std::io::Error::other)?;
+
+        if semantic_path.exists() {
+            std::fs::remove_dir_all(&semantic_path)?;
+        }
+
         if tantivy_path.exists() {
             // On Windows, we may need multiple attempts due to file locking
             let mut attempts = 0;
@@ -269,6 +430,47 @@ impl IndexPersistence {
         Ok(())
     }
 
+    /// Measure how much disk space the index uses
+    pub fn disk_usage(&self) -> IndexDiskUsage {
+        IndexDiskUsage::measure(&self.base_path)
+    }
+
+    /// Index artifacts currently present under the base path.
+    ///
+    /// Only files and directories written by the indexer are listed, so
+    /// `settings.toml` and other profiles' indexes are never included.
+    pub fn artifacts(&self) -> Vec<PathBuf> {
+        INDEX_ARTIFACTS
+            .iter()
+            .map(|name| self.base_path.join(name))
+            .filter(|path| path.exists())
+            .collect()
+    }
+
+    /// Remove every index artifact: Tantivy, semantic vectors, metadata, the
+    /// symbol cache and the occurrence index.
+    ///
+    /// Returns each removed path with the number of bytes it occupied.
+    pub fn remove_all(&self) -> Result<Vec<(PathBuf, u64)>, std::io::Error> {
+        let _lock = if self.base_path.exists() {
+            Some(self.lock_exclusive().map_err(std::io::Error::other)?)
+        } else {
+            None
+        };
+
+        let mut removed = Vec::new();
+        for path in self.artifacts() {
+            let size = disk_usage(&path);
+            if path.is_dir() {
+                std::fs::remove_dir_all(&path)?;
+            } else {
+                std::fs::remove_file(&path)?;
+            }
+            removed.push((path, size));
+        }
+        Ok(removed)
+    }
+
     /// Update the project registry with latest metadata
     fn update_project_registry(&self, metadata: &IndexMetadata) -> IndexResult<()> {
         // Try to read the project ID file
@@ -350,6 +552,103 @@ mod tests {
         assert!(metadata_path.exists());
     }
 
+    #[test]
+    fn test_save_fails_while_another_manager_holds_lock() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings = Settings {
+            index_path: temp_dir.path().to_path_buf(),
+            ..Settings::default()
+        };
+        std::fs::create_dir_all(temp_dir.path().join("tantivy")).unwrap();
+        let indexer = SimpleIndexer::with_settings(Arc::new(settings));
+
+        let holder = IndexPersistence::new(temp_dir.path().to_path_buf());
+        let lock = holder.lock_exclusive().unwrap();
+        // Re-entrant for the manager that already holds it
+        holder.save(&indexer).unwrap();
+
+        let other = IndexPersistence::new(temp_dir.path().to_path_buf());
+        assert!(matches!(
+            other.lock_exclusive(),
+            Err(StorageError::Locked { .. })
+        ));
+        let err = other.save(&indexer).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("locked by another codanna process")
+        );
+
+        drop(lock);
+        other.save(&indexer).unwrap();
+        // Released again once the save returns
+        holder.save(&indexer).unwrap();
+    }
+
+    #[test]
+    fn test_load_refuses_other_index_format() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings = Arc::new(Settings {
+            index_path: temp_dir.path().to_path_buf(),
+            ..Settings::default()
+        });
+        std::fs::create_dir_all(temp_dir.path().join("tantivy")).unwrap();
+        let persistence = IndexPersistence::new(temp_dir.path().to_path_buf());
+        persistence
+            .save(&SimpleIndexer::with_settings(settings.clone()))
+            .unwrap();
+
+        let mut metadata = IndexMetadata::load(temp_dir.path()).unwrap();
+        assert_eq!(metadata.version, INDEX_FORMAT_VERSION);
+        metadata.version = 1;
+        metadata.save(temp_dir.path()).unwrap();
+
+        assert!(matches!(
+            persistence.load_with_settings(settings, false),
+            Err(IndexError::IncompatibleIndex {
+                found: 1,
+                expected: INDEX_FORMAT_VERSION
+            })
+        ));
+    }
+
+    #[test]
+    fn test_force_reindex_with_other_model_clears_semantic_store() {
+        use crate::semantic::{SemanticMetadata, SimpleSemanticSearch};
+
+        let temp_dir = TempDir::new().unwrap();
+        let persistence = IndexPersistence::new(temp_dir.path().to_path_buf());
+
+        // A store left behind by a model with other dimensions
+        let semantic_path = persistence.semantic_path();
+        std::fs::create_dir_all(&semantic_path).unwrap();
+        SemanticMetadata::new("MultilingualE5Large".to_string(), 1024, 0)
+            .save(&semantic_path)
+            .unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("tantivy")).unwrap();
+
+        persistence.clear().unwrap();
+        assert!(!has_semantic_data(&persistence));
+        assert!(temp_dir.path().join("tantivy").exists());
+
+        // The re-index writes the configured model's vectors without a mismatch
+        let mut search = match SimpleSemanticSearch::new() {
+            Ok(search) => search,
+            Err(_) => {
+                eprintln!("Skipping rest of test: FastEmbed model not available");
+                return;
+            }
+        };
+        search
+            .index_doc_comment(
+                crate::SymbolId::new(1).unwrap(),
+                "Parse JSON data from file",
+            )
+            .unwrap();
+        search.save(&semantic_path).unwrap();
+        let stored = SemanticMetadata::load(&semantic_path).unwrap();
+        assert_eq!(stored.model_name, "AllMiniLML6V2");
+    }
+
     #[test]
     fn test_exists() {
         let temp_dir = TempDir::new().unwrap();
@@ -367,6 +666,69 @@ mod tests {
         assert!(persistence.exists());
     }
 
+    #[test]
+    fn test_remove_all_keeps_settings() {
+        let temp_dir = TempDir::new().unwrap();
+        let index_path = temp_dir.path().join("index");
+        let persistence = IndexPersistence::new(index_path.clone());
+
+        std::fs::create_dir_all(index_path.join("tantivy")).unwrap();
+        std::fs::write(index_path.join("tantivy").join("meta.json"), "{}").unwrap();
+        std::fs::create_dir_all(index_path.join("semantic")).unwrap();
+        std::fs::write(index_path.join("semantic").join("segment_0.vec"), [0u8; 16]).unwrap();
+        std::fs::write(index_path.join("index.meta"), "{}").unwrap();
+        std::fs::create_dir_all(index_path.join("profiles").join("tests")).unwrap();
+        std::fs::write(temp_dir.path().join("settings.toml"), "version = 1").unwrap();
+
+        assert_eq!(persistence.artifacts().len(), 3);
+
+        let removed = persistence.remove_all().unwrap();
+        assert_eq!(removed.len(), 3);
+        assert_eq!(removed.iter().map(|(_, size)| size).sum::<u64>(), 20);
+
+        assert!(!persistence.exists());
+        assert!(persistence.artifacts().is_empty());
+        assert!(index_path.join("profiles").join("tests").exists());
+        assert!(temp_dir.path().join("settings.toml").exists());
+    }
+
+    #[test]
+    fn test_disk_usage() {
+        let temp_dir = TempDir::new().unwrap();
+        let persistence = IndexPersistence::new(temp_dir.path().to_path_buf());
+        assert_eq!(persistence.disk_usage(), IndexDiskUsage::default());
+
+        std::fs::create_dir_all(temp_dir.path().join("tantivy")).unwrap();
+        std::fs::write(
+            temp_dir.path().join("tantivy").join("meta.json"),
+            [0u8; 100],
+        )
+        .unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("semantic")).unwrap();
+        std::fs::write(
+            temp_dir.path().join("semantic").join("segment_0.vec"),
+            [0u8; 40],
+        )
+        .unwrap();
+        std::fs::write(
+            temp_dir.path().join("semantic").join("metadata.json"),
+            [0u8; 10],
+        )
+        .unwrap();
+        std::fs::write(temp_dir.path().join("index.meta"), [0u8; 10]).unwrap();
+        std::fs::write(temp_dir.path().join("occurrences.bin"), [0u8; 20]).unwrap();
+
+        let usage = persistence.disk_usage();
+        assert_eq!(usage.tantivy_bytes, 100);
+        assert_eq!(usage.semantic_bytes, 50);
+        assert_eq!(usage.semantic_vectors_bytes, 40);
+        assert_eq!(usage.occurrences_bytes, 20);
+        assert_eq!(usage.metadata_bytes, 10);
+        assert_eq!(usage.total_bytes, 180);
+        assert_eq!(usage.bytes_per_symbol(4), Some(45));
+        assert_eq!(usage.bytes_per_symbol(0), None);
+    }
+
     #[test]
     fn test_semantic_paths() {
         let temp_dir = TempDir::new().unwrap();
@@ -4,7 +4,7 @@
 //! enabling semantic search across documentation, code, and symbols.
 
 use super::{MetadataKey, StorageError, StorageResult};
-use crate::relationship::RelationshipMetadata;
+use crate::relationship::{Provenance, RelationshipMetadata};
 use crate::vector::{ClusterId, EmbeddingGenerator, SegmentOrdinal, VectorId, VectorSearchEngine};
 use crate::{FileId, RelationKind, Relationship, SymbolId, SymbolKind};
 use serde::{Deserialize, Serialize};
@@ -18,7 +18,10 @@ use tantivy::{
     Term,
     collector::TopDocs,
     directory::MmapDirectory,
-    query::{BooleanQuery, FuzzyTermQuery, Occur, Query, QueryParser, TermQuery},
+    query::{
+        BooleanQuery, EmptyQuery, FuzzyTermQuery, Occur, PhrasePrefixQuery, Query, QueryParser,
+        RegexQuery, TermQuery, TermSetQuery,
+    },
     schema::{
         FAST, Field, IndexRecordOption, NumericOptions, STORED, STRING, Schema, SchemaBuilder,
         TextFieldIndexing, TextOptions, Value,
@@ -48,7 +51,11 @@ pub struct IndexSchema {
     pub context: Field,
     pub visibility: Field,
     pub scope_context: Field,
-    pub language: Field, // Language identifier for the symbol
+    pub language: Field,        // Language identifier for the symbol
+    pub content_hash: Field,    // Hash of the symbol's source text for re-anchoring
+    pub parent_id: Field,       // Symbol the symbol is nested in (0 at file level)
+    pub attributes: Field,      // Attribute/decorator lines as written (stored only)
+    pub attribute_names: Field, // Attribute names, tokenized for `attribute:` queries
 
     // Relationship fields
     pub from_symbol_id: Field,
@@ -58,6 +65,8 @@ pub struct IndexSchema {
     pub relation_line: Field,
     pub relation_column: Field,
     pub relation_context: Field,
+    pub relation_provenance: Field, // How the target was resolved (resolved/ambiguous/heuristic)
+    pub derived_traits: Field,      // Traits a type derives, as written and by last segment
 
     // File info fields
     pub file_id: Field,
@@ -134,7 +143,10 @@ impl IndexSchema {
         // String fields for filtering (using STRING for exact match)
         let module_path = builder.add_text_field("module_path", STRING | STORED);
         let kind = builder.add_text_field("kind", STRING | STORED);
-        let visibility = builder.add_u64_field("visibility", STORED);
+        let visibility = builder.add_u64_field(
+            "visibility",
+            NumericOptions::default().set_indexed().set_stored(),
+        );
         let scope_context = builder.add_text_field("scope_context", STRING | STORED);
         let language = builder.add_text_field("language", STRING | STORED | FAST);
 
@@ -168,6 +180,23 @@ impl IndexSchema {
         let import_is_glob = builder.add_u64_field("import_is_glob", STORED);
         let import_is_type_only = builder.add_u64_field("import_is_type_only", STORED);
 
+        // Symbol content hash (added after import fields to keep existing field ids stable)
+        let content_hash = builder.add_text_field("content_hash", STRING | STORED);
+        let parent_id = builder.add_u64_field("parent_id", STORED);
+        let attributes = builder.add_text_field("attributes", STORED);
+        let attribute_names = builder.add_text_field(
+            "attribute_names",
+            TextOptions::default().set_indexing_options(
+                TextFieldIndexing::default()
+                    .set_tokenizer("default")
+                    .set_index_option(IndexRecordOption::WithFreqsAndPositions),
+            ),
+        );
+
+        // Relationship provenance, appended so existing field ids stay stable
+        let relation_provenance = builder.add_text_field("relation_provenance", STRING | STORED);
+        let derived_traits = builder.add_text_field("derived_traits", STRING);
+
         let schema = builder.build();
         let index_schema = IndexSchema {
             doc_type,
@@ -187,6 +216,10 @@ impl IndexSchema {
             visibility,
             scope_context,
             language,
+            content_hash,
+            parent_id,
+            attributes,
+            attribute_names,
             from_symbol_id,
             to_symbol_id,
             relation_kind,
@@ -194,6 +227,8 @@ impl IndexSchema {
             relation_line,
             relation_column,
             relation_context,
+            relation_provenance,
+            derived_traits,
             file_id,
             file_hash,
             file_timestamp,
@@ -364,31 +399,234 @@ impl ClusterCache {
     }
 }
 
+/// Where a search result came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchSource {
+    /// Full-text, regex or substring match from the Tantivy index
+    #[default]
+    FullText,
+    /// Semantic search used because full-text search found nothing
+    Semantic,
+}
+
 /// Search result with rich metadata
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
 pub struct SearchResult {
     pub symbol_id: SymbolId,
     pub name: String,
     pub kind: SymbolKind,
+    #[serde(serialize_with = "crate::paths::serialize_display_path")]
+    #[schemars(with = "String")]
     pub file_path: String,
     pub line: u32,
     pub column: u16,
     pub doc_comment: Option<String>,
     pub signature: Option<String>,
     pub module_path: String,
+    /// Attribute or decorator lines attached to the symbol
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<String>,
     pub score: f32,
     pub highlights: Vec<TextHighlight>,
     pub context: Option<String>,
+    /// Full-text for index matches; semantic for fallback results, whose
+    /// score is a 0-1 similarity rather than relevance
+    pub source: SearchSource,
+}
+
+impl SearchResult {
+    /// Build a result from a symbol found outside the full-text index
+    pub fn from_symbol(symbol: &crate::Symbol, score: f32, source: SearchSource) -> Self {
+        Self {
+            symbol_id: symbol.id,
+            name: symbol.name.to_string(),
+            kind: symbol.kind,
+            file_path: symbol.file_path.to_string(),
+            line: symbol.range.start_line,
+            column: symbol.range.start_column,
+            doc_comment: symbol.doc_comment.as_deref().map(str::to_string),
+            signature: symbol.signature.as_deref().map(str::to_string),
+            module_path: symbol.module_path.as_deref().unwrap_or("").to_string(),
+            attributes: symbol.attributes.iter().map(|a| a.to_string()).collect(),
+            score,
+            highlights: Vec::new(),
+            context: None,
+            source,
+        }
+    }
+}
+
+/// Drop results scoring below `min_score`, returning how many were removed
+///
+/// Full-text scores are unnormalized BM25 relevance, so the useful range depends
+/// on the index: exact and partial name matches usually score several points
+/// higher than fuzzy-only matches, which score about 1.0. Regex matches always
+/// score 1.0.
+pub fn retain_min_score(results: &mut Vec<SearchResult>, min_score: f32) -> usize {
+    let before = results.len();
+    results.retain(|result| result.score >= min_score);
+    before - results.len()
+}
+
+/// Filters a search applies inside its query, next to kind, module and
+/// language, so `limit` counts only symbols that pass them
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SearchFilters<'a> {
+    /// Keep symbols with an attribute matching this filter (see
+    /// [`crate::symbol::attribute_matches`])
+    pub attribute: Option<&'a str>,
+    /// Keep symbols with this visibility
+    pub visibility: Option<crate::Visibility>,
+    /// Keep symbols in one of these files (paths as stored in the index)
+    pub files: Option<&'a std::collections::HashSet<String>>,
+}
+
+/// Key search results are aggregated by with [`group_results`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchGroupBy {
+    File,
+    Module,
+    Kind,
+}
+
+impl SearchGroupBy {
+    const FILTERS: &'static [(SearchGroupBy, &'static str)] = &[
+        (SearchGroupBy::File, "file"),
+        (SearchGroupBy::Module, "module"),
+        (SearchGroupBy::Kind, "kind"),
+    ];
+
+    /// Parse `file`, `module` or `kind`, ignoring case
+    pub fn parse_filter(s: &str) -> Result<Self, String> {
+        let needle = s.trim();
+        Self::FILTERS
+            .iter()
+            .find(|(_, name)| name.eq_ignore_ascii_case(needle))
+            .map(|(group_by, _)| *group_by)
+            .ok_or_else(|| {
+                let accepted: Vec<&str> = Self::FILTERS.iter().map(|(_, name)| *name).collect();
+                format!(
+                    "Unknown group-by key '{s}'. Accepted values: {}",
+                    accepted.join(", ")
+                )
+            })
+    }
+
+    fn key(self, result: &SearchResult) -> String {
+        match self {
+            SearchGroupBy::File => crate::paths::display_path(&result.file_path).into_owned(),
+            SearchGroupBy::Module if result.module_path.is_empty() => "(no module)".to_string(),
+            SearchGroupBy::Module => result.module_path.clone(),
+            SearchGroupBy::Kind => format!("{:?}", result.kind),
+        }
+    }
+}
+
+/// Number of search results sharing one file, module or kind
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SearchGroup {
+    pub key: String,
+    pub count: usize,
+}
+
+impl std::fmt::Display for SearchGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:>5}  {}", self.count, self.key)
+    }
+}
+
+/// Count `results` per `group_by` key, largest group first and ties by key
+///
+/// Only the given results are counted, so the counts are local to the page
+/// a search returned and stop at its limit; they are not totals over every
+/// match in the index.
+pub fn group_results(results: &[SearchResult], group_by: SearchGroupBy) -> Vec<SearchGroup> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for result in results {
+        *counts.entry(group_by.key(result)).or_default() += 1;
+    }
+    let mut groups: Vec<SearchGroup> = counts
+        .into_iter()
+        .map(|(key, count)| SearchGroup { key, count })
+        .collect();
+    groups.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.key.cmp(&b.key)));
+    groups
+}
+
+/// Field prefixes accepted in search queries and the schema fields they target
+///
+/// `name:` searches the ngram-tokenized name (case-sensitive, partial matches
+/// of 3+ characters); `doc:` and `sig:` use the default tokenizer, which
+/// splits on non-alphanumerics and lowercases.
+pub const QUERY_FIELD_PREFIXES: &[(&str, &str)] = &[
+    ("name", "name_text"),
+    ("doc", "doc_comment"),
+    ("sig", "signature"),
+    ("attribute", "attribute_names"),
+];
+
+/// Rewrite `name:`, `doc:`, `sig:` and `attribute:` prefixes to schema field names
+///
+/// Prefixes only count at the start of a term (after whitespace, `(`, `+` or
+/// `-`) and outside quotes, and `name::` is left alone so Rust paths still
+/// work. Returns `None` when the query has no field prefix.
+fn rewrite_field_prefixes(query: &str) -> Option<String> {
+    let mut rewritten = String::with_capacity(query.len() + 16);
+    let mut found = false;
+    let mut in_quotes = false;
+    let mut term_start = true;
+    let mut rest = query;
+
+    while let Some(ch) = rest.chars().next() {
+        if term_start && !in_quotes {
+            let prefix = QUERY_FIELD_PREFIXES.iter().find(|(prefix, _)| {
+                rest.strip_prefix(prefix)
+                    .is_some_and(|r| r.starts_with(':') && !r.starts_with("::"))
+            });
+            if let Some((prefix, field)) = prefix {
+                rewritten.push_str(field);
+                rewritten.push(':');
+                rest = &rest[prefix.len() + 1..];
+                found = true;
+                term_start = false;
+                continue;
+            }
+        }
+
+        if ch == '"' {
+            in_quotes = !in_quotes;
+        }
+        term_start = ch.is_whitespace() || matches!(ch, '(' | '+' | '-');
+        rewritten.push(ch);
+        rest = &rest[ch.len_utf8()..];
+    }
+
+    found.then_some(rewritten)
 }
 
 /// Highlighted text region
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
 pub struct TextHighlight {
     pub field: String,
     pub start: usize,
     pub end: usize,
 }
 
+/// Minimum Tantivy heap per writer thread
+const WRITER_MIN_HEAP_PER_THREAD: usize = 15_000_000;
+
+/// Maximum number of Tantivy writer threads
+const WRITER_MAX_THREADS: usize = 8;
+
+/// Writer threads for `parallel_threads`, limited by what `heap_size` can hold
+fn writer_thread_count(parallel_threads: usize, heap_size: usize) -> usize {
+    parallel_threads
+        .min(WRITER_MAX_THREADS)
+        .min(heap_size / WRITER_MIN_HEAP_PER_THREAD)
+        .max(1)
+}
+
 /// Document index for full-text search
 pub struct DocumentIndex {
     index: Index,
@@ -398,6 +636,8 @@ pub struct DocumentIndex {
     pub(crate) writer: Mutex<Option<IndexWriter<Document>>>,
     /// Tantivy heap size in bytes
     heap_size: usize,
+    /// Number of Tantivy writer threads
+    writer_threads: usize,
     /// Maximum retry attempts for transient errors
     max_retry_attempts: u32,
     /// Optional path for vector storage files
@@ -414,12 +654,15 @@ pub struct DocumentIndex {
     pending_symbol_counter: Mutex<Option<u32>>,
     /// Pending file counter during batch operations
     pending_file_counter: Mutex<Option<u32>>,
+    /// Opened with `open_read_only`; no writer may be created
+    read_only: bool,
 }
 
 impl std::fmt::Debug for DocumentIndex {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("DocumentIndex")
             .field("index_path", &self.index_path)
+            .field("read_only", &self.read_only)
             .field("schema", &self.schema)
             .field("vector_storage_path", &self.vector_storage_path)
             .field("has_vector_engine", &self.vector_engine.is_some())
@@ -445,13 +688,45 @@ impl DocumentIndex {
         index_path: impl AsRef<Path>,
         settings: &crate::config::Settings,
     ) -> StorageResult<Self> {
-        let index_path = index_path.as_ref().to_path_buf();
-        std::fs::create_dir_all(&index_path)?;
+        Self::open(index_path.as_ref(), settings, false)
+    }
+
+    /// Open an existing index for queries only
+    ///
+    /// Nothing is created on disk and no index writer is ever started, so the
+    /// Tantivy writer lock is never taken and any number of read-only processes
+    /// can query the index alongside one writer. Mutating calls fail with
+    /// `StorageError::ReadOnly`.
+    pub fn open_read_only(
+        index_path: impl AsRef<Path>,
+        settings: &crate::config::Settings,
+    ) -> StorageResult<Self> {
+        Self::open(index_path.as_ref(), settings, true)
+    }
+
+    fn open(
+        index_path: &Path,
+        settings: &crate::config::Settings,
+        read_only: bool,
+    ) -> StorageResult<Self> {
+        let index_path = index_path.to_path_buf();
+        if read_only {
+            if !index_path.join("meta.json").exists() {
+                return Err(StorageError::Io(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("no Tantivy index at {}", index_path.display()),
+                )));
+            }
+        } else {
+            std::fs::create_dir_all(&index_path)?;
+        }
 
         // Extract and validate heap size
         let heap_size = settings.indexing.tantivy_heap_mb * 1_000_000;
         let heap_size = heap_size.clamp(10_000_000, 1_000_000_000); // 10MB-1GB
 
+        let writer_threads = writer_thread_count(settings.indexing.parallel_threads, heap_size);
+
         let max_retry_attempts = settings.indexing.max_retry_attempts;
 
         let (schema, index_schema) = IndexSchema::build();
@@ -487,6 +762,7 @@ impl DocumentIndex {
             index_path,
             writer: Mutex::new(None),
             heap_size,
+            writer_threads,
             max_retry_attempts,
             vector_storage_path: None,
             vector_engine: None,
@@ -495,13 +771,37 @@ impl DocumentIndex {
             pending_embeddings: Mutex::new(Vec::new()),
             pending_symbol_counter: Mutex::new(None),
             pending_file_counter: Mutex::new(None),
+            read_only,
         })
     }
 
+    /// Whether the index was opened with `open_read_only`
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Reject `operation` on a read-only index
+    fn ensure_writable(&self, operation: &str) -> StorageResult<()> {
+        if self.read_only {
+            return Err(StorageError::ReadOnly {
+                operation: operation.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Number of threads the index writer uses
+    pub fn writer_threads(&self) -> usize {
+        self.writer_threads
+    }
+
     /// Create index writer with retry logic for transient errors
     fn create_writer_with_retry(&self) -> Result<IndexWriter<Document>, tantivy::TantivyError> {
         for attempt in 0..self.max_retry_attempts {
-            match self.index.writer::<Document>(self.heap_size) {
+            match self
+                .index
+                .writer_with_num_threads::<Document>(self.writer_threads, self.heap_size)
+            {
                 Ok(writer) => return Ok(writer),
                 Err(e) => {
                     // Check for transient I/O errors using ErrorKind
@@ -862,6 +1162,7 @@ impl DocumentIndex {
 
     /// Start a batch operation for adding multiple documents
     pub fn start_batch(&self) -> StorageResult<()> {
+        self.ensure_writable("start_batch")?;
         let mut writer_lock = self.writer.lock().map_err(|_| StorageError::LockPoisoned)?;
         if writer_lock.is_none() {
             let writer = self.create_writer_with_retry()?;
@@ -904,6 +1205,53 @@ impl DocumentIndex {
         visibility: crate::Visibility,
         scope_context: Option<crate::ScopeContext>,
         language_id: Option<&str>, // Language identifier for the symbol
+    ) -> StorageResult<()> {
+        self.add_symbol_document(
+            symbol_id,
+            name,
+            kind,
+            file_id,
+            file_path,
+            line,
+            column,
+            end_line,
+            end_column,
+            doc_comment,
+            signature,
+            module_path,
+            context,
+            visibility,
+            scope_context,
+            language_id,
+            None,
+            None,
+            &[],
+        )
+    }
+
+    /// Add a symbol document, optionally anchored by the hash of its source text
+    #[allow(clippy::too_many_arguments)]
+    fn add_symbol_document(
+        &self,
+        symbol_id: SymbolId,
+        name: &str,
+        kind: SymbolKind,
+        file_id: FileId,
+        file_path: &str,
+        line: u32,
+        column: u16,
+        end_line: u32,
+        end_column: u16,
+        doc_comment: Option<&str>,
+        signature: Option<&str>,
+        module_path: &str,
+        context: Option<&str>,
+        visibility: crate::Visibility,
+        scope_context: Option<crate::ScopeContext>,
+        language_id: Option<&str>, // Language identifier for the symbol
+        content_hash: Option<&str>,
+        parent: Option<SymbolId>,
+        attributes: &[Box<str>],
     ) -> StorageResult<()> {
         let mut writer_lock = self.writer.lock().map_err(|_| StorageError::LockPoisoned)?;
         let writer = writer_lock.as_mut().ok_or(StorageError::NoActiveBatch)?;
@@ -951,6 +1299,30 @@ impl DocumentIndex {
             doc.add_text(self.schema.language, "");
         }
 
+        if let Some(hash) = content_hash {
+            doc.add_text(self.schema.content_hash, hash);
+        }
+
+        if let Some(parent) = parent {
+            doc.add_u64(self.schema.parent_id, parent.value() as u64);
+        }
+
+        for attribute in attributes {
+            doc.add_text(self.schema.attributes, attribute);
+            doc.add_text(
+                self.schema.attribute_names,
+                crate::indexing::entrypoints::attribute_path(attribute),
+            );
+            // Derives name traits that may not be indexed, so they are kept by
+            // name for lookups that cannot go through an Implements edge
+            for trait_path in crate::symbol::derived_traits(attribute) {
+                doc.add_text(self.schema.derived_traits, trait_path);
+                if let Some((_, trait_name)) = trait_path.rsplit_once("::") {
+                    doc.add_text(self.schema.derived_traits, trait_name);
+                }
+            }
+        }
+
         // Add default vector fields - these will be updated later if vectors are generated
         if self.has_vector_support() {
             doc.add_u64(self.schema.cluster_id, 0); // 0 means not yet assigned
@@ -975,6 +1347,24 @@ impl DocumentIndex {
         Ok(())
     }
 
+    /// Discard all uncommitted changes of the current batch
+    ///
+    /// Used when a multi-step removal fails halfway, so none of it is applied.
+    pub fn rollback_batch(&self) -> StorageResult<()> {
+        let mut writer_lock = self.writer.lock().map_err(|_| StorageError::LockPoisoned)?;
+        if let Some(mut writer) = writer_lock.take() {
+            writer.rollback()?;
+        }
+
+        if let Ok(mut pending_guard) = self.pending_symbol_counter.lock() {
+            *pending_guard = None;
+        }
+        if let Ok(mut pending_guard) = self.pending_file_counter.lock() {
+            *pending_guard = None;
+        }
+        Ok(())
+    }
+
     /// Commit the current batch and reload the reader
     pub fn commit_batch(&self) -> StorageResult<()> {
         let mut writer_lock = match self.writer.lock() {
@@ -1052,6 +1442,7 @@ impl DocumentIndex {
         } else {
             // Create temporary writer for single operation
             drop(writer_lock); // Release lock before creating new writer
+            self.ensure_writable("remove_file_documents")?;
             let mut writer = self.index.writer::<Document>(50_000_000)?;
             writer.delete_term(term);
             writer.commit()?;
@@ -1069,10 +1460,30 @@ impl DocumentIndex {
         kind_filter: Option<SymbolKind>,
         module_filter: Option<&str>,
         language_filter: Option<&str>,
+    ) -> StorageResult<Vec<SearchResult>> {
+        self.search_filtered(
+            query_str,
+            limit,
+            kind_filter,
+            module_filter,
+            language_filter,
+            &SearchFilters::default(),
+        )
+    }
+
+    /// [`search`](Self::search) with further [`SearchFilters`]
+    pub fn search_filtered(
+        &self,
+        query_str: &str,
+        limit: usize,
+        kind_filter: Option<SymbolKind>,
+        module_filter: Option<&str>,
+        language_filter: Option<&str>,
+        filters: &SearchFilters,
     ) -> StorageResult<Vec<SearchResult>> {
         let searcher = self.reader.searcher();
 
-        let query_parser = QueryParser::for_index(
+        let mut query_parser = QueryParser::for_index(
             &self.index,
             vec![
                 self.schema.name_text, // Use name_text for full-text search (tokenized)
@@ -1082,6 +1493,20 @@ impl DocumentIndex {
             ],
         );
 
+        // Fielded queries (name:, doc:, sig:) search only the named fields and
+        // require every term; fuzzy name matching does not apply to them
+        if let Some(fielded) = rewrite_field_prefixes(query_str) {
+            query_parser.set_conjunction_by_default();
+            let mut all_clauses = vec![(Occur::Must, query_parser.parse_query(&fielded)?)];
+            all_clauses.extend(self.symbol_filter_clauses(
+                kind_filter,
+                module_filter,
+                language_filter,
+                filters,
+            )?);
+            return self.collect_search_results(&searcher, &BooleanQuery::new(all_clauses), limit);
+        }
+
         // Try parsing as Tantivy query syntax first, fall back to literal matching
         // for queries with special characters (interface{}, Vec<T>, etc.)
         let main_query = match query_parser.parse_query(query_str) {
@@ -1146,17 +1571,161 @@ impl DocumentIndex {
         ));
 
         // Add mandatory filters.
-        all_clauses.push((
+        all_clauses.extend(self.symbol_filter_clauses(
+            kind_filter,
+            module_filter,
+            language_filter,
+            filters,
+        )?);
+
+        let final_query = BooleanQuery::new(all_clauses);
+        self.collect_search_results(&searcher, &final_query, limit)
+    }
+
+    /// Run a symbol query and convert the top hits to search results
+    fn collect_search_results(
+        &self,
+        searcher: &tantivy::Searcher,
+        query: &dyn Query,
+        limit: usize,
+    ) -> StorageResult<Vec<SearchResult>> {
+        let top_docs = searcher.search(query, &TopDocs::with_limit(limit))?;
+
+        let mut results = Vec::new();
+        for (score, doc_address) in top_docs {
+            let doc: Document = searcher.doc(doc_address)?;
+            results.push(self.document_to_search_result(&doc, score)?);
+        }
+
+        Ok(results)
+    }
+
+    /// Search symbols whose name matches a regular expression
+    ///
+    /// The kind/module/language and other filters narrow candidates inside Tantivy; names
+    /// are then matched in memory. Matching stops after `limit` hits and fails
+    /// once `deadline`, if any, passes so pathological patterns cannot stall a
+    /// request.
+    #[allow(clippy::too_many_arguments)]
+    pub fn search_by_name_regex(
+        &self,
+        regex: &regex::Regex,
+        limit: usize,
+        kind_filter: Option<SymbolKind>,
+        module_filter: Option<&str>,
+        language_filter: Option<&str>,
+        filters: &SearchFilters,
+        deadline: Option<std::time::Instant>,
+    ) -> StorageResult<Vec<SearchResult>> {
+        let searcher = self.reader.searcher();
+        let query = BooleanQuery::new(self.symbol_filter_clauses(
+            kind_filter,
+            module_filter,
+            language_filter,
+            filters,
+        )?);
+
+        // Sort addresses so results are stable across runs
+        let mut candidates: Vec<_> = searcher
+            .search(&query, &tantivy::collector::DocSetCollector)?
+            .into_iter()
+            .collect();
+        candidates.sort();
+
+        let mut results = Vec::new();
+        for doc_address in candidates {
+            if results.len() >= limit {
+                break;
+            }
+            if deadline.is_some_and(|deadline| std::time::Instant::now() >= deadline) {
+                return Err(StorageError::General(format!(
+                    "regex search timed out after {} matches",
+                    results.len()
+                )));
+            }
+
+            let doc: Document = searcher.doc(doc_address)?;
+            let name = doc
+                .get_first(self.schema.name)
+                .and_then(|v| v.as_str())
+                .unwrap_or("");
+            if regex.is_match(name) {
+                results.push(self.document_to_search_result(&doc, 1.0)?);
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Search symbols whose whole name equals `name`
+    ///
+    /// Case-sensitive lookups are a term query on the untokenized name field,
+    /// others a case-insensitive regex over its terms; either runs inside
+    /// Tantivy together with the filters. Every match scores 1.0.
+    #[allow(clippy::too_many_arguments)]
+    pub fn search_by_exact_name(
+        &self,
+        name: &str,
+        case_sensitive: bool,
+        limit: usize,
+        kind_filter: Option<SymbolKind>,
+        module_filter: Option<&str>,
+        language_filter: Option<&str>,
+        filters: &SearchFilters,
+    ) -> StorageResult<Vec<SearchResult>> {
+        let name_query: Box<dyn Query> = if case_sensitive {
+            Box::new(TermQuery::new(
+                Term::from_field_text(self.schema.name, name),
+                IndexRecordOption::Basic,
+            ))
+        } else {
+            let pattern = format!("(?i){}", regex::escape(name));
+            Box::new(RegexQuery::from_pattern(&pattern, self.schema.name)?)
+        };
+        let mut clauses =
+            self.symbol_filter_clauses(kind_filter, module_filter, language_filter, filters)?;
+        clauses.push((Occur::Must, name_query));
+
+        // Sort addresses so results are stable across runs
+        let searcher = self.reader.searcher();
+        let mut matches: Vec<_> = searcher
+            .search(
+                &BooleanQuery::new(clauses),
+                &tantivy::collector::DocSetCollector,
+            )?
+            .into_iter()
+            .collect();
+        matches.sort();
+
+        matches
+            .into_iter()
+            .take(limit)
+            .map(|doc_address| {
+                let doc: Document = searcher.doc(doc_address)?;
+                self.document_to_search_result(&doc, 1.0)
+            })
+            .collect()
+    }
+
+    /// Mandatory clauses restricting a query to symbols matching the filters
+    fn symbol_filter_clauses(
+        &self,
+        kind_filter: Option<SymbolKind>,
+        module_filter: Option<&str>,
+        language_filter: Option<&str>,
+        filters: &SearchFilters,
+    ) -> StorageResult<Vec<(Occur, Box<dyn Query>)>> {
+        let mut clauses: Vec<(Occur, Box<dyn Query>)> = vec![(
             Occur::Must,
             Box::new(TermQuery::new(
                 Term::from_field_text(self.schema.doc_type, "symbol"),
                 IndexRecordOption::Basic,
             )),
-        ));
+        )];
 
         if let Some(kind) = kind_filter {
             let term = Term::from_field_text(self.schema.kind, &format!("{kind:?}"));
-            all_clauses.push((
+            clauses.push((
                 Occur::Must,
                 Box::new(TermQuery::new(term, IndexRecordOption::Basic)),
             ));
@@ -1164,7 +1733,7 @@ impl DocumentIndex {
 
         if let Some(module) = module_filter {
             let term = Term::from_field_text(self.schema.module_path, module);
-            all_clauses.push((
+            clauses.push((
                 Occur::Must,
                 Box::new(TermQuery::new(term, IndexRecordOption::Basic)),
             ));
@@ -1173,107 +1742,134 @@ impl DocumentIndex {
         // Add language filter if provided
         if let Some(lang) = language_filter {
             let term = Term::from_field_text(self.schema.language, lang);
-            all_clauses.push((
+            clauses.push((
                 Occur::Must,
                 Box::new(TermQuery::new(term, IndexRecordOption::Basic)),
             ));
         }
 
-        let final_query = BooleanQuery::new(all_clauses);
+        if let Some(files) = filters.files {
+            let terms = files
+                .iter()
+                .map(|path| Term::from_field_text(self.schema.file_path, path));
+            clauses.push((Occur::Must, Box::new(TermSetQuery::new(terms))));
+        }
 
-        let top_docs = searcher.search(&final_query, &TopDocs::with_limit(limit))?;
+        if let Some(visibility) = filters.visibility {
+            let term = Term::from_field_u64(self.schema.visibility, visibility as u64);
+            clauses.push((
+                Occur::Must,
+                Box::new(TermQuery::new(term, IndexRecordOption::Basic)),
+            ));
+        }
 
-        let mut results = Vec::new();
-        for (score, doc_address) in top_docs {
-            let doc: Document = searcher.doc(doc_address)?;
+        // Attribute names are tokenized into words, so the filter's words
+        // must appear in order with the last one as a prefix
+        if let Some(attribute) = filters.attribute {
+            let field = self.schema.attribute_names;
+            let mut analyzer = self.index.tokenizer_for_field(field)?;
+            let mut terms = Vec::new();
+            analyzer
+                .token_stream(crate::indexing::entrypoints::attribute_path(
+                    attribute.trim(),
+                ))
+                .process(&mut |token| terms.push(Term::from_field_text(field, &token.text)));
+            let query: Box<dyn Query> = if terms.is_empty() {
+                Box::new(EmptyQuery)
+            } else {
+                Box::new(PhrasePrefixQuery::new(terms))
+            };
+            clauses.push((Occur::Must, query));
+        }
 
-            // Extract fields
-            let symbol_id = doc
-                .get_first(self.schema.symbol_id)
-                .and_then(|v| v.as_u64())
-                .and_then(|id| SymbolId::new(id as u32))
-                .ok_or(StorageError::InvalidFieldValue {
-                    field: "symbol_id".to_string(),
-                    reason: "not a valid u32".to_string(),
-                })?;
+        Ok(clauses)
+    }
 
-            let name = doc
-                .get_first(self.schema.name)
-                .and_then(|v| v.as_str())
-                .unwrap_or("")
-                .to_string();
+    /// Build a search result from a stored symbol document
+    fn document_to_search_result(&self, doc: &Document, score: f32) -> StorageResult<SearchResult> {
+        // Extract fields
+        let symbol_id = doc
+            .get_first(self.schema.symbol_id)
+            .and_then(|v| v.as_u64())
+            .and_then(|id| SymbolId::new(id as u32))
+            .ok_or(StorageError::InvalidFieldValue {
+                field: "symbol_id".to_string(),
+                reason: "not a valid u32".to_string(),
+            })?;
 
-            let file_path = doc
-                .get_first(self.schema.file_path)
-                .and_then(|v| v.as_str())
-                .unwrap_or("")
-                .to_string();
+        let name = doc
+            .get_first(self.schema.name)
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string();
 
-            let line = doc
-                .get_first(self.schema.line_number)
-                .and_then(|v| v.as_u64())
-                .unwrap_or(0) as u32;
+        let file_path = doc
+            .get_first(self.schema.file_path)
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string();
 
-            let column = doc
-                .get_first(self.schema.column)
-                .and_then(|v| v.as_u64())
-                .unwrap_or(0) as u16;
+        let line = doc
+            .get_first(self.schema.line_number)
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0) as u32;
 
-            let doc_comment = doc
-                .get_first(self.schema.doc_comment)
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string());
+        let column = doc
+            .get_first(self.schema.column)
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0) as u16;
 
-            let signature = doc
-                .get_first(self.schema.signature)
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string());
+        let doc_comment = doc
+            .get_first(self.schema.doc_comment)
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
 
-            let context = doc
-                .get_first(self.schema.context)
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string());
+        let signature = doc
+            .get_first(self.schema.signature)
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
 
-            // Extract kind from facet (stored as string representation)
-            let kind_str = doc
-                .get_first(self.schema.kind)
-                .and_then(|v| v.as_str())
-                .unwrap_or("Unknown");
-
-            let kind = match kind_str {
-                "Function" => SymbolKind::Function,
-                "Struct" => SymbolKind::Struct,
-                "Trait" => SymbolKind::Trait,
-                "Method" => SymbolKind::Method,
-                "Field" => SymbolKind::Field,
-                "Module" => SymbolKind::Module,
-                "Constant" => SymbolKind::Constant,
-                _ => SymbolKind::Function, // Default fallback
-            };
+        let context = doc
+            .get_first(self.schema.context)
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
 
-            let module_path = doc
-                .get_first(self.schema.module_path)
-                .and_then(|v| v.as_str())
-                .unwrap_or("")
-                .to_string();
+        // Extract kind from facet (stored as string representation)
+        let kind_str = doc
+            .get_first(self.schema.kind)
+            .and_then(|v| v.as_str())
+            .unwrap_or("Unknown");
 
-            results.push(SearchResult {
-                symbol_id,
-                name,
-                kind,
-                file_path,
-                line,
-                column,
-                doc_comment,
-                signature,
-                module_path,
-                score,
-                highlights: Vec::new(), // TODO: Implement highlighting
-                context,
-            });
-        }
+        let kind = SymbolKind::from_str_with_default(kind_str);
 
-        Ok(results)
+        let module_path = doc
+            .get_first(self.schema.module_path)
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string();
+
+        let attributes = doc
+            .get_all(self.schema.attributes)
+            .filter_map(|v| v.as_str())
+            .map(|s| s.to_string())
+            .collect();
+
+        Ok(SearchResult {
+            symbol_id,
+            name,
+            kind,
+            file_path,
+            line,
+            column,
+            doc_comment,
+            signature,
+            module_path,
+            attributes,
+            score,
+            highlights: Vec::new(), // TODO: Implement highlighting
+            context,
+            source: SearchSource::FullText,
+        })
     }
 
     /// Get total number of indexed documents
@@ -1290,6 +1886,7 @@ impl DocumentIndex {
         if !meta_path.exists() {
             return Ok(());
         }
+        self.ensure_writable("clear")?;
 
         let mut writer = self.index.writer::<Document>(50_000_000)?;
         writer.delete_all_documents()?;
@@ -1403,6 +2000,53 @@ impl DocumentIndex {
         Ok(symbols)
     }
 
+    /// Find types deriving `trait_name`, given as written in the derive
+    /// (`serde::Serialize`) or by its last segment (`Serialize`)
+    pub fn find_symbols_deriving(
+        &self,
+        trait_name: &str,
+        language_filter: Option<&str>,
+    ) -> StorageResult<Vec<crate::Symbol>> {
+        let searcher = self.reader.searcher();
+        let mut query_clauses: Vec<(Occur, Box<dyn Query>)> = vec![
+            (
+                Occur::Must,
+                Box::new(TermQuery::new(
+                    Term::from_field_text(self.schema.derived_traits, trait_name),
+                    IndexRecordOption::Basic,
+                )),
+            ),
+            (
+                Occur::Must,
+                Box::new(TermQuery::new(
+                    Term::from_field_text(self.schema.doc_type, "symbol"),
+                    IndexRecordOption::Basic,
+                )),
+            ),
+        ];
+        if let Some(lang) = language_filter {
+            query_clauses.push((
+                Occur::Must,
+                Box::new(TermQuery::new(
+                    Term::from_field_text(self.schema.language, lang),
+                    IndexRecordOption::Basic,
+                )),
+            ));
+        }
+
+        let top_docs = searcher.search(
+            &BooleanQuery::new(query_clauses),
+            &TopDocs::with_limit(10000),
+        )?;
+        let mut symbols = Vec::new();
+        for (_score, doc_address) in top_docs {
+            let doc = searcher.doc::<Document>(doc_address)?;
+            symbols.push(self.document_to_symbol(&doc)?);
+        }
+        symbols.sort_by_key(|symbol| symbol.id.value());
+        Ok(symbols)
+    }
+
     /// Find symbols by file ID
     pub fn find_symbols_by_file(&self, file_id: FileId) -> StorageResult<Vec<crate::Symbol>> {
         let searcher = self.reader.searcher();
@@ -1434,6 +2078,48 @@ impl DocumentIndex {
         Ok(symbols)
     }
 
+    /// Find the content hashes of a file's symbols.
+    ///
+    /// Returns `(symbol, content_hash)` pairs for symbols indexed with a hash.
+    pub fn find_symbol_hashes_by_file(
+        &self,
+        file_id: FileId,
+    ) -> StorageResult<Vec<(crate::Symbol, String)>> {
+        let searcher = self.reader.searcher();
+        let query = BooleanQuery::from(vec![
+            (
+                Occur::Must,
+                Box::new(TermQuery::new(
+                    Term::from_field_text(self.schema.doc_type, "symbol"),
+                    IndexRecordOption::Basic,
+                )) as Box<dyn Query>,
+            ),
+            (
+                Occur::Must,
+                Box::new(TermQuery::new(
+                    Term::from_field_u64(self.schema.file_id, file_id.0 as u64),
+                    IndexRecordOption::Basic,
+                )) as Box<dyn Query>,
+            ),
+        ]);
+
+        let doc_addresses = searcher.search(&query, &tantivy::collector::DocSetCollector)?;
+        let mut hashes = Vec::new();
+
+        for doc_address in doc_addresses {
+            let doc = searcher.doc::<Document>(doc_address)?;
+            if let Some(hash) = doc
+                .get_first(self.schema.content_hash)
+                .and_then(|v| v.as_str())
+            {
+                let hash = hash.to_string();
+                hashes.push((self.document_to_symbol(&doc)?, hash));
+            }
+        }
+
+        Ok(hashes)
+    }
+
     /// Get all symbols (use with caution on large indexes)
     pub fn get_all_symbols(&self, limit: usize) -> StorageResult<Vec<crate::Symbol>> {
         let searcher = self.reader.searcher();
@@ -1618,6 +2304,15 @@ impl DocumentIndex {
             module_path: module_path.map(|s| s.into()),
             visibility,
             scope_context,
+            parent: doc
+                .get_first(self.schema.parent_id)
+                .and_then(|v| v.as_u64())
+                .and_then(|id| SymbolId::new(id as u32)),
+            attributes: doc
+                .get_all(self.schema.attributes)
+                .filter_map(|v| v.as_str())
+                .map(Into::into)
+                .collect(),
             language_id: {
                 // Read the language field from the document and convert to LanguageId
                 // using the language registry (which maintains the static strings)
@@ -1637,10 +2332,9 @@ impl DocumentIndex {
         })
     }
 
-    /// Get file info by path
-    pub fn get_file_info(&self, path: &str) -> StorageResult<Option<(FileId, String)>> {
-        let searcher = self.reader.searcher();
-        let query = BooleanQuery::from(vec![
+    /// Query matching the file info document of `path`
+    fn file_info_query(&self, path: &str) -> BooleanQuery {
+        BooleanQuery::from(vec![
             (
                 Occur::Must,
                 Box::new(TermQuery::new(
@@ -1655,7 +2349,13 @@ impl DocumentIndex {
                     IndexRecordOption::Basic,
                 )) as Box<dyn Query>,
             ),
-        ]);
+        ])
+    }
+
+    /// Get file info by path
+    pub fn get_file_info(&self, path: &str) -> StorageResult<Option<(FileId, String)>> {
+        let searcher = self.reader.searcher();
+        let query = self.file_info_query(path);
 
         let top_docs = searcher.search(&query, &TopDocs::with_limit(1))?;
 
@@ -1686,6 +2386,43 @@ impl DocumentIndex {
         }
     }
 
+    /// When `path` was last indexed, as recorded in its file info
+    pub(crate) fn get_file_timestamp(&self, path: &str) -> StorageResult<Option<u64>> {
+        let searcher = self.reader.searcher();
+        let top_docs = searcher.search(&self.file_info_query(path), &TopDocs::with_limit(1))?;
+        match top_docs.first() {
+            Some((_score, doc_address)) => Ok(searcher
+                .doc::<Document>(*doc_address)?
+                .get_first(self.schema.file_timestamp)
+                .and_then(|v| v.as_u64())),
+            None => Ok(None),
+        }
+    }
+
+    /// Replace the file info of `path`, keeping its symbols
+    pub(crate) fn replace_file_info(
+        &self,
+        file_id: FileId,
+        path: &str,
+        hash: &str,
+        timestamp: u64,
+    ) -> StorageResult<()> {
+        {
+            let mut writer_lock = match self.writer.lock() {
+                Ok(lock) => lock,
+                Err(poisoned) => {
+                    eprintln!(
+                        "Warning: Recovering from poisoned writer mutex in replace_file_info"
+                    );
+                    poisoned.into_inner()
+                }
+            };
+            let writer = writer_lock.as_mut().ok_or(StorageError::NoActiveBatch)?;
+            writer.delete_query(Box::new(self.file_info_query(path)))?;
+        }
+        self.store_file_info(file_id, path, hash, timestamp)
+    }
+
     /// Get next file ID
     pub fn get_next_file_id(&self) -> StorageResult<u32> {
         // During batch operations, use and increment the pending counter
@@ -1768,6 +2505,17 @@ impl DocumentIndex {
         Ok(())
     }
 
+    /// Delete relationships originating from a symbol, keeping incoming edges
+    pub fn delete_outgoing_relationships(&self, id: SymbolId) -> StorageResult<()> {
+        let mut writer_lock = self.writer.lock().map_err(|_| StorageError::LockPoisoned)?;
+        let writer = writer_lock.as_mut().ok_or(StorageError::NoActiveBatch)?;
+
+        let from_term = Term::from_field_u64(self.schema.from_symbol_id, id.0 as u64);
+        writer.delete_term(from_term);
+
+        Ok(())
+    }
+
     /// Count symbols
     pub fn count_symbols(&self) -> StorageResult<usize> {
         let searcher = self.reader.searcher();
@@ -1880,7 +2628,8 @@ impl DocumentIndex {
                 })?;
 
             // Extract metadata if present
-            let mut relationship = Relationship::new(kind);
+            let mut relationship =
+                Relationship::new(kind).with_provenance(self.stored_provenance(&doc));
 
             // Extract metadata fields
             if let Some(line) = doc
@@ -1898,7 +2647,7 @@ impl DocumentIndex {
                         .get_first(self.schema.relation_context)
                         .and_then(|v| v.as_str())
                     {
-                        metadata = metadata.with_context(context);
+                        metadata = metadata.with_stored_context(context);
                     }
 
                     relationship = relationship.with_metadata(metadata);
@@ -1958,7 +2707,8 @@ impl DocumentIndex {
                 })?;
 
             // Extract metadata if present
-            let mut relationship = Relationship::new(kind);
+            let mut relationship =
+                Relationship::new(kind).with_provenance(self.stored_provenance(&doc));
 
             // Extract metadata fields
             if let Some(line) = doc
@@ -1976,7 +2726,7 @@ impl DocumentIndex {
                         .get_first(self.schema.relation_context)
                         .and_then(|v| v.as_str())
                     {
-                        metadata = metadata.with_context(context);
+                        metadata = metadata.with_stored_context(context);
                     }
 
                     relationship = relationship.with_metadata(metadata);
@@ -2042,6 +2792,66 @@ impl DocumentIndex {
         Ok(relationships)
     }
 
+    /// Get the endpoints of every stored relationship as `(from, to)` pairs
+    pub fn get_all_relationship_endpoints(&self) -> StorageResult<Vec<(SymbolId, SymbolId)>> {
+        let searcher = self.reader.searcher();
+        let query = TermQuery::new(
+            Term::from_field_text(self.schema.doc_type, "relationship"),
+            IndexRecordOption::Basic,
+        );
+
+        let doc_addresses = searcher.search(&query, &tantivy::collector::DocSetCollector)?;
+        let mut endpoints = Vec::with_capacity(doc_addresses.len());
+
+        for doc_address in doc_addresses {
+            let doc = searcher.doc::<Document>(doc_address)?;
+            let symbol_field = |field| {
+                doc.get_first(field)
+                    .and_then(|v| v.as_u64())
+                    .and_then(|id| SymbolId::new(id as u32))
+            };
+
+            if let (Some(from), Some(to)) = (
+                symbol_field(self.schema.from_symbol_id),
+                symbol_field(self.schema.to_symbol_id),
+            ) {
+                endpoints.push((from, to));
+            }
+        }
+
+        Ok(endpoints)
+    }
+
+    /// Map every indexed symbol to the file that defines it
+    pub fn get_symbol_file_ids(&self) -> StorageResult<HashMap<SymbolId, FileId>> {
+        let searcher = self.reader.searcher();
+        let query = TermQuery::new(
+            Term::from_field_text(self.schema.doc_type, "symbol"),
+            IndexRecordOption::Basic,
+        );
+
+        let doc_addresses = searcher.search(&query, &tantivy::collector::DocSetCollector)?;
+        let mut files = HashMap::with_capacity(doc_addresses.len());
+
+        for doc_address in doc_addresses {
+            let doc = searcher.doc::<Document>(doc_address)?;
+            let symbol_id = doc
+                .get_first(self.schema.symbol_id)
+                .and_then(|v| v.as_u64())
+                .and_then(|id| SymbolId::new(id as u32));
+            let file_id = doc
+                .get_first(self.schema.file_id)
+                .and_then(|v| v.as_u64())
+                .and_then(|id| FileId::new(id as u32));
+
+            if let (Some(symbol_id), Some(file_id)) = (symbol_id, file_id) {
+                files.insert(symbol_id, file_id);
+            }
+        }
+
+        Ok(files)
+    }
+
     /// Get file path by ID
     pub fn get_file_path(&self, file_id: FileId) -> StorageResult<Option<String>> {
         let searcher = self.reader.searcher();
@@ -2114,6 +2924,7 @@ impl DocumentIndex {
         doc.add_u64(self.schema.to_symbol_id, to.value() as u64);
         doc.add_text(self.schema.relation_kind, format!("{:?}", rel.kind));
         doc.add_f64(self.schema.relation_weight, rel.weight as f64);
+        doc.add_text(self.schema.relation_provenance, rel.provenance.as_str());
 
         if let Some(ref metadata) = rel.metadata {
             if let Some(line) = metadata.line {
@@ -2122,7 +2933,7 @@ impl DocumentIndex {
             if let Some(column) = metadata.column {
                 doc.add_u64(self.schema.relation_column, column as u64);
             }
-            if let Some(ref context) = metadata.context {
+            if let Some(context) = metadata.stored_context() {
                 doc.add_text(self.schema.relation_context, context.as_ref());
             }
         }
@@ -2133,7 +2944,20 @@ impl DocumentIndex {
 
     /// Index a symbol from a Symbol struct
     pub fn index_symbol(&self, symbol: &crate::Symbol, file_path: &str) -> StorageResult<()> {
-        self.add_document(
+        self.index_symbol_with_hash(symbol, file_path, None)
+    }
+
+    /// Index a symbol together with the hash of its source text.
+    ///
+    /// The hash lets a later re-index re-anchor the symbol to its new position
+    /// (keeping its `SymbolId`) when only surrounding lines moved.
+    pub fn index_symbol_with_hash(
+        &self,
+        symbol: &crate::Symbol,
+        file_path: &str,
+        content_hash: Option<&str>,
+    ) -> StorageResult<()> {
+        self.add_symbol_document(
             symbol.id,
             &symbol.name,
             symbol.kind,
@@ -2168,6 +2992,9 @@ impl DocumentIndex {
             // This should be tested with real workloads to ensure we maintain our performance targets.
             symbol.scope_context.clone(),
             symbol.language_id.as_ref().map(|id| id.as_str()),
+            content_hash,
+            symbol.parent,
+            &symbol.attributes,
         )
     }
 
@@ -2389,95 +3216,211 @@ impl DocumentIndex {
         let mut relationships = Vec::new();
         for (_score, doc_address) in top_docs {
             let doc: Document = searcher.doc(doc_address)?;
+            if let Some(relationship) = self.document_to_relationship(&doc)? {
+                relationships.push(relationship);
+            }
+        }
 
-            let from_id = doc
-                .get_first(self.schema.from_symbol_id)
-                .and_then(|v| v.as_u64())
-                .and_then(|id| SymbolId::new(id as u32))
-                .ok_or(StorageError::InvalidFieldValue {
-                    field: "from_symbol_id".to_string(),
-                    reason: "not a valid u32".to_string(),
-                })?;
+        Ok(relationships)
+    }
 
-            let to_id = doc
-                .get_first(self.schema.to_symbol_id)
-                .and_then(|v| v.as_u64())
-                .and_then(|id| SymbolId::new(id as u32))
-                .ok_or(StorageError::InvalidFieldValue {
-                    field: "to_symbol_id".to_string(),
-                    reason: "not a valid u32".to_string(),
-                })?;
+    /// Visit every stored symbol without collecting them into memory
+    ///
+    /// Symbols are visited in id order. Only their ids are held in memory;
+    /// documents are loaded one at a time, so the callback can stream symbols
+    /// to a writer regardless of index size.
+    pub fn for_each_symbol<F>(&self, mut visit: F) -> StorageResult<()>
+    where
+        F: FnMut(crate::Symbol) -> StorageResult<()>,
+    {
+        let searcher = self.reader.searcher();
+        let query = TermQuery::new(
+            Term::from_field_text(self.schema.doc_type, "symbol"),
+            IndexRecordOption::Basic,
+        );
 
-            let kind_str = doc
-                .get_first(self.schema.relation_kind)
-                .and_then(|v| v.as_str())
-                .ok_or(StorageError::InvalidFieldValue {
-                    field: "relation_kind".to_string(),
-                    reason: "missing from document".to_string(),
-                })?;
+        let ids = searcher
+            .segment_readers()
+            .iter()
+            .map(|segment| {
+                Ok(segment
+                    .fast_fields()
+                    .u64("symbol_id")?
+                    .first_or_default_col(0))
+            })
+            .collect::<StorageResult<Vec<_>>>()?;
+        let mut doc_addresses: Vec<_> = searcher
+            .search(&query, &tantivy::collector::DocSetCollector)?
+            .into_iter()
+            .collect();
+        doc_addresses
+            .sort_by_key(|address| ids[address.segment_ord as usize].get_val(address.doc_id));
 
-            let weight = doc
-                .get_first(self.schema.relation_weight)
-                .and_then(|v| v.as_f64())
-                .unwrap_or(1.0) as f32;
-
-            // Parse RelationKind from string
-            let kind = match kind_str {
-                "Calls" => RelationKind::Calls,
-                "CalledBy" => RelationKind::CalledBy,
-                "Extends" => RelationKind::Extends,
-                "ExtendedBy" => RelationKind::ExtendedBy,
-                "Implements" => RelationKind::Implements,
-                "ImplementedBy" => RelationKind::ImplementedBy,
-                "Uses" => RelationKind::Uses,
-                "UsedBy" => RelationKind::UsedBy,
-                "Defines" => RelationKind::Defines,
-                "DefinedIn" => RelationKind::DefinedIn,
-                "References" => RelationKind::References,
-                "ReferencedBy" => RelationKind::ReferencedBy,
-                _ => continue, // Skip unknown relation kinds
-            };
+        for doc_address in doc_addresses {
+            let doc = searcher.doc::<Document>(doc_address)?;
+            visit(self.document_to_symbol(&doc)?)?;
+        }
 
-            let mut relationship = Relationship::new(kind).with_weight(weight);
+        Ok(())
+    }
 
-            // Check for metadata
-            let has_metadata = doc.get_first(self.schema.relation_line).is_some()
-                || doc.get_first(self.schema.relation_column).is_some()
-                || doc.get_first(self.schema.relation_context).is_some();
+    /// Visit every stored relationship without collecting them into memory
+    ///
+    /// Relationships are visited ordered by source id, target id and kind.
+    pub fn for_each_relationship<F>(&self, mut visit: F) -> StorageResult<()>
+    where
+        F: FnMut(SymbolId, SymbolId, Relationship) -> StorageResult<()>,
+    {
+        let searcher = self.reader.searcher();
+        let query = TermQuery::new(
+            Term::from_field_text(self.schema.doc_type, "relationship"),
+            IndexRecordOption::Basic,
+        );
 
-            if has_metadata {
-                let mut metadata = RelationshipMetadata::new();
+        let columns = searcher
+            .segment_readers()
+            .iter()
+            .map(|segment| {
+                let fast_fields = segment.fast_fields();
+                Ok((
+                    fast_fields.u64("from_symbol_id")?.first_or_default_col(0),
+                    fast_fields.u64("to_symbol_id")?.first_or_default_col(0),
+                    fast_fields.str("relation_kind")?,
+                ))
+            })
+            .collect::<StorageResult<Vec<_>>>()?;
+        let mut keyed = Vec::new();
+        for address in searcher.search(&query, &tantivy::collector::DocSetCollector)? {
+            let (from, to, kinds) = &columns[address.segment_ord as usize];
+            let mut kind = String::new();
+            let kind_ord = kinds
+                .as_ref()
+                .and_then(|kinds| Some((kinds, kinds.term_ords(address.doc_id).next()?)));
+            if let Some((kinds, ord)) = kind_ord {
+                kinds.ord_to_str(ord, &mut kind)?;
+            }
+            keyed.push((
+                (
+                    from.get_val(address.doc_id),
+                    to.get_val(address.doc_id),
+                    kind,
+                ),
+                address,
+            ));
+        }
+        keyed.sort();
 
-                if let Some(line) = doc
-                    .get_first(self.schema.relation_line)
-                    .and_then(|v| v.as_u64())
-                {
-                    metadata.line = Some(line as u32);
-                }
-                if let Some(column) = doc
-                    .get_first(self.schema.relation_column)
-                    .and_then(|v| v.as_u64())
-                {
-                    metadata.column = Some(column as u16);
-                }
-                if let Some(context) = doc
-                    .get_first(self.schema.relation_context)
-                    .and_then(|v| v.as_str())
-                {
-                    metadata.context = Some(context.into());
-                }
+        for (_, doc_address) in keyed {
+            let doc = searcher.doc::<Document>(doc_address)?;
+            if let Some((from_id, to_id, relationship)) = self.document_to_relationship(&doc)? {
+                visit(from_id, to_id, relationship)?;
+            }
+        }
 
-                relationship = relationship.with_metadata(metadata);
+        Ok(())
+    }
+
+    /// Convert a Tantivy document to a relationship, skipping unknown kinds
+    fn document_to_relationship(
+        &self,
+        doc: &Document,
+    ) -> StorageResult<Option<(SymbolId, SymbolId, Relationship)>> {
+        let from_id = doc
+            .get_first(self.schema.from_symbol_id)
+            .and_then(|v| v.as_u64())
+            .and_then(|id| SymbolId::new(id as u32))
+            .ok_or(StorageError::InvalidFieldValue {
+                field: "from_symbol_id".to_string(),
+                reason: "not a valid u32".to_string(),
+            })?;
+
+        let to_id = doc
+            .get_first(self.schema.to_symbol_id)
+            .and_then(|v| v.as_u64())
+            .and_then(|id| SymbolId::new(id as u32))
+            .ok_or(StorageError::InvalidFieldValue {
+                field: "to_symbol_id".to_string(),
+                reason: "not a valid u32".to_string(),
+            })?;
+
+        let kind_str = doc
+            .get_first(self.schema.relation_kind)
+            .and_then(|v| v.as_str())
+            .ok_or(StorageError::InvalidFieldValue {
+                field: "relation_kind".to_string(),
+                reason: "missing from document".to_string(),
+            })?;
+
+        let weight = doc
+            .get_first(self.schema.relation_weight)
+            .and_then(|v| v.as_f64())
+            .unwrap_or(1.0) as f32;
+
+        // Parse RelationKind from string
+        let kind = match kind_str {
+            "Calls" => RelationKind::Calls,
+            "CalledBy" => RelationKind::CalledBy,
+            "Extends" => RelationKind::Extends,
+            "ExtendedBy" => RelationKind::ExtendedBy,
+            "Implements" => RelationKind::Implements,
+            "ImplementedBy" => RelationKind::ImplementedBy,
+            "Uses" => RelationKind::Uses,
+            "UsedBy" => RelationKind::UsedBy,
+            "Defines" => RelationKind::Defines,
+            "DefinedIn" => RelationKind::DefinedIn,
+            "References" => RelationKind::References,
+            "ReferencedBy" => RelationKind::ReferencedBy,
+            _ => return Ok(None), // Skip unknown relation kinds
+        };
+
+        let mut relationship = Relationship::new(kind)
+            .with_weight(weight)
+            .with_provenance(self.stored_provenance(&doc));
+
+        // Check for metadata
+        let has_metadata = doc.get_first(self.schema.relation_line).is_some()
+            || doc.get_first(self.schema.relation_column).is_some()
+            || doc.get_first(self.schema.relation_context).is_some();
+
+        if has_metadata {
+            let mut metadata = RelationshipMetadata::new();
+
+            if let Some(line) = doc
+                .get_first(self.schema.relation_line)
+                .and_then(|v| v.as_u64())
+            {
+                metadata.line = Some(line as u32);
+            }
+            if let Some(column) = doc
+                .get_first(self.schema.relation_column)
+                .and_then(|v| v.as_u64())
+            {
+                metadata.column = Some(column as u16);
+            }
+            if let Some(context) = doc
+                .get_first(self.schema.relation_context)
+                .and_then(|v| v.as_str())
+            {
+                metadata = metadata.with_stored_context(context);
             }
 
-            relationships.push((from_id, to_id, relationship));
+            relationship = relationship.with_metadata(metadata);
         }
 
-        Ok(relationships)
+        Ok(Some((from_id, to_id, relationship)))
+    }
+
+    /// Provenance stored on a relationship document
+    ///
+    /// Documents written before provenance was recorded count as resolved.
+    fn stored_provenance(&self, doc: &Document) -> Provenance {
+        doc.get_first(self.schema.relation_provenance)
+            .and_then(|v| v.as_str())
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_default()
     }
 
     /// Query all file information from the index
-    #[allow(dead_code)]
     pub(crate) fn query_file_info(&self) -> StorageResult<Vec<(FileId, String, String, u64)>> {
         let searcher = self.reader.searcher();
         let query = TermQuery::new(
@@ -2613,6 +3556,101 @@ mod tests {
         assert!(field_entry.is_stored(), "Language field should be stored");
     }
 
+    #[test]
+    fn test_writer_thread_count() {
+        // Follows parallel_threads when the heap allows it
+        assert_eq!(writer_thread_count(2, 100_000_000), 2);
+        // Bounded by the heap budget per thread
+        assert_eq!(writer_thread_count(8, 50_000_000), 3);
+        // Bounded by Tantivy's maximum
+        assert_eq!(writer_thread_count(32, 1_000_000_000), 8);
+        // Always at least one thread
+        assert_eq!(writer_thread_count(0, 10_000_000), 1);
+    }
+
+    #[test]
+    fn test_retain_min_score() {
+        let result = |score: f32| SearchResult {
+            symbol_id: SymbolId::new(1).unwrap(),
+            name: "parse".to_string(),
+            kind: SymbolKind::Function,
+            file_path: "src/lib.rs".to_string(),
+            line: 1,
+            column: 0,
+            doc_comment: None,
+            signature: None,
+            module_path: String::new(),
+            attributes: Vec::new(),
+            score,
+            highlights: Vec::new(),
+            context: None,
+            source: SearchSource::FullText,
+        };
+        let mut results = vec![result(12.5), result(3.0), result(1.0)];
+
+        assert_eq!(retain_min_score(&mut results, 3.0), 1);
+        assert_eq!(results.len(), 2);
+        assert_eq!(retain_min_score(&mut results, 0.0), 0);
+    }
+
+    #[test]
+    fn test_group_results() {
+        let result = |file: &str, module: &str, kind: SymbolKind| SearchResult {
+            symbol_id: SymbolId::new(1).unwrap(),
+            name: "handler".to_string(),
+            kind,
+            file_path: file.to_string(),
+            line: 1,
+            column: 0,
+            doc_comment: None,
+            signature: None,
+            module_path: module.to_string(),
+            attributes: Vec::new(),
+            score: 1.0,
+            highlights: Vec::new(),
+            context: None,
+            source: SearchSource::FullText,
+        };
+        let results = vec![
+            result("src/b.rs", "crate::b", SymbolKind::Function),
+            result("src/a.rs", "crate::a", SymbolKind::Method),
+            result("src/b.rs", "crate::b", SymbolKind::Function),
+            result("src/c.rs", "", SymbolKind::Function),
+        ];
+        let counts = |group_by| -> Vec<(String, usize)> {
+            group_results(&results, group_by)
+                .into_iter()
+                .map(|group| (group.key, group.count))
+                .collect()
+        };
+
+        assert_eq!(
+            counts(SearchGroupBy::File),
+            vec![
+                ("src/b.rs".to_string(), 2),
+                ("src/a.rs".to_string(), 1),
+                ("src/c.rs".to_string(), 1)
+            ]
+        );
+        assert_eq!(
+            counts(SearchGroupBy::Module),
+            vec![
+                ("crate::b".to_string(), 2),
+                ("(no module)".to_string(), 1),
+                ("crate::a".to_string(), 1)
+            ]
+        );
+        assert_eq!(
+            counts(SearchGroupBy::Kind),
+            vec![("Function".to_string(), 3), ("Method".to_string(), 1)]
+        );
+        assert_eq!(
+            SearchGroupBy::parse_filter(" Module"),
+            Ok(SearchGroupBy::Module)
+        );
+        assert!(SearchGroupBy::parse_filter("line").is_err());
+    }
+
     #[test]
     fn test_add_and_search_document() {
         let temp_dir = TempDir::new().unwrap();
@@ -2659,6 +3697,217 @@ mod tests {
         assert_eq!(result.file_path, "src/parser.rs");
     }
 
+    #[test]
+    fn test_read_only_index_queries_without_writer() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings = crate::config::Settings::default();
+
+        let missing = DocumentIndex::open_read_only(temp_dir.path().join("none"), &settings);
+        assert!(missing.is_err());
+        assert!(!temp_dir.path().join("none").exists());
+
+        let index = DocumentIndex::new(temp_dir.path(), &settings).unwrap();
+        index.start_batch().unwrap();
+        index
+            .add_document(
+                SymbolId::new(1).unwrap(),
+                "parse_json",
+                SymbolKind::Function,
+                FileId::new(1).unwrap(),
+                "src/parser.rs",
+                42,
+                5,
+                50,
+                0,
+                None,
+                None,
+                "crate::parser",
+                None,
+                crate::Visibility::Public,
+                Some(crate::ScopeContext::Module),
+                None,
+            )
+            .unwrap();
+        index.commit_batch().unwrap();
+
+        // The writable index keeps its writer; a reader must not need the lock
+        index.start_batch().unwrap();
+        let reader = DocumentIndex::open_read_only(temp_dir.path(), &settings).unwrap();
+        assert!(reader.is_read_only());
+
+        let results = reader.search("json", 10, None, None, None).unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(matches!(
+            reader.start_batch(),
+            Err(StorageError::ReadOnly { .. })
+        ));
+        assert!(matches!(reader.clear(), Err(StorageError::ReadOnly { .. })));
+    }
+
+    #[test]
+    fn test_rewrite_field_prefixes() {
+        assert_eq!(
+            rewrite_field_prefixes("name:parse doc:\"error handling\"").as_deref(),
+            Some("name_text:parse doc_comment:\"error handling\"")
+        );
+        assert_eq!(
+            rewrite_field_prefixes("+sig:Result -(doc:deprecated)").as_deref(),
+            Some("+signature:Result -(doc_comment:deprecated)")
+        );
+        // Prefix-like text inside quotes or words is not a field
+        assert_eq!(
+            rewrite_field_prefixes("\"see doc:x\" sig:str").as_deref(),
+            Some("\"see doc:x\" signature:str")
+        );
+        assert_eq!(
+            rewrite_field_prefixes("attribute:test").as_deref(),
+            Some("attribute_names:test")
+        );
+        assert_eq!(rewrite_field_prefixes("parse json"), None);
+        assert_eq!(rewrite_field_prefixes("rename:x"), None);
+        assert_eq!(rewrite_field_prefixes("name::Parser"), None);
+    }
+
+    #[test]
+    fn test_fielded_search() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings = crate::config::Settings::default();
+        let index = DocumentIndex::new(temp_dir.path(), &settings).unwrap();
+
+        index.start_batch().unwrap();
+        let symbols = [
+            (
+                1,
+                "parse_json",
+                "Parse JSON with error handling",
+                "fn parse_json(input: &str)",
+            ),
+            (
+                2,
+                "load_config",
+                "Read the parse settings",
+                "fn load_config() -> Config",
+            ),
+            (
+                3,
+                "report_error",
+                "Print an error",
+                "fn report_error(err: Error)",
+            ),
+        ];
+        for (id, name, doc, sig) in symbols {
+            index
+                .add_document(
+                    SymbolId::new(id).unwrap(),
+                    name,
+                    SymbolKind::Function,
+                    FileId::new(1).unwrap(),
+                    "src/lib.rs",
+                    id,
+                    0,
+                    id,
+                    0,
+                    Some(doc),
+                    Some(sig),
+                    "crate",
+                    None,
+                    crate::Visibility::Public,
+                    Some(crate::ScopeContext::Module),
+                    None,
+                )
+                .unwrap();
+        }
+        let test_symbol = crate::Symbol::new(
+            SymbolId::new(4).unwrap(),
+            "handles_upload",
+            SymbolKind::Function,
+            FileId::new(1).unwrap(),
+            crate::Range::new(4, 0, 8, 1),
+        )
+        .with_attributes(vec!["#[tokio::test]".into(), "#[ignore]".into()]);
+        index.index_symbol(&test_symbol, "src/lib.rs").unwrap();
+        index.commit_batch().unwrap();
+
+        let names = |query: &str| -> Vec<String> {
+            let mut names: Vec<String> = index
+                .search(query, 10, None, None, None)
+                .unwrap()
+                .into_iter()
+                .map(|r| r.name)
+                .collect();
+            names.sort();
+            names
+        };
+
+        // Combined search matches "parse" in names and docs alike
+        assert_eq!(names("parse"), vec!["load_config", "parse_json"]);
+        assert_eq!(names("name:parse"), vec!["parse_json"]);
+        assert_eq!(names("doc:parse"), vec!["load_config", "parse_json"]);
+        assert_eq!(names("sig:Config"), vec!["load_config"]);
+        // Every fielded term is required
+        assert_eq!(names("doc:error sig:Error"), vec!["report_error"]);
+        assert_eq!(names("doc:\"error handling\""), vec!["parse_json"]);
+        // Attribute names match without arguments or delimiters
+        assert_eq!(names("attribute:test"), vec!["handles_upload"]);
+        assert_eq!(names("attribute:\"tokio::test\""), vec!["handles_upload"]);
+        assert_eq!(names("attribute:derive"), Vec::<String>::new());
+        let filtered = |attribute: &str| -> Vec<String> {
+            let filters = SearchFilters {
+                attribute: Some(attribute),
+                ..SearchFilters::default()
+            };
+            index
+                .search_filtered("handles", 10, None, None, None, &filters)
+                .unwrap()
+                .into_iter()
+                .map(|r| r.name)
+                .collect()
+        };
+        assert_eq!(filtered("#[tokio::test]"), vec!["handles_upload"]);
+        assert_eq!(filtered("Tok"), vec!["handles_upload"]);
+        assert_eq!(filtered("tokio::main"), Vec::<String>::new());
+        assert_eq!(filtered("#[]"), Vec::<String>::new());
+        let with_visibility = |query: &str, visibility| -> Vec<String> {
+            let filters = SearchFilters {
+                visibility: Some(visibility),
+                ..SearchFilters::default()
+            };
+            index
+                .search_filtered(query, 10, None, None, None, &filters)
+                .unwrap()
+                .into_iter()
+                .map(|r| r.name)
+                .collect()
+        };
+        assert_eq!(
+            with_visibility("handles", crate::Visibility::Private),
+            vec!["handles_upload"]
+        );
+        assert!(with_visibility("handles", crate::Visibility::Public).is_empty());
+        let in_files = |files: &[&str]| -> usize {
+            let files = files.iter().map(|path| path.to_string()).collect();
+            let filters = SearchFilters {
+                files: Some(&files),
+                ..SearchFilters::default()
+            };
+            index
+                .search_filtered("handles", 10, None, None, None, &filters)
+                .unwrap()
+                .len()
+        };
+        assert_eq!(in_files(&["src/lib.rs", "src/main.rs"]), 1);
+        assert_eq!(in_files(&["src/main.rs"]), 0);
+        assert_eq!(in_files(&[]), 0);
+        assert_eq!(
+            index
+                .find_symbol_by_id(SymbolId::new(4).unwrap())
+                .unwrap()
+                .unwrap()
+                .attributes,
+            test_symbol.attributes
+        );
+    }
+
     #[test]
     fn test_store_and_retrieve_symbol_with_language() {
         use crate::parsing::registry::LanguageId;
@@ -1,13 +1,14 @@
 //! Symbol context aggregation for comprehensive metadata display
 
-use crate::relationship::RelationshipMetadata;
-use crate::{Symbol, Visibility};
+use crate::paths::display_path;
+use crate::relationship::{Provenance, RelationKind, RelationshipMetadata};
+use crate::{Symbol, SymbolId, Visibility};
 use bitflags::bitflags;
 use serde::Serialize;
 use std::fmt;
 
 /// Comprehensive context for a symbol including all relationships
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
 pub struct SymbolContext {
     /// The symbol itself with all its metadata
     pub symbol: Symbol,
@@ -18,18 +19,171 @@ pub struct SymbolContext {
 }
 
 /// Container for all types of symbol relationships
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, Serialize, schemars::JsonSchema)]
 pub struct SymbolRelationships {
+    /// The symbol this one is nested in (type owning a method, function owning a local)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<Symbol>,
     /// What traits this type implements
     pub implements: Option<Vec<Symbol>>,
     /// What types implement this trait
     pub implemented_by: Option<Vec<Symbol>>,
+    /// Entries of `implements` or `implemented_by` that come from a derive
+    /// attribute rather than an impl block
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub derived: Vec<SymbolId>,
+    /// Base classes this type extends, in declaration order
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub extends: Vec<Symbol>,
+    /// Types that extend this one directly
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub extended_by: Vec<Symbol>,
     /// What methods/fields this symbol defines
     pub defines: Option<Vec<Symbol>>,
-    /// What this symbol calls (with relationship metadata including call site location)
-    pub calls: Option<Vec<(Symbol, Option<RelationshipMetadata>)>>,
-    /// What calls this symbol (with relationship metadata including call site location)
-    pub called_by: Option<Vec<(Symbol, Option<RelationshipMetadata>)>>,
+    /// What this symbol calls (with relationship metadata including call site location,
+    /// and how the call was resolved)
+    pub calls: Option<Vec<(Symbol, Option<RelationshipMetadata>, Provenance)>>,
+    /// What calls this symbol (with relationship metadata including call site location,
+    /// and how the call was resolved)
+    pub called_by: Option<Vec<(Symbol, Option<RelationshipMetadata>, Provenance)>>,
+    /// Callees expanded past direct calls (`retrieve describe --depth`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub call_tree: Option<CallTree>,
+    /// Callers expanded past direct callers (`retrieve describe --depth`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub caller_tree: Option<CallTree>,
+}
+
+/// Which way a [`CallTree`] follows call relationships
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallDirection {
+    /// Symbols that call the root, then their callers
+    Callers,
+    /// Symbols the root calls, then what they call
+    Callees,
+}
+
+/// Callers or callees of a symbol, expanded to a fixed depth
+///
+/// Each symbol appears once, at the shallowest level it was reached.
+#[derive(Debug, Clone, Default, Serialize, schemars::JsonSchema)]
+pub struct CallTree {
+    /// Number of levels walked
+    pub depth: usize,
+    /// Directly related symbols, each with its own related symbols
+    pub symbols: Vec<CallTreeNode>,
+    /// Related symbols left out at this level by the fan-out cap
+    pub truncated: usize,
+}
+
+/// One symbol in a [`CallTree`]
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+pub struct CallTreeNode {
+    pub symbol: Symbol,
+    /// Symbols one level further in the same direction
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub symbols: Vec<CallTreeNode>,
+    /// Related symbols left out by the fan-out cap
+    #[serde(skip_serializing_if = "is_zero")]
+    pub truncated: usize,
+    /// Already expanded earlier in the tree (or a cycle back to an ancestor),
+    /// so its children are not repeated here
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub repeated: bool,
+}
+
+fn is_zero(n: &usize) -> bool {
+    *n == 0
+}
+
+impl CallTree {
+    /// Whether the fan-out cap dropped symbols anywhere in the tree
+    pub fn is_truncated(&self) -> bool {
+        fn nodes_truncated(nodes: &[CallTreeNode]) -> bool {
+            nodes
+                .iter()
+                .any(|node| node.truncated > 0 || nodes_truncated(&node.symbols))
+        }
+        self.truncated > 0 || nodes_truncated(&self.symbols)
+    }
+}
+
+/// Which way a [`TypeHierarchy`] follows inheritance relationships
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HierarchyDirection {
+    /// Types the root extends or implements, then what those extend or implement
+    Ancestors,
+    /// Types extending or implementing the root, then theirs
+    Descendants,
+    /// Ancestors and descendants
+    Both,
+}
+
+impl HierarchyDirection {
+    const FILTERS: &'static [(HierarchyDirection, &'static str)] = &[
+        (HierarchyDirection::Ancestors, "ancestors"),
+        (HierarchyDirection::Descendants, "descendants"),
+        (HierarchyDirection::Both, "both"),
+    ];
+
+    /// Parse `ancestors`, `descendants` or `both`, ignoring case
+    pub fn parse_filter(s: &str) -> Result<Self, String> {
+        let needle = s.trim();
+        Self::FILTERS
+            .iter()
+            .find(|(_, name)| name.eq_ignore_ascii_case(needle))
+            .map(|(direction, _)| *direction)
+            .ok_or_else(|| {
+                let accepted: Vec<&str> = Self::FILTERS.iter().map(|(_, name)| *name).collect();
+                format!(
+                    "Unknown direction '{s}'. Accepted values: {}",
+                    accepted.join(", ")
+                )
+            })
+    }
+
+    pub fn includes_ancestors(self) -> bool {
+        self != HierarchyDirection::Descendants
+    }
+
+    pub fn includes_descendants(self) -> bool {
+        self != HierarchyDirection::Ancestors
+    }
+}
+
+/// Ancestors and descendants of a type through `Extends` and `Implements`
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+pub struct TypeHierarchy {
+    pub symbol: Symbol,
+    /// Location as `path:line`
+    pub location: String,
+    /// Number of levels walked in each direction
+    pub depth: usize,
+    /// What the type extends or implements, each with its own ancestors;
+    /// `None` when ancestors were not requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ancestors: Option<Vec<TypeHierarchyNode>>,
+    /// What extends or implements the type, each with its own descendants;
+    /// `None` when descendants were not requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub descendants: Option<Vec<TypeHierarchyNode>>,
+}
+
+/// One type in a [`TypeHierarchy`]
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+pub struct TypeHierarchyNode {
+    pub symbol: Symbol,
+    /// Location as `path:line`
+    pub location: String,
+    /// `Extends` or `Implements`, read from the descendant to the ancestor
+    pub relation: RelationKind,
+    /// Types one level further in the same direction
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub types: Vec<TypeHierarchyNode>,
+    /// Already expanded earlier in the hierarchy (or a cycle back to an
+    /// ancestor), so its children are not repeated here
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub repeated: bool,
 }
 
 bitflags! {
@@ -115,6 +269,14 @@ impl SymbolContext {
             ));
         }
 
+        if !self.symbol.attributes.is_empty() {
+            output.push_str(&format!(
+                "{}Attributes: {}\n",
+                indent,
+                self.symbol.attributes.join(" ")
+            ));
+        }
+
         // Documentation preview
         if let Some(doc) = self.symbol.as_doc_comment() {
             let preview: Vec<&str> = doc.lines().take(2).collect();
@@ -129,17 +291,29 @@ impl SymbolContext {
     }
 
     fn append_relationships(&self, output: &mut String, indent: &str) {
+        if let Some(parent) = &self.relationships.parent {
+            output.push_str(&format!(
+                "{}Parent: {} ({:?}) at {} [symbol_id:{}]\n",
+                indent,
+                parent.name,
+                parent.kind,
+                SymbolContext::symbol_location(parent),
+                parent.id.value()
+            ));
+        }
+
         // Implementations
         if let Some(impls) = &self.relationships.implements {
             if !impls.is_empty() {
                 output.push_str(&format!("{indent}Implements:\n"));
                 for symbol in impls {
                     output.push_str(&format!(
-                        "{}  - {} ({:?}) at {}\n",
+                        "{}  - {} ({:?}) at {}{}\n",
                         indent,
                         symbol.name,
                         symbol.kind,
-                        SymbolContext::symbol_location(symbol)
+                        SymbolContext::symbol_location(symbol),
+                        self.derived_marker(symbol.id)
                     ));
                 }
             }
@@ -155,16 +329,47 @@ impl SymbolContext {
                 ));
                 for impl_type in impl_by {
                     output.push_str(&format!(
-                        "{}  - {} ({:?}) at {}\n",
+                        "{}  - {} ({:?}) at {}{}\n",
                         indent,
                         impl_type.name,
                         impl_type.kind,
-                        SymbolContext::symbol_location(impl_type)
+                        SymbolContext::symbol_location(impl_type),
+                        self.derived_marker(impl_type.id)
                     ));
                 }
             }
         }
 
+        // Inheritance
+        if !self.relationships.extends.is_empty() {
+            output.push_str(&format!("{indent}Extends:\n"));
+            for base in &self.relationships.extends {
+                output.push_str(&format!(
+                    "{}  - {} ({:?}) at {}\n",
+                    indent,
+                    base.name,
+                    base.kind,
+                    SymbolContext::symbol_location(base)
+                ));
+            }
+        }
+        if !self.relationships.extended_by.is_empty() {
+            output.push_str(&format!(
+                "{}Extended by {} symbol(s):\n",
+                indent,
+                self.relationships.extended_by.len()
+            ));
+            for subclass in &self.relationships.extended_by {
+                output.push_str(&format!(
+                    "{}  - {} ({:?}) at {}\n",
+                    indent,
+                    subclass.name,
+                    subclass.kind,
+                    SymbolContext::symbol_location(subclass)
+                ));
+            }
+        }
+
         // Methods defined
         if let Some(defines) = &self.relationships.defines {
             if !defines.is_empty() {
@@ -187,14 +392,20 @@ impl SymbolContext {
         }
 
         // Calls
-        if let Some(calls) = &self.relationships.calls {
+        if let Some(tree) = &self.relationships.call_tree {
+            Self::append_call_tree(output, indent, "Calls", tree);
+        } else if let Some(calls) = &self.relationships.calls {
             if !calls.is_empty() {
                 output.push_str(&format!("{}Calls {} function(s):\n", indent, calls.len()));
-                for (called, metadata) in calls {
+                for (called, metadata, provenance) in calls {
                     // Use call site location from metadata if available, otherwise definition location
                     let location = if let Some(meta) = metadata {
                         if let Some(line) = meta.line {
-                            format!("{}:{}", called.file_path, line.saturating_add(1))
+                            format!(
+                                "{}:{}",
+                                display_path(&called.file_path),
+                                line.saturating_add(1)
+                            )
                         } else {
                             Self::symbol_location(called)
                         }
@@ -213,30 +424,39 @@ impl SymbolContext {
 
                     // Show receiver info if available
                     if let Some(meta) = metadata {
-                        if let Some(context) = &meta.context {
+                        if let Some(call) = &meta.call {
+                            output.push_str(&format!(" [{call}]"));
+                        } else if let Some(context) = &meta.context {
                             if !context.is_empty() {
                                 output.push_str(&format!(" [{context}]"));
                             }
                         }
                     }
+                    Self::append_provenance(output, *provenance);
                     output.push('\n');
                 }
             }
         }
 
         // Called by
-        if let Some(callers) = &self.relationships.called_by {
+        if let Some(tree) = &self.relationships.caller_tree {
+            Self::append_call_tree(output, indent, "Called by", tree);
+        } else if let Some(callers) = &self.relationships.called_by {
             if !callers.is_empty() {
                 output.push_str(&format!(
                     "{}Called by {} function(s):\n",
                     indent,
                     callers.len()
                 ));
-                for (caller, metadata) in callers {
+                for (caller, metadata, provenance) in callers {
                     // Use call site location from metadata if available, otherwise definition location
                     let location = if let Some(meta) = metadata {
                         if let Some(line) = meta.line {
-                            format!("{}:{}", caller.file_path, line.saturating_add(1))
+                            format!(
+                                "{}:{}",
+                                display_path(&caller.file_path),
+                                line.saturating_add(1)
+                            )
                         } else {
                             Self::symbol_location(caller)
                         }
@@ -255,25 +475,73 @@ impl SymbolContext {
 
                     // Show receiver info if available
                     if let Some(meta) = metadata {
-                        if let Some(context) = &meta.context {
+                        if let Some(call) = &meta.call {
+                            output.push_str(&format!(" [{call}]"));
+                        } else if let Some(context) = &meta.context {
                             if !context.is_empty() {
                                 output.push_str(&format!(" [{context}]"));
                             }
                         }
                     }
+                    Self::append_provenance(output, *provenance);
                     output.push('\n');
                 }
             }
         }
     }
 
+    fn append_call_tree(output: &mut String, indent: &str, title: &str, tree: &CallTree) {
+        output.push_str(&format!("{indent}{title} (depth {}):\n", tree.depth));
+        Self::append_call_tree_nodes(
+            output,
+            &format!("{indent}  "),
+            &tree.symbols,
+            tree.truncated,
+        );
+    }
+
+    fn append_call_tree_nodes(
+        output: &mut String,
+        indent: &str,
+        nodes: &[CallTreeNode],
+        truncated: usize,
+    ) {
+        for node in nodes {
+            output.push_str(&format!(
+                "{}- {} ({:?}) at {} [symbol_id:{}]\n",
+                indent,
+                node.symbol.name,
+                node.symbol.kind,
+                Self::symbol_location(&node.symbol),
+                node.symbol.id.value()
+            ));
+            Self::append_call_tree_nodes(
+                output,
+                &format!("{indent}  "),
+                &node.symbols,
+                node.truncated,
+            );
+        }
+        if truncated > 0 {
+            output.push_str(&format!("{indent}... and {truncated} more (truncated)\n"));
+        }
+    }
+
+    /// Mark edges that resolution did not pin down; resolved edges stay unmarked
+    fn append_provenance(output: &mut String, provenance: Provenance) {
+        if provenance != Provenance::Resolved {
+            output.push_str(&format!(" ({provenance})"));
+        }
+    }
+
     pub(crate) fn symbol_location(symbol: &Symbol) -> String {
         let start = symbol.range.start_line.saturating_add(1);
         let end = symbol.range.end_line.saturating_add(1);
+        let path = display_path(&symbol.file_path);
         if start == end {
-            format!("{}:{start}", symbol.file_path)
+            format!("{path}:{start}")
         } else {
-            format!("{}:{start}-{end}", symbol.file_path)
+            format!("{path}:{start}-{end}")
         }
     }
 }
@@ -287,4 +555,12 @@ impl SymbolContext {
             output.push('\n');
         }
     }
+
+    fn derived_marker(&self, id: SymbolId) -> &'static str {
+        if self.relationships.derived.contains(&id) {
+            " (derived)"
+        } else {
+            ""
+        }
+    }
 }
@@ -1,12 +1,13 @@
 pub mod context;
 
+use crate::indexing::entrypoints::attribute_path;
 use crate::parsing::registry::LanguageId;
 use crate::types::{CompactString, FileId, Range, SymbolId, SymbolKind, compact_string};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Visibility of a symbol
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
 pub enum Visibility {
     /// Public visibility (pub)
     Public,
@@ -18,11 +19,44 @@ pub enum Visibility {
     Private,
 }
 
+impl Visibility {
+    /// Names accepted by [`parse_filter`](Self::parse_filter), with aliases
+    const FILTERS: &[(Visibility, &[&str])] = &[
+        (Visibility::Public, &["public"]),
+        (Visibility::Crate, &["crate"]),
+        (Visibility::Module, &["module", "protected"]),
+        (Visibility::Private, &["private"]),
+    ];
+
+    /// Parse a visibility filter such as `private`, ignoring case.
+    ///
+    /// `protected` is accepted for `Module`, which is how TypeScript records
+    /// protected members. Unknown values produce an error listing every
+    /// accepted name.
+    pub fn parse_filter(s: &str) -> Result<Self, String> {
+        let needle = s.trim();
+        Self::FILTERS
+            .iter()
+            .find(|(_, names)| names.iter().any(|name| name.eq_ignore_ascii_case(needle)))
+            .map(|(visibility, _)| *visibility)
+            .ok_or_else(|| {
+                let accepted: Vec<&str> = Self::FILTERS
+                    .iter()
+                    .flat_map(|(_, names)| names.iter().copied())
+                    .collect();
+                format!(
+                    "Unknown visibility '{s}'. Accepted values: {}",
+                    accepted.join(", ")
+                )
+            })
+    }
+}
+
 /// Scope context for symbol definition
 ///
 /// This enum represents where a symbol is defined in the code structure,
 /// enabling proper resolution without heuristics.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default, schemars::JsonSchema)]
 pub enum ScopeContext {
     /// Local to function/method/block
     Local {
@@ -46,7 +80,7 @@ pub enum ScopeContext {
     Global,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct Symbol {
     pub id: SymbolId,
     pub name: CompactString,
@@ -54,6 +88,8 @@ pub struct Symbol {
     pub file_id: FileId,
     pub range: Range,
     /// Clean file path without line numbers (e.g., "src/lib.rs")
+    #[serde(serialize_with = "crate::paths::serialize_display_path")]
+    #[schemars(with = "String")]
     pub file_path: Box<str>,
     pub signature: Option<Box<str>>,
     /// Documentation comment extracted from source (e.g., /// or /** */ in Rust)
@@ -67,6 +103,15 @@ pub struct Symbol {
     /// This field enables proper resolution without heuristics.
     /// It's Optional during migration - will become required in future.
     pub scope_context: Option<ScopeContext>,
+    /// Innermost symbol this one is nested in: the type owning a method
+    /// (including Rust `impl` blocks), the function owning a local, or the
+    /// module owning an item. `None` at file level.
+    pub parent: Option<SymbolId>,
+    /// Attributes, decorators or annotations attached to the symbol
+    /// (`#[test]`, `@app.route("/")`) in source order, each on one line with
+    /// its whitespace collapsed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<Box<str>>,
     /// Language identifier for the symbol
     ///
     /// This field enables language-specific filtering in searches.
@@ -86,7 +131,9 @@ pub struct CompactSymbol {
     pub end_line: u32,
     pub end_col: u16,
     pub symbol_id: u32,
-    _padding: [u8; 2],
+    /// String table offset of the attribute lines joined by `\n`; 0 (the
+    /// empty string) when the symbol has none
+    pub attributes_offset: u32,
 }
 
 impl Symbol {
@@ -109,7 +156,9 @@ impl Symbol {
             module_path: None,
             visibility: Visibility::Private,
             scope_context: None, // Default to None for backward compatibility
-            language_id: None,   // Default to None for backward compatibility
+            parent: None,
+            attributes: Vec::new(),
+            language_id: None, // Default to None for backward compatibility
         }
     }
 
@@ -132,6 +181,11 @@ impl Symbol {
         self
     }
 
+    pub fn with_parent(mut self, parent: SymbolId) -> Self {
+        self.parent = Some(parent);
+        self
+    }
+
     pub fn with_signature(mut self, signature: impl Into<Box<str>>) -> Self {
         self.signature = Some(signature.into());
         self
@@ -157,6 +211,11 @@ impl Symbol {
         self
     }
 
+    pub fn with_attributes(mut self, attributes: Vec<Box<str>>) -> Self {
+        self.attributes = attributes;
+        self
+    }
+
     pub fn with_language_id(mut self, language_id: LanguageId) -> Self {
         self.language_id = Some(language_id);
         self
@@ -182,6 +241,14 @@ impl Symbol {
         self.doc_comment.as_deref()
     }
 
+    /// Names of the symbol's attributes, see
+    /// [`attribute_path`](crate::indexing::entrypoints::attribute_path)
+    pub fn attribute_names(&self) -> impl Iterator<Item = &str> {
+        self.attributes
+            .iter()
+            .map(|attribute| attribute_path(attribute))
+    }
+
     /// Get a reference to the module path if present
     pub fn as_module_path(&self) -> Option<&str> {
         self.module_path.as_deref()
@@ -189,6 +256,11 @@ impl Symbol {
 
     pub fn to_compact(&self, string_table: &mut StringTable) -> CompactSymbol {
         let name_offset = string_table.intern(&self.name);
+        let attributes_offset = if self.attributes.is_empty() {
+            0
+        } else {
+            string_table.intern(&self.attributes.join("\n"))
+        };
 
         CompactSymbol {
             name_offset,
@@ -200,7 +272,7 @@ impl Symbol {
             end_line: self.range.end_line,
             end_col: self.range.end_column,
             symbol_id: self.id.value(),
-            _padding: [0; 2],
+            attributes_offset,
         }
     }
 }
@@ -229,6 +301,10 @@ impl fmt::Display for Symbol {
             write!(f, "\n  Module: {module}")?;
         }
 
+        if !self.attributes.is_empty() {
+            write!(f, "\n  Attributes: {}", self.attributes.join(" "))?;
+        }
+
         if let Some(doc) = &self.doc_comment {
             let truncated = if doc.len() > 100 {
                 format!("{}...", &doc[..100])
@@ -242,6 +318,43 @@ impl fmt::Display for Symbol {
     }
 }
 
+/// Whether an attribute line matches an `attribute` filter: the words of its
+/// name contain the filter's words in order, the last one possibly cut short,
+/// ignoring case. This is how search queries match the tokenized attribute
+/// names. The filter may carry its own delimiters, so `deprecated`,
+/// `#[deprecated]` and `@Deprecated` all match `#[deprecated(since = "0.2")]`,
+/// and `tokio` matches `#[tokio::main]`.
+pub fn attribute_matches(attribute: &str, filter: &str) -> bool {
+    let words = |name: &str| -> Vec<String> {
+        name.split(|ch: char| !ch.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_lowercase)
+            .collect()
+    };
+    let filter = words(attribute_path(filter.trim()));
+    let name = words(attribute_path(attribute));
+    let Some((last, leading)) = filter.split_last() else {
+        return false;
+    };
+    name.windows(filter.len()).any(|window| {
+        window[..leading.len()] == *leading && window[leading.len()].starts_with(last.as_str())
+    })
+}
+
+/// Trait paths listed in a `#[derive(...)]` attribute, empty for other attributes
+pub fn derived_traits(attribute: &str) -> impl Iterator<Item = &str> {
+    attribute
+        .strip_prefix("#[")
+        .and_then(|inner| inner.strip_suffix(']'))
+        .and_then(|inner| inner.trim().strip_prefix("derive"))
+        .and_then(|args| args.trim_start().strip_prefix('('))
+        .and_then(|args| args.trim_end().strip_suffix(')'))
+        .into_iter()
+        .flat_map(|args| args.split(','))
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+}
+
 pub struct StringTable {
     data: Vec<u8>,
     offsets: std::collections::HashMap<String, u32>,
@@ -286,11 +399,44 @@ impl StringTable {
 
         std::str::from_utf8(&self.data[start..end]).ok()
     }
+
+    /// Offset of an already interned string, without interning it
+    pub fn lookup(&self, s: &str) -> Option<u32> {
+        self.offsets.get(s).copied()
+    }
+
+    /// Raw null-terminated string data, as written to disk
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Rebuild a table from data produced by [`StringTable::as_bytes`]
+    pub fn from_bytes(data: Vec<u8>) -> Option<Self> {
+        if data.first() != Some(&0) || data.last() != Some(&0) {
+            return None;
+        }
+
+        let mut offsets = std::collections::HashMap::new();
+        let mut start = 1;
+        while start < data.len() {
+            let end = start + data[start..].iter().position(|&b| b == 0)?;
+            let s = std::str::from_utf8(&data[start..end]).ok()?;
+            offsets.insert(s.to_string(), start as u32);
+            start = end + 1;
+        }
+
+        Some(Self { data, offsets })
+    }
 }
 
 impl CompactSymbol {
     pub fn from_symbol(symbol: &Symbol, string_table: &StringTable) -> Option<Self> {
         let name_offset = string_table.offsets.get(symbol.name.as_ref())?;
+        let attributes_offset = if symbol.attributes.is_empty() {
+            0
+        } else {
+            string_table.lookup(&symbol.attributes.join("\n"))?
+        };
 
         Some(CompactSymbol {
             name_offset: *name_offset,
@@ -302,12 +448,17 @@ impl CompactSymbol {
             end_line: symbol.range.end_line,
             end_col: symbol.range.end_column,
             symbol_id: symbol.id.value(),
-            _padding: [0; 2],
+            attributes_offset,
         })
     }
 
     pub fn to_symbol(&self, string_table: &StringTable) -> Option<Symbol> {
         let name = string_table.get(self.name_offset)?;
+        let attributes = string_table
+            .get(self.attributes_offset)?
+            .lines()
+            .map(Into::into)
+            .collect();
         let kind = match self.kind {
             0 => SymbolKind::Function,
             1 => SymbolKind::Method,
@@ -323,6 +474,7 @@ impl CompactSymbol {
             11 => SymbolKind::Parameter,
             12 => SymbolKind::TypeAlias,
             13 => SymbolKind::Macro,
+            14 => SymbolKind::EnumVariant,
             _ => return None,
         };
 
@@ -338,7 +490,9 @@ impl CompactSymbol {
             module_path: None,
             visibility: Visibility::Private,
             scope_context: None, // CompactSymbol doesn't store scope info yet
-            language_id: None,   // CompactSymbol doesn't store language info yet
+            parent: None,
+            attributes,
+            language_id: None, // CompactSymbol doesn't store language info yet
         })
     }
 }
@@ -348,6 +502,19 @@ mod tests {
     use super::*;
     use std::mem;
 
+    #[test]
+    fn test_parse_visibility_filter() {
+        assert_eq!(Visibility::parse_filter("Private"), Ok(Visibility::Private));
+        assert_eq!(
+            Visibility::parse_filter("protected"),
+            Ok(Visibility::Module)
+        );
+        assert_eq!(
+            Visibility::parse_filter("internal").unwrap_err(),
+            "Unknown visibility 'internal'. Accepted values: public, crate, module, protected, private"
+        );
+    }
+
     #[test]
     fn test_symbol_creation() {
         let id = SymbolId::new(1).unwrap();
@@ -381,6 +548,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_attribute_names() {
+        let symbol = Symbol::new(
+            SymbolId::new(1).unwrap(),
+            "index",
+            SymbolKind::Function,
+            FileId::new(1).unwrap(),
+            Range::new(1, 0, 3, 1),
+        )
+        .with_attributes(vec![
+            "#[tokio::main]".into(),
+            "#![allow(dead_code)]".into(),
+            "@app.route(\"/\")".into(),
+            "[[nodiscard]]".into(),
+            "[HttpGet(\"items\")]".into(),
+        ]);
+
+        assert_eq!(
+            symbol.attribute_names().collect::<Vec<_>>(),
+            vec!["tokio::main", "allow", "app.route", "nodiscard", "HttpGet"]
+        );
+    }
+
+    #[test]
+    fn test_attribute_matches() {
+        assert!(attribute_matches("#[tokio::main]", "tokio::main"));
+        assert!(attribute_matches("#[tokio::main]", "tokio"));
+        assert!(attribute_matches("#[tokio::main]", "Main"));
+        assert!(attribute_matches(
+            "#[deprecated(since = \"0.2\")]",
+            "deprecated"
+        ));
+        assert!(attribute_matches("@Deprecated", "deprecated"));
+        assert!(attribute_matches(
+            "@pytest.fixture(scope=\"module\")",
+            "@pytest.fixture"
+        ));
+        assert!(!attribute_matches("#[test]", "tokio::test"));
+        assert!(!attribute_matches("#[inline]", "deprecated"));
+        assert!(!attribute_matches("#[inline]", ""));
+    }
+
     #[test]
     fn test_compact_symbol_size() {
         assert_eq!(mem::size_of::<CompactSymbol>(), 32);
@@ -404,6 +613,21 @@ mod tests {
         assert_eq!(table.get(999), None);
     }
 
+    #[test]
+    fn test_string_table_round_trip() {
+        let mut table = StringTable::new();
+        let hello = table.intern("hello");
+        let world = table.intern("world");
+
+        let restored = StringTable::from_bytes(table.as_bytes().to_vec()).unwrap();
+        assert_eq!(restored.lookup("hello"), Some(hello));
+        assert_eq!(restored.lookup("world"), Some(world));
+        assert_eq!(restored.lookup("missing"), None);
+        assert_eq!(restored.get(world), Some("world"));
+
+        assert!(StringTable::from_bytes(b"hello".to_vec()).is_none());
+    }
+
     #[test]
     fn test_symbol_to_compact_and_back() {
         let mut string_table = StringTable::new();
@@ -424,6 +648,36 @@ mod tests {
         assert_eq!(original.kind, restored.kind);
         assert_eq!(original.file_id, restored.file_id);
         assert_eq!(original.range, restored.range);
+        assert!(restored.attributes.is_empty());
+    }
+
+    #[test]
+    fn test_compact_symbol_interns_attributes() {
+        let mut string_table = StringTable::new();
+        let attributes: Vec<Box<str>> = vec!["#[test]".into(), "#[ignore]".into()];
+        let symbol = |id, name| {
+            Symbol::new(
+                SymbolId::new(id).unwrap(),
+                name,
+                SymbolKind::Function,
+                FileId::new(1).unwrap(),
+                Range::new(1, 0, 3, 1),
+            )
+            .with_attributes(attributes.clone())
+        };
+
+        let first = symbol(1, "slow_one").to_compact(&mut string_table);
+        let second = symbol(2, "slow_two").to_compact(&mut string_table);
+        assert_ne!(first.attributes_offset, 0);
+        assert_eq!(first.attributes_offset, second.attributes_offset);
+
+        let restored = first.to_symbol(&string_table).unwrap();
+        assert_eq!(restored.attributes, attributes);
+
+        let compact = CompactSymbol::from_symbol(&symbol(3, "slow_one"), &string_table).unwrap();
+        assert_eq!(compact.attributes_offset, first.attributes_offset);
+        let unknown = symbol(4, "slow_one").with_attributes(vec!["#[inline]".into()]);
+        assert!(CompactSymbol::from_symbol(&unknown, &string_table).is_none());
     }
 
     #[test]
@@ -443,6 +697,7 @@ mod tests {
             SymbolKind::Parameter,
             SymbolKind::TypeAlias,
             SymbolKind::Macro,
+            SymbolKind::EnumVariant,
         ];
 
         let mut string_table = StringTable::new();
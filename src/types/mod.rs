@@ -5,10 +5,10 @@ pub use symbol_counter::SymbolCounter;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct SymbolId(pub u32);
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct FileId(pub u32);
 
 /// Result of an indexing operation
@@ -33,7 +33,7 @@ impl IndexingResult {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct Range {
     pub start_line: u32,
     pub start_column: u16,
@@ -41,7 +41,7 @@ pub struct Range {
     pub end_column: u16,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, schemars::JsonSchema)]
 pub enum SymbolKind {
     Function,
     Method,
@@ -57,6 +57,7 @@ pub enum SymbolKind {
     Parameter,
     TypeAlias,
     Macro,
+    EnumVariant,
 }
 
 impl SymbolId {
@@ -135,16 +136,70 @@ impl FromStr for SymbolKind {
             "Parameter" => Ok(SymbolKind::Parameter),
             "TypeAlias" => Ok(SymbolKind::TypeAlias),
             "Macro" => Ok(SymbolKind::Macro),
+            "EnumVariant" => Ok(SymbolKind::EnumVariant),
             _ => Err("Unknown symbol kind"),
         }
     }
 }
 
+/// Names accepted by [`SymbolKind::parse_filter`], canonical name first
+const KIND_FILTERS: &[(SymbolKind, &[&str])] = &[
+    (SymbolKind::Function, &["function", "fn", "func"]),
+    (SymbolKind::Method, &["method"]),
+    (SymbolKind::Struct, &["struct"]),
+    (SymbolKind::Enum, &["enum"]),
+    (SymbolKind::EnumVariant, &["enum_variant", "variant"]),
+    (SymbolKind::Trait, &["trait"]),
+    (SymbolKind::Interface, &["interface", "iface"]),
+    (SymbolKind::Class, &["class", "cls"]),
+    (SymbolKind::Module, &["module", "mod"]),
+    (SymbolKind::Variable, &["variable", "var"]),
+    (SymbolKind::Constant, &["constant", "const"]),
+    (SymbolKind::Field, &["field"]),
+    (SymbolKind::Parameter, &["parameter", "param"]),
+    (SymbolKind::TypeAlias, &["type_alias", "type", "ty"]),
+    (SymbolKind::Macro, &["macro"]),
+];
+
 impl SymbolKind {
     /// Parse from string with a default fallback for unknown values
     pub fn from_str_with_default(s: &str) -> Self {
         s.parse().unwrap_or(SymbolKind::Function)
     }
+
+    /// Parse a user-supplied kind filter such as `function`, `fn` or `Struct`.
+    ///
+    /// Case-insensitive, ignores `_` and `-` (so `EnumVariant` and
+    /// `enum-variant` match) and accepts common aliases (`func`, `cls`,
+    /// `iface`, `const`, `var`, `ty`, ...). Unknown values produce an error
+    /// listing every accepted name.
+    pub fn parse_filter(s: &str) -> Result<Self, String> {
+        let key = |name: &str| {
+            name.chars()
+                .filter(|c| *c != '_' && *c != '-')
+                .collect::<String>()
+                .to_ascii_lowercase()
+        };
+        let needle = key(s.trim());
+        KIND_FILTERS
+            .iter()
+            .find(|(_, names)| names.iter().any(|name| key(name) == needle))
+            .map(|(kind, _)| *kind)
+            .ok_or_else(|| {
+                let accepted: Vec<String> = KIND_FILTERS
+                    .iter()
+                    .map(|(_, names)| match names {
+                        [name] => name.to_string(),
+                        [name, aliases @ ..] => format!("{name} ({})", aliases.join(", ")),
+                        [] => String::new(),
+                    })
+                    .collect();
+                format!(
+                    "Unknown symbol kind '{s}'. Accepted values: {}",
+                    accepted.join(", ")
+                )
+            })
+    }
 }
 
 pub type CompactString = Box<str>;
@@ -157,6 +212,34 @@ pub fn compact_string(s: &str) -> CompactString {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_kind_filter() {
+        assert_eq!(SymbolKind::parse_filter("fn"), Ok(SymbolKind::Function));
+        assert_eq!(SymbolKind::parse_filter("Func"), Ok(SymbolKind::Function));
+        assert_eq!(SymbolKind::parse_filter("cls"), Ok(SymbolKind::Class));
+        assert_eq!(SymbolKind::parse_filter("iface"), Ok(SymbolKind::Interface));
+        assert_eq!(SymbolKind::parse_filter("const"), Ok(SymbolKind::Constant));
+        assert_eq!(SymbolKind::parse_filter("var"), Ok(SymbolKind::Variable));
+        assert_eq!(SymbolKind::parse_filter("ty"), Ok(SymbolKind::TypeAlias));
+        assert_eq!(
+            SymbolKind::parse_filter("TypeAlias"),
+            Ok(SymbolKind::TypeAlias)
+        );
+        assert_eq!(
+            SymbolKind::parse_filter("enum-variant"),
+            Ok(SymbolKind::EnumVariant)
+        );
+        assert_eq!(
+            SymbolKind::parse_filter("EnumVariant"),
+            Ok(SymbolKind::EnumVariant)
+        );
+
+        let err = SymbolKind::parse_filter("widget").unwrap_err();
+        assert!(err.contains("'widget'"));
+        assert!(err.contains("function (fn, func)"));
+        assert!(err.contains("macro"));
+    }
+
     #[test]
     fn test_symbol_id_creation() {
         assert!(SymbolId::new(0).is_none());
@@ -216,9 +299,10 @@ mod tests {
             SymbolKind::Parameter,
             SymbolKind::TypeAlias,
             SymbolKind::Macro,
+            SymbolKind::EnumVariant,
         ];
 
-        assert_eq!(kinds.len(), 14);
+        assert_eq!(kinds.len(), 15);
     }
 
     #[test]
@@ -0,0 +1,321 @@
+//! Inverted-file (IVF) index for approximate nearest-neighbor search.
+//!
+//! Vectors are partitioned by K-means into clusters; a query is compared
+//! against the centroids and only the vectors in the closest `probes`
+//! clusters are scored. More probes means better recall and slower queries.
+//!
+//! # Storage Format
+//!
+//! One little-endian file next to the `.vec` segments:
+//! - Header (16 bytes): magic, version, dimension, cluster count
+//! - Centroids: `cluster count * dimension` f32 values
+//! - Lists: per cluster, a u32 length followed by that many vector IDs
+
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use rayon::prelude::*;
+
+use crate::vector::{
+    ClusteringError, VectorId, VectorStorageError, assign_to_nearest_centroid, cosine_similarity,
+    kmeans_clustering,
+};
+
+/// Current IVF file format version.
+const IVF_VERSION: u32 = 1;
+
+/// Magic bytes to identify IVF index files.
+const MAGIC_BYTES: &[u8; 4] = b"CIVF";
+
+/// Training vectors per cluster. K-means runs on this sample rather than the
+/// whole store; every vector is still assigned to its nearest centroid.
+const TRAINING_POINTS_PER_CLUSTER: usize = 32;
+
+/// Approximate nearest-neighbor index over a fixed set of vectors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IvfIndex {
+    /// Normalized cluster centroids
+    centroids: Vec<Vec<f32>>,
+
+    /// Vector IDs assigned to each cluster, parallel to `centroids`
+    lists: Vec<Vec<VectorId>>,
+}
+
+impl IvfIndex {
+    /// Builds an index with `clusters` partitions over `vectors`.
+    ///
+    /// The cluster count is clamped to the number of vectors.
+    pub fn build(vectors: &[(VectorId, &[f32])], clusters: usize) -> Result<Self, ClusteringError> {
+        if vectors.is_empty() {
+            return Err(ClusteringError::EmptyVectorSet);
+        }
+
+        let clusters = clusters.clamp(1, vectors.len());
+        let sample_size = (clusters * TRAINING_POINTS_PER_CLUSTER).min(vectors.len());
+        let step = vectors.len() / sample_size;
+        let sample: Vec<Vec<f32>> = vectors
+            .iter()
+            .step_by(step)
+            .take(sample_size)
+            .map(|(_, vector)| vector.to_vec())
+            .collect();
+
+        let centroids = kmeans_clustering(&sample, clusters)?.centroids;
+
+        let centroid_refs: Vec<&[f32]> = centroids.iter().map(|c| c.as_slice()).collect();
+        let assignments: Vec<_> = vectors
+            .par_iter()
+            .map(|(_, vector)| assign_to_nearest_centroid(vector, &centroid_refs))
+            .collect();
+
+        let mut lists = vec![Vec::new(); centroids.len()];
+        for ((id, _), cluster) in vectors.iter().zip(assignments) {
+            lists[(cluster.get() - 1) as usize].push(*id);
+        }
+
+        Ok(Self { centroids, lists })
+    }
+
+    /// Returns the IDs in the `probes` clusters closest to `query`.
+    ///
+    /// Candidates still need exact scoring; cluster order says nothing about
+    /// the order of vectors within the clusters.
+    pub fn candidates(&self, query: &[f32], probes: usize) -> Vec<VectorId> {
+        let mut ranked: Vec<(usize, f32)> = self
+            .centroids
+            .iter()
+            .enumerate()
+            .map(|(i, centroid)| (i, cosine_similarity(query, centroid)))
+            .collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        ranked
+            .iter()
+            .take(probes.max(1))
+            .flat_map(|(i, _)| self.lists[*i].iter().copied())
+            .collect()
+    }
+
+    /// Number of clusters
+    pub fn cluster_count(&self) -> usize {
+        self.centroids.len()
+    }
+
+    /// Number of vectors assigned to clusters
+    pub fn vector_count(&self) -> usize {
+        self.lists.iter().map(Vec::len).sum()
+    }
+
+    /// All vector IDs covered by the index
+    pub fn vector_ids(&self) -> impl Iterator<Item = VectorId> + '_ {
+        self.lists.iter().flatten().copied()
+    }
+
+    /// Writes the index to `path`, replacing any existing file.
+    pub fn save(&self, path: &Path) -> Result<(), VectorStorageError> {
+        let dimension = self.centroids.first().map_or(0, Vec::len);
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        writer.write_all(MAGIC_BYTES)?;
+        writer.write_all(&IVF_VERSION.to_le_bytes())?;
+        writer.write_all(&(dimension as u32).to_le_bytes())?;
+        writer.write_all(&(self.centroids.len() as u32).to_le_bytes())?;
+
+        for centroid in &self.centroids {
+            for &value in centroid {
+                writer.write_all(&value.to_le_bytes())?;
+            }
+        }
+
+        for list in &self.lists {
+            writer.write_all(&(list.len() as u32).to_le_bytes())?;
+            for id in list {
+                writer.write_all(&id.to_bytes())?;
+            }
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Reads an index written by [`IvfIndex::save`].
+    ///
+    /// Counts read from the file are checked against the bytes left in it
+    /// before anything is allocated, so a corrupt header is reported as an
+    /// invalid format.
+    pub fn load(path: &Path) -> Result<Self, VectorStorageError> {
+        let file = File::open(path)?;
+        // Magic, version, dimension and cluster count
+        let mut remaining = file.metadata()?.len().saturating_sub(16);
+        let mut reader = BufReader::new(file);
+
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC_BYTES {
+            return Err(VectorStorageError::InvalidFormat(
+                "Not an IVF index file".to_string(),
+            ));
+        }
+
+        let version = read_u32(&mut reader)?;
+        if version != IVF_VERSION {
+            return Err(VectorStorageError::InvalidFormat(format!(
+                "Unsupported IVF index version {version}"
+            )));
+        }
+
+        let dimension = read_u32(&mut reader)? as usize;
+        let cluster_count = read_u32(&mut reader)? as usize;
+
+        // Every centroid, plus a length prefix per list
+        let centroid_values = (cluster_count as u64).checked_mul(dimension as u64 + 1);
+        take_bytes(&mut remaining, centroid_values, "centroids")?;
+
+        let mut centroids = Vec::with_capacity(cluster_count);
+        for _ in 0..cluster_count {
+            let mut centroid = Vec::with_capacity(dimension);
+            for _ in 0..dimension {
+                centroid.push(f32::from_le_bytes(read_bytes(&mut reader)?));
+            }
+            centroids.push(centroid);
+        }
+
+        let mut lists = Vec::with_capacity(cluster_count);
+        for _ in 0..cluster_count {
+            let len = read_u32(&mut reader)? as usize;
+            take_bytes(&mut remaining, Some(len as u64), "vector list")?;
+            let mut list = Vec::with_capacity(len);
+            for _ in 0..len {
+                let id = VectorId::from_bytes(read_bytes(&mut reader)?).ok_or_else(|| {
+                    VectorStorageError::InvalidFormat("Invalid vector ID in IVF list".to_string())
+                })?;
+                list.push(id);
+            }
+            lists.push(list);
+        }
+
+        Ok(Self { centroids, lists })
+    }
+}
+
+/// Account for `count` four-byte values in the `remaining` bytes of a file
+fn take_bytes(
+    remaining: &mut u64,
+    count: Option<u64>,
+    what: &str,
+) -> Result<(), VectorStorageError> {
+    match count.and_then(|count| count.checked_mul(4)) {
+        Some(bytes) if bytes <= *remaining => {
+            *remaining -= bytes;
+            Ok(())
+        }
+        _ => Err(VectorStorageError::InvalidFormat(format!(
+            "IVF index {what} extend past the end of the file"
+        ))),
+    }
+}
+
+fn read_bytes(reader: &mut impl Read) -> io::Result<[u8; 4]> {
+    let mut bytes = [0u8; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    read_bytes(reader).map(u32::from_le_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn id(n: u32) -> VectorId {
+        VectorId::new(n).unwrap()
+    }
+
+    /// Two well-separated groups: IDs 1-10 near the x axis, 11-20 near y
+    fn grouped_vectors() -> Vec<(VectorId, Vec<f32>)> {
+        (1..=20)
+            .map(|n| {
+                let jitter = n as f32 * 0.01;
+                let vector = if n <= 10 {
+                    vec![1.0, jitter, 0.0]
+                } else {
+                    vec![jitter, 1.0, 0.0]
+                };
+                (id(n), vector)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_candidates_come_from_nearest_cluster() {
+        let vectors = grouped_vectors();
+        let refs: Vec<(VectorId, &[f32])> =
+            vectors.iter().map(|(i, v)| (*i, v.as_slice())).collect();
+        let index = IvfIndex::build(&refs, 2).unwrap();
+
+        assert_eq!(index.cluster_count(), 2);
+        assert_eq!(index.vector_count(), 20);
+
+        let mut candidates: Vec<u32> = index
+            .candidates(&[1.0, 0.0, 0.0], 1)
+            .iter()
+            .map(VectorId::get)
+            .collect();
+        candidates.sort_unstable();
+        assert_eq!(candidates, (1..=10).collect::<Vec<_>>());
+
+        // Probing every cluster is exhaustive
+        assert_eq!(index.candidates(&[1.0, 0.0, 0.0], 2).len(), 20);
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let vectors = grouped_vectors();
+        let refs: Vec<(VectorId, &[f32])> =
+            vectors.iter().map(|(i, v)| (*i, v.as_slice())).collect();
+        let index = IvfIndex::build(&refs, 2).unwrap();
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("ann.ivf");
+        index.save(&path).unwrap();
+        assert_eq!(IvfIndex::load(&path).unwrap(), index);
+
+        std::fs::write(&path, b"CVEC0000").unwrap();
+        assert!(matches!(
+            IvfIndex::load(&path),
+            Err(VectorStorageError::InvalidFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_load_rejects_counts_past_end_of_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("ann.ivf");
+
+        let mut header = Vec::new();
+        header.extend_from_slice(MAGIC_BYTES);
+        header.extend_from_slice(&IVF_VERSION.to_le_bytes());
+        header.extend_from_slice(&u32::MAX.to_le_bytes());
+        header.extend_from_slice(&u32::MAX.to_le_bytes());
+        std::fs::write(&path, &header).unwrap();
+        assert!(matches!(
+            IvfIndex::load(&path),
+            Err(VectorStorageError::InvalidFormat(_))
+        ));
+
+        // One empty centroid whose list claims more IDs than the file holds
+        let mut truncated = header[..8].to_vec();
+        truncated.extend_from_slice(&0u32.to_le_bytes());
+        truncated.extend_from_slice(&1u32.to_le_bytes());
+        truncated.extend_from_slice(&u32::MAX.to_le_bytes());
+        std::fs::write(&path, &truncated).unwrap();
+        assert!(matches!(
+            IvfIndex::load(&path),
+            Err(VectorStorageError::InvalidFormat(_))
+        ));
+    }
+}
@@ -17,6 +17,7 @@
 mod clustering;
 mod embedding;
 mod engine;
+mod ivf;
 mod storage;
 mod types;
 
@@ -31,7 +32,8 @@ pub use embedding::{
     parse_embedding_model,
 };
 pub use engine::VectorSearchEngine;
-pub use storage::{ConcurrentVectorStorage, MmapVectorStorage, VectorStorageError};
+pub use ivf::IvfIndex;
+pub use storage::{ConcurrentVectorStorage, MappedSegment, MmapVectorStorage, VectorStorageError};
 pub use types::{
     ClusterId, Score, SegmentOrdinal, VECTOR_DIMENSION_384, VectorDimension, VectorError, VectorId,
 };
@@ -16,6 +16,7 @@
 //! - Memory usage: 4 bytes per dimension per vector
 //! - Startup time: <1ms (mmap is lazy-loaded by OS)
 
+use std::collections::HashMap;
 use std::fs::{File, OpenOptions};
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
@@ -355,6 +356,11 @@ impl MmapVectorStorage {
         Ok(std::fs::metadata(&self.path)?.len())
     }
 
+    /// Returns the file size needed for `vector_count` vectors of `dimension`.
+    pub fn expected_file_size(dimension: usize, vector_count: usize) -> u64 {
+        (HEADER_SIZE + vector_count * (BYTES_PER_ID + dimension * BYTES_PER_F32)) as u64
+    }
+
     // Private helper methods
 
     fn segment_path(base_path: &Path, segment: SegmentOrdinal) -> PathBuf {
@@ -477,6 +483,148 @@ impl ConcurrentVectorStorage {
     }
 }
 
+/// Read-only, memory-mapped view of a segment's vectors.
+///
+/// Only an ID-to-offset table lives on the heap; vector data is read from the
+/// mapping on demand, so loading is fast and resident memory stays small.
+/// Vectors can be hidden (when removed or replaced) but not added.
+#[derive(Debug)]
+pub struct MappedSegment {
+    /// Mapping of the whole segment file.
+    mmap: Mmap,
+
+    /// Vector dimension.
+    dimension: usize,
+
+    /// Byte offset of each vector's data (just past its ID).
+    offsets: HashMap<VectorId, usize>,
+}
+
+impl MappedSegment {
+    /// Maps an existing segment file.
+    ///
+    /// Records are located with the dimension stride from the header; a
+    /// trailing partial record (e.g. from an interrupted write) is ignored.
+    pub fn open(
+        base_path: impl AsRef<Path>,
+        segment: SegmentOrdinal,
+    ) -> Result<Self, VectorStorageError> {
+        let path = MmapVectorStorage::segment_path(base_path.as_ref(), segment);
+        let file = File::open(&path)?;
+        let mmap = unsafe { MmapOptions::new().map(&file)? };
+
+        let (version, dimension, _) = MmapVectorStorage::read_header(&mmap)?;
+        if version != STORAGE_VERSION {
+            return Err(VectorError::VersionMismatch {
+                expected: STORAGE_VERSION,
+                actual: version,
+            }
+            .into());
+        }
+
+        let dimension = dimension.get();
+        let vector_size = BYTES_PER_ID + dimension * BYTES_PER_F32;
+        let mut offsets = HashMap::with_capacity((mmap.len() - HEADER_SIZE) / vector_size);
+
+        let mut offset = HEADER_SIZE;
+        while offset + vector_size <= mmap.len() {
+            let id = VectorId::from_bytes(read_le_bytes(&mmap, offset)).ok_or_else(|| {
+                VectorStorageError::InvalidFormat("Invalid vector ID".to_string())
+            })?;
+            offsets.insert(id, offset + BYTES_PER_ID);
+            offset += vector_size;
+        }
+
+        Ok(Self {
+            mmap,
+            dimension,
+            offsets,
+        })
+    }
+
+    /// Returns the vector dimension.
+    #[must_use]
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    /// Returns the number of visible vectors.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    /// Returns true if no vectors are visible.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    /// Checks whether a vector is visible.
+    #[must_use]
+    pub fn contains(&self, id: VectorId) -> bool {
+        self.offsets.contains_key(&id)
+    }
+
+    /// Iterates over the IDs of visible vectors.
+    pub fn ids(&self) -> impl Iterator<Item = VectorId> + '_ {
+        self.offsets.keys().copied()
+    }
+
+    /// Hides a vector. Returns true if it was visible.
+    pub fn remove(&mut self, id: VectorId) -> bool {
+        self.offsets.remove(&id).is_some()
+    }
+
+    /// Copies a vector out of the mapping.
+    #[must_use]
+    pub fn read(&self, id: VectorId) -> Option<Vec<f32>> {
+        let offset = *self.offsets.get(&id)?;
+        Some(self.values(offset).collect())
+    }
+
+    /// Cosine similarity between `query` and a stored vector, computed
+    /// directly from the mapping without copying the vector.
+    #[must_use]
+    pub fn cosine_similarity(&self, id: VectorId, query: &[f32]) -> Option<f32> {
+        let offset = *self.offsets.get(&id)?;
+        if query.len() != self.dimension {
+            return None;
+        }
+
+        let mut dot_product = 0.0f32;
+        let mut norm_stored = 0.0f32;
+        for (value, q) in self.values(offset).zip(query) {
+            dot_product += value * q;
+            norm_stored += value * value;
+        }
+        let norm_query: f32 = query.iter().map(|x| x * x).sum::<f32>().sqrt();
+        let norm_stored = norm_stored.sqrt();
+
+        if norm_query == 0.0 || norm_stored == 0.0 {
+            return Some(0.0);
+        }
+        Some(dot_product / (norm_query * norm_stored))
+    }
+
+    /// Decodes the vector whose data starts at `offset`. Values are read
+    /// byte-wise, so the mapping needs no particular alignment.
+    fn values(&self, offset: usize) -> impl Iterator<Item = f32> + '_ {
+        (0..self.dimension)
+            .map(move |i| f32::from_le_bytes(read_le_bytes(&self.mmap, offset + i * BYTES_PER_F32)))
+    }
+}
+
+/// Reads four bytes at `offset`; callers have bounds-checked the record.
+fn read_le_bytes(bytes: &[u8], offset: usize) -> [u8; 4] {
+    [
+        bytes[offset],
+        bytes[offset + 1],
+        bytes[offset + 2],
+        bytes[offset + 3],
+    ]
+}
+
 impl Clone for MmapVectorStorage {
     fn clone(&self) -> Self {
         // Clone path and metadata, but not mmap (will be lazy-loaded)
@@ -513,6 +661,38 @@ mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_mapped_segment() {
+        let temp_dir = TempDir::new().unwrap();
+        let segment = SegmentOrdinal::new(0);
+        let dimension = VectorDimension::new(3).unwrap();
+
+        let mut storage = MmapVectorStorage::new(&temp_dir, segment, dimension).unwrap();
+        let a = VectorId::new(1).unwrap();
+        let b = VectorId::new(2).unwrap();
+        storage
+            .write_batch(&[(a, &[1.0, 0.0, 0.0]), (b, &[0.0, 2.0, 0.0])])
+            .unwrap();
+
+        // A trailing partial record is ignored
+        let path = temp_dir.path().join("segment_0.vec");
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(&[7, 0, 0, 0, 1]).unwrap();
+
+        let mut mapped = MappedSegment::open(&temp_dir, segment).unwrap();
+        assert_eq!(mapped.dimension(), 3);
+        assert_eq!(mapped.len(), 2);
+        assert_eq!(mapped.read(b), Some(vec![0.0, 2.0, 0.0]));
+        assert_eq!(mapped.cosine_similarity(a, &[2.0, 0.0, 0.0]), Some(1.0));
+        assert_eq!(mapped.cosine_similarity(b, &[1.0, 0.0, 0.0]), Some(0.0));
+        assert_eq!(mapped.cosine_similarity(a, &[1.0, 0.0]), None);
+
+        assert!(mapped.remove(a));
+        assert!(!mapped.contains(a));
+        assert_eq!(mapped.read(a), None);
+        assert_eq!(mapped.ids().collect::<Vec<_>>(), vec![b]);
+    }
+
     #[test]
     fn test_storage_create_and_open() {
         let temp_dir = TempDir::new().unwrap();
@@ -559,6 +739,12 @@ mod tests {
 
         // Non-existent vector should return None
         assert!(storage.read_vector(VectorId::new(999).unwrap()).is_none());
+
+        // File size matches the layout
+        assert_eq!(
+            storage.file_size().unwrap(),
+            MmapVectorStorage::expected_file_size(4, 3)
+        );
     }
 
     #[test]
@@ -0,0 +1,55 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+fn codanna_binary() -> PathBuf {
+    if let Some(path) = option_env!("CARGO_BIN_EXE_codanna") {
+        return PathBuf::from(path);
+    }
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| std::env::current_dir().expect("current dir"));
+    manifest_dir.join("target").join("debug").join("codanna")
+}
+
+fn help_output(args: &[&str]) -> String {
+    let output = Command::new(codanna_binary())
+        .args(args)
+        .arg("--help")
+        .env_remove("NO_COLOR")
+        .output()
+        .expect("run codanna CLI");
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn color_always_styles_piped_output() {
+    let stdout = help_output(&["--color", "always"]);
+    assert!(
+        stdout.contains("\u{1b}["),
+        "expected ANSI codes in: {stdout}"
+    );
+}
+
+#[test]
+fn color_never_and_auto_stay_plain_when_piped() {
+    for args in [&["--color=never"][..], &[][..]] {
+        let stdout = help_output(args);
+        assert!(stdout.contains("Quick Start:"));
+        assert!(
+            !stdout.contains("\u{1b}["),
+            "unexpected ANSI codes in: {stdout}"
+        );
+    }
+}
+
+#[test]
+fn color_always_overrides_no_color() {
+    let output = Command::new(codanna_binary())
+        .args(["--color", "always", "--help"])
+        .env("NO_COLOR", "1")
+        .output()
+        .expect("run codanna CLI");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("\u{1b}["));
+}
@@ -0,0 +1,92 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use tempfile::TempDir;
+
+fn codanna_binary() -> PathBuf {
+    if let Some(path) = option_env!("CARGO_BIN_EXE_codanna") {
+        return PathBuf::from(path);
+    }
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| std::env::current_dir().expect("current dir"));
+    manifest_dir.join("target").join("debug").join("codanna")
+}
+
+fn run_cli(workspace: &Path, args: &[&str]) -> (i32, String, String) {
+    let test_home = workspace.join("home");
+    std::fs::create_dir_all(&test_home).expect("create test home directory");
+
+    let output = Command::new(codanna_binary())
+        .args(args)
+        .current_dir(workspace)
+        .env("HOME", &test_home)
+        .output()
+        .expect("run codanna CLI");
+
+    (
+        output.status.code().unwrap_or(-1),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+    )
+}
+
+/// Three files each defining a function named `build`
+fn prepare_workspace(workspace: &Path) {
+    let config_dir = workspace.join(".codanna");
+    std::fs::create_dir_all(&config_dir).expect("create config dir");
+    std::fs::write(
+        config_dir.join("settings.toml"),
+        "index_path = \"index\"\n\n[semantic_search]\nenabled = false\n",
+    )
+    .expect("write settings file");
+
+    std::fs::create_dir_all(workspace.join("src")).expect("create src dir");
+    for module in ["a", "b", "c"] {
+        std::fs::write(
+            workspace.join(format!("src/{module}.py")),
+            "def build():\n    return 1\n",
+        )
+        .expect("write source");
+    }
+
+    let (code, _, stderr) = run_cli(workspace, &["index", "src"]);
+    assert_eq!(code, 0, "stderr: {stderr}");
+}
+
+#[test]
+fn find_symbol_limit_shows_subset_and_reports_total() {
+    let temp = TempDir::new().expect("create temp dir");
+    let workspace = temp.path();
+    prepare_workspace(workspace);
+
+    let (code, stdout, stderr) = run_cli(
+        workspace,
+        &["mcp", "find_symbol", "build", "limit:2", "--json"],
+    );
+    assert_eq!(code, 0, "stderr: {stderr}");
+    let value: serde_json::Value = serde_json::from_str(&stdout).expect("valid JSON output");
+    assert_eq!(value["data"].as_array().expect("data array").len(), 2);
+    assert!(
+        value["system_message"]
+            .as_str()
+            .is_some_and(|message| message.contains("Showing 2 of 3")),
+        "stdout: {stdout}"
+    );
+
+    let (code, stdout, stderr) = run_cli(workspace, &["mcp", "find_symbol", "build", "limit:2"]);
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert!(
+        stdout.contains("Found 3 symbol(s) named 'build', showing the first 2"),
+        "stdout: {stdout}"
+    );
+    assert!(stdout.contains("... and 1 more"), "stdout: {stdout}");
+
+    // Under the limit nothing is left out
+    let (_, stdout, _) = run_cli(workspace, &["mcp", "find_symbol", "build"]);
+    assert!(
+        stdout.contains("Found 3 symbol(s) named 'build':"),
+        "stdout: {stdout}"
+    );
+}
@@ -0,0 +1,195 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use tempfile::TempDir;
+
+const NAMES: [&str; 5] = [
+    "parse_input",
+    "ParseState",
+    "PARSE_LIMIT",
+    "ParseMode",
+    "ParseHandler",
+];
+
+fn codanna_binary() -> PathBuf {
+    if let Some(path) = option_env!("CARGO_BIN_EXE_codanna") {
+        return PathBuf::from(path);
+    }
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| std::env::current_dir().expect("current dir"));
+    manifest_dir.join("target").join("debug").join("codanna")
+}
+
+fn prepare_workspace(workspace: &Path) {
+    let config_dir = workspace.join(".codanna");
+    std::fs::create_dir_all(&config_dir).expect("create config dir");
+    std::fs::write(
+        config_dir.join("settings.toml"),
+        "index_path = \"index\"\n\n[semantic_search]\nenabled = false\n",
+    )
+    .expect("write settings file");
+
+    std::fs::create_dir_all(workspace.join("src")).expect("create src dir");
+    std::fs::write(
+        workspace.join("src/lib.rs"),
+        "pub fn parse_input() {}\n\
+         pub struct ParseState;\n\
+         pub const PARSE_LIMIT: u32 = 1;\n\
+         pub enum ParseMode { Fast }\n\
+         pub trait ParseHandler {}\n",
+    )
+    .expect("write source");
+
+    let (code, _, stderr) = run_cli(workspace, &["index", "src/lib.rs"]);
+    assert_eq!(code, 0, "stderr: {stderr}");
+}
+
+fn run_cli(workspace: &Path, args: &[&str]) -> (i32, String, String) {
+    let test_home = workspace.join("home");
+    std::fs::create_dir_all(&test_home).expect("create test home directory");
+
+    let output = Command::new(codanna_binary())
+        .args(args)
+        .current_dir(workspace)
+        .env("HOME", &test_home)
+        .output()
+        .expect("run codanna CLI");
+
+    (
+        output.status.code().unwrap_or(-1),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+    )
+}
+
+fn json_names(stdout: &str, items: &str) -> Vec<String> {
+    let value: serde_json::Value = serde_json::from_str(stdout).expect("valid JSON output");
+    let items = value
+        .pointer(items)
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    let mut names: Vec<String> = items
+        .iter()
+        .filter_map(|item| {
+            item.pointer("/symbol/name")
+                .or_else(|| item.get("name"))
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+/// Names returned by `retrieve search`, MCP `search_symbols` and its JSON variant
+fn names_per_entry_point(workspace: &Path, kind: &str) -> [Vec<String>; 3] {
+    let (code, stdout, stderr) = run_cli(
+        workspace,
+        &[
+            "retrieve",
+            "search",
+            "parse",
+            "--substring",
+            "--kind",
+            kind,
+            "--json",
+        ],
+    );
+    assert_eq!(code, 0, "retrieve search --kind {kind}: {stderr}");
+    let retrieve = json_names(&stdout, "/data/items");
+
+    let kind_arg = format!("kind:{kind}");
+    let (code, stdout, stderr) = run_cli(
+        workspace,
+        &[
+            "mcp",
+            "search_symbols",
+            "query:parse",
+            "substring:true",
+            &kind_arg,
+            "--json",
+        ],
+    );
+    assert_eq!(code, 0, "mcp search_symbols --json {kind_arg}: {stderr}");
+    let mcp_json = json_names(&stdout, "/data");
+
+    let (code, stdout, stderr) = run_cli(
+        workspace,
+        &[
+            "mcp",
+            "search_symbols",
+            "query:parse",
+            "substring:true",
+            &kind_arg,
+        ],
+    );
+    assert_eq!(code, 0, "mcp search_symbols {kind_arg}: {stderr}");
+    let mut mcp_text: Vec<String> = NAMES
+        .iter()
+        .filter(|name| stdout.contains(*name))
+        .map(|name| name.to_string())
+        .collect();
+    mcp_text.sort();
+
+    [retrieve, mcp_json, mcp_text]
+}
+
+#[test]
+fn kind_filters_match_across_cli_and_mcp() {
+    let temp = TempDir::new().expect("create temp dir");
+    let workspace = temp.path();
+    prepare_workspace(workspace);
+
+    let cases = [
+        ("function", "parse_input"),
+        ("fn", "parse_input"),
+        ("Func", "parse_input"),
+        ("struct", "ParseState"),
+        ("const", "PARSE_LIMIT"),
+        ("Constant", "PARSE_LIMIT"),
+        ("enum", "ParseMode"),
+        ("trait", "ParseHandler"),
+    ];
+
+    for (kind, expected) in cases {
+        for names in names_per_entry_point(workspace, kind) {
+            assert_eq!(names, vec![expected.to_string()], "kind {kind}");
+        }
+    }
+}
+
+#[test]
+fn unknown_kind_lists_accepted_values() {
+    let temp = TempDir::new().expect("create temp dir");
+    let workspace = temp.path();
+    prepare_workspace(workspace);
+
+    let (code, _, stderr) = run_cli(
+        workspace,
+        &["retrieve", "search", "parse", "--kind", "widget", "--json"],
+    );
+    assert_eq!(code, 1);
+    assert!(stderr.contains("Accepted values"), "stderr: {stderr}");
+
+    let (code, _, stderr) = run_cli(
+        workspace,
+        &[
+            "mcp",
+            "search_symbols",
+            "query:parse",
+            "kind:widget",
+            "--json",
+        ],
+    );
+    assert_eq!(code, 1);
+    assert!(stderr.contains("Accepted values"), "stderr: {stderr}");
+
+    let (_, stdout, _) = run_cli(
+        workspace,
+        &["mcp", "search_symbols", "query:parse", "kind:widget"],
+    );
+    assert!(stdout.contains("Accepted values"), "stdout: {stdout}");
+}
@@ -0,0 +1,98 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use tempfile::TempDir;
+
+fn codanna_binary() -> PathBuf {
+    if let Some(path) = option_env!("CARGO_BIN_EXE_codanna") {
+        return PathBuf::from(path);
+    }
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| std::env::current_dir().expect("current dir"));
+    manifest_dir.join("target").join("debug").join("codanna")
+}
+
+fn prepare_workspace(workspace: &Path) {
+    let config_dir = workspace.join(".codanna");
+    std::fs::create_dir_all(&config_dir).expect("create config dir");
+    std::fs::write(
+        config_dir.join("settings.toml"),
+        "index_path = \"index\"\n\n[semantic_search]\nenabled = false\n",
+    )
+    .expect("write settings file");
+
+    std::fs::create_dir_all(workspace.join("src")).expect("create src dir");
+    std::fs::write(
+        workspace.join("src/Shapes.kt"),
+        include_str!("../fixtures/kotlin/extensions.kt"),
+    )
+    .expect("write source");
+
+    let (code, _, stderr) = run_cli(workspace, &["index", "src/Shapes.kt"]);
+    assert_eq!(code, 0, "stderr: {stderr}");
+}
+
+fn run_cli(workspace: &Path, args: &[&str]) -> (i32, String, String) {
+    let test_home = workspace.join("home");
+    std::fs::create_dir_all(&test_home).expect("create test home directory");
+
+    let output = Command::new(codanna_binary())
+        .args(args)
+        .current_dir(workspace)
+        .env("HOME", &test_home)
+        .output()
+        .expect("run codanna CLI");
+
+    (
+        output.status.code().unwrap_or(-1),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+    )
+}
+
+fn find_symbol_json(workspace: &Path, name: &str) -> serde_json::Value {
+    let (code, stdout, stderr) = run_cli(workspace, &["mcp", "find_symbol", name, "--json"]);
+    assert_eq!(code, 0, "find_symbol {name}: {stderr}");
+    let value: serde_json::Value = serde_json::from_str(&stdout).expect("valid JSON output");
+    value["data"][0].clone()
+}
+
+#[test]
+fn find_symbol_reports_kotlin_declarations_with_kinds() {
+    let temp = TempDir::new().expect("create temp dir");
+    let workspace = temp.path();
+    prepare_workspace(workspace);
+
+    for (name, kind) in [
+        ("shout", "Method"),
+        ("distanceTo", "Method"),
+        ("topLevel", "Function"),
+        ("Companion", "Class"),
+        ("Defaults", "Class"),
+        ("Logger", "Class"),
+        ("Point", "Class"),
+        ("copy", "Method"),
+        ("component1", "Method"),
+    ] {
+        let found = find_symbol_json(workspace, name);
+        assert_eq!(found["symbol"]["kind"], kind, "{name}: {found}");
+    }
+}
+
+#[test]
+fn find_symbol_links_extension_functions_to_receiver() {
+    let temp = TempDir::new().expect("create temp dir");
+    let workspace = temp.path();
+    prepare_workspace(workspace);
+
+    let point = find_symbol_json(workspace, "Point");
+    let defines: Vec<&str> = point["relationships"]["defines"]
+        .as_array()
+        .expect("Point defines")
+        .iter()
+        .filter_map(|symbol| symbol["name"].as_str())
+        .collect();
+    assert!(defines.contains(&"distanceTo"), "defines: {defines:?}");
+}
@@ -0,0 +1,108 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use tempfile::TempDir;
+
+fn codanna_binary() -> PathBuf {
+    if let Some(path) = option_env!("CARGO_BIN_EXE_codanna") {
+        return PathBuf::from(path);
+    }
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| std::env::current_dir().expect("current dir"));
+    manifest_dir.join("target").join("debug").join("codanna")
+}
+
+fn prepare_workspace(workspace: &Path, server_section: &str) {
+    let config_dir = workspace.join(".codanna");
+    std::fs::create_dir_all(&config_dir).expect("create config dir");
+    std::fs::write(
+        config_dir.join("settings.toml"),
+        format!(
+            "index_path = \"index\"\n\n[semantic_search]\nenabled = false\n\n[server]\n{server_section}"
+        ),
+    )
+    .expect("write settings file");
+
+    std::fs::create_dir_all(workspace.join("src")).expect("create src dir");
+    std::fs::write(workspace.join("src/lib.rs"), "pub fn hello() {}\n").expect("write source");
+}
+
+fn run_cli(workspace: &Path, args: &[&str]) -> (i32, String) {
+    let test_home = workspace.join("home");
+    std::fs::create_dir_all(&test_home).expect("create test home directory");
+
+    let output = Command::new(codanna_binary())
+        .args(args)
+        .current_dir(workspace)
+        .env("HOME", &test_home)
+        .output()
+        .expect("run codanna CLI");
+
+    let code = output.status.code().unwrap_or(-1);
+    (code, String::from_utf8_lossy(&output.stderr).to_string())
+}
+
+fn saving_event(stderr: &str) -> Option<serde_json::Value> {
+    stderr
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .find(|event| {
+            event["fields"]["message"]
+                .as_str()
+                .is_some_and(|message| message.starts_with("Saving index"))
+        })
+}
+
+#[test]
+fn log_format_json_emits_structured_lines() {
+    let temp = TempDir::new().expect("create temp dir");
+    let workspace = temp.path();
+    prepare_workspace(workspace, "");
+
+    let (code, stderr) = run_cli(workspace, &["--log-format", "json", "index", "src/lib.rs"]);
+    assert_eq!(code, 0, "stderr: {stderr}");
+
+    let event = saving_event(&stderr).unwrap_or_else(|| panic!("no JSON event in: {stderr}"));
+    assert_eq!(event["level"], "INFO");
+    assert!(event["timestamp"].is_string());
+    assert!(
+        event["fields"]["symbols"]
+            .as_u64()
+            .is_some_and(|count| count > 0),
+        "event: {event}"
+    );
+    assert!(event["fields"]["relationships"].is_u64(), "event: {event}");
+}
+
+#[test]
+fn log_format_is_read_from_config() {
+    let temp = TempDir::new().expect("create temp dir");
+    let workspace = temp.path();
+    prepare_workspace(workspace, "log_format = \"json\"\n");
+
+    let (code, stderr) = run_cli(workspace, &["index", "src/lib.rs"]);
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert!(saving_event(&stderr).is_some(), "stderr: {stderr}");
+
+    // The flag overrides the config
+    let (code, stderr) = run_cli(
+        workspace,
+        &["--log-format", "text", "index", "src/lib.rs", "--force"],
+    );
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert!(saving_event(&stderr).is_none(), "stderr: {stderr}");
+    assert!(stderr.contains("Saving index"), "stderr: {stderr}");
+}
+
+#[test]
+fn unknown_log_format_is_rejected() {
+    let temp = TempDir::new().expect("create temp dir");
+    let workspace = temp.path();
+    prepare_workspace(workspace, "");
+
+    let (code, stderr) = run_cli(workspace, &["--log-format", "yaml", "config"]);
+    assert_ne!(code, 0);
+    assert!(stderr.contains("Unknown log format"), "stderr: {stderr}");
+}
@@ -0,0 +1,142 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+use tempfile::TempDir;
+
+fn codanna_binary() -> PathBuf {
+    if let Some(path) = option_env!("CARGO_BIN_EXE_codanna") {
+        return PathBuf::from(path);
+    }
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| std::env::current_dir().expect("current dir"));
+    manifest_dir.join("target").join("debug").join("codanna")
+}
+
+fn run_list_tools(args: &[&str]) -> (i32, String) {
+    let temp = TempDir::new().expect("create temp dir");
+    let test_home = temp.path().join("home");
+    std::fs::create_dir_all(&test_home).expect("create test home directory");
+
+    let output = Command::new(codanna_binary())
+        .args(["mcp", "list-tools"])
+        .args(args)
+        .current_dir(temp.path())
+        .env("HOME", &test_home)
+        .output()
+        .expect("run codanna CLI");
+
+    let code = output.status.code().unwrap_or(-1);
+    (code, String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[test]
+fn list_tools_json_is_tools_list_payload() {
+    let (code, stdout) = run_list_tools(&["--json"]);
+    assert_eq!(code, 0, "stdout: {stdout}");
+
+    let payload: serde_json::Value = serde_json::from_str(&stdout).expect("valid JSON");
+    let tools = payload["tools"].as_array().expect("tools array");
+
+    let search = tools
+        .iter()
+        .find(|tool| tool["name"] == "search_symbols")
+        .expect("search_symbols listed");
+    assert!(search["description"].is_string());
+    assert_eq!(search["inputSchema"]["type"], "object");
+    assert!(search["inputSchema"]["properties"]["query"].is_object());
+    assert!(
+        tools.iter().any(|tool| tool["name"] == "find_symbol"),
+        "find_symbol listed"
+    );
+}
+
+#[test]
+fn list_tools_text_shows_parameters() {
+    let (code, stdout) = run_list_tools(&[]);
+    assert_eq!(code, 0, "stdout: {stdout}");
+
+    assert!(stdout.contains("find_symbol\n"), "stdout: {stdout}");
+    assert!(
+        stdout.contains("query (string, required)"),
+        "stdout: {stdout}"
+    );
+}
+
+#[test]
+fn list_tools_uses_configured_tool_guidance() {
+    let temp = TempDir::new().expect("create temp dir");
+    let test_home = temp.path().join("home");
+    std::fs::create_dir_all(&test_home).expect("create test home directory");
+    let config_dir = temp.path().join(".codanna");
+    std::fs::create_dir_all(&config_dir).expect("create config dir");
+    std::fs::write(
+        config_dir.join("settings.toml"),
+        "[mcp.tool_guidance]\nfind_symbol = \"Team workflow: start here\"\n",
+    )
+    .expect("write settings file");
+
+    let output = Command::new(codanna_binary())
+        .args(["mcp", "list-tools", "--json"])
+        .current_dir(temp.path())
+        .env("HOME", &test_home)
+        .output()
+        .expect("run codanna CLI");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0), "stdout: {stdout}");
+
+    let payload: serde_json::Value = serde_json::from_str(&stdout).expect("valid JSON");
+    let description = |name: &str| {
+        payload["tools"]
+            .as_array()
+            .expect("tools array")
+            .iter()
+            .find(|tool| tool["name"] == name)
+            .and_then(|tool| tool["description"].as_str())
+            .map(str::to_string)
+    };
+    assert_eq!(
+        description("find_symbol").as_deref(),
+        Some("Team workflow: start here")
+    );
+    // Tools without an override keep the built-in text
+    assert!(
+        description("get_calls").is_some_and(|text| text.contains("CALLS")),
+        "stdout: {stdout}"
+    );
+}
+
+#[test]
+fn list_tools_warns_about_unknown_tool_guidance() {
+    let temp = TempDir::new().expect("create temp dir");
+    let test_home = temp.path().join("home");
+    std::fs::create_dir_all(&test_home).expect("create test home directory");
+    let config_dir = temp.path().join(".codanna");
+    std::fs::create_dir_all(&config_dir).expect("create config dir");
+    std::fs::write(
+        config_dir.join("settings.toml"),
+        "[mcp.tool_guidance]\nfind_symbol = \"Start here\"\nfind_symbols = \"Typo\"\n",
+    )
+    .expect("write settings file");
+
+    let output = Command::new(codanna_binary())
+        .args(["mcp", "list-tools", "--json"])
+        .current_dir(temp.path())
+        .env("HOME", &test_home)
+        .output()
+        .expect("run codanna CLI");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(0), "stderr: {stderr}");
+
+    assert!(
+        stderr.contains("Unknown tool 'find_symbols' in [mcp.tool_guidance]"),
+        "stderr: {stderr}"
+    );
+    assert!(!stderr.contains("'find_symbol'"), "stderr: {stderr}");
+    assert_eq!(
+        stderr.matches("Unknown tool").count(),
+        1,
+        "stderr: {stderr}"
+    );
+}
@@ -146,6 +146,53 @@ fn dry_run_add_reports_expected_output() {
             stdout.contains("DRY RUN: Would install plugin 'demo-plugin'"),
             "stdout should mention dry-run install, got:\n{stdout}"
         );
+        assert!(
+            stdout.contains(".claude/commands/demo-plugin/cli-command.md"),
+            "stdout should list planned files, got:\n{stdout}"
+        );
+        assert!(
+            stdout.contains("Conflicts: none"),
+            "stdout should report no conflicts, got:\n{stdout}"
+        );
+        assert!(
+            !workspace.join(".codanna/plugins/lockfile.json").exists(),
+            "dry-run add must not write the lockfile"
+        );
+    });
+}
+
+#[test]
+fn dry_run_add_reports_file_conflicts() {
+    with_temp_workspace(|workspace| {
+        prepare_workspace(workspace);
+        let repo_url = create_marketplace_repo(workspace, "demo-plugin");
+        let existing = workspace.join(".claude/commands/demo-plugin/cli-command.md");
+        std::fs::create_dir_all(existing.parent().unwrap()).expect("create commands dir");
+        std::fs::write(&existing, "# Local command\n").expect("write existing command");
+
+        let (code, stdout, stderr) = run_cli(
+            workspace,
+            &["plugin", "add", &repo_url, "demo-plugin", "--dry-run"],
+        );
+
+        assert_eq!(code, 0, "dry-run add should succeed, stderr: {stderr}");
+        assert!(
+            stdout.contains("Conflicts (1):"),
+            "stdout should report the conflict, got:\n{stdout}"
+        );
+        assert!(
+            stdout.contains("re-run with --force"),
+            "stdout should suggest --force, got:\n{stdout}"
+        );
+        assert_eq!(
+            std::fs::read_to_string(&existing).expect("read existing command"),
+            "# Local command\n",
+            "dry-run must not overwrite existing files"
+        );
+        assert!(
+            !workspace.join(".codanna/plugins/lockfile.json").exists(),
+            "dry-run add must not write the lockfile"
+        );
     });
 }
 
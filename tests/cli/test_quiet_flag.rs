@@ -0,0 +1,77 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use tempfile::TempDir;
+
+fn codanna_binary() -> PathBuf {
+    if let Some(path) = option_env!("CARGO_BIN_EXE_codanna") {
+        return PathBuf::from(path);
+    }
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| std::env::current_dir().expect("current dir"));
+    manifest_dir.join("target").join("debug").join("codanna")
+}
+
+fn prepare_workspace(workspace: &Path) {
+    let config_dir = workspace.join(".codanna");
+    std::fs::create_dir_all(&config_dir).expect("create config dir");
+    std::fs::write(
+        config_dir.join("settings.toml"),
+        "index_path = \"index\"\n\n[semantic_search]\nenabled = false\n",
+    )
+    .expect("write settings file");
+
+    std::fs::create_dir_all(workspace.join("src")).expect("create src dir");
+    std::fs::write(workspace.join("src/lib.rs"), "pub fn hello() {}\n").expect("write source");
+}
+
+fn run_cli(workspace: &Path, args: &[&str]) -> (i32, String) {
+    let test_home = workspace.join("home");
+    std::fs::create_dir_all(&test_home).expect("create test home directory");
+
+    let output = Command::new(codanna_binary())
+        .args(args)
+        .current_dir(workspace)
+        .env("HOME", &test_home)
+        .output()
+        .expect("run codanna CLI");
+
+    let code = output.status.code().unwrap_or(-1);
+    (code, String::from_utf8_lossy(&output.stderr).to_string())
+}
+
+#[test]
+fn quiet_suppresses_informational_stderr() {
+    let temp = TempDir::new().expect("create temp dir");
+    let workspace = temp.path();
+    prepare_workspace(workspace);
+
+    let (code, stderr) = run_cli(workspace, &["index", "src/lib.rs"]);
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert!(stderr.contains("Saving index"), "stderr: {stderr}");
+
+    let (code, stderr) = run_cli(workspace, &["--quiet", "index", "src/lib.rs", "--force"]);
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert!(!stderr.contains("Saving index"), "stderr: {stderr}");
+}
+
+#[test]
+fn quiet_still_prints_errors() {
+    let temp = TempDir::new().expect("create temp dir");
+    let workspace = temp.path();
+    prepare_workspace(workspace);
+
+    let (code, stderr) = run_cli(workspace, &["-q", "index", "missing.rs"]);
+    assert_eq!(code, 1);
+    assert!(stderr.contains("missing.rs"), "stderr: {stderr}");
+}
+
+#[test]
+fn quiet_conflicts_with_info() {
+    let temp = TempDir::new().expect("create temp dir");
+    let (code, stderr) = run_cli(temp.path(), &["--quiet", "--info", "config"]);
+    assert_eq!(code, 2);
+    assert!(stderr.contains("cannot be used with"), "stderr: {stderr}");
+}
@@ -0,0 +1,119 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use tempfile::TempDir;
+
+fn codanna_binary() -> PathBuf {
+    if let Some(path) = option_env!("CARGO_BIN_EXE_codanna") {
+        return PathBuf::from(path);
+    }
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| std::env::current_dir().expect("current dir"));
+    manifest_dir.join("target").join("debug").join("codanna")
+}
+
+fn prepare_workspace(workspace: &Path) {
+    let config_dir = workspace.join(".codanna");
+    std::fs::create_dir_all(&config_dir).expect("create config dir");
+    std::fs::write(
+        config_dir.join("settings.toml"),
+        "index_path = \"index\"\n\n[semantic_search]\nenabled = false\n",
+    )
+    .expect("write settings file");
+
+    std::fs::create_dir_all(workspace.join("src")).expect("create src dir");
+    std::fs::write(
+        workspace.join("src/parser.py"),
+        "class Parser:\n    def parse(self, text):\n        return text\n",
+    )
+    .expect("write source");
+
+    let (code, _, stderr) = run_cli(workspace, &["index", "src/parser.py"]);
+    assert_eq!(code, 0, "stderr: {stderr}");
+}
+
+fn run_cli(workspace: &Path, args: &[&str]) -> (i32, String, String) {
+    let test_home = workspace.join("home");
+    std::fs::create_dir_all(&test_home).expect("create test home directory");
+
+    let output = Command::new(codanna_binary())
+        .args(args)
+        .current_dir(workspace)
+        .env("HOME", &test_home)
+        .output()
+        .expect("run codanna CLI");
+
+    (
+        output.status.code().unwrap_or(-1),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+    )
+}
+
+fn symbol_at_json(workspace: &Path, args: &[&str]) -> serde_json::Value {
+    let mut full = vec!["mcp", "symbol_at", "src/parser.py"];
+    full.extend_from_slice(args);
+    full.push("--json");
+    let (code, stdout, stderr) = run_cli(workspace, &full);
+    assert_eq!(code, 0, "symbol_at {args:?}: {stderr}");
+    serde_json::from_str(&stdout).expect("valid JSON output")
+}
+
+fn enclosing_names(value: &serde_json::Value) -> Vec<String> {
+    value["data"]["enclosing"]
+        .as_array()
+        .expect("enclosing array")
+        .iter()
+        .filter_map(|symbol| symbol["name"].as_str().map(str::to_string))
+        .collect()
+}
+
+#[test]
+fn symbol_at_returns_innermost_symbol_and_enclosing_chain() {
+    let temp = TempDir::new().expect("create temp dir");
+    let workspace = temp.path();
+    prepare_workspace(workspace);
+
+    // `return text` on line 3, 1-based
+    let one_based = symbol_at_json(workspace, &["line:3", "column:11"]);
+    assert_eq!(one_based["data"]["symbol"]["name"], "parse");
+    assert!(enclosing_names(&one_based).contains(&"Parser".to_string()));
+
+    // The same position as an LSP-style 0-based position
+    let zero_based = symbol_at_json(workspace, &["line:2", "column:10", "zero_based:true"]);
+    assert_eq!(zero_based["data"]["symbol"], one_based["data"]["symbol"]);
+
+    let (code, stdout, stderr) = run_cli(
+        workspace,
+        &["mcp", "symbol_at", "src/parser.py", "line:3", "column:11"],
+    );
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert!(
+        stdout.contains("Symbol at src/parser.py:3:11"),
+        "stdout: {stdout}"
+    );
+    assert!(stdout.contains("Enclosed by"), "stdout: {stdout}");
+}
+
+#[test]
+fn symbol_at_rejects_zero_line_without_zero_based() {
+    let temp = TempDir::new().expect("create temp dir");
+    let workspace = temp.path();
+    prepare_workspace(workspace);
+
+    let (code, stdout, _) = run_cli(
+        workspace,
+        &[
+            "mcp",
+            "symbol_at",
+            "src/parser.py",
+            "line:0",
+            "column:1",
+            "--json",
+        ],
+    );
+    assert_eq!(code, 1);
+    assert!(stdout.contains("1-based"), "stdout: {stdout}");
+}
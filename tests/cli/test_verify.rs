@@ -0,0 +1,96 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use tempfile::TempDir;
+
+fn codanna_binary() -> PathBuf {
+    if let Some(path) = option_env!("CARGO_BIN_EXE_codanna") {
+        return PathBuf::from(path);
+    }
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| std::env::current_dir().expect("current dir"));
+    manifest_dir.join("target").join("debug").join("codanna")
+}
+
+fn run_cli(workspace: &Path, args: &[&str]) -> (i32, String, String) {
+    let test_home = workspace.join("home");
+    std::fs::create_dir_all(&test_home).expect("create test home directory");
+
+    let output = Command::new(codanna_binary())
+        .args(args)
+        .current_dir(workspace)
+        .env("HOME", &test_home)
+        .output()
+        .expect("run codanna CLI");
+
+    (
+        output.status.code().unwrap_or(-1),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+    )
+}
+
+fn prepare_workspace(workspace: &Path) {
+    let config_dir = workspace.join(".codanna");
+    std::fs::create_dir_all(&config_dir).expect("create config dir");
+    std::fs::write(
+        config_dir.join("settings.toml"),
+        "index_path = \"index\"\n\n[semantic_search]\nenabled = false\n",
+    )
+    .expect("write settings file");
+
+    std::fs::create_dir_all(workspace.join("src")).expect("create src dir");
+    for module in ["a", "b", "c"] {
+        std::fs::write(
+            workspace.join(format!("src/{module}.py")),
+            format!("def {module}():\n    return 1\n"),
+        )
+        .expect("write source");
+    }
+
+    let (code, _, stderr) = run_cli(workspace, &["index", "src"]);
+    assert_eq!(code, 0, "stderr: {stderr}");
+}
+
+#[test]
+fn verify_exits_nonzero_when_files_diverge() {
+    let temp = TempDir::new().expect("create temp dir");
+    let workspace = temp.path();
+    prepare_workspace(workspace);
+
+    let (code, stdout, stderr) = run_cli(workspace, &["verify"]);
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert!(
+        stdout.contains("3 ok, 0 changed, 0 missing"),
+        "stdout: {stdout}"
+    );
+
+    std::fs::write(workspace.join("src/a.py"), "def a():\n    return 2\n").expect("edit source");
+    std::fs::remove_file(workspace.join("src/b.py")).expect("delete source");
+
+    let (code, stdout, _) = run_cli(workspace, &["verify"]);
+    assert_eq!(code, 9, "stdout: {stdout}");
+    assert!(
+        stdout.contains("1 ok, 1 changed, 1 missing"),
+        "stdout: {stdout}"
+    );
+
+    let (code, stdout, _) = run_cli(workspace, &["verify", "--json"]);
+    assert_eq!(code, 9);
+    let value: serde_json::Value = serde_json::from_str(&stdout).expect("valid JSON output");
+    assert_eq!(value["ok"], 1);
+    assert!(
+        value["changed"][0]
+            .as_str()
+            .is_some_and(|path| path.ends_with("a.py")),
+        "stdout: {stdout}"
+    );
+    assert!(
+        value["missing"][0]
+            .as_str()
+            .is_some_and(|path| path.ends_with("b.py")),
+        "stdout: {stdout}"
+    );
+}
@@ -2,3 +2,30 @@
 
 #[path = "cli/test_plugin_commands.rs"]
 mod test_plugin_commands;
+
+#[path = "cli/test_color_flag.rs"]
+mod test_color_flag;
+
+#[path = "cli/test_quiet_flag.rs"]
+mod test_quiet_flag;
+
+#[path = "cli/test_log_format.rs"]
+mod test_log_format;
+
+#[path = "cli/test_mcp_list_tools.rs"]
+mod test_mcp_list_tools;
+
+#[path = "cli/test_kind_filter.rs"]
+mod test_kind_filter;
+
+#[path = "cli/test_symbol_at.rs"]
+mod test_symbol_at;
+
+#[path = "cli/test_kotlin_symbols.rs"]
+mod test_kotlin_symbols;
+
+#[path = "cli/test_find_symbol_limit.rs"]
+mod test_find_symbol_limit;
+
+#[path = "cli/test_verify.rs"]
+mod test_verify;
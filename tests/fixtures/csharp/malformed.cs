@@ -0,0 +1,27 @@
+// Test fixture with syntax errors part way through the file.
+// Members before and after the broken code must still be extracted.
+
+namespace Fixtures
+{
+    public class Before
+    {
+        public int Value;
+
+        public int Compute(int x) { return x + 1; }
+    }
+
+    public class Broken
+    {
+        public int First() { return 1; }
+
+        public int Bad(int a) { return a + * ; }
+
+        public int Last() { return 2; }
+    }
+
+    public enum After
+    {
+        One,
+        Two,
+    }
+}
@@ -0,0 +1,31 @@
+// Test fixture with syntax errors part way through the file.
+// Declarations before and after the broken code must still be extracted.
+
+package fixtures
+
+type Before struct {
+	Value int
+}
+
+func before(x int) int {
+	return x + 1
+}
+
+func broken(a int) int {
+	y := a + *
+	return y +
+}
+
+type Shape interface {
+	Area() float64
+	Name() string
+}
+
+type After struct {
+	First  int
+	Second string
+}
+
+func after() After {
+	return After{}
+}
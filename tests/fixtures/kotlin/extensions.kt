@@ -0,0 +1,35 @@
+package com.example.shapes
+
+/** A point on the plane */
+data class Point(val x: Int, val y: Int)
+
+class Registry {
+    fun register(name: String) {
+        println(name)
+    }
+
+    companion object {
+        fun create(): Registry = Registry()
+    }
+}
+
+class Config {
+    companion object Defaults {
+        const val TIMEOUT = 30
+    }
+}
+
+object Logger {
+    fun log(message: String) {
+        println(message)
+    }
+}
+
+/** Shout a string */
+fun String.shout(): String = uppercase() + "!"
+
+fun Point.distanceTo(other: Point): Int = (other.x - x) + (other.y - y)
+
+fun <T> List<T>.second(): T = this[1]
+
+fun topLevel() {}
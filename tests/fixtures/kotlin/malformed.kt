@@ -0,0 +1,25 @@
+// Test fixture with syntax errors part way through the file.
+// Declarations before and after the broken code must still be extracted.
+
+package fixtures
+
+class Before(val value: Int) {
+    fun compute(x: Int): Int = x + 1
+}
+
+class Broken {
+    fun first(): Int = 1
+
+    fun bad(a: Int): Int {
+        return a + *
+    }
+
+    fun last(): Int = 2
+}
+
+enum class After {
+    ONE,
+    TWO
+}
+
+fun after(): After = After.ONE
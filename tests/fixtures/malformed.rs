@@ -0,0 +1,30 @@
+// Test fixture with syntax errors part way through the file.
+// Items before and after the broken code must still be extracted.
+
+pub struct Before {
+    pub value: i32,
+}
+
+pub fn before(x: i32) -> i32 {
+    x + 1
+}
+
+fn broken(a: i32) -> i32 {
+    let y = a + * ;
+    y +
+}
+
+pub trait Shape {
+    fn area(&self) -> f64;
+    fn perimeter(&self) -> ;
+    fn name(&self) -> String;
+}
+
+pub enum After {
+    First,
+    Second,
+}
+
+pub fn after() -> After {
+    After::First
+}
@@ -0,0 +1,95 @@
+// Test fixture for calls chained on each other's return values.
+// Every call in `configure` and `fetch` must resolve on the type its
+// receiver returns.
+
+pub struct Builder {
+    name: String,
+    size: usize,
+}
+
+pub struct Config {
+    name: String,
+    size: usize,
+}
+
+impl Builder {
+    pub fn new() -> Self {
+        Builder {
+            name: String::new(),
+            size: 0,
+        }
+    }
+
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    pub fn size(mut self, size: usize) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn build(self) -> Config {
+        Config {
+            name: self.name,
+            size: self.size,
+        }
+    }
+}
+
+impl Config {
+    /// Shares its name with `Builder::build`
+    pub fn build(&self) -> String {
+        format!("{}:{}", self.name, self.size)
+    }
+}
+
+pub fn configure() -> Config {
+    let builder = Builder::new();
+    builder.name("app").size(3).build()
+}
+
+pub struct Client;
+
+pub struct Request {
+    url: String,
+}
+
+pub struct Response {
+    body: String,
+}
+
+impl Client {
+    pub fn new() -> Self {
+        Client
+    }
+
+    pub fn get(&self, url: &str) -> Request {
+        Request {
+            url: url.to_string(),
+        }
+    }
+}
+
+impl Request {
+    pub fn send(self) -> Response {
+        Response { body: self.url }
+    }
+
+    /// Shares its name with `Response::text`
+    pub fn text(&self) -> &str {
+        &self.url
+    }
+}
+
+impl Response {
+    pub fn text(self) -> String {
+        self.body
+    }
+}
+
+pub fn fetch() -> String {
+    let client = Client::new();
+    client.get("/").send().text()
+}
@@ -0,0 +1,30 @@
+"""Small class hierarchy with multiple inheritance and super() calls."""
+
+
+class Shape:
+    def area(self):
+        return 0
+
+    def describe(self):
+        return "shape"
+
+
+class Named:
+    def describe(self):
+        return "named"
+
+
+class Square(Shape, Named):
+    def __init__(self, side):
+        self.side = side
+
+    def area(self):
+        return self.side * self.side
+
+    def describe(self):
+        return "square, " + super().describe()
+
+
+class Cube(Square):
+    def area(self):
+        return 6 * super().area()
@@ -0,0 +1,33 @@
+// Test fixture with syntax errors part way through the file.
+// Declarations before and after the broken code must still be extracted.
+
+export class Before {
+    value: number = 0;
+
+    compute(x: number): number {
+        return x + 1;
+    }
+}
+
+export class Broken {
+    first(): number {
+        return 1;
+    }
+
+    bad(a: number): number {
+        return a + * ;
+    }
+
+    last(): number {
+        return 2;
+    }
+}
+
+export enum After {
+    One,
+    Two,
+}
+
+export function after(): After {
+    return After.One;
+}
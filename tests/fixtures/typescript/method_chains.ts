@@ -0,0 +1,38 @@
+// Test fixture for calls chained on each other's return values.
+// Every call in `fetchText` must resolve on the type its receiver returns.
+
+export class Client {
+    get(url: string): Request {
+        return new Request(url);
+    }
+}
+
+export class Request {
+    constructor(private url: string) {}
+
+    header(name: string): this {
+        return this;
+    }
+
+    send(): Response {
+        return new Response(this.url);
+    }
+
+    /** Shares its name with `Response.text` */
+    text(): string {
+        return this.url;
+    }
+}
+
+export class Response {
+    constructor(private body: string) {}
+
+    text(): string {
+        return this.body;
+    }
+}
+
+export function fetchText(): string {
+    const client = new Client();
+    return client.get("/").header("accept").send().text();
+}
@@ -168,6 +168,7 @@ async fn test_kotlin_semantic_search_and_dependency_injection() {
             limit: 5,
             threshold: None,
             lang: Some("kotlin".to_string()),
+            explain: false,
         }))
         .await
         .expect("semantic_search_docs should succeed");
@@ -194,6 +195,7 @@ async fn test_kotlin_semantic_search_and_dependency_injection() {
             limit: 3,
             threshold: None,
             lang: Some("kotlin".to_string()),
+            explain: false,
         }))
         .await
         .expect("semantic_search_with_context should succeed");
@@ -218,6 +220,7 @@ async fn test_kotlin_semantic_search_and_dependency_injection() {
         .find_symbol(Parameters(FindSymbolRequest {
             name: "ReadWritePgClient".to_string(),
             lang: Some("kotlin".to_string()),
+            limit: 20,
         }))
         .await
         .expect("find_symbol should succeed");
@@ -333,6 +336,7 @@ class UserService(
             limit: 10,
             threshold: None,
             lang: Some("kotlin".to_string()),
+            explain: false,
         }))
         .await
         .expect("semantic_search_docs should succeed");
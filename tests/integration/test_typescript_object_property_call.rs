@@ -94,7 +94,7 @@ const actions = {
     // Note: The property method might be indexed as a separate symbol or as part of the actions object
     let callers = indexer.get_calling_functions_with_metadata(submit_form.id);
     println!("\nCallers of submitForm: {}", callers.len());
-    for (caller, metadata) in &callers {
+    for (caller, metadata, _) in &callers {
         println!(
             "  - {}: kind={:?}, line={}, metadata={:?}",
             caller.name, caller.kind, caller.range.start_line, metadata
@@ -166,7 +166,7 @@ function handleRequest(request: any) {
     // Check callers
     let callers = indexer.get_calling_functions_with_metadata(submit_form.id);
     println!("Callers of submitForm: {}", callers.len());
-    for (caller, metadata) in &callers {
+    for (caller, metadata, _) in &callers {
         println!(
             "  - {}: kind={:?}, metadata={:?}",
             caller.name, caller.kind, metadata
@@ -0,0 +1,21 @@
+use codanna::parsing::kotlin::parser::KotlinParser;
+use codanna::parsing::parser::LanguageParser;
+use codanna::types::{FileId, SymbolCounter};
+
+#[test]
+fn test_parse_recovers_after_syntax_errors() {
+    let code = include_str!("../../fixtures/kotlin/malformed.kt");
+    let mut parser = KotlinParser::new().expect("Failed to create parser");
+    let mut counter = SymbolCounter::new();
+    let symbols = parser.parse(code, FileId::new(1).unwrap(), &mut counter);
+    let names: Vec<&str> = symbols.iter().map(|s| s.name.as_ref()).collect();
+
+    // Before the broken method
+    for name in ["Before", "compute", "Broken", "first"] {
+        assert!(names.contains(&name), "missing {name} in {names:?}");
+    }
+    // After it
+    for name in ["last", "After", "after"] {
+        assert!(names.contains(&name), "missing {name} in {names:?}");
+    }
+}
@@ -0,0 +1,134 @@
+use codanna::Symbol;
+use codanna::parsing::kotlin::parser::KotlinParser;
+use codanna::parsing::parser::LanguageParser;
+use codanna::types::{FileId, SymbolCounter, SymbolKind};
+
+fn load_fixture() -> &'static str {
+    include_str!("../../fixtures/kotlin/extensions.kt")
+}
+
+fn parse_fixture() -> Vec<Symbol> {
+    let mut parser = KotlinParser::new().expect("Failed to create parser");
+    let mut counter = SymbolCounter::new();
+    parser.parse(load_fixture(), FileId::new(1).unwrap(), &mut counter)
+}
+
+fn find<'a>(symbols: &'a [Symbol], name: &str) -> &'a Symbol {
+    symbols
+        .iter()
+        .find(|s| s.name.as_ref() == name)
+        .unwrap_or_else(|| panic!("expected symbol {name}, got {symbols:?}"))
+}
+
+#[test]
+fn test_extension_functions_record_receiver() {
+    let symbols = parse_fixture();
+
+    let shout = find(&symbols, "shout");
+    assert_eq!(shout.kind, SymbolKind::Method);
+    let signature = shout.signature.as_deref().unwrap();
+    assert!(signature.contains("String.shout"), "signature: {signature}");
+    assert_eq!(shout.doc_comment.as_deref(), Some("Shout a string"));
+
+    let second = find(&symbols, "second");
+    assert_eq!(second.kind, SymbolKind::Method);
+    assert!(
+        second
+            .signature
+            .as_deref()
+            .unwrap()
+            .contains("List<T>.second")
+    );
+
+    // Plain top-level functions stay functions
+    assert_eq!(find(&symbols, "topLevel").kind, SymbolKind::Function);
+}
+
+#[test]
+fn test_extension_functions_defined_on_receiver() {
+    let mut parser = KotlinParser::new().expect("Failed to create parser");
+    let defines = parser.find_defines(load_fixture());
+    let pairs: Vec<(&str, &str)> = defines.iter().map(|(d, m, _)| (*d, *m)).collect();
+
+    assert!(pairs.contains(&("String", "shout")), "defines: {pairs:?}");
+    assert!(
+        pairs.contains(&("Point", "distanceTo")),
+        "defines: {pairs:?}"
+    );
+    assert!(pairs.contains(&("List", "second")), "defines: {pairs:?}");
+    assert!(
+        pairs.contains(&("Registry", "register")),
+        "defines: {pairs:?}"
+    );
+    assert!(
+        !pairs.iter().any(|(_, m)| *m == "topLevel"),
+        "defines: {pairs:?}"
+    );
+}
+
+#[test]
+fn test_companion_and_object_declarations() {
+    let symbols = parse_fixture();
+
+    let companion = find(&symbols, "Companion");
+    assert_eq!(companion.kind, SymbolKind::Class);
+    assert_eq!(companion.signature.as_deref(), Some("companion object"));
+
+    let defaults = find(&symbols, "Defaults");
+    assert_eq!(defaults.kind, SymbolKind::Class);
+    assert_eq!(
+        defaults.signature.as_deref(),
+        Some("companion object Defaults")
+    );
+
+    assert_eq!(find(&symbols, "create").kind, SymbolKind::Method);
+    assert_eq!(find(&symbols, "Logger").kind, SymbolKind::Class);
+    assert_eq!(find(&symbols, "log").kind, SymbolKind::Method);
+}
+
+#[test]
+fn test_data_class_generated_members() {
+    let symbols = parse_fixture();
+
+    let point = find(&symbols, "Point");
+    assert_eq!(point.kind, SymbolKind::Class);
+
+    // Constructor properties become fields
+    for name in ["x", "y"] {
+        let field = symbols
+            .iter()
+            .find(|s| s.name.as_ref() == name && s.kind == SymbolKind::Field)
+            .unwrap_or_else(|| panic!("expected field {name}"));
+        assert_eq!(field.range.start_line, point.range.start_line);
+    }
+
+    for name in [
+        "component1",
+        "component2",
+        "copy",
+        "equals",
+        "hashCode",
+        "toString",
+    ] {
+        let member = find(&symbols, name);
+        assert_eq!(member.kind, SymbolKind::Method, "{name}");
+        assert_eq!(member.range.start_line, point.range.start_line, "{name}");
+    }
+    assert_eq!(
+        find(&symbols, "component2").signature.as_deref(),
+        Some("operator fun component2(): Int")
+    );
+    assert_eq!(
+        find(&symbols, "copy").signature.as_deref(),
+        Some("fun copy(x: Int = this.x, y: Int = this.y): Point")
+    );
+
+    // Regular classes get no generated members
+    assert_eq!(
+        symbols
+            .iter()
+            .filter(|s| s.name.as_ref() == "toString")
+            .count(),
+        1
+    );
+}
@@ -60,3 +60,9 @@ mod test_kotlin_interfaces_and_enums;
 
 #[path = "parsers/kotlin/test_nested_scopes.rs"]
 mod test_kotlin_nested_scopes;
+
+#[path = "parsers/kotlin/test_extensions_and_objects.rs"]
+mod test_kotlin_extensions_and_objects;
+
+#[path = "parsers/kotlin/test_error_recovery.rs"]
+mod test_kotlin_error_recovery;
@@ -270,6 +270,72 @@ fn verify_plugin_detects_tampering() {
     });
 }
 
+#[test]
+fn repair_plugin_restores_locked_files() {
+    with_temp_workspace(|workspace| {
+        let settings = load_workspace_settings(workspace);
+        let repo_url = create_marketplace_repo(
+            workspace,
+            "repair_repo",
+            "repair-plugin",
+            r#"{
+    "name": "repair-plugin",
+    "version": "0.1.0",
+    "description": "Plugin used to test repair",
+    "author": { "name": "Test" },
+    "mcpServers": "./.mcp.json"
+}"#,
+            &[
+                ("commands/repair.md", "# Repair Command\n"),
+                (
+                    ".mcp.json",
+                    r#"{
+    "mcpServers": {
+        "repair-server": {
+            "command": "echo",
+            "args": ["repair"]
+        }
+    }
+}"#,
+                ),
+            ],
+        );
+
+        plugins::add_plugin(&settings, &repo_url, "repair-plugin", None, false, false)
+            .expect("install succeeds");
+
+        let command = workspace.join(".claude/commands/repair-plugin/repair.md");
+        fs::write(&command, "tampered content").expect("tamper file");
+        let user_file = workspace.join(".claude/commands/user.md");
+        fs::write(&user_file, "user content").expect("write user file");
+        fs::write(workspace.join(".mcp.json"), r#"{"mcpServers": {}}"#).expect("drop server");
+
+        let err = plugins::verify_plugin(&settings, "repair-plugin", false)
+            .expect_err("verification should fail after tampering");
+        assert!(matches!(err, PluginError::IntegrityCheckFailed { .. }));
+
+        plugins::repair_plugin(&settings, "repair-plugin", true).expect("repair succeeds");
+
+        assert_eq!(
+            fs::read_to_string(&command).expect("read restored command"),
+            "# Repair Command\n"
+        );
+        assert_eq!(
+            fs::read_to_string(&user_file).expect("read user file"),
+            "user content",
+            "repair must not touch files outside the lockfile entry"
+        );
+        let mcp = read_json(workspace, ".mcp.json");
+        assert!(
+            mcp["mcpServers"]["repair-server"].is_object(),
+            "repair should re-merge drifted MCP servers"
+        );
+
+        plugins::verify_plugin(&settings, "repair-plugin", false)
+            .expect("verification succeeds after repair");
+    });
+}
+
 #[test]
 fn list_plugins_reports_state() {
     with_temp_workspace(|workspace| {
@@ -396,6 +462,88 @@ fn install_fails_on_invalid_manifest() {
     });
 }
 
+#[test]
+fn install_resolves_glob_path_specs() {
+    with_temp_workspace(|workspace| {
+        let settings = load_workspace_settings(workspace);
+        let repo_url = create_marketplace_repo(
+            workspace,
+            "glob_repo",
+            "glob-plugin",
+            r#"{
+    "name": "glob-plugin",
+    "version": "0.1.0",
+    "description": "Plugin with glob path specs",
+    "author": { "name": "Test" },
+    "commands": ["./extra/*.md", "./nested/**/*.md", "./single/exact.md"]
+}"#,
+            &[
+                ("extra/top.md", "# Top\n"),
+                ("extra/skip.txt", "not markdown"),
+                ("nested/a/b/deep.md", "# Deep\n"),
+                ("single/exact.md", "# Exact\n"),
+            ],
+        );
+
+        plugins::add_plugin(&settings, &repo_url, "glob-plugin", None, false, false)
+            .expect("install succeeds");
+
+        let lockfile = read_json(workspace, ".codanna/plugins/lockfile.json");
+        let files: Vec<&str> = lockfile["plugins"]["glob-plugin"]["files"]
+            .as_array()
+            .expect("files should be array")
+            .iter()
+            .filter_map(|f| f.as_str())
+            .collect();
+        for expected in [
+            ".claude/plugins/glob-plugin/extra/top.md",
+            ".claude/plugins/glob-plugin/nested/a/b/deep.md",
+            ".claude/plugins/glob-plugin/single/exact.md",
+        ] {
+            assert!(
+                files.contains(&expected),
+                "lockfile should track {expected}, got {files:?}"
+            );
+        }
+    });
+}
+
+#[test]
+fn install_fails_on_unmatched_glob() {
+    with_temp_workspace(|workspace| {
+        let settings = load_workspace_settings(workspace);
+        let repo_url = create_marketplace_repo(
+            workspace,
+            "empty_glob_repo",
+            "empty-glob-plugin",
+            r#"{
+    "name": "empty-glob-plugin",
+    "version": "0.1.0",
+    "description": "Plugin whose glob matches nothing",
+    "author": { "name": "Test" },
+    "commands": "./missing/**/*.md"
+}"#,
+            &[("extra/top.md", "# Top\n")],
+        );
+
+        let err = plugins::add_plugin(
+            &settings,
+            &repo_url,
+            "empty-glob-plugin",
+            None,
+            false,
+            false,
+        )
+        .expect_err("unmatched glob should fail");
+        match err {
+            PluginError::InvalidPluginManifest { reason } => {
+                assert!(reason.contains("did not match any files"), "{reason}");
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+    });
+}
+
 #[test]
 fn install_fails_on_mcp_conflict() {
     with_temp_workspace(|workspace| {
@@ -462,3 +610,94 @@ fn install_fails_on_mcp_conflict() {
         );
     });
 }
+
+#[test]
+fn install_from_local_directory_without_git() {
+    with_temp_workspace(|workspace| {
+        let settings = load_workspace_settings(workspace);
+        let repo_url = create_marketplace_repo(
+            workspace,
+            "local_repo",
+            "local-plugin",
+            r#"{
+    "name": "local-plugin",
+    "version": "0.1.0",
+    "description": "Plugin installed from disk",
+    "author": { "name": "Test" }
+}"#,
+            &[("commands/local.md", "# Local Command\n")],
+        );
+        fs::remove_dir_all(Path::new(&repo_url).join(".git")).expect("remove git metadata");
+
+        plugins::add_plugin(
+            &settings,
+            &format!("file://{repo_url}"),
+            "local-plugin",
+            None,
+            false,
+            false,
+        )
+        .expect("local marketplace install succeeds");
+
+        let command = workspace.join(".claude/commands/local-plugin/local.md");
+        assert_file_exists(workspace, ".claude/commands/local-plugin/local.md");
+        let lockfile = read_json(workspace, ".codanna/plugins/lockfile.json");
+        let commit = lockfile["plugins"]["local-plugin"]["commit"]
+            .as_str()
+            .expect("commit recorded");
+        assert!(commit.starts_with("local:"), "unexpected commit {commit}");
+
+        fs::write(&command, "tampered content").expect("tamper file");
+        plugins::repair_plugin(&settings, "local-plugin", false).expect("repair from disk");
+        assert_eq!(
+            fs::read_to_string(&command).expect("read restored command"),
+            "# Local Command\n"
+        );
+
+        plugins::update_plugin(&settings, "local-plugin", None, false, false)
+            .expect("update of unchanged local plugin succeeds");
+
+        let err = plugins::add_plugin(
+            &settings,
+            &repo_url,
+            "local-plugin",
+            Some("v1.0"),
+            true,
+            false,
+        )
+        .expect_err("--ref is rejected for local sources");
+        assert!(matches!(err, PluginError::InvalidReference { .. }));
+    });
+}
+
+#[test]
+fn install_standalone_plugin_directory_from_file() {
+    with_temp_workspace(|workspace| {
+        let settings = load_workspace_settings(workspace);
+        let repo_url = create_marketplace_repo(
+            workspace,
+            "standalone_repo",
+            "standalone-plugin",
+            r#"{
+    "name": "standalone-plugin",
+    "version": "0.2.0",
+    "description": "Plugin directory without a marketplace",
+    "author": { "name": "Test" }
+}"#,
+            &[("agents/helper.md", "# Helper Agent\n")],
+        );
+        let plugin_dir = Path::new(&repo_url).join("plugin");
+
+        let err =
+            plugins::add_plugin_from_dir(&settings, &plugin_dir, "other-plugin", false, false)
+                .expect_err("name must match the plugin manifest");
+        assert!(matches!(err, PluginError::PluginNotFound { .. }));
+
+        plugins::add_plugin_from_dir(&settings, &plugin_dir, "standalone-plugin", false, false)
+            .expect("standalone plugin install succeeds");
+
+        assert_file_exists(workspace, ".claude/agents/standalone-plugin/helper.md");
+        plugins::verify_plugin(&settings, "standalone-plugin", false)
+            .expect("verification succeeds");
+    });
+}
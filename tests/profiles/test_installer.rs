@@ -221,6 +221,8 @@ fn test_sidecar_for_different_profile() {
         commit: None,
         provider_id: None,
         source: None,
+        file_hashes: Default::default(),
+        variables: Default::default(),
     });
 
     let installer = ProfileInstaller::new();
@@ -271,6 +273,8 @@ fn test_same_profile_updates_without_sidecar() {
         commit: None,
         provider_id: None,
         source: None,
+        file_hashes: Default::default(),
+        variables: Default::default(),
     });
 
     let installer = ProfileInstaller::new();
@@ -21,6 +21,8 @@ fn test_add_profile() {
         commit: None,
         provider_id: None,
         source: None,
+        file_hashes: Default::default(),
+        variables: Default::default(),
     };
 
     lockfile.add_profile(entry);
@@ -41,6 +43,8 @@ fn test_find_file_owner() {
         commit: None,
         provider_id: None,
         source: None,
+        file_hashes: Default::default(),
+        variables: Default::default(),
     };
 
     lockfile.add_profile(entry);
@@ -65,6 +69,8 @@ fn test_serialize_with_integrity() {
         commit: None,
         provider_id: None,
         source: None,
+        file_hashes: Default::default(),
+        variables: Default::default(),
     };
 
     lockfile.add_profile(entry);
@@ -2,7 +2,8 @@
 
 use codanna::profiles::error::ProfileError;
 use codanna::profiles::lockfile::ProfileLockfile;
-use codanna::profiles::orchestrator::install_profile;
+use codanna::profiles::orchestrator::{install_profile, install_profile_with_variables};
+use std::collections::HashMap;
 use std::fs;
 use tempfile::tempdir;
 
@@ -263,3 +264,127 @@ fn test_install_profile_conflict_creates_sidecar() {
     let content_b = fs::read_to_string(&sidecar_path).unwrap();
     assert_eq!(content_b, "# Profile B");
 }
+
+#[test]
+fn test_install_profile_renders_template_variables() {
+    let temp = tempdir().unwrap();
+    let profiles_dir = temp.path().join("profiles");
+    let claude_dir = profiles_dir.join("claude");
+    fs::create_dir_all(&claude_dir).unwrap();
+
+    let manifest_json = r#"{
+        "name": "claude",
+        "version": "1.0.0",
+        "files": ["CLAUDE.md"],
+        "variables": {
+            "project_name": { "default": "my-project" },
+            "author": { "default": "Anonymous" }
+        }
+    }"#;
+    fs::write(claude_dir.join("profile.json"), manifest_json).unwrap();
+    fs::write(
+        claude_dir.join("CLAUDE.md"),
+        "# {{project_name}} by {{author}}",
+    )
+    .unwrap();
+
+    let workspace = temp.path().join("workspace");
+    fs::create_dir_all(&workspace).unwrap();
+
+    let mut overrides = HashMap::new();
+    overrides.insert("project_name".to_string(), "codanna".to_string());
+    install_profile_with_variables(
+        "claude",
+        &profiles_dir,
+        &workspace,
+        false,
+        None,
+        None,
+        None,
+        &overrides,
+    )
+    .unwrap();
+
+    assert_eq!(
+        fs::read_to_string(workspace.join("CLAUDE.md")).unwrap(),
+        "# codanna by Anonymous"
+    );
+
+    let lockfile = ProfileLockfile::load(&workspace.join(".codanna/profiles.lock.json")).unwrap();
+    let entry = lockfile.get_profile("claude").unwrap();
+    assert_eq!(
+        entry.variables.get("project_name"),
+        Some(&"codanna".to_string())
+    );
+
+    // Reinstalling without overrides keeps the recorded ones
+    install_profile("claude", &profiles_dir, &workspace, true, None, None, None).unwrap();
+    assert_eq!(
+        fs::read_to_string(workspace.join("CLAUDE.md")).unwrap(),
+        "# codanna by Anonymous"
+    );
+}
+
+#[test]
+fn test_install_profile_rejects_undeclared_variable() {
+    let temp = tempdir().unwrap();
+    let profiles_dir = temp.path().join("profiles");
+    let claude_dir = profiles_dir.join("claude");
+    fs::create_dir_all(&claude_dir).unwrap();
+    fs::write(
+        claude_dir.join("profile.json"),
+        r#"{"name": "claude", "version": "1.0.0", "files": ["CLAUDE.md"]}"#,
+    )
+    .unwrap();
+    fs::write(claude_dir.join("CLAUDE.md"), "# Claude").unwrap();
+
+    let workspace = temp.path().join("workspace");
+    fs::create_dir_all(&workspace).unwrap();
+
+    let mut overrides = HashMap::new();
+    overrides.insert("project_name".to_string(), "codanna".to_string());
+    let result = install_profile_with_variables(
+        "claude",
+        &profiles_dir,
+        &workspace,
+        false,
+        None,
+        None,
+        None,
+        &overrides,
+    );
+
+    assert!(matches!(result, Err(ProfileError::InvalidManifest { .. })));
+    assert!(!workspace.join("CLAUDE.md").exists());
+}
+
+#[test]
+fn test_install_profile_render_failure_leaves_workspace_untouched() {
+    let temp = tempdir().unwrap();
+    let profiles_dir = temp.path().join("profiles");
+    let claude_dir = profiles_dir.join("claude");
+    fs::create_dir_all(&claude_dir).unwrap();
+    fs::write(
+        claude_dir.join("profile.json"),
+        r#"{
+        "name": "claude",
+        "version": "1.0.0",
+        "files": ["CLAUDE.md", "docs/GUIDE.md"],
+        "variables": { "project_name": { "default": "my-project" } }
+    }"#,
+    )
+    .unwrap();
+    fs::write(claude_dir.join("CLAUDE.md"), "# {{project_name}}").unwrap();
+    fs::create_dir_all(claude_dir.join("docs")).unwrap();
+    // References a variable the manifest does not declare
+    fs::write(claude_dir.join("docs/GUIDE.md"), "By {{author}}").unwrap();
+
+    let workspace = temp.path().join("workspace");
+    fs::create_dir_all(&workspace).unwrap();
+
+    let result = install_profile("claude", &profiles_dir, &workspace, false, None, None, None);
+
+    assert!(matches!(result, Err(ProfileError::InvalidManifest { .. })));
+    assert!(!workspace.join("CLAUDE.md").exists());
+    assert!(!workspace.join("docs").exists());
+}
@@ -1,6 +1,8 @@
 //! Tests for variable merging
 
-use codanna::profiles::variables::Variables;
+use codanna::profiles::manifest::ProfileManifest;
+use codanna::profiles::variables::{Variables, parse_assignments, profile_variables};
+use std::collections::HashMap;
 
 #[test]
 fn test_merge_empty() {
@@ -79,3 +81,65 @@ fn test_merge_respects_priority() {
     assert_eq!(merged.get("d"), Some(&"cli-d".to_string()));
     assert_eq!(merged.get("e"), Some(&"cli-e".to_string()));
 }
+
+#[test]
+fn test_parse_assignments() {
+    let parsed = parse_assignments(&[
+        "project_name=my-app".to_string(),
+        "greeting=a=b".to_string(),
+    ])
+    .unwrap();
+    assert_eq!(parsed.get("project_name"), Some(&"my-app".to_string()));
+    assert_eq!(parsed.get("greeting"), Some(&"a=b".to_string()));
+
+    assert!(parse_assignments(&["missing-equals".to_string()]).is_err());
+    assert!(parse_assignments(&["=value".to_string()]).is_err());
+}
+
+#[test]
+fn test_profile_variables_applies_defaults_and_overrides() {
+    let manifest = ProfileManifest::from_json(
+        r#"{
+        "name": "claude",
+        "version": "1.0.0",
+        "variables": {
+            "project_name": { "default": "my-project", "description": "Project name" },
+            "author": { "default": "Anonymous" }
+        }
+    }"#,
+    )
+    .unwrap();
+
+    let mut overrides = HashMap::new();
+    overrides.insert("author".to_string(), "Ada".to_string());
+
+    let merged = profile_variables(&manifest, &overrides).unwrap().merge();
+    assert_eq!(merged.get("project_name"), Some(&"my-project".to_string()));
+    assert_eq!(merged.get("author"), Some(&"Ada".to_string()));
+
+    overrides.insert("unknown".to_string(), "x".to_string());
+    assert!(profile_variables(&manifest, &overrides).is_err());
+}
+
+#[test]
+fn test_profile_variables_requires_values_without_default() {
+    let manifest = ProfileManifest::from_json(
+        r#"{
+        "name": "claude",
+        "version": "1.0.0",
+        "variables": {
+            "project_name": { "description": "Project name" },
+            "author": { "default": "Anonymous" }
+        }
+    }"#,
+    )
+    .unwrap();
+
+    let err = profile_variables(&manifest, &HashMap::new()).unwrap_err();
+    assert!(err.to_string().contains("project_name"), "{err}");
+
+    let mut overrides = HashMap::new();
+    overrides.insert("project_name".to_string(), "codanna".to_string());
+    let merged = profile_variables(&manifest, &overrides).unwrap().merge();
+    assert_eq!(merged.get("project_name"), Some(&"codanna".to_string()));
+}
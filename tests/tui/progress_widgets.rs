@@ -54,3 +54,56 @@ fn spinner_succeeds_and_hides_extra_fields_when_zero() {
     assert!(rendered.starts_with("✓ Indexing complete"));
     assert!(!rendered.contains("retry batches"));
 }
+
+#[test]
+fn progress_bar_shows_units_rate_and_current_item() {
+    let options = ProgressBarOptions::default()
+        .with_width(4)
+        .show_elapsed(false);
+    let bar = ProgressBar::with_options(2, "files", "indexed", "", options).with_units("symbols");
+
+    bar.inc();
+    bar.add_extra1(1);
+    bar.add_units(40);
+    bar.set_message("src/very/long/path/".repeat(10) + "lib.rs");
+    std::thread::sleep(Duration::from_millis(5));
+
+    let rendered = format!("{bar}");
+    let lines: Vec<&str> = rendered.lines().collect();
+    assert_eq!(lines.len(), 3, "unexpected rendering: {rendered}");
+    assert!(lines[1].starts_with("1/2 files | 1 indexed"));
+    assert!(
+        lines[1].contains(" symbols/s"),
+        "unexpected rendering: {rendered}"
+    );
+    assert!(lines[2].starts_with('…') && lines[2].ends_with("lib.rs"));
+    assert!(lines[2].chars().count() <= 72);
+
+    bar.set_message("");
+    assert_eq!(format!("{bar}").lines().count(), 2);
+}
+
+#[test]
+fn progress_bar_shows_eta_after_first_sample() {
+    let options = ProgressBarOptions::default()
+        .with_width(4)
+        .show_elapsed(false)
+        .show_eta(true);
+    let bar = ProgressBar::with_options(4, "files", "", "", options).with_units("symbols");
+
+    bar.inc();
+    bar.add_units(100);
+    assert!(!format!("{bar}").contains("ETA"));
+
+    std::thread::sleep(Duration::from_millis(550));
+    let rendered = format!("{bar}");
+    assert!(
+        rendered.contains(" | ETA "),
+        "unexpected rendering: {rendered}"
+    );
+
+    for _ in 0..3 {
+        bar.inc();
+    }
+    assert!(!format!("{bar}").contains("ETA"));
+}